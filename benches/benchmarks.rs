@@ -1,9 +1,20 @@
-use std::{rc::Rc, time::Duration};
+use std::{
+	rc::Rc,
+	time::{Duration, Instant}
+};
 
 use const_format::concatcp;
-use criterion::{measurement::Measurement, BenchmarkGroup, Criterion};
-use fixedstr::str8;
-use quartiles_solver::{dictionary::Dictionary, solver::Solver};
+use criterion::{
+	measurement::{Measurement, WallTime},
+	BenchmarkGroup,
+	Criterion
+};
+#[cfg(feature = "compress")]
+use quartiles_solver::dictionary::Compression;
+use quartiles_solver::{
+	dictionary::Dictionary,
+	solver::{Fragment, Solver}
+};
 
 /// The path of the directory containing the dictionaries.
 #[inline]
@@ -61,42 +72,131 @@ fn bench_deserialize_from_file<M: Measurement>(g: &mut BenchmarkGroup<M>)
 	});
 }
 
-/// Benchmark solving a puzzle.
+/// Benchmark deserializing a compressed dictionary from a file, for each
+/// supported compression.
+///
+/// # Arguments
+///
+/// * `g` - The benchmark group.
+#[cfg(feature = "compress")]
+fn bench_deserialize_compressed<M: Measurement>(g: &mut BenchmarkGroup<M>)
+{
+	let dictionary = Dictionary::deserialize_from_file(path_dict()).unwrap();
+	let dir = tempfile::TempDir::new().unwrap();
+	for (name, compression) in
+		[("gzip", Compression::Gzip), ("zstd", Compression::Zstd)]
+	{
+		let path = dir.path().join(format!("{}.dict", name));
+		dictionary.serialize_to_file_with(&path, compression).unwrap();
+		g.bench_function(format!("deserialize_from_file_{}", name), |b| {
+			b.iter(|| Dictionary::deserialize_from_file(&path).unwrap());
+		});
+	}
+}
+
+/// The time quantum of the text user interface.
+const QUANTUM: Duration = Duration::from_millis(5);
+
+/// The fragments of the puzzle to solve.
+///
+/// # Returns
+///
+/// The fragments.
+#[must_use]
+fn fragments() -> [Fragment; 20]
+{
+	[
+		Fragment::from("azz"),
+		Fragment::from("th"),
+		Fragment::from("ss"),
+		Fragment::from("tru"),
+		Fragment::from("ref"),
+		Fragment::from("fu"),
+		Fragment::from("ra"),
+		Fragment::from("nih"),
+		Fragment::from("cro"),
+		Fragment::from("mat"),
+		Fragment::from("wo"),
+		Fragment::from("sh"),
+		Fragment::from("re"),
+		Fragment::from("rds"),
+		Fragment::from("tic"),
+		Fragment::from("il"),
+		Fragment::from("lly"),
+		Fragment::from("zz"),
+		Fragment::from("is"),
+		Fragment::from("ment")
+	]
+}
+
+/// Benchmark solving a puzzle, with and without suffix pruning and the letter
+/// precheck. Only the solver is measured, not the loading of the dictionary,
+/// nor the building of its reversed index or its letter filter.
 ///
 /// # Arguments
 ///
 /// * `g` - The benchmark group.
 fn bench_solver<M: Measurement>(g: &mut BenchmarkGroup<M>)
 {
-	g.bench_function("solve", |b| {
-		b.iter(|| {
-			let dictionary = Rc::new(Dictionary::open(dir(), name()).unwrap());
-			let fragments = [
-				str8::from("azz"),
-				str8::from("th"),
-				str8::from("ss"),
-				str8::from("tru"),
-				str8::from("ref"),
-				str8::from("fu"),
-				str8::from("ra"),
-				str8::from("nih"),
-				str8::from("cro"),
-				str8::from("mat"),
-				str8::from("wo"),
-				str8::from("sh"),
-				str8::from("re"),
-				str8::from("rds"),
-				str8::from("tic"),
-				str8::from("il"),
-				str8::from("lly"),
-				str8::from("zz"),
-				str8::from("is"),
-				str8::from("ment")
-			];
-			let solver = Solver::new(dictionary, fragments);
-			// 10s should be vastly more than enough time to solve the puzzle.
-			let solver = solver.solve_fully();
-			assert!(solver.is_solved());
+	let dictionary = Rc::new(Dictionary::open(dir(), name()).unwrap());
+	let _ = dictionary.contains_suffix("");
+	let _ = dictionary.letter_filter();
+	let cases = [
+		("solve", true, false),
+		("solve_unpruned", false, false),
+		("solve_prechecked", true, true)
+	];
+	for (id, prune_suffixes, letter_precheck) in cases
+	{
+		g.bench_function(id, |b| {
+			b.iter(|| {
+				let solver = Solver::builder()
+					.dictionary(Rc::clone(&dictionary))
+					.fragments(fragments())
+					.prune_suffixes(prune_suffixes)
+					.letter_precheck(letter_precheck)
+					.build()
+					.unwrap();
+				// 10s should be vastly more than enough time to solve the
+				// puzzle.
+				let solver = solver.solve_fully();
+				assert!(solver.is_solved());
+			});
+		});
+	}
+}
+
+/// Benchmark the responsiveness of the solver, i.e., the worst time that a
+/// single call to [`solve`](Solver::solve) takes with the time quantum of the
+/// text user interface. Each iteration solves the puzzle fully, one quantum at
+/// a time, and reports its slowest call rather than the total, so a long
+/// dictionary probe that overruns the quantum shows up as a regression.
+///
+/// # Arguments
+///
+/// * `g` - The benchmark group.
+fn bench_solver_quantum(g: &mut BenchmarkGroup<WallTime>)
+{
+	let dictionary = Rc::new(Dictionary::open(dir(), name()).unwrap());
+	g.bench_function("solve_quantum_worst", |b| {
+		b.iter_custom(|iters| {
+			(0..iters)
+				.map(|_| {
+					let mut solver =
+						Solver::new(Rc::clone(&dictionary), fragments());
+					let mut worst = Duration::ZERO;
+					while !solver.is_finished()
+					{
+						let start_time = Instant::now();
+						solver = solver.solve(QUANTUM).0;
+						worst = worst.max(start_time.elapsed());
+					}
+					// The solver itself must agree that no call overran the
+					// quantum by more than the slowest call took.
+					assert!(solver.max_overrun() <= worst);
+					worst
+				})
+				.sum()
 		});
 	});
 }
@@ -117,7 +217,10 @@ fn main()
 	group.measurement_time(Duration::from_secs(30));
 	bench_read_from_file(&mut group);
 	bench_deserialize_from_file(&mut group);
+	#[cfg(feature = "compress")]
+	bench_deserialize_compressed(&mut group);
 	bench_solver(&mut group);
+	bench_solver_quantum(&mut group);
 	group.finish();
 
 	// Generate the final summary.
@@ -0,0 +1,12 @@
+//! Feed arbitrary bytes to the decoder of binary dictionaries, which must
+//! refuse corrupt input with an error rather than panic or allocate without
+//! bound.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use quartiles_solver::dictionary::Dictionary;
+
+fuzz_target!(|bytes: &[u8]| {
+	let _ = Dictionary::deserialize_from_bytes(bytes);
+});
@@ -0,0 +1,271 @@
+//! # Fragment advisor
+//!
+//! Herein is support for checking the fragments of the board against the
+//! dictionary while the user types them, so that typos are caught before
+//! solving, and for suggesting completions of the fragment being typed.
+
+use std::{
+	rc::Rc,
+	time::{Duration, Instant}
+};
+
+use quartiles_solver::{dictionary::Dictionary, solver::Fragment};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Advisors.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The maximum number of suggestions to offer.
+pub const MAX_SUGGESTIONS: usize = 8;
+
+/// The maximum length of a suggestion, in characters. Fragments in puzzles
+/// rarely exceed this.
+const MAX_SUGGESTION_LEN: usize = 4;
+
+/// How long the board must stay unchanged before the dictionary is scanned.
+/// Every keystroke changes the board, and each scan visits every word, so
+/// scanning only once the user pauses keeps typing responsive.
+pub const ADVISOR_DELAY: Duration = Duration::from_millis(150);
+
+/// Checks the fragments of the board against a dictionary, and suggests
+/// completions of the current fragment. Scanning the dictionary is too slow
+/// to repeat for every frame, or even for every keystroke, so the scans wait
+/// until the board has [settled](ADVISOR_DELAY), and the verdicts and
+/// suggestions are cached until the board or the dictionary changes again.
+#[derive(Debug, Default)]
+#[must_use]
+pub struct Advisor
+{
+	/// The dictionary against which the verdicts were reached, if any.
+	dictionary: Option<Rc<Dictionary>>,
+
+	/// The cells of the board, as of the last update.
+	cells: [Fragment; 20],
+
+	/// The index of the cell being edited, as of the last update.
+	current: usize,
+
+	/// When the board or the dictionary last changed, if the verdicts or
+	/// suggestions are out of date.
+	changed_at: Option<Instant>,
+
+	/// The fragment last checked for each cell, and whether some word of the
+	/// dictionary contains it.
+	verdicts: [Option<(Fragment, bool)>; 20],
+
+	/// The fragment for which the [suggestions](Self::suggestions) were
+	/// computed.
+	prefix: Fragment,
+
+	/// The suggested completions of the [prefix](Self::prefix).
+	suggestions: Vec<String>
+}
+
+impl Advisor
+{
+	/// Bring the verdicts and suggestions up to date with the board, once it
+	/// has [settled](ADVISOR_DELAY). Only the cells whose fragments have
+	/// changed since the last scan are checked again, unless the dictionary
+	/// has changed, in which case every cell is.
+	///
+	/// # Arguments
+	///
+	/// * `dictionary` - The dictionary.
+	/// * `cells` - The cells of the board.
+	/// * `current` - The index of the cell being edited.
+	/// * `now` - The current time.
+	pub fn update(
+		&mut self,
+		dictionary: &Rc<Dictionary>,
+		cells: &[Fragment; 20],
+		current: usize,
+		now: Instant
+	)
+	{
+		if !self
+			.dictionary
+			.as_ref()
+			.is_some_and(|d| Rc::ptr_eq(d, dictionary))
+		{
+			*self = Self {
+				dictionary: Some(Rc::clone(dictionary)),
+				changed_at: Some(now),
+				..Default::default()
+			};
+		}
+		if self.cells != *cells || self.current != current
+		{
+			self.cells = *cells;
+			self.current = current;
+			self.changed_at = Some(now);
+		}
+		match self.changed_at
+		{
+			Some(changed_at)
+				if now.saturating_duration_since(changed_at) >= ADVISOR_DELAY =>
+			{
+				self.scan(dictionary);
+				self.changed_at = None;
+			},
+			_ =>
+			{}
+		}
+	}
+
+	/// Check whether the board has changed since the last scan, so that a
+	/// scan is still owed once the board settles.
+	///
+	/// # Returns
+	///
+	/// `true` if a scan is pending, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_pending(&self) -> bool
+	{
+		self.changed_at.is_some()
+	}
+
+	/// Check the changed fragments against the dictionary, and suggest
+	/// completions of the current fragment if it has changed.
+	///
+	/// # Arguments
+	///
+	/// * `dictionary` - The dictionary.
+	fn scan(&mut self, dictionary: &Dictionary)
+	{
+		let cells = &self.cells;
+		for (verdict, cell) in self.verdicts.iter_mut().zip(cells)
+		{
+			if verdict.is_none_or(|(fragment, _)| fragment != *cell)
+			{
+				*verdict = Some((*cell, dictionary.contains_fragment(cell)));
+			}
+		}
+		let prefix = cells[self.current];
+		if prefix != self.prefix
+		{
+			self.suggestions = match prefix.is_empty()
+			{
+				true => Vec::new(),
+				false => dictionary.suggest_fragments(
+					&prefix,
+					MAX_SUGGESTION_LEN,
+					MAX_SUGGESTIONS
+				)
+			};
+			self.prefix = prefix;
+		}
+	}
+
+	/// Check whether the fragment in the specified cell is impossible, i.e.,
+	/// whether no word of the dictionary contains it. A cell that has not
+	/// been checked since it last changed is not impossible.
+	///
+	/// # Arguments
+	///
+	/// * `index` - The index of the cell.
+	///
+	/// # Returns
+	///
+	/// `true` if the fragment is impossible, `false` otherwise.
+	#[must_use]
+	pub fn is_impossible(&self, index: usize) -> bool
+	{
+		matches!(
+			self.verdicts[index],
+			Some((fragment, false)) if fragment == self.cells[index]
+		)
+	}
+
+	/// Answer the suggested completions of the current fragment, most common
+	/// first. There are none until the current fragment has been scanned.
+	///
+	/// # Returns
+	///
+	/// The suggestions.
+	#[must_use]
+	pub fn suggestions(&self) -> &[String]
+	{
+		match self.prefix == self.cells[self.current]
+		{
+			true => &self.suggestions,
+			false => &[]
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::{rc::Rc, time::Instant};
+
+	use quartiles_solver::{dictionary::Dictionary, solver::Fragment};
+
+	use crate::advisor::{Advisor, ADVISOR_DELAY};
+
+	/// Ensure that impossible fragments are flagged, that suggestions follow
+	/// the current cell, that changing the dictionary rechecks the board, and
+	/// that nothing is scanned until the board settles.
+	#[test]
+	fn test_update()
+	{
+		let dictionary = Rc::new(
+			["truss", "trust", "tryst"].into_iter().collect::<Dictionary>()
+		);
+		let mut cells = [Fragment::new(); 20];
+		cells[0] = Fragment::from("tr");
+		cells[1] = Fragment::from("zz");
+		cells[2] = Fragment::from("yst");
+		let mut advisor = Advisor::default();
+		let mut now = Instant::now();
+		advisor.update(&dictionary, &cells, 0, now);
+		assert!(advisor.is_pending());
+		assert!(!advisor.is_impossible(1));
+		assert!(advisor.suggestions().is_empty());
+		now += ADVISOR_DELAY;
+		advisor.update(&dictionary, &cells, 0, now);
+		assert!(!advisor.is_pending());
+		assert!(!advisor.is_impossible(0));
+		assert!(advisor.is_impossible(1));
+		assert!(!advisor.is_impossible(2));
+		assert!(!advisor.is_impossible(3));
+		assert_eq!(advisor.suggestions(), ["tru", "trus", "try", "trys"]);
+
+		// Typing postpones the scan, and a stale verdict is never reported.
+		cells[1] = Fragment::from("ss");
+		advisor.update(&dictionary, &cells, 1, now);
+		assert!(advisor.is_pending());
+		assert!(!advisor.is_impossible(1));
+		assert!(advisor.suggestions().is_empty());
+		now += ADVISOR_DELAY / 2;
+		cells[1] = Fragment::from("zz");
+		advisor.update(&dictionary, &cells, 1, now);
+		now += ADVISOR_DELAY / 2;
+		advisor.update(&dictionary, &cells, 1, now);
+		assert!(advisor.is_pending());
+		now += ADVISOR_DELAY;
+		advisor.update(&dictionary, &cells, 1, now);
+		assert!(!advisor.is_pending());
+		assert!(advisor.is_impossible(1));
+
+		cells[1] = Fragment::from("ss");
+		advisor.update(&dictionary, &cells, 1, now);
+		now += ADVISOR_DELAY;
+		advisor.update(&dictionary, &cells, 1, now);
+		assert!(!advisor.is_impossible(1));
+		assert!(advisor.suggestions().is_empty());
+
+		let dictionary = Rc::new(["truss"].into_iter().collect::<Dictionary>());
+		advisor.update(&dictionary, &cells, 3, now);
+		assert!(advisor.is_pending());
+		assert!(!advisor.is_impossible(2));
+		now += ADVISOR_DELAY;
+		advisor.update(&dictionary, &cells, 3, now);
+		assert!(advisor.is_impossible(2));
+		assert!(advisor.suggestions().is_empty());
+	}
+}
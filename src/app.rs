@@ -1,37 +1,91 @@
 //! # Application
 //!
 //! The application state and logic, including the text-based user interface
-//! (TUI).
+//! (TUI). The solve screen lives here, alongside the state that every screen
+//! shares; each of the other screens lives in a submodule of its own.
 
 use std::{
-	collections::HashSet,
+	cell::{Cell, OnceCell},
+	cmp::Reverse,
+	collections::{btree_map, BTreeMap, BTreeSet, HashMap, HashSet},
+	fmt::{self, Display, Formatter},
 	io,
 	mem::swap,
+	path::PathBuf,
 	rc::Rc,
 	time::{Duration, Instant}
 };
 
 use crossterm::event::{
-	Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, poll, read
+	Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton,
+	MouseEvent, MouseEventKind
 };
-use fixedstr::str8;
+use serde::Serialize;
 use quartiles_solver::{
-	dictionary::Dictionary,
-	solver::{FragmentPath, Solver}
+	dictionary::{
+		normalize_word,
+		Composition,
+		Dictionary,
+		Source,
+		MAX_WORD_LEN,
+		MIN_WORD_LEN
+	},
+	inventory::{Inventory, Rarity},
+	solver::{
+		closest_words, explain, parse_fragment, Checkpoint, Completeness,
+		Explanation, Fragment, FragmentPath, Solver, SolverConfig,
+		SolverMetrics, Word, WordFilter, MAX_FRAGMENT_BYTES
+	}
 };
+#[cfg(feature = "trace-ui")]
+use quartiles_solver::trace::Decision;
 use ratatui::{
 	Frame,
+	backend::Backend,
 	buffer::Buffer,
-	layout::{Alignment, Constraint, Direction, Layout, Rect},
-	style::{Color, Style, Stylize},
-	text::{Line, Text},
+	layout::{
+		Alignment, Constraint, Direction, Flex, Layout, Margin, Position, Rect
+	},
+	style::Style,
+	text::{Line, Span, Text},
 	widgets::{
-		Block, BorderType, Borders, List, ListState, Paragraph, StatefulWidget,
-		Widget, Wrap
+		Block, BorderType, Borders, Clear, Gauge, List, ListState, Padding,
+		Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+		StatefulWidget, Widget, Wrap
 	}
 };
+use tracing::{debug_span, info, warn};
+use unicode_normalization::char::is_combining_mark;
+
+use crate::{
+	advisor::{Advisor, MAX_SUGGESTIONS},
+	card::Card,
+	curation,
+	daily::Date,
+	driver::{Clock, EventSource, SystemClock, ThreadedEvents},
+	history::{CachedSolve, Entry, Provenance, Statistics},
+	keymap::{Action, Keymap, Mode, Resolution},
+	loader::Loader,
+	locale::{Lang, Messages},
+	play::{Game, Timer, Verdict},
+	replay::{Recorder, Replay},
+	state,
+	theme::{Theme, ThemeName},
+	tui::{self, Tui}
+};
+#[cfg(feature = "graphics")]
+use crate::graphics::Renderer;
+
+mod history;
+mod menu;
+mod play;
+mod settings;
+mod statistics;
+#[cfg(feature = "trace-ui")]
+mod trace;
 
-use crate::tui::Tui;
+use menu::MENU;
+use settings::Setting;
 
 ////////////////////////////////////////////////////////////////////////////////
 //                                Application.                                //
@@ -41,15 +95,197 @@ use crate::tui::Tui;
 #[must_use]
 pub struct App
 {
+	/// The current screen.
+	screen: Screen,
+
+	/// Whether the application has a [main menu](Screen::Menu), to which
+	/// leaving any other screen returns.
+	has_menu: bool,
+
+	/// How long each round of [play](Screen::Play) lasts, if rounds are
+	/// timed.
+	time_limit: Option<Duration>,
+
 	/// Whether the application is running.
 	state: ExecutionState,
 
 	/// How long (in µs) to highlight an individual word in the TUI.
 	highlight_duration_µs: u64,
 
+	/// Whether motion is reduced: the solver never pauses to
+	/// [highlight](ExecutionState::Highlighting) a word, but stays
+	/// [solving](ExecutionState::Solving) and streams the words into the
+	/// solution list.
+	is_motion_reduced: bool,
+
+	/// How long the solver may run before it stops and reports the best
+	/// solution found so far, if its time is limited.
+	solve_budget: Option<Duration>,
+
+	/// When the current solve runs out of time, if its time is limited.
+	solve_deadline: Option<Instant>,
+
 	/// The dictionary to use for solving the puzzle.
 	dictionary: Rc<Dictionary>,
 
+	/// The [subset](Dictionary::subset) of the dictionary that the solver
+	/// walks, i.e., the words that Quartiles can form, computed on demand.
+	/// The puzzle generator and the daily puzzle need every word, so they use
+	/// the [whole](Self::dictionary) dictionary.
+	solver_dictionary: OnceCell<Rc<Dictionary>>,
+
+	/// The [inventory](Inventory) of the
+	/// [solver's dictionary](Self::solver_dictionary), which judges the
+	/// [rarity](Rarity) of the words of the solution, learned on demand.
+	inventory: OnceCell<Inventory>,
+
+	/// The name of the dictionary, for display in the status bar.
+	dictionary_name: Option<String>,
+
+	/// The directory containing the dictionary files, from which the
+	/// [settings](Screen::Settings) load another dictionary.
+	dictionary_directory: PathBuf,
+
+	/// The dictionary that is loading in the background, if any. Once it
+	/// has loaded, it replaces the [current](Self::dictionary) dictionary.
+	loader: Option<Loader>,
+
+	/// How to compose every dictionary that loads in the background.
+	composition: Composition,
+
+	/// Whether every dictionary that loads in the background
+	/// [folds](Dictionary::set_folding) its words and lookups.
+	is_folding: bool,
+
+	/// Whether the splash screen is showing, i.e., whether the initial
+	/// dictionary is still loading in the background.
+	is_splash_visible: bool,
+
+	/// The error that ended the application prematurely, if any.
+	fatal_error: Option<io::Error>,
+
+	/// The dialog that reports a dictionary that could not be opened, while
+	/// it is open.
+	load_failure: Option<LoadFailure>,
+
+	/// The optional configuration file, to which the
+	/// [settings](Screen::Settings) persist every change.
+	config_file: Option<PathBuf>,
+
+	/// The optional [word filter](WordFilter) to install in the solver.
+	filter: Option<Rc<dyn WordFilter>>,
+
+	/// The [configuration](SolverConfig) to install in the solver.
+	solver_config: SolverConfig,
+
+	/// The optional state file, which persists an in-progress solve so that
+	/// it can be resumed after the terminal disconnects.
+	state_file: Option<PathBuf>,
+
+	/// When the state file was last written.
+	last_checkpoint: Instant,
+
+	/// The optional history file, which records every finished solve.
+	history_file: Option<PathBuf>,
+
+	/// The optional favorites file, which records the favorite words.
+	favorites_file: Option<PathBuf>,
+
+	/// The words that the user marked as favorites, which are flagged with a
+	/// heart in the solution.
+	favorites: BTreeSet<String>,
+
+	/// The order of the words within each section of the solution list.
+	sort_order: SortOrder,
+
+	/// The provenance of the board, if it was generated rather than typed.
+	provenance: Option<Provenance>,
+
+	/// The most recently exported share card, which is handed over in the
+	/// [outcome](Outcome) once the TUI exits.
+	card: Option<Card>,
+
+	/// The file to which to record each [replay](Replay) of a solve, if any.
+	record_file: Option<PathBuf>,
+
+	/// The recorder of the replay in progress, if it is being recorded.
+	recorder: Option<Recorder>,
+
+	/// The name of the color scheme.
+	theme_name: ThemeName,
+
+	/// The color scheme.
+	theme: Theme,
+
+	/// Whether the TUI is accessible: no state is conveyed by color alone,
+	/// and every change of state is [announced](Self::announce).
+	is_accessible: bool,
+
+	/// The most recent announcement, while the TUI is accessible.
+	announcement: Option<String>,
+
+	/// The messages of the user interface, in the selected language.
+	messages: &'static Messages,
+
+	/// The keymap.
+	keymap: Keymap,
+
+	/// The advisor, which checks the fragments of the board against the
+	/// dictionary while [populating](ExecutionState::Populating).
+	advisor: Advisor,
+
+	/// The board renderer.
+	#[cfg(feature = "graphics")]
+	renderer: Renderer,
+
+	/// Whether the help overlay is open.
+	is_help_visible: bool,
+
+	/// The bulk-entry modal, while it is open.
+	bulk_entry: Option<BulkEntry>,
+
+	/// The explain modal, while it is open.
+	explain: Option<ExplainPrompt>,
+
+	/// Whether exiting with a partially or fully typed board asks for
+	/// confirmation first, lest the board be lost.
+	confirm_exit: bool,
+
+	/// Whether the exit confirmation modal is open.
+	is_exit_pending: bool,
+
+	/// The index of the cell that most recently refused a character because
+	/// it was full, until the next key press.
+	overflow: Option<usize>,
+
+	/// The index of the first visible word of the solution list, unless
+	/// [following](Self::is_following) the most recently discovered word.
+	scroll_offset: usize,
+
+	/// Whether the solution list automatically scrolls to the most recently
+	/// discovered word.
+	is_following: bool,
+
+	/// The number of words that fit in the solution list, as of the most
+	/// recent frame. Scrolling by a page uses this.
+	viewport_height: Cell<usize>,
+
+	/// The source of the current time.
+	clock: Rc<dyn Clock>,
+
+	/// When the current solve started, if any.
+	solve_started: Option<Instant>,
+
+	/// How long the most recent solve took, once it has finished.
+	solve_duration: Option<Duration>,
+
+	/// The areas of the cells of the board, in grid order, as of the most
+	/// recent frame. Mouse events are hit-tested against these.
+	cell_areas: Cell<[Rect; 20]>,
+
+	/// The index of the cell that the mouse is dragging, if any.
+	drag_origin: Option<usize>,
+
 	/// The coordinates of the cursor. The first element is X, which
 	/// corresponds to the column, and the second element is Y, which
 	/// corresponds to the row. The origin is the top-left corner.
@@ -58,7 +294,7 @@ pub struct App
 	/// The content of the 4×5 grid, linearized in row-major order. The first
 	/// element is the top-left corner (i.e., the origin), and the last element
 	/// is the bottom-right corner.
-	cells: [str8; 20]
+	cells: [Fragment; 20]
 }
 
 // Public interface.
@@ -79,1226 +315,7672 @@ impl App
 	pub fn new(highlight_duration_µs: u64, dictionary: Dictionary) -> Self
 	{
 		Self {
+			screen: Screen::Solve,
+			has_menu: false,
+			time_limit: None,
 			state: ExecutionState::Populating,
 			highlight_duration_µs,
+			is_motion_reduced: false,
+			solve_budget: None,
+			solve_deadline: None,
 			dictionary: Rc::new(dictionary),
+			solver_dictionary: OnceCell::new(),
+			inventory: OnceCell::new(),
+			dictionary_name: None,
+			dictionary_directory: PathBuf::from("dict"),
+			loader: None,
+			composition: Composition::default(),
+			is_folding: false,
+			is_splash_visible: false,
+			fatal_error: None,
+			load_failure: None,
+			config_file: None,
+			filter: None,
+			solver_config: SolverConfig::default(),
+			state_file: None,
+			last_checkpoint: Instant::now(),
+			history_file: None,
+			favorites_file: None,
+			favorites: BTreeSet::new(),
+			sort_order: SortOrder::default(),
+			provenance: None,
+			card: None,
+			record_file: None,
+			recorder: None,
+			theme_name: ThemeName::default(),
+			theme: Theme::default(),
+			is_accessible: false,
+			announcement: None,
+			messages: Messages::of(Lang::default()),
+			keymap: Keymap::default(),
+			advisor: Advisor::default(),
+			#[cfg(feature = "graphics")]
+			renderer: Renderer::default(),
+			is_help_visible: false,
+			bulk_entry: None,
+			explain: None,
+			confirm_exit: true,
+			is_exit_pending: false,
+			overflow: None,
+			scroll_offset: 0,
+			is_following: true,
+			viewport_height: Cell::new(0),
+			clock: Rc::new(SystemClock),
+			solve_started: None,
+			solve_duration: None,
+			cell_areas: Cell::new([Rect::default(); 20]),
+			drag_origin: None,
 			cursor: (0, 0),
-			cells: [str8::default(); 20]
+			cells: [Fragment::default(); 20]
 		}
 	}
 
-	/// Run the application. This amounts to:
+	/// Install the specified [word filter](WordFilter) in every solver that
+	/// the application creates.
 	///
-	/// * Running any background tasks, such as the solver or the highlighter.
-	/// * Rendering the application frame.
-	/// * Processing events.
+	/// # Arguments
+	///
+	/// * `filter` - The word filter.
+	///
+	/// # Returns
+	///
+	/// The application state, with the word filter installed.
+	#[inline]
+	pub fn with_filter(mut self, filter: Rc<dyn WordFilter>) -> Self
+	{
+		self.filter = Some(filter);
+		self
+	}
+
+	/// Install the specified [configuration](SolverConfig) in every solver
+	/// that the application creates.
 	///
 	/// # Arguments
 	///
-	/// * `tui` - The text-based user interface (TUI).
+	/// * `config` - The solver configuration.
 	///
 	/// # Returns
 	///
-	/// The solution to the puzzle, as a word list.
+	/// The application state, with the solver configuration installed.
+	#[inline]
+	pub fn with_solver_config(mut self, config: SolverConfig) -> Self
+	{
+		self.solver_config = config;
+		self
+	}
+
+	/// Use the specified color scheme.
+	///
+	/// # Arguments
+	///
+	/// * `name` - The name of the color scheme.
 	///
-	/// # Errors
+	/// # Returns
 	///
-	/// Any error that occurs while running the application.
-	pub fn run(mut self, tui: &mut Tui) -> io::Result<Vec<String>>
+	/// The application state, with the color scheme installed.
+	#[inline]
+	pub fn with_theme(mut self, name: ThemeName) -> Self
 	{
-		while self.is_running()
-		{
-			self.process_systems();
-			tui.draw(|frame| self.render_frame(frame))?;
-			self.process_event()?;
-		}
-		// Only produce a solution if the solver has finished.
-		match self.state
-		{
-			ExecutionState::Exiting { solution } => Ok(solution),
-			_ => Ok(vec![])
-		}
+		self.theme_name = name;
+		self.theme = Theme::new(name);
+		self
 	}
 
-	/// Check if the application is running.
+	/// Name the dictionary, for display in the status bar.
+	///
+	/// # Arguments
+	///
+	/// * `name` - The name of the dictionary.
 	///
 	/// # Returns
 	///
-	/// `true` if the application is running, `false` otherwise.
+	/// The application state, with the dictionary named.
 	#[inline]
-	#[must_use]
-	pub fn is_running(&self) -> bool
+	pub fn with_dictionary_name(mut self, name: impl Into<String>) -> Self
 	{
-		!matches!(self.state, ExecutionState::Exiting { .. })
+		self.dictionary_name = Some(name.into());
+		self
 	}
-}
 
-// Private implementation details.
-impl App
-{
-	/// Move the cursor by the given deltas, saturating at the edges of the
-	/// grid.
+	/// Use the specified directory of dictionary files, from which the
+	/// [settings](Screen::Settings) load another dictionary.
 	///
 	/// # Arguments
 	///
-	/// * `dx` - The change in the X-coordinate.
-	/// * `dy` - The change in the Y-coordinate.
-	fn move_cursor(&mut self, dx: i8, dy: i8)
+	/// * `directory` - The directory.
+	///
+	/// # Returns
+	///
+	/// The application state, with the directory installed.
+	#[inline]
+	pub fn with_dictionary_directory(mut self, directory: PathBuf) -> Self
 	{
-		let x = self.cursor.0 as i8 + dx;
-		let y = self.cursor.1 as i8 + dy;
-		if (0..4).contains(&x) && (0..5).contains(&y)
-		{
-			self.cursor = (x as u8, y as u8);
-		}
+		self.dictionary_directory = directory;
+		self
 	}
 
-	/// Move the cursor by the given index delta, saturating at the edges of the
-	/// grid. This supports tabbing through the cells.
+	/// Compose every dictionary that loads in the background as specified.
 	///
 	/// # Arguments
-	fn move_index(&mut self, di: i8)
+	///
+	/// * `composition` - How to compose the dictionary.
+	///
+	/// # Returns
+	///
+	/// The application state, with the composition installed.
+	#[inline]
+	pub fn with_composition(mut self, composition: Composition) -> Self
 	{
-		let index = self.cursor.1 as usize * 4 + self.cursor.0 as usize;
-		let new_index = index as i8 + di;
-		if (0..20).contains(&new_index)
-		{
-			self.cursor = (new_index as u8 & 3, new_index as u8 >> 2);
-		}
+		self.composition = composition;
+		self
 	}
 
-	/// Get the index of the current cell.
+	/// [Fold](Dictionary::set_folding) the words and lookups of every
+	/// dictionary that loads in the background, so that lookups ignore case
+	/// and stray whitespace.
+	///
+	/// # Arguments
+	///
+	/// * `folding` - Whether to fold.
 	///
 	/// # Returns
 	///
-	/// The index of the current cell.
+	/// The application state, with folding configured.
 	#[inline]
-	#[must_use]
-	fn current_index(&self) -> usize
+	pub fn with_folding(mut self, folding: bool) -> Self
 	{
-		self.cursor.1 as usize * 4 + self.cursor.0 as usize
+		self.is_folding = folding;
+		self
 	}
 
-	/// Get the content of the current cell.
+	/// Replace the dictionary with one that is loading in the background.
+	/// Until it has loaded, a splash screen reports its progress, and the
+	/// user can cancel loading, which exits the application. If the
+	/// dictionary cannot be opened, then the application exits, and
+	/// [`run`](Self::run) answers the error.
+	///
+	/// # Arguments
+	///
+	/// * `loader` - The loader of the dictionary.
 	///
 	/// # Returns
 	///
-	/// The content of the current cell.
+	/// The application state, with the loader installed.
 	#[inline]
-	#[must_use]
-	#[cfg(test)]
-	fn current_cell(&self) -> &str8 { &self.cells[self.current_index()] }
+	pub fn with_loader(mut self, loader: Loader) -> Self
+	{
+		self.loader = Some(loader);
+		self.is_splash_visible = true;
+		self
+	}
 
-	/// Get a mutable reference to the content of the current cell.
+	/// Persist every change made on the [settings](Screen::Settings) screen
+	/// to the specified configuration file.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The configuration file.
 	///
 	/// # Returns
 	///
-	/// A mutable reference to the content of the current cell.
+	/// The application state, with the configuration file installed.
 	#[inline]
-	#[must_use]
-	fn current_cell_mut(&mut self) -> &mut str8
+	pub fn with_config_file(mut self, path: PathBuf) -> Self
 	{
-		&mut self.cells[self.current_index()]
+		self.config_file = Some(path);
+		self
 	}
 
-	/// Delete the last character of the current cell. If the cell is empty, do
-	/// nothing.
-	fn delete(&mut self)
+	/// Set whether exiting with a typed board asks for confirmation first.
+	///
+	/// # Arguments
+	///
+	/// * `confirm` - Whether to ask for confirmation.
+	///
+	/// # Returns
+	///
+	/// The application state, with the confirmation policy installed.
+	#[inline]
+	pub fn with_confirm_exit(mut self, confirm: bool) -> Self
 	{
-		let cell = self.current_cell_mut();
-		cell.truncate(cell.len().saturating_sub(1));
+		self.confirm_exit = confirm;
+		self
 	}
 
-	/// Clear the content of the current cell.
-	fn clear(&mut self)
+	/// Make the TUI accessible, e.g., for a screen reader: mark the cursor
+	/// and the selection with symbols as well as color, and describe every
+	/// change of state in words, both in an announcement line above the
+	/// status bar and in the log.
+	///
+	/// # Arguments
+	///
+	/// * `accessible` - Whether the TUI is accessible.
+	///
+	/// # Returns
+	///
+	/// The application state, with accessibility configured.
+	#[inline]
+	pub fn with_accessible(mut self, accessible: bool) -> Self
 	{
-		let cell = self.current_cell_mut();
-		cell.clear();
+		self.is_accessible = accessible;
+		self
 	}
 
-	/// Clear the contents of all cells.
-	fn clear_all(&mut self) { self.cells.iter_mut().for_each(str8::clear); }
+	/// Reduce motion: never flash each word as the solver finds it, but keep
+	/// solving and stream the words into the solution list.
+	///
+	/// # Arguments
+	///
+	/// * `reduced` - Whether motion is reduced.
+	///
+	/// # Returns
+	///
+	/// The application state, with motion configured.
+	#[inline]
+	pub fn with_reduced_motion(mut self, reduced: bool) -> Self
+	{
+		self.is_motion_reduced = reduced;
+		self
+	}
 
-	/// Move the word index. If nothing is highlighted, use the sign of the
-	/// change to determine which end of the solution to start from, i.e.,
-	/// positive for the beginning and negative for the end.
+	/// Limit how long the solver may run. Once its time runs out, the solver
+	/// stops and the application reports the best solution found so far,
+	/// marked as truncated.
 	///
-	/// If the change would move the index out of bounds, remove the highlight.
+	/// # Arguments
+	///
+	/// * `budget` - How long the solver may run, or `None` for no limit.
+	///
+	/// # Returns
+	///
+	/// The application state, with the time budget configured.
+	#[inline]
+	pub fn with_solve_budget(mut self, budget: Option<Duration>) -> Self
+	{
+		self.solve_budget = budget;
+		self
+	}
+
+	/// Use the specified language for the user interface.
 	///
 	/// # Arguments
 	///
-	/// * `di` - The change in the word index.
-	fn move_word_index(&mut self, di: i8)
+	/// * `lang` - The language.
+	///
+	/// # Returns
+	///
+	/// The application state, with the language selected.
+	#[inline]
+	pub fn with_lang(mut self, lang: Lang) -> Self
 	{
-		if let ExecutionState::Finished {
-			ref solver,
-			ref mut highlight,
-			..
-		} = self.state
-		{
-			let solution = solver.solution();
-			if let Some(index) = highlight
-			{
-				let new_highlight = index.wrapping_add(di as usize);
-				if (0..solution.len()).contains(&new_highlight)
-				{
-					*highlight = Some(new_highlight);
-				}
-				else
-				{
-					*highlight = None;
-				}
-			}
-			else if di > 0
-			{
-				*highlight = Some((di.wrapping_sub(1)) as usize);
-			}
-			else if di < 0
-			{
-				*highlight = Some(solution.len().wrapping_add(di as usize));
-			}
-		}
+		self.messages = Messages::of(lang);
+		self
 	}
 
-	/// Append the given alphabetic character to the current cell. If the cell
-	/// is full, do nothing.
+	/// Use the specified keymap.
 	///
 	/// # Arguments
 	///
-	/// * `c` - The character to append.
+	/// * `keymap` - The keymap.
 	///
-	/// # Panics
+	/// # Returns
 	///
-	/// If the character is not alphabetic.
-	fn append(&mut self, c: char)
+	/// The application state, with the keymap installed.
+	#[inline]
+	pub fn with_keymap(mut self, keymap: Keymap) -> Self
 	{
-		assert!(c.is_alphabetic());
-		let cell = self.current_cell_mut();
-		if cell.len() < 8
-		{
-			cell.push_char(c);
-		}
+		self.keymap = keymap;
+		self
 	}
 
-	/// Render the application frame.
+	/// Use the specified board renderer.
 	///
 	/// # Arguments
 	///
-	/// * `frame` - The target frame.
-	fn render_frame(&self, frame: &mut Frame)
+	/// * `renderer` - The board renderer.
+	///
+	/// # Returns
+	///
+	/// The application state, with the board renderer installed.
+	#[cfg(feature = "graphics")]
+	#[inline]
+	pub fn with_renderer(mut self, renderer: Renderer) -> Self
 	{
-		frame.render_widget(self, frame.area());
+		self.renderer = renderer;
+		self
 	}
 
-	/// Render the [population](ExecutionState::Populating) UI.
+	/// Populate the board with the specified fragments, e.g., those of a
+	/// generated puzzle. Has no effect if an interrupted solve is being
+	/// [resumed](ExecutionState::Resuming), since the resumed solve determines
+	/// the board.
 	///
 	/// # Arguments
 	///
-	/// * `area` - The target area.
-	/// * `buf` - The target buffer.
-	fn render_populating(&self, area: Rect, buf: &mut Buffer)
+	/// * `fragments` - The fragments, in grid order.
+	///
+	/// # Returns
+	///
+	/// The application state, with the board populated.
+	pub fn with_fragments(mut self, fragments: [Fragment; 20]) -> Self
 	{
-		// Split the screen into two parts: the puzzle and the solution.
-		let outer = self.split_outer_screen(area);
-		// The puzzle comprises a 4×5 grid of cells.
-		let board = self.split_board(outer[0]);
-		// Render the board.
-		self.render_board(
-			outer[0],
-			buf,
-			Some(
-				"\
-					←↑↓→ - move \
-					⇥ - next \
-					⇧⇥ - previous \
-					A-Z - edit \
-					⌫ - delete \
-					⌦ - clear\
-				"
-				.cyan()
-			),
-			Some("↵ – solve".green().bold())
-		);
-		// Render all of the cells.
-		self.render_cells(board, buf, |index, cell| {
-			let cell_style = if index == self.current_index()
-			{
-				Style::default().fg(Color::Black).bg(Color::Cyan)
-			}
-			else
-			{
-				Style::default()
-			};
-			let border_color = if cell.is_empty()
-			{
-				Color::Red
-			}
-			else
-			{
-				Color::White
-			};
-			let block = Block::new()
-				.border_type(BorderType::Rounded)
-				.borders(Borders::ALL)
-				.border_style(Style::default().fg(border_color));
-			let cell = Paragraph::new(cell.as_str())
-				.block(block)
-				.alignment(Alignment::Left)
-				.style(cell_style)
-				.wrap(Wrap { trim: true });
-			cell
-		});
-		// Render the empty solution.
-		self.render_solution_list(
-			outer[1],
-			buf,
-			None,
-			Some(None),
-			None::<&str>,
-			None,
-			None
-		);
+		if !matches!(self.state, ExecutionState::Resuming { .. })
+		{
+			self.cells = fragments;
+		}
+		self
 	}
 
-	/// Render the [solving](ExecutionState::Solving) UI.
+	/// Populate the board with the specified generated puzzle, and remember
+	/// its [provenance](Provenance) for the history. As with
+	/// [`with_fragments`](Self::with_fragments), this has no effect if an
+	/// interrupted solve is being resumed.
 	///
 	/// # Arguments
 	///
-	/// * `area` - The target area.
-	/// * `buf` - The target buffer.
-	/// * `solver` - The solver.
-	fn render_solving(&self, area: Rect, buf: &mut Buffer, solver: &Solver)
+	/// * `provenance` - The provenance of the puzzle.
+	///
+	/// # Returns
+	///
+	/// The application state, with the board populated.
+	pub fn with_provenance(mut self, provenance: Provenance) -> Self
 	{
-		// Split the screen into two parts: the puzzle and the solution.
-		let outer = self.split_outer_screen(area);
-		// The puzzle comprises a 4×5 grid of cells.
-		let board = self.split_board(outer[0]);
-		// Render the board.
-		self.render_board(outer[0], buf, None::<&str>, None::<&str>);
-		// Render all of the cells.
-		self.render_cells(board, buf, |_, cell| {
-			let block = Block::new()
-				.border_type(BorderType::Rounded)
-				.borders(Borders::ALL)
-				.border_style(Style::default().fg(Color::White));
-			let cell = Paragraph::new(cell.as_str())
-				.block(block)
-				.alignment(Alignment::Left)
-				.style(Style::default())
-				.wrap(Wrap { trim: true });
-			cell
-		});
-		// Render the solution.
-		self.render_solution_list(
-			outer[1],
-			buf,
-			Some(solver),
-			None,
-			None::<&str>,
-			Some(Style::default().fg(Color::White)),
-			None
-		);
+		self.provenance = Some(provenance);
+		self.with_fragments(provenance.fragments)
 	}
 
-	/// Render a [highlighting](ExecutionState::Highlighting) UI.
+	/// Record every finished solve in the specified history file, and allow
+	/// the user to [browse](Screen::History) the history in order to
+	/// reopen a past puzzle.
 	///
 	/// # Arguments
 	///
-	/// * `area` - The target area.
-	/// * `buf` - The target buffer.
-	/// * `solver` - The solver.
-	/// * `path` - The fragment path of the solution to highlight.
-	fn render_highlighting(
-		&self,
-		area: Rect,
-		buf: &mut Buffer,
-		solver: &Solver,
-		path: &FragmentPath
-	)
+	/// * `path` - The history file.
+	///
+	/// # Returns
+	///
+	/// The application state, with the history file installed.
+	#[inline]
+	pub fn with_history_file(mut self, path: PathBuf) -> Self
 	{
-		// Split the screen into two parts: the puzzle and the solution.
-		let outer = self.split_outer_screen(area);
-		// The puzzle comprises a 4×5 grid of cells.
-		let board = self.split_board(outer[0]);
-		self.render_board(outer[0], buf, None::<&str>, None::<&str>);
-		// Build all of the cells.
-		self.render_cells(board, buf, |index, cell| {
-			let in_fragment =
-				path.iter().any(|i| matches!(i, Some(x) if x == index));
-			let border_color = if in_fragment
-			{
-				Color::Black
-			}
-			else
-			{
-				Color::White
-			};
-			let block = Block::new()
-				.border_type(BorderType::Rounded)
-				.borders(Borders::ALL)
-				.border_style(Style::default().fg(border_color));
-			let cell = if in_fragment
-			{
-				let index_in_fragment = path
-					.iter()
-					.position(|i| matches!(i, Some(x) if x == index))
-					.unwrap();
-				let label =
-					format!("{} {}", index_in_fragment + 1, cell.as_str());
-				Paragraph::new(label)
-					.block(block)
-					.alignment(Alignment::Left)
-					.style(Style::default().fg(Color::Black).bg(Color::Green))
-					.wrap(Wrap { trim: true })
-			}
-			else
-			{
-				Paragraph::new(cell.as_str())
-					.block(block)
-					.alignment(Alignment::Left)
-					.style(Style::default())
-					.wrap(Wrap { trim: true })
-			};
-			cell
-		});
-		// Render the solution. Colorize the quartiles. Highlight the last word,
-		// which corresponds to the argument fragment path.
-		self.render_solution_list(
-			outer[1],
-			buf,
-			Some(solver),
-			None,
-			None::<&str>,
-			Some(Style::default().fg(Color::White)),
-			Some(Style::default().fg(Color::Black).bg(Color::Green))
-		);
+		self.history_file = Some(path);
+		self
 	}
 
-	/// Render the [finished](ExecutionState::Finished) UI.
+	/// Record the favorite words in the specified favorites file, reading the
+	/// favorites already recorded there. A favorites file that cannot be
+	/// read is treated as empty.
 	///
 	/// # Arguments
 	///
-	/// * `area` - The target area.
-	/// * `buf` - The target buffer.
-	/// * `solver` - The solver.
-	/// * `is_solved` - Whether the puzzle has been solved.
-	/// * `highlight` - The index of the solution to highlight, if any.
-	fn render_finished(
-		&self,
-		area: Rect,
-		buf: &mut Buffer,
-		solver: &Solver,
-		is_solved: bool,
-		highlight: Option<usize>
-	)
+	/// * `path` - The favorites file.
+	///
+	/// # Returns
+	///
+	/// The application state, with the favorites file installed.
+	pub fn with_favorites_file(mut self, path: PathBuf) -> Self
 	{
-		// Split the screen into two parts: the puzzle and the solution.
-		let outer = self.split_outer_screen(area);
-		// The puzzle comprises a 4×5 grid of cells.
-		let board = self.split_board(outer[0]);
-		self.render_board(
-			outer[0],
-			buf,
-			Some(
-				if is_solved
-				{
-					"✓ Solved".green().bold()
-				}
-				else
-				{
-					"✗ No solution".red().bold()
-				}
-			),
-			None::<&str>
-		);
-		// Render all of the cells.
-		self.render_cells(board, buf, |_, cell| {
-			let block = Block::new()
-				.border_type(BorderType::Rounded)
-				.borders(Borders::ALL)
-				.border_style(Style::default().fg(Color::White));
-			let cell = Paragraph::new(cell.as_str())
-				.block(block)
-				.alignment(Alignment::Left)
-				.style(Style::default())
-				.wrap(Wrap { trim: true });
-			cell
+		self.favorites = curation::load_favorites(&path).unwrap_or_else(|e| {
+			warn!("Failed to read favorites file: {}: {}", path.display(), e);
+			BTreeSet::new()
 		});
-		// Render the solution. Colorize the quartiles. Highlight the selected
-		// word.
-		self.render_solution_list(
-			outer[1],
-			buf,
-			Some(solver),
-			Some(highlight),
-			Some("↑↓ - move".cyan()),
-			Some(Style::default().fg(Color::White)),
-			Some(Style::default().fg(Color::Black).bg(Color::Cyan))
-		);
+		self.favorites_file = Some(path);
+		self
 	}
 
-	/// Split the specified area into two parts: the puzzle and the solution.
+	/// Record each [replay](Replay) of a solve in the specified file, in the
+	/// asciicast v2 format. Each replay replaces the previous recording.
 	///
 	/// # Arguments
 	///
-	/// * `area` - The target area to split. This will be the complete screen
-	///   available to the application.
+	/// * `path` - The file.
 	///
 	/// # Returns
 	///
-	/// The split areas.
-	fn split_outer_screen(&self, area: Rect) -> Rc<[Rect]>
+	/// The application state, with the recording file installed.
+	pub fn with_record_file(mut self, path: PathBuf) -> Self
 	{
-		Layout::default()
-			.direction(Direction::Horizontal)
-			.margin(1)
-			.constraints([Constraint::Percentage(100), Constraint::Min(20)])
-			.split(area)
+		self.record_file = Some(path);
+		self
 	}
 
-	/// Split the specified area into rows: two margins and 5 central
-	/// rows.
+	/// Open the application at the [main menu](Screen::Menu), from which
+	/// every screen is reachable. Leaving any other screen then returns to
+	/// the menu, rather than exiting the application.
+	///
+	/// # Returns
+	///
+	/// The application state, with the main menu installed.
+	#[inline]
+	pub fn with_menu(mut self) -> Self
+	{
+		self.screen = Screen::Menu { selected: 0 };
+		self.has_menu = true;
+		self
+	}
+
+	/// Time each round of [play](Screen::Play), ending the round when the
+	/// countdown expires.
 	///
 	/// # Arguments
 	///
-	/// * `area` - The target area to split.
+	/// * `limit` - How long each round lasts.
 	///
 	/// # Returns
 	///
-	/// The split areas.
-	fn split_board(&self, area: Rect) -> Rc<[Rect]>
+	/// The application state, with timed rounds.
+	#[inline]
+	pub fn with_time_limit(mut self, limit: Duration) -> Self
 	{
-		Layout::default()
-			.direction(Direction::Vertical)
-			.margin(3)
-			.constraints([
-				Constraint::Ratio(1, 3),
-				Constraint::Length(3),
-				Constraint::Length(3),
-				Constraint::Length(3),
-				Constraint::Length(3),
-				Constraint::Length(3),
-				Constraint::Ratio(1, 3)
-			])
-			.split(area)
+		self.time_limit = Some(limit);
+		self
 	}
 
-	/// Render the board, with optional titles at the bottom center and top
-	/// right.
+	/// Persist in-progress solves to the specified state file. If the state
+	/// file already holds an interrupted solve, then offer to
+	/// [resume](ExecutionState::Resuming) it.
 	///
 	/// # Arguments
 	///
-	/// * `area` - The target area.
-	/// * `buf` - The target buffer.
-	/// * `bottom_center` - The title to render at the bottom center.
-	/// * `top_right` - The title to render at the top right.
-	fn render_board<'a>(
-		&self,
-		area: Rect,
-		buf: &mut Buffer,
-		bottom_center: Option<impl Into<Line<'a>>>,
-		top_right: Option<impl Into<Line<'a>>>
-	)
+	/// * `path` - The state file.
+	///
+	/// # Returns
+	///
+	/// The application state, with the state file installed.
+	pub fn with_state_file(mut self, path: PathBuf) -> Self
 	{
-		let mut block = Block::default()
-			.borders(Borders::ALL)
-			.border_style(Style::default().fg(Color::White))
-			.title_top(Line::from("Puzzle").centered())
-			.title_top(Line::from("⎋ – exit".yellow().bold()).left_aligned());
-		if let Some(title) = bottom_center
-		{
-			block = block.title_bottom(title.into().centered());
-		}
-		if let Some(title) = top_right
+		match state::load(&path)
 		{
-			block = block.title_top(title.into().right_aligned());
+			Ok(Some(checkpoint)) if !checkpoint.is_finished() =>
+			{
+				self.cells = checkpoint.fragments();
+				self.state = ExecutionState::Resuming { checkpoint };
+			},
+			Ok(_) =>
+			{},
+			Err(e) =>
+			{
+				warn!("Discarding state file: {}: {}", path.display(), e);
+				let _ = state::clear(&path);
+			}
 		}
-		block.render(area, buf);
+		self.state_file = Some(path);
+		self
 	}
 
-	/// Render the cells of the board.
+	/// Set the source of the current time, e.g., a fake clock that a test
+	/// advances by hand.
 	///
 	/// # Arguments
 	///
-	/// * `board` - The board area, as a margin, followed by 5 rows, followed by
-	///   another margin.
-	/// * `buf` - The target buffer.
-	/// * `cell_builder` - A function that builds a cell from an index and a
-	///   string.
-	fn render_cells(
-		&self,
-		board: Rc<[Rect]>,
-		buf: &mut Buffer,
-		cell_builder: impl Fn(usize, &str8) -> Paragraph<'_>
-	)
+	/// * `clock` - The clock.
+	///
+	/// # Returns
+	///
+	/// The application state, with the clock installed.
+	#[cfg(test)]
+	pub fn with_clock(mut self, clock: Rc<dyn Clock>) -> Self
 	{
-		let cells = self
-			.cells
-			.iter()
-			.enumerate()
-			.map(|(index, cell)| cell_builder(index, cell))
-			.collect::<Vec<_>>();
-		// Lay out the cells in a 4×5 grid.
-		cells
-			.chunks_exact(4)
-			.enumerate()
-			.for_each(|(index, chunk)| {
-				let row = Layout::default()
-					.direction(Direction::Horizontal)
-					.constraints([
-						Constraint::Min(10),
-						Constraint::Min(10),
-						Constraint::Min(10),
-						Constraint::Min(10)
-					])
-					.split(board[index + 1]);
-				for (column, cell) in chunk.iter().enumerate()
-				{
-					cell.render(row[column], buf);
-				}
-			});
+		self.last_checkpoint = clock.now();
+		self.clock = clock;
+		self
 	}
 
-	/// Construct a solution list from the solver, providing colorization based
-	/// on the status of individual words. Specifically, quartiles are colored
-	/// green, while shorter words are colored white. Deduplicate the list.
+	/// Run the application against the events of the terminal, which a
+	/// dedicated [input thread](ThreadedEvents) reads. This amounts to:
+	///
+	/// * Running any background tasks, such as the solver or the highlighter.
+	/// * Rendering the application frame, but no more often than every
+	///   [tick](FRAME_INTERVAL), and only when something has changed.
+	/// * Processing every pending event. When there is no background work,
+	///   this blocks until the next event or frame, so that an idle
+	///   application doesn't burn CPU.
 	///
 	/// # Arguments
 	///
-	/// * `solver` - The solver.
+	/// * `tui` - The text-based user interface (TUI).
 	///
 	/// # Returns
 	///
-	/// A list of styled text items.
-	fn solution_list(&self, solver: &Solver) -> Vec<Text>
+	/// The [outcome](Outcome) of the solve.
+	///
+	/// # Errors
+	///
+	/// * Any error that occurs while running the application.
+	/// * If the dictionary [loading](Self::with_loader) at startup cannot be
+	///   opened, the error is returned.
+	#[inline]
+	pub fn run<B: Backend>(self, tui: &mut Tui<B>) -> io::Result<Outcome>
 	{
-		let mut seen = HashSet::new();
-		solver
-			.solution_paths()
-			.iter()
-			.filter_map(|path| {
-				let color = match path.is_full()
-				{
-					false => Color::White,
-					true => Color::Green
-				};
-				let word = solver.word(path).to_string();
-				let style = Style::default().fg(color);
-				if seen.contains(&word)
-				{
-					None
-				}
-				else
-				{
-					seen.insert(word.clone());
-					Some(Text::styled(word, style))
-				}
-			})
-			.collect()
+		self.run_with(tui, &mut ThreadedEvents::spawn()?)
 	}
 
-	/// Render the solution list.
+	/// Run the application against the specified source of events. Same as
+	/// [`run`](Self::run) otherwise.
 	///
 	/// # Arguments
 	///
-	/// * `area` - The target area.
-	/// * `buf` - The target buffer.
-	/// * `solver` - The solver, which is only used in some application states.
-	/// * `highlight` - The optional index of the highlighted item. If `None`,
-	///   use the last item. If the inner `Option` is `None`, do not highlight
-	///   any item.
-	/// * `bottom_center` - The optional title to render at the bottom center.
-	/// * `style` - The optional base style to apply to the list.
-	/// * `highlight_style` - The optional style to apply to the highlighted
-	///   item.
-	#[allow(clippy::too_many_arguments)]
-	fn render_solution_list<'a>(
-		&self,
-		area: Rect,
-		buf: &mut Buffer,
-		solver: Option<&Solver>,
-		highlight: Option<Option<usize>>,
-		bottom_center: Option<impl Into<Line<'a>>>,
-		style: Option<Style>,
-		highlight_style: Option<Style>
-	)
-	{
-		let list = match solver
-		{
-			None => List::default(),
-			Some(solver) => List::new(self.solution_list(solver))
-		};
-		let list = list.block({
-			let block = Block::default()
-				.borders(Borders::ALL)
-				.title_top(Line::from("Solution").centered());
-			match bottom_center
-			{
-				None => block,
-				Some(title) => block.title_bottom(title.into().centered())
-			}
-		});
-		let list = match style
-		{
-			None => list,
-			Some(style) => list.style(style)
-		};
-		let list = match highlight_style
-		{
-			None => list,
-			Some(highlight_style) => list.highlight_style(highlight_style)
-		};
-		let mut list_state = ListState::default();
-		if let Some(solver) = solver
-		{
-			if let Some(highlight) = highlight
-			{
-				list_state.select(highlight);
-			}
-			else
-			{
-				list_state.select(Some(solver.solution().len() - 1));
-			}
-		}
-		StatefulWidget::render(&list, area, buf, &mut list_state);
-	}
-
-	/// Run any background tasks, such as the solver or the highlighter.
-	fn process_systems(&mut self)
-	{
-		match self.state
-		{
-			ExecutionState::Swapping => unreachable!(),
-			ExecutionState::Populating =>
-			{},
-			ExecutionState::Solving { .. } => self.run_solver(),
-			ExecutionState::Highlighting { .. } => self.run_highlighter(),
-			ExecutionState::Finished { .. } =>
-			{},
-			ExecutionState::Exiting { .. } =>
-			{}
-		}
-	}
-
-	/// Run the solver for a short while.
-	fn run_solver(&mut self)
+	/// * `tui` - The text-based user interface (TUI).
+	/// * `events` - The source of events.
+	///
+	/// # Returns
+	///
+	/// The [outcome](Outcome) of the solve.
+	///
+	/// # Errors
+	///
+	/// * Any error that occurs while running the application.
+	/// * If the dictionary [loading](Self::with_loader) at startup cannot be
+	///   opened, the error is returned.
+	pub fn run_with<B: Backend>(
+		mut self,
+		tui: &mut Tui<B>,
+		events: &mut dyn EventSource
+	) -> io::Result<Outcome>
 	{
-		// Take care to evacuate the application state in order to keep the
-		// borrow happy while juggling state ownership and mutable references.
-		let mut state = ExecutionState::Swapping;
-		swap(&mut self.state, &mut state);
-		if let ExecutionState::Solving { solver } = state
+		let mut next_frame = self.clock.now();
+		let mut is_dirty = true;
+		let mut phase = None;
+		while self.is_running()
 		{
-			// Run the solver for only a short while, lest the application
-			// become unresponsive.
-			let (solver, path) = solver.solve(Duration::from_millis(5));
-			if solver.is_finished()
+			self.process_systems();
+			// Summarizing the application for a crash report is too costly for
+			// every frame, so refresh the summary only at each transition.
+			if phase != Some(self.phase())
 			{
-				// The solver has finished.
-				let is_solved = solver.is_solved();
-				self.state = ExecutionState::Finished {
-					solver,
-					is_solved,
-					highlight: None
-				};
+				phase = Some(self.phase());
+				tui::set_crash_context(self.crash_context());
 			}
-			else if let Some(path) = path
+			let now = self.clock.now();
+			let is_busy = self.is_working() || self.is_animating();
+			if (is_dirty || is_busy) && now >= next_frame
 			{
-				// Highlight the most recently discovered solution.
-				let until = Instant::now()
-					+ Duration::from_millis(self.highlight_duration_µs);
-				self.state = ExecutionState::Highlighting {
-					solver,
-					until,
-					path
-				};
+				let _span = debug_span!("render_frame").entered();
+				let frame = tui.draw(|frame| self.render_frame(frame))?;
+				let recorded = self.recorder
+					.as_mut()
+					.map(|recorder| recorder.record(frame.buffer, now));
+				if let Some(Err(e)) = recorded
+				{
+					warn!("Failed to record replay: {}", e);
+					self.recorder = None;
+				}
+				next_frame = now + FRAME_INTERVAL;
+				// Owe one more frame after the work or animation stops, so
+				// that its final state reaches the screen.
+				is_dirty = is_busy;
 			}
-			else
+			// Keep the solver busy, but otherwise sleep until the next frame
+			// is due, or, if nothing will change until the user acts, until
+			// the next event.
+			let timeout = if self.is_working()
 			{
-				// Maintain the solving state.
-				self.state = ExecutionState::Solving { solver };
+				Duration::from_micros(500)
 			}
-		}
-		else
-		{
-			unreachable!()
-		}
-	}
-
-	/// Run the highlighter for a short while.
-	fn run_highlighter(&mut self)
-	{
-		// Take care to evacuate the application state in order to keep the
-		// borrow checker happy while juggling state ownership and mutable
-		// references.
-		let mut state = ExecutionState::Swapping;
-		swap(&mut self.state, &mut state);
-		if let ExecutionState::Highlighting {
-			solver,
-			until,
-			path
-		} = state
-		{
-			if Instant::now() >= until
+			else if is_dirty || self.is_animating()
 			{
-				// Return to the solving state.
-				self.state = ExecutionState::Solving { solver };
+				next_frame.saturating_duration_since(self.clock.now())
 			}
 			else
 			{
-				// Maintain the highlighting.
-				self.state = ExecutionState::Highlighting {
-					solver,
-					until,
-					path
-				};
-			}
-		}
-		else
-		{
-			unreachable!()
+				Duration::MAX
+			};
+			is_dirty |= self.process_events(events, timeout)?;
 		}
+		self.into_outcome()
 	}
 
-	/// Process events. Block for only half a millisecond, so as not to stall
-	/// any background tasks.
+	/// Advance the application by a single iteration of its event loop,
+	/// without rendering: run any background tasks, and then process every
+	/// pending event. This drives the application headlessly, e.g., in tests.
+	///
+	/// # Arguments
+	///
+	/// * `events` - The source of events.
 	///
 	/// # Errors
 	///
 	/// Any error that occurs while processing events.
-	fn process_event(&mut self) -> io::Result<()>
+	#[cfg(test)]
+	pub fn tick(&mut self, events: &mut dyn EventSource) -> io::Result<()>
 	{
-		if poll(Duration::from_micros(500))?
-		{
-			match read()?
-			{
-				Event::Key(event) if event.kind == KeyEventKind::Press =>
-				{
-					self.process_key_event(event)
-				},
-				_ =>
-				{}
-			}
-		}
-		Ok(())
+		self.process_systems();
+		self.process_events(events, Duration::ZERO).map(|_| ())
 	}
 
-	/// Process a key event:
+	/// Conclude the application, once it has stopped running.
+	///
+	/// # Returns
 	///
-	/// * Escape - Exit the application.
-	/// * Up - Move the cursor up.
-	/// * Down - Move the cursor down.
-	/// * Left - Move the cursor left.
-	/// * Right - Move the cursor right.
-	/// * BackTab - (Shift+Tab) Move the cursor to the previous cell.
-	/// * Tab - Move the cursor to the next cell.
-	/// * Backspace - Delete the last character of the current cell.
-	/// * A-Z - Append the corresponding character to the current cell.
+	/// The [outcome](Outcome) of the solve.
 	///
-	/// # Arguments
+	/// # Errors
 	///
-	/// * `event` - The key event to process.
-	fn process_key_event(&mut self, event: KeyEvent)
+	/// If the dictionary [loading](Self::with_loader) at startup cannot be
+	/// opened, the error is returned.
+	pub fn into_outcome(mut self) -> io::Result<Outcome>
 	{
-		match self.state
+		if let Some(e) = self.fatal_error.take()
 		{
-			ExecutionState::Swapping => unreachable!(),
-			ExecutionState::Populating =>
-			{
-				self.process_key_event_populating(event)
-			},
-			ExecutionState::Solving { .. } =>
-			{
-				self.process_key_event_solving(event)
-			},
-			ExecutionState::Highlighting { .. } =>
-			{
-				self.process_key_event_highlighting(event)
-			},
-			ExecutionState::Finished { .. } =>
-			{
-				self.process_key_event_finished(event)
-			},
-			ExecutionState::Exiting { .. } =>
-			{}
+			return Err(e)
 		}
+		// Only produce a solution if the solver has finished.
+		let outcome = match self.state
+		{
+			ExecutionState::Exiting { outcome } => outcome,
+			_ => Outcome::default()
+		};
+		Ok(Outcome { card: self.card, ..outcome })
 	}
 
-	/// Process a key event while [populating](ExecutionState::Populating) the
-	/// puzzle:
+	/// Name the current screen and execution state, which together determine
+	/// when the [crash context](Self::crash_context) is refreshed.
 	///
-	/// * Escape - Exit the application.
-	/// * Up - Move the cursor up.
-	/// * Down - Move the cursor down.
-	/// * Left - Move the cursor left.
-	/// * Right - Move the cursor right.
-	/// * BackTab - (Shift+Tab) Move the cursor to the previous cell.
-	/// * Tab - Move the cursor to the next cell.
-	/// * Backspace - Delete the last character of the current cell.
-	/// * Enter - Solve the puzzle.
-	/// * A-Z - Append the corresponding character to the current cell.
-	///
-	/// # Arguments
+	/// # Returns
 	///
-	/// * `event` - The key event to process.
-	fn process_key_event_populating(&mut self, event: KeyEvent)
+	/// The names of the screen and the execution state.
+	fn phase(&self) -> (&'static str, &'static str)
 	{
-		match event.code
+		let screen = match self.screen
 		{
-			KeyCode::Esc => self.exit(),
-			KeyCode::Up => self.move_cursor(0, -1),
-			KeyCode::Down => self.move_cursor(0, 1),
-			KeyCode::Left => self.move_cursor(-1, 0),
-			KeyCode::Right => self.move_cursor(1, 0),
-			KeyCode::BackTab => self.move_index(-1),
-			KeyCode::Tab => self.move_index(1),
-			KeyCode::Backspace => self.delete(),
-			KeyCode::Delete
-				if event.modifiers.contains(KeyModifiers::SHIFT) =>
-			{
-				self.clear_all()
-			},
-			KeyCode::Delete => self.clear(),
-			KeyCode::Enter => self.start_solver(),
-			KeyCode::Char(c) if c.is_alphabetic() => self.append(c),
-			_ =>
-			{}
-		}
+			Screen::Menu { .. } => "menu",
+			Screen::Solve => "solve",
+			Screen::Play { .. } => "play",
+			Screen::History { .. } => "history",
+			Screen::Statistics { .. } => "statistics",
+			Screen::Settings { .. } => "settings",
+			#[cfg(feature = "trace-ui")]
+			Screen::Trace { .. } => "trace"
+		};
+		let state = match self.state
+		{
+			ExecutionState::Swapping => "swapping",
+			ExecutionState::Populating => "populating",
+			ExecutionState::Resuming { .. } => "resuming",
+			ExecutionState::Solving { .. } => "solving",
+			ExecutionState::Highlighting { .. } => "highlighting",
+			ExecutionState::Finished { is_solved: true, .. } => "solved",
+			ExecutionState::Finished { is_solved: false, .. } => "unsolved",
+			ExecutionState::Exiting { .. } => "exiting"
+		};
+		(screen, state)
 	}
 
-	/// Attempt to start the solver. If the puzzle is not fully populated, do
-	/// nothing; the UI already provides feedback to the user.
-	fn start_solver(&mut self)
+	/// Summarize the application state for a crash report: the screen, the
+	/// execution state, the dictionary, and the board. The
+	/// [run loop](Self::run_with) publishes the summary whenever the screen or
+	/// the execution state changes, so the board is the one at that moment.
+	///
+	/// # Returns
+	///
+	/// The summary, as multiple lines of text.
+	#[must_use]
+	pub fn crash_context(&self) -> String
 	{
-		if self.cells.iter().all(|cell| !cell.is_empty())
+		let (screen, state) = self.phase();
+		let mut context = format!(
+			"Screen: {}\nExecution state: {}\nDictionary: {}/{} ({} words)\n\
+				Cursor: {:?}\nSeed: {}\nBoard:",
+			screen,
+			state,
+			self.dictionary_directory.display(),
+			self.dictionary_name.as_deref().unwrap_or("—"),
+			self.dictionary.len(),
+			self.cursor,
+			self.provenance
+				.map(|provenance| provenance.seed.to_string())
+				.unwrap_or_else(|| "—".to_string())
+		);
+		for row in self.cells.chunks(4)
 		{
-			let solver = Solver::new(self.dictionary.clone(), self.cells);
-			self.state = ExecutionState::Solving { solver };
+			let row = row
+				.iter()
+				.map(|cell| match cell.is_empty()
+				{
+					true => format!("{:<7}", "·"),
+					false => format!("{:<7}", cell)
+				})
+				.collect::<Vec<_>>();
+			context.push_str("\n  ");
+			context.push_str(row.join(" ").trim_end());
 		}
+		context
 	}
 
-	/// Process a key event while [solving](ExecutionState::Solving) the
-	/// puzzle:
+	/// Check if the application is running.
 	///
-	/// * Escape - Exit the application.
+	/// # Returns
 	///
-	/// Also, run the solver for a short while, potentially highlighting the
-	/// most recently discovered solution.
+	/// `true` if the application is running, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_running(&self) -> bool
+	{
+		!matches!(self.state, ExecutionState::Exiting { .. })
+	}
+}
+
+// Private implementation details.
+impl App
+{
+	/// Move the cursor by the given deltas, saturating at the edges of the
+	/// grid.
 	///
 	/// # Arguments
 	///
-	/// * `event` - The key event to process.
-	/// * `solver` - The solver.
-	fn process_key_event_solving(&mut self, event: KeyEvent)
+	/// * `dx` - The change in the X-coordinate.
+	/// * `dy` - The change in the Y-coordinate.
+	fn move_cursor(&mut self, dx: i8, dy: i8)
 	{
-		if let KeyCode::Esc = event.code
+		let x = self.cursor.0 as i8 + dx;
+		let y = self.cursor.1 as i8 + dy;
+		if (0..4).contains(&x) && (0..5).contains(&y)
 		{
-			self.exit()
+			self.cursor = (x as u8, y as u8);
 		}
 	}
 
-	/// Process a key event while [highlighting](ExecutionState::Highlighting)
-	/// the puzzle:
+	/// Swap the content of the current cell with that of its neighbor at the
+	/// given deltas, and move the cursor along with the content, so that
+	/// repeated swaps carry a fragment across the grid. Does nothing at the
+	/// edges of the grid.
 	///
-	/// * Escape - Exit the application.
+	/// # Arguments
 	///
-	/// Maintain the highlight for long enough to be visible, then return to the
-	/// [solving](ExecutionState::Solving) state.
+	/// * `dx` - The X delta.
+	/// * `dy` - The Y delta.
+	fn swap_cursor(&mut self, dx: i8, dy: i8)
+	{
+		let from = self.current_index();
+		self.move_cursor(dx, dy);
+		let to = self.current_index();
+		self.cells.swap(from, to);
+	}
+
+	/// Move the cursor by the given index delta, saturating at the edges of the
+	/// grid. This supports tabbing through the cells.
 	///
 	/// # Arguments
-	///
-	/// * `event` - The key event to process.
-	/// * `solver` - The solver.
-	fn process_key_event_highlighting(&mut self, event: KeyEvent)
+	fn move_index(&mut self, di: i8)
 	{
-		if let KeyCode::Esc = event.code
+		let index = self.cursor.1 as usize * 4 + self.cursor.0 as usize;
+		let new_index = index as i8 + di;
+		if (0..20).contains(&new_index)
 		{
-			self.exit()
+			self.cursor = (new_index as u8 & 3, new_index as u8 >> 2);
 		}
 	}
 
-	/// Process a key event while [reviewing](ExecutionState::Finished) the
-	/// solution:
+	/// Get the index of the current cell.
+	///
+	/// # Returns
 	///
-	/// * Escape - Exit the application.
+	/// The index of the current cell.
+	#[inline]
+	#[must_use]
+	fn current_index(&self) -> usize
+	{
+		self.cursor.1 as usize * 4 + self.cursor.0 as usize
+	}
+
+	/// Get the content of the current cell.
 	///
-	/// # Arguments
+	/// # Returns
 	///
-	/// * `event` - The key event to process.
-	/// * `solver` - The solver.
-	fn process_key_event_finished(&mut self, event: KeyEvent)
+	/// The content of the current cell.
+	#[inline]
+	#[must_use]
+	#[cfg(test)]
+	fn current_cell(&self) -> &Fragment { &self.cells[self.current_index()] }
+
+	/// Get a mutable reference to the content of the current cell.
+	///
+	/// # Returns
+	///
+	/// A mutable reference to the content of the current cell.
+	#[inline]
+	#[must_use]
+	fn current_cell_mut(&mut self) -> &mut Fragment
 	{
-		match event.code
-		{
-			KeyCode::Esc => self.exit(),
-			KeyCode::Up => self.move_word_index(-1),
-			KeyCode::Down => self.move_word_index(1),
-			_ =>
-			{}
-		}
+		&mut self.cells[self.current_index()]
 	}
 
-	/// Mark the application for exit. The application will exit after the next
-	/// iteration of the main loop.
-	fn exit(&mut self)
+	/// Delete the last character of the current cell. If the cell is empty, do
+	/// nothing.
+	fn delete(&mut self)
 	{
-		let next_state = match self.state
+		// Truncation counts characters, not bytes, since cells may hold
+		// accented letters.
+		let cell = self.current_cell_mut();
+		let len = cell.as_str().chars().count();
+		cell.truncate(len.saturating_sub(1));
+	}
+
+	/// Clear the content of the current cell.
+	fn clear(&mut self)
+	{
+		let cell = self.current_cell_mut();
+		cell.clear();
+	}
+
+	/// Clear the contents of all cells.
+	fn clear_all(&mut self) { self.cells.iter_mut().for_each(Fragment::clear); }
+
+	/// Move the word index. If nothing is highlighted, use the sign of the
+	/// change to determine which end of the solution to start from, i.e.,
+	/// positive for the beginning and negative for the end.
+	///
+	/// If the change would move the index out of bounds, remove the highlight.
+	///
+	/// # Arguments
+	///
+	/// * `di` - The change in the word index.
+	fn move_word_index(&mut self, di: i8)
+	{
+		if let ExecutionState::Finished {
+			ref solver,
+			ref mut highlight,
+			ref mut placement,
+			..
+		} = self.state
 		{
-			ExecutionState::Swapping => unreachable!(),
-			ExecutionState::Populating =>
-			{
-				ExecutionState::Exiting { solution: vec![] }
-			},
-			ExecutionState::Solving { .. } =>
+			*placement = 0;
+			let solution = solver.solution_deduped();
+			if let Some(index) = highlight
 			{
-				ExecutionState::Exiting { solution: vec![] }
-			},
-			ExecutionState::Highlighting { .. } =>
+				let new_highlight = index.wrapping_add(di as usize);
+				if (0..solution.len()).contains(&new_highlight)
+				{
+					*highlight = Some(new_highlight);
+				}
+				else
+				{
+					*highlight = None;
+				}
+			}
+			else if di > 0
 			{
-				ExecutionState::Exiting { solution: vec![] }
-			},
-			ExecutionState::Finished { ref solver, .. } =>
+				*highlight = Some((di.wrapping_sub(1)) as usize);
+			}
+			else if di < 0
 			{
-				ExecutionState::Exiting {
-					solution: solver
-						.solution()
-						.iter()
-						.map(|s| s.to_string())
-						.collect()
-				}
-			},
-			ExecutionState::Exiting { ref solution } =>
+				*highlight = Some(solution.len().wrapping_add(di as usize));
+			}
+		}
+	}
+
+	/// Scroll the solution list by the given number of pages. Scrolling to the
+	/// end of the list resumes [following](Self::is_following) the most
+	/// recently discovered word. While reviewing the solution, the selected
+	/// word, if any, moves by the same amount instead.
+	///
+	/// # Arguments
+	///
+	/// * `pages` - The number of pages to scroll, negative to scroll up.
+	fn scroll_pages(&mut self, pages: isize)
+	{
+		let page = self.viewport_height.get().max(1);
+		let (len, words) = match self.state
+		{
+			ExecutionState::Solving { ref solver }
+				| ExecutionState::Highlighting { ref solver, .. }
+				| ExecutionState::Finished { ref solver, .. } =>
 			{
-				ExecutionState::Exiting {
-					solution: solution.clone()
-				}
+				(
+					self.solution_list(solver).len(),
+					solver.solution_deduped().len()
+				)
 			},
+			_ => return
 		};
-		self.state = next_state;
+		if let ExecutionState::Finished {
+			highlight: Some(ref mut index),
+			ref mut placement,
+			..
+		} = self.state
+		{
+			*placement = 0;
+			*index = index
+				.saturating_add_signed(pages * page as isize)
+				.min(words.saturating_sub(1));
+			return
+		}
+		let max_offset = len.saturating_sub(page);
+		let offset = match self.is_following
+		{
+			true => max_offset,
+			false => self.scroll_offset.min(max_offset)
+		};
+		self.scroll_offset = offset
+			.saturating_add_signed(pages * page as isize)
+			.min(max_offset);
+		self.is_following = self.scroll_offset == max_offset;
 	}
-}
 
-impl Widget for &App
-{
-	fn render(self, area: Rect, buf: &mut Buffer)
+	/// Show the next placement of the selected word, wrapping around to the
+	/// first placement after the last.
+	fn next_placement(&mut self)
+	{
+		if let ExecutionState::Finished {
+			highlight: Some(_),
+			ref mut placement,
+			..
+		} = self.state
+		{
+			// The placement is reduced modulo the number of placements at
+			// render time.
+			*placement = placement.wrapping_add(1);
+		}
+	}
+
+	/// Answer the selected word, while [reviewing](ExecutionState::Finished)
+	/// the solution.
+	///
+	/// # Returns
+	///
+	/// The selected word, or `None` if no word is selected.
+	fn selected_word(&self) -> Option<String>
 	{
 		match self.state
 		{
-			ExecutionState::Swapping => unreachable!(),
-			ExecutionState::Populating => self.render_populating(area, buf),
-			ExecutionState::Solving { ref solver } =>
-			{
-				self.render_solving(area, buf, solver)
-			},
-			ExecutionState::Highlighting {
-				ref solver,
-				ref path,
-				..
-			} => self.render_highlighting(area, buf, solver, path),
 			ExecutionState::Finished {
 				ref solver,
-				is_solved,
-				highlight
-			} => self.render_finished(area, buf, solver, is_solved, highlight),
-			ExecutionState::Exiting { .. } =>
-			{}
+				highlight: Some(index),
+				..
+			} => self.grouped_solution(solver).0
+				.get(index)
+				.map(|w| w.to_string()),
+			_ => None
 		}
 	}
-}
-
-/// The execution state of the application.
-#[derive(Clone, Debug)]
-enum ExecutionState
-{
-	/// The application state is transitioning to the next state. This is a
-	/// transient state that should not be rendered.
-	Swapping,
-
-	/// The user is populating the puzzle with fragments.
-	Populating,
 
-	/// The solver is running, incrementally populating the solution.
-	Solving
+	/// Mark the selected word as bogus: remove it from the solution, and
+	/// exclude it from the dictionary, both for the rest of the session and,
+	/// via the dictionary's exclusion list, for future solves. The solution
+	/// is analyzed anew, since the word may have been a quartile. The word
+	/// below, if any, is selected next.
+	fn mark_bogus(&mut self)
 	{
-		/// The solver for the puzzle.
-		solver: Solver
-	},
+		let Some(word) = self.selected_word()
+		else
+		{
+			return
+		};
+		if let ExecutionState::Finished {
+			ref mut solver,
+			ref mut is_solved,
+			is_truncated,
+			ref mut highlight,
+			ref mut placement,
+			ref mut completeness,
+			ref mut cover,
+			..
+		} = self.state
+		{
+			solver.retract(&word);
+			*is_solved = solver.is_solved();
+			// As ever, don't analyze a truncated solution.
+			*completeness = match *is_solved || is_truncated
+			{
+				true => None,
+				false => Some(solver.completeness(MAX_NEAR_MISSES))
+			};
+			*cover = solver.cover();
+			*placement = 0;
+			let len = solver.solution_deduped().len();
+			*highlight = highlight
+				.filter(|_| len > 0)
+				.map(|index| index.min(len - 1));
+		}
+		info!("Marked bogus: {}", word);
+		Rc::make_mut(&mut self.dictionary).exclude([&word]);
+		self.solver_dictionary = OnceCell::new();
+		if let Some(ref name) = self.dictionary_name
+		{
+			let path =
+				curation::exclusion_list(&self.dictionary_directory, name);
+			if let Err(e) = curation::exclude(&path, &word)
+			{
+				warn!(
+					"Failed to write exclusion list: {}: {}",
+					path.display(),
+					e
+				);
+			}
+		}
+	}
 
-	/// The solver is highlighting the most recently discovered solution, and
-	/// will momentarily return to the [Solving](ExecutionState::Solving) state.
-	Highlighting
+	/// Mark the selected word as a favorite, or unmark it if it already is
+	/// one, and persist the favorites.
+	fn mark_favorite(&mut self)
 	{
-		/// The solver for the puzzle.
-		solver: Solver,
-
-		/// When to transition back to the [Solving](ExecutionState::Solving)
-		/// state.
-		until: Instant,
-
-		/// The fragment path of the solution to highlight.
-		path: FragmentPath
-	},
+		let Some(word) = self.selected_word()
+		else
+		{
+			return
+		};
+		if !self.favorites.remove(&word)
+		{
+			info!("Marked favorite: {}", word);
+			self.favorites.insert(word);
+		}
+		if let Some(ref path) = self.favorites_file
+		{
+			if let Err(e) = curation::save_favorites(path, &self.favorites)
+			{
+				warn!(
+					"Failed to write favorites file: {}: {}",
+					path.display(),
+					e
+				);
+			}
+		}
+	}
 
-	/// The solver has finished, but the user is reviewing the solution.
-	Finished
+	/// Export a [share card](Card) of the finished solve or of the game in
+	/// play, replacing any card exported before. The card is handed over in
+	/// the [outcome](Outcome), since the terminal belongs to the TUI until it
+	/// exits. The puzzle is named by its provenance only if the user didn't
+	/// edit the generated board.
+	fn export_card(&mut self)
 	{
-		/// The solver for the puzzle.
-		solver: Solver,
-
-		/// Whether a complete solution was found.
-		is_solved: bool,
-
+		let provenance = self.provenance
+			.filter(|provenance| provenance.fragments == self.cells);
+		let card = match (&self.screen, &self.state)
+		{
+			(Screen::Play { game, .. }, _) => Card {
+				date: provenance.and_then(|provenance| provenance.daily),
+				seed: provenance.map(|provenance| provenance.seed),
+				quartiles: game.quartiles_found(),
+				words: game.found().len(),
+				total_words: Some(game.word_count()),
+				score: game.score(),
+				max_score: Some(game.max_score())
+			},
+			(Screen::Solve, ExecutionState::Finished { solver, .. }) =>
+			{
+				let (words, quartiles) = self.solution_statistics(solver);
+				Card {
+					date: provenance.and_then(|provenance| provenance.daily),
+					seed: provenance.map(|provenance| provenance.seed),
+					quartiles,
+					words,
+					total_words: None,
+					score: Solution::of(solver).score,
+					max_score: None
+				}
+			},
+			_ => return
+		};
+		info!("Exported share card:\n{}", card);
+		self.card = Some(card);
+	}
+
+	/// Get the selected word and its current placement, while
+	/// [reviewing](ExecutionState::Finished) the solution.
+	///
+	/// # Returns
+	///
+	/// A 3-tuple comprising the fragment path of the current placement of the
+	/// selected word, the (zero-based) index of the placement, and the number
+	/// of placements, or `None` if no word is selected.
+	fn selected_placement(&self) -> Option<(FragmentPath, usize, usize)>
+	{
+		if let ExecutionState::Finished {
+			ref solver,
+			highlight: Some(index),
+			placement,
+			..
+		} = self.state
+		{
+			let word = *self.grouped_solution(solver).0.get(index)?;
+			let paths = solver.paths_for_word(word.as_str());
+			if paths.is_empty()
+			{
+				return None
+			}
+			let placement = placement % paths.len();
+			Some((paths[placement], placement, paths.len()))
+		}
+		else
+		{
+			None
+		}
+	}
+
+	/// Append the given alphabetic character to the current cell, and then
+	/// [normalize](normalize_word) the cell, so that fragments always match
+	/// the dictionary regardless of case. A combining mark, e.g., from a dead
+	/// key, composes with the preceding letter. If the cell would overflow,
+	/// leave it unchanged, and remember the overflow, so that the user learns
+	/// why the character was refused.
+	///
+	/// # Arguments
+	///
+	/// * `c` - The character to append.
+	///
+	/// # Panics
+	///
+	/// If the character is neither alphabetic nor a combining mark.
+	fn append(&mut self, c: char)
+	{
+		assert!(c.is_alphabetic() || is_combining_mark(c));
+		let cell = self.current_cell_mut();
+		let mut fragment = cell.to_string();
+		fragment.push(c);
+		match parse_fragment(&normalize_word(&fragment))
+		{
+			Ok(fragment) => *cell = fragment,
+			Err(_) => self.overflow = Some(self.current_index())
+		}
+	}
+
+	/// Render the application frame.
+	///
+	/// # Arguments
+	///
+	/// * `frame` - The target frame.
+	fn render_frame(&self, frame: &mut Frame)
+	{
+		frame.render_widget(self, frame.area());
+	}
+
+	/// Render the solve screen, according to the current
+	/// [execution state](ExecutionState).
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	fn render_solve(&self, area: Rect, buf: &mut Buffer)
+	{
+		match self.state
+		{
+			ExecutionState::Swapping => unreachable!(),
+			ExecutionState::Populating => self.render_populating(area, buf),
+			ExecutionState::Resuming { .. } => self.render_resuming(area, buf),
+			ExecutionState::Solving { ref solver } =>
+			{
+				self.render_solving(area, buf, solver)
+			},
+			ExecutionState::Highlighting {
+				ref solver,
+				ref path,
+				..
+			} => self.render_highlighting(area, buf, solver, path),
+			ExecutionState::Finished {
+				ref solver,
+				replay: Some(ref replay),
+				..
+			} => self.render_replay(area, buf, solver, replay),
+			ExecutionState::Finished {
+				ref solver,
+				is_solved,
+				is_truncated,
+				highlight,
+				ref completeness,
+				..
+			} => self.render_finished(
+				area,
+				buf,
+				solver,
+				is_solved,
+				is_truncated,
+				highlight,
+				completeness.as_ref()
+			),
+			ExecutionState::Exiting { .. } =>
+			{}
+		}
+	}
+
+	/// Render the [population](ExecutionState::Populating) UI.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	fn render_populating(&self, area: Rect, buf: &mut Buffer)
+	{
+		// Split the screen into two parts: the puzzle and the solution.
+		let outer = self.split_outer_screen(area);
+		// The puzzle comprises a 4×5 grid of cells.
+		let board = self.split_board(outer[0]);
+		// Render the board.
+		self.render_board(
+			outer[0],
+			buf,
+			Some(Span::styled(self.populating_hints(), self.theme.hint)),
+			self.keymap
+				.label(Action::Solve)
+				.map(|keys| Span::styled(
+					format!("{} – {}", keys, self.text("solve.solve")),
+					self.theme.action
+				))
+		);
+		// Render all of the cells.
+		self.render_cells(board, buf, |index, cell| {
+			let cell_style = if index == self.current_index()
+			{
+				self.theme.cursor
+			}
+			else
+			{
+				Style::default()
+			};
+			let border_style = if cell.is_empty()
+			{
+				self.theme.empty_border
+			}
+			else if self.advisor.is_impossible(index)
+			{
+				self.theme.warning
+			}
+			else
+			{
+				self.theme.border
+			};
+			let mut block = Block::new()
+				.border_type(BorderType::Rounded)
+				.borders(Borders::ALL)
+				.border_style(border_style);
+			if let Some(marker) = self.cell_marker(index)
+			{
+				block = block.title_top(marker);
+			}
+			let cell = Paragraph::new(cell.as_str())
+				.block(block)
+				.alignment(Alignment::Left)
+				.style(cell_style)
+				.wrap(Wrap { trim: true });
+			cell
+		});
+		// Render the empty solution, above the suggestions.
+		let [solution, suggestions] = Layout::vertical([
+			Constraint::Min(0),
+			Constraint::Length(SUGGESTIONS_HEIGHT)
+		])
+		.areas(outer[1]);
+		self.render_solution_list(
+			solution,
+			buf,
+			None,
+			Some(None),
+			None::<&str>,
+			None,
+			None
+		);
+		self.render_suggestions(suggestions, buf);
+	}
+
+	/// Render the suggestions panel, which offers completions of the fragment
+	/// in the current cell, or warns that no word contains it.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	fn render_suggestions(&self, area: Rect, buf: &mut Buffer)
+	{
+		let mut lines = Vec::new();
+		if self.overflow == Some(self.current_index())
+		{
+			lines.push(Line::styled(
+				self.text_with("solve.full", &[("bytes", &MAX_FRAGMENT_BYTES)]),
+				self.theme.failure
+			));
+		}
+		if self.advisor.is_impossible(self.current_index())
+		{
+			let fragment = self.cells[self.current_index()];
+			lines.push(Line::styled(
+				self.text_with("solve.impossible", &[("fragment", &fragment)]),
+				self.theme.warning
+			));
+		}
+		lines.extend(
+			self.advisor
+				.suggestions()
+				.iter()
+				.map(|fragment| Line::raw(fragment.as_str()))
+		);
+		Paragraph::new(lines)
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.title_top(
+						Line::from(self.text("solve.suggestions")).centered()
+					)
+			)
+			.style(self.theme.suggestion)
+			.wrap(Wrap { trim: true })
+			.render(area, buf);
+	}
+
+	/// Render the [resuming](ExecutionState::Resuming) UI.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	fn render_resuming(&self, area: Rect, buf: &mut Buffer)
+	{
+		// Split the screen into two parts: the puzzle and the solution.
+		let outer = self.split_outer_screen(area);
+		// The puzzle comprises a 4×5 grid of cells.
+		let board = self.split_board(outer[0]);
+		// Render the board.
+		self.render_board(
+			outer[0],
+			buf,
+			Some(Span::styled(self.text("resume.prompt"), self.theme.hint)),
+			Some(Span::styled(self.text("resume.resume"), self.theme.action))
+		);
+		// Render all of the cells.
+		self.render_cells(board, buf, |_, cell| {
+			let block = Block::new()
+				.border_type(BorderType::Rounded)
+				.borders(Borders::ALL)
+				.border_style(self.theme.border);
+			let cell = Paragraph::new(cell.as_str())
+				.block(block)
+				.alignment(Alignment::Left)
+				.style(Style::default())
+				.wrap(Wrap { trim: true });
+			cell
+		});
+		// Render the empty solution.
+		self.render_solution_list(
+			outer[1],
+			buf,
+			None,
+			Some(None),
+			None::<&str>,
+			None,
+			None
+		);
+	}
+
+	/// Render the [splash screen](Self::is_splash_visible), which reports the
+	/// progress of loading the initial dictionary.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	fn render_splash(&self, area: Rect, buf: &mut Buffer)
+	{
+		let Some(ref loader) = self.loader else { return };
+		let progress = loader.progress();
+		let (read, total) = (progress.bytes_read(), progress.total_bytes());
+		let ratio = match total
+		{
+			0 => 0.0,
+			total => (read as f64 / total as f64).min(1.0)
+		};
+		let mib = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+		let width = 50.min(area.width);
+		let height = 7.min(area.height);
+		let popup = Rect {
+			x: area.x + (area.width - width) / 2,
+			y: area.y + (area.height - height) / 2,
+			width,
+			height
+		};
+		let mut block = Block::default()
+			.borders(Borders::ALL)
+			.border_type(BorderType::Rounded)
+			.border_style(self.theme.border)
+			.title_top(Line::from(self.text("menu.title")).centered())
+			.padding(Padding::horizontal(1));
+		if let Some(keys) = self.keymap.label(Action::Exit)
+		{
+			block = block.title_bottom(
+				Line::from(Span::styled(
+					format!("{} – {}", keys, self.text("common.cancel")),
+					self.theme.exit
+				))
+				.centered()
+			);
+		}
+		let inner = block.inner(popup);
+		block.render(popup, buf);
+		let [title, path, gauge, words] = Layout::vertical([
+			Constraint::Length(1),
+			Constraint::Length(1),
+			Constraint::Length(1),
+			Constraint::Length(1)
+		])
+		.flex(Flex::Center)
+		.areas(inner);
+		let loading = format!(
+			"{} {}",
+			loader.spinner(),
+			self.text_with("splash.loading", &[("name", &loader.name())])
+		);
+		Paragraph::new(loading).style(self.theme.text).render(title, buf);
+		Paragraph::new(loader.directory().display().to_string())
+			.style(self.theme.hint)
+			.render(path, buf);
+		Gauge::default()
+			.gauge_style(self.theme.success)
+			.ratio(ratio)
+			.label(format!("{:.1} / {:.1} MiB", mib(read), mib(total)))
+			.render(gauge, buf);
+		let inserted = self.text_with(
+			"splash.inserted",
+			&[("count", &progress.words_inserted())]
+		);
+		Paragraph::new(inserted).style(self.theme.text).render(words, buf);
+	}
+
+	/// Render the dictionary picker of the [settings](Screen::Settings) UI
+	/// as a popup, marking the current dictionary.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `picker` - The picker.
+	fn render_picker(&self, area: Rect, buf: &mut Buffer, picker: &Picker)
+	{
+		let current = self.dictionary_name.as_deref();
+		let items = picker.names
+			.iter()
+			.map(|name| {
+				let mark = match Some(name.as_str()) == current
+				{
+					true => "✓",
+					false => " "
+				};
+				Text::from(format!("{} {}", mark, name))
+			})
+			.collect::<Vec<_>>();
+		let hints = self.hints(&[
+			(&[Action::MoveUp, Action::MoveDown], self.text("common.move")),
+			(&[Action::Solve], self.text("picker.load"))
+		]);
+		let title = self.text("picker.title");
+		let width = items.iter()
+			.map(Text::width)
+			.chain([hints.chars().count(), title.chars().count()])
+			.max()
+			.unwrap_or(0) as u16 + 6;
+		let height = items.len().max(1) as u16 + 2;
+		let popup = Rect {
+			x: area.x + area.width.saturating_sub(width) / 2,
+			y: area.y + area.height.saturating_sub(height) / 2,
+			width: width.min(area.width),
+			height: height.min(area.height)
+		};
+		Clear.render(popup, buf);
+		let block = Block::default()
+			.borders(Borders::ALL)
+			.border_type(BorderType::Rounded)
+			.border_style(self.theme.border)
+			.title_top(Line::from(title).centered())
+			.title_bottom(
+				Line::from(Span::styled(hints, self.theme.hint)).centered()
+			)
+			.padding(Padding::horizontal(1));
+		if items.is_empty()
+		{
+			Paragraph::new(self.text("picker.empty"))
+				.style(self.theme.hint)
+				.block(block)
+				.render(popup, buf);
+			return
+		}
+		let list = List::new(items)
+			.block(block)
+			.style(self.theme.text)
+			.highlight_style(self.theme.selection)
+			.highlight_symbol(self.highlight_symbol());
+		let mut list_state =
+			ListState::default().with_selected(Some(picker.selected));
+		StatefulWidget::render(&list, popup, buf, &mut list_state);
+	}
+
+	/// Render the [bulk-entry](BulkEntry) modal, centered over the board.
+	/// Invalid fragments, and any fragments beyond the 20th, are styled as
+	/// failures while the user types, and the number of fragments is shown,
+	/// so that mistakes are visible before the fragments are assigned.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `bulk_entry` - The bulk-entry modal.
+	fn render_bulk_entry(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		bulk_entry: &BulkEntry
+	)
+	{
+		// Split the text into alternating runs of separators and fragments,
+		// so that the text reads exactly as it was typed.
+		let mut spans = Vec::new();
+		let mut count = 0;
+		let mut rest = bulk_entry.text.as_str();
+		while !rest.is_empty()
+		{
+			let is_separator =
+				BulkEntry::is_separator(rest.chars().next().unwrap());
+			let end = rest
+				.find(|c| BulkEntry::is_separator(c) != is_separator)
+				.unwrap_or(rest.len());
+			let (run, tail) = rest.split_at(end);
+			let style = if is_separator
+			{
+				self.theme.text
+			}
+			else
+			{
+				count += 1;
+				match count <= 20 && BulkEntry::is_valid(run)
+				{
+					true => self.theme.text,
+					false => self.theme.failure
+				}
+			};
+			spans.push(Span::styled(run, style));
+			rest = tail;
+		}
+		spans.push(Span::styled("▏", self.theme.text));
+		let status = match bulk_entry.error
+		{
+			Some(ref e) => Span::styled(e.as_str(), self.theme.failure),
+			None => Span::styled(
+				self.text_with("bulk.count", &[("count", &count)]),
+				match count
+				{
+					20 => self.theme.success,
+					_ => self.theme.hint
+				}
+			)
+		};
+		let width = area.width.min(64);
+		let height = area.height.min(9);
+		let popup = Rect {
+			x: area.x + (area.width - width) / 2,
+			y: area.y + (area.height - height) / 2,
+			width,
+			height
+		};
+		Clear.render(popup, buf);
+		let block = Block::default()
+			.borders(Borders::ALL)
+			.border_type(BorderType::Rounded)
+			.border_style(self.theme.border)
+			.title_top(Line::from(self.text("bulk.title")).centered())
+			.title_bottom(
+				Line::from(Span::styled(
+					self.text("bulk.hints"),
+					self.theme.hint
+				))
+				.centered()
+			)
+			.padding(Padding::horizontal(1));
+		let inner = block.inner(popup);
+		block.render(popup, buf);
+		let [text_area, status_area] = Layout::vertical([
+			Constraint::Min(0),
+			Constraint::Length(1)
+		])
+		.areas(inner);
+		Paragraph::new(Line::from(spans))
+			.wrap(Wrap { trim: false })
+			.render(text_area, buf);
+		Paragraph::new(Line::from(status)).render(status_area, buf);
+	}
+
+	/// Render the exit confirmation modal, centered over the board.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	fn render_exit_prompt(&self, area: Rect, buf: &mut Buffer)
+	{
+		let width = area.width.min(40);
+		let height = area.height.min(5);
+		let popup = Rect {
+			x: area.x + (area.width - width) / 2,
+			y: area.y + (area.height - height) / 2,
+			width,
+			height
+		};
+		Clear.render(popup, buf);
+		let block = Block::default()
+			.borders(Borders::ALL)
+			.border_type(BorderType::Rounded)
+			.border_style(self.theme.border)
+			.title_top(Line::from(self.text("exit.title")).centered())
+			.title_bottom(
+				Line::from(vec![
+					Span::styled(self.text("exit.exit"), self.theme.action),
+					Span::styled(self.text("exit.keep"), self.theme.hint)
+				])
+				.centered()
+			)
+			.padding(Padding::horizontal(1));
+		Paragraph::new(Line::from(Span::styled(
+			self.text("exit.prompt"),
+			self.theme.text
+		)))
+			.alignment(Alignment::Center)
+			.wrap(Wrap { trim: false })
+			.block(block)
+			.render(popup, buf);
+	}
+
+	/// Render the [load failure](LoadFailure) dialog, if it is open, centered
+	/// over the current screen. While the user is browsing, the dictionaries
+	/// of the directory are listed instead.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	fn render_load_failure(&self, area: Rect, buf: &mut Buffer)
+	{
+		let Some(ref failure) = self.load_failure else { return };
+		if let Some(ref picker) = failure.picker
+		{
+			return self.render_picker(area, buf, picker)
+		}
+		let dismiss = match self.is_splash_visible
+		{
+			true => self.text("failure.quit"),
+			false => self.text("failure.dismiss")
+		};
+		let mut hints = vec![
+			Span::styled(self.text("failure.retry"), self.theme.action),
+			Span::styled(self.text("failure.browse"), self.theme.hint)
+		];
+		if let Some(keys) = self.keymap.label(Action::Exit)
+		{
+			hints.push(Span::styled(
+				format!("  {} – {}", keys, dismiss),
+				self.theme.exit
+			));
+		}
+		let width = area.width.min(60);
+		let height = area.height.min(8);
+		let popup = Rect {
+			x: area.x + (area.width - width) / 2,
+			y: area.y + (area.height - height) / 2,
+			width,
+			height
+		};
+		Clear.render(popup, buf);
+		let block = Block::default()
+			.borders(Borders::ALL)
+			.border_type(BorderType::Rounded)
+			.border_style(self.theme.border)
+			.title_top(Line::from(self.text("failure.title")).centered())
+			.title_bottom(Line::from(hints).centered())
+			.padding(Padding::horizontal(1));
+		Paragraph::new(Line::from(Span::styled(
+			failure.message.as_str(),
+			self.theme.failure
+		)))
+			.wrap(Wrap { trim: true })
+			.block(block)
+			.render(popup, buf);
+	}
+
+	/// Render the [explain](ExplainPrompt) modal, centered over the board.
+	/// Once a word has been explained, the explanation is shown beneath the
+	/// prompt, followed by the closest words that the solver can find.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `prompt` - The explain modal.
+	fn render_explain(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		prompt: &ExplainPrompt
+	)
+	{
+		let mut lines = vec![Line::from(vec![
+			Span::styled(self.text("explain.word"), self.theme.hint),
+			Span::styled(prompt.text.as_str(), self.theme.text),
+			Span::styled("▏", self.theme.text)
+		])];
+		if let Some((ref word, ref explanation, ref closest)) = prompt.result
+		{
+			let style = match explanation
+			{
+				Explanation::Formable { .. } => self.theme.success,
+				_ => self.theme.failure
+			};
+			lines.push(Line::default());
+			lines.push(Line::from(Span::styled(
+				format!("{}: {}", word, explanation),
+				style
+			)));
+			if !closest.is_empty()
+			{
+				lines.push(Line::default());
+				lines.push(Line::from(Span::styled(
+					self.text_with(
+						"explain.closest",
+						&[("words", &closest.join(", "))]
+					),
+					self.theme.text
+				)));
+			}
+		}
+		let width = area.width.min(64);
+		let height = area.height.min(10);
+		let popup = Rect {
+			x: area.x + (area.width - width) / 2,
+			y: area.y + (area.height - height) / 2,
+			width,
+			height
+		};
+		Clear.render(popup, buf);
+		let block = Block::default()
+			.borders(Borders::ALL)
+			.border_type(BorderType::Rounded)
+			.border_style(self.theme.border)
+			.title_top(Line::from(self.text("explain.title")).centered())
+			.title_bottom(
+				Line::from(Span::styled(
+					self.text("explain.hints"),
+					self.theme.hint
+				))
+				.centered()
+			)
+			.padding(Padding::horizontal(1));
+		Paragraph::new(lines)
+			.wrap(Wrap { trim: false })
+			.block(block)
+			.render(popup, buf);
+	}
+
+	/// Render the [solving](ExecutionState::Solving) UI.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `solver` - The solver.
+	fn render_solving(&self, area: Rect, buf: &mut Buffer, solver: &Solver)
+	{
+		// Split the screen into two parts: the puzzle and the solution.
+		let outer = self.split_outer_screen(area);
+		// The puzzle comprises a 4×5 grid of cells.
+		let board = self.split_board(outer[0]);
+		// Render the board.
+		self.render_board(outer[0], buf, None::<&str>, None::<&str>);
+		// Render all of the cells.
+		self.render_cells(board, buf, |_, cell| {
+			let block = Block::new()
+				.border_type(BorderType::Rounded)
+				.borders(Borders::ALL)
+				.border_style(self.theme.border);
+			let cell = Paragraph::new(cell.as_str())
+				.block(block)
+				.alignment(Alignment::Left)
+				.style(Style::default())
+				.wrap(Wrap { trim: true });
+			cell
+		});
+		// Render the solution, summarizing the progress of the solver.
+		self.render_solution_list(
+			outer[1],
+			buf,
+			Some(solver),
+			None,
+			Some(self.progress_summary(solver)),
+			Some(self.theme.text),
+			None
+		);
+	}
+
+	/// Render a [highlighting](ExecutionState::Highlighting) UI.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `solver` - The solver.
+	/// * `path` - The fragment path of the solution to highlight.
+	fn render_highlighting(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		solver: &Solver,
+		path: &FragmentPath
+	)
+	{
+		// Split the screen into two parts: the puzzle and the solution.
+		let outer = self.split_outer_screen(area);
+		// The puzzle comprises a 4×5 grid of cells.
+		let board = self.split_board(outer[0]);
+		self.render_board(outer[0], buf, None::<&str>, None::<&str>);
+		// Build all of the cells.
+		self.render_cells(board, buf, |index, cell| {
+			self.path_cell(index, cell, path)
+		});
+		// Render the solution. Colorize the quartiles. Highlight the word that
+		// corresponds to the argument fragment path, which need not be last,
+		// since the quartiles come first, but only while following.
+		let word = solver.word(path);
+		let highlight = self.grouped_solution(solver).0
+			.iter()
+			.position(|w| *w == word)
+			.filter(|_| self.is_following);
+		self.render_solution_list(
+			outer[1],
+			buf,
+			Some(solver),
+			Some(highlight),
+			Some(self.progress_summary(solver)),
+			Some(self.theme.text),
+			Some(self.theme.discovery)
+		);
+	}
+
+	/// Build a cell of the board, highlighting it if it participates in the
+	/// specified fragment path. A participating cell is labeled with its
+	/// position in the path.
+	///
+	/// # Arguments
+	///
+	/// * `index` - The index of the cell.
+	/// * `cell` - The content of the cell.
+	/// * `path` - The fragment path to highlight.
+	///
+	/// # Returns
+	///
+	/// The cell.
+	fn path_cell<'a>(
+		&self,
+		index: usize,
+		cell: &'a Fragment,
+		path: &FragmentPath
+	) -> Paragraph<'a>
+	{
+		let position = path
+			.iter()
+			.position(|i| matches!(i, Some(x) if x == index));
+		let border_style = match position
+		{
+			Some(_) => self.theme.path_border,
+			None => self.theme.border
+		};
+		let block = Block::new()
+			.border_type(BorderType::Rounded)
+			.borders(Borders::ALL)
+			.border_style(border_style);
+		match position
+		{
+			Some(position) =>
+			{
+				let label = format!("{} {}", position + 1, cell.as_str());
+				Paragraph::new(label)
+					.block(block)
+					.alignment(Alignment::Left)
+					.style(self.theme.path)
+					.wrap(Wrap { trim: true })
+			},
+			None =>
+			{
+				Paragraph::new(cell.as_str())
+					.block(block)
+					.alignment(Alignment::Left)
+					.style(Style::default())
+					.wrap(Wrap { trim: true })
+			}
+		}
+	}
+
+	/// Render the [finished](ExecutionState::Finished) UI.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `solver` - The solver.
+	/// * `is_solved` - Whether the puzzle has been solved.
+	/// * `is_truncated` - Whether the solver ran out of time.
+	/// * `highlight` - The index of the solution to highlight, if any.
+	#[allow(clippy::too_many_arguments)]
+	fn render_finished(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		solver: &Solver,
+		is_solved: bool,
+		is_truncated: bool,
+		highlight: Option<usize>,
+		completeness: Option<&Completeness>
+	)
+	{
+		// Split the screen into two parts: the puzzle and the solution.
+		let outer = self.split_outer_screen(area);
+		// The puzzle comprises a 4×5 grid of cells.
+		let board = self.split_board(outer[0]);
+		// If the selected word can be formed in more than one way, then offer
+		// to cycle through its placements.
+		let placement = self.selected_placement();
+		let placements = placement
+			.filter(|(_, _, count)| *count > 1)
+			.and_then(|(_, index, count)| {
+				self.keymap.label(Action::NextPlacement).map(|keys| {
+					Span::styled(
+						self.text_with(
+							"finished.placement",
+							&[
+								("index", &(index + 1)),
+								("count", &count),
+								("keys", &keys)
+							]
+						),
+						self.theme.action
+					)
+				})
+			});
+		self.render_board(
+			outer[0],
+			buf,
+			Some(
+				if is_solved
+				{
+					Span::styled(self.text("common.solved"), self.theme.success)
+				}
+				else if is_truncated
+				{
+					Span::styled(
+						self.text_with(
+							"finished.truncated",
+							&[(
+								"seconds",
+								&self.solve_budget
+									.unwrap_or_default()
+									.as_secs()
+							)]
+						),
+						self.theme.failure
+					)
+				}
+				else
+				{
+					Span::styled(
+						self.text("finished.no-solution"),
+						self.theme.failure
+					)
+				}
+			),
+			placements
+		);
+		// Render all of the cells. Highlight the placement of the selected
+		// word, if any.
+		let path = placement.map(|(path, ..)| path).unwrap_or_default();
+		self.render_cells(board.clone(), buf, |index, cell| {
+			self.path_cell(index, cell, &path)
+		});
+		// Explain an incomplete solution beneath the board.
+		if let Some(completeness) = completeness
+		{
+			self.render_completeness(board[6], buf, completeness);
+		}
+		// Render the solution. Colorize the quartiles. Highlight the selected
+		// word.
+		self.render_solution_list(
+			outer[1],
+			buf,
+			Some(solver),
+			Some(highlight),
+			Some(Span::styled(
+				self.hints(&[
+					(
+						&[Action::MoveUp, Action::MoveDown],
+						self.text("common.move")
+					),
+					(&[Action::Sort], &self.sort_label())
+				]),
+				self.theme.hint
+			)),
+			Some(self.theme.text),
+			Some(self.theme.selection)
+		);
+	}
+
+	/// Render a [replay](Replay) of the finished solve: the board highlights
+	/// the fragment path of the current word, and the solution lists the words
+	/// discovered so far, in order of discovery, ending with the current word.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `solver` - The solver.
+	/// * `replay` - The replay.
+	fn render_replay(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		solver: &Solver,
+		replay: &Replay
+	)
+	{
+		// Split the screen into two parts: the puzzle and the solution.
+		let outer = self.split_outer_screen(area);
+		// The puzzle comprises a 4×5 grid of cells.
+		let board = self.split_board(outer[0]);
+		let hints = self.hints(&[
+			(
+				&[Action::MoveLeft, Action::MoveRight],
+				&self.text_with("replay.speed", &[("speed", &replay.speed())])
+			),
+			(&[Action::Replay], self.text("replay.stop"))
+		]);
+		self.render_board(
+			outer[0],
+			buf,
+			Some(Span::styled(hints, self.theme.hint)),
+			Some(Span::styled(
+				format!("▶ {}/{}", replay.step() + 1, replay.len()),
+				self.theme.action
+			))
+		);
+		self.render_cells(board, buf, |index, cell| {
+			self.path_cell(index, cell, replay.path())
+		});
+		// List the words discovered so far, following the current word.
+		let items = replay.discovered()
+			.iter()
+			.map(|path| {
+				let word = solver.word(path);
+				self.solution_item(solver, &word, path, path.is_full(), false)
+			})
+			.collect::<Vec<_>>();
+		let len = items.len();
+		let height = outer[1].height.saturating_sub(2) as usize;
+		let list = List::new(items)
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.title_top(
+						Line::from(self.text("solution.title")).centered()
+					)
+			)
+			.style(self.theme.text)
+			.highlight_style(self.theme.discovery)
+			.highlight_symbol(self.highlight_symbol());
+		let mut list_state = ListState::default()
+			.with_offset(len.saturating_sub(height))
+			.with_selected(Some(len - 1));
+		StatefulWidget::render(&list, outer[1], buf, &mut list_state);
+	}
+
+	/// Render the analysis of an incomplete solution: how many quartiles were
+	/// found, which fragments went unused, and which full fragment paths came
+	/// closest to forming another quartile.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `completeness` - The analysis.
+	fn render_completeness(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		completeness: &Completeness
+	)
+	{
+		let mut lines = vec![Line::from(Span::styled(
+			self.text_with(
+				"completeness.quartiles",
+				&[("count", &completeness.quartiles)]
+			),
+			self.theme.failure
+		))];
+		if !completeness.unused.is_empty()
+		{
+			lines.push(Line::from(vec![
+				Span::styled(self.text("completeness.unused"), self.theme.hint),
+				Span::styled(completeness.unused.join(" "), self.theme.text)
+			]));
+		}
+		for near_miss in &completeness.near_misses
+		{
+			lines.push(Line::from(vec![
+				Span::styled(
+					self.text("completeness.near-miss"),
+					self.theme.hint
+				),
+				Span::styled(near_miss.to_string(), self.theme.text)
+			]));
+		}
+		Paragraph::new(lines)
+			.alignment(Alignment::Center)
+			.wrap(Wrap { trim: true })
+			.render(area, buf);
+	}
+
+	/// Split the specified area into two parts: the puzzle and the solution.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area to split. This will be the complete screen
+	///   available to the application.
+	///
+	/// # Returns
+	///
+	/// The split areas.
+	fn split_outer_screen(&self, area: Rect) -> Rc<[Rect]>
+	{
+		Layout::default()
+			.direction(Direction::Horizontal)
+			.margin(1)
+			.constraints([Constraint::Percentage(100), Constraint::Min(28)])
+			.split(area)
+	}
+
+	/// Split the specified area into rows: two margins and 5 central
+	/// rows.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area to split.
+	///
+	/// # Returns
+	///
+	/// The split areas.
+	fn split_board(&self, area: Rect) -> Rc<[Rect]>
+	{
+		Layout::default()
+			.direction(Direction::Vertical)
+			.margin(3)
+			.constraints([
+				Constraint::Ratio(1, 3),
+				Constraint::Length(3),
+				Constraint::Length(3),
+				Constraint::Length(3),
+				Constraint::Length(3),
+				Constraint::Length(3),
+				Constraint::Ratio(1, 3)
+			])
+			.split(area)
+	}
+
+	/// Render the board, with optional titles at the bottom center and top
+	/// right.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `bottom_center` - The title to render at the bottom center.
+	/// * `top_right` - The title to render at the top right.
+	fn render_board<'a>(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		bottom_center: Option<impl Into<Line<'a>>>,
+		top_right: Option<impl Into<Line<'a>>>
+	)
+	{
+		let mut block = Block::default()
+			.borders(Borders::ALL)
+			.border_style(self.theme.border)
+			.title_top(Line::from(self.text("solve.puzzle")).centered());
+		if let Some(keys) = self.keymap.label(Action::Exit)
+		{
+			let label = match self.has_menu
+			{
+				true => self.text("common.back"),
+				false => self.text("common.exit")
+			};
+			block = block.title_top(
+				Line::from(Span::styled(
+					format!("{} – {}", keys, label),
+					self.theme.exit
+				))
+				.left_aligned()
+			);
+		}
+		if let Some(keys) = self.keymap.label(Action::Help)
+		{
+			block = block.title_bottom(
+				Line::from(Span::styled(
+					format!("{} – {}", keys, self.text("common.help")),
+					self.theme.hint
+				))
+				.left_aligned()
+			);
+		}
+		if let Some(title) = bottom_center
+		{
+			block = block.title_bottom(title.into().centered());
+		}
+		if let Some(title) = top_right
+		{
+			block = block.title_top(title.into().right_aligned());
+		}
+		block.render(area, buf);
+	}
+
+	/// Render the status bar, which summarizes the current [screen](Screen)
+	/// and [execution state](ExecutionState), the progress of the solver or
+	/// the player, and the dictionary.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area, a single row.
+	/// * `buf` - The target buffer.
+	fn render_status_bar(&self, area: Rect, buf: &mut Buffer)
+	{
+		let mut fields = Vec::new();
+		if matches!(
+			self.state,
+			ExecutionState::Swapping | ExecutionState::Exiting { .. }
+		)
+		{
+			return
+		}
+		let (label, solver) = match self.screen
+		{
+			Screen::Menu { .. } => (self.text("status.menu"), None),
+			Screen::Play { .. } => (self.text("status.playing"), None),
+			Screen::History { .. } => (self.text("status.history"), None),
+			Screen::Statistics { .. } => (self.text("status.statistics"), None),
+			Screen::Settings { .. } => (self.text("status.settings"), None),
+			#[cfg(feature = "trace-ui")]
+			Screen::Trace { .. } => (self.text("status.tracing"), None),
+			Screen::Solve => match self.state
+			{
+				ExecutionState::Solving { ref solver }
+					| ExecutionState::Highlighting { ref solver, .. } =>
+				{
+					(self.text("status.solving"), Some(solver))
+				},
+				ExecutionState::Finished {
+					ref solver,
+					is_truncated: true,
+					..
+				} =>
+				{
+					(self.text("status.truncated"), Some(solver))
+				},
+				ExecutionState::Finished { ref solver, .. } =>
+				{
+					(self.text("status.finished"), Some(solver))
+				},
+				ExecutionState::Resuming { .. } =>
+				{
+					(self.text("status.resuming"), None)
+				},
+				_ => (self.text("status.populating"), None)
+			}
+		};
+		fields.push(label.to_string());
+		if let Some(solver) = solver
+		{
+			let elapsed = match self.solve_duration
+			{
+				Some(duration) => duration,
+				None => self.solve_started
+					.map(|started| self.elapsed_since(started))
+					.unwrap_or_default()
+			};
+			let (words, quartiles) = self.solution_statistics(solver);
+			fields.push(format!("{:.1}s", elapsed.as_secs_f64()));
+			fields.push(self.text_with("status.words", &[("count", &words)]));
+			fields.push(
+				self.text_with("status.quartiles", &[("count", &quartiles)])
+			);
+		}
+		if let Screen::Play { ref game, .. } = self.screen
+		{
+			if game.players() > 1
+			{
+				fields.push(self.text_with(
+					"status.turn",
+					&[("player", &(game.turn() + 1))]
+				));
+			}
+			fields.push(
+				self.text_with("status.points", &[("count", &game.score())])
+			);
+			fields.push(
+				self.text_with(
+					"status.words",
+					&[("count", &game.found().len())]
+				)
+			);
+			fields.push(self.text_with(
+				"status.quartiles",
+				&[("count", &game.quartiles_found())]
+			));
+		}
+		if self.card.is_some()
+		{
+			fields.push(self.text("status.card").to_string());
+		}
+		fields.push(self.text_with(
+			"status.dictionary",
+			&[
+				(
+					"name",
+					&self.dictionary_name
+						.as_deref()
+						.unwrap_or(self.text("status.unnamed"))
+				),
+				("count", &self.dictionary.len())
+			]
+		));
+		if let Some(ref loader) = self.loader
+		{
+			fields.push(format!(
+				"{} {}",
+				loader.spinner(),
+				self.text_with("status.loading", &[("name", &loader.name())])
+			));
+		}
+		Paragraph::new(format!(" {}", fields.join(" │ ")))
+			.style(self.theme.status)
+			.render(area, buf);
+	}
+
+	/// Render the announcement line, which shows the most recent
+	/// [announcement](Self::announce), if any.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area, a single row, or none if the TUI isn't
+	///   accessible.
+	/// * `buf` - The target buffer.
+	fn render_announcement(&self, area: Rect, buf: &mut Buffer)
+	{
+		if let Some(ref announcement) = self.announcement
+		{
+			Paragraph::new(format!(" {}", announcement))
+				.style(self.theme.text)
+				.render(area, buf);
+		}
+	}
+
+	/// Answer the symbol that marks the selected item of a list, in addition
+	/// to its style, if the TUI is accessible.
+	///
+	/// # Returns
+	///
+	/// The symbol, which is empty unless the TUI is accessible.
+	fn highlight_symbol(&self) -> &'static str
+	{
+		match self.is_accessible
+		{
+			true => "▶ ",
+			false => ""
+		}
+	}
+
+	/// Answer the marker of the specified cell of the board, if the TUI is
+	/// accessible: `▶` for the cursor, and `✗` for a fragment that occurs in
+	/// no word of the dictionary, both of which are otherwise conveyed only
+	/// by color.
+	///
+	/// # Arguments
+	///
+	/// * `index` - The index of the cell.
+	///
+	/// # Returns
+	///
+	/// The marker, if any.
+	fn cell_marker(&self, index: usize) -> Option<&'static str>
+	{
+		if !self.is_accessible
+		{
+			return None
+		}
+		let is_cursor = index == self.current_index();
+		match (is_cursor, self.advisor.is_impossible(index))
+		{
+			(false, false) => None,
+			(true, false) => Some("▶"),
+			(false, true) => Some("✗"),
+			(true, true) => Some("▶ ✗")
+		}
+	}
+
+	/// Summarize the progress of the solver, e.g., `Found 17 words (3
+	/// quartiles) — ~42% searched, ~3s left`, so that the user knows whether
+	/// to wait or abort. The time remaining is extrapolated from the time
+	/// elapsed and the [progress](Solver::progress) estimate, so it is omitted
+	/// until the estimate becomes meaningful.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The solver.
+	///
+	/// # Returns
+	///
+	/// The summary.
+	fn progress_summary(&self, solver: &Solver) -> String
+	{
+		let (words, quartiles) = self.solution_statistics(solver);
+		let progress = solver.progress();
+		let key = match (words == 1, quartiles == 1)
+		{
+			(true, true) => "progress.one-one",
+			(true, false) => "progress.one-other",
+			(false, true) => "progress.other-one",
+			(false, false) => "progress.other-other"
+		};
+		let mut summary = self.text_with(
+			key,
+			&[
+				("words", &words),
+				("quartiles", &quartiles),
+				("percentage", &format!("{:.0}", progress * 100.0))
+			]
+		);
+		let elapsed = self.solve_started
+			.map(|started| self.elapsed_since(started))
+			.unwrap_or_default();
+		if (0.01..1.0).contains(&progress)
+		{
+			let remaining = elapsed.as_secs_f64() * (1.0 - progress) / progress;
+			summary.push_str(&self.text_with(
+				"progress.left",
+				&[("seconds", &format!("{:.0}", remaining.ceil()))]
+			));
+		}
+		summary
+	}
+
+	/// Count the distinct words and quartiles found by the solver so far.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The solver.
+	///
+	/// # Returns
+	///
+	/// The number of distinct words and the number of distinct quartiles.
+	fn solution_statistics(&self, solver: &Solver) -> (usize, usize)
+	{
+		let mut words = HashSet::new();
+		let mut quartiles = HashSet::new();
+		for path in solver.solution_paths().iter()
+		{
+			let word = solver.word(path);
+			if path.is_full()
+			{
+				quartiles.insert(word);
+			}
+			words.insert(word);
+		}
+		(words.len(), quartiles.len())
+	}
+
+	/// Describe the current state in words, for an
+	/// [announcement](Self::announce): the screen, and, on the
+	/// [solve screen](Screen::Solve), the focused cell, the highlighted word,
+	/// or the result of the solve.
+	///
+	/// # Returns
+	///
+	/// The description, or `None` if there is nothing to describe.
+	fn describe(&self) -> Option<String>
+	{
+		if self.is_splash_visible
+		{
+			return Some(self.text("announce.loading").to_string())
+		}
+		if self.load_failure.is_some()
+		{
+			return Some(self.text("announce.failure").to_string())
+		}
+		if self.is_help_visible
+		{
+			return Some(self.text("announce.help").to_string())
+		}
+		let description = match self.screen
+		{
+			Screen::Menu { selected } => MENU.get(selected)
+				.map(|(label, description)| {
+					self.text_with(
+						"announce.menu",
+						&[
+							("label", &self.text(label)),
+							("description", &self.text(description))
+						]
+					)
+				})?,
+			Screen::Play { .. } => self.text("announce.play").to_string(),
+			Screen::History { .. } =>
+			{
+				self.text("announce.history").to_string()
+			},
+			Screen::Statistics { .. } =>
+			{
+				self.text("announce.statistics").to_string()
+			},
+			Screen::Settings { selected, .. } => Setting::ALL.get(selected)
+				.map(|&setting| {
+					self.text_with(
+						"announce.settings",
+						&[
+							("setting", &setting.label(self.messages)),
+							("value", &self.setting_value(setting))
+						]
+					)
+				})?,
+			#[cfg(feature = "trace-ui")]
+			Screen::Trace { .. } => self.text("announce.trace").to_string(),
+			Screen::Solve if self.is_exit_pending =>
+			{
+				self.text("announce.exit").to_string()
+			},
+			Screen::Solve => self.describe_solve()?
+		};
+		Some(description)
+	}
+
+	/// Describe the [solve screen](Screen::Solve) in words, according to the
+	/// current [execution state](ExecutionState).
+	///
+	/// # Returns
+	///
+	/// The description, or `None` if there is nothing to describe.
+	fn describe_solve(&self) -> Option<String>
+	{
+		let description = match self.state
+		{
+			ExecutionState::Swapping | ExecutionState::Exiting { .. } =>
+			{
+				return None
+			},
+			ExecutionState::Populating =>
+			{
+				let index = self.current_index();
+				let cell = &self.cells[index];
+				let mut description = match cell.is_empty()
+				{
+					true => self.text_with(
+						"announce.empty-cell",
+						&[
+							("row", &(index / 4 + 1)),
+							("column", &(index % 4 + 1))
+						]
+					),
+					false => self.text_with(
+						"announce.cell",
+						&[
+							("row", &(index / 4 + 1)),
+							("column", &(index % 4 + 1)),
+							("fragment", cell)
+						]
+					)
+				};
+				if self.advisor.is_impossible(index)
+				{
+					description.push_str(&self.text_with(
+						"announce.impossible",
+						&[("fragment", cell)]
+					));
+				}
+				let filled = self.cells.iter()
+					.filter(|cell| !cell.is_empty())
+					.count();
+				description.push_str(
+					&self.text_with("announce.filled", &[("count", &filled)])
+				);
+				description
+			},
+			ExecutionState::Resuming { .. } =>
+			{
+				self.text("announce.resume").to_string()
+			},
+			ExecutionState::Solving { .. } =>
+			{
+				self.text("announce.solving").to_string()
+			},
+			ExecutionState::Highlighting { ref solver, ref path, .. } =>
+			{
+				self.text_with(
+					"announce.found",
+					&[("word", &self.describe_word(solver, path))]
+				)
+			},
+			ExecutionState::Finished {
+				ref solver,
+				replay: Some(ref replay),
+				..
+			} => self.text_with(
+				"announce.replay",
+				&[
+					("index", &(replay.step() + 1)),
+					("count", &replay.len()),
+					("word", &self.describe_word(solver, replay.path()))
+				]
+			),
+			ExecutionState::Finished {
+				ref solver,
+				highlight: Some(index),
+				..
+			} =>
+			{
+				let (path, placement, placements) =
+					self.selected_placement()?;
+				let mut description = self.text_with(
+					"announce.word",
+					&[
+						("index", &(index + 1)),
+						("count", &self.grouped_solution(solver).0.len()),
+						("word", &self.describe_word(solver, &path))
+					]
+				);
+				if placements > 1
+				{
+					description.push_str(&self.text_with(
+						"announce.placement",
+						&[("index", &(placement + 1)), ("count", &placements)]
+					));
+				}
+				description
+			},
+			ExecutionState::Finished {
+				ref solver,
+				is_solved,
+				is_truncated,
+				..
+			} =>
+			{
+				let (words, quartiles) = self.solution_statistics(solver);
+				let key = match (is_solved, is_truncated)
+				{
+					(true, _) => "announce.solved",
+					(false, false) => "announce.unsolved",
+					(false, true) => "announce.truncated"
+				};
+				self.text_with(
+					key,
+					&[("quartiles", &quartiles), ("words", &words)]
+				)
+			}
+		};
+		Some(description)
+	}
+
+	/// Describe the word formed by the specified fragment path in words: the
+	/// word, whether it is a quartile, its points and [rarity](Rarity),
+	/// whether it is a favorite, and then each of its fragments, in order,
+	/// with its position on the board.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The solver.
+	/// * `path` - The fragment path.
+	///
+	/// # Returns
+	///
+	/// The description, e.g., `truthfully, a quartile, 8 points, common:
+	/// “tru” at row 1, column 4; …`.
+	fn describe_word(&self, solver: &Solver, path: &FragmentPath) -> String
+	{
+		let word = solver.word(path);
+		let rarity = match self.inventory().rarity(word.as_str())
+		{
+			Rarity::Common => self.text("announce.common"),
+			Rarity::Uncommon => self.text("announce.uncommon"),
+			Rarity::Rare => self.text("announce.rare")
+		};
+		let key = match path.is_full()
+		{
+			true => "announce.quartile",
+			false => "announce.bonus-word"
+		};
+		let mut description = self.text_with(
+			key,
+			&[
+				("word", &word),
+				("score", &solver.score(path)),
+				("rarity", &rarity)
+			]
+		);
+		if self.favorites.contains(word.as_str())
+		{
+			description.push_str(self.text("announce.favorite"));
+		}
+		let fragments = path.iter()
+			.flatten()
+			.map(|index| {
+				self.text_with(
+					"announce.fragment",
+					&[
+						("fragment", &self.cells[index]),
+						("row", &(index / 4 + 1)),
+						("column", &(index % 4 + 1))
+					]
+				)
+			})
+			.collect::<Vec<_>>();
+		self.text_with(
+			"announce.fragments",
+			&[
+				("description", &description),
+				("fragments", &fragments.join("; "))
+			]
+		)
+	}
+
+	/// Render the help overlay, a popup that lists the keybindings of the
+	/// current [screen](Screen) and [execution state](ExecutionState).
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area. The popup is centered within it.
+	/// * `buf` - The target buffer.
+	fn render_help(&self, area: Rect, buf: &mut Buffer)
+	{
+		let entries = self.help_entries();
+		let key_width = entries.iter()
+			.map(|(keys, _)| keys.chars().count())
+			.max()
+			.unwrap_or(0);
+		let lines = entries.iter()
+			.map(|(keys, description)| Line::from(vec![
+				Span::styled(
+					format!("{:>width$}", keys, width = key_width),
+					self.theme.action
+				),
+				Span::styled(format!("  {}", description), self.theme.text)
+			]))
+			.collect::<Vec<_>>();
+		let width = lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 4;
+		let height = lines.len() as u16 + 2;
+		let popup = Rect {
+			x: area.x + area.width.saturating_sub(width) / 2,
+			y: area.y + area.height.saturating_sub(height) / 2,
+			width: width.min(area.width),
+			height: height.min(area.height)
+		};
+		let block = Block::default()
+			.borders(Borders::ALL)
+			.border_type(BorderType::Rounded)
+			.border_style(self.theme.border)
+			.title_top(Line::from(self.text("help.title")).centered())
+			.title_bottom(
+				Line::from(Span::styled(
+					self.text("help.any-key"),
+					self.theme.hint
+				))
+				.centered()
+			);
+		Clear.render(popup, buf);
+		Paragraph::new(lines)
+			.block(block.padding(Padding::horizontal(1)))
+			.render(popup, buf);
+	}
+
+	/// Compute the entries of the help overlay for the current
+	/// [screen](Screen) and [execution state](ExecutionState). Actions without
+	/// any bound keys are omitted.
+	///
+	/// # Returns
+	///
+	/// The entries, as pairs of key labels and descriptions.
+	fn help_entries(&self) -> Vec<(String, &'static str)>
+	{
+		let mut entries = Vec::new();
+		let actions: &[Action] = match self.screen
+		{
+			Screen::Menu { .. } =>
+			{
+				self.push_help_entries(&mut entries, &[
+					(Action::Solve, self.text("help.open-screen")),
+					(Action::Exit, self.text("help.exit"))
+				]);
+				&[
+					Action::MoveUp,
+					Action::MoveDown,
+					Action::History,
+					Action::Statistics,
+					Action::Help
+				]
+			},
+			Screen::Play { .. } =>
+			{
+				if self.keymap.mode() == Mode::Insert
+				{
+					entries.push(
+						("A-Z".to_string(), self.text("help.type-letter"))
+					);
+				}
+				self.push_help_entries(&mut entries, &[
+					(Action::Solve, self.text("help.submit-guess")),
+					(Action::DeleteChar, self.text("help.delete-letter")),
+					(Action::ClearCell, self.text("help.clear-guess")),
+					(
+						Action::Reveal,
+						self.text("help.give-up-and-reveal-words")
+					),
+					(Action::Exit, self.text("help.stop-playing"))
+				]);
+				&[
+					Action::Shuffle,
+					Action::ExportCard,
+					Action::InsertMode,
+					Action::NormalMode,
+					Action::Help
+				]
+			},
+			Screen::History { .. } =>
+			{
+				self.push_help_entries(&mut entries, &[
+					(Action::Solve, self.text("help.reopen-puzzle")),
+					(Action::Exit, self.text("help.close-history"))
+				]);
+				&[
+					Action::MoveUp,
+					Action::MoveDown,
+					Action::PageUp,
+					Action::PageDown,
+					Action::Help
+				]
+			},
+			Screen::Statistics { .. } =>
+			{
+				self.push_help_entries(&mut entries, &[
+					(Action::Exit, self.text("help.close-statistics"))
+				]);
+				&[Action::Help]
+			},
+			#[cfg(feature = "trace-ui")]
+			Screen::Trace { .. } =>
+			{
+				entries.push(
+					("A-Z".to_string(), self.text("help.filter-by-word"))
+				);
+				self.push_help_entries(&mut entries, &[
+					(
+						Action::DeleteChar,
+						self.text("help.delete-filter-letter")
+					),
+					(Action::Exit, self.text("help.close-trace"))
+				]);
+				&[
+					Action::MoveUp,
+					Action::MoveDown,
+					Action::PageUp,
+					Action::PageDown,
+					Action::Help
+				]
+			},
+			Screen::Settings { editing: Some(_), .. } =>
+			{
+				entries.push(("↵".to_string(), self.text("help.apply-change")));
+				entries.push(
+					("⎋".to_string(), self.text("help.cancel-change"))
+				);
+				&[]
+			},
+			Screen::Settings { picker: Some(_), .. } =>
+			{
+				self.push_help_entries(&mut entries, &[
+					(Action::Solve, self.text("help.load-dictionary")),
+					(Action::Exit, self.text("help.close-picker"))
+				]);
+				&[Action::MoveUp, Action::MoveDown, Action::Help]
+			},
+			Screen::Settings { .. } =>
+			{
+				self.push_help_entries(&mut entries, &[
+					(Action::MoveLeft, self.text("help.previous-value")),
+					(Action::MoveRight, self.text("help.next-value")),
+					(Action::Solve, self.text("help.edit-pick-or-next-value")),
+					(Action::Exit, self.text("help.close-settings"))
+				]);
+				&[Action::MoveUp, Action::MoveDown, Action::Help]
+			},
+			Screen::Solve if self.bulk_entry.is_some() =>
+			{
+				entries.push(
+					("↵".to_string(), self.text("help.assign-fragments"))
+				);
+				entries.push(("⎋".to_string(), self.text("help.cancel")));
+				&[]
+			},
+			Screen::Solve if self.is_exit_pending =>
+			{
+				entries.push(("Y ↵".to_string(), self.text("help.exit")));
+				entries.push(("N".to_string(), self.text("help.keep-editing")));
+				self.push_help_entries(
+					&mut entries,
+					&[(Action::Exit, self.text("help.exit"))]
+				);
+				&[]
+			},
+			Screen::Solve if self.explain.is_some() =>
+			{
+				entries.push(("↵".to_string(), self.text("help.explain-word")));
+				entries.push(("⎋".to_string(), self.text("help.close")));
+				&[]
+			},
+			Screen::Solve => match self.state
+			{
+				ExecutionState::Populating =>
+				{
+					if self.keymap.mode() == Mode::Insert
+					{
+						entries.push(
+							("A-Z".to_string(), self.text("help.type-letter"))
+						);
+					}
+					&[
+						Action::MoveUp,
+						Action::MoveDown,
+						Action::MoveLeft,
+						Action::MoveRight,
+						Action::NextCell,
+						Action::PreviousCell,
+						Action::SwapUp,
+						Action::SwapDown,
+						Action::SwapLeft,
+						Action::SwapRight,
+						Action::DeleteChar,
+						Action::ClearCell,
+						Action::ClearAll,
+						Action::BulkEntry,
+						Action::Explain,
+						Action::InsertMode,
+						Action::NormalMode,
+						Action::Solve,
+						Action::History,
+						Action::Statistics,
+						Action::Exit,
+						Action::Help
+					]
+				},
+				ExecutionState::Resuming { .. } =>
+				{
+					entries.push(
+						("Y ↵".to_string(), self.text("help.resume-solve"))
+					);
+					entries.push(
+						("N".to_string(), self.text("help.discard-solve"))
+					);
+					entries.push(("⎋".to_string(), self.text("help.exit")));
+					&[Action::Help]
+				},
+				ExecutionState::Solving { .. }
+					| ExecutionState::Highlighting { .. } =>
+				{
+					&[
+						Action::PageUp,
+						Action::PageDown,
+						#[cfg(feature = "trace-ui")]
+						Action::Trace,
+						Action::Exit,
+						Action::Help
+					]
+				},
+				ExecutionState::Finished { .. } =>
+				{
+					&[
+						Action::MoveUp,
+						Action::MoveDown,
+						Action::PageUp,
+						Action::PageDown,
+						Action::NextPlacement,
+						Action::MarkBogus,
+						Action::MarkFavorite,
+						Action::Sort,
+						Action::ExportCard,
+						Action::Replay,
+						Action::Explain,
+						#[cfg(feature = "trace-ui")]
+						Action::Trace,
+						Action::Exit,
+						Action::Help
+					]
+				},
+				ExecutionState::Swapping | ExecutionState::Exiting { .. } =>
+				{
+					&[]
+				}
+			}
+		};
+		for action in actions
+		{
+			let labels = self.keymap.labels(*action);
+			if !labels.is_empty()
+			{
+				entries.push(
+					(labels.join(" "), action.description(self.messages))
+				);
+			}
+		}
+		entries
+	}
+
+	/// Append help entries for the specified actions, with descriptions
+	/// that are specific to the current screen. Actions without any bound
+	/// keys are omitted.
+	///
+	/// # Arguments
+	///
+	/// * `entries` - The entries of the help overlay.
+	/// * `actions` - The actions, each with a description.
+	fn push_help_entries(
+		&self,
+		entries: &mut Vec<(String, &'static str)>,
+		actions: &[(Action, &'static str)]
+	)
+	{
+		for (action, description) in actions
+		{
+			let labels = self.keymap.labels(*action);
+			if !labels.is_empty()
+			{
+				entries.push((labels.join(" "), *description));
+			}
+		}
+	}
+
+	/// Describe the keys bound to the specified groups of actions, for use in
+	/// hints. The keys of the actions in a group are concatenated, e.g., `←↑↓→`
+	/// for the four movement actions. Groups without any bound keys are
+	/// omitted.
+	///
+	/// # Arguments
+	///
+	/// * `groups` - The groups of actions, each with a description.
+	///
+	/// # Returns
+	///
+	/// The hint text.
+	fn hints(&self, groups: &[(&[Action], &str)]) -> String
+	{
+		groups
+			.iter()
+			.filter_map(|(actions, description)| {
+				let keys = actions
+					.iter()
+					.filter_map(|action| self.keymap.label(*action))
+					.collect::<String>();
+				(!keys.is_empty()).then(|| format!("{} - {}", keys, description))
+			})
+			.collect::<Vec<_>>()
+			.join(" ")
+	}
+
+	/// Answer the [message](Messages) with the specified key, in the selected
+	/// language.
+	///
+	/// # Arguments
+	///
+	/// * `key` - The key of the message, e.g., `menu.title`.
+	///
+	/// # Returns
+	///
+	/// The message.
+	fn text(&self, key: &'static str) -> &'static str
+	{
+		self.messages.text(key)
+	}
+
+	/// Answer the [message](Messages) with the specified key, in the selected
+	/// language, with each of its placeholders filled in.
+	///
+	/// # Arguments
+	///
+	/// * `key` - The key of the message.
+	/// * `args` - The arguments, as pairs of a placeholder name and its value.
+	///
+	/// # Returns
+	///
+	/// The formatted message.
+	fn text_with(&self, key: &str, args: &[(&str, &dyn Display)]) -> String
+	{
+		self.messages.text_with(key, args)
+	}
+
+	/// Describe the current [sort order](SortOrder), for use in hints.
+	///
+	/// # Returns
+	///
+	/// The description, e.g., `by score`.
+	fn sort_label(&self) -> String
+	{
+		self.text_with(
+			"finished.sort",
+			&[("order", &self.text(self.sort_order.key()))]
+		)
+	}
+
+	/// Describe the keys available while
+	/// [populating](ExecutionState::Populating) the puzzle, for use in hints.
+	/// The hints depend on the mode of the keymap.
+	///
+	/// # Returns
+	///
+	/// The hint text.
+	fn populating_hints(&self) -> String
+	{
+		let movement = [
+			Action::MoveLeft,
+			Action::MoveUp,
+			Action::MoveDown,
+			Action::MoveRight
+		];
+		match self.keymap.mode()
+		{
+			Mode::Insert =>
+			{
+				let hints = self.hints(&[
+					(&movement, self.text("common.move")),
+					(&[Action::NextCell], self.text("populate.next")),
+					(&[Action::PreviousCell], self.text("populate.previous"))
+				]);
+				let mode = match self.keymap.is_modal()
+				{
+					true => self.text("populate.insert-mode"),
+					false => ""
+				};
+				format!(
+					"{}{} {} {}",
+					mode,
+					hints,
+					self.text("populate.edit"),
+					self.hints(&[
+						(&[Action::DeleteChar], self.text("common.delete")),
+						(&[Action::ClearCell], self.text("common.clear")),
+						(&[Action::NormalMode], self.text("populate.normal"))
+					])
+				)
+			},
+			Mode::Normal => self.hints(&[
+				(&movement, self.text("common.move")),
+				(&[Action::NextCell], self.text("populate.next")),
+				(&[Action::PreviousCell], self.text("populate.previous")),
+				(&[Action::InsertMode], self.text("populate.insert")),
+				(&[Action::DeleteChar], self.text("common.delete")),
+				(&[Action::ClearCell], self.text("common.clear")),
+				(&[Action::ClearAll], self.text("populate.clear-all"))
+			])
+		}
+	}
+
+	/// Render the cells of the board, each at its own position.
+	///
+	/// # Arguments
+	///
+	/// * `board` - The board area, as a margin, followed by 5 rows, followed by
+	///   another margin.
+	/// * `buf` - The target buffer.
+	/// * `cell_builder` - A function that builds a cell from an index and a
+	///   string.
+	fn render_cells(
+		&self,
+		board: Rc<[Rect]>,
+		buf: &mut Buffer,
+		cell_builder: impl Fn(usize, &Fragment) -> Paragraph<'_>
+	)
+	{
+		let identity = std::array::from_fn(|index| index);
+		self.render_cells_arranged(board, buf, &identity, cell_builder);
+	}
+
+	/// Render the cells of the board in the specified arrangement, which
+	/// affects only where the cells appear, not their indices.
+	///
+	/// # Arguments
+	///
+	/// * `board` - The board area, as a margin, followed by 5 rows, followed by
+	///   another margin.
+	/// * `buf` - The target buffer.
+	/// * `arrangement` - The index of the cell to show at each position of the
+	///   grid, in row-major order.
+	/// * `cell_builder` - A function that builds a cell from an index and a
+	///   string.
+	fn render_cells_arranged(
+		&self,
+		board: Rc<[Rect]>,
+		buf: &mut Buffer,
+		arrangement: &[usize; 20],
+		cell_builder: impl Fn(usize, &Fragment) -> Paragraph<'_>
+	)
+	{
+		let cells = arrangement
+			.iter()
+			.map(|&index| cell_builder(index, &self.cells[index]))
+			.collect::<Vec<_>>();
+		// Lay out the cells in a 4×5 grid, remembering where each cell lands
+		// for the sake of the mouse.
+		let mut areas = [Rect::default(); 20];
+		cells
+			.chunks_exact(4)
+			.enumerate()
+			.for_each(|(index, chunk)| {
+				let row = Layout::default()
+					.direction(Direction::Horizontal)
+					.constraints([
+						Constraint::Min(10),
+						Constraint::Min(10),
+						Constraint::Min(10),
+						Constraint::Min(10)
+					])
+					.split(board[index + 1]);
+				for (column, cell) in chunk.iter().enumerate()
+				{
+					areas[arrangement[index * 4 + column]] = row[column];
+					cell.render(row[column], buf);
+					#[cfg(feature = "graphics")]
+					self.renderer.render_tile(row[column], buf);
+				}
+			});
+		self.cell_areas.set(areas);
+	}
+
+	/// Answer the distinct words of the solution in the order of the solution
+	/// list: the quartiles, then the bonus words, each in the current
+	/// [sort order](SortOrder). The index of the
+	/// [selected](Self::selected_word) word is an index into these words.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The solver.
+	///
+	/// # Returns
+	///
+	/// The words, and how many of the leading words are quartiles.
+	fn grouped_solution(&self, solver: &Solver) -> (Vec<Word>, usize)
+	{
+		let quartiles = solver
+			.solution_paths()
+			.iter()
+			.filter(|path| path.is_full())
+			.map(|path| solver.word(path))
+			.collect::<HashSet<_>>();
+		let (mut words, mut bonus_words): (Vec<_>, Vec<_>) = solver
+			.solution_deduped()
+			.into_iter()
+			.partition(|word| quartiles.contains(word));
+		self.sort_words(solver, &mut words);
+		self.sort_words(solver, &mut bonus_words);
+		let count = words.len();
+		words.extend(bonus_words);
+		(words, count)
+	}
+
+	/// Sort the specified words of the solution in the current
+	/// [sort order](SortOrder). The sort is stable, so ties remain in order of
+	/// discovery.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The solver.
+	/// * `words` - The words, in order of discovery.
+	fn sort_words(&self, solver: &Solver, words: &mut [Word])
+	{
+		match self.sort_order
+		{
+			SortOrder::Discovery => {},
+			SortOrder::Alphabetical =>
+			{
+				words.sort_by(|a, b| a.as_str().cmp(b.as_str()))
+			},
+			SortOrder::Length =>
+			{
+				words.sort_by_key(|word| Reverse(word.chars().count()))
+			},
+			SortOrder::Score =>
+			{
+				let mut scores = HashMap::new();
+				for path in solver.solution_paths()
+				{
+					scores.entry(solver.word(&path))
+						.or_insert_with(|| solver.score(&path));
+				}
+				words.sort_by_key(|word| Reverse(scores[word]))
+			}
+		}
+	}
+
+	/// Advance to the next [sort order](SortOrder) of the solution list,
+	/// keeping the selected word, if any, selected.
+	fn cycle_sort_order(&mut self)
+	{
+		let selected = self.selected_word();
+		self.sort_order = self.sort_order.next();
+		info!("Sorting solution by {}", self.sort_order);
+		let index = match (selected, &self.state)
+		{
+			(Some(word), ExecutionState::Finished { solver, .. }) =>
+			{
+				self.grouped_solution(solver).0
+					.iter()
+					.position(|w| w.as_str() == word)
+			},
+			_ => None
+		};
+		if let ExecutionState::Finished {
+			highlight: Some(ref mut highlight),
+			..
+		} = self.state
+		{
+			*highlight = index.unwrap_or(*highlight);
+		}
+	}
+
+	/// Answer the row of the solution list that shows the specified word,
+	/// allowing for the section headers.
+	///
+	/// # Arguments
+	///
+	/// * `index` - The index of the word, as for
+	///   [`grouped_solution`](Self::grouped_solution).
+	/// * `quartiles` - The number of quartiles.
+	///
+	/// # Returns
+	///
+	/// The row.
+	fn solution_row(index: usize, quartiles: usize) -> usize
+	{
+		match index < quartiles
+		{
+			true => index + 1,
+			false => index + 2
+		}
+	}
+
+	/// Construct a solution list from the solver, providing colorization based
+	/// on the status of individual words. Specifically, quartiles and shorter
+	/// words are styled according to the [theme](Theme). The list has two
+	/// sections, the quartiles and the bonus words, each headed by its title
+	/// and count. Each word appears only once, even if the solver formed it
+	/// from several fragment paths, and carries the
+	/// [badges](Self::solution_item) of the path that formed it first.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The solver.
+	///
+	/// # Returns
+	///
+	/// A list of styled text items.
+	fn solution_list(&self, solver: &Solver) -> Vec<Text<'_>>
+	{
+		let (words, quartiles) = self.grouped_solution(solver);
+		let cover = match self.state
+		{
+			ExecutionState::Finished { cover: Some(ref cover), .. }
+				if quartiles > 5 =>
+			{
+				cover.iter().map(|path| solver.word(path)).collect()
+			},
+			_ => HashSet::new()
+		};
+		let header = |title, count| {
+			Text::styled(format!("{} ({})", title, count), self.theme.hint)
+		};
+		let mut paths = HashMap::new();
+		for path in solver.solution_paths()
+		{
+			paths.entry(solver.word(&path)).or_insert(path);
+		}
+		let mut items = words
+			.iter()
+			.enumerate()
+			.map(|(index, word)| {
+				self.solution_item(
+					solver,
+					word,
+					&paths[word],
+					index < quartiles,
+					cover.contains(word)
+				)
+			})
+			.collect::<Vec<_>>();
+		let bonus_words = words.len() - quartiles;
+		items.insert(
+			quartiles,
+			header(self.text("solution.bonus-words"), bonus_words)
+		);
+		items.insert(0, header(self.text("solution.quartiles"), quartiles));
+		items
+	}
+
+	/// Construct an item of the [solution list](Self::solution_list): the
+	/// word, styled according to whether it is a quartile, followed by its
+	/// badges. A quartile is flagged with a star, which is filled if the
+	/// finished solver found more than 5 quartiles and the quartile belongs
+	/// to the [cover](Solver::cover), and a
+	/// [favorite](Action::MarkFavorite) word is flagged with a heart. Then
+	/// come the points that the word scores, the number of its fragments, and
+	/// its [rarity](Rarity): `○` for common, `◐` for uncommon, and `●` for
+	/// rare.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The solver.
+	/// * `word` - The word.
+	/// * `path` - The fragment path that formed the word.
+	/// * `is_quartile` - Whether the word is a quartile.
+	/// * `is_cover` - Whether the word belongs to the flagged cover.
+	///
+	/// # Returns
+	///
+	/// The styled text item.
+	fn solution_item(
+		&self,
+		solver: &Solver,
+		word: &Word,
+		path: &FragmentPath,
+		is_quartile: bool,
+		is_cover: bool
+	) -> Text<'static>
+	{
+		let style = match is_quartile
+		{
+			false => self.theme.word,
+			true => self.theme.quartile
+		};
+		let star = match (is_quartile, is_cover)
+		{
+			(_, true) => " ★",
+			(true, false) => " ☆",
+			(false, false) => ""
+		};
+		let heart = match self.favorites.contains(word.as_str())
+		{
+			true => " ♥",
+			false => ""
+		};
+		let rarity = match self.inventory().rarity(word.as_str())
+		{
+			Rarity::Common => "○",
+			Rarity::Uncommon => "◐",
+			Rarity::Rare => "●"
+		};
+		Text::from(Line::from(vec![
+			Span::styled(format!("{}{}{}", word, star, heart), style),
+			Span::styled(
+				format!(" {}p ×{} {}", solver.score(path), path.len(), rarity),
+				self.theme.hint
+			)
+		]))
+	}
+
+	/// Render the solution list.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `solver` - The solver, which is only used in some application states.
+	/// * `highlight` - The optional index of the highlighted word, as for
+	///   [`grouped_solution`](Self::grouped_solution). If `None`, use the last
+	///   item, but only while [following](Self::is_following) the most
+	///   recently discovered word. If the inner `Option` is `None`, do not
+	///   highlight any item.
+	/// * `bottom_center` - The optional title to render at the bottom center.
+	/// * `style` - The optional base style to apply to the list.
+	/// * `highlight_style` - The optional style to apply to the highlighted
+	///   item.
+	#[allow(clippy::too_many_arguments)]
+	fn render_solution_list<'a>(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		solver: Option<&Solver>,
+		highlight: Option<Option<usize>>,
+		bottom_center: Option<impl Into<Line<'a>>>,
+		style: Option<Style>,
+		highlight_style: Option<Style>
+	)
+	{
+		let (items, quartiles) = match solver
+		{
+			None => (Vec::new(), 0),
+			Some(solver) =>
+			{
+				let (_, quartiles) = self.grouped_solution(solver);
+				(self.solution_list(solver), quartiles)
+			}
+		};
+		let len = items.len();
+		let list = List::new(items);
+		let list = list.block({
+			let block = Block::default()
+				.borders(Borders::ALL)
+				.title_top(Line::from(self.text("solution.title")).centered());
+			match bottom_center
+			{
+				None => block,
+				Some(title) => block.title_bottom(title.into().centered())
+			}
+		});
+		let list = match style
+		{
+			None => list,
+			Some(style) => list.style(style)
+		};
+		let list = match highlight_style
+		{
+			None => list,
+			Some(highlight_style) => list.highlight_style(highlight_style)
+		};
+		let list = list.highlight_symbol(self.highlight_symbol());
+		// Scroll the list. Remember the height of the viewport, so that
+		// scrolling by a page knows how far to go.
+		let height = area.height.saturating_sub(2) as usize;
+		self.viewport_height.set(height);
+		let max_offset = len.saturating_sub(height);
+		let offset = match self.is_following
+		{
+			true => max_offset,
+			false => self.scroll_offset.min(max_offset)
+		};
+		let selected = match highlight
+		{
+			Some(highlight) => highlight
+				.map(|index| Self::solution_row(index, quartiles)),
+			None if self.is_following => len.checked_sub(1),
+			None => None
+		};
+		let mut list_state = ListState::default()
+			.with_offset(offset)
+			.with_selected(selected);
+		StatefulWidget::render(&list, area, buf, &mut list_state);
+		// Show a scrollbar whenever the list overflows the viewport.
+		if len > height
+		{
+			let mut scrollbar_state = ScrollbarState::new(max_offset)
+				.position(list_state.offset());
+			Scrollbar::new(ScrollbarOrientation::VerticalRight)
+				.begin_symbol(None)
+				.end_symbol(None)
+				.style(self.theme.border)
+				.render(
+					area.inner(Margin { vertical: 1, horizontal: 0 }),
+					buf,
+					&mut scrollbar_state
+				);
+		}
+	}
+
+	/// Run any background tasks, such as the solver or the highlighter,
+	/// install any dictionary that has finished loading, and then
+	/// [announce](Self::announce) any change of state.
+	fn process_systems(&mut self)
+	{
+		self.finish_loading();
+		match self.state
+		{
+			ExecutionState::Swapping => unreachable!(),
+			ExecutionState::Populating if !self.is_splash_visible =>
+			{
+				let index = self.current_index();
+				self.advisor.update(
+					&self.dictionary,
+					&self.cells,
+					index,
+					self.clock.now()
+				);
+			},
+			ExecutionState::Populating =>
+			{},
+			ExecutionState::Resuming { .. } =>
+			{},
+			ExecutionState::Solving { .. } => self.run_solver(),
+			ExecutionState::Highlighting { .. } => self.run_highlighter(),
+			ExecutionState::Finished { replay: Some(_), .. } =>
+			{
+				self.run_replay()
+			},
+			ExecutionState::Finished { .. } =>
+			{},
+			ExecutionState::Exiting { .. } =>
+			{}
+		}
+		self.announce();
+	}
+
+	/// Announce the current state, if the TUI is accessible and the
+	/// [description](Self::describe) of the state has changed since the
+	/// previous announcement. The announcement replaces its predecessor in
+	/// the announcement line, and is logged, so that a screen reader that
+	/// follows the log speaks every change, e.g., each word as the solver
+	/// discovers it.
+	fn announce(&mut self)
+	{
+		if !self.is_accessible
+		{
+			return
+		}
+		let Some(description) = self.describe()
+		else
+		{
+			return
+		};
+		if self.announcement.as_ref() != Some(&description)
+		{
+			info!("Announcement: {}", description);
+			self.announcement = Some(description);
+		}
+	}
+
+	/// Run the solver for a short while.
+	fn run_solver(&mut self)
+	{
+		// Take care to evacuate the application state in order to keep the
+		// borrow happy while juggling state ownership and mutable references.
+		let mut state = ExecutionState::Swapping;
+		swap(&mut self.state, &mut state);
+		if let ExecutionState::Solving { solver } = state
+		{
+			// Run the solver for only a short while, lest the application
+			// become unresponsive.
+			let (solver, path) = solver.step();
+			// A solver that runs out of time stops where it is, and its
+			// solution is truncated.
+			let is_truncated = !solver.is_finished()
+				&& self.solve_deadline
+					.is_some_and(|deadline| self.clock.now() >= deadline);
+			if solver.is_finished() || is_truncated
+			{
+				// The solver has stopped, so there is nothing left to resume.
+				self.clear_checkpoint();
+				self.solve_duration =
+					self.solve_started.map(|t| self.elapsed_since(t));
+				self.record_history(&solver);
+				let is_solved = solver.is_solved();
+				info!(
+					"Finished solve in {:?}: solved: {}, truncated: {}",
+					self.solve_duration.unwrap_or_default(),
+					is_solved,
+					is_truncated
+				);
+				// Analyze an incomplete solution only once, since finding
+				// the near misses repeats part of the search. Likewise, select
+				// the cover only once, since an unofficial puzzle may yield
+				// many quartiles. Don't analyze a truncated solution at all,
+				// since the search is exactly what ran out of time.
+				let completeness = match is_solved || is_truncated
+				{
+					true => None,
+					false => Some(solver.completeness(MAX_NEAR_MISSES))
+				};
+				let cover = solver.cover();
+				self.state = ExecutionState::Finished {
+					solver,
+					is_solved,
+					is_truncated,
+					highlight: None,
+					placement: 0,
+					completeness,
+					cover,
+					replay: None
+				};
+			}
+			else if let Some(path) = path.filter(|_| !self.is_motion_reduced)
+			{
+				// Highlight the most recently discovered solution.
+				self.save_checkpoint(&solver, false);
+				let until = self.clock.now()
+					+ Duration::from_millis(self.highlight_duration_µs);
+				self.state = ExecutionState::Highlighting {
+					solver,
+					until,
+					path
+				};
+			}
+			else
+			{
+				// Maintain the solving state.
+				self.save_checkpoint(&solver, false);
+				self.state = ExecutionState::Solving { solver };
+			}
+		}
+		else
+		{
+			unreachable!()
+		}
+	}
+
+	/// Write a checkpoint of the solver to the state file, if any. Unless
+	/// forced, write at most once per second, to keep the solver responsive.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The solver.
+	/// * `force` - Whether to ignore the rate limit.
+	fn save_checkpoint(&mut self, solver: &Solver, force: bool)
+	{
+		if let Some(ref path) = self.state_file
+		{
+			let elapsed = self.elapsed_since(self.last_checkpoint);
+			if force || elapsed >= Duration::from_secs(1)
+			{
+				if let Err(e) = state::save(path, &solver.checkpoint())
+				{
+					warn!("Failed to write state file: {}: {}", path.display(), e);
+				}
+				self.last_checkpoint = self.clock.now();
+			}
+		}
+	}
+
+	/// Remove the state file, if any.
+	fn clear_checkpoint(&self)
+	{
+		if let Some(ref path) = self.state_file
+		{
+			if let Err(e) = state::clear(path)
+			{
+				warn!("Failed to remove state file: {}: {}", path.display(), e);
+			}
+		}
+	}
+
+	/// Record the finished solve in the history file, if any. The provenance
+	/// of the board is recorded only if the user didn't edit the generated
+	/// board. The solve itself is [cached](CachedSolve), unless a word filter
+	/// is installed.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The finished solver.
+	fn record_history(&self, solver: &Solver)
+	{
+		if let Some(ref path) = self.history_file
+		{
+			let provenance = self.provenance
+				.filter(|provenance| provenance.fragments == self.cells);
+			let (words, quartiles) = self.solution_statistics(solver);
+			let mut seen = HashSet::new();
+			let score = solver
+				.solution_paths()
+				.iter()
+				.filter(|path| seen.insert(solver.word(path)))
+				.map(|path| solver.score(path))
+				.sum();
+			let entry = Entry {
+				date: provenance
+					.and_then(|provenance| provenance.daily)
+					.unwrap_or_else(Date::today),
+				daily: provenance.is_some_and(|p| p.daily.is_some()),
+				seed: provenance.map(|provenance| provenance.seed),
+				fragments: self.cells.iter().map(|f| f.to_string()).collect(),
+				words,
+				quartiles,
+				score,
+				duration_ms: self.solve_duration
+					.map(|duration| duration.as_millis() as u64),
+				completed: solver.is_solved(),
+				cache: match self.filter
+				{
+					Some(_) => None,
+					None => CachedSolve::of(solver)
+				}
+			};
+			if let Err(e) = crate::history::append(path, entry)
+			{
+				warn!("Failed to write history file: {}: {}", path.display(), e);
+			}
+		}
+	}
+
+	/// Start a [replay](Replay) of the finished solve, which highlights the
+	/// words of the solution one at a time, in order of discovery. If a
+	/// recording file was specified, the replay is recorded, replacing any
+	/// earlier recording.
+	fn start_replay(&mut self)
+	{
+		let now = self.clock.now();
+		let ExecutionState::Finished { ref solver, ref mut replay, .. } =
+			self.state
+		else
+		{
+			return
+		};
+		let mut seen = HashSet::new();
+		let paths = solver.solution_paths()
+			.into_iter()
+			.filter(|path| seen.insert(solver.word(path)))
+			.collect();
+		*replay = Replay::new(paths, now);
+		if replay.is_none()
+		{
+			return
+		}
+		info!("Replaying solve");
+		if let Some(ref path) = self.record_file
+		{
+			match Recorder::create(path)
+			{
+				Ok(recorder) => self.recorder = Some(recorder),
+				Err(e) =>
+				{
+					warn!("Failed to record replay: {}: {}", path.display(), e)
+				}
+			}
+		}
+	}
+
+	/// Stop the [replay](Replay) in progress, if any, and finish its
+	/// recording, if any.
+	fn stop_replay(&mut self)
+	{
+		if let ExecutionState::Finished { ref mut replay, .. } = self.state
+		{
+			*replay = None;
+		}
+		if let Some(recorder) = self.recorder.take()
+		{
+			if let Err(e) = recorder.finish()
+			{
+				warn!("Failed to record replay: {}", e);
+			}
+		}
+	}
+
+	/// Advance the [replay](Replay) in progress, stopping it after its last
+	/// step.
+	fn run_replay(&mut self)
+	{
+		let now = self.clock.now();
+		if let ExecutionState::Finished { replay: Some(ref mut replay), .. } =
+			self.state
+		{
+			if !replay.advance(now)
+			{
+				self.stop_replay();
+			}
+		}
+	}
+
+	/// Run the highlighter for a short while.
+	fn run_highlighter(&mut self)
+	{
+		// Take care to evacuate the application state in order to keep the
+		// borrow checker happy while juggling state ownership and mutable
+		// references.
+		let mut state = ExecutionState::Swapping;
+		swap(&mut self.state, &mut state);
+		if let ExecutionState::Highlighting {
+			solver,
+			until,
+			path
+		} = state
+		{
+			if self.clock.now() >= until
+			{
+				// Return to the solving state.
+				self.state = ExecutionState::Solving { solver };
+			}
+			else
+			{
+				// Maintain the highlighting.
+				self.state = ExecutionState::Highlighting {
+					solver,
+					until,
+					path
+				};
+			}
+		}
+		else
+		{
+			unreachable!()
+		}
+	}
+
+	/// Answer how much time has elapsed since the specified instant, by the
+	/// application's [clock](Clock).
+	///
+	/// # Arguments
+	///
+	/// * `instant` - The instant.
+	///
+	/// # Returns
+	///
+	/// The elapsed time, or zero if the instant is in the future.
+	fn elapsed_since(&self, instant: Instant) -> Duration
+	{
+		self.clock.now().saturating_duration_since(instant)
+	}
+
+	/// Answer the deadline of a solve that starts now, by the application's
+	/// [clock](Clock), according to the [solve budget](Self::solve_budget).
+	///
+	/// # Returns
+	///
+	/// The deadline, or `None` if there is no budget or if the budget is too
+	/// large to represent as a deadline.
+	fn deadline(&self) -> Option<Instant>
+	{
+		self.solve_budget
+			.and_then(|budget| self.clock.now().checked_add(budget))
+	}
+
+	/// Process every pending event, so that a burst of keys is handled at
+	/// once rather than one key per frame. Block only for the first event, and
+	/// not at all for the rest.
+	///
+	/// # Arguments
+	///
+	/// * `events` - The source of events.
+	/// * `timeout` - How long to wait for the first event.
+	///
+	/// # Returns
+	///
+	/// `true` if any event was processed, `false` otherwise.
+	///
+	/// # Errors
+	///
+	/// Any error that occurs while processing events.
+	fn process_events(
+		&mut self,
+		events: &mut dyn EventSource,
+		mut timeout: Duration
+	) -> io::Result<bool>
+	{
+		let mut any = false;
+		while self.is_running()
+		{
+			let Some(event) = events.next_event(timeout)?
+			else
+			{
+				break
+			};
+			debug_span!("handle_event").in_scope(|| self.process_event(event));
+			timeout = Duration::ZERO;
+			any = true;
+		}
+		Ok(any)
+	}
+
+	/// Check whether the application has background work pending, i.e.,
+	/// whether the solver or the highlighter is running.
+	///
+	/// # Returns
+	///
+	/// `true` if there is background work, `false` otherwise.
+	fn is_working(&self) -> bool
+	{
+		matches!(
+			self.state,
+			ExecutionState::Solving { .. } | ExecutionState::Highlighting { .. }
+		)
+	}
+
+	/// Check whether the application changes over time, even if the user
+	/// doesn't act, e.g., because a dictionary is loading behind a spinner,
+	/// the clock of a timed round is counting down, or the advisor is waiting
+	/// for the board to settle.
+	///
+	/// # Returns
+	///
+	/// `true` if the frame must be redrawn periodically, `false` otherwise.
+	fn is_animating(&self) -> bool
+	{
+		let is_advising = matches!(self.state, ExecutionState::Populating)
+			&& !self.is_splash_visible
+			&& self.advisor.is_pending();
+		let is_replaying = matches!(
+			self.state,
+			ExecutionState::Finished { replay: Some(_), .. }
+		);
+		self.loader.is_some() || is_advising || is_replaying || matches!(
+			self.screen,
+			Screen::Play { timer: Some(ref timer), .. } if !timer.is_over()
+		)
+	}
+
+	/// Process an event by dispatching it to the handler for its kind.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The event to process.
+	fn process_event(&mut self, event: Event)
+	{
+		match event
+		{
+			Event::Key(event) if event.kind == KeyEventKind::Press =>
+			{
+				self.process_key_event(event)
+			},
+			// The load failure dialog is modal.
+			Event::Paste(_) | Event::Mouse(_) if self.load_failure.is_some() =>
+			{},
+			Event::Paste(text) => self.process_paste(&text),
+			Event::Mouse(event) => self.process_mouse_event(event),
+			_ =>
+			{}
+		}
+	}
+
+	/// Process a key event by dispatching it to the handler for the current
+	/// [screen](Screen) and, on the solve screen, the current
+	/// [execution state](ExecutionState). While the help overlay is open, any
+	/// key closes it instead.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	fn process_key_event(&mut self, event: KeyEvent)
+	{
+		if self.is_help_visible
+		{
+			self.is_help_visible = false;
+			return
+		}
+		if self.load_failure.is_some()
+		{
+			return self.process_key_event_load_failure(event)
+		}
+		if self.is_splash_visible
+		{
+			return self.process_key_event_splash(event)
+		}
+		match self.screen
+		{
+			Screen::Menu { .. } => return self.process_key_event_menu(event),
+			Screen::Play { .. } => return self.process_key_event_play(event),
+			Screen::History { .. } =>
+			{
+				return self.process_key_event_history(event)
+			},
+			Screen::Statistics { .. } =>
+			{
+				return self.process_key_event_statistics(event)
+			},
+			Screen::Settings { .. } =>
+			{
+				return self.process_key_event_settings(event)
+			},
+			#[cfg(feature = "trace-ui")]
+			Screen::Trace { .. } => return self.process_key_event_trace(event),
+			Screen::Solve =>
+			{}
+		}
+		if self.is_exit_pending
+		{
+			return self.process_key_event_exit(event)
+		}
+		if self.explain.is_some()
+		{
+			return self.process_key_event_explain(event)
+		}
+		match self.state
+		{
+			ExecutionState::Swapping => unreachable!(),
+			ExecutionState::Populating =>
+			{
+				self.process_key_event_populating(event)
+			},
+			ExecutionState::Resuming { .. } =>
+			{
+				self.process_key_event_resuming(event)
+			},
+			ExecutionState::Solving { .. } =>
+			{
+				self.process_key_event_solving(event)
+			},
+			ExecutionState::Highlighting { .. } =>
+			{
+				self.process_key_event_highlighting(event)
+			},
+			ExecutionState::Finished { .. } =>
+			{
+				self.process_key_event_finished(event)
+			},
+			ExecutionState::Exiting { .. } =>
+			{}
+		}
+	}
+
+	/// Process a key event while [populating](ExecutionState::Populating) the
+	/// puzzle. The [keymap](Keymap) resolves the key event to an
+	/// [action](Action):
+	///
+	/// * [Exit](Action::Exit) - [Leave](Self::leave) the solve screen.
+	/// * [MoveUp](Action::MoveUp) - Move the cursor up.
+	/// * [MoveDown](Action::MoveDown) - Move the cursor down.
+	/// * [MoveLeft](Action::MoveLeft) - Move the cursor left.
+	/// * [MoveRight](Action::MoveRight) - Move the cursor right.
+	/// * [PreviousCell](Action::PreviousCell) - Move the cursor to the
+	///   previous cell.
+	/// * [NextCell](Action::NextCell) - Move the cursor to the next cell.
+	/// * [SwapUp](Action::SwapUp), [SwapDown](Action::SwapDown),
+	///   [SwapLeft](Action::SwapLeft), [SwapRight](Action::SwapRight) - Swap
+	///   the current cell with a neighbor, moving the cursor along with it.
+	/// * [DeleteChar](Action::DeleteChar) - Delete the last character of the
+	///   current cell.
+	/// * [ClearCell](Action::ClearCell) - Clear the current cell.
+	/// * [ClearAll](Action::ClearAll) - Clear all cells.
+	/// * [BulkEntry](Action::BulkEntry) - Open the
+	///   [bulk-entry](BulkEntry) modal.
+	/// * [Explain](Action::Explain) - Open the [explain](ExplainPrompt)
+	///   modal.
+	/// * [Solve](Action::Solve) - Solve the puzzle.
+	/// * [History](Action::History) - Browse the history.
+	/// * [Statistics](Action::Statistics) - Show the statistics.
+	/// * [InsertMode](Action::InsertMode) - Enter insert mode.
+	/// * [NormalMode](Action::NormalMode) - Leave insert mode.
+	/// * [Help](Action::Help) - Show the keybindings.
+	///
+	/// In insert mode, an unbound alphabetic key appends the corresponding
+	/// character to the current cell.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	fn process_key_event_populating(&mut self, event: KeyEvent)
+	{
+		if self.bulk_entry.is_some()
+		{
+			return self.process_key_event_bulk_entry(event)
+		}
+		self.overflow = None;
+		match self.keymap.resolve(event)
+		{
+			Resolution::Action(action) => match action
+			{
+				Action::Exit => self.leave(),
+				Action::MoveUp => self.move_cursor(0, -1),
+				Action::MoveDown => self.move_cursor(0, 1),
+				Action::MoveLeft => self.move_cursor(-1, 0),
+				Action::MoveRight => self.move_cursor(1, 0),
+				Action::PreviousCell => self.move_index(-1),
+				Action::NextCell => self.move_index(1),
+				Action::SwapUp => self.swap_cursor(0, -1),
+				Action::SwapDown => self.swap_cursor(0, 1),
+				Action::SwapLeft => self.swap_cursor(-1, 0),
+				Action::SwapRight => self.swap_cursor(1, 0),
+				Action::DeleteChar => self.delete(),
+				Action::ClearCell => self.clear(),
+				Action::ClearAll => self.clear_all(),
+				Action::BulkEntry =>
+				{
+					self.bulk_entry = Some(BulkEntry::default())
+				},
+				Action::Explain => self.explain = Some(ExplainPrompt::default()),
+				Action::Solve => self.start_solver(),
+				Action::InsertMode => self.keymap.set_mode(Mode::Insert),
+				Action::NormalMode => self.keymap.set_mode(Mode::Normal),
+				Action::Help => self.is_help_visible = true,
+				Action::History => self.open_history(),
+				Action::Statistics => self.open_statistics(),
+				// The solution list is empty while populating the puzzle, and
+				// only a game can be shuffled.
+				Action::PageUp
+				| Action::PageDown
+				| Action::NextPlacement
+				| Action::MarkBogus
+				| Action::MarkFavorite
+				| Action::Sort
+				| Action::ExportCard
+				| Action::Replay
+				| Action::Shuffle
+				| Action::Reveal =>
+				{},
+				// There is nothing to trace until the solver has started.
+				#[cfg(feature = "trace-ui")]
+				Action::Trace =>
+				{}
+			},
+			Resolution::Unbound(KeyEvent {
+				code: KeyCode::Char(c),
+				..
+			}) if (c.is_alphabetic() || is_combining_mark(c))
+				&& self.keymap.mode() == Mode::Insert =>
+			{
+				self.append(c)
+			},
+			Resolution::Pending | Resolution::Unbound(_) =>
+			{}
+		}
+	}
+
+	/// Process a key event while the [bulk-entry](BulkEntry) modal is open.
+	/// Keys edit the text directly, bypassing the [keymap](Keymap):
+	///
+	/// * Enter - Assign the fragments to the cells, if they are valid, and
+	///   close the modal. Otherwise, explain why they are not.
+	/// * Escape - Close the modal, leaving the board unchanged.
+	/// * Backspace - Delete the last character.
+	///
+	/// Any other character is appended to the text.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	fn process_key_event_bulk_entry(&mut self, event: KeyEvent)
+	{
+		let Some(ref mut bulk_entry) = self.bulk_entry else { return };
+		match event.code
+		{
+			KeyCode::Enter => match bulk_entry.parse(self.messages)
+			{
+				Ok(fragments) =>
+				{
+					self.cells = fragments;
+					self.bulk_entry = None;
+				},
+				Err(e) => bulk_entry.error = Some(e)
+			},
+			KeyCode::Esc => self.bulk_entry = None,
+			KeyCode::Backspace =>
+			{
+				bulk_entry.text.pop();
+				bulk_entry.error = None;
+			},
+			KeyCode::Char(c)
+				if !c.is_control()
+					&& !event.modifiers.contains(KeyModifiers::CONTROL) =>
+			{
+				bulk_entry.text.push(c);
+				bulk_entry.error = None;
+			},
+			_ =>
+			{}
+		}
+	}
+
+	/// Process a key event while the [explain](ExplainPrompt) modal is open.
+	/// Keys edit the word directly, bypassing the [keymap](Keymap):
+	///
+	/// * Enter - Explain the word against the fragments of the board.
+	/// * Escape - Close the modal.
+	/// * Backspace - Delete the last character.
+	///
+	/// Any other character is appended to the word.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	fn process_key_event_explain(&mut self, event: KeyEvent)
+	{
+		let Some(ref mut prompt) = self.explain else { return };
+		match event.code
+		{
+			KeyCode::Enter =>
+			{
+				let word = normalize_word(&prompt.text);
+				if !word.is_empty()
+				{
+					let fragments = self
+						.cells
+						.iter()
+						.filter(|cell| !cell.is_empty())
+						.collect::<Vec<_>>();
+					let explanation =
+						explain(&self.dictionary, &fragments, &word);
+					let closest = closest_words(
+						&self.dictionary,
+						&fragments,
+						&word,
+						MAX_ALTERNATIVES
+					);
+					prompt.result = Some((word, explanation, closest));
+				}
+			},
+			KeyCode::Esc => self.explain = None,
+			KeyCode::Backspace =>
+			{
+				prompt.text.pop();
+			},
+			KeyCode::Char(c)
+				if !c.is_control()
+					&& !event.modifiers.contains(KeyModifiers::CONTROL) =>
+			{
+				prompt.text.push(c);
+			},
+			_ =>
+			{}
+		}
+	}
+
+	/// Process a mouse event. While populating the puzzle, pressing the left
+	/// button over a cell selects it, and dragging it onto another cell swaps
+	/// the two, so that a misplaced fragment can be moved without retyping
+	/// it. The cursor follows the drag, in order to show where the fragment
+	/// will land.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The mouse event to process.
+	fn process_mouse_event(&mut self, event: MouseEvent)
+	{
+		let is_populating = !self.is_splash_visible
+			&& !self.is_help_visible
+			&& self.bulk_entry.is_none()
+			&& self.explain.is_none()
+			&& matches!(self.screen, Screen::Solve)
+			&& matches!(self.state, ExecutionState::Populating);
+		if !is_populating
+		{
+			self.drag_origin = None;
+			return
+		}
+		let position = Position::new(event.column, event.row);
+		let target = self
+			.cell_areas
+			.get()
+			.iter()
+			.position(|area| area.contains(position));
+		match (event.kind, target)
+		{
+			(MouseEventKind::Down(MouseButton::Left), Some(index)) =>
+			{
+				self.cursor = (index as u8 & 3, index as u8 >> 2);
+				self.drag_origin = Some(index);
+			},
+			(MouseEventKind::Drag(MouseButton::Left), Some(index))
+				if self.drag_origin.is_some() =>
+			{
+				self.cursor = (index as u8 & 3, index as u8 >> 2);
+			},
+			(MouseEventKind::Up(MouseButton::Left), target) =>
+			{
+				if let (Some(from), Some(to)) = (self.drag_origin.take(), target)
+				{
+					self.cells.swap(from, to);
+					self.cursor = (to as u8 & 3, to as u8 >> 2);
+				}
+			},
+			_ =>
+			{}
+		}
+	}
+
+	/// Process pasted text. While populating the puzzle, text comprising
+	/// several fragments opens the [bulk-entry](BulkEntry) modal, and text
+	/// pasted into the open modal is appended to it, with line breaks
+	/// flattened into spaces. Everywhere else, the text is processed as
+	/// though it had been typed, one key at a time.
+	///
+	/// # Arguments
+	///
+	/// * `text` - The pasted text.
+	fn process_paste(&mut self, text: &str)
+	{
+		let is_populating = !self.is_splash_visible
+			&& !self.is_help_visible
+			&& matches!(self.screen, Screen::Solve)
+			&& matches!(self.state, ExecutionState::Populating);
+		if is_populating
+			&& (self.bulk_entry.is_some() || BulkEntry::tokens(text).count() > 1)
+		{
+			let bulk_entry = self.bulk_entry.get_or_insert_default();
+			bulk_entry.text.push_str(&text.replace(['\r', '\n'], " "));
+			bulk_entry.error = None;
+			return
+		}
+		for c in text.chars()
+		{
+			let code = match c
+			{
+				'\r' | '\n' => KeyCode::Enter,
+				c => KeyCode::Char(c)
+			};
+			self.process_key_event(code.into());
+		}
+	}
+
+	/// Attempt to start the solver. If the puzzle is not fully populated, do
+	/// nothing; the UI already provides feedback to the user. If the history
+	/// caches a solve of the board, then the solver resumes it instead of
+	/// searching again, and finishes at once.
+	fn start_solver(&mut self)
+	{
+		if self.is_populated()
+		{
+			let cached = self.cached_solver();
+			// A cached solve took no time at all, so its duration is unknown,
+			// just like that of a resumed solve.
+			self.solve_started = cached.is_none().then(|| self.clock.now());
+			self.solve_deadline = self.deadline();
+			let solver = cached.unwrap_or_else(|| self.build_solver());
+			#[cfg(feature = "trace-ui")]
+			let solver = solver.with_trace(TRACE_CAPACITY);
+			let board =
+				self.cells.iter().map(Fragment::as_str).collect::<Vec<_>>();
+			info!("Starting solve: {}", board.join(" "));
+			// Persist the board immediately, so that even an early disconnect
+			// can be resumed.
+			self.save_checkpoint(&solver, true);
+			self.keymap.set_mode(Mode::Normal);
+			self.scroll_offset = 0;
+			self.is_following = true;
+			self.solve_duration = None;
+			self.state = ExecutionState::Solving { solver };
+		}
+	}
+
+	/// Resume the most recent cached solve of the board from the history
+	/// file, if any, provided that no word filter is installed.
+	///
+	/// # Returns
+	///
+	/// The finished solver, or `None` if no solve of the board is cached.
+	fn cached_solver(&self) -> Option<Solver>
+	{
+		if self.filter.is_some()
+		{
+			return None
+		}
+		let path = self.history_file.as_ref()?;
+		let entries = match crate::history::load(path)
+		{
+			Ok(entries) => entries,
+			Err(e) =>
+			{
+				warn!("Failed to read history file: {}: {}", path.display(), e);
+				return None
+			}
+		};
+		crate::history::cached_solver(
+			&entries,
+			self.solver_dictionary(),
+			&self.cells,
+			self.solver_config
+		)
+	}
+
+	/// Check whether every cell of the board is populated.
+	///
+	/// # Returns
+	///
+	/// `true` if the board is fully populated, `false` otherwise.
+	fn is_populated(&self) -> bool
+	{
+		self.cells.iter().all(|cell| !cell.is_empty())
+	}
+
+	/// Answer the [subset](Self::solver_dictionary) of the dictionary that the
+	/// solver walks, computing it first if necessary.
+	///
+	/// # Returns
+	///
+	/// The subset of the dictionary.
+	fn solver_dictionary(&self) -> Rc<Dictionary>
+	{
+		Rc::clone(self.solver_dictionary.get_or_init(|| {
+			Rc::new(self.dictionary.subset(MIN_WORD_LEN, MAX_WORD_LEN))
+		}))
+	}
+
+	/// Answer the [inventory](Self::inventory) of the solver's dictionary,
+	/// learning it first if necessary. Marking a word as bogus doesn't
+	/// invalidate the inventory, since a single word hardly sways it.
+	///
+	/// # Returns
+	///
+	/// The inventory.
+	fn inventory(&self) -> &Inventory
+	{
+		self.inventory
+			.get_or_init(|| Inventory::learn(&self.solver_dictionary()))
+	}
+
+	/// Build a solver for the board, installing the word filter and the
+	/// solver configuration.
+	///
+	/// # Returns
+	///
+	/// The solver.
+	fn build_solver(&self) -> Solver
+	{
+		let mut builder = Solver::builder()
+			.dictionary(self.solver_dictionary())
+			.fragments(self.cells)
+			.config(self.solver_config);
+		if let Some(ref filter) = self.filter
+		{
+			builder = builder.filter(Rc::clone(filter));
+		}
+		// Both the dictionary and the fragments were supplied, so building
+		// cannot fail.
+		builder.build().unwrap()
+	}
+
+	/// Process a key event while [resuming](ExecutionState::Resuming) an
+	/// interrupted solve:
+	///
+	/// * Escape - [Leave](Self::leave) the solve screen, preserving the state
+	///   file.
+	/// * Y, Enter - Resume the interrupted solve.
+	/// * N - Discard the interrupted solve, but keep the board for editing.
+	/// * [Help](Action::Help) - Show the keybindings.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	fn process_key_event_resuming(&mut self, event: KeyEvent)
+	{
+		if let Resolution::Action(Action::Help) = self.keymap.resolve(event)
+		{
+			self.is_help_visible = true;
+			return
+		}
+		match event.code
+		{
+			KeyCode::Esc => self.leave(),
+			KeyCode::Char('y' | 'Y') | KeyCode::Enter => self.resume_solver(),
+			KeyCode::Char('n' | 'N') =>
+			{
+				self.clear_checkpoint();
+				self.state = ExecutionState::Populating;
+			},
+			_ =>
+			{}
+		}
+	}
+
+	/// Process a key event while the exit confirmation modal is open:
+	///
+	/// * Y, Enter, [Exit](Action::Exit) - Exit the application, discarding
+	///   the board. Pressing [Exit](Action::Exit) twice thus skips the
+	///   question.
+	/// * N - Close the modal, keeping the board for editing.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	fn process_key_event_exit(&mut self, event: KeyEvent)
+	{
+		if let Resolution::Action(Action::Exit) = self.keymap.resolve(event)
+		{
+			self.is_exit_pending = false;
+			return self.exit()
+		}
+		match event.code
+		{
+			KeyCode::Char('y' | 'Y') | KeyCode::Enter =>
+			{
+				self.is_exit_pending = false;
+				self.exit();
+			},
+			KeyCode::Char('n' | 'N') => self.is_exit_pending = false,
+			_ =>
+			{}
+		}
+	}
+
+	/// Check whether exiting would discard a board that the user has typed,
+	/// and the user wants to be asked first.
+	///
+	/// # Returns
+	///
+	/// `true` if exiting requires confirmation, `false` otherwise.
+	fn needs_exit_confirmation(&self) -> bool
+	{
+		self.confirm_exit
+			&& matches!(self.state, ExecutionState::Populating)
+			&& self.cells.iter().any(|cell| !cell.is_empty())
+	}
+
+	/// Leave the current screen. If the application has a
+	/// [main menu](Screen::Menu), then return to it, selecting the screen just
+	/// left. Otherwise, leaving the solve screen exits the application, and
+	/// leaving any other screen returns to the solve screen. Exiting with a
+	/// typed board asks for [confirmation](Self::needs_exit_confirmation)
+	/// first.
+	fn leave(&mut self)
+	{
+		match self.screen
+		{
+			_ if self.has_menu =>
+			{
+				let selected = self.screen.menu_index();
+				self.screen = Screen::Menu { selected };
+			},
+			Screen::Solve if self.needs_exit_confirmation() =>
+			{
+				self.is_exit_pending = true
+			},
+			Screen::Solve => self.exit(),
+			_ => self.screen = Screen::Solve
+		}
+	}
+
+	/// Process a key event while the [splash screen](Self::is_splash_visible)
+	/// is showing. Only [Exit](Action::Exit) is recognized, which cancels
+	/// loading the dictionary and exits the application.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	fn process_key_event_splash(&mut self, event: KeyEvent)
+	{
+		if let Resolution::Action(Action::Exit) = self.keymap.resolve(event)
+		{
+			if let Some(loader) = self.loader.take()
+			{
+				loader.cancel();
+			}
+			self.exit();
+		}
+	}
+
+	/// Start loading the specified dictionary in the background. Any
+	/// dictionary that is already loading is abandoned. The new dictionary
+	/// takes effect once [loading finishes](Self::finish_loading).
+	///
+	/// # Arguments
+	///
+	/// * `directory` - The directory containing the dictionary files.
+	/// * `name` - The name of the dictionary.
+	fn load_dictionary(&mut self, directory: PathBuf, name: String)
+	{
+		self.report_setting_result(Ok(()));
+		self.loader = Some(
+			Loader::spawn(directory, name, self.composition.clone())
+		);
+	}
+
+	/// Install the dictionary loaded in the background, if it has finished
+	/// loading, and persist the dictionary settings. If the board has
+	/// already been solved, or is being solved, then solve it again with the
+	/// new dictionary. If the dictionary could not be opened, then the
+	/// current dictionary remains in use, and the [failure](LoadFailure) is
+	/// reported in a dialog, from which the user may retry or pick another
+	/// dictionary.
+	///
+	/// The initial dictionary, which loads behind the
+	/// [splash screen](Self::is_splash_visible), is different: it doesn't
+	/// change the settings, so it isn't persisted, and there is no current
+	/// dictionary to fall back on, so dismissing the dialog exits the
+	/// application.
+	fn finish_loading(&mut self)
+	{
+		if !self.loader.as_ref().is_some_and(Loader::is_finished)
+		{
+			return
+		}
+		let loader = self.loader.take().unwrap();
+		let directory = loader.directory().to_path_buf();
+		let name = loader.name().to_string();
+		let result = match loader.join()
+		{
+			Ok(mut dictionary) =>
+			{
+				dictionary.set_folding(self.is_folding);
+				info!(
+					"Loaded dictionary: {}/{}: {} words",
+					directory.display(),
+					name,
+					dictionary.len()
+				);
+				self.dictionary = Rc::new(dictionary);
+				self.solver_dictionary = OnceCell::new();
+				self.inventory = OnceCell::new();
+				self.dictionary_directory = directory;
+				self.dictionary_name = Some(name);
+				if self.is_splash_visible
+				{
+					self.is_splash_visible = false;
+					return
+				}
+				if matches!(
+					self.state,
+					ExecutionState::Solving { .. }
+						| ExecutionState::Highlighting { .. }
+						| ExecutionState::Finished { .. }
+				)
+				{
+					self.start_solver();
+				}
+				self.persist_setting(Setting::Directory)
+					.and_then(|_| self.persist_setting(Setting::Dictionary))
+			},
+			Err(e) =>
+			{
+				warn!(
+					"Failed to open dictionary: {}/{}: {}",
+					directory.display(),
+					name,
+					e
+				);
+				let message = self.text_with(
+					"failure.open",
+					&[
+						("path", &directory.join(&name).display()),
+						("error", &e)
+					]
+				);
+				self.load_failure = Some(LoadFailure {
+					directory,
+					name,
+					kind: e.kind(),
+					message,
+					picker: None
+				});
+				return
+			}
+		};
+		if let Err(ref e) = result
+		{
+			warn!("{}", e);
+		}
+		self.report_setting_result(result);
+	}
+
+	/// Process a key event while the [load failure](LoadFailure) dialog is
+	/// open:
+	///
+	/// * R, Enter - Retry the dictionary that failed.
+	/// * B - Browse the dictionaries of its directory.
+	/// * [Exit](Action::Exit) - Dismiss the dialog, keeping the current
+	///   dictionary, or, if there is none yet, exit the application.
+	///
+	/// While browsing, [MoveUp](Action::MoveUp) and
+	/// [MoveDown](Action::MoveDown) select a dictionary,
+	/// [Solve](Action::Solve) loads it, and [Exit](Action::Exit) returns to
+	/// the dialog.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	fn process_key_event_load_failure(&mut self, event: KeyEvent)
+	{
+		let Some(ref mut failure) = self.load_failure else { return };
+		let resolution = self.keymap.resolve(event);
+		if let Some(Picker { ref names, ref mut selected }) = failure.picker
+		{
+			match resolution
+			{
+				Resolution::Action(Action::Exit) => failure.picker = None,
+				Resolution::Action(Action::MoveUp) =>
+				{
+					*selected = selected.saturating_sub(1)
+				},
+				Resolution::Action(Action::MoveDown) =>
+				{
+					*selected = (*selected + 1).min(names.len() - 1)
+				},
+				Resolution::Action(Action::Solve) =>
+				{
+					let name = names[*selected].clone();
+					let directory = failure.directory.clone();
+					self.load_failure = None;
+					self.load_dictionary(directory, name);
+				},
+				_ =>
+				{}
+			}
+			return
+		}
+		if let Resolution::Action(Action::Exit) = resolution
+		{
+			let failure = self.load_failure.take().unwrap();
+			if self.is_splash_visible
+			{
+				self.fatal_error =
+					Some(io::Error::new(failure.kind, failure.message));
+				self.exit();
+			}
+			else
+			{
+				self.report_setting_result(Err(io::Error::new(
+					failure.kind,
+					failure.message
+				)));
+			}
+			return
+		}
+		match event.code
+		{
+			KeyCode::Char('r' | 'R') | KeyCode::Enter =>
+			{
+				let failure = self.load_failure.take().unwrap();
+				self.load_dictionary(failure.directory, failure.name);
+			},
+			KeyCode::Char('b' | 'B') =>
+			{
+				match Dictionary::available(&failure.directory)
+				{
+					Ok(names) if !names.is_empty() =>
+					{
+						let selected = names
+							.iter()
+							.position(|name| *name == failure.name)
+							.unwrap_or_default();
+						failure.picker = Some(Picker { names, selected });
+					},
+					Ok(_) =>
+					{
+						failure.message = self.messages.text_with(
+							"failure.empty",
+							&[("path", &failure.directory.display())]
+						);
+					},
+					Err(e) =>
+					{
+						failure.message = self.messages.text_with(
+							"failure.list",
+							&[
+								("path", &failure.directory.display()),
+								("error", &e)
+							]
+						);
+					}
+				}
+			},
+			_ =>
+			{}
+		}
+	}
+
+	/// Resume the interrupted solve from its checkpoint.
+	fn resume_solver(&mut self)
+	{
+		let mut state = ExecutionState::Swapping;
+		swap(&mut self.state, &mut state);
+		if let ExecutionState::Resuming { checkpoint } = state
+		{
+			// The search continues under the configuration with which it was
+			// checkpointed, even if the settings have changed since, lest the
+			// solution mix words found under different rules.
+			let mut solver = match Solver::from_checkpoint(
+				self.solver_dictionary(),
+				checkpoint
+			)
+			{
+				Ok(solver) => solver,
+				Err(e) =>
+				{
+					warn!("Failed to resume solve: {}", e);
+					self.clear_checkpoint();
+					self.state = ExecutionState::Populating;
+					return
+				}
+			};
+			if let Some(ref filter) = self.filter
+			{
+				solver = solver.with_filter(Rc::clone(filter));
+			}
+			#[cfg(feature = "trace-ui")]
+			{
+				solver = solver.with_trace(TRACE_CAPACITY);
+			}
+			self.keymap.set_mode(Mode::Normal);
+			self.scroll_offset = 0;
+			self.is_following = true;
+			self.solve_deadline = self.deadline();
+			self.state = ExecutionState::Solving { solver };
+		}
+		else
+		{
+			unreachable!()
+		}
+	}
+
+	/// Process a key event while [solving](ExecutionState::Solving) the
+	/// puzzle:
+	///
+	/// * [Exit](Action::Exit) - [Leave](Self::leave) the solve screen.
+	/// * [PageUp](Action::PageUp) - Scroll the solution up by a page.
+	/// * [PageDown](Action::PageDown) - Scroll the solution down by a page.
+	/// * [Trace](Action::Trace) - Step through the decisions of the solver so
+	///   far. Only with the `trace-ui` feature.
+	/// * [Help](Action::Help) - Show the keybindings.
+	///
+	/// Also, run the solver for a short while, potentially highlighting the
+	/// most recently discovered solution.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	/// * `solver` - The solver.
+	fn process_key_event_solving(&mut self, event: KeyEvent)
+	{
+		match self.keymap.resolve(event)
+		{
+			Resolution::Action(Action::Exit) => self.leave(),
+			Resolution::Action(Action::PageUp) => self.scroll_pages(-1),
+			Resolution::Action(Action::PageDown) => self.scroll_pages(1),
+			#[cfg(feature = "trace-ui")]
+			Resolution::Action(Action::Trace) => self.open_trace(),
+			Resolution::Action(Action::Help) => self.is_help_visible = true,
+			_ =>
+			{}
+		}
+	}
+
+	/// Process a key event while [highlighting](ExecutionState::Highlighting)
+	/// the puzzle:
+	///
+	/// * [Exit](Action::Exit) - [Leave](Self::leave) the solve screen.
+	/// * [PageUp](Action::PageUp) - Scroll the solution up by a page.
+	/// * [PageDown](Action::PageDown) - Scroll the solution down by a page.
+	/// * [Trace](Action::Trace) - Step through the decisions of the solver so
+	///   far. Only with the `trace-ui` feature.
+	/// * [Help](Action::Help) - Show the keybindings.
+	///
+	/// Maintain the highlight for long enough to be visible, then return to the
+	/// [solving](ExecutionState::Solving) state.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	/// * `solver` - The solver.
+	fn process_key_event_highlighting(&mut self, event: KeyEvent)
+	{
+		match self.keymap.resolve(event)
+		{
+			Resolution::Action(Action::Exit) => self.leave(),
+			Resolution::Action(Action::PageUp) => self.scroll_pages(-1),
+			Resolution::Action(Action::PageDown) => self.scroll_pages(1),
+			#[cfg(feature = "trace-ui")]
+			Resolution::Action(Action::Trace) => self.open_trace(),
+			Resolution::Action(Action::Help) => self.is_help_visible = true,
+			_ =>
+			{}
+		}
+	}
+
+	/// Process a key event while [reviewing](ExecutionState::Finished) the
+	/// solution:
+	///
+	/// * [Exit](Action::Exit) - [Leave](Self::leave) the solve screen.
+	/// * [MoveUp](Action::MoveUp) - Select the word above.
+	/// * [MoveDown](Action::MoveDown) - Select the word below.
+	/// * [PageUp](Action::PageUp) - Scroll (or move the selection) up by a
+	///   page.
+	/// * [PageDown](Action::PageDown) - Scroll (or move the selection) down by
+	///   a page.
+	/// * [NextPlacement](Action::NextPlacement) - Show the next placement of
+	///   the selected word on the board.
+	/// * [MarkBogus](Action::MarkBogus) - [Exclude](Self::mark_bogus) the
+	///   selected word.
+	/// * [MarkFavorite](Action::MarkFavorite) - [Toggle](Self::mark_favorite)
+	///   whether the selected word is a favorite.
+	/// * [Sort](Action::Sort) - [Cycle](Self::cycle_sort_order) the order of
+	///   the solution list.
+	/// * [ExportCard](Action::ExportCard) - [Export](Self::export_card) a
+	///   share card of the solve.
+	/// * [Replay](Action::Replay) - [Replay](Self::start_replay) the
+	///   discovery of each word.
+	/// * [Explain](Action::Explain) - Open the [explain](ExplainPrompt)
+	///   modal, e.g., to learn why the solver didn't find some word.
+	/// * [Trace](Action::Trace) - Step through the decisions of the solver.
+	///   Only with the `trace-ui` feature.
+	/// * [Help](Action::Help) - Show the keybindings.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	/// * `solver` - The solver.
+	fn process_key_event_finished(&mut self, event: KeyEvent)
+	{
+		if let ExecutionState::Finished { replay: Some(_), .. } = self.state
+		{
+			return self.process_key_event_replay(event)
+		}
+		match self.keymap.resolve(event)
+		{
+			Resolution::Action(Action::Exit) => self.leave(),
+			Resolution::Action(Action::MoveUp) => self.move_word_index(-1),
+			Resolution::Action(Action::MoveDown) => self.move_word_index(1),
+			Resolution::Action(Action::PageUp) => self.scroll_pages(-1),
+			Resolution::Action(Action::PageDown) => self.scroll_pages(1),
+			Resolution::Action(Action::NextPlacement) => self.next_placement(),
+			Resolution::Action(Action::MarkBogus) => self.mark_bogus(),
+			Resolution::Action(Action::MarkFavorite) => self.mark_favorite(),
+			Resolution::Action(Action::Sort) => self.cycle_sort_order(),
+			Resolution::Action(Action::ExportCard) => self.export_card(),
+			Resolution::Action(Action::Replay) => self.start_replay(),
+			Resolution::Action(Action::Explain) =>
+			{
+				self.explain = Some(ExplainPrompt::default())
+			},
+			#[cfg(feature = "trace-ui")]
+			Resolution::Action(Action::Trace) => self.open_trace(),
+			Resolution::Action(Action::Help) => self.is_help_visible = true,
+			_ =>
+			{}
+		}
+	}
+
+	/// Process a key event while [replaying](Replay) the solve:
+	///
+	/// * [Exit](Action::Exit), [Replay](Action::Replay) -
+	///   [Stop](Self::stop_replay) the replay.
+	/// * [MoveLeft](Action::MoveLeft) - Slow down the replay.
+	/// * [MoveRight](Action::MoveRight) - Speed up the replay.
+	/// * [Help](Action::Help) - Show the keybindings.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	fn process_key_event_replay(&mut self, event: KeyEvent)
+	{
+		let resolution = self.keymap.resolve(event);
+		let now = self.clock.now();
+		let ExecutionState::Finished { replay: Some(ref mut replay), .. } =
+			self.state
+		else
+		{
+			unreachable!()
+		};
+		match resolution
+		{
+			Resolution::Action(Action::Exit | Action::Replay) =>
+			{
+				self.stop_replay()
+			},
+			Resolution::Action(Action::MoveLeft) => replay.slower(),
+			Resolution::Action(Action::MoveRight) => replay.faster(now),
+			Resolution::Action(Action::Help) => self.is_help_visible = true,
+			_ =>
+			{}
+		}
+	}
+
+	/// Mark the application for exit. The application will exit after the next
+	/// iteration of the main loop. Exiting deliberately while solving abandons
+	/// the solve, so the state file is removed.
+	fn exit(&mut self)
+	{
+		let next_state = match self.state
+		{
+			ExecutionState::Swapping => unreachable!(),
+			ExecutionState::Populating =>
+			{
+				ExecutionState::Exiting { outcome: Outcome::default() }
+			},
+			ExecutionState::Resuming { .. } =>
+			{
+				ExecutionState::Exiting { outcome: Outcome::default() }
+			},
+			ExecutionState::Solving { .. } =>
+			{
+				self.clear_checkpoint();
+				ExecutionState::Exiting { outcome: Outcome::default() }
+			},
+			ExecutionState::Highlighting { .. } =>
+			{
+				self.clear_checkpoint();
+				ExecutionState::Exiting { outcome: Outcome::default() }
+			},
+			ExecutionState::Finished {
+				ref solver,
+				is_truncated,
+				ref completeness,
+				ref cover,
+				..
+			} =>
+			{
+				ExecutionState::Exiting {
+					outcome: Outcome::finished(
+						solver,
+						is_truncated,
+						completeness.clone(),
+						cover.as_deref()
+					)
+					.with_duration(self.solve_duration)
+				}
+			},
+			ExecutionState::Exiting { ref outcome } =>
+			{
+				ExecutionState::Exiting {
+					outcome: outcome.clone()
+				}
+			},
+		};
+		self.state = next_state;
+	}
+}
+
+/// The height of the [suggestions panel](App::render_suggestions), in rows:
+/// room for every suggestion and a warning, plus the borders.
+const SUGGESTIONS_HEIGHT: u16 = MAX_SUGGESTIONS as u16 + 3;
+
+impl Widget for &App
+{
+	fn render(self, area: Rect, buf: &mut Buffer)
+	{
+		if self.is_splash_visible
+		{
+			self.render_splash(area, buf);
+			self.render_load_failure(area, buf);
+			return
+		}
+		// Reserve the bottom row for the status bar, and, if the TUI is
+		// accessible, the row above it for the announcement line.
+		let [main, announcement, status] = Layout::vertical([
+			Constraint::Min(0),
+			Constraint::Length(self.is_accessible as u16),
+			Constraint::Length(1)
+		]).areas(area);
+		self.render_announcement(announcement, buf);
+		self.render_status_bar(status, buf);
+		let area = main;
+		match self.screen
+		{
+			Screen::Menu { selected } => self.render_menu(area, buf, selected),
+			Screen::Play {
+				ref game,
+				ref guess,
+				ref verdict,
+				ref spellings,
+				ref arrangement,
+				ref timer
+			} => self.render_play(
+				area,
+				buf,
+				game,
+				guess,
+				verdict.as_ref(),
+				spellings,
+				arrangement,
+				timer.as_ref()
+			),
+			Screen::History { ref entries, selected, ref error } =>
+			{
+				self.render_history(area, buf, entries, selected, error)
+			},
+			Screen::Statistics { ref statistics, ref error } =>
+			{
+				self.render_statistics(area, buf, statistics, error)
+			},
+			#[cfg(feature = "trace-ui")]
+			Screen::Trace {
+				ref decisions,
+				dropped,
+				selected,
+				ref filter
+			} => self.render_trace(
+				area,
+				buf,
+				decisions,
+				dropped,
+				selected,
+				filter
+			),
+			Screen::Settings {
+				selected,
+				ref editing,
+				ref picker,
+				ref error
+			} =>
+			{
+				self.render_settings(
+					area,
+					buf,
+					selected,
+					editing.as_deref(),
+					error.as_deref()
+				);
+				if let Some(picker) = picker
+				{
+					self.render_picker(area, buf, picker);
+				}
+			},
+			Screen::Solve =>
+			{
+				self.render_solve(area, buf);
+				if let Some(ref bulk_entry) = self.bulk_entry
+				{
+					self.render_bulk_entry(area, buf, bulk_entry);
+				}
+				if let Some(ref prompt) = self.explain
+				{
+					self.render_explain(area, buf, prompt);
+				}
+				if self.is_exit_pending
+				{
+					self.render_exit_prompt(area, buf);
+				}
+			}
+		}
+		self.render_load_failure(area, buf);
+		if self.is_help_visible
+		{
+			self.render_help(main, buf);
+		}
+	}
+}
+
+/// The execution state of the application, which tracks the solve flow of
+/// the [solve screen](Screen::Solve).
+#[derive(Clone, Debug)]
+enum ExecutionState
+{
+	/// The application state is transitioning to the next state. This is a
+	/// transient state that should not be rendered.
+	Swapping,
+
+	/// The user is populating the puzzle with fragments.
+	Populating,
+
+	/// The state file holds an interrupted solve, and the user is deciding
+	/// whether to resume it.
+	Resuming
+	{
+		/// The checkpoint of the interrupted solve.
+		checkpoint: Checkpoint
+	},
+
+	/// The solver is running, incrementally populating the solution.
+	Solving
+	{
+		/// The solver for the puzzle.
+		solver: Solver
+	},
+
+	/// The solver is highlighting the most recently discovered solution, and
+	/// will momentarily return to the [Solving](ExecutionState::Solving) state.
+	Highlighting
+	{
+		/// The solver for the puzzle.
+		solver: Solver,
+
+		/// When to transition back to the [Solving](ExecutionState::Solving)
+		/// state.
+		until: Instant,
+
+		/// The fragment path of the solution to highlight.
+		path: FragmentPath
+	},
+
+	/// The solver has finished, but the user is reviewing the solution.
+	Finished
+	{
+		/// The solver for the puzzle.
+		solver: Solver,
+
+		/// Whether a complete solution was found.
+		is_solved: bool,
+
+		/// Whether the solver ran out of time before it finished, so that the
+		/// solution is only what it found in time.
+		is_truncated: bool,
+
 		/// The index of the word to highlight in the solution.
-		highlight: Option<usize>
+		highlight: Option<usize>,
+
+		/// Which of the fragment paths that form the highlighted word to show
+		/// on the board. Reduced modulo the number of such paths.
+		placement: usize,
+
+		/// Why the solution is incomplete, if the puzzle went unsolved.
+		completeness: Option<Completeness>,
+
+		/// The 5 quartiles that together use every fragment exactly once, if
+		/// any.
+		cover: Option<Vec<FragmentPath>>,
+
+		/// The replay of the discovery of the words, if one is in progress.
+		replay: Option<Replay>
+	},
+
+	/// The application is exiting.
+	Exiting
+	{
+		/// The outcome of the solve.
+		outcome: Outcome
+	}
+}
+
+/// The exit status of a solve that found a complete solution. Every other
+/// subcommand also exits with this status when it succeeds.
+pub const EXIT_SOLVED: u8 = 0;
+
+/// The exit status of a solve that finished without a complete solution.
+pub const EXIT_UNSOLVED: u8 = 1;
+
+/// The exit status of a solve that was abandoned before the solver finished.
+pub const EXIT_ABORTED: u8 = 2;
+
+/// The exit status of any failure, e.g., a missing dictionary or a broken
+/// terminal.
+pub const EXIT_FAILED: u8 = 3;
+
+/// The solution to a puzzle, as reported when the application exits.
+/// [Displayed](Display), it is one word per line, in two sections: the
+/// quartiles, then the bonus words, each headed by its title and count.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Solution
+{
+	/// The distinct quartiles of the solution, in alphabetical order.
+	pub quartiles: Vec<String>,
+
+	/// The distinct words of the solution that aren't quartiles, in
+	/// alphabetical order.
+	pub bonus_words: Vec<String>,
+
+	/// The fragments that form each word of the solution, in order. A word
+	/// that can be formed in more than one way is given the way that the
+	/// solver found first.
+	pub paths: BTreeMap<String, Vec<String>>,
+
+	/// The total score of the words, as the solver scores them.
+	pub score: u32,
+
+	/// How long the solve took, in milliseconds, if it was timed. A solve
+	/// reused from the history is not.
+	pub duration_ms: Option<u64>,
+
+	/// Whether a complete solution was found.
+	#[serde(rename = "solved")]
+	pub is_solved: bool
+}
+
+impl Solution
+{
+	/// Answer the solution found by the specified finished solver.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The finished solver.
+	///
+	/// # Returns
+	///
+	/// The solution.
+	#[must_use]
+	pub fn of(solver: &Solver) -> Self
+	{
+		let fragments = solver.fragments();
+		let mut paths = BTreeMap::new();
+		let mut quartiles = BTreeSet::new();
+		let mut score = 0;
+		for path in solver.solution_paths()
+		{
+			let word = solver.word(&path).to_string();
+			if path.is_full()
+			{
+				quartiles.insert(word.clone());
+			}
+			if let btree_map::Entry::Vacant(entry) = paths.entry(word)
+			{
+				score += solver.score(&path);
+				entry.insert(
+					path.iter()
+						.flatten()
+						.map(|index| fragments[index].to_string())
+						.collect()
+				);
+			}
+		}
+		Self {
+			bonus_words: paths.keys()
+				.filter(|word| !quartiles.contains(*word))
+				.cloned()
+				.collect(),
+			quartiles: quartiles.into_iter().collect(),
+			paths,
+			score,
+			duration_ms: None,
+			is_solved: solver.is_solved()
+		}
+	}
+
+	/// Answer whether the solution has no words.
+	///
+	/// # Returns
+	///
+	/// `true` if the solution is empty, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_empty(&self) -> bool
+	{
+		self.quartiles.is_empty() && self.bonus_words.is_empty()
+	}
+
+	/// Answer the distinct words of the solution: the quartiles, then the
+	/// bonus words, each in alphabetical order.
+	///
+	/// # Returns
+	///
+	/// An iterator over the words.
+	pub fn words(&self) -> impl Iterator<Item = &String> + '_
+	{
+		self.quartiles.iter().chain(&self.bonus_words)
+	}
+
+	/// Answer the nonempty sections of the solution, each comprising its
+	/// title and its words: the [quartiles](QUARTILES_TITLE), then the
+	/// [bonus words](BONUS_WORDS_TITLE).
+	///
+	/// # Returns
+	///
+	/// An iterator over the sections.
+	pub fn sections(&self) -> impl Iterator<Item = (&str, &[String])> + '_
+	{
+		[
+			(QUARTILES_TITLE, self.quartiles.as_slice()),
+			(BONUS_WORDS_TITLE, self.bonus_words.as_slice())
+		]
+			.into_iter()
+			.filter(|(_, words)| !words.is_empty())
+	}
+}
+
+impl Display for Solution
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+	{
+		for (index, (title, words)) in self.sections().enumerate()
+		{
+			if index > 0
+			{
+				writeln!(f)?;
+			}
+			writeln!(f, "{} ({})", title, words.len())?;
+			for word in words
+			{
+				writeln!(f, "{}", word)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// The outcome of a solve, as reported when the application exits.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Outcome
+{
+	/// The solution to the puzzle. Empty unless the solver finished or ran
+	/// out of time.
+	pub solution: Solution,
+
+	/// Whether the solver finished, rather than being abandoned.
+	#[serde(rename = "finished")]
+	pub is_finished: bool,
+
+	/// Whether the solver ran out of time before it finished, so that the
+	/// solution is only the best found within the time allotted.
+	#[serde(rename = "truncated")]
+	pub is_truncated: bool,
+
+	/// Why the solution is incomplete, if the solver finished without
+	/// solving the puzzle.
+	pub completeness: Option<Completeness>,
+
+	/// The 5 quartiles that together use every fragment exactly once, if the
+	/// solver finished and such quartiles exist. This singles out the
+	/// intended solution of an unofficial puzzle with more than 5 quartiles.
+	pub cover: Option<Vec<String>>,
+
+	/// The grid coordinates of the fragments of each word of the solution,
+	/// in order, as `(row, column)` pairs counted from zero at the top left.
+	/// A word that can be formed in more than one way is given the way that
+	/// the solver found first.
+	pub coordinates: BTreeMap<String, Vec<(usize, usize)>>,
+
+	/// The [source](Source) of each word of the solution that an inclusion
+	/// list supplied, rather than the base word list. Omitted from JSON when
+	/// empty, as it is unless the dictionary was composed.
+	#[serde(skip_serializing_if = "BTreeMap::is_empty")]
+	pub sources: BTreeMap<String, Source>,
+
+	/// The counters of the solver's work since it began or last resumed, if
+	/// the solver finished. These are for diagnosis, so they are never
+	/// serialized.
+	#[serde(skip)]
+	pub metrics: Option<SolverMetrics>,
+
+	/// The share card most recently exported from the TUI, if any. It is
+	/// exported once the TUI exits, so it is never serialized.
+	#[serde(skip)]
+	pub card: Option<Card>,
+
+	/// The fragments of the board, in grid order, if the solver finished.
+	/// These are for [rendering](crate::html::Page) the board, so they are
+	/// never serialized.
+	#[serde(skip)]
+	pub board: Vec<String>
+}
+
+impl Outcome
+{
+	/// Answer the exit status that reports the outcome to the shell:
+	/// [`EXIT_SOLVED`], [`EXIT_UNSOLVED`], or [`EXIT_ABORTED`].
+	///
+	/// # Returns
+	///
+	/// The exit status.
+	#[must_use]
+	pub fn exit_status(&self) -> u8
+	{
+		match (self.is_finished || self.is_truncated, self.solution.is_solved)
+		{
+			(_, true) => EXIT_SOLVED,
+			(true, false) => EXIT_UNSOLVED,
+			(false, false) => EXIT_ABORTED
+		}
+	}
+
+	/// Answer the outcome of the specified solver, which either finished or
+	/// ran out of time, analyzing an incomplete solution just as the TUI does.
+	/// The solution of a solver that stopped early without being cancelled
+	/// ran out of time, so it is [truncated](Self::is_truncated), and isn't
+	/// analyzed, since finding the near misses would repeat part of the
+	/// search.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The solver.
+	///
+	/// # Returns
+	///
+	/// The outcome.
+	pub fn of(solver: &Solver) -> Self
+	{
+		let is_analyzed = solver.is_finished() && !solver.is_solved();
+		let completeness = match is_analyzed
+		{
+			true => Some(solver.completeness(MAX_NEAR_MISSES)),
+			false => None
+		};
+		let is_truncated = !solver.is_finished() && !solver.is_cancelled();
+		let cover = solver.cover();
+		Self::finished(solver, is_truncated, completeness, cover.as_deref())
+	}
+
+	/// Set how long the solve took.
+	///
+	/// # Arguments
+	///
+	/// * `duration` - How long the solve took, if it was timed.
+	///
+	/// # Returns
+	///
+	/// The receiver, for chaining.
+	#[must_use]
+	pub fn with_duration(mut self, duration: Option<Duration>) -> Self
+	{
+		self.solution.duration_ms =
+			duration.map(|duration| duration.as_millis() as u64);
+		self
+	}
+
+	/// Answer the outcome of the specified solver, which either finished or
+	/// stopped early, and whose solution has already been analyzed.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The solver.
+	/// * `is_truncated` - Whether the solver ran out of time before it
+	///   finished.
+	/// * `completeness` - Why the solution is incomplete, if it is.
+	/// * `cover` - The 5 quartiles that together use every fragment, if any.
+	///
+	/// # Returns
+	///
+	/// The outcome.
+	fn finished(
+		solver: &Solver,
+		is_truncated: bool,
+		completeness: Option<Completeness>,
+		cover: Option<&[FragmentPath]>
+	) -> Self
+	{
+		let solution = Solution::of(solver);
+		let sources = solution.words()
+			.map(|word| (word, solver.dictionary().source(word)))
+			.filter(|(_, source)| *source != Source::Base)
+			.map(|(word, source)| (word.clone(), source))
+			.collect();
+		Self {
+			solution,
+			is_finished: solver.is_finished(),
+			is_truncated,
+			completeness,
+			cover: cover.map(|cover| {
+				cover
+					.iter()
+					.map(|path| solver.word(path).to_string())
+					.collect()
+			}),
+			// Collect in reverse, so that the first placement of each word is
+			// the one that survives.
+			coordinates: solver
+				.solution_paths()
+				.iter()
+				.rev()
+				.map(|path| (solver.word(path).to_string(), path.coordinates()))
+				.collect(),
+			sources,
+			metrics: Some(*solver.metrics()),
+			card: None,
+			board: solver.fragments()
+				.iter()
+				.map(|fragment| fragment.to_string())
+				.collect()
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Screens.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The screens of the application. The [solve](Screen::Solve) screen hosts the
+/// solve flow, whose progress the [execution state](ExecutionState) tracks
+/// even while another screen is showing; every other screen owns its state.
+#[derive(Clone, Debug)]
+enum Screen
+{
+	/// The user is choosing a screen from the main menu.
+	Menu
+	{
+		/// The index of the selected item of the [menu](MENU).
+		selected: usize
+	},
+
+	/// The user is populating and solving the puzzle.
+	Solve,
+
+	/// The user is finding the words of the puzzle.
+	Play
+	{
+		/// The game, boxed since it dwarfs the other screens.
+		game: Box<Game>,
+
+		/// The pending guess.
+		guess: String,
+
+		/// The most recent guess and its verdict, if any.
+		verdict: Option<(String, Verdict)>,
+
+		/// The words of the dictionary that the most recent guess might have
+		/// misspelled, nearest first, if the dictionary lacks the guess.
+		spellings: Vec<String>,
+
+		/// The index of the cell shown at each position of the grid, in
+		/// row-major order. [Shuffling](Action::Shuffle) changes only this,
+		/// so the cells themselves, and hence the game, are unaffected.
+		arrangement: [u8; 20],
+
+		/// The countdown of the round, if it is timed.
+		timer: Option<Timer>
+	},
+
+	/// The developer is stepping through the decisions of the solver, in
+	/// order to diagnose the solution.
+	#[cfg(feature = "trace-ui")]
+	Trace
+	{
+		/// The decisions recorded by the solver, oldest first.
+		decisions: Vec<Decision>,
+
+		/// The number of older decisions that the solver discarded.
+		dropped: usize,
+
+		/// The index of the selected decision among those that match the
+		/// filter.
+		selected: usize,
+
+		/// The filter, which keeps only the decisions whose candidate words
+		/// contain it.
+		filter: String
 	},
 
-	/// The application is exiting.
-	Exiting
+	/// The user is browsing the history, in order to reopen a past puzzle.
+	History
+	{
+		/// The entries of the history, in the order that they were recorded.
+		entries: Vec<Entry>,
+
+		/// The index of the selected entry.
+		selected: usize,
+
+		/// The error that prevented the history from being read, if any.
+		error: Option<String>
+	},
+
+	/// The user is viewing statistics aggregated from the history.
+	Statistics
+	{
+		/// The statistics.
+		statistics: Statistics,
+
+		/// The error that prevented the history from being read, if any.
+		error: Option<String>
+	},
+
+	/// The user is reviewing and changing the settings.
+	Settings
+	{
+		/// The index of the selected [setting](Setting::ALL).
+		selected: usize,
+
+		/// The pending value of the selected setting, while it is being edited
+		/// as text.
+		editing: Option<String>,
+
+		/// The dictionary picker, while it is open.
+		picker: Option<Picker>,
+
+		/// The error that resulted from the most recent change, if any.
+		error: Option<String>
+	}
+}
+
+/// The dictionary picker of the [settings screen](Screen::Settings), which
+/// lists the dictionaries [available](Dictionary::available) in the
+/// dictionary directory.
+#[derive(Clone, Debug)]
+struct Picker
+{
+	/// The names of the available dictionaries, in sorted order.
+	names: Vec<String>,
+
+	/// The index of the selected name.
+	selected: usize
+}
+
+/// The dialog that reports a dictionary that could not be opened, whether at
+/// startup or when switching dictionaries, and offers to retry it or to
+/// browse its directory for another, rather than ending the application.
+#[derive(Clone, Debug)]
+struct LoadFailure
+{
+	/// The directory containing the dictionary files.
+	directory: PathBuf,
+
+	/// The name of the dictionary.
+	name: String,
+
+	/// The kind of error that prevented the dictionary from opening.
+	kind: io::ErrorKind,
+
+	/// Why the dictionary could not be opened.
+	message: String,
+
+	/// The dictionaries available in the directory, while the user is
+	/// browsing them.
+	picker: Option<Picker>
+}
+
+/// The bulk-entry modal, in which the user types or pastes every fragment of
+/// the board as a single line of text, rather than cell by cell.
+#[derive(Clone, Debug, Default)]
+struct BulkEntry
+{
+	/// The text typed or pasted so far.
+	text: String,
+
+	/// Why the text could not be assigned to the board, if it couldn't.
+	error: Option<String>
+}
+
+impl BulkEntry
+{
+	/// Check whether the specified character separates fragments. Besides
+	/// whitespace, commas, semicolons, and vertical bars are accepted, since
+	/// lists of fragments copied from elsewhere are often punctuated so.
+	///
+	/// # Arguments
+	///
+	/// * `c` - The character.
+	///
+	/// # Returns
+	///
+	/// `true` if the character is a separator, `false` otherwise.
+	fn is_separator(c: char) -> bool
+	{
+		c.is_whitespace() || matches!(c, ',' | ';' | '|')
+	}
+
+	/// Split the specified text into fragments, which may be invalid.
+	///
+	/// # Arguments
+	///
+	/// * `text` - The text.
+	///
+	/// # Returns
+	///
+	/// An iterator over the fragments.
+	fn tokens(text: &str) -> impl Iterator<Item = &str>
+	{
+		text.split(Self::is_separator).filter(|token| !token.is_empty())
+	}
+
+	/// Check whether the specified token is a valid fragment, i.e., whether
+	/// it comprises only letters, and fits into a cell once normalized.
+	///
+	/// # Arguments
+	///
+	/// * `token` - The token.
+	///
+	/// # Returns
+	///
+	/// `true` if the token is a valid fragment, `false` otherwise.
+	fn is_valid(token: &str) -> bool
+	{
+		let fragment = normalize_word(token);
+		!fragment.is_empty()
+			&& fragment.len() <= MAX_FRAGMENT_BYTES
+			&& fragment
+				.chars()
+				.all(|c| c.is_alphabetic() || is_combining_mark(c))
+	}
+
+	/// Parse the text into the fragments of the board.
+	///
+	/// # Arguments
+	///
+	/// * `messages` - The messages in which to describe a problem.
+	///
+	/// # Returns
+	///
+	/// The normalized fragments, in grid order.
+	///
+	/// # Errors
+	///
+	/// If any fragment is invalid or too long, or there are not exactly 20
+	/// fragments, a description of the problem is returned.
+	fn parse(&self, messages: &Messages) -> Result<[Fragment; 20], String>
+	{
+		let tokens = Self::tokens(&self.text).collect::<Vec<_>>();
+		if let Some(token) = tokens.iter().find(|token| !Self::is_valid(token))
+		{
+			let fragment = normalize_word(token);
+			return Err(match fragment.len() > MAX_FRAGMENT_BYTES
+			{
+				true => messages.text_with(
+					"bulk.too-long",
+					&[("fragment", token), ("bytes", &MAX_FRAGMENT_BYTES)]
+				),
+				false => messages.text_with(
+					"bulk.invalid",
+					&[("fragment", token)]
+				)
+			})
+		}
+		if tokens.len() != 20
+		{
+			return Err(messages.text_with(
+				"bulk.expected",
+				&[("count", &tokens.len())]
+			))
+		}
+		Ok(std::array::from_fn(|i| {
+			Fragment::from(normalize_word(tokens[i]).as_str())
+		}))
+	}
+}
+
+/// The explain modal, in which the user types a word in order to learn why
+/// the solver does or doesn't find it among the fragments of the board.
+#[derive(Clone, Debug, Default)]
+struct ExplainPrompt
+{
+	/// The word typed so far.
+	text: String,
+
+	/// The most recently explained word, its explanation, and the closest
+	/// words that the solver can find, if any word has been explained.
+	result: Option<(String, Explanation, Vec<String>)>
+}
+
+/// The orders in which the words within each section of the
+/// [solution list](App::solution_list) may be sorted.
+/// [Cycling](App::cycle_sort_order) visits them in declaration order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum SortOrder
+{
+	/// The order in which the solver discovered the words.
+	#[default]
+	Discovery,
+
+	/// Alphabetical order.
+	Alphabetical,
+
+	/// Longest first.
+	Length,
+
+	/// Highest score first.
+	Score
+}
+
+impl SortOrder
+{
+	/// Answer the next sort order, wrapping around after the last.
+	///
+	/// # Returns
+	///
+	/// The next sort order.
+	fn next(self) -> Self
+	{
+		match self
+		{
+			SortOrder::Discovery => SortOrder::Alphabetical,
+			SortOrder::Alphabetical => SortOrder::Length,
+			SortOrder::Length => SortOrder::Score,
+			SortOrder::Score => SortOrder::Discovery
+		}
+	}
+
+	/// Answer the key of the [message](Messages) that names the sort order.
+	///
+	/// # Returns
+	///
+	/// The key.
+	fn key(self) -> &'static str
+	{
+		match self
+		{
+			SortOrder::Discovery => "sort.discovery",
+			SortOrder::Alphabetical => "sort.alphabetical",
+			SortOrder::Length => "sort.length",
+			SortOrder::Score => "sort.score"
+		}
+	}
+}
+
+impl Display for SortOrder
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+	{
+		let name = match self
+		{
+			SortOrder::Discovery => "discovery",
+			SortOrder::Alphabetical => "alphabet",
+			SortOrder::Length => "length",
+			SortOrder::Score => "score"
+		};
+		write!(f, "{}", name)
+	}
+}
+
+/// The title of the section of the solution that lists the quartiles.
+pub const QUARTILES_TITLE: &str = "Quartiles";
+
+/// The title of the section of the solution that lists the words that aren't
+/// quartiles.
+pub const BONUS_WORDS_TITLE: &str = "Bonus words";
+
+/// The maximum number of alternative words that the
+/// [explain](ExplainPrompt) modal suggests.
+const MAX_ALTERNATIVES: usize = 5;
+
+/// The maximum number of near misses that the analysis of an incomplete
+/// solution reports.
+const MAX_NEAR_MISSES: usize = 3;
+
+/// The number of decisions that the solver retains for the
+/// [solver trace](Screen::Trace).
+#[cfg(feature = "trace-ui")]
+const TRACE_CAPACITY: usize = 1 << 16;
+
+/// The minimum interval between frames, capping redraws at about 30 per
+/// second, so that drawing doesn't compete with the solver and the handling of
+/// input.
+const FRAME_INTERVAL: Duration = Duration::from_millis(33);
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::fs;
+
+	use quartiles_solver::solver::CancellationToken;
+	use ratatui::backend::TestBackend;
+
+	use super::*;
+	use crate::{
+		advisor::ADVISOR_DELAY,
+		driver::{FakeClock, ScriptedEvents},
+		keymap::KeymapPreset
+	};
+
+	/// A board whose solution comprises 30 words, including 5 quartiles.
+	pub(super) const BOARD: [&str; 20] = [
+		"azz", "th", "ss", "tru", "ref", "fu", "ra", "nih", "cro", "mat", "wo",
+		"sh", "re", "rds", "tic", "il", "lly", "zz", "is", "ment"
+	];
+
+	/// Script the keystrokes that type [BOARD] into the grid, one fragment per
+	/// cell, and then start the solver.
+	///
+	/// # Arguments
+	///
+	/// * `events` - The script.
+	pub(super) fn type_board(events: &mut ScriptedEvents)
+	{
+		for fragment in BOARD
+		{
+			events.type_text(fragment);
+			events.press(KeyCode::Tab);
+		}
+		events.press(KeyCode::Enter);
+	}
+
+	/// Tick the specified application until its solver stops.
+	///
+	/// # Arguments
+	///
+	/// * `app` - The application.
+	/// * `events` - The script of events to deliver.
+	pub(super) fn finish_solve(app: &mut App, events: &mut ScriptedEvents)
+	{
+		while !matches!(app.state, ExecutionState::Finished { .. })
+		{
+			app.tick(events).unwrap();
+		}
+	}
+
+	/// Type [BOARD] into the grid of the specified application, and run the
+	/// solver until it stops.
+	///
+	/// # Arguments
+	///
+	/// * `app` - The application.
+	/// * `events` - The script of events to deliver, to which the keystrokes
+	///   are appended.
+	pub(super) fn solve_board(app: &mut App, events: &mut ScriptedEvents)
+	{
+		type_board(events);
+		finish_solve(app, events);
+	}
+
+	/// Ensure that the application exits when the escape key is pressed.
+	#[test]
+	fn test_handle_exit()
+	{
+		let mut app = App::new(0, Dictionary::default());
+		assert!(app.is_running());
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(!app.is_running());
+		let outcome = app.into_outcome().unwrap();
+		assert!(!outcome.is_finished);
+		assert_eq!(outcome.exit_status(), EXIT_ABORTED);
+	}
+
+	/// Ensure that exiting with a typed board asks for confirmation, that N
+	/// keeps the board, and that Y or a second escape exits.
+	#[test]
+	fn test_confirm_exit()
+	{
+		let mut app = App::new(0, Dictionary::default());
+		app.process_key_event(KeyCode::Char('a').into());
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(app.is_running());
+		assert!(app.is_exit_pending);
+		let area = Rect::new(0, 0, 100, 40);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let screen = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(screen.contains("lose the current puzzle"), "{}", screen);
+		app.process_key_event(KeyCode::Char('n').into());
+		assert!(!app.is_exit_pending);
+		assert_eq!(app.cells[0], Fragment::from("a"));
+		app.process_key_event(KeyCode::Esc.into());
+		app.process_key_event(KeyCode::Char('y').into());
+		assert!(!app.is_running());
+
+		let mut app = App::new(0, Dictionary::default());
+		app.process_key_event(KeyCode::Char('a').into());
+		app.process_key_event(KeyCode::Esc.into());
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(!app.is_running());
+
+		let mut app = App::new(0, Dictionary::default())
+			.with_confirm_exit(false);
+		app.process_key_event(KeyCode::Char('a').into());
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(!app.is_running());
+	}
+
+	/// Ensure that the cursor moves up, down, left, and right when the
+	/// corresponding arrow keys are pressed. Test all possible cursor
+	/// movements.
+	#[test]
+	fn test_handle_arrows()
+	{
+		let mut app = App::new(0, Dictionary::default());
+		assert_eq!(app.cursor, (0, 0));
+		// Test all possible cursor movements. Each case is a tuple of the
+		// initial cursor position and the expected cursor position after
+		// moving up, right, down, and left, respectively.
+		let cases = vec![
+			((0, 0), [(0, 0), (1, 0), (0, 1), (0, 0)]),
+			((0, 1), [(0, 0), (1, 1), (0, 2), (0, 1)]),
+			((0, 2), [(0, 1), (1, 2), (0, 3), (0, 2)]),
+			((0, 3), [(0, 2), (1, 3), (0, 4), (0, 3)]),
+			((0, 4), [(0, 3), (1, 4), (0, 4), (0, 4)]),
+			((1, 0), [(1, 0), (2, 0), (1, 1), (0, 0)]),
+			((1, 1), [(1, 0), (2, 1), (1, 2), (0, 1)]),
+			((1, 2), [(1, 1), (2, 2), (1, 3), (0, 2)]),
+			((1, 3), [(1, 2), (2, 3), (1, 4), (0, 3)]),
+			((1, 4), [(1, 3), (2, 4), (1, 4), (0, 4)]),
+			((2, 0), [(2, 0), (3, 0), (2, 1), (1, 0)]),
+			((2, 1), [(2, 0), (3, 1), (2, 2), (1, 1)]),
+			((2, 2), [(2, 1), (3, 2), (2, 3), (1, 2)]),
+			((2, 3), [(2, 2), (3, 3), (2, 4), (1, 3)]),
+			((2, 4), [(2, 3), (3, 4), (2, 4), (1, 4)]),
+			((3, 0), [(3, 0), (3, 0), (3, 1), (2, 0)]),
+			((3, 1), [(3, 0), (3, 1), (3, 2), (2, 1)]),
+			((3, 2), [(3, 1), (3, 2), (3, 3), (2, 2)]),
+			((3, 3), [(3, 2), (3, 3), (3, 4), (2, 3)]),
+			((3, 4), [(3, 3), (3, 4), (3, 4), (2, 4)]),
+		];
+		for (initial, expected) in cases
+		{
+			app.cursor = initial;
+			app.process_key_event(KeyCode::Up.into());
+			assert_eq!(app.cursor, expected[0], "up");
+			app.cursor = initial;
+			app.process_key_event(KeyCode::Right.into());
+			assert_eq!(app.cursor, expected[1], "right");
+			app.cursor = initial;
+			app.process_key_event(KeyCode::Down.into());
+			assert_eq!(app.cursor, expected[2], "down");
+			app.cursor = initial;
+			app.process_key_event(KeyCode::Left.into());
+			assert_eq!(app.cursor, expected[3], "left");
+		}
+	}
+
+	/// Ensure that the cursor moves to the next cell when the tab key is
+	/// pressed.
+	#[test]
+	fn test_handle_tab()
+	{
+		let mut app = App::new(0, Dictionary::default());
+		assert_eq!(app.cursor, (0, 0));
+		// Test all possible cursor movements. Each case is a tuple of the
+		// initial cursor position and the expected cursor position after
+		// tab and shift-tab, respectively.
+		let cases = vec![
+			((0, 0), [(1, 0), (0, 0)]),
+			((1, 0), [(2, 0), (0, 0)]),
+			((2, 0), [(3, 0), (1, 0)]),
+			((3, 0), [(0, 1), (2, 0)]),
+			((0, 1), [(1, 1), (3, 0)]),
+			((1, 1), [(2, 1), (0, 1)]),
+			((2, 1), [(3, 1), (1, 1)]),
+			((3, 1), [(0, 2), (2, 1)]),
+			((0, 2), [(1, 2), (3, 1)]),
+			((1, 2), [(2, 2), (0, 2)]),
+			((2, 2), [(3, 2), (1, 2)]),
+			((3, 2), [(0, 3), (2, 2)]),
+			((0, 3), [(1, 3), (3, 2)]),
+			((1, 3), [(2, 3), (0, 3)]),
+			((2, 3), [(3, 3), (1, 3)]),
+			((3, 3), [(0, 4), (2, 3)]),
+			((0, 4), [(1, 4), (3, 3)]),
+			((1, 4), [(2, 4), (0, 4)]),
+			((2, 4), [(3, 4), (1, 4)]),
+			((3, 4), [(3, 4), (2, 4)]),
+		];
+		for (initial, expected) in cases
+		{
+			app.cursor = initial;
+			app.process_key_event(KeyCode::Tab.into());
+			assert_eq!(app.cursor, expected[0], "tab");
+			app.cursor = initial;
+			app.process_key_event(KeyCode::BackTab.into());
+			assert_eq!(app.cursor, expected[1], "shift-tab");
+		}
+	}
+
+	/// Ensure that the current cell is edited correctly when alphabetic
+	/// characters are appended and deleted.
+	#[test]
+	fn test_handle_edit()
+	{
+		let mut app = App::new(0, Dictionary::default());
+		assert_eq!(app.current_cell(), &Fragment::default());
+		// Test deleting from an empty cell.
+		app.process_key_event(KeyCode::Backspace.into());
+		assert_eq!(app.current_cell(), &Fragment::default());
+		// Test appending and deleting all alphabetic characters.
+		for c in 'a'..='z'
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+			assert_eq!(app.current_cell(), &Fragment::make(&c.to_string()));
+			app.process_key_event(KeyCode::Backspace.into());
+			assert_eq!(app.current_cell(), &Fragment::default());
+		}
+		// Test saturating the cell.
+		let mut s = String::new();
+		for c in 'a'..='t'
+		{
+			s.push(c);
+			app.process_key_event(KeyCode::Char(c).into());
+			// Take the first few characters from the string.
+			let s = s.chars().take(MAX_FRAGMENT_BYTES).collect::<String>();
+			assert_eq!(app.current_cell(), &Fragment::make(&s));
+		}
+		// The overflow is reported until the next key press.
+		assert_eq!(app.overflow, Some(0));
+		let area = Rect::new(0, 0, 100, 40);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let text = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(text.contains("Full: at most 15"), "{}", text);
+		app.process_key_event(KeyCode::Right.into());
+		assert_eq!(app.overflow, None);
+		app.process_key_event(KeyCode::Left.into());
+		// Test normalizing case and combining marks.
+		app.clear_all();
+		for c in ['C', 'a', 'f', 'e', '\u{301}']
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+		}
+		assert_eq!(app.current_cell(), &Fragment::make("café"));
+		// Test saturating the cell with multibyte characters.
+		for _ in 0..6
+		{
+			app.process_key_event(KeyCode::Char('é').into());
+		}
+		assert_eq!(app.current_cell(), &Fragment::make("caféééééé"));
+		// Test deleting multibyte characters.
+		for expected in
+			["cafééééé", "caféééé", "cafééé", "caféé", "café", "caf"]
+		{
+			app.process_key_event(KeyCode::Backspace.into());
+			assert_eq!(app.current_cell(), &Fragment::make(expected));
+		}
+	}
+
+	/// Ensure that the vim keymap distinguishes normal mode from insert mode.
+	#[test]
+	fn test_handle_vim()
+	{
+		let mut app = App::new(0, Dictionary::default())
+			.with_keymap(Keymap::new(KeymapPreset::Vim));
+		// In normal mode, letters move the cursor instead of editing.
+		app.process_key_event(KeyCode::Char('l').into());
+		assert_eq!(app.cursor, (1, 0));
+		assert_eq!(app.current_cell(), &Fragment::default());
+		// In insert mode, letters edit the current cell.
+		app.process_key_event(KeyCode::Char('i').into());
+		app.process_key_event(KeyCode::Char('l').into());
+		assert_eq!(app.current_cell(), &Fragment::make("l"));
+		// Escape returns to normal mode rather than exiting.
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(app.is_running());
+		app.process_key_event(KeyCode::Char('x').into());
+		assert_eq!(app.current_cell(), &Fragment::default());
+		app.process_key_event(KeyCode::Char('Z').into());
+		assert!(app.is_running());
+		app.process_key_event(KeyCode::Char('Z').into());
+		assert!(!app.is_running());
+	}
+
+	/// Ensure that the help overlay opens on demand, lists the bindings of the
+	/// current keymap, and closes on any key without performing its action.
+	#[test]
+	fn test_help()
+	{
+		let mut app = App::new(0, Dictionary::default());
+		app.process_key_event(KeyCode::F(1).into());
+		assert!(app.is_help_visible);
+		let entries = app.help_entries();
+		assert!(entries.contains(&("↵".to_string(), "solve")));
+		assert!(entries.contains(&("? F1".to_string(), "help")));
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(!app.is_help_visible);
+		assert!(app.is_running());
+
+		let mut app = App::new(0, Dictionary::default())
+			.with_keymap(Keymap::new(KeymapPreset::Vim));
+		app.process_key_event(KeyCode::Char('?').into());
+		assert!(app.is_help_visible);
+		let entries = app.help_entries();
+		assert!(entries.contains(&("ZZ".to_string(), "exit")));
+		assert!(!entries.iter().any(|(_, action)| *action == "type letter"));
+	}
+
+	/// Ensure that an interrupted solve is offered for resumption, and that
+	/// resuming it restores the board, even over prepopulated fragments, and
+	/// continues solving.
+	#[test]
+	fn test_resume()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		let path = dir.path().join("quartiles.state");
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let fragments = ["tru", "th"].repeat(10);
+		let mut app = App::new(0, dictionary.clone())
+			.with_state_file(path.clone())
+			.with_fragments(
+				std::array::from_fn(|i| Fragment::from(fragments[i]))
+			);
+		assert!(matches!(app.state, ExecutionState::Populating));
+		assert_eq!(app.cells[0], Fragment::from("tru"));
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(matches!(app.state, ExecutionState::Solving { .. }));
+		assert!(path.exists());
+
+		// Simulate a disconnect by abandoning the application.
+		let mut app = App::new(0, dictionary)
+			.with_state_file(path.clone())
+			.with_fragments([Fragment::from("zz"); 20]);
+		assert!(matches!(app.state, ExecutionState::Resuming { .. }));
+		assert_eq!(app.cells[1], Fragment::from("th"));
+		app.process_key_event(KeyCode::Char('y').into());
+		assert!(matches!(app.state, ExecutionState::Solving { .. }));
+		while matches!(
+			app.state,
+			ExecutionState::Solving { .. } | ExecutionState::Highlighting { .. }
+		)
+		{
+			app.process_systems();
+		}
+		assert!(matches!(app.state, ExecutionState::Finished { .. }));
+		assert!(!path.exists());
+	}
+
+	/// Ensure that a resumed solve keeps the configuration under which it was
+	/// interrupted, even if the settings have changed since, so that its whole
+	/// solution obeys the same rules.
+	#[test]
+	fn test_resume_config()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		let path = dir.path().join("quartiles.state");
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let config = SolverConfig { min_len: 8, ..Default::default() };
+		let mut app = App::new(0, dictionary.clone())
+			.with_solver_config(config)
+			.with_state_file(path.clone())
+			.with_fragments(BOARD.map(Fragment::from));
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(matches!(app.state, ExecutionState::Solving { .. }));
+
+		// Simulate a disconnect, and then resume under a laxer minimum length.
+		let mut app = App::new(0, dictionary)
+			.with_solver_config(SolverConfig::default())
+			.with_state_file(path);
+		assert!(matches!(app.state, ExecutionState::Resuming { .. }));
+		app.process_key_event(KeyCode::Char('y').into());
+		while matches!(
+			app.state,
+			ExecutionState::Solving { .. } | ExecutionState::Highlighting { .. }
+		)
+		{
+			app.process_systems();
+		}
+		let ExecutionState::Finished { ref solver, .. } = app.state
+		else
+		{
+			unreachable!()
+		};
+		assert_eq!(solver.config().min_len, 8);
+		let solution = solver.solution();
+		assert!(!solution.is_empty());
+		assert!(solution.iter().all(|word| word.chars().count() >= 8));
+	}
+
+	/// Ensure that paging through a long solution scrolls the solution list,
+	/// and that scrolling to the end resumes following the latest word.
+	#[test]
+	fn test_scroll()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let mut app = App::new(0, dictionary);
+		solve_board(&mut app, &mut ScriptedEvents::default());
+		let area = Rect::new(0, 0, 80, 12);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let height = app.viewport_height.get();
+		assert_eq!(height, 7);
+		assert!(app.is_following);
+
+		app.process_key_event(KeyCode::PageUp.into());
+		assert!(!app.is_following);
+		let offset = app.scroll_offset;
+		app.process_key_event(KeyCode::PageUp.into());
+		assert_eq!(app.scroll_offset, offset.saturating_sub(height));
+		app.process_key_event(KeyCode::PageDown.into());
+		app.process_key_event(KeyCode::PageDown.into());
+		assert!(app.is_following);
+	}
+
+	/// Ensure that the alternative placements of the selected word can be
+	/// cycled.
+	#[test]
+	fn test_placements()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let mut app = App::new(0, dictionary);
+		for (index, fragment) in ["tru", "th"].repeat(10).iter().enumerate()
+		{
+			app.cells[index] = Fragment::from(*fragment);
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		while matches!(
+			app.state,
+			ExecutionState::Solving { .. } | ExecutionState::Highlighting { .. }
+		)
+		{
+			app.process_systems();
+		}
+		assert_eq!(app.selected_placement(), None);
+		let truth = match app.state
+		{
+			ExecutionState::Finished { ref solver, .. } => app
+				.grouped_solution(solver).0
+				.iter()
+				.position(|word| word.as_str() == "truth")
+				.unwrap(),
+			_ => unreachable!()
+		};
+		for _ in 0..=truth
+		{
+			app.process_key_event(KeyCode::Down.into());
+		}
+		let (first, index, count) = app.selected_placement().unwrap();
+		assert_eq!((index, count), (0, 100));
+		app.process_key_event(KeyCode::Char(' ').into());
+		let (second, index, _) = app.selected_placement().unwrap();
+		assert_eq!(index, 1);
+		assert_ne!(first, second);
+		for _ in 1..count
+		{
+			app.process_key_event(KeyCode::Char(' ').into());
+		}
+		assert_eq!(app.selected_placement(), Some((first, 0, count)));
+	}
+
+	/// Ensure that the status bar reports the statistics of a finished solve.
+	#[test]
+	fn test_status_bar()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let mut app = App::new(0, dictionary).with_dictionary_name("english");
+		solve_board(&mut app, &mut ScriptedEvents::default());
+		assert!(app.solve_duration.is_some());
+		let area = Rect::new(0, 0, 120, 1);
+		let mut buf = Buffer::empty(area);
+		app.render_status_bar(area, &mut buf);
+		let status = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(status.contains("Finished"), "{}", status);
+		assert!(status.contains("5/5 quartiles"), "{}", status);
+		assert!(status.contains("english ("), "{}", status);
+	}
+
+	/// Ensure that the application can be driven end to end without a
+	/// terminal: type the board, press Enter, wait out the highlights on a fake
+	/// clock, and verify the solution list.
+	#[test]
+	fn test_headless()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let clock = FakeClock::default();
+		let mut app = App::new(1_000, dictionary)
+			.with_clock(Rc::new(clock.clone()));
+		let mut events = ScriptedEvents::default();
+		type_board(&mut events);
+		while !events.is_empty()
+		{
+			app.tick(&mut events).unwrap();
+		}
+		assert_eq!(app.cells.map(|cell| cell.to_string()), BOARD);
+		assert!(matches!(app.state, ExecutionState::Solving { .. }));
+
+		// The highlighter waits on the fake clock, so the solve only makes
+		// progress when the clock is advanced.
+		while !matches!(app.state, ExecutionState::Highlighting { .. })
+		{
+			app.tick(&mut events).unwrap();
+		}
+		app.tick(&mut events).unwrap();
+		assert!(matches!(app.state, ExecutionState::Highlighting { .. }));
+		while !matches!(app.state, ExecutionState::Finished { .. })
+		{
+			clock.advance(Duration::from_secs(1));
+			app.tick(&mut events).unwrap();
+		}
+		let area = Rect::new(0, 0, 100, 40);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let screen = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		for word in ["truthfully", "crosswords", "razzmatazz"]
+		{
+			assert!(screen.contains(word), "{}: {}", word, screen);
+		}
+	}
+
+	/// Ensure that, with reduced motion, the solver never pauses to highlight
+	/// a word, but streams each word into the solution list as soon as it is
+	/// found, so the solve finishes without the clock ever advancing.
+	#[test]
+	fn test_reduced_motion()
+	{
+		let dictionary = ["this", "truth", "truthfully"]
+			.into_iter()
+			.collect::<Dictionary>();
+		let clock = FakeClock::default();
+		let mut app = App::new(1_000, dictionary)
+			.with_clock(Rc::new(clock.clone()))
+			.with_reduced_motion(true);
+		let screen = |app: &App| {
+			let area = Rect::new(0, 0, 100, 40);
+			let mut buf = Buffer::empty(area);
+			app.render(area, &mut buf);
+			buf.content()
+				.iter()
+				.map(|cell| cell.symbol())
+				.collect::<String>()
+		};
+		let mut events = ScriptedEvents::default();
+		type_board(&mut events);
+		let mut streamed = Vec::new();
+		while !matches!(app.state, ExecutionState::Finished { .. })
+		{
+			app.tick(&mut events).unwrap();
+			assert!(
+				!matches!(app.state, ExecutionState::Highlighting { .. }),
+				"Highlighting with reduced motion"
+			);
+			if let ExecutionState::Solving { ref solver } = app.state
+			{
+				let solution = solver.solution();
+				if solution.len() > streamed.len()
+				{
+					// The words found so far are listed while the solver
+					// still runs.
+					let screen = screen(&app);
+					for word in &solution
+					{
+						assert!(screen.contains(word.as_str()), "{}", screen);
+					}
+					streamed = solution;
+				}
+			}
+		}
+		assert!(!streamed.is_empty());
+		let ExecutionState::Finished { ref solver, .. } = app.state
+		else
+		{
+			unreachable!()
+		};
+		assert_eq!(solver.solution().len(), 3);
+		let screen = screen(&app);
+		for word in ["this", "truth", "truthfully"]
+		{
+			assert!(screen.contains(word), "{}", screen);
+		}
+	}
+
+	/// Ensure that a solver that runs out of time stops where it is, and
+	/// that its outcome is truncated rather than abandoned, unlike that of a
+	/// cancelled solver.
+	#[test]
+	fn test_solve_budget()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let clock = FakeClock::default();
+		let mut app = App::new(0, dictionary)
+			.with_clock(Rc::new(clock.clone()))
+			.with_solve_budget(Some(Duration::from_secs(5)));
+		let mut events = ScriptedEvents::default();
+		type_board(&mut events);
+		app.tick(&mut events).unwrap();
+		assert!(matches!(
+			app.state,
+			ExecutionState::Solving { .. } | ExecutionState::Highlighting { .. }
+		));
+		clock.advance(Duration::from_secs(5));
+		finish_solve(&mut app, &mut events);
+		let ExecutionState::Finished {
+			ref solver,
+			is_solved,
+			is_truncated,
+			ref completeness,
+			..
+		} = app.state
+		else
+		{
+			unreachable!()
+		};
+		assert!(!solver.is_finished());
+		assert!(!is_solved);
+		assert!(is_truncated);
+		assert!(completeness.is_none());
+		app.exit();
+		let ExecutionState::Exiting { ref outcome } = app.state
+		else
+		{
+			unreachable!()
+		};
+		assert!(outcome.is_truncated);
+		assert!(!outcome.is_finished);
+		assert_eq!(outcome.exit_status(), EXIT_UNSOLVED);
+		let json = serde_json::to_value(outcome).unwrap();
+		assert_eq!(json["truncated"], true);
+
+		// A solver that is cancelled also stops early, but its outcome is
+		// abandoned rather than truncated.
+		let token = CancellationToken::new();
+		token.cancel();
+		let solver = app.build_solver().with_cancellation(token).solve_fully();
+		let outcome = Outcome::of(&solver);
+		assert!(!outcome.is_truncated);
+		assert_eq!(outcome.exit_status(), EXIT_ABORTED);
+
+		// A budget too large to represent as a deadline is no budget at all,
+		// so the solver runs to completion.
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let mut app = App::new(0, dictionary)
+			.with_clock(Rc::new(clock.clone()))
+			.with_solve_budget(Some(Duration::from_secs(u64::MAX)));
+		let mut events = ScriptedEvents::default();
+		solve_board(&mut app, &mut events);
+		let ExecutionState::Finished { is_solved, is_truncated, .. } = app.state
+		else
+		{
+			unreachable!()
+		};
+		assert!(is_solved);
+		assert!(!is_truncated);
+	}
+
+	/// Ensure that the application runs against an in-memory backend, that
+	/// it handles a burst of events at once, and that its frames reach the
+	/// backend.
+	#[test]
+	fn test_run_with_backend()
+	{
+		let mut tui = Tui::new(TestBackend::new(100, 40)).unwrap();
+		let mut events = ScriptedEvents::default();
+		events.type_text("tru");
+		events.press(KeyCode::Esc);
+		events.press(KeyCode::Esc);
+		let outcome = App::new(0, Dictionary::default())
+			.run_with(&mut tui, &mut events)
+			.unwrap();
+		assert!(events.is_empty());
+		assert!(outcome.solution.is_empty());
+		let screen = tui.backend()
+			.buffer()
+			.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(!screen.trim().is_empty());
+	}
+
+	/// Ensure that the application only counts as busy while the solver runs,
+	/// and as animating while a timed round counts down.
+	#[test]
+	fn test_idle()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let fragments = BOARD.map(Fragment::from);
+		let mut app = App::new(0, dictionary.clone()).with_fragments(fragments);
+		assert!(!app.is_working());
+		assert!(!app.is_animating());
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(app.is_working());
+		while app.is_working()
+		{
+			app.process_systems();
+		}
+		assert!(matches!(app.state, ExecutionState::Finished { .. }));
+		assert!(!app.is_animating());
+
+		let mut app = App::new(0, dictionary)
+			.with_fragments(fragments)
+			.with_time_limit(Duration::from_secs(300));
+		app.open_play(1);
+		assert!(!app.is_working());
+		assert!(app.is_animating());
+	}
+
+	/// Ensure that the bulk-entry modal splits its text into fragments,
+	/// rejects invalid text, and assigns valid text to the board.
+	#[test]
+	fn test_bulk_entry()
+	{
+		let mut app = App::new(0, Dictionary::default());
+		app.process_key_event(
+			KeyEvent::new(KeyCode::Char('b'), KeyModifiers::CONTROL)
+		);
+		assert!(app.bulk_entry.is_some());
+		// Keys edit the text, rather than the board or the cursor.
+		for code in [KeyCode::Char('Z'), KeyCode::Char('z'), KeyCode::Left]
+		{
+			app.process_key_event(code.into());
+		}
+		app.process_key_event(KeyCode::Backspace.into());
+		assert_eq!(app.bulk_entry.as_ref().unwrap().text, "Z");
+		assert_eq!(app.cursor, (0, 0));
+		assert!(app.cells[0].is_empty());
+		app.process_key_event(KeyCode::Enter.into());
+		assert_eq!(
+			app.bulk_entry.as_ref().unwrap().error.as_deref(),
+			Some("Expected 20 fragments, found 1")
+		);
+		app.process_paste(", 2x azzurro");
+		app.process_key_event(KeyCode::Enter.into());
+		assert_eq!(
+			app.bulk_entry.as_ref().unwrap().error.as_deref(),
+			Some("Invalid fragment: 2x")
+		);
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(app.bulk_entry.is_none());
+		assert!(app.is_running());
+
+		// Overlong fragments are reported, rather than truncated.
+		app.process_paste("abcdefghijklmnop tru");
+		app.process_key_event(KeyCode::Enter.into());
+		assert_eq!(
+			app.bulk_entry.as_ref().unwrap().error.as_deref(),
+			Some("Fragment too long: abcdefghijklmnop (at most 15 bytes)")
+		);
+		app.process_key_event(KeyCode::Esc.into());
+
+		// Pasting several fragments opens the modal by itself.
+		app.process_paste("AZZ th ss tru ref\nfu ra nih cro mat\n");
+		app.process_paste("wo sh re rds tic\r\nil lly zz is ment");
+		let area = Rect::new(0, 0, 100, 40);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let text = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(text.contains("20/20 fragments"), "{}", text);
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(app.bulk_entry.is_none());
+		assert_eq!(app.cells[0].as_str(), "azz");
+		assert_eq!(app.cells[19].as_str(), "ment");
+		assert!(app.is_populated());
+
+		// Pasting a single fragment types it into the current cell.
+		app.clear();
+		app.process_paste("azz");
+		assert!(app.bulk_entry.is_none());
+		assert_eq!(app.cells[0].as_str(), "azz");
+	}
+
+	/// Ensure that the explain modal explains a word against the board,
+	/// suggests the closest formable words, and keeps keys off the board.
+	#[test]
+	fn test_explain()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let fragments = BOARD.map(Fragment::from);
+		let mut app = App::new(0, dictionary).with_fragments(fragments);
+		app.process_key_event(
+			KeyEvent::new(KeyCode::Char('e'), KeyModifiers::CONTROL)
+		);
+		assert!(app.explain.is_some());
+		for c in "Truthfuly".chars()
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		let (word, explanation, closest) =
+			app.explain.as_ref().unwrap().result.clone().unwrap();
+		assert_eq!(word, "truthfuly");
+		let Explanation::NotInDictionary { ref near } = explanation
+		else
+		{
+			panic!("Explained as {:?}", explanation)
+		};
+		assert!(near.iter().any(|word| word == "truthfully"));
+		assert_eq!(closest.first().map(String::as_str), Some("truthfully"));
+		assert_eq!(app.cells, fragments);
+
+		for _ in 0..9
+		{
+			app.process_key_event(KeyCode::Backspace.into());
+		}
+		app.process_paste("xylophone\n");
+		let area = Rect::new(0, 0, 100, 40);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let text = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(text.contains("Why not this word?"), "{}", text);
+		assert!(text.contains("xylophone: in the dictionary"), "{}", text);
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(app.explain.is_none());
+		assert!(app.is_running());
+	}
+
+	/// Ensure that an unsolved puzzle is analyzed when the solver finishes,
+	/// that the analysis is shown beneath the board, and that it accompanies
+	/// the solution on exit.
+	#[test]
+	fn test_completeness()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		// Misenter the last fragment of "refreshment".
+		let mut fragments = BOARD.map(Fragment::from);
+		fragments[19] = Fragment::from("mant");
+		let mut app = App::new(0, dictionary).with_fragments(fragments);
+		app.process_key_event(KeyCode::Enter.into());
+		while !matches!(app.state, ExecutionState::Finished { .. })
+		{
+			app.process_systems();
+		}
+		let ExecutionState::Finished {
+			is_solved: false,
+			completeness: Some(ref completeness),
+			..
+		} = app.state
+		else
+		{
+			panic!("expected an unsolved puzzle");
+		};
+		assert_eq!(completeness.quartiles, 4);
+		assert_eq!(completeness.unused, ["ref", "sh", "re", "mant"]);
+		assert!(completeness.near_misses.len() <= MAX_NEAR_MISSES);
+		let area = Rect::new(0, 0, 100, 40);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let text = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(text.contains("Quartiles found: 4/5"), "{}", text);
+		assert!(text.contains("Quartiles (4)"), "{}", text);
+		assert!(text.contains("Bonus words ("), "{}", text);
+		assert!(text.contains("Unused: ref sh re mant"), "{}", text);
+
+		let completeness = completeness.clone();
+		app.process_key_event(KeyCode::Esc.into());
+		let ExecutionState::Exiting { ref outcome } = app.state
+		else
+		{
+			panic!("expected to exit");
+		};
+		assert!(!outcome.solution.is_solved);
+		assert!(outcome.is_finished);
+		assert_eq!(outcome.exit_status(), EXIT_UNSOLVED);
+		assert!(!outcome.solution.is_empty());
+		assert_eq!(outcome.solution.quartiles.len(), 4);
+		let bonus_words = outcome.solution.bonus_words.len();
+		assert_ne!(bonus_words, 0);
+		let display = outcome.solution.to_string();
+		assert!(display.starts_with("Quartiles (4)\n"), "{}", display);
+		assert!(
+			display.contains(&format!("\n\nBonus words ({})\n", bonus_words)),
+			"{}",
+			display
+		);
+		assert_eq!(outcome.completeness, Some(completeness));
+
+		// Solving without the TUI reaches the same outcome, but for the
+		// metrics and the timing.
+		assert!(outcome.solution.duration_ms.is_some());
+		let headless = Outcome::of(&app.build_solver().solve_fully())
+			.with_duration(app.solve_duration);
+		assert_eq!(
+			Outcome { metrics: None, ..headless },
+			Outcome { metrics: None, ..outcome.clone() }
+		);
+	}
+
+	/// Ensure that the sort order cycles through every order, sorting each
+	/// section of the solution separately, and that the selected word stays
+	/// selected.
+	#[test]
+	fn test_sort()
 	{
-		/// The solver for the puzzle.
-		solution: Vec<String>
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let mut app = App::new(0, dictionary);
+		solve_board(&mut app, &mut ScriptedEvents::default());
+		for _ in 0..8
+		{
+			app.process_key_event(KeyCode::Down.into());
+		}
+		let selected = app.selected_word().unwrap();
+		let sorted = |app: &App| {
+			let ExecutionState::Finished { ref solver, .. } = app.state
+			else
+			{
+				panic!("expected a finished solve");
+			};
+			let (words, quartiles) = app.grouped_solution(solver);
+			assert_eq!(quartiles, 5);
+			let bonus_words = words[quartiles..]
+				.iter()
+				.map(|word| word.to_string())
+				.collect::<Vec<_>>();
+			(bonus_words, solver.solution_deduped().len())
+		};
+		let (discovery, len) = sorted(&app);
+		let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+		app.process_key_event(ctrl('s'));
+		assert_eq!(app.sort_order, SortOrder::Alphabetical);
+		assert_eq!(app.selected_word().as_ref(), Some(&selected));
+		let (alphabetical, _) = sorted(&app);
+		assert!(alphabetical.windows(2).all(|w| w[0] < w[1]));
+		app.process_key_event(ctrl('s'));
+		assert_eq!(app.sort_order, SortOrder::Length);
+		assert_eq!(app.selected_word().as_ref(), Some(&selected));
+		let (length, _) = sorted(&app);
+		assert!(length.windows(2).all(|w| w[0].len() >= w[1].len()));
+		app.process_key_event(ctrl('s'));
+		assert_eq!(app.sort_order, SortOrder::Score);
+		assert_eq!(app.selected_word().as_ref(), Some(&selected));
+		app.process_key_event(ctrl('s'));
+		assert_eq!(app.sort_order, SortOrder::Discovery);
+		assert_eq!(sorted(&app), (discovery, len));
+		assert_eq!(app.selected_word(), Some(selected));
 	}
-}
 
-////////////////////////////////////////////////////////////////////////////////
-//                                   Tests.                                   //
-////////////////////////////////////////////////////////////////////////////////
+	/// Ensure that a replay highlights the words of the solution in order of
+	/// discovery at an adjustable speed, that it is recorded as an asciicast
+	/// when frames are drawn, and that it ends by itself.
+	#[test]
+	fn test_replay()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		let record_file = dir.path().join("replay.cast");
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let clock = FakeClock::default();
+		let mut app = App::new(0, dictionary)
+			.with_clock(Rc::new(clock.clone()))
+			.with_record_file(record_file.clone());
+		solve_board(&mut app, &mut ScriptedEvents::default());
+		assert!(!app.is_animating());
+		let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+		app.process_key_event(ctrl('p'));
+		assert!(app.is_animating());
+		assert!(app.recorder.is_some());
+		let step = |app: &App| match app.state
+		{
+			ExecutionState::Finished {
+				ref solver,
+				replay: Some(ref replay),
+				..
+			} => Some((replay.step(), solver.word(replay.path()).to_string())),
+			_ => None
+		};
+		let ExecutionState::Finished { ref solver, .. } = app.state
+		else
+		{
+			unreachable!()
+		};
+		let words = solver.solution_deduped()
+			.iter()
+			.map(|word| word.to_string())
+			.collect::<Vec<_>>();
+		assert_eq!(step(&app), Some((0, words[0].clone())));
+		let mut tui = Tui::new(TestBackend::new(100, 40)).unwrap();
+		let frame = tui.draw(|frame| app.render_frame(frame)).unwrap();
+		app.recorder.as_mut().unwrap().record(frame.buffer, clock.now())
+			.unwrap();
+		let screen = frame.buffer.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(
+			screen.contains(&format!("▶ 1/{}", words.len())),
+			"{}",
+			screen
+		);
+		clock.advance(Duration::from_millis(800));
+		app.process_systems();
+		assert_eq!(step(&app), Some((1, words[1].clone())));
+		// Speeding up cuts the current step short.
+		app.process_key_event(KeyCode::Right.into());
+		clock.advance(Duration::from_millis(400));
+		app.process_systems();
+		assert_eq!(step(&app), Some((2, words[2].clone())));
+		// Stopping the replay finishes the recording.
+		app.process_key_event(ctrl('p'));
+		assert_eq!(step(&app), None);
+		assert!(app.recorder.is_none());
+		let recording = std::fs::read_to_string(&record_file).unwrap();
+		assert_eq!(recording.lines().count(), 2, "{}", recording);
+		assert!(recording.starts_with("{"), "{}", recording);
+		// Left alone, a replay ends after its last word.
+		app.process_key_event(ctrl('p'));
+		clock.advance(Duration::from_secs(60));
+		app.process_systems();
+		assert_eq!(step(&app), None);
+		assert!(!app.is_animating());
+	}
 
-#[cfg(test)]
-mod test
-{
-	use super::*;
+	/// Ensure that an accessible TUI announces the focused cell, the result of
+	/// the solve, and the selected word in words, and marks the cursor and
+	/// the selection with symbols; and that an ordinary TUI doesn't.
+	#[test]
+	fn test_accessible()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let fragments = BOARD.map(Fragment::from);
+		let mut app = App::new(0, dictionary.clone())
+			.with_fragments(fragments);
+		app.process_systems();
+		assert_eq!(app.announcement, None);
+		let mut app = App::new(0, dictionary)
+			.with_fragments(fragments)
+			.with_accessible(true);
+		app.process_systems();
+		assert_eq!(
+			app.announcement.as_deref(),
+			Some("Row 1, column 1: “azz”. 20 of 20 cells filled.")
+		);
+		app.process_key_event(KeyCode::Down.into());
+		app.process_key_event(KeyCode::Backspace.into());
+		app.process_systems();
+		assert_eq!(
+			app.announcement.as_deref(),
+			Some("Row 2, column 1: “re”. 20 of 20 cells filled.")
+		);
+		let mut tui = Tui::new(TestBackend::new(100, 40)).unwrap();
+		let frame = tui.draw(|frame| app.render_frame(frame)).unwrap();
+		let row = |buffer: &Buffer, y| {
+			(0..buffer.area.width)
+				.map(|x| buffer[(x, y)].symbol())
+				.collect::<String>()
+		};
+		let announcement = row(frame.buffer, 38);
+		assert!(announcement.starts_with(" Row 2, column 1: “re”."));
+		let screen = (0..40).map(|y| row(frame.buffer, y)).collect::<String>();
+		assert!(screen.contains("╭▶"), "{}", screen);
+		// Restore the fragment, and solve the puzzle.
+		app.process_key_event(KeyCode::Char('f').into());
+		app.process_key_event(KeyCode::Enter.into());
+		while !matches!(app.state, ExecutionState::Finished { .. })
+		{
+			app.process_systems();
+		}
+		app.process_systems();
+		let announcement = app.announcement.clone().unwrap();
+		assert!(
+			announcement.starts_with("Solved: 5 of 5 quartiles, "),
+			"{}",
+			announcement
+		);
+		app.process_key_event(KeyCode::Down.into());
+		app.process_systems();
+		let announcement = app.announcement.clone().unwrap();
+		let word = app.selected_word().unwrap();
+		assert!(
+			announcement.starts_with("Word 1 of "),
+			"{}",
+			announcement
+		);
+		assert!(
+			announcement.contains(&format!("{}, a quartile, ", word)),
+			"{}",
+			announcement
+		);
+		assert_eq!(announcement.matches(" at row ").count(), 4);
+		let frame = tui.draw(|frame| app.render_frame(frame)).unwrap();
+		let screen = (0..40).map(|y| row(frame.buffer, y)).collect::<String>();
+		assert!(screen.contains(&format!("▶ {}", word)), "{}", screen);
+	}
 
-	/// Ensure that the application exits when the escape key is pressed.
+	/// Ensure that a share card can be exported while playing and while
+	/// reviewing a solve, that it names the generated puzzle by its seed, and
+	/// that the most recent card is handed over in the outcome.
 	#[test]
-	fn test_handle_exit()
+	fn test_export_card()
 	{
-		let mut app = App::new(0, Dictionary::default());
-		assert!(app.is_running());
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let fragments = BOARD.map(Fragment::from);
+		let provenance = Provenance { fragments, seed: 7, daily: None };
+		let mut app = App::new(0, dictionary).with_provenance(provenance);
+		let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+		app.open_play(1);
+		for c in "truthfully".chars()
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		app.process_key_event(ctrl('y'));
+		let Screen::Play { ref game, .. } = app.screen
+		else
+		{
+			panic!("Not playing")
+		};
+		assert_eq!(
+			app.card,
+			Some(Card {
+				date: None,
+				seed: Some(7),
+				quartiles: 1,
+				words: 1,
+				total_words: Some(game.word_count()),
+				score: 8,
+				max_score: Some(game.max_score())
+			})
+		);
+		assert!(!app.card.as_ref().unwrap().to_string().contains("truth"));
+		app.screen = Screen::Solve;
+		app.process_key_event(KeyCode::Enter.into());
+		while !matches!(app.state, ExecutionState::Finished { .. })
+		{
+			app.process_systems();
+		}
+		app.process_key_event(ctrl('y'));
+		let ExecutionState::Finished { ref solver, .. } = app.state
+		else
+		{
+			panic!("expected a finished solve");
+		};
+		let card = app.card.clone().unwrap();
+		assert_eq!(card.seed, Some(7));
+		assert_eq!(card.quartiles, 5);
+		assert_eq!(card.words, solver.solution_deduped().len());
+		assert_eq!(card.score, Solution::of(solver).score);
+		app.exit();
+		assert_eq!(app.into_outcome().unwrap().card, Some(card));
+	}
+
+	/// Ensure that a reviewed word can be marked as a favorite, which flags
+	/// it in the solution and persists it, and as bogus, which removes it
+	/// from the solution and the dictionary, and persists the exclusion.
+	#[test]
+	fn test_curation()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		let favorites_file = dir.path().join("favorites.txt");
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let mut app = App::new(0, dictionary)
+			.with_dictionary_name("english")
+			.with_dictionary_directory(dir.path().to_path_buf())
+			.with_favorites_file(favorites_file.clone());
+		solve_board(&mut app, &mut ScriptedEvents::default());
+		let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+		// Nothing is selected yet.
+		app.process_key_event(ctrl('f'));
+		assert!(app.favorites.is_empty());
+		app.process_key_event(KeyCode::Down.into());
+		let first = app.selected_word().unwrap();
+		app.process_key_event(ctrl('f'));
+		assert_eq!(
+			fs::read_to_string(&favorites_file).unwrap(),
+			format!("{}\n", first)
+		);
+		let ExecutionState::Finished { ref solver, .. } = app.state
+		else
+		{
+			panic!("expected a finished solve");
+		};
+		let list = app.solution_list(solver);
+		assert!(list[1].to_string().contains(" ♥ "), "{}", list[1]);
+		let app = app.with_favorites_file(favorites_file.clone());
+		assert!(app.favorites.contains(&first));
+		let mut app = app;
+		app.process_key_event(ctrl('f'));
+		assert!(app.favorites.is_empty());
+
+		app.process_key_event(ctrl('x'));
+		let ExecutionState::Finished { ref solver, highlight, .. } = app.state
+		else
+		{
+			panic!("expected a finished solve");
+		};
+		assert!(!solver.solution_deduped().iter().any(|w| w.as_str() == first));
+		assert_eq!(highlight, Some(0));
+		assert!(!app.dictionary.contains(&first));
+		assert_eq!(
+			fs::read_to_string(dir.path().join("english.exclude.txt"))
+				.unwrap(),
+			format!("{}\n", first)
+		);
+	}
+
+	/// Ensure that the outcome names the source of each word that an
+	/// inclusion list supplied, and only of those.
+	#[test]
+	fn test_outcome_sources()
+	{
+		let mut dictionary = Dictionary::open("dict", "english").unwrap();
+		dictionary.exclude(["truthfully"]);
+		dictionary.include(["truthfully", "truth"], Source::Custom);
+		let fragments = BOARD.map(Fragment::from);
+		let solver = Solver::new(Rc::new(dictionary), fragments).solve_fully();
+		let outcome = Outcome::of(&solver);
+		assert!(outcome.solution.is_solved);
+		assert!(outcome.solution.words().any(|word| word == "truth"));
+		assert_eq!(
+			outcome.sources,
+			BTreeMap::from([("truthfully".to_string(), Source::Custom)])
+		);
+	}
+
+	/// Ensure that the quartiles of the cover are flagged when more than 5
+	/// quartiles are found, and that they accompany the solution on exit.
+	#[test]
+	fn test_cover()
+	{
+		let fragments = [
+			"ab", "ac", "ad", "af", "ag", "ah", "aj", "ak", "al", "am",
+			"an", "ap", "aq", "ar", "as", "at", "av", "aw", "ax", "ay"
+		].map(Fragment::from);
+		let quartile = |indices: [usize; 4]| {
+			indices.iter()
+				.map(|&index| fragments[index].as_str())
+				.collect::<String>()
+		};
+		// The first row forms a quartile, but the columns form the cover.
+		let dictionary = [
+			[0, 1, 2, 3],
+			[0, 4, 8, 12],
+			[1, 5, 9, 13],
+			[2, 6, 10, 14],
+			[3, 7, 11, 15],
+			[16, 17, 18, 19]
+		]
+			.map(quartile)
+			.into_iter()
+			.collect::<Dictionary>();
+		let mut app = App::new(0, dictionary).with_fragments(fragments);
+		app.process_key_event(KeyCode::Enter.into());
+		while !matches!(app.state, ExecutionState::Finished { .. })
+		{
+			app.process_systems();
+		}
+		let area = Rect::new(0, 0, 100, 40);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let text = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert_eq!(text.matches('★').count(), 5, "{}", text);
+		assert!(text.contains(&format!("{} ★ 8p ×4", quartile([0, 4, 8, 12]))));
+		assert!(text.contains(&format!("{} ☆ 8p ×4", quartile([0, 1, 2, 3]))));
+
 		app.process_key_event(KeyCode::Esc.into());
-		assert!(!app.is_running());
+		let ExecutionState::Exiting { ref outcome } = app.state
+		else
+		{
+			panic!("expected to exit");
+		};
+		assert!(outcome.solution.is_solved);
+		assert_eq!(outcome.exit_status(), EXIT_SOLVED);
+		assert_eq!(outcome.solution.quartiles.len(), 6);
+		assert!(outcome.solution.bonus_words.is_empty());
+		assert_eq!(outcome.solution.score, 6 * 8);
+		assert_eq!(
+			outcome.solution.paths[&quartile([0, 4, 8, 12])],
+			[0, 4, 8, 12].map(|index| fragments[index].to_string())
+		);
+		let quartiles = &outcome.solution.quartiles;
+		assert!(quartiles.windows(2).all(|w| w[0] < w[1]));
+		assert_eq!(
+			outcome.solution.to_string().lines().collect::<Vec<_>>(),
+			[&["Quartiles (6)".to_string()], quartiles.as_slice()].concat()
+		);
+		assert_eq!(outcome.cover.as_ref().map(Vec::len), Some(5));
+		assert_eq!(outcome.coordinates.len(), 6);
+		assert_eq!(
+			outcome.coordinates[&quartile([0, 4, 8, 12])],
+			vec![(0, 0), (1, 0), (2, 0), (3, 0)]
+		);
+		assert_eq!(
+			outcome.coordinates[&quartile([16, 17, 18, 19])],
+			vec![(4, 0), (4, 1), (4, 2), (4, 3)]
+		);
 	}
 
-	/// Ensure that the cursor moves up, down, left, and right when the
-	/// corresponding arrow keys are pressed. Test all possible cursor
-	/// movements.
+	/// Ensure that cells can be swapped with their neighbors from the
+	/// keyboard, and dragged onto one another with the mouse.
 	#[test]
-	fn test_handle_arrows()
+	fn test_swap()
 	{
 		let mut app = App::new(0, Dictionary::default());
+		app.cells[0] = Fragment::from("azz");
+		app.cells[1] = Fragment::from("th");
+		let shift = |code| KeyEvent::new(code, KeyModifiers::SHIFT);
+		app.process_key_event(shift(KeyCode::Right));
+		assert_eq!(app.cells[0].as_str(), "th");
+		assert_eq!(app.cells[1].as_str(), "azz");
+		assert_eq!(app.cursor, (1, 0));
+		app.process_key_event(shift(KeyCode::Down));
+		assert_eq!(app.cells[5].as_str(), "azz");
+		assert!(app.cells[1].is_empty());
+		// Swapping off the edge of the grid does nothing.
+		app.process_key_event(shift(KeyCode::Left));
+		app.process_key_event(shift(KeyCode::Left));
+		assert_eq!(app.cells[4].as_str(), "azz");
+		assert_eq!(app.cursor, (0, 1));
+
+		// The mouse can only find the cells once they have been rendered.
+		let area = Rect::new(0, 0, 100, 40);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let areas = app.cell_areas.get();
+		let mouse = |kind, area: Rect| MouseEvent {
+			kind,
+			column: area.x + 1,
+			row: area.y + 1,
+			modifiers: KeyModifiers::NONE
+		};
+		let left = MouseButton::Left;
+		app.process_mouse_event(mouse(MouseEventKind::Down(left), areas[0]));
 		assert_eq!(app.cursor, (0, 0));
-		// Test all possible cursor movements. Each case is a tuple of the
-		// initial cursor position and the expected cursor position after
-		// moving up, right, down, and left, respectively.
-		let cases = vec![
-			((0, 0), [(0, 0), (1, 0), (0, 1), (0, 0)]),
-			((0, 1), [(0, 0), (1, 1), (0, 2), (0, 1)]),
-			((0, 2), [(0, 1), (1, 2), (0, 3), (0, 2)]),
-			((0, 3), [(0, 2), (1, 3), (0, 4), (0, 3)]),
-			((0, 4), [(0, 3), (1, 4), (0, 4), (0, 4)]),
-			((1, 0), [(1, 0), (2, 0), (1, 1), (0, 0)]),
-			((1, 1), [(1, 0), (2, 1), (1, 2), (0, 1)]),
-			((1, 2), [(1, 1), (2, 2), (1, 3), (0, 2)]),
-			((1, 3), [(1, 2), (2, 3), (1, 4), (0, 3)]),
-			((1, 4), [(1, 3), (2, 4), (1, 4), (0, 4)]),
-			((2, 0), [(2, 0), (3, 0), (2, 1), (1, 0)]),
-			((2, 1), [(2, 0), (3, 1), (2, 2), (1, 1)]),
-			((2, 2), [(2, 1), (3, 2), (2, 3), (1, 2)]),
-			((2, 3), [(2, 2), (3, 3), (2, 4), (1, 3)]),
-			((2, 4), [(2, 3), (3, 4), (2, 4), (1, 4)]),
-			((3, 0), [(3, 0), (3, 0), (3, 1), (2, 0)]),
-			((3, 1), [(3, 0), (3, 1), (3, 2), (2, 1)]),
-			((3, 2), [(3, 1), (3, 2), (3, 3), (2, 2)]),
-			((3, 3), [(3, 2), (3, 3), (3, 4), (2, 3)]),
-			((3, 4), [(3, 3), (3, 4), (3, 4), (2, 4)]),
-		];
-		for (initial, expected) in cases
+		app.process_mouse_event(mouse(MouseEventKind::Drag(left), areas[19]));
+		assert_eq!(app.cursor, (3, 4));
+		app.process_mouse_event(mouse(MouseEventKind::Up(left), areas[19]));
+		assert!(app.cells[0].is_empty());
+		assert_eq!(app.cells[19].as_str(), "th");
+		assert_eq!(app.cursor, (3, 4));
+		// A click merely selects a cell.
+		app.process_mouse_event(mouse(MouseEventKind::Down(left), areas[4]));
+		app.process_mouse_event(mouse(MouseEventKind::Up(left), areas[4]));
+		assert_eq!(app.cells[4].as_str(), "azz");
+		assert_eq!(app.cursor, (0, 1));
+	}
+
+	/// Ensure that an impossible fragment is flagged while populating, once
+	/// the board settles, and that the suggestions panel offers completions of
+	/// the current fragment.
+	#[test]
+	fn test_suggestions()
+	{
+		let dictionary = ["truss", "trust", "tryst"]
+			.into_iter()
+			.collect::<Dictionary>();
+		let clock = FakeClock::default();
+		let mut app = App::new(0, dictionary)
+			.with_clock(Rc::new(clock.clone()));
+		app.cells[1] = Fragment::from("zz");
+		app.append('t');
+		app.process_systems();
+		app.append('r');
+		app.process_systems();
+		// The board hasn't settled, so nothing has been scanned yet.
+		assert!(app.is_animating());
+		assert!(app.advisor.suggestions().is_empty());
+		assert!(!app.advisor.is_impossible(1));
+		clock.advance(ADVISOR_DELAY);
+		app.process_systems();
+		assert!(!app.is_animating());
+		let area = Rect::new(0, 0, 100, 40);
+		let mut buf = Buffer::empty(area);
+		app.render_populating(area, &mut buf);
+		let text = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(text.contains("Suggestions"));
+		assert!(text.contains("trus"));
+		assert!(!text.contains("No word contains"));
+		let warning = buf.content()
+			.iter()
+			.filter(|cell| Some(cell.fg) == app.theme.warning.fg)
+			.count();
+		// Only the border of the impossible fragment's tile is a warning.
+		assert!(warning > 0);
+
+		app.move_cursor(1, 0);
+		app.process_systems();
+		clock.advance(ADVISOR_DELAY);
+		app.process_systems();
+		let mut buf = Buffer::empty(area);
+		app.render_populating(area, &mut buf);
+		let text = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		// The warning wraps within the narrow panel.
+		assert!(text.contains("No word"));
+		assert!(text.contains("“zz”"));
+		assert!(!text.contains("trus"));
+	}
+
+	/// Ensure that the progress summary counts the words and quartiles found
+	/// so far.
+	#[test]
+	fn test_progress_summary()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let mut app = App::new(0, dictionary);
+		let mut events = ScriptedEvents::default();
+		type_board(&mut events);
+		while !events.is_empty()
 		{
-			app.cursor = initial;
-			app.process_key_event(KeyCode::Up.into());
-			assert_eq!(app.cursor, expected[0], "up");
-			app.cursor = initial;
-			app.process_key_event(KeyCode::Right.into());
-			assert_eq!(app.cursor, expected[1], "right");
-			app.cursor = initial;
-			app.process_key_event(KeyCode::Down.into());
-			assert_eq!(app.cursor, expected[2], "down");
-			app.cursor = initial;
-			app.process_key_event(KeyCode::Left.into());
-			assert_eq!(app.cursor, expected[3], "left");
+			app.tick(&mut events).unwrap();
 		}
+		let ExecutionState::Solving { ref solver } = app.state
+		else
+		{
+			panic!("not solving");
+		};
+		assert_eq!(
+			app.progress_summary(solver),
+			"Found 0 words (0 quartiles) — ~0% searched"
+		);
+		let solver = solver.clone().solve_fully();
+		assert_eq!(
+			app.progress_summary(&solver),
+			"Found 30 words (5 quartiles) — ~100% searched"
+		);
 	}
 
-	/// Ensure that the cursor moves to the next cell when the tab key is
-	/// pressed.
+	/// Ensure that switching dictionaries reports the dictionary that loads
+	/// in the status bar, installs it once it has loaded, and solves a
+	/// finished board again with it.
 	#[test]
-	fn test_handle_tab()
+	fn test_switch_dictionary()
 	{
-		let mut app = App::new(0, Dictionary::default());
-		assert_eq!(app.cursor, (0, 0));
-		// Test all possible cursor movements. Each case is a tuple of the
-		// initial cursor position and the expected cursor position after
-		// tab and shift-tab, respectively.
-		let cases = vec![
-			((0, 0), [(1, 0), (0, 0)]),
-			((1, 0), [(2, 0), (0, 0)]),
-			((2, 0), [(3, 0), (1, 0)]),
-			((3, 0), [(0, 1), (2, 0)]),
-			((0, 1), [(1, 1), (3, 0)]),
-			((1, 1), [(2, 1), (0, 1)]),
-			((2, 1), [(3, 1), (1, 1)]),
-			((3, 1), [(0, 2), (2, 1)]),
-			((0, 2), [(1, 2), (3, 1)]),
-			((1, 2), [(2, 2), (0, 2)]),
-			((2, 2), [(3, 2), (1, 2)]),
-			((3, 2), [(0, 3), (2, 2)]),
-			((0, 3), [(1, 3), (3, 2)]),
-			((1, 3), [(2, 3), (0, 3)]),
-			((2, 3), [(3, 3), (1, 3)]),
-			((3, 3), [(0, 4), (2, 3)]),
-			((0, 4), [(1, 4), (3, 3)]),
-			((1, 4), [(2, 4), (0, 4)]),
-			((2, 4), [(3, 4), (1, 4)]),
-			((3, 4), [(3, 4), (2, 4)]),
-		];
-		for (initial, expected) in cases
+		let dir = tempfile::TempDir::new().unwrap();
+		fs::write(dir.path().join("tiny.txt"), "this\ntruth\ntruthfully\n")
+			.unwrap();
+		let dictionary = ["crosswords", "truth"]
+			.into_iter()
+			.collect::<Dictionary>();
+		let mut app = App::new(0, dictionary)
+			.with_dictionary_name("small")
+			.with_fragments(BOARD.map(Fragment::from));
+		let status = |app: &App| {
+			let area = Rect::new(0, 0, 120, 1);
+			let mut buf = Buffer::empty(area);
+			app.render_status_bar(area, &mut buf);
+			buf.content()
+				.iter()
+				.map(|cell| cell.symbol())
+				.collect::<String>()
+		};
+		let words = |app: &mut App| {
+			while !matches!(app.state, ExecutionState::Finished { .. })
+			{
+				app.process_systems();
+			}
+			let ExecutionState::Finished { ref solver, .. } = app.state
+			else
+			{
+				unreachable!()
+			};
+			let mut words = solver.solution_deduped()
+				.iter()
+				.map(|word| word.to_string())
+				.collect::<Vec<_>>();
+			words.sort();
+			words
+		};
+		app.process_key_event(KeyCode::Enter.into());
+		assert_eq!(words(&mut app), ["crosswords", "truth"]);
+
+		app.load_dictionary(dir.path().to_path_buf(), "tiny".into());
+		let loading = status(&app);
+		assert!(loading.contains("small (2 words)"), "{}", loading);
+		assert!(loading.contains("loading tiny…"), "{}", loading);
+		while app.loader.is_some()
 		{
-			app.cursor = initial;
-			app.process_key_event(KeyCode::Tab.into());
-			assert_eq!(app.cursor, expected[0], "tab");
-			app.cursor = initial;
-			app.process_key_event(KeyCode::BackTab.into());
-			assert_eq!(app.cursor, expected[1], "shift-tab");
+			app.process_systems();
 		}
+		assert_eq!(app.dictionary_name.as_deref(), Some("tiny"));
+		assert_eq!(app.dictionary_directory, dir.path());
+		assert_eq!(words(&mut app), ["this", "truth", "truthfully"]);
+		let loaded = status(&app);
+		assert!(loaded.contains("tiny (3 words)"), "{}", loaded);
+		assert!(!loaded.contains("loading"), "{}", loaded);
 	}
 
-	/// Ensure that the current cell is edited correctly when alphabetic
-	/// characters are appended and deleted.
+	/// Ensure that the splash screen shows while the initial dictionary
+	/// loads, that it can be cancelled, and that a dictionary that can't be
+	/// opened ends the application once the failure is dismissed.
 	#[test]
-	fn test_handle_edit()
+	fn test_splash()
 	{
-		let mut app = App::new(0, Dictionary::default());
-		assert_eq!(app.current_cell(), &str8::default());
-		// Test deleting from an empty cell.
-		app.process_key_event(KeyCode::Backspace.into());
-		assert_eq!(app.current_cell(), &str8::default());
-		// Test appending and deleting all alphabetic characters.
-		for c in 'a'..='z'
+		let loader = Loader::spawn(
+			PathBuf::from("dict"),
+			"english".into(),
+			Composition::default()
+		);
+		let mut app = App::new(0, Dictionary::default())
+			.with_loader(loader)
+			.with_dictionary_name("english");
+		let area = Rect::new(0, 0, 80, 24);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let screen = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(screen.contains("Loading dictionary: english"), "{}", screen);
+		// Keys other than exit are ignored until the dictionary has loaded.
+		app.process_key_event(KeyCode::Char('a').into());
+		assert!(app.cells[0].is_empty());
+		while app.is_splash_visible
 		{
-			app.process_key_event(KeyCode::Char(c).into());
-			assert_eq!(app.current_cell(), &str8::make(&c.to_string()));
-			app.process_key_event(KeyCode::Backspace.into());
-			assert_eq!(app.current_cell(), &str8::default());
+			app.process_systems();
 		}
-		// Test saturating the cell.
-		let mut s = String::new();
-		for c in 'a'..='j'
+		assert!(app.dictionary.contains("truth"));
+		assert!(app.is_running());
+
+		// Cancel loading.
+		let loader = Loader::spawn(
+			PathBuf::from("dict"),
+			"english".into(),
+			Composition::default()
+		);
+		let mut app = App::new(0, Dictionary::default()).with_loader(loader);
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(!app.is_running());
+		assert!(app.fatal_error.is_none());
+
+		// Fail to load.
+		let loader = Loader::spawn(
+			PathBuf::from("dict"),
+			"missing".into(),
+			Composition::default()
+		);
+		let mut app = App::new(0, Dictionary::default()).with_loader(loader);
+		while app.load_failure.is_none()
 		{
-			s.push(c);
-			app.process_key_event(KeyCode::Char(c).into());
-			// Take the first 7 characters from the string.
-			let s = s.chars().take(7).collect::<String>();
-			assert_eq!(app.current_cell(), &str8::make(&s));
+			app.process_systems();
+		}
+		assert!(app.is_running());
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(!app.is_running());
+		assert!(app.fatal_error.is_some());
+	}
+
+	/// Ensure that a dictionary that can't be opened opens the load failure
+	/// dialog, from which the user may retry it, dismiss it and keep the
+	/// current dictionary, or browse for another dictionary, even behind the
+	/// splash screen.
+	#[test]
+	fn test_load_failure()
+	{
+		let fail = |app: &mut App| {
+			while app.loader.is_some()
+			{
+				app.process_systems();
+			}
+			assert!(app.load_failure.is_some());
+			assert!(app.is_running());
+		};
+		let screen = |app: &App| {
+			let area = Rect::new(0, 0, 80, 24);
+			let mut buf = Buffer::empty(area);
+			app.render(area, &mut buf);
+			buf.content()
+				.iter()
+				.map(|cell| cell.symbol())
+				.collect::<String>()
+		};
+		let mut app = App::new(0, Dictionary::from_iter(["truth"]))
+			.with_dictionary_name("tiny");
+		app.load_dictionary(PathBuf::from("dict"), "missing".into());
+		fail(&mut app);
+		let text = screen(&app);
+		assert!(text.contains("Dictionary unavailable"), "{}", text);
+		assert!(text.contains("dict/missing"), "{}", text);
+
+		// Retry, and fail again.
+		app.process_key_event(KeyCode::Char('r').into());
+		assert!(app.load_failure.is_none());
+		fail(&mut app);
+
+		// Dismiss, keeping the current dictionary.
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(app.load_failure.is_none());
+		assert_eq!(app.dictionary_name.as_deref(), Some("tiny"));
+		assert_eq!(app.dictionary.len(), 1);
+
+		// Fail behind the splash screen, and then browse for another
+		// dictionary, which replaces the splash screen once it loads.
+		let loader = Loader::spawn(
+			PathBuf::from("dict"),
+			"missing".into(),
+			Composition::default()
+		);
+		let mut app = App::new(0, Dictionary::default()).with_loader(loader);
+		fail(&mut app);
+		assert!(screen(&app).contains("Dictionary unavailable"));
+		app.process_key_event(KeyCode::Char('b').into());
+		assert!(matches!(
+			app.load_failure,
+			Some(LoadFailure { picker: Some(Picker { ref names, .. }), .. })
+				if names == &["english"]
+		));
+		assert!(screen(&app).contains("english"));
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(app.load_failure.is_none());
+		while app.is_splash_visible
+		{
+			app.process_systems();
 		}
+		assert!(app.load_failure.is_none());
+		assert_eq!(app.dictionary_name.as_deref(), Some("english"));
+		assert!(app.dictionary.contains("truth"));
+	}
+
+	/// Ensure that the crash context summarizes the screen, the execution
+	/// state, and the board.
+	#[test]
+	fn test_crash_context()
+	{
+		let mut app = App::new(0, Dictionary::default())
+			.with_dictionary_name("english");
+		app.cells[0] = Fragment::from("azz");
+		app.cells[19] = Fragment::from("ment");
+		let context = app.crash_context();
+		assert!(context.contains("Screen: solve"), "{}", context);
+		assert!(context.contains("Execution state: populating"), "{}", context);
+		assert!(context.contains("Dictionary: dict/english (0 words)"));
+		assert!(context.contains("azz     ·"), "{}", context);
+		assert!(context.ends_with("ment"), "{}", context);
 	}
 }
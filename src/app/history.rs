@@ -0,0 +1,393 @@
+//! # History screen
+//!
+//! The screen on which the user browses the puzzles recorded in the history,
+//! in order to reopen one.
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+	buffer::Buffer,
+	layout::{Alignment, Constraint, Layout, Rect},
+	text::{Line, Span, Text},
+	widgets::{
+		Block, Borders, List, ListState, Paragraph, StatefulWidget, Widget,
+		Wrap
+	}
+};
+use tracing::warn;
+
+use super::{App, ExecutionState, Screen};
+use crate::{
+	history::{self, Entry},
+	keymap::{Action, Resolution}
+};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  History.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+impl App
+{
+	/// Render the [history](Screen::History) UI: the entries of the
+	/// history above, and a preview of the selected puzzle below.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `entries` - The entries of the history.
+	/// * `selected` - The index of the selected entry.
+	/// * `error` - The error that prevented the history from being read, if
+	///   any, which is shown instead of the entries.
+	pub(super) fn render_history(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		entries: &[Entry],
+		selected: usize,
+		error: &Option<String>
+	)
+	{
+		let [list_area, preview_area] = Layout::vertical([
+			Constraint::Min(0),
+			Constraint::Length(7)
+		])
+		.margin(1)
+		.areas(area);
+		let mut block = Block::default()
+			.borders(Borders::ALL)
+			.border_style(self.theme.border)
+			.title_top(Line::from(self.text("history.title")).centered())
+			.title_bottom(
+				Line::from(Span::styled(
+					self.hints(&[
+						(
+							&[Action::MoveUp, Action::MoveDown],
+							self.text("common.move")
+						),
+						(&[Action::Solve], self.text("history.reopen"))
+					]),
+					self.theme.hint
+				))
+				.centered()
+			);
+		if let Some(keys) = self.keymap.label(Action::Exit)
+		{
+			block = block.title_top(
+				Line::from(Span::styled(
+					format!("{} – {}", keys, self.text("common.back")),
+					self.theme.exit
+				))
+				.left_aligned()
+			);
+		}
+		let items = entries
+			.iter()
+			.map(|entry| {
+				let origin = match (entry.daily, entry.seed)
+				{
+					(true, _) => self.text("history.daily").to_string(),
+					(false, Some(seed)) =>
+					{
+						self.text_with("history.seed", &[("seed", &seed)])
+					},
+					(false, None) => self.text("history.custom").to_string()
+				};
+				let line = format!(
+					"{}  {:<18}  {:>4} {}  {:>4} {}  {}",
+					entry.date,
+					origin,
+					entry.words,
+					self.text("history.words"),
+					entry.score,
+					self.text("history.points"),
+					if entry.completed { "✓" } else { "✗" }
+				);
+				let style = match entry.completed
+				{
+					true => self.theme.quartile,
+					false => self.theme.word
+				};
+				Text::styled(line, style)
+			})
+			.collect::<Vec<_>>();
+		if let Some(ref e) = error
+		{
+			Paragraph::new(e.as_str())
+				.style(self.theme.failure)
+				.alignment(Alignment::Center)
+				.wrap(Wrap { trim: true })
+				.block(block)
+				.render(list_area, buf);
+		}
+		else if items.is_empty()
+		{
+			Paragraph::new(self.text("history.empty"))
+				.style(self.theme.text)
+				.alignment(Alignment::Center)
+				.block(block)
+				.render(list_area, buf);
+		}
+		else
+		{
+			self.viewport_height
+				.set(list_area.height.saturating_sub(2) as usize);
+			let list = List::new(items)
+				.block(block)
+				.highlight_style(self.theme.selection)
+				.highlight_symbol(self.highlight_symbol());
+			let mut list_state = ListState::default()
+				.with_selected(Some(selected));
+			StatefulWidget::render(&list, list_area, buf, &mut list_state);
+		}
+		let preview = entries
+			.get(selected)
+			.map(|entry| history::grid(&entry.fragments))
+			.unwrap_or_default()
+			.into_iter()
+			.map(Line::from)
+			.collect::<Vec<_>>();
+		Paragraph::new(preview)
+			.style(self.theme.text)
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.border_style(self.theme.border)
+					.title_top(Line::from(self.text("solve.puzzle")).centered())
+			)
+			.render(preview_area, buf);
+	}
+
+	/// Open the history browser, selecting the most recent entry. If there is
+	/// no history file, do nothing; if it can't be read, the browser explains
+	/// why in place of the entries.
+	pub(super) fn open_history(&mut self)
+	{
+		if let Some(ref path) = self.history_file
+		{
+			self.screen = match history::load(path)
+			{
+				Ok(entries) =>
+				{
+					let selected = entries.len().saturating_sub(1);
+					Screen::History { entries, selected, error: None }
+				},
+				Err(e) =>
+				{
+					warn!(
+						"Failed to read history file: {}: {}",
+						path.display(),
+						e
+					);
+					let error = self.text_with(
+						"history.unreadable",
+						&[("path", &path.display()), ("error", &e)]
+					);
+					Screen::History {
+						entries: Vec::new(),
+						selected: 0,
+						error: Some(error)
+					}
+				}
+			};
+		}
+	}
+
+	/// Process a key event while [browsing](Screen::History) the history:
+	///
+	/// * [Exit](Action::Exit), [History](Action::History) -
+	///   [Leave](Self::leave) the history browser.
+	/// * [MoveUp](Action::MoveUp) - Select the entry above.
+	/// * [MoveDown](Action::MoveDown) - Select the entry below.
+	/// * [PageUp](Action::PageUp) - Move the selection up by a page.
+	/// * [PageDown](Action::PageDown) - Move the selection down by a page.
+	/// * [Solve](Action::Solve) - Reopen the selected puzzle for solving.
+	/// * [Help](Action::Help) - Show the keybindings.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	pub(super) fn process_key_event_history(&mut self, event: KeyEvent)
+	{
+		let page = self.viewport_height.get().max(1) as isize;
+		let delta = match self.keymap.resolve(event)
+		{
+			Resolution::Action(Action::Exit | Action::History) =>
+			{
+				self.leave();
+				return
+			},
+			Resolution::Action(Action::Solve) =>
+			{
+				self.reopen();
+				return
+			},
+			Resolution::Action(Action::Help) =>
+			{
+				self.is_help_visible = true;
+				return
+			},
+			Resolution::Action(Action::MoveUp) => -1,
+			Resolution::Action(Action::MoveDown) => 1,
+			Resolution::Action(Action::PageUp) => -page,
+			Resolution::Action(Action::PageDown) => page,
+			_ => return
+		};
+		if let Screen::History {
+			ref entries,
+			ref mut selected,
+			..
+		} = self.screen
+		{
+			*selected = selected
+				.saturating_add_signed(delta)
+				.min(entries.len().saturating_sub(1));
+		}
+	}
+
+	/// Reopen the selected puzzle of the history on the solve screen. An entry
+	/// that doesn't record a valid board is ignored, as is any entry while a
+	/// solve is underway or awaiting resumption.
+	fn reopen(&mut self)
+	{
+		if !matches!(
+			self.state,
+			ExecutionState::Populating | ExecutionState::Finished { .. }
+		)
+		{
+			return
+		}
+		if let Screen::History { ref entries, selected, .. } = self.screen
+		{
+			if let Some(entry) = entries.get(selected)
+			{
+				if let Some(board) = entry.board()
+				{
+					self.cells = board;
+					self.provenance = entry.provenance();
+					self.cursor = (0, 0);
+					self.state = ExecutionState::Populating;
+					self.screen = Screen::Solve;
+				}
+			}
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use crossterm::event::KeyCode;
+	use quartiles_solver::{dictionary::Dictionary, solver::Fragment};
+
+	use super::*;
+	use crate::{app::test::BOARD, daily::Date, history::Provenance};
+
+	/// Ensure that a finished solve is recorded in the history, with its
+	/// provenance, and that the history browser reopens it.
+	#[test]
+	fn test_history()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		let path = dir.path().join("history.json");
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let fragments = BOARD.map(Fragment::from);
+		let date = Date { year: 2024, month: 6, day: 1 };
+		let provenance = Provenance { fragments, seed: 7, daily: Some(date) };
+		let mut app = App::new(0, dictionary.clone())
+			.with_history_file(path.clone())
+			.with_provenance(provenance);
+		app.process_key_event(KeyCode::Enter.into());
+		while !matches!(app.state, ExecutionState::Finished { .. })
+		{
+			app.process_systems();
+		}
+		let entries = crate::history::load(&path).unwrap();
+		assert_eq!(entries.len(), 1);
+		assert_eq!(entries[0].date, date);
+		assert!(entries[0].daily);
+		assert_eq!(entries[0].seed, Some(7));
+		assert_eq!(entries[0].words, 30);
+		assert_eq!(entries[0].quartiles, 5);
+		assert!(entries[0].score >= 40);
+		assert!(entries[0].duration_ms.is_some());
+		assert!(entries[0].completed);
+
+		let mut app = App::new(0, dictionary).with_history_file(path);
+		app.process_key_event(KeyCode::F(2).into());
+		assert!(matches!(app.screen, Screen::History { selected: 0, .. }));
+		app.process_key_event(KeyCode::Down.into());
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(matches!(app.screen, Screen::Solve));
+		assert!(matches!(app.state, ExecutionState::Populating));
+		assert_eq!(app.cells, fragments);
+		assert_eq!(app.provenance, Some(provenance));
+		app.process_key_event(KeyCode::F(2).into());
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(matches!(app.screen, Screen::Solve));
+		assert!(matches!(app.state, ExecutionState::Populating));
+
+		// Solving the reopened board again reuses the cached solve, which
+		// finishes at once.
+		assert!(entries[0].cache.is_some());
+		app.process_key_event(KeyCode::Enter.into());
+		app.process_systems();
+		let ExecutionState::Finished { ref solver, is_solved, .. } = app.state
+		else
+		{
+			panic!("Not finished")
+		};
+		assert!(is_solved);
+		assert_eq!(solver.solution().len(), 30);
+		let entries = crate::history::load(app.history_file.as_ref().unwrap())
+			.unwrap();
+		assert_eq!(entries.len(), 2);
+		assert_eq!(entries[1].words, 30);
+		assert_eq!(entries[1].duration_ms, None);
+	}
+
+	/// Ensure that a history file that can't be read is reported in the
+	/// history browser and the statistics dashboard, rather than leaving the
+	/// user on the solve screen without a word.
+	#[test]
+	fn test_unreadable_history()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		let path = dir.path().join("history.json");
+		std::fs::write(&path, "not json").unwrap();
+		let mut app = App::new(0, Dictionary::default())
+			.with_history_file(path);
+		let render = |app: &App| {
+			let area = Rect::new(0, 0, 100, 24);
+			let mut buf = Buffer::empty(area);
+			app.render(area, &mut buf);
+			buf.content()
+				.iter()
+				.map(|cell| cell.symbol())
+				.collect::<String>()
+		};
+		app.process_key_event(KeyCode::F(2).into());
+		let Screen::History { ref entries, ref error, .. } = app.screen
+		else
+		{
+			panic!("Not browsing history")
+		};
+		assert!(entries.is_empty());
+		assert!(error.is_some());
+		let screen = render(&app);
+		assert!(screen.contains("Failed to read history file"), "{}", screen);
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(matches!(app.screen, Screen::Solve));
+
+		app.process_key_event(KeyCode::F(3).into());
+		assert!(matches!(
+			app.screen,
+			Screen::Statistics { error: Some(_), .. }
+		));
+		let screen = render(&app);
+		assert!(screen.contains("Failed to read history file"), "{}", screen);
+	}
+}
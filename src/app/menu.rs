@@ -0,0 +1,330 @@
+//! # Main menu
+//!
+//! The main menu of the TUI, from which the user opens every other screen.
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+	buffer::Buffer,
+	layout::Rect,
+	text::{Line, Span, Text},
+	widgets::{
+		Block, BorderType, Borders, List, ListState, Padding, StatefulWidget
+	}
+};
+
+use super::{App, Screen};
+use crate::keymap::{Action, Resolution};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Main menu.                                 //
+////////////////////////////////////////////////////////////////////////////////
+
+impl App
+{
+	/// Render the [main menu](Screen::Menu): the screens of the application,
+	/// each with a description.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `selected` - The index of the selected item.
+	pub(super) fn render_menu(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		selected: usize
+	)
+	{
+		let label_width = MENU.iter()
+			.map(|(label, _)| self.text(label).chars().count())
+			.max()
+			.unwrap_or(0);
+		let items = MENU
+			.iter()
+			.map(|(label, description)| {
+				let (label, description) =
+					(self.text(label), self.text(description));
+				Text::from(Line::from(vec![
+					Span::styled(
+						format!("{:<width$}", label, width = label_width),
+						self.theme.action
+					),
+					Span::styled(format!("  {}", description), self.theme.text)
+				]))
+			})
+			.collect::<Vec<_>>();
+		let width = items.iter().map(Text::width).max().unwrap_or(0)
+			+ Span::raw(self.highlight_symbol()).width()
+			+ 6;
+		let width = width as u16;
+		let height = items.len() as u16 + 2;
+		let popup = Rect {
+			x: area.x + area.width.saturating_sub(width) / 2,
+			y: area.y + area.height.saturating_sub(height) / 2,
+			width: width.min(area.width),
+			height: height.min(area.height)
+		};
+		let mut block = Block::default()
+			.borders(Borders::ALL)
+			.border_type(BorderType::Rounded)
+			.border_style(self.theme.border)
+			.title_top(Line::from(self.text("menu.title")).centered())
+			.title_bottom(
+				Line::from(Span::styled(
+					self.hints(&[
+						(
+							&[Action::MoveUp, Action::MoveDown],
+							self.text("common.move")
+						),
+						(&[Action::Solve], self.text("menu.open"))
+					]),
+					self.theme.hint
+				))
+				.centered()
+			);
+		if let Some(keys) = self.keymap.label(Action::Exit)
+		{
+			block = block.title_top(
+				Line::from(Span::styled(
+					format!("{} – {}", keys, self.text("common.exit")),
+					self.theme.exit
+				))
+				.left_aligned()
+			);
+		}
+		let list = List::new(items)
+			.block(block.padding(Padding::horizontal(1)))
+			.highlight_style(self.theme.selection)
+			.highlight_symbol(self.highlight_symbol());
+		let mut list_state = ListState::default().with_selected(Some(selected));
+		StatefulWidget::render(&list, popup, buf, &mut list_state);
+	}
+
+	/// Process a key event while at the [main menu](Screen::Menu):
+	///
+	/// * [Exit](Action::Exit) - Exit the application.
+	/// * [MoveUp](Action::MoveUp) - Select the item above.
+	/// * [MoveDown](Action::MoveDown) - Select the item below.
+	/// * [Solve](Action::Solve) - Open the selected screen.
+	/// * [History](Action::History) - Browse the history.
+	/// * [Statistics](Action::Statistics) - Show the statistics.
+	/// * [Help](Action::Help) - Show the keybindings.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	pub(super) fn process_key_event_menu(&mut self, event: KeyEvent)
+	{
+		let Screen::Menu { selected } = self.screen else { unreachable!() };
+		match self.keymap.resolve(event)
+		{
+			Resolution::Action(Action::Exit) => self.exit(),
+			Resolution::Action(Action::MoveUp) =>
+			{
+				self.screen = Screen::Menu {
+					selected: selected.saturating_sub(1)
+				}
+			},
+			Resolution::Action(Action::MoveDown) =>
+			{
+				self.screen = Screen::Menu {
+					selected: (selected + 1).min(MENU.len() - 1)
+				}
+			},
+			Resolution::Action(Action::Solve) => self.open_menu_item(selected),
+			Resolution::Action(Action::History) => self.open_history(),
+			Resolution::Action(Action::Statistics) => self.open_statistics(),
+			Resolution::Action(Action::Help) => self.is_help_visible = true,
+			_ =>
+			{}
+		}
+	}
+
+	/// Open the screen of the specified item of the [main menu](MENU).
+	///
+	/// # Arguments
+	///
+	/// * `index` - The index of the item.
+	fn open_menu_item(&mut self, index: usize)
+	{
+		match index
+		{
+			0 => self.screen = Screen::Solve,
+			1 => self.open_play(1),
+			2 => self.open_play(VERSUS_PLAYERS),
+			3 => self.open_history(),
+			4 => self.open_statistics(),
+			5 =>
+			{
+				self.screen = Screen::Settings {
+					selected: 0,
+					editing: None,
+					picker: None,
+					error: None
+				}
+			},
+			_ => self.exit()
+		}
+	}
+}
+
+impl Screen
+{
+	/// Answer the index of the item of the [main menu](MENU) that opens the
+	/// screen.
+	///
+	/// # Returns
+	///
+	/// The index of the menu item.
+	pub(super) fn menu_index(&self) -> usize
+	{
+		match self
+		{
+			Screen::Solve => 0,
+			Screen::Play { game, .. } if game.players() > 1 => 2,
+			Screen::Play { .. } => 1,
+			Screen::History { .. } => 3,
+			Screen::Statistics { .. } => 4,
+			Screen::Settings { .. } => 5,
+			#[cfg(feature = "trace-ui")]
+			Screen::Trace { .. } => 0,
+			Screen::Menu { selected } => *selected
+		}
+	}
+}
+
+/// The items of the [main menu](Screen::Menu), as pairs of the
+/// [message](crate::locale::Messages) keys of labels and descriptions, in the
+/// order of [`Screen::menu_index`].
+pub(super) const MENU: [(&str, &str); 7] = [
+	("menu.solve", "menu.solve-description"),
+	("menu.play", "menu.play-description"),
+	("menu.versus", "menu.versus-description"),
+	("menu.history", "menu.history-description"),
+	("menu.statistics", "menu.statistics-description"),
+	("menu.settings", "menu.settings-description"),
+	("menu.quit", "menu.quit-description")
+];
+
+/// The number of players in a game of [versus](MENU), who share the board.
+const VERSUS_PLAYERS: usize = 2;
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use crossterm::event::KeyCode;
+	use quartiles_solver::{dictionary::Dictionary, solver::Fragment};
+	use ratatui::widgets::Widget;
+
+	use super::*;
+	use crate::{app::test::BOARD, play::Verdict};
+
+	/// Ensure that the main menu opens every screen, that leaving a screen
+	/// returns to the menu, and that guesses on the play screen are judged
+	/// against the board.
+	#[test]
+	fn test_menu()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let fragments = BOARD.map(Fragment::from);
+		let mut app = App::new(0, dictionary)
+			.with_fragments(fragments)
+			.with_menu();
+		assert!(matches!(app.screen, Screen::Menu { selected: 0 }));
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(matches!(app.screen, Screen::Solve));
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(matches!(app.screen, Screen::Menu { selected: 0 }));
+		assert!(app.is_running());
+
+		// Play the board.
+		app.process_key_event(KeyCode::Down.into());
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(matches!(app.screen, Screen::Play { .. }));
+		for c in "TRUTHFULLY".chars()
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		for c in "truthx".chars()
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+		}
+		app.process_key_event(KeyCode::Backspace.into());
+		app.process_key_event(KeyCode::Enter.into());
+		app.process_key_event(KeyCode::Char('q').into());
+		app.process_key_event(KeyCode::Enter.into());
+		let Screen::Play { ref game, ref guess, ref verdict, .. } = app.screen
+		else
+		{
+			panic!("Not playing")
+		};
+		assert_eq!(game.found(), ["truthfully", "truth"]);
+		assert_eq!(game.quartiles_found(), 1);
+		assert!(guess.is_empty());
+		assert_eq!(verdict, &Some(("q".to_string(), Verdict::Invalid)));
+		let area = Rect::new(0, 0, 80, 24);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let screen = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(screen.contains("q isn't on the board"), "{}", screen);
+		assert!(screen.contains("2/30 words"), "{}", screen);
+		assert!(screen.contains("24 bonus left"), "{}", screen);
+		assert_eq!(screen.matches("▢ ▢ ▢ ▢").count(), 4, "{}", screen);
+
+		// A misspelled guess suggests the words that it might have meant,
+		// but a real word that the board can't form suggests nothing.
+		for c in "trutful".chars()
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		let Screen::Play { ref verdict, ref spellings, .. } = app.screen
+		else
+		{
+			panic!("Not playing")
+		};
+		assert_eq!(verdict, &Some(("trutful".to_string(), Verdict::Invalid)));
+		assert!(spellings.iter().any(|word| word == "truthful"));
+		(&app).render(area, &mut buf);
+		let screen = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(screen.contains("trutful? Try "), "{}", screen);
+		for c in "cat".chars()
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		let Screen::Play { ref verdict, ref spellings, .. } = app.screen
+		else
+		{
+			panic!("Not playing")
+		};
+		assert_eq!(verdict, &Some(("cat".to_string(), Verdict::Invalid)));
+		assert!(spellings.is_empty());
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(matches!(app.screen, Screen::Menu { selected: 1 }));
+
+		// Review the settings, then quit from the menu.
+		for _ in 0..4
+		{
+			app.process_key_event(KeyCode::Down.into());
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(matches!(app.screen, Screen::Settings { .. }));
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(matches!(app.screen, Screen::Menu { selected: 5 }));
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(!app.is_running());
+	}
+}
@@ -0,0 +1,1005 @@
+//! # Play screen
+//!
+//! The screen on which the user finds the words of the board, alone or taking
+//! turns with another player, against the clock or not. The rules of the game
+//! live in [`crate::play`].
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use quartiles_solver::{
+	dictionary::{normalize_word, Dictionary},
+	generator::{Difficulty, Generator, Rng}
+};
+use ratatui::{
+	buffer::Buffer,
+	layout::{Alignment, Constraint, Flex, Layout, Rect},
+	style::Style,
+	text::{Line, Span, Text},
+	widgets::{
+		Block, BorderType, Borders, Clear, Gauge, List, ListState, Paragraph,
+		StatefulWidget, Widget, Wrap
+	}
+};
+use tracing::warn;
+use unicode_normalization::char::is_combining_mark;
+
+use super::{App, Screen};
+use crate::{
+	history::Provenance,
+	keymap::{Action, Mode, Resolution},
+	play::{Game, Timer, Verdict}
+};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Play.                                    //
+////////////////////////////////////////////////////////////////////////////////
+
+impl App
+{
+	/// Render the [play](Screen::Play) UI: the board and the pending guess on
+	/// the left, and the words found so far on the right. In a timed round,
+	/// the clock counts down above the board, and once the round is over, the
+	/// words found give way to a summary of every word of the board. If
+	/// several players share the board, then a scoreboard tracks each player,
+	/// and a winner screen covers the board once the round is over.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `game` - The game.
+	/// * `guess` - The pending guess.
+	/// * `verdict` - The most recent guess and its verdict, if any.
+	/// * `spellings` - The words that the most recent guess might have
+	///   misspelled, if any.
+	/// * `arrangement` - The index of the cell shown at each position of the
+	///   grid.
+	/// * `timer` - The countdown of the round, if it is timed.
+	#[allow(clippy::too_many_arguments)]
+	pub(super) fn render_play(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		game: &Game,
+		guess: &str,
+		verdict: Option<&(String, Verdict)>,
+		spellings: &[String],
+		arrangement: &[u8; 20],
+		timer: Option<&Timer>
+	)
+	{
+		// Split the screen into two parts: the puzzle and the words found.
+		let outer = self.split_outer_screen(area);
+		// The puzzle comprises a 4×5 grid of cells.
+		let board = self.split_board(outer[0]);
+		let is_over = is_round_over(game, timer);
+		// Report the verdict on the most recent guess, if any, or else how to
+		// play.
+		let feedback = match verdict
+		{
+			_ if game.is_won() =>
+			{
+				let label = match timer
+				{
+					Some(timer) => self.text_with(
+						"play.solved-in",
+						&[("time", &format_clock(timer.elapsed()))]
+					),
+					None => self.text("common.solved").to_string()
+				};
+				Span::styled(label, self.theme.success)
+			},
+			_ if game.revealed().is_some() => Span::styled(
+				self.text_with(
+					"play.gave-up",
+					&[("score", &game.score()), ("max", &game.max_score())]
+				),
+				self.theme.failure
+			),
+			_ if is_over => Span::styled(
+				self.text_with(
+					"play.time-up",
+					&[("score", &game.score()), ("max", &game.max_score())]
+				),
+				self.theme.failure
+			),
+			Some((word, Verdict::Word { score })) => Span::styled(
+				self.text_with(
+					"play.word",
+					&[("word", word), ("score", score)]
+				),
+				self.theme.success
+			),
+			Some((word, Verdict::Quartile { score })) => Span::styled(
+				self.text_with(
+					"play.quartile",
+					&[("word", word), ("score", score)]
+				),
+				self.theme.success
+			),
+			Some((word, Verdict::Duplicate)) => Span::styled(
+				self.text_with("play.duplicate", &[("word", word)]),
+				self.theme.failure
+			),
+			Some((word, Verdict::Invalid)) if !spellings.is_empty() =>
+				Span::styled(
+					self.text_with(
+						"play.misspelled",
+						&[
+							("word", word),
+							(
+								"spellings",
+								&spellings.join(
+									&format!(" {} ", self.text("play.or"))
+								)
+							)
+						]
+					),
+					self.theme.failure
+				),
+			Some((word, Verdict::Invalid)) => Span::styled(
+				self.text_with("play.invalid", &[("word", word)]),
+				self.theme.failure
+			),
+			None => Span::styled(
+				self.hints(&[
+					(&[Action::DeleteChar], self.text("common.delete")),
+					(&[Action::ClearCell], self.text("common.clear")),
+					(&[Action::Shuffle], self.text("play.shuffle"))
+				]),
+				self.theme.hint
+			)
+		};
+		self.render_board(
+			outer[0],
+			buf,
+			Some(feedback),
+			self.keymap
+				.label(Action::Solve)
+				.map(|keys| Span::styled(
+					format!("{} – {}", keys, self.text("play.guess")),
+					self.theme.action
+				))
+		);
+		// Render all of the cells.
+		let arrangement = arrangement.map(usize::from);
+		self.render_cells_arranged(board.clone(), buf, &arrangement, |_, cell| {
+			let block = Block::new()
+				.border_type(BorderType::Rounded)
+				.borders(Borders::ALL)
+				.border_style(self.theme.border);
+			let cell = Paragraph::new(cell.as_str())
+				.block(block)
+				.alignment(Alignment::Left)
+				.style(Style::default())
+				.wrap(Wrap { trim: true });
+			cell
+		});
+		// Render the clock above the board.
+		if let Some(timer) = timer
+		{
+			self.render_clock(board[0], buf, timer);
+		}
+		// Render the pending guess beneath the board, unless the round is over.
+		if !is_over
+		{
+			let mode = match self.keymap.mode()
+			{
+				Mode::Insert => "▏",
+				Mode::Normal => ""
+			};
+			let prompt = match game.players()
+			{
+				1 => self.text("play.prompt").to_string(),
+				_ => self.text_with(
+					"play.player",
+					&[("player", &(game.turn() + 1))]
+				)
+			};
+			Paragraph::new(format!("{}: {}{}", prompt, guess, mode))
+				.style(self.theme.cursor)
+				.alignment(Alignment::Center)
+				.render(board[board.len() - 1], buf);
+		}
+		// Reserve a slot for every quartile above the words found, filling in
+		// the slots as the quartiles are found. When several players share the
+		// board, keep score for each of them beneath the slots.
+		let scoreboard_height = match game.players()
+		{
+			1 => 0,
+			players => players as u16 + 2
+		};
+		let [quartiles_area, scoreboard_area, found_area] = Layout::vertical([
+			Constraint::Length(game.quartile_count() as u16 + 2),
+			Constraint::Length(scoreboard_height),
+			Constraint::Min(0)
+		])
+		.areas(outer[1]);
+		self.render_quartile_slots(quartiles_area, buf, game, is_over);
+		if game.players() > 1
+		{
+			self.render_scoreboard(scoreboard_area, buf, game, is_over);
+		}
+		if is_over
+		{
+			self.render_summary(found_area, buf, game);
+			if game.players() > 1
+			{
+				self.render_winners(outer[0], buf, game);
+			}
+			return
+		}
+		// Render the words found so far, colorizing the quartiles and, if
+		// several players share the board, marking each word with its finder.
+		let items = game.found()
+			.iter()
+			.map(|word| {
+				let style = match game.is_quartile(word)
+				{
+					false => self.theme.word,
+					true => self.theme.quartile
+				};
+				match (game.players(), game.finder(word))
+				{
+					(2.., Some(player)) => Text::styled(
+						format!("{} {}", player + 1, word),
+						style
+					),
+					_ => Text::styled(word.clone(), style)
+				}
+			})
+			.collect::<Vec<_>>();
+		let height = found_area.height.saturating_sub(2) as usize;
+		let list = List::new(items)
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.title_top(Line::from(self.text("play.found")).centered())
+					.title_bottom(
+						Line::from(Span::styled(
+							self.text_with(
+								"play.found-words",
+								&[
+									("found", &game.found().len()),
+									("total", &game.word_count())
+								]
+							),
+							self.theme.hint
+						))
+						.centered()
+					)
+			)
+			.style(self.theme.text);
+		let mut list_state = ListState::default()
+			.with_offset(game.found().len().saturating_sub(height));
+		StatefulWidget::render(&list, found_area, buf, &mut list_state);
+	}
+
+	/// Render a slot for every quartile of the game: the quartiles found so
+	/// far, in the order that they were found, followed by placeholders for
+	/// the rest. Beneath the slots, count the bonus words that remain.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `game` - The game.
+	/// * `reveal` - Whether to reveal the quartiles that remain, instead of
+	///   rendering placeholders for them.
+	fn render_quartile_slots(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		game: &Game,
+		reveal: bool
+	)
+	{
+		let found = game
+			.found_quartiles()
+			.map(|word| Line::styled(word, self.theme.quartile));
+		let placeholders = match reveal
+		{
+			true => game.words()
+				.into_iter()
+				.filter(|&(word, _)| {
+					game.is_quartile(word) && !game.is_found(word)
+				})
+				.map(|(word, _)| Line::styled(word, self.theme.suggestion))
+				.collect::<Vec<_>>(),
+			false => (game.quartiles_found()..game.quartile_count())
+				.map(|_| Line::styled("▢ ▢ ▢ ▢", self.theme.suggestion))
+				.collect()
+		};
+		let remaining = game.bonus_words_remaining();
+		let counter = Span::styled(
+			self.text_with("play.bonus-left", &[("count", &remaining)]),
+			match remaining
+			{
+				0 => self.theme.success,
+				_ => self.theme.hint
+			}
+		);
+		Paragraph::new(found.chain(placeholders).collect::<Vec<_>>())
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.title_top(
+						Line::from(self.text("solve.quartiles")).centered()
+					)
+					.title_bottom(Line::from(counter).centered())
+			)
+			.render(area, buf);
+	}
+
+	/// Render the scoreboard of a game shared by several players: the score
+	/// and number of words found of each player, marking the player whose
+	/// turn it is, unless the round is over.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `game` - The game.
+	/// * `is_over` - Whether the round is over.
+	fn render_scoreboard(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		game: &Game,
+		is_over: bool
+	)
+	{
+		let lines = (0..game.players())
+			.map(|player| {
+				let is_turn = !is_over && player == game.turn();
+				let line = format!(
+					"{} {}",
+					if is_turn { "▶" } else { " " },
+					self.text_with(
+						"play.score",
+						&[
+							("player", &(player + 1)),
+							("score", &game.player_score(player)),
+							("words", &game.found_by(player).count())
+						]
+					)
+				);
+				match is_turn
+				{
+					true => Line::styled(line, self.theme.cursor),
+					false => Line::styled(line, self.theme.text)
+				}
+			})
+			.collect::<Vec<_>>();
+		Paragraph::new(lines)
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.title_top(Line::from(self.text("play.scores")).centered())
+			)
+			.render(area, buf);
+	}
+
+	/// Render the winner screen of a game shared by several players: a popup
+	/// over the board that announces the winner, or the tie, and the final
+	/// score of each player.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `game` - The game.
+	fn render_winners(&self, area: Rect, buf: &mut Buffer, game: &Game)
+	{
+		let leaders = game.leaders();
+		let headline = match leaders[..]
+		{
+			[winner] =>
+			{
+				self.text_with("play.wins", &[("player", &(winner + 1))])
+			},
+			_ => self.text("play.tie").to_string()
+		};
+		let mut lines = vec![
+			Line::styled(headline, self.theme.success).centered(),
+			Line::default()
+		];
+		lines.extend((0..game.players()).map(|player| {
+			let style = match leaders.contains(&player)
+			{
+				true => self.theme.quartile,
+				false => self.theme.text
+			};
+			Line::styled(
+				self.text_with(
+					"play.final",
+					&[
+						("player", &(player + 1)),
+						("score", &game.player_score(player)),
+						("words", &game.found_by(player).count())
+					]
+				),
+				style
+			)
+			.centered()
+		}));
+		let width = (lines.iter().map(Line::width).max().unwrap_or(0) as u16
+			+ 6)
+			.min(area.width);
+		let height = (lines.len() as u16 + 2).min(area.height);
+		let [popup] = Layout::horizontal([Constraint::Length(width)])
+			.flex(Flex::Center)
+			.areas(area);
+		let [popup] = Layout::vertical([Constraint::Length(height)])
+			.flex(Flex::Center)
+			.areas(popup);
+		Clear.render(popup, buf);
+		let mut block = Block::default()
+			.borders(Borders::ALL)
+			.border_type(BorderType::Rounded)
+			.border_style(self.theme.border)
+			.title_top(Line::from(self.text("play.game-over")).centered());
+		if let Some(keys) = self.keymap.label(Action::Exit)
+		{
+			block = block.title_bottom(
+				Line::from(Span::styled(
+					format!("{} – {}", keys, self.text("common.back")),
+					self.theme.exit
+				))
+				.centered()
+			);
+		}
+		Paragraph::new(lines).block(block).render(popup, buf);
+	}
+
+	/// Render the clock of a timed round as a gauge that drains as time runs
+	/// out, labeled with the time remaining. The clock turns to the failure
+	/// style when time is running short.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area. The clock occupies its bottom row.
+	/// * `buf` - The target buffer.
+	/// * `timer` - The countdown of the round.
+	fn render_clock(&self, area: Rect, buf: &mut Buffer, timer: &Timer)
+	{
+		let [_, clock] = Layout::vertical([
+			Constraint::Min(0),
+			Constraint::Length(1)
+		])
+		.areas(area);
+		let remaining = timer.remaining();
+		let ratio = match timer.limit().is_zero()
+		{
+			true => 0.0,
+			false => remaining.as_secs_f64() / timer.limit().as_secs_f64()
+		};
+		let style = match remaining < CLOCK_WARNING
+		{
+			true => self.theme.failure,
+			false => self.theme.success
+		};
+		Gauge::default()
+			.gauge_style(style)
+			.ratio(ratio)
+			.label(format!("⏱ {}", format_clock(remaining)))
+			.render(clock, buf);
+	}
+
+	/// Render the summary of a finished round: every word of the board,
+	/// quartiles first, with the words that the user found checked off and
+	/// the rest dimmed. If the players gave up, then the words that they
+	/// found come first instead, followed by the rest in the order
+	/// [revealed](Game::reveal). If several players shared the board, then
+	/// each word found is marked with its finder instead. Beneath the words,
+	/// compare the total score with the best possible score.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `game` - The game.
+	fn render_summary(&self, area: Rect, buf: &mut Buffer, game: &Game)
+	{
+		let words = match game.revealed()
+		{
+			Some(revealed) => game.words()
+				.into_iter()
+				.filter(|&(word, _)| game.is_found(word))
+				.chain(
+					revealed.iter().map(|(word, score)| (word.as_str(), *score))
+				)
+				.collect(),
+			None => game.words()
+		};
+		let items = words
+			.into_iter()
+			.map(|(word, score)| {
+				let style = match (game.is_found(word), game.is_quartile(word))
+				{
+					(true, true) => self.theme.quartile,
+					(true, false) => self.theme.word,
+					(false, _) => self.theme.suggestion
+				};
+				let mark = match (game.players(), game.finder(word))
+				{
+					(_, None) => " ".to_string(),
+					(1, Some(_)) => "✓".to_string(),
+					(_, Some(player)) => (player + 1).to_string()
+				};
+				Text::styled(format!("{} {} (+{})", mark, word, score), style)
+			})
+			.collect::<Vec<_>>();
+		let list = List::new(items)
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.title_top(Line::from(self.text("play.summary")).centered())
+					.title_bottom(
+						Line::from(Span::styled(
+							self.text_with(
+								"play.points",
+								&[
+									("score", &game.score()),
+									("max", &game.max_score())
+								]
+							),
+							self.theme.hint
+						))
+						.centered()
+					)
+			)
+			.style(self.theme.text);
+		Widget::render(&list, area, buf);
+	}
+
+	/// Start playing the board. If the board isn't fully populated, then
+	/// first populate it with a freshly generated puzzle.
+	///
+	/// # Arguments
+	///
+	/// * `players` - The number of players, who take turns to guess.
+	pub(super) fn open_play(&mut self, players: usize)
+	{
+		if !self.is_populated()
+		{
+			let seed = SystemTime::now()
+				.duration_since(UNIX_EPOCH)
+				.map(|d| d.as_nanos() as u64)
+				.unwrap_or_default();
+			match Generator::new(self.dictionary.clone())
+				.generate(seed, Difficulty::Medium)
+			{
+				Ok(puzzle) =>
+				{
+					self.cells = puzzle.fragments;
+					self.provenance = Some(Provenance {
+						fragments: puzzle.fragments,
+						seed: puzzle.seed,
+						daily: None
+					});
+				},
+				Err(e) =>
+				{
+					warn!("Failed to generate puzzle: {}", e);
+					return
+				}
+			}
+		}
+		let game = Game::new(self.build_solver(), self.solve_budget)
+			.with_players(players);
+		self.keymap.set_mode(Mode::Insert);
+		self.screen = Screen::Play {
+			game: Box::new(game),
+			guess: String::new(),
+			verdict: None,
+			spellings: Vec::new(),
+			arrangement: std::array::from_fn(|index| index as u8),
+			timer: self.time_limit.map(Timer::new)
+		};
+	}
+
+	/// Process a key event while [playing](Screen::Play):
+	///
+	/// * [Exit](Action::Exit) - [Leave](Self::leave) the play screen,
+	///   abandoning the game.
+	/// * [Solve](Action::Solve) - Submit the pending guess, unless the round
+	///   is over, and pass the turn to the next player, if any. Finding the
+	///   last quartile stops the clock.
+	/// * [DeleteChar](Action::DeleteChar) - Delete the last character of the
+	///   pending guess.
+	/// * [ClearCell](Action::ClearCell), [ClearAll](Action::ClearAll) - Clear
+	///   the pending guess.
+	/// * [Shuffle](Action::Shuffle) - Shuffle the displayed positions of the
+	///   fragments.
+	/// * [ExportCard](Action::ExportCard) - [Export](Self::export_card) a
+	///   share card of the game so far.
+	/// * [Reveal](Action::Reveal) - Give up, unless the round is over, ending
+	///   the round and [revealing](Game::reveal) the words yet to be found.
+	/// * [InsertMode](Action::InsertMode) - Enter insert mode.
+	/// * [NormalMode](Action::NormalMode) - Leave insert mode.
+	/// * [Help](Action::Help) - Show the keybindings.
+	///
+	/// In insert mode, an unbound alphabetic key appends the corresponding
+	/// character to the pending guess.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	pub(super) fn process_key_event_play(&mut self, event: KeyEvent)
+	{
+		let resolution = self.keymap.resolve(event);
+		let is_insert = self.keymap.mode() == Mode::Insert;
+		let Screen::Play {
+			ref mut game,
+			ref mut guess,
+			ref mut verdict,
+			ref mut spellings,
+			ref mut arrangement,
+			ref mut timer
+		} = self.screen
+		else
+		{
+			unreachable!()
+		};
+		let is_over = is_round_over(game, timer.as_ref());
+		match resolution
+		{
+			Resolution::Action(Action::Exit) => self.leave(),
+			Resolution::Action(Action::ExportCard) => self.export_card(),
+			Resolution::Action(Action::Solve | Action::Reveal) if is_over =>
+			{},
+			Resolution::Action(Action::Reveal) =>
+			{
+				game.reveal();
+				if let Some(timer) = timer.as_mut()
+				{
+					timer.stop();
+				}
+			},
+			Resolution::Action(Action::Solve) if !guess.is_empty() =>
+			{
+				let word = normalize_word(guess);
+				let outcome = game.guess(guess);
+				*spellings = match outcome
+				{
+					Verdict::Invalid if !self.dictionary.contains(&word) =>
+						suggest_spellings(&self.dictionary, &word),
+					_ => Vec::new()
+				};
+				*verdict = Some((word, outcome));
+				guess.clear();
+				if let Some(timer) = timer.as_mut().filter(|_| game.is_won())
+				{
+					timer.stop();
+				}
+			},
+			Resolution::Action(Action::DeleteChar) =>
+			{
+				guess.pop();
+			},
+			Resolution::Action(Action::ClearCell | Action::ClearAll) =>
+			{
+				guess.clear()
+			},
+			Resolution::Action(Action::Shuffle) =>
+			{
+				let seed = SystemTime::now()
+					.duration_since(UNIX_EPOCH)
+					.map(|d| d.as_nanos() as u64)
+					.unwrap_or_default();
+				Rng::new(seed).shuffle(arrangement);
+			},
+			Resolution::Action(Action::InsertMode) =>
+			{
+				self.keymap.set_mode(Mode::Insert)
+			},
+			Resolution::Action(Action::NormalMode) =>
+			{
+				self.keymap.set_mode(Mode::Normal)
+			},
+			Resolution::Action(Action::Help) => self.is_help_visible = true,
+			Resolution::Unbound(KeyEvent {
+				code: KeyCode::Char(c),
+				..
+			}) if (c.is_alphabetic() || is_combining_mark(c)) && is_insert =>
+			{
+				guess.push(c);
+				*guess = normalize_word(guess);
+			},
+			_ =>
+			{}
+		}
+	}
+}
+
+/// The maximum number of spellings suggested for a rejected guess during
+/// [play](Screen::Play).
+const MAX_SPELLINGS: usize = 2;
+
+/// The maximum edit distance between a rejected guess and a suggested
+/// spelling. Shorter guesses allow less, so that a guess of a few letters
+/// doesn't resemble half of the dictionary.
+const MAX_SPELLING_DISTANCE: usize = 2;
+
+/// The time remaining below which the [clock](App::render_clock) warns that
+/// time is running short.
+const CLOCK_WARNING: Duration = Duration::from_secs(30);
+
+/// Check whether the round of the specified game is over: the countdown has
+/// expired or stopped, the players gave up, or, if several players share the
+/// board, every quartile has been found.
+///
+/// # Arguments
+///
+/// * `game` - The game.
+/// * `timer` - The countdown of the round, if it is timed.
+///
+/// # Returns
+///
+/// `true` if the round is over, `false` otherwise.
+fn is_round_over(game: &Game, timer: Option<&Timer>) -> bool
+{
+	timer.is_some_and(Timer::is_over)
+		|| game.revealed().is_some()
+		|| (game.players() > 1 && game.is_won())
+}
+
+/// Suggest the words of the dictionary that the specified guess might have
+/// misspelled, nearest first. The edit distance allowed grows with the length
+/// of the guess, up to [`MAX_SPELLING_DISTANCE`], so a guess of one letter
+/// receives no suggestions.
+///
+/// # Arguments
+///
+/// * `dictionary` - The dictionary.
+/// * `guess` - The guess, which the dictionary lacks.
+///
+/// # Returns
+///
+/// At most [`MAX_SPELLINGS`] suggestions.
+fn suggest_spellings(dictionary: &Dictionary, guess: &str) -> Vec<String>
+{
+	let max_dist = (guess.chars().count() / 2).min(MAX_SPELLING_DISTANCE);
+	dictionary.search_levenshtein(guess, max_dist)
+		.into_iter()
+		.filter(|&(_, distance)| distance > 0)
+		.take(MAX_SPELLINGS)
+		.map(|(word, _)| word.to_string())
+		.collect()
+}
+
+/// Format the specified duration for a clock, as minutes and seconds.
+///
+/// # Arguments
+///
+/// * `duration` - The duration, rounded up to the next second.
+///
+/// # Returns
+///
+/// The formatted duration, e.g., `4:05`.
+fn format_clock(duration: Duration) -> String
+{
+	let seconds = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+	format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use crossterm::event::KeyModifiers;
+	use quartiles_solver::solver::Fragment;
+
+	use super::*;
+	use crate::app::test::BOARD;
+
+	/// Ensure that shuffling the board while playing moves the fragments on
+	/// screen, but leaves the cells, and hence the game, alone.
+	#[test]
+	fn test_shuffle()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let fragments = BOARD.map(Fragment::from);
+		let mut app = App::new(0, dictionary).with_fragments(fragments);
+		app.open_play(1);
+		app.process_key_event(
+			KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL)
+		);
+		let Screen::Play { ref guess, arrangement, .. } = app.screen
+		else
+		{
+			panic!("Not playing")
+		};
+		assert!(guess.is_empty());
+		let mut sorted = arrangement;
+		sorted.sort_unstable();
+		assert_eq!(sorted, std::array::from_fn(|index| index as u8));
+		assert_ne!(arrangement, sorted);
+		assert_eq!(app.cells, fragments);
+		// The top-left tile shows the first cell of the arrangement.
+		let area = Rect::new(0, 0, 100, 40);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let areas = app.cell_areas.get();
+		let top_left = areas.iter().min_by_key(|area| (area.y, area.x));
+		assert_eq!(top_left, Some(&areas[arrangement[0] as usize]));
+		for c in "truthfully".chars()
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		let Screen::Play { ref game, .. } = app.screen else { unreachable!() };
+		assert_eq!(game.quartiles_found(), 1);
+	}
+
+	/// Ensure that a timed round shows its clock, and that once time runs
+	/// out, guesses are ignored and the summary reveals every word.
+	#[test]
+	fn test_timed_play()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let fragments = BOARD.map(Fragment::from);
+		let render = |app: &App| {
+			let area = Rect::new(0, 0, 100, 40);
+			let mut buf = Buffer::empty(area);
+			app.render(area, &mut buf);
+			buf.content()
+				.iter()
+				.map(|cell| cell.symbol())
+				.collect::<String>()
+		};
+		let mut app = App::new(0, dictionary.clone())
+			.with_fragments(fragments)
+			.with_time_limit(Duration::from_secs(300));
+		app.open_play(1);
+		let screen = render(&app);
+		assert!(screen.contains("⏱ 5:00"), "{}", screen);
+		assert!(screen.contains("Found"), "{}", screen);
+
+		let mut app = App::new(0, dictionary)
+			.with_fragments(fragments)
+			.with_time_limit(Duration::ZERO);
+		app.open_play(1);
+		for c in "truthfully".chars()
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		let Screen::Play { ref game, ref verdict, .. } = app.screen
+		else
+		{
+			panic!("Not playing")
+		};
+		assert!(game.found().is_empty());
+		assert!(verdict.is_none());
+		let screen = render(&app);
+		assert!(screen.contains("⏱ 0:00"), "{}", screen);
+		assert!(screen.contains("Time's up! 0 of"), "{}", screen);
+		assert!(screen.contains("Summary"), "{}", screen);
+		assert!(screen.contains("truthfully (+"), "{}", screen);
+		assert!(!screen.contains("▢ ▢ ▢ ▢"), "{}", screen);
+	}
+
+	/// Ensure that giving up ends the round, and that the summary lists the
+	/// words found before the words revealed, which exclude them.
+	#[test]
+	fn test_reveal()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let fragments = BOARD.map(Fragment::from);
+		let mut app = App::new(0, dictionary).with_fragments(fragments);
+		app.open_play(1);
+		for c in "truthfully".chars()
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		app.process_key_event(
+			KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL)
+		);
+		let Screen::Play { ref game, .. } = app.screen
+		else
+		{
+			panic!("Not playing")
+		};
+		let revealed = game.revealed().unwrap();
+		assert_eq!(revealed.len(), game.word_count() - 1);
+		assert!(revealed.iter().all(|(word, _)| word != "truthfully"));
+		// The quartiles that cover the most fragments come first.
+		assert!(game.is_quartile(&revealed[0].0));
+		for c in "truth".chars()
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		let Screen::Play { ref game, .. } = app.screen
+		else
+		{
+			panic!("Not playing")
+		};
+		assert_eq!(game.found(), ["truthfully"]);
+		let area = Rect::new(0, 0, 100, 40);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let screen = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(screen.contains("Gave up! 8 of"), "{}", screen);
+		assert!(screen.contains("✓ truthfully (+8)"), "{}", screen);
+	}
+
+	/// Ensure that the players of a versus game take turns, that a word found
+	/// by one player can't be found by the other, and that the winner is
+	/// announced once every quartile is found.
+	#[test]
+	fn test_versus()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let fragments = BOARD.map(Fragment::from);
+		let mut app = App::new(0, dictionary)
+			.with_fragments(fragments)
+			.with_menu();
+		app.process_key_event(KeyCode::Down.into());
+		app.process_key_event(KeyCode::Down.into());
+		app.process_key_event(KeyCode::Enter.into());
+		let guess = |app: &mut App, word: &str| {
+			for c in word.chars()
+			{
+				app.process_key_event(KeyCode::Char(c).into());
+			}
+			app.process_key_event(KeyCode::Enter.into());
+		};
+		guess(&mut app, "truthfully");
+		guess(&mut app, "truthfully");
+		guess(&mut app, "truth");
+		let Screen::Play { ref game, ref verdict, .. } = app.screen
+		else
+		{
+			panic!("Not playing")
+		};
+		assert_eq!(game.players(), 2);
+		assert_eq!(game.turn(), 1);
+		assert_eq!(game.finder("truthfully"), Some(0));
+		assert_eq!(game.finder("truth"), Some(0));
+		assert!(matches!(verdict, Some((_, Verdict::Word { .. }))));
+		let render = |app: &App| {
+			let area = Rect::new(0, 0, 100, 40);
+			let mut buf = Buffer::empty(area);
+			app.render(area, &mut buf);
+			buf.content()
+				.iter()
+				.map(|cell| cell.symbol())
+				.collect::<String>()
+		};
+		let screen = render(&app);
+		assert!(screen.contains("Player 2: ▏"), "{}", screen);
+		assert!(screen.contains("Player 2 to play"), "{}", screen);
+		assert!(screen.contains("1 truthfully"), "{}", screen);
+
+		// Player 2 finds the remaining quartiles, and so wins.
+		let quartiles = game.words()
+			.into_iter()
+			.filter(|&(word, _)| game.is_quartile(word) && !game.is_found(word))
+			.map(|(word, _)| word.to_string())
+			.collect::<Vec<_>>();
+		for word in quartiles
+		{
+			guess(&mut app, &word);
+			guess(&mut app, "q");
+		}
+		let Screen::Play { ref game, .. } = app.screen else { unreachable!() };
+		assert!(game.is_won());
+		assert_eq!(game.leaders(), [1]);
+		let screen = render(&app);
+		assert!(screen.contains("Player 2 wins!"), "{}", screen);
+		assert!(screen.contains("Summary"), "{}", screen);
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(matches!(app.screen, Screen::Menu { selected: 2 }));
+	}
+}
@@ -0,0 +1,781 @@
+//! # Settings screen
+//!
+//! The screen on which the user reviews and changes the settings, each of
+//! which persists to the configuration file.
+
+use std::{io, path::PathBuf};
+
+use clap::ValueEnum;
+use crossterm::event::{KeyCode, KeyEvent};
+use quartiles_solver::dictionary::{Dictionary, MAX_WORD_LEN};
+use ratatui::{
+	buffer::Buffer,
+	layout::{Constraint, Layout, Rect},
+	text::{Line, Span, Text},
+	widgets::{
+		Block, Borders, List, ListState, Padding, Paragraph, StatefulWidget,
+		Widget
+	}
+};
+
+use super::{App, Picker, Screen};
+use crate::{
+	config::Config,
+	keymap::{Action, Resolution},
+	locale::Messages,
+	theme::{Theme, ThemeName}
+};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Settings.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+impl App
+{
+	/// Render the [settings](Screen::Settings) UI: the editable settings,
+	/// followed by the files in use.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `selected` - The index of the selected setting.
+	/// * `editing` - The pending value of the selected setting, if it is
+	///   being edited.
+	/// * `error` - The error that resulted from the most recent change, if
+	///   any.
+	pub(super) fn render_settings(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		selected: usize,
+		editing: Option<&str>,
+		error: Option<&str>
+	)
+	{
+		let [list_area, files_area] = Layout::vertical([
+			Constraint::Min(0),
+			Constraint::Length(5)
+		])
+		.margin(1)
+		.areas(area);
+		let label_width = Setting::ALL.iter()
+			.map(|&setting| setting.label(self.messages).chars().count())
+			.max()
+			.unwrap_or(0);
+		let items = Setting::ALL
+			.iter()
+			.enumerate()
+			.map(|(index, setting)| {
+				let value = match editing
+				{
+					Some(value) if index == selected => format!("{}▏", value),
+					_ => self.setting_value(*setting)
+				};
+				Text::from(Line::from(vec![
+					Span::styled(
+						format!(
+							"{:<width$}",
+							setting.label(self.messages),
+							width = label_width
+						),
+						self.theme.action
+					),
+					Span::raw(format!("  {}", value))
+				]))
+			})
+			.collect::<Vec<_>>();
+		let footer = match error
+		{
+			Some(error) =>
+			{
+				Span::styled(format!("✗ {}", error), self.theme.failure)
+			},
+			None => Span::styled(
+				match editing
+				{
+					Some(_) => self.text("settings.editing").to_string(),
+					None => self.hints(&[
+						(
+							&[Action::MoveUp, Action::MoveDown],
+							self.text("common.move")
+						),
+						(
+							&[Action::MoveLeft, Action::MoveRight],
+							self.text("settings.change")
+						),
+						(&[Action::Solve], self.text("settings.edit"))
+					])
+				},
+				self.theme.hint
+			)
+		};
+		let mut block = Block::default()
+			.borders(Borders::ALL)
+			.border_style(self.theme.border)
+			.title_top(Line::from(self.text("settings.title")).centered())
+			.title_bottom(Line::from(footer).centered());
+		if let Some(keys) = self.keymap.label(Action::Exit)
+		{
+			block = block.title_top(
+				Line::from(Span::styled(
+					format!("{} – {}", keys, self.text("common.back")),
+					self.theme.exit
+				))
+				.left_aligned()
+			);
+		}
+		let list = List::new(items)
+			.block(block.padding(Padding::horizontal(1)))
+			.style(self.theme.text)
+			.highlight_style(self.theme.selection)
+			.highlight_symbol(self.highlight_symbol());
+		let mut list_state = ListState::default().with_selected(Some(selected));
+		StatefulWidget::render(&list, list_area, buf, &mut list_state);
+		// Show the files in use, which can only be changed from the command
+		// line.
+		let path = |path: &Option<PathBuf>| {
+			path.as_ref()
+				.map(|path| path.display().to_string())
+				.unwrap_or_else(|| "—".to_string())
+		};
+		let file = |key, file| {
+			Line::from(self.text_with(key, &[("path", &path(file))]))
+		};
+		let files = vec![
+			file("settings.config-file", &self.config_file),
+			file("settings.state-file", &self.state_file),
+			file("settings.history-file", &self.history_file),
+			file("settings.favorites-file", &self.favorites_file)
+		];
+		Paragraph::new(files)
+			.style(self.theme.text)
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.border_style(self.theme.border)
+					.title_top(
+						Line::from(self.text("settings.files")).centered()
+					)
+					.padding(Padding::horizontal(1))
+			)
+			.render(files_area, buf);
+	}
+
+	/// Describe the current value of the specified setting.
+	///
+	/// # Arguments
+	///
+	/// * `setting` - The setting.
+	///
+	/// # Returns
+	///
+	/// The value, as text.
+	pub(super) fn setting_value(&self, setting: Setting) -> String
+	{
+		match setting
+		{
+			Setting::Directory =>
+			{
+				self.dictionary_directory.display().to_string()
+			},
+			Setting::Dictionary => match self.loader
+			{
+				Some(ref loader) => format!(
+					"{} {}",
+					loader.spinner(),
+					self.text_with(
+						"status.loading",
+						&[("name", &loader.name())]
+					)
+				),
+				None => self.text_with(
+					"status.dictionary",
+					&[
+						(
+							"name",
+							&self.dictionary_name.as_deref().unwrap_or("—")
+						),
+						("count", &self.dictionary.len())
+					]
+				)
+			},
+			Setting::Theme => self.theme_name
+				.to_possible_value()
+				.map(|value| value.get_name().to_string())
+				.unwrap_or_default(),
+			Setting::HighlightDuration =>
+			{
+				format!("{} µs", self.highlight_duration_µs)
+			},
+			Setting::MinLen => self.solver_config.min_len.to_string(),
+			Setting::MaxLen => match self.solver_config.max_len
+			{
+				usize::MAX => "—".to_string(),
+				max_len => max_len.to_string()
+			},
+			Setting::QuartilesOnly => match self.solver_config.quartiles_only
+			{
+				true => self.text("settings.yes").to_string(),
+				false => self.text("settings.no").to_string()
+			}
+		}
+	}
+
+	/// Process a key event while viewing the [settings](Screen::Settings):
+	///
+	/// * [Exit](Action::Exit) - [Leave](Self::leave) the settings.
+	/// * [MoveUp](Action::MoveUp) - Select the setting above.
+	/// * [MoveDown](Action::MoveDown) - Select the setting below.
+	/// * [MoveLeft](Action::MoveLeft) - Change the selected setting to its
+	///   previous value.
+	/// * [MoveRight](Action::MoveRight) - Change the selected setting to its
+	///   next value.
+	/// * [Solve](Action::Solve) - Edit the dictionary directory, open the
+	///   [dictionary picker](Picker), or else change the selected setting to
+	///   its next value. Applying a new dictionary directory opens the picker
+	///   on it.
+	/// * [Help](Action::Help) - Show the keybindings.
+	///
+	/// While a setting is being edited as text, keys bypass the keymap:
+	/// Enter applies the change, Escape cancels it, Backspace deletes the last
+	/// character, and any other character is appended.
+	///
+	/// While the dictionary picker is open, [MoveUp](Action::MoveUp) and
+	/// [MoveDown](Action::MoveDown) select a dictionary,
+	/// [Solve](Action::Solve) loads it, and [Exit](Action::Exit) closes the
+	/// picker.
+	///
+	/// Every change takes effect immediately, and is persisted to the
+	/// configuration file, if any. Dictionaries load in the background, so
+	/// changes to the dictionary take effect once loading finishes.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	pub(super) fn process_key_event_settings(&mut self, event: KeyEvent)
+	{
+		let Screen::Settings {
+			selected,
+			ref mut editing,
+			ref mut picker,
+			..
+		} = self.screen
+		else
+		{
+			unreachable!()
+		};
+		let setting = Setting::ALL[selected];
+		if let Some(Picker { ref names, selected: ref mut index }) = *picker
+		{
+			match self.keymap.resolve(event)
+			{
+				Resolution::Action(Action::Exit) => *picker = None,
+				Resolution::Action(Action::Help) => self.is_help_visible = true,
+				Resolution::Action(Action::MoveUp) =>
+				{
+					*index = index.saturating_sub(1)
+				},
+				Resolution::Action(Action::MoveDown) =>
+				{
+					*index = (*index + 1).min(names.len().saturating_sub(1))
+				},
+				Resolution::Action(Action::Solve) =>
+				{
+					if let Some(name) = names.get(*index).cloned()
+					{
+						*picker = None;
+						self.load_dictionary(
+							self.dictionary_directory.clone(),
+							name
+						);
+					}
+				},
+				_ =>
+				{}
+			}
+			return
+		}
+		if let Some(value) = editing
+		{
+			match event.code
+			{
+				KeyCode::Enter =>
+				{
+					let directory = PathBuf::from(value.trim());
+					*editing = None;
+					let result = self.change_directory(directory);
+					self.report_setting_result(result);
+				},
+				KeyCode::Esc => *editing = None,
+				KeyCode::Backspace =>
+				{
+					value.pop();
+				},
+				KeyCode::Char(c) if !c.is_control() => value.push(c),
+				_ =>
+				{}
+			}
+			return
+		}
+		let delta = match self.keymap.resolve(event)
+		{
+			Resolution::Action(Action::Exit) =>
+			{
+				self.leave();
+				return
+			},
+			Resolution::Action(Action::Help) =>
+			{
+				self.is_help_visible = true;
+				return
+			},
+			Resolution::Action(Action::MoveUp) =>
+			{
+				*self.settings_selection() = selected.saturating_sub(1);
+				return
+			},
+			Resolution::Action(Action::MoveDown) =>
+			{
+				*self.settings_selection() =
+					(selected + 1).min(Setting::ALL.len() - 1);
+				return
+			},
+			Resolution::Action(Action::Solve)
+				if setting == Setting::Directory =>
+			{
+				let value = self.dictionary_directory.display().to_string();
+				if let Screen::Settings { ref mut editing, .. } = self.screen
+				{
+					*editing = Some(value);
+				}
+				return
+			},
+			Resolution::Action(Action::Solve)
+				if setting == Setting::Dictionary =>
+			{
+				let result = self.open_picker();
+				self.report_setting_result(result);
+				return
+			},
+			Resolution::Action(Action::MoveLeft) => -1,
+			Resolution::Action(Action::MoveRight | Action::Solve) => 1,
+			_ => return
+		};
+		if setting.is_stepped()
+		{
+			let result = self.adjust_setting(setting, delta);
+			self.report_setting_result(result);
+		}
+	}
+
+	/// Answer the index of the selected setting, for modification.
+	///
+	/// # Returns
+	///
+	/// The index of the selected setting.
+	///
+	/// # Panics
+	///
+	/// If the [settings](Screen::Settings) screen isn't showing.
+	fn settings_selection(&mut self) -> &mut usize
+	{
+		match self.screen
+		{
+			Screen::Settings { ref mut selected, .. } => selected,
+			_ => unreachable!()
+		}
+	}
+
+	/// Report the result of the most recent change on the
+	/// [settings](Screen::Settings) screen.
+	///
+	/// # Arguments
+	///
+	/// * `result` - The result of the change.
+	pub(super) fn report_setting_result(&mut self, result: io::Result<()>)
+	{
+		if let Screen::Settings { ref mut error, .. } = self.screen
+		{
+			*error = result.err().map(|e| e.to_string());
+		}
+	}
+
+	/// Change the specified setting to its previous or next value, and then
+	/// persist it. Numeric settings saturate at their bounds, and the other
+	/// settings wrap around.
+	///
+	/// # Arguments
+	///
+	/// * `setting` - The setting, which must be [stepped](Setting::is_stepped).
+	/// * `delta` - `-1` for the previous value, `1` for the next value.
+	///
+	/// # Errors
+	///
+	/// If the configuration file cannot be updated, an error is returned.
+	fn adjust_setting(&mut self, setting: Setting, delta: i8) -> io::Result<()>
+	{
+		let config = &mut self.solver_config;
+		match setting
+		{
+			Setting::Directory | Setting::Dictionary => unreachable!(),
+			Setting::Theme =>
+			{
+				let names = ThemeName::value_variants();
+				let index = names
+					.iter()
+					.position(|name| *name == self.theme_name)
+					.unwrap_or_default();
+				let index = (index as isize + delta as isize)
+					.rem_euclid(names.len() as isize);
+				self.theme_name = names[index as usize];
+				self.theme = Theme::new(self.theme_name);
+			},
+			Setting::HighlightDuration =>
+			{
+				self.highlight_duration_µs = match delta
+				{
+					-1 => self.highlight_duration_µs
+						.saturating_sub(HIGHLIGHT_DURATION_STEP),
+					_ => self.highlight_duration_µs + HIGHLIGHT_DURATION_STEP
+				}
+			},
+			Setting::MinLen =>
+			{
+				config.min_len = config.min_len
+					.saturating_add_signed(delta as isize)
+					.min(MAX_WORD_LEN);
+			},
+			Setting::MaxLen =>
+			{
+				// The maximum length is either unbounded or in
+				// `1..=MAX_WORD_LEN`. Stepping past the bounds goes unbounded.
+				config.max_len = match (config.max_len, delta)
+				{
+					(usize::MAX, -1) => MAX_WORD_LEN,
+					(usize::MAX, _) => usize::MAX,
+					(1, -1) | (MAX_WORD_LEN, 1) => usize::MAX,
+					(max_len, -1) => max_len - 1,
+					(max_len, _) => max_len + 1
+				};
+			},
+			Setting::QuartilesOnly =>
+			{
+				config.quartiles_only = !config.quartiles_only
+			}
+		}
+		self.persist_setting(setting)
+	}
+
+	/// Change the dictionary directory, persist it, and then
+	/// [open the dictionary picker](Self::open_picker) on it. The current
+	/// dictionary remains in use until another is picked.
+	///
+	/// # Arguments
+	///
+	/// * `directory` - The new dictionary directory.
+	///
+	/// # Errors
+	///
+	/// * If the configuration file cannot be updated, an error is returned.
+	/// * If the new directory cannot be read, an error is returned.
+	fn change_directory(&mut self, directory: PathBuf) -> io::Result<()>
+	{
+		self.dictionary_directory = directory;
+		self.persist_setting(Setting::Directory)?;
+		self.open_picker()
+	}
+
+	/// Open the [dictionary picker](Picker) of the
+	/// [settings](Screen::Settings) screen, selecting the current dictionary.
+	///
+	/// # Errors
+	///
+	/// If the dictionary directory cannot be read, an error is returned, and
+	/// the picker stays closed.
+	fn open_picker(&mut self) -> io::Result<()>
+	{
+		let names = Dictionary::available(&self.dictionary_directory)
+			.map_err(|e| io::Error::new(
+				e.kind(),
+				self.text_with(
+					"failure.list",
+					&[
+						("path", &self.dictionary_directory.display()),
+						("error", &e)
+					]
+				)
+			))?;
+		let selected = names
+			.iter()
+			.position(|name| Some(name) == self.dictionary_name.as_ref())
+			.unwrap_or_default();
+		if let Screen::Settings { ref mut picker, .. } = self.screen
+		{
+			*picker = Some(Picker { names, selected });
+		}
+		Ok(())
+	}
+
+	/// Persist the current value of the specified setting to the
+	/// configuration file, if any. The configuration file is reread first, so
+	/// that only the specified setting changes, and not, e.g., settings
+	/// overridden from the command line.
+	///
+	/// # Arguments
+	///
+	/// * `setting` - The setting.
+	///
+	/// # Errors
+	///
+	/// If the configuration file cannot be read or written, an error is
+	/// returned.
+	pub(super) fn persist_setting(&self, setting: Setting) -> io::Result<()>
+	{
+		let Some(ref path) = self.config_file else { return Ok(()) };
+		let mut config = Config::load(path)?;
+		match setting
+		{
+			Setting::Directory =>
+			{
+				config.directory = self.dictionary_directory.clone()
+			},
+			Setting::Dictionary =>
+			{
+				config.dictionary = self.dictionary_name.clone()
+			},
+			Setting::Theme => config.theme = self.theme_name,
+			Setting::HighlightDuration =>
+			{
+				config.highlight_duration = self.highlight_duration_µs
+			},
+			Setting::MinLen => config.min_len = self.solver_config.min_len,
+			Setting::MaxLen =>
+			{
+				config.max_len = Some(self.solver_config.max_len)
+					.filter(|max_len| *max_len != usize::MAX)
+			},
+			Setting::QuartilesOnly =>
+			{
+				config.quartiles_only = self.solver_config.quartiles_only
+			}
+		}
+		config.save(path)
+	}
+}
+
+/// The settings that the [settings screen](Screen::Settings) can change.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum Setting
+{
+	/// The directory containing the dictionary files.
+	Directory,
+
+	/// The name of the dictionary.
+	Dictionary,
+
+	/// The color scheme.
+	Theme,
+
+	/// How long to highlight an individual word.
+	HighlightDuration,
+
+	/// The minimum length of a word.
+	MinLen,
+
+	/// The maximum length of a word.
+	MaxLen,
+
+	/// Whether to exclude every word but the quartiles.
+	QuartilesOnly
+}
+
+impl Setting
+{
+	/// Every setting, in display order.
+	pub(super) const ALL: [Setting; 7] = [
+		Setting::Directory,
+		Setting::Dictionary,
+		Setting::Theme,
+		Setting::HighlightDuration,
+		Setting::MinLen,
+		Setting::MaxLen,
+		Setting::QuartilesOnly
+	];
+
+	/// Answer the label of the setting.
+	///
+	/// # Arguments
+	///
+	/// * `messages` - The messages of the user interface.
+	///
+	/// # Returns
+	///
+	/// The label.
+	pub(super) fn label(self, messages: &'static Messages) -> &'static str
+	{
+		match self
+		{
+			Setting::Directory => messages.text("settings.directory"),
+			Setting::Dictionary => messages.text("settings.dictionary"),
+			Setting::Theme => messages.text("settings.theme"),
+			Setting::HighlightDuration =>
+			{
+				messages.text("settings.highlight-duration")
+			},
+			Setting::MinLen => messages.text("settings.min-len"),
+			Setting::MaxLen => messages.text("settings.max-len"),
+			Setting::QuartilesOnly => messages.text("settings.quartiles-only")
+		}
+	}
+
+	/// Check whether the setting is adjusted in steps, rather than edited as
+	/// text or picked from a list.
+	///
+	/// # Returns
+	///
+	/// `true` if the setting is adjusted in steps, `false` otherwise.
+	fn is_stepped(self) -> bool
+	{
+		!matches!(self, Setting::Directory | Setting::Dictionary)
+	}
+}
+
+/// The step by which the highlight duration setting changes.
+const HIGHLIGHT_DURATION_STEP: u64 = 100;
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::fs;
+
+	use super::*;
+
+	/// Ensure that the settings change at runtime and persist to the
+	/// configuration file, that dictionaries are picked from the dictionary
+	/// directory and load in the background, and that a dictionary that can't
+	/// be opened leaves the dictionary unchanged.
+	#[test]
+	fn test_settings()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		let path = dir.path().join("quartiles.toml");
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let mut app = App::new(0, dictionary)
+			.with_dictionary_name("english")
+			.with_config_file(path.clone())
+			.with_menu();
+		for _ in 0..5
+		{
+			app.process_key_event(KeyCode::Down.into());
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(matches!(app.screen, Screen::Settings { selected: 0, .. }));
+
+		// Cycle the theme.
+		app.process_key_event(KeyCode::Down.into());
+		app.process_key_event(KeyCode::Down.into());
+		app.process_key_event(KeyCode::Right.into());
+		assert_eq!(app.theme_name, ThemeName::Light);
+		assert_eq!(app.theme, Theme::light());
+
+		// Bound the word length, then toggle the quartiles-only filter.
+		for _ in 0..3
+		{
+			app.process_key_event(KeyCode::Down.into());
+		}
+		app.process_key_event(KeyCode::Left.into());
+		assert_eq!(app.solver_config.max_len, MAX_WORD_LEN);
+		app.process_key_event(KeyCode::Down.into());
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(app.solver_config.quartiles_only);
+		let config = Config::load(&path).unwrap();
+		assert_eq!(config.theme, ThemeName::Light);
+		assert_eq!(config.max_len, Some(MAX_WORD_LEN));
+		assert!(config.quartiles_only);
+		assert_eq!(config.dictionary, None);
+
+		// Open the dictionary picker on the current directory, then close it.
+		for _ in 0..5
+		{
+			app.process_key_event(KeyCode::Up.into());
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(matches!(
+			app.screen,
+			Screen::Settings { picker: Some(Picker { ref names, .. }), .. }
+				if names == &["english"]
+		));
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(matches!(app.screen, Screen::Settings { picker: None, .. }));
+
+		// Change the directory, which opens the picker on it.
+		let words = dir.path().join("words");
+		fs::create_dir(&words).unwrap();
+		fs::write(words.join("small.txt"), "cross\nword\n").unwrap();
+		fs::write(words.join("tiny.txt"), "cross\n").unwrap();
+		app.process_key_event(KeyCode::Up.into());
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(matches!(
+			app.screen,
+			Screen::Settings { editing: Some(ref value), .. } if value == "dict"
+		));
+		for _ in 0..4
+		{
+			app.process_key_event(KeyCode::Backspace.into());
+		}
+		for c in words.display().to_string().chars()
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+		}
+		app.process_key_event(KeyCode::Enter.into());
+		assert!(matches!(
+			app.screen,
+			Screen::Settings { picker: Some(Picker { ref names, .. }), .. }
+				if names == &["small", "tiny"]
+		));
+		assert_eq!(Config::load(&path).unwrap().directory, words);
+
+		// Attempt to load a dictionary that vanished after the picker opened,
+		// and then dismiss the failure.
+		fs::remove_file(words.join("small.txt")).unwrap();
+		app.process_key_event(KeyCode::Enter.into());
+		while app.loader.is_some()
+		{
+			app.process_systems();
+		}
+		assert!(app.load_failure.is_some());
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(app.load_failure.is_none());
+		assert!(matches!(
+			app.screen,
+			Screen::Settings { picker: None, error: Some(_), .. }
+		));
+		assert_eq!(app.dictionary_name.as_deref(), Some("english"));
+		assert_eq!(Config::load(&path).unwrap().dictionary, None);
+
+		// Load another dictionary in the background.
+		app.process_key_event(KeyCode::Down.into());
+		app.process_key_event(KeyCode::Enter.into());
+		app.process_key_event(KeyCode::Enter.into());
+		let value = app.setting_value(Setting::Dictionary);
+		assert!(value.contains("loading tiny"), "{}", value);
+		while app.loader.is_some()
+		{
+			app.process_systems();
+		}
+		assert!(matches!(app.screen, Screen::Settings { error: None, .. }));
+		assert_eq!(app.dictionary_name.as_deref(), Some("tiny"));
+		assert_eq!(app.dictionary.len(), 1);
+		assert_eq!(
+			Config::load(&path).unwrap().dictionary.as_deref(),
+			Some("tiny")
+		);
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(matches!(app.screen, Screen::Menu { selected: 5 }));
+	}
+}
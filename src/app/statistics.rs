@@ -0,0 +1,264 @@
+//! # Statistics screen
+//!
+//! The screen that shows the statistics aggregated from the history.
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+	buffer::Buffer,
+	layout::{Constraint, Direction, Layout, Rect},
+	text::{Line, Span},
+	widgets::{
+		Bar, BarChart, BarGroup, Block, Borders, Padding, Paragraph, Sparkline,
+		Widget, Wrap
+	}
+};
+use tracing::warn;
+
+use super::{App, Screen};
+use crate::{
+	history::{self, Statistics},
+	keymap::{Action, Resolution}
+};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                Statistics.                                 //
+////////////////////////////////////////////////////////////////////////////////
+
+impl App
+{
+	/// Render the [statistics](Screen::Statistics) UI: a summary
+	/// above, and charts of the most common fragments and of the scores of
+	/// the played puzzles below.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `statistics` - The statistics.
+	/// * `error` - The error that prevented the history from being read, if
+	///   any, which is shown instead of the summary.
+	pub(super) fn render_statistics(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		statistics: &Statistics,
+		error: &Option<String>
+	)
+	{
+		let [summary_area, charts_area] = Layout::vertical([
+			Constraint::Length(6),
+			Constraint::Min(0)
+		])
+		.margin(1)
+		.areas(area);
+		let [fragments_area, scores_area] = Layout::horizontal([
+			Constraint::Percentage(50),
+			Constraint::Percentage(50)
+		])
+		.areas(charts_area);
+		// Summarize the history.
+		let percentage = match statistics.played
+		{
+			0 => 0.0,
+			n => statistics.completed as f64 * 100.0 / n as f64
+		};
+		let fastest = statistics.fastest
+			.map(|d| format!("{:.1}s", d.as_secs_f64()))
+			.unwrap_or_else(|| "—".to_string());
+		let summary = vec![
+			Line::from(self.text_with(
+				"statistics.played",
+				&[("count", &statistics.played)]
+			)),
+			Line::from(self.text_with(
+				"statistics.solved",
+				&[
+					("count", &statistics.completed),
+					("percentage", &format!("{:.0}", percentage))
+				]
+			)),
+			Line::from(self.text_with(
+				"statistics.bonus-words",
+				&[(
+					"average",
+					&format!("{:.1}", statistics.average_bonus_words)
+				)]
+			)),
+			Line::from(
+				self.text_with("statistics.fastest", &[("time", &fastest)])
+			)
+		];
+		let mut block = Block::default()
+			.borders(Borders::ALL)
+			.border_style(self.theme.border)
+			.title_top(Line::from(self.text("statistics.title")).centered());
+		if let Some(keys) = self.keymap.label(Action::Exit)
+		{
+			block = block.title_top(
+				Line::from(Span::styled(
+					format!("{} – {}", keys, self.text("common.back")),
+					self.theme.exit
+				))
+				.left_aligned()
+			);
+		}
+		let summary = match error
+		{
+			Some(ref e) => Paragraph::new(e.as_str())
+				.style(self.theme.failure)
+				.wrap(Wrap { trim: true }),
+			None => Paragraph::new(summary).style(self.theme.text)
+		};
+		summary
+			.block(block.padding(Padding::horizontal(1)))
+			.render(summary_area, buf);
+		// Chart the most common fragments, one horizontal bar apiece.
+		let bars = statistics.common_fragments
+			.iter()
+			.map(|(fragment, count)| {
+				Bar::default()
+					.label(Line::from(fragment.as_str()))
+					.value(*count as u64)
+			})
+			.collect::<Vec<_>>();
+		BarChart::default()
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.border_style(self.theme.border)
+					.title_top(
+						Line::from(self.text("statistics.fragments")).centered()
+					)
+			)
+			.direction(Direction::Horizontal)
+			.data(BarGroup::default().bars(&bars))
+			.bar_width(1)
+			.bar_gap(0)
+			.bar_style(self.theme.quartile)
+			.value_style(self.theme.selection)
+			.label_style(self.theme.text)
+			.render(fragments_area, buf);
+		// Chart the scores of the most recent puzzles that fit.
+		let width = scores_area.width.saturating_sub(2) as usize;
+		let scores = &statistics.scores[
+			statistics.scores.len().saturating_sub(width)..
+		];
+		Sparkline::default()
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.border_style(self.theme.border)
+					.title_top(
+						Line::from(self.text("statistics.scores")).centered()
+					)
+			)
+			.data(scores)
+			.style(self.theme.quartile)
+			.render(scores_area, buf);
+	}
+
+	/// Open the statistics dashboard, aggregating the entries of the history
+	/// file. If there is no history file, do nothing; if it can't be read, the
+	/// dashboard explains why in place of the summary.
+	pub(super) fn open_statistics(&mut self)
+	{
+		if let Some(ref path) = self.history_file
+		{
+			self.screen = match history::load(path)
+			{
+				Ok(entries) =>
+				{
+					let statistics = Statistics::new(&entries);
+					Screen::Statistics { statistics, error: None }
+				},
+				Err(e) =>
+				{
+					warn!(
+						"Failed to read history file: {}: {}",
+						path.display(),
+						e
+					);
+					let error = self.text_with(
+						"history.unreadable",
+						&[("path", &path.display()), ("error", &e)]
+					);
+					Screen::Statistics {
+						statistics: Statistics::default(),
+						error: Some(error)
+					}
+				}
+			};
+		}
+	}
+
+	/// Process a key event while viewing the
+	/// [statistics](Screen::Statistics):
+	///
+	/// * [Exit](Action::Exit), [Statistics](Action::Statistics) -
+	///   [Leave](Self::leave) the statistics dashboard.
+	/// * [Help](Action::Help) - Show the keybindings.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	pub(super) fn process_key_event_statistics(&mut self, event: KeyEvent)
+	{
+		match self.keymap.resolve(event)
+		{
+			Resolution::Action(Action::Exit | Action::Statistics) =>
+			{
+				self.leave()
+			},
+			Resolution::Action(Action::Help) => self.is_help_visible = true,
+			_ =>
+			{}
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use crossterm::event::KeyCode;
+	use quartiles_solver::dictionary::Dictionary;
+	use ratatui::widgets::Widget;
+
+	use super::*;
+	use crate::{
+		app::{test::solve_board, ExecutionState},
+		driver::ScriptedEvents
+	};
+
+	/// Ensure that the statistics dashboard summarizes the history recorded by
+	/// an earlier session, and that leaving it returns to the solver.
+	#[test]
+	fn test_statistics()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		let path = dir.path().join("history.json");
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let mut app = App::new(0, dictionary.clone())
+			.with_history_file(path.clone());
+		solve_board(&mut app, &mut ScriptedEvents::default());
+		let mut app = App::new(0, dictionary).with_history_file(path);
+		app.process_key_event(KeyCode::F(3).into());
+		assert!(matches!(app.screen, Screen::Statistics { .. }));
+		let area = Rect::new(0, 0, 80, 24);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let screen = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(screen.contains("Puzzles played: 1"), "{}", screen);
+		assert!(screen.contains("Puzzles solved: 1 (100%)"), "{}", screen);
+		assert!(screen.contains("Average bonus words: 25.0"), "{}", screen);
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(matches!(app.screen, Screen::Solve));
+		assert!(matches!(app.state, ExecutionState::Populating));
+	}
+}
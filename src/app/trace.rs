@@ -0,0 +1,349 @@
+//! # Trace screen
+//!
+//! The screen on which the developer steps through the decisions of the
+//! solver, in order to diagnose a solution. Available only with the
+//! `trace-ui` feature.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use quartiles_solver::{dictionary::normalize_word, trace::Decision};
+use ratatui::{
+	buffer::Buffer,
+	layout::{Constraint, Layout, Rect},
+	text::{Line, Span, Text},
+	widgets::{
+		Block, Borders, List, ListState, Paragraph, StatefulWidget, Widget
+	}
+};
+use unicode_normalization::char::is_combining_mark;
+
+use super::{App, ExecutionState, Screen};
+use crate::keymap::{Action, Resolution};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Trace.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+impl App
+{
+	/// Render the [solver trace](Screen::Trace) UI: the decisions that match
+	/// the filter above, and the details of the selected decision below,
+	/// including the fragments of its path.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `decisions` - The decisions recorded by the solver.
+	/// * `dropped` - The number of older decisions that were discarded.
+	/// * `selected` - The index of the selected decision among those that
+	///   match the filter.
+	/// * `filter` - The filter.
+	pub(super) fn render_trace(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		decisions: &[Decision],
+		dropped: usize,
+		selected: usize,
+		filter: &str
+	)
+	{
+		let [list_area, detail_area] = Layout::vertical([
+			Constraint::Min(0),
+			Constraint::Length(4)
+		])
+		.margin(1)
+		.areas(area);
+		let matches = matching_decisions(decisions, filter);
+		let mut block = Block::default()
+			.borders(Borders::ALL)
+			.border_style(self.theme.border)
+			.title_top(Line::from(self.text("trace.title")).centered())
+			.title_top(
+				Line::from(Span::styled(
+					self.text_with(
+						"trace.decisions",
+						&[
+							("matches", &matches.len()),
+							("total", &decisions.len()),
+							("dropped", &dropped)
+						]
+					),
+					self.theme.hint
+				))
+				.right_aligned()
+			)
+			.title_bottom(
+				Line::from(Span::styled(
+					self.hints(&[
+						(
+							&[Action::MoveUp, Action::MoveDown],
+							self.text("trace.step")
+						),
+						(&[Action::DeleteChar], self.text("common.delete"))
+					]),
+					self.theme.hint
+				))
+				.centered()
+			)
+			.title_bottom(
+				Line::from(Span::styled(
+					self.text_with("trace.filter", &[("filter", &filter)]),
+					self.theme.cursor
+				))
+				.right_aligned()
+			);
+		if let Some(keys) = self.keymap.label(Action::Exit)
+		{
+			block = block.title_top(
+				Line::from(Span::styled(
+					format!("{} – {}", keys, self.text("common.back")),
+					self.theme.exit
+				))
+				.left_aligned()
+			);
+		}
+		let items = matches
+			.iter()
+			.map(|&index| {
+				let decision = &decisions[index];
+				let style = match decision
+				{
+					Decision::Accepted { .. } => self.theme.quartile,
+					Decision::Rejected { .. } => self.theme.warning,
+					Decision::Pruned { .. } => self.theme.suggestion,
+					Decision::Backtracked { .. } => self.theme.text,
+					Decision::Exhausted => self.theme.success
+				};
+				Text::styled(format!("{:>6}  {}", index + 1, decision), style)
+			})
+			.collect::<Vec<_>>();
+		self.viewport_height.set(list_area.height.saturating_sub(2) as usize);
+		let list = List::new(items)
+			.block(block)
+			.highlight_style(self.theme.selection)
+			.highlight_symbol(self.highlight_symbol());
+		let mut list_state = ListState::default()
+			.with_selected((!matches.is_empty()).then_some(selected));
+		StatefulWidget::render(&list, list_area, buf, &mut list_state);
+		// Show the fragments of the selected decision's path, both by index
+		// and by content.
+		let detail = matches
+			.get(selected)
+			.map(|&index| &decisions[index])
+			.map(|decision| {
+				let indices = decision
+					.path()
+					.map(|path| path.iter().flatten().collect::<Vec<_>>())
+					.unwrap_or_default();
+				let fragments = indices
+					.iter()
+					.map(|&index| self.cells[index].to_string())
+					.collect::<Vec<_>>();
+				vec![
+					Line::from(decision.to_string()),
+					Line::from(format!(
+						"{:?}  {}",
+						indices,
+						fragments.join(" · ")
+					))
+				]
+			})
+			.unwrap_or_default();
+		Paragraph::new(detail)
+			.style(self.theme.text)
+			.block(
+				Block::default()
+					.borders(Borders::ALL)
+					.border_style(self.theme.border)
+					.title_top(
+						Line::from(self.text("trace.decision")).centered()
+					)
+			)
+			.render(detail_area, buf);
+	}
+
+	/// Open the [solver trace](Screen::Trace), selecting the most recent
+	/// decision. If the solver isn't tracing, e.g., because no solve has
+	/// started, do nothing.
+	pub(super) fn open_trace(&mut self)
+	{
+		let trace = match self.state
+		{
+			ExecutionState::Solving { ref solver }
+			| ExecutionState::Highlighting { ref solver, .. }
+			| ExecutionState::Finished { ref solver, .. } => solver.trace(),
+			_ => None
+		};
+		if let Some(trace) = trace
+		{
+			let decisions = trace.iter().copied().collect::<Vec<_>>();
+			self.screen = Screen::Trace {
+				selected: decisions.len().saturating_sub(1),
+				decisions,
+				dropped: trace.dropped(),
+				filter: String::new()
+			};
+		}
+	}
+
+	/// Process a key event while stepping through the
+	/// [solver trace](Screen::Trace):
+	///
+	/// * [Exit](Action::Exit), [Trace](Action::Trace) - Return to the solve
+	///   screen.
+	/// * [MoveUp](Action::MoveUp) - Step to the previous decision.
+	/// * [MoveDown](Action::MoveDown) - Step to the next decision.
+	/// * [PageUp](Action::PageUp) - Step back by a page.
+	/// * [PageDown](Action::PageDown) - Step forward by a page.
+	/// * [DeleteChar](Action::DeleteChar) - Delete the last character of the
+	///   filter.
+	/// * [Help](Action::Help) - Show the keybindings.
+	///
+	/// An unbound alphabetic key appends the corresponding character to the
+	/// filter, which keeps only the decisions whose candidate words contain
+	/// it. Changing the filter selects the first matching decision.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event to process.
+	pub(super) fn process_key_event_trace(&mut self, event: KeyEvent)
+	{
+		let page = self.viewport_height.get().max(1) as isize;
+		let resolution = self.keymap.resolve(event);
+		let Screen::Trace {
+			ref decisions,
+			ref mut selected,
+			ref mut filter,
+			..
+		} = self.screen
+		else
+		{
+			unreachable!()
+		};
+		let delta = match resolution
+		{
+			Resolution::Action(Action::Exit | Action::Trace) =>
+			{
+				self.screen = Screen::Solve;
+				return
+			},
+			Resolution::Action(Action::Help) =>
+			{
+				self.is_help_visible = true;
+				return
+			},
+			Resolution::Action(Action::DeleteChar) =>
+			{
+				filter.pop();
+				*selected = 0;
+				return
+			},
+			Resolution::Unbound(KeyEvent {
+				code: KeyCode::Char(c),
+				..
+			}) if c.is_alphabetic() || is_combining_mark(c) =>
+			{
+				filter.push(c);
+				*filter = normalize_word(filter);
+				*selected = 0;
+				return
+			},
+			Resolution::Action(Action::MoveUp) => -1,
+			Resolution::Action(Action::MoveDown) => 1,
+			Resolution::Action(Action::PageUp) => -page,
+			Resolution::Action(Action::PageDown) => page,
+			_ => return
+		};
+		let matches = matching_decisions(decisions, filter).len();
+		*selected = selected
+			.saturating_add_signed(delta)
+			.min(matches.saturating_sub(1));
+	}
+}
+
+/// Find the decisions whose candidate words contain the specified filter.
+///
+/// # Arguments
+///
+/// * `decisions` - The decisions.
+/// * `filter` - The filter. An empty filter matches every decision.
+///
+/// # Returns
+///
+/// The indices of the matching decisions, in order.
+pub(super) fn matching_decisions(decisions: &[Decision], filter: &str) -> Vec<usize>
+{
+	decisions
+		.iter()
+		.enumerate()
+		.filter(|(_, decision)| {
+			filter.is_empty()
+				|| decision.word().is_some_and(|word| word.contains(filter))
+		})
+		.map(|(index, _)| index)
+		.collect()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use quartiles_solver::dictionary::Dictionary;
+
+	use super::*;
+	use crate::{app::test::solve_board, driver::ScriptedEvents};
+
+	/// Ensure that the solver trace records the decisions of a solve, and
+	/// that filtering it finds the decisions about a word.
+	#[cfg(feature = "trace-ui")]
+	#[test]
+	fn test_trace()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let mut app = App::new(0, dictionary);
+		app.process_key_event(KeyCode::F(4).into());
+		assert!(matches!(app.screen, Screen::Solve));
+		solve_board(&mut app, &mut ScriptedEvents::default());
+		app.process_key_event(KeyCode::F(4).into());
+		let Screen::Trace { ref decisions, selected, .. } = app.screen
+		else
+		{
+			panic!("Not tracing")
+		};
+		assert_eq!(selected, decisions.len() - 1);
+		assert_eq!(decisions.last(), Some(&Decision::Exhausted));
+		for c in "truthful".chars()
+		{
+			app.process_key_event(KeyCode::Char(c).into());
+		}
+		let Screen::Trace { ref decisions, ref filter, selected, .. } =
+			app.screen
+		else
+		{
+			unreachable!()
+		};
+		assert_eq!(filter, "truthful");
+		assert_eq!(selected, 0);
+		let matches = matching_decisions(decisions, filter);
+		assert!(matches.iter().any(|&index| {
+			matches!(decisions[index], Decision::Accepted { word, .. }
+				if word.as_str() == "truthfully")
+		}));
+		let area = Rect::new(0, 0, 100, 40);
+		let mut buf = Buffer::empty(area);
+		(&app).render(area, &mut buf);
+		let screen = buf.content()
+			.iter()
+			.map(|cell| cell.symbol())
+			.collect::<String>();
+		assert!(screen.contains("Solver trace"), "{}", screen);
+		assert!(screen.contains("accept truthfully"), "{}", screen);
+		assert!(screen.contains("Filter: truthful"), "{}", screen);
+		app.process_key_event(KeyCode::Esc.into());
+		assert!(matches!(app.screen, Screen::Solve));
+	}
+}
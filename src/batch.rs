@@ -0,0 +1,803 @@
+//! # Batch solving
+//!
+//! Herein is support for the `solve-batch` subcommand, which solves many
+//! puzzles in one run and records the outcome and timing of each, so that
+//! dictionaries and the quality of generated puzzles can be evaluated at
+//! scale.
+
+use std::{
+	collections::BTreeSet,
+	fmt::{self, Display, Formatter},
+	io::{self, BufRead, ErrorKind, Write},
+	path::Path,
+	rc::Rc,
+	time::{Duration, Instant}
+};
+
+use clap::ValueEnum;
+use quartiles_solver::{
+	dictionary::{normalize_word, Dictionary},
+	solver::{parse_fragment, Fragment, Solver, SolverConfig, WordFilter}
+};
+use serde::{Deserialize, Serialize};
+use tracing::{trace, warn};
+
+use crate::app::Outcome;
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Boards.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// Convert the specified fragments into a board. Each fragment is
+/// [normalized](normalize_word) first.
+///
+/// # Arguments
+///
+/// * `fragments` - The fragments, in grid order.
+///
+/// # Returns
+///
+/// The board.
+///
+/// # Errors
+///
+/// If there are not exactly 20 fragments, or a fragment is empty or too long,
+/// an [`ErrorKind::InvalidData`] is returned.
+pub fn parse_board<T: AsRef<str>>(
+	fragments: &[T]
+) -> Result<[Fragment; 20], io::Error>
+{
+	if fragments.len() != 20
+	{
+		return Err(io::Error::new(
+			ErrorKind::InvalidData,
+			format!("expected 20 fragments, but found {}", fragments.len())
+		))
+	}
+	let mut board = [Fragment::new(); 20];
+	for (cell, fragment) in board.iter_mut().zip(fragments)
+	{
+		let fragment = normalize_word(fragment.as_ref());
+		if fragment.is_empty()
+		{
+			return Err(io::Error::new(ErrorKind::InvalidData, "empty fragment"))
+		}
+		*cell = parse_fragment(&fragment)
+			.map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+	}
+	Ok(board)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Batches.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The formats of a batch of puzzles, one puzzle per line. Blank lines are
+/// ignored.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum BatchFormat
+{
+	/// JSON lines, each either an array of the 20 fragments or an object
+	/// whose `fragments` field is such an array, e.g., an entry of the
+	/// history file.
+	#[default]
+	Json,
+
+	/// Comma-separated values, each line comprising the 20 fragments, without
+	/// a header.
+	Csv
+}
+
+impl BatchFormat
+{
+	/// Detect the format of the specified batch from its extension: `.csv`
+	/// for [CSV](Self::Csv), and [JSON lines](Self::Json) otherwise.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The batch.
+	///
+	/// # Returns
+	///
+	/// The format.
+	pub fn detect<T: AsRef<Path>>(path: T) -> Self
+	{
+		match path.as_ref().extension().and_then(|e| e.to_str())
+		{
+			Some(e) if e.eq_ignore_ascii_case("csv") => Self::Csv,
+			_ => Self::Json
+		}
+	}
+
+	/// Parse the fragments from a single line of a batch.
+	///
+	/// # Arguments
+	///
+	/// * `line` - The line, which must not be blank.
+	///
+	/// # Returns
+	///
+	/// The fragments, as written.
+	///
+	/// # Errors
+	///
+	/// If the line is malformed, an [`ErrorKind::InvalidData`] is returned.
+	fn parse_line(self, line: &str) -> Result<Vec<String>, io::Error>
+	{
+		match self
+		{
+			Self::Json => match serde_json::from_str(line)?
+			{
+				JsonPuzzle::Fragments(fragments) => Ok(fragments),
+				JsonPuzzle::Entry { fragments } => Ok(fragments)
+			},
+			Self::Csv => Ok(
+				line.split(',')
+					.map(|f| f.trim().trim_matches('"').to_string())
+					.collect()
+			)
+		}
+	}
+}
+
+/// A puzzle in the [JSON lines](BatchFormat::Json) format.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonPuzzle
+{
+	/// The bare fragments.
+	Fragments(Vec<String>),
+
+	/// An object with the fragments, whose other fields are ignored.
+	Entry
+	{
+		/// The fragments.
+		fragments: Vec<String>
+	}
+}
+
+/// The solver of a batch of puzzles. Each puzzle is solved by a fresh
+/// [solver](Solver) sharing the same dictionary, configuration, and word
+/// filter.
+#[must_use]
+pub struct Batch
+{
+	/// The dictionary.
+	dictionary: Rc<Dictionary>,
+
+	/// The configuration of each solver.
+	config: SolverConfig,
+
+	/// The word filter of each solver, if any.
+	filter: Option<Rc<dyn WordFilter>>,
+
+	/// How long each solver may run, if its time is limited.
+	time_budget: Option<Duration>
+}
+
+impl Batch
+{
+	/// Construct a batch solver that uses the specified dictionary.
+	///
+	/// # Arguments
+	///
+	/// * `dictionary` - The dictionary.
+	///
+	/// # Returns
+	///
+	/// The batch solver.
+	pub fn new(dictionary: Rc<Dictionary>) -> Self
+	{
+		Self {
+			dictionary,
+			config: SolverConfig::default(),
+			filter: None,
+			time_budget: None
+		}
+	}
+
+	/// Set the configuration of each solver.
+	///
+	/// # Arguments
+	///
+	/// * `config` - The configuration.
+	///
+	/// # Returns
+	///
+	/// The batch solver, with the configuration set.
+	pub fn with_config(mut self, config: SolverConfig) -> Self
+	{
+		self.config = config;
+		self
+	}
+
+	/// Set the word filter of each solver.
+	///
+	/// # Arguments
+	///
+	/// * `filter` - The word filter.
+	///
+	/// # Returns
+	///
+	/// The batch solver, with the word filter set.
+	pub fn with_filter(mut self, filter: Rc<dyn WordFilter>) -> Self
+	{
+		self.filter = Some(filter);
+		self
+	}
+
+	/// Limit how long each solver may run. A solver that runs out of time
+	/// reports the best solution found so far, marked as truncated.
+	///
+	/// # Arguments
+	///
+	/// * `budget` - How long each solver may run.
+	///
+	/// # Returns
+	///
+	/// The batch solver, with the time budget set.
+	pub fn with_time_budget(mut self, budget: Duration) -> Self
+	{
+		self.time_budget = Some(budget);
+		self
+	}
+
+	/// Solve every puzzle of the batch, writing a [record](Record) for each
+	/// to the output as a JSON line, in order. A malformed puzzle doesn't stop
+	/// the batch; its record reports the problem instead.
+	///
+	/// # Arguments
+	///
+	/// * `input` - The batch.
+	/// * `format` - The format of the batch.
+	/// * `output` - The destination of the records.
+	///
+	/// # Returns
+	///
+	/// The summary of the batch.
+	///
+	/// # Errors
+	///
+	/// If the batch cannot be read or the records cannot be written, an error
+	/// is returned.
+	pub fn run(
+		&self,
+		input: impl BufRead,
+		format: BatchFormat,
+		mut output: impl Write
+	) -> Result<Summary, io::Error>
+	{
+		let mut summary = Summary::default();
+		for (index, line) in input.lines().enumerate()
+		{
+			let line = line?;
+			if line.trim().is_empty()
+			{
+				continue
+			}
+			let record = self.solve(index + 1, format.parse_line(&line));
+			summary.puzzles += 1;
+			summary.duration += record.duration;
+			match record.outcome
+			{
+				Some(ref outcome) if outcome.solution.is_solved =>
+				{
+					summary.solved += 1
+				},
+				Some(_) => {},
+				None => summary.invalid += 1
+			}
+			serde_json::to_writer(&mut output, &record)?;
+			writeln!(output)?;
+		}
+		output.flush()?;
+		trace!("Solved batch: {}", summary);
+		Ok(summary)
+	}
+
+	/// Solve a single puzzle of the batch.
+	///
+	/// # Arguments
+	///
+	/// * `line` - The line of the puzzle, counted from 1.
+	/// * `fragments` - The fragments of the puzzle, if they could be parsed.
+	///
+	/// # Returns
+	///
+	/// The record of the puzzle.
+	fn solve(
+		&self,
+		line: usize,
+		fragments: Result<Vec<String>, io::Error>
+	) -> Record
+	{
+		let fragments = match fragments
+		{
+			Ok(fragments) => fragments,
+			Err(e) => return Record::invalid(line, Vec::new(), e)
+		};
+		let fragments = fragments
+			.iter()
+			.map(|f| normalize_word(f))
+			.collect::<Vec<_>>();
+		let board = match parse_board(&fragments)
+		{
+			Ok(board) => board,
+			Err(e) => return Record::invalid(line, fragments, e)
+		};
+		let (outcome, duration) = self.solve_board(board);
+		Record {
+			line,
+			fragments,
+			error: None,
+			quartiles: outcome.solution.quartiles.len(),
+			duration,
+			outcome: Some(outcome)
+		}
+	}
+
+	/// Solve the specified board.
+	///
+	/// # Arguments
+	///
+	/// * `board` - The board.
+	///
+	/// # Returns
+	///
+	/// The outcome, and how long the solver ran, excluding the analysis of an
+	/// incomplete solution.
+	fn solve_board(&self, board: [Fragment; 20]) -> (Outcome, Duration)
+	{
+		let started = Instant::now();
+		let mut solver = Solver::new(Rc::clone(&self.dictionary), board)
+			.with_config(self.config);
+		if let Some(ref filter) = self.filter
+		{
+			solver = solver.with_filter(Rc::clone(filter));
+		}
+		let solver = match self.time_budget
+		{
+			Some(budget) => solver.solve_within(budget),
+			None => solver.solve_fully()
+		};
+		let duration = started.elapsed();
+		(Outcome::of(&solver).with_duration(Some(duration)), duration)
+	}
+}
+
+/// The record of a single puzzle of a batch, as written to the output.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[must_use]
+pub struct Record
+{
+	/// The line of the puzzle in the batch, counted from 1.
+	pub line: usize,
+
+	/// The normalized fragments of the puzzle, in grid order. Empty if the
+	/// line is malformed.
+	pub fragments: Vec<String>,
+
+	/// Why the puzzle is invalid, if it is.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+
+	/// The number of quartiles in the solution.
+	pub quartiles: usize,
+
+	/// How long the solver ran, excluding the analysis of an incomplete
+	/// solution.
+	#[serde(rename = "duration-us", serialize_with = "serialize_micros")]
+	pub duration: Duration,
+
+	/// The outcome of the solve, unless the puzzle is invalid.
+	#[serde(flatten)]
+	pub outcome: Option<Outcome>
+}
+
+impl Record
+{
+	/// Construct the record of an invalid puzzle.
+	///
+	/// # Arguments
+	///
+	/// * `line` - The line of the puzzle, counted from 1.
+	/// * `fragments` - The fragments of the puzzle, if any.
+	/// * `error` - Why the puzzle is invalid.
+	///
+	/// # Returns
+	///
+	/// The record.
+	fn invalid(line: usize, fragments: Vec<String>, error: io::Error) -> Self
+	{
+		Self {
+			line,
+			fragments,
+			error: Some(error.to_string()),
+			quartiles: 0,
+			duration: Duration::ZERO,
+			outcome: None
+		}
+	}
+}
+
+/// Serialize the specified duration as a whole number of microseconds.
+///
+/// # Arguments
+///
+/// * `duration` - The duration.
+/// * `serializer` - The serializer.
+///
+/// # Returns
+///
+/// The result of serialization.
+///
+/// # Errors
+///
+/// If the serializer fails, its error is returned.
+fn serialize_micros<S: serde::Serializer>(
+	duration: &Duration,
+	serializer: S
+) -> Result<S::Ok, S::Error>
+{
+	serializer.serialize_u64(duration.as_micros() as u64)
+}
+
+/// The summary of a batch, as reported once every puzzle has been solved.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[must_use]
+pub struct Summary
+{
+	/// The number of puzzles, including invalid ones.
+	pub puzzles: usize,
+
+	/// The number of puzzles that were solved.
+	pub solved: usize,
+
+	/// The number of invalid puzzles, which were not solved.
+	pub invalid: usize,
+
+	/// The total time spent solving.
+	pub duration: Duration
+}
+
+impl Summary
+{
+	/// Get the mean time spent solving a valid puzzle.
+	///
+	/// # Returns
+	///
+	/// The mean duration, or zero if there are no valid puzzles.
+	#[must_use]
+	pub fn mean(&self) -> Duration
+	{
+		match self.puzzles - self.invalid
+		{
+			0 => Duration::ZERO,
+			n => self.duration / n as u32
+		}
+	}
+}
+
+impl Display for Summary
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(
+			f,
+			"Solved {} of {} puzzles ({} invalid) in {:?}, {:?} per puzzle",
+			self.solved,
+			self.puzzles,
+			self.invalid,
+			self.duration,
+			self.mean()
+		)
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                Comparisons.                                //
+////////////////////////////////////////////////////////////////////////////////
+
+/// Solve every puzzle of a batch with each of two batch solvers, e.g., with
+/// different dictionaries, and write the differences between their solutions
+/// to the output, in the manner of `diff`: for each puzzle solved differently,
+/// a heading with its line and whether each solver solved it, and then the
+/// words found only by the first solver (`<`) and only by the second (`>`).
+/// Invalid puzzles are skipped.
+///
+/// # Arguments
+///
+/// * `solvers` - The name and batch solver of each side of the comparison.
+/// * `input` - The batch.
+/// * `format` - The format of the batch.
+/// * `output` - The destination of the differences.
+///
+/// # Returns
+///
+/// The summary of the comparison.
+///
+/// # Errors
+///
+/// If the batch cannot be read or the differences cannot be written, an
+/// error is returned.
+pub fn compare(
+	solvers: [(&str, &Batch); 2],
+	input: impl BufRead,
+	format: BatchFormat,
+	mut output: impl Write
+) -> Result<Comparison, io::Error>
+{
+	let [(first_name, first), (second_name, second)] = solvers;
+	let mut comparison = Comparison::default();
+	for (index, line) in input.lines().enumerate()
+	{
+		let line = line?;
+		if line.trim().is_empty()
+		{
+			continue
+		}
+		let board = format.parse_line(&line)
+			.and_then(|fragments| parse_board(&fragments));
+		let board = match board
+		{
+			Ok(board) => board,
+			Err(e) =>
+			{
+				warn!("Skipping invalid puzzle: line {}: {}", index + 1, e);
+				comparison.invalid += 1;
+				continue
+			}
+		};
+		let (left, _) = first.solve_board(board);
+		let (right, _) = second.solve_board(board);
+		comparison.puzzles += 1;
+		let left_words = left.solution.words().collect::<BTreeSet<_>>();
+		let right_words = right.solution.words().collect::<BTreeSet<_>>();
+		let (left_solved, right_solved) =
+			(left.solution.is_solved, right.solution.is_solved);
+		if left_solved == right_solved && left_words == right_words
+		{
+			continue
+		}
+		comparison.differing += 1;
+		writeln!(
+			output,
+			"line {}: {} {}, {} {}",
+			index + 1,
+			first_name,
+			status(left_solved),
+			second_name,
+			status(right_solved)
+		)?;
+		for word in left_words.difference(&right_words)
+		{
+			writeln!(output, "< {}", word)?;
+		}
+		for word in right_words.difference(&left_words)
+		{
+			writeln!(output, "> {}", word)?;
+		}
+	}
+	output.flush()?;
+	Ok(comparison)
+}
+
+/// Describe whether a puzzle was solved.
+///
+/// # Arguments
+///
+/// * `is_solved` - Whether the puzzle was solved.
+///
+/// # Returns
+///
+/// The description.
+fn status(is_solved: bool) -> &'static str
+{
+	match is_solved
+	{
+		true => "solved",
+		false => "unsolved"
+	}
+}
+
+/// The summary of a [comparison](compare).
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[must_use]
+pub struct Comparison
+{
+	/// The number of valid puzzles, all of which were compared.
+	pub puzzles: usize,
+
+	/// The number of puzzles solved differently.
+	pub differing: usize,
+
+	/// The number of invalid puzzles, which were skipped.
+	pub invalid: usize
+}
+
+impl Display for Comparison
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(
+			f,
+			"{} of {} puzzles solved differently ({} invalid)",
+			self.differing,
+			self.puzzles,
+			self.invalid
+		)
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::{io::ErrorKind, rc::Rc, time::Duration};
+
+	use quartiles_solver::{dictionary::Dictionary, solver::SolverConfig};
+	use serde_json::Value;
+
+	use crate::batch::{
+		compare,
+		parse_board,
+		Batch,
+		BatchFormat,
+		Comparison,
+		Summary
+	};
+
+	/// The fragments of a solvable puzzle, whose quartiles are `crosswords`,
+	/// `nihilistic`, `razzmatazz`, `refreshment`, and `truthfully`.
+	const PUZZLE: [&str; 20] = [
+		"azz", "th", "ss", "tru", "ref", "fu", "ra", "nih", "cro", "mat",
+		"wo", "sh", "re", "rds", "tic", "il", "lly", "zz", "is", "ment"
+	];
+
+	/// Test [`parse_board`].
+	#[test]
+	fn test_parse_board()
+	{
+		let board = parse_board(&PUZZLE.map(str::to_uppercase)).unwrap();
+		assert_eq!(board[0].as_str(), "azz");
+		assert_eq!(board[19].as_str(), "ment");
+		let error = parse_board(&PUZZLE[..19]).unwrap_err();
+		assert_eq!(error.kind(), ErrorKind::InvalidData);
+		assert!(error.to_string().contains("found 19"));
+		let mut empty = PUZZLE;
+		empty[3] = " ";
+		assert!(parse_board(&empty).is_err());
+		let mut long = PUZZLE;
+		long[3] = "incomprehensibilities";
+		assert!(parse_board(&long).is_err());
+	}
+
+	/// Test [`BatchFormat::detect`].
+	#[test]
+	fn test_detect()
+	{
+		assert_eq!(BatchFormat::detect("puzzles.csv"), BatchFormat::Csv);
+		assert_eq!(BatchFormat::detect("PUZZLES.CSV"), BatchFormat::Csv);
+		assert_eq!(BatchFormat::detect("puzzles.jsonl"), BatchFormat::Json);
+		assert_eq!(BatchFormat::detect("puzzles"), BatchFormat::Json);
+	}
+
+	/// Ensure that a batch of JSON lines is solved in order, that a malformed
+	/// or incomplete puzzle is reported without stopping the batch, and that
+	/// blank lines are skipped but still counted.
+	#[test]
+	fn test_run_json()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let array = serde_json::to_string(&PUZZLE).unwrap();
+		let input = format!(
+			"{}\n\n{{\"date\": \"2024-06-01\", \"fragments\": {}}}\n\
+				not json\n[\"tru\", \"th\"]\n",
+			array,
+			array
+		);
+		let mut output = Vec::new();
+		let summary = Batch::new(dictionary)
+			.run(input.as_bytes(), BatchFormat::Json, &mut output)
+			.unwrap();
+		assert_eq!(summary.puzzles, 4);
+		assert_eq!(summary.solved, 2);
+		assert_eq!(summary.invalid, 2);
+		let records = String::from_utf8(output)
+			.unwrap()
+			.lines()
+			.map(|line| serde_json::from_str::<Value>(line).unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(records.len(), 4);
+		let lines = records.iter()
+			.map(|record| record["line"].as_u64().unwrap())
+			.collect::<Vec<_>>();
+		assert_eq!(lines, [1, 3, 4, 5]);
+		for record in &records[..2]
+		{
+			assert_eq!(record["solution"]["solved"], true);
+			assert_eq!(record["quartiles"], 5);
+			assert!(record["duration-us"].is_u64());
+			assert_eq!(record["fragments"][0], "azz");
+			assert!(record.get("error").is_none());
+		}
+		assert!(records[2]["error"].is_string());
+		assert_eq!(records[2]["fragments"], Value::Array(Vec::new()));
+		assert!(records[3]["error"].as_str().unwrap().contains("found 2"));
+		assert!(records[3].get("solution").is_none());
+	}
+
+	/// Ensure that a CSV batch is solved with the given solver configuration.
+	#[test]
+	fn test_run_csv()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let input = format!("{}\n", PUZZLE.join(", "));
+		let mut output = Vec::new();
+		let summary = Batch::new(dictionary)
+			.with_config(SolverConfig {
+				quartiles_only: true,
+				..SolverConfig::default()
+			})
+			.run(input.as_bytes(), BatchFormat::Csv, &mut output)
+			.unwrap();
+		assert_eq!((summary.puzzles, summary.solved), (1, 1));
+		let record = serde_json::from_slice::<Value>(&output).unwrap();
+		assert_eq!(
+			record["solution"]["quartiles"].as_array().unwrap().len(),
+			5
+		);
+		assert_eq!(record["solution"]["bonus-words"], Value::Array(Vec::new()));
+	}
+
+	/// Ensure that [`compare`] reports only the puzzles solved differently,
+	/// with the words that only one side found, and skips invalid puzzles.
+	#[test]
+	fn test_compare()
+	{
+		let full = Dictionary::open("dict", "english").unwrap();
+		let mut pruned = full.clone();
+		pruned.exclude(["truthfully", "thrash"]);
+		let full = Batch::new(Rc::new(full));
+		let pruned = Batch::new(Rc::new(pruned));
+		let input = format!(
+			"{}\n{}\n[\"tru\"]\n",
+			PUZZLE.join(","),
+			["ab"; 20].join(",")
+		);
+		let mut output = Vec::new();
+		let comparison = compare(
+			[("full", &full), ("pruned", &pruned)],
+			input.as_bytes(),
+			BatchFormat::Csv,
+			&mut output
+		)
+		.unwrap();
+		assert_eq!(
+			comparison,
+			Comparison { puzzles: 2, differing: 1, invalid: 1 }
+		);
+		assert_eq!(
+			String::from_utf8(output).unwrap(),
+			"line 1: full solved, pruned unsolved\n< thrash\n< truthfully\n"
+		);
+	}
+
+	/// Test [`Summary::mean`].
+	#[test]
+	fn test_mean()
+	{
+		let summary = Summary {
+			puzzles: 3,
+			solved: 1,
+			invalid: 1,
+			duration: Duration::from_millis(10)
+		};
+		assert_eq!(summary.mean(), Duration::from_millis(5));
+		assert_eq!(Summary::default().mean(), Duration::ZERO);
+	}
+}
@@ -0,0 +1,304 @@
+//! # Benchmarks
+//!
+//! Herein is support for the `bench` subcommand, which times dictionary loading
+//! and a canned puzzle solve on the user's own machine, so that dictionary
+//! backends can be evaluated without installing a benchmarking harness.
+
+use std::{
+	fmt::{self, Display, Formatter},
+	io::{self, Write},
+	path::Path,
+	rc::Rc,
+	time::{Duration, Instant}
+};
+
+use quartiles_solver::{
+	dictionary::Dictionary,
+	solver::{Fragment, Solver}
+};
+use tracing::trace;
+
+////////////////////////////////////////////////////////////////////////////////
+//                                Benchmarks.                                 //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The fragments of the canned puzzle, whose solution contains the quartiles
+/// `crosswords`, `nihilistic`, `razzmatazz`, `refreshment`, and `truthfully`.
+const PUZZLE: [&str; 20] = [
+	"azz", "th", "ss", "tru", "ref", "fu", "ra", "nih", "cro", "mat",
+	"wo", "sh", "re", "rds", "tic", "il", "lly", "zz", "is", "ment"
+];
+
+/// The timings of a single benchmarked operation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[must_use]
+pub struct Measurement
+{
+	/// The name of the operation.
+	pub name: String,
+
+	/// The duration of each run of the operation, in order.
+	pub samples: Vec<Duration>
+}
+
+impl Measurement
+{
+	/// Get the mean duration of the operation.
+	///
+	/// # Returns
+	///
+	/// The mean duration, or zero if there are no samples.
+	#[must_use]
+	pub fn mean(&self) -> Duration
+	{
+		match self.samples.len()
+		{
+			0 => Duration::ZERO,
+			n => self.samples.iter().sum::<Duration>() / n as u32
+		}
+	}
+
+	/// Get the shortest duration of the operation.
+	///
+	/// # Returns
+	///
+	/// The shortest duration, or zero if there are no samples.
+	#[must_use]
+	pub fn min(&self) -> Duration
+	{
+		self.samples.iter().min().copied().unwrap_or_default()
+	}
+
+	/// Get the longest duration of the operation.
+	///
+	/// # Returns
+	///
+	/// The longest duration, or zero if there are no samples.
+	#[must_use]
+	pub fn max(&self) -> Duration
+	{
+		self.samples.iter().max().copied().unwrap_or_default()
+	}
+}
+
+/// Run every benchmark against the dictionary with the given name. Loading
+/// the text dictionary is skipped if there is no text dictionary, e.g.,
+/// because only the binary dictionary was installed.
+///
+/// # Arguments
+///
+/// * `dir` - The directory containing the dictionary files.
+/// * `name` - The name of the dictionary.
+/// * `iterations` - The number of times to run each operation.
+///
+/// # Returns
+///
+/// The measurements, in the order that the operations were run.
+///
+/// # Errors
+///
+/// If any dictionary cannot be read or written, an error is returned.
+pub fn run<T: AsRef<Path>>(
+	dir: T,
+	name: &str,
+	iterations: usize
+) -> Result<Vec<Measurement>, io::Error>
+{
+	let dir = dir.as_ref();
+	let mut measurements = Vec::new();
+	let txt_path = dir.join(format!("{}.txt", name));
+	if txt_path.exists()
+	{
+		measurements.push(measure("load text", iterations, || {
+			Dictionary::read_from_file(&txt_path)
+		})?);
+	}
+	let dict_path = dir.join(format!("{}.dict", name));
+	let mut dictionary = None;
+	measurements.push(measure("load binary", iterations, || {
+		dictionary = Some(Dictionary::deserialize_from_file(&dict_path)?);
+		Ok(())
+	})?);
+	let dictionary = Rc::new(dictionary.unwrap_or_default());
+	#[cfg(feature = "compress")]
+	{
+		use quartiles_solver::dictionary::Compression;
+		for (label, compression) in [
+			("load gzip", Compression::Gzip),
+			("load zstd", Compression::Zstd)
+		]
+		{
+			let path = std::env::temp_dir().join(format!(
+				"quartiles-bench-{}-{:?}.dict",
+				std::process::id(),
+				compression
+			));
+			dictionary.serialize_to_file_with(&path, compression)?;
+			let measurement = measure(label, iterations, || {
+				Dictionary::deserialize_from_file(&path)
+			});
+			let _ = std::fs::remove_file(&path);
+			measurements.push(measurement?);
+		}
+	}
+	let fragments = PUZZLE.map(Fragment::from);
+	measurements.push(measure("solve puzzle", iterations, || {
+		let solver = Solver::builder()
+			.dictionary(Rc::clone(&dictionary))
+			.fragments(fragments)
+			.build()
+			.map_err(io::Error::other)?;
+		Ok(solver.solve_fully())
+	})?);
+	Ok(measurements)
+}
+
+/// Time the given operation.
+///
+/// # Arguments
+///
+/// * `name` - The name of the operation.
+/// * `iterations` - The number of times to run the operation.
+/// * `operation` - The operation.
+///
+/// # Returns
+///
+/// The measurement.
+///
+/// # Errors
+///
+/// If any run of the operation fails, its error is returned.
+fn measure<T>(
+	name: &str,
+	iterations: usize,
+	mut operation: impl FnMut() -> Result<T, io::Error>
+) -> Result<Measurement, io::Error>
+{
+	let mut samples = Vec::with_capacity(iterations);
+	for _ in 0..iterations
+	{
+		let start = Instant::now();
+		let result = operation()?;
+		samples.push(start.elapsed());
+		// Drop the result outside the timed region, since freeing a large
+		// dictionary is not part of loading it.
+		drop(result);
+	}
+	trace!("Benchmarked {}: {:?}", name, samples);
+	Ok(Measurement { name: name.to_string(), samples })
+}
+
+/// A table of [measurements](Measurement), formatted for a terminal.
+#[derive(Copy, Clone, Debug)]
+pub struct Table<'a>(pub &'a [Measurement]);
+
+impl Display for Table<'_>
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		let width = self.0
+			.iter()
+			.map(|m| m.name.len())
+			.chain(["operation".len()])
+			.max()
+			.unwrap_or_default();
+		writeln!(
+			f,
+			"{:<width$}  {:>10}  {:>10}  {:>10}",
+			"operation",
+			"mean",
+			"min",
+			"max"
+		)?;
+		writeln!(f, "{}", "-".repeat(width + 36))?;
+		for measurement in self.0
+		{
+			writeln!(
+				f,
+				"{:<width$}  {:>10}  {:>10}  {:>10}",
+				measurement.name,
+				format_duration(measurement.mean()),
+				format_duration(measurement.min()),
+				format_duration(measurement.max())
+			)?;
+		}
+		Ok(())
+	}
+}
+
+/// Format the given duration in milliseconds, with a fixed precision.
+///
+/// # Arguments
+///
+/// * `duration` - The duration.
+///
+/// # Returns
+///
+/// The formatted duration.
+fn format_duration(duration: Duration) -> String
+{
+	format!("{:.2} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// Write the given measurements to standard output as a table.
+///
+/// # Arguments
+///
+/// * `measurements` - The measurements.
+///
+/// # Errors
+///
+/// If standard output cannot be written, an error is returned.
+pub fn print_table(measurements: &[Measurement]) -> Result<(), io::Error>
+{
+	write!(io::stdout().lock(), "{}", Table(measurements))
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::time::Duration;
+
+	use crate::bench::{run, Measurement, Table};
+
+	/// Ensure that every benchmark runs against the English dictionary.
+	#[test]
+	fn test_run()
+	{
+		let measurements = run("dict", "english", 1).unwrap();
+		let names = measurements
+			.iter()
+			.map(|m| m.name.as_str())
+			.collect::<Vec<_>>();
+		assert_eq!(names.first(), Some(&"load text"));
+		assert!(names.contains(&"load binary"));
+		assert_eq!(names.last(), Some(&"solve puzzle"));
+		assert!(measurements.iter().all(|m| m.samples.len() == 1));
+		assert!(run("dict", "does-not-exist", 1).is_err());
+	}
+
+	/// Ensure that measurements are summarized and tabulated correctly.
+	#[test]
+	fn test_table()
+	{
+		let measurement = Measurement {
+			name: "load binary".to_string(),
+			samples: [3, 1, 2].map(Duration::from_millis).to_vec()
+		};
+		assert_eq!(measurement.mean(), Duration::from_millis(2));
+		assert_eq!(measurement.min(), Duration::from_millis(1));
+		assert_eq!(measurement.max(), Duration::from_millis(3));
+		let table = Table(&[measurement]).to_string();
+		let lines = table.lines().collect::<Vec<_>>();
+		assert_eq!(lines.len(), 3);
+		assert!(lines[0].starts_with("operation"));
+		assert_eq!(
+			lines[2],
+			"load binary     2.00 ms     1.00 ms     3.00 ms"
+		);
+	}
+}
@@ -0,0 +1,247 @@
+//! # Share cards
+//!
+//! Herein is support for sharing the result of a puzzle without spoiling it:
+//! a short card of emoji and text, in the manner of Wordle, that names the
+//! puzzle and tallies the quartiles, words, and points, but never reveals the
+//! words themselves. The card is printed to standard output or copied to the
+//! clipboard via the OSC 52 escape sequence, which most terminals honor.
+
+use std::{
+	fmt::{self, Display, Formatter},
+	io::{self, stderr, Write}
+};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::daily::Date;
+
+////////////////////////////////////////////////////////////////////////////////
+//                                Share cards.                                //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The number of quartiles in an official puzzle, i.e., the number of squares
+/// on a share card.
+const QUARTILES: usize = 5;
+
+/// A share card, which summarizes the result of a puzzle without spoiling it.
+/// Displays as a few lines, e.g.:
+///
+/// ```text
+/// Quartiles 2026-10-16
+/// 🟩🟩🟩🟩⬜
+/// 📖 18/30 words · ⭐ 64/112 points
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Card
+{
+	/// The date of the puzzle, if it is a daily puzzle.
+	pub date: Option<Date>,
+
+	/// The seed from which the puzzle was generated, if it was.
+	pub seed: Option<u64>,
+
+	/// The number of quartiles found.
+	pub quartiles: usize,
+
+	/// The number of words found.
+	pub words: usize,
+
+	/// The number of words that the board yields, if the player may not have
+	/// found them all.
+	pub total_words: Option<usize>,
+
+	/// The score of the words found.
+	pub score: u32,
+
+	/// The best possible score, if the player may not have achieved it.
+	pub max_score: Option<u32>
+}
+
+impl Display for Card
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+	{
+		match (self.date, self.seed)
+		{
+			(Some(date), _) => writeln!(f, "Quartiles {}", date)?,
+			(None, Some(seed)) => writeln!(f, "Quartiles #{}", seed)?,
+			(None, None) => writeln!(f, "Quartiles")?
+		}
+		let found = self.quartiles.min(QUARTILES);
+		writeln!(f, "{}{}", "🟩".repeat(found), "⬜".repeat(QUARTILES - found))?;
+		let words = match self.total_words
+		{
+			Some(total) => format!("{}/{}", self.words, total),
+			None => self.words.to_string()
+		};
+		let score = match self.max_score
+		{
+			Some(max) => format!("{}/{}", self.score, max),
+			None => self.score.to_string()
+		};
+		writeln!(f, "📖 {} words · ⭐ {} points", words, score)
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Export.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// Where to send a share card exported from the TUI.
+#[derive(
+	Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+	ValueEnum
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum CardTarget
+{
+	/// Print the card to standard output, after the solution.
+	#[default]
+	Stdout,
+
+	/// Copy the card to the clipboard, via the terminal.
+	Clipboard
+}
+
+/// Export the specified share card to the specified target. The TUI must have
+/// exited, since the clipboard is reached through the terminal.
+///
+/// # Arguments
+///
+/// * `card` - The share card.
+/// * `target` - Where to send the card.
+///
+/// # Errors
+///
+/// If the card cannot be written, an error is returned.
+pub fn export(card: &Card, target: CardTarget) -> Result<(), io::Error>
+{
+	match target
+	{
+		CardTarget::Stdout =>
+		{
+			print!("{}", card);
+			Ok(())
+		},
+		CardTarget::Clipboard => copy_to_clipboard(stderr(), &card.to_string())
+	}
+}
+
+/// Copy the specified text to the clipboard, by writing the OSC 52 escape
+/// sequence to the specified terminal. A terminal that doesn't honor the
+/// sequence ignores it.
+///
+/// # Arguments
+///
+/// * `terminal` - The terminal.
+/// * `text` - The text to copy.
+///
+/// # Errors
+///
+/// If the terminal cannot be written, an error is returned.
+pub fn copy_to_clipboard<W: Write>(
+	mut terminal: W,
+	text: &str
+) -> Result<(), io::Error>
+{
+	write!(terminal, "\x1b]52;c;{}\x07", base64(text.as_bytes()))?;
+	terminal.flush()
+}
+
+/// Encode the specified bytes as padded Base64, as OSC 52 requires.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes to encode.
+///
+/// # Returns
+///
+/// The encoding.
+fn base64(bytes: &[u8]) -> String
+{
+	const ALPHABET: &[u8; 64] =
+		b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+	let mut encoding = String::with_capacity(bytes.len().div_ceil(3) * 4);
+	for chunk in bytes.chunks(3)
+	{
+		let group = chunk.iter()
+			.enumerate()
+			.fold(0u32, |group, (index, &byte)| {
+				group | (byte as u32) << (16 - 8 * index)
+			});
+		for index in 0..4
+		{
+			match index <= chunk.len()
+			{
+				true =>
+				{
+					let sextet = (group >> (18 - 6 * index)) & 0x3f;
+					encoding.push(ALPHABET[sextet as usize] as char);
+				},
+				false => encoding.push('=')
+			}
+		}
+	}
+	encoding
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use crate::{
+		card::{base64, copy_to_clipboard, Card},
+		daily::Date
+	};
+
+	/// Ensure that a card names its puzzle by date, then by seed, and tallies
+	/// the result without any words.
+	#[test]
+	fn test_display()
+	{
+		let card = Card {
+			date: Some(Date { year: 2026, month: 10, day: 16 }),
+			seed: Some(7),
+			quartiles: 4,
+			words: 18,
+			total_words: Some(30),
+			score: 64,
+			max_score: Some(112)
+		};
+		assert_eq!(
+			card.to_string(),
+			"Quartiles 2026-10-16\n🟩🟩🟩🟩⬜\n📖 18/30 words · ⭐ 64/112 points\n"
+		);
+		let card = Card { date: None, ..card };
+		assert!(card.to_string().starts_with("Quartiles #7\n"));
+		let card = Card {
+			seed: None,
+			quartiles: 6,
+			total_words: None,
+			max_score: None,
+			..card
+		};
+		assert_eq!(
+			card.to_string(),
+			"Quartiles\n🟩🟩🟩🟩🟩\n📖 18 words · ⭐ 64 points\n"
+		);
+	}
+
+	/// Ensure that the clipboard receives the Base64 encoding of the text.
+	#[test]
+	fn test_clipboard()
+	{
+		assert_eq!(base64(b""), "");
+		assert_eq!(base64(b"M"), "TQ==");
+		assert_eq!(base64(b"Ma"), "TWE=");
+		assert_eq!(base64(b"Man"), "TWFu");
+		assert_eq!(base64("⭐".as_bytes()), "4q2Q");
+		let mut terminal = Vec::new();
+		copy_to_clipboard(&mut terminal, "Man").unwrap();
+		assert_eq!(terminal, b"\x1b]52;c;TWFu\x07");
+	}
+}
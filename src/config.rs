@@ -0,0 +1,295 @@
+//! # Configuration
+//!
+//! Herein is support for the optional configuration file, which is written in
+//! [TOML](https://toml.io). Every setting is optional, and a missing
+//! configuration file is equivalent to an empty one.
+
+use std::{
+	collections::BTreeMap,
+	fs::{self, File},
+	io::{self, ErrorKind, Write},
+	path::{Path, PathBuf}
+};
+
+use quartiles_solver::{dictionary::Language, solver::SolverConfig};
+use serde::{Deserialize, Serialize};
+use tracing::trace;
+
+use crate::{
+	keymap::{Action, KeymapPreset},
+	locale::Lang,
+	paths,
+	theme::ThemeName
+};
+
+////////////////////////////////////////////////////////////////////////////////
+//                               Configuration.                               //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The application configuration.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+#[must_use]
+pub struct Config
+{
+	/// The path to a [Rhai](https://rhai.rs) script that defines custom word
+	/// filters and scorers. Requires the `scripting` feature.
+	pub script: Option<PathBuf>,
+
+	/// The language of the dictionary.
+	pub language: Language,
+
+	/// The path to the directory containing the dictionary files.
+	pub directory: PathBuf,
+
+	/// The name of the dictionary, sans the extension. Defaults to the name
+	/// of the [language](Self::language).
+	pub dictionary: Option<String>,
+
+	/// How long (in µs) to highlight an individual word in the TUI.
+	pub highlight_duration: u64,
+
+	/// The minimum length of a word, in letters.
+	pub min_len: usize,
+
+	/// The maximum length of a word, in letters, if any.
+	pub max_len: Option<usize>,
+
+	/// Whether to exclude every word but the quartiles from the solution.
+	pub quartiles_only: bool,
+
+	/// Whether dictionary lookups ignore case and stray whitespace, e.g., so
+	/// that `Hello` in a word list whose case was kept still matches.
+	pub fold_case: bool,
+
+	/// The path to the state file, which persists an in-progress solve so
+	/// that it can be resumed after the terminal disconnects.
+	pub state_file: PathBuf,
+
+	/// The path to the history file, which records the puzzles that the user
+	/// has played.
+	pub history_file: PathBuf,
+
+	/// The path to the favorites file, which records the words that the user
+	/// marked as favorites while reviewing a solution.
+	pub favorites_file: PathBuf,
+
+	/// The color scheme of the TUI.
+	pub theme: ThemeName,
+
+	/// The keymap preset of the TUI.
+	pub keymap: KeymapPreset,
+
+	/// Whether exiting the TUI with a typed board asks for confirmation.
+	pub confirm_exit: bool,
+
+	/// Whether the TUI is accessible, e.g., for a screen reader: no state is
+	/// conveyed by color alone, and every change of state is described in
+	/// words.
+	pub accessible: bool,
+
+	/// Whether motion in the TUI is reduced: the solver streams the words it
+	/// finds without highlighting each one in turn.
+	pub reduced_motion: bool,
+
+	/// The language of the user interface. Defaults to the
+	/// [language](Self::language) of the dictionary.
+	pub lang: Option<Lang>,
+
+	/// Overrides of the keymap preset. Each action maps to the key sequences
+	/// that trigger it, e.g., `solve = ["ctrl+s"]`.
+	pub keys: BTreeMap<Action, Vec<String>>
+}
+
+impl Default for Config
+{
+	fn default() -> Self
+	{
+		Self {
+			script: None,
+			language: Language::default(),
+			directory: paths::dictionary_directory(),
+			dictionary: None,
+			highlight_duration: 400,
+			min_len: 0,
+			max_len: None,
+			quartiles_only: false,
+			fold_case: false,
+			state_file: paths::state_file(),
+			history_file: paths::history_file(),
+			favorites_file: paths::favorites_file(),
+			theme: ThemeName::default(),
+			keymap: KeymapPreset::default(),
+			confirm_exit: true,
+			accessible: false,
+			reduced_motion: false,
+			lang: None,
+			keys: BTreeMap::new()
+		}
+	}
+}
+
+impl Config
+{
+	/// Read the configuration from the given file. If the file does not exist,
+	/// answer the default configuration.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The configuration file.
+	///
+	/// # Returns
+	///
+	/// The configuration.
+	///
+	/// # Errors
+	///
+	/// * If the file exists but cannot be read, an error is returned.
+	/// * If the file contains invalid data, an [`ErrorKind::InvalidData`] is
+	///   returned.
+	pub fn load<T: AsRef<Path>>(path: T) -> Result<Self, io::Error>
+	{
+		let path = path.as_ref();
+		if !path.exists()
+		{
+			trace!("No configuration file: {}", path.display());
+			return Ok(Self::default())
+		}
+		let content = fs::read_to_string(path)?;
+		let config = toml::from_str(&content)
+			.map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+		trace!("Read configuration file: {}", path.display());
+		Ok(config)
+	}
+
+	/// Write the configuration to the given file, replacing it atomically.
+	/// Comments and formatting of the previous file are not preserved. Missing
+	/// parent directories are created.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The configuration file.
+	///
+	/// # Errors
+	///
+	/// * If the configuration cannot be serialized, an
+	///   [`ErrorKind::InvalidData`] is returned.
+	/// * If the file cannot be written, an error is returned.
+	pub fn save<T: AsRef<Path>>(&self, path: T) -> Result<(), io::Error>
+	{
+		let path = path.as_ref();
+		let content = toml::to_string_pretty(self)
+			.map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+		if let Some(parent) = path.parent()
+		{
+			fs::create_dir_all(parent)?;
+		}
+		let temp_path = path.with_extension("tmp");
+		let mut file = File::create(&temp_path)?;
+		file.write_all(content.as_bytes())?;
+		file.sync_all()?;
+		fs::rename(&temp_path, path)?;
+		trace!("Wrote configuration file: {}", path.display());
+		Ok(())
+	}
+
+	/// Answer the [solver configuration](SolverConfig) described by the
+	/// configuration.
+	///
+	/// # Returns
+	///
+	/// The solver configuration.
+	pub fn solver_config(&self) -> SolverConfig
+	{
+		SolverConfig {
+			min_len: self.min_len,
+			max_len: self.max_len.unwrap_or(usize::MAX),
+			quartiles_only: self.quartiles_only,
+			..Default::default()
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::{io::Write, path::PathBuf};
+
+	use quartiles_solver::dictionary::Language;
+	use tempfile::NamedTempFile;
+
+	use crate::{
+		config::Config,
+		keymap::{Action, KeymapPreset},
+		locale::Lang,
+		theme::ThemeName
+	};
+
+	/// Ensure that a configuration file can be read, and that a missing
+	/// configuration file produces the default configuration.
+	#[test]
+	fn test_load()
+	{
+		let config = Config::load("does/not/exist.toml").unwrap();
+		assert_eq!(config, Config::default());
+
+		let mut file = NamedTempFile::new().unwrap();
+		writeln!(file, "script = \"filters.rhai\"").unwrap();
+		writeln!(file, "language = \"german\"").unwrap();
+		writeln!(file, "history-file = \"history.json\"").unwrap();
+		writeln!(file, "directory = \"words\"").unwrap();
+		writeln!(file, "highlight-duration = 100").unwrap();
+		writeln!(file, "max-len = 12").unwrap();
+		writeln!(file, "theme = \"high-contrast\"").unwrap();
+		writeln!(file, "keymap = \"vim\"").unwrap();
+		writeln!(file, "confirm-exit = false").unwrap();
+		writeln!(file, "accessible = true").unwrap();
+		writeln!(file, "reduced-motion = true").unwrap();
+		writeln!(file, "lang = \"de\"").unwrap();
+		writeln!(file, "fold-case = true").unwrap();
+		writeln!(file, "[keys]").unwrap();
+		writeln!(file, "solve = [\"ctrl+s\"]").unwrap();
+		let config = Config::load(file.path()).unwrap();
+		assert_eq!(config.script, Some(PathBuf::from("filters.rhai")));
+		assert_eq!(config.language, Language::German);
+		assert_eq!(config.history_file, PathBuf::from("history.json"));
+		assert_eq!(config.directory, PathBuf::from("words"));
+		assert_eq!(config.dictionary, None);
+		assert_eq!(config.highlight_duration, 100);
+		assert_eq!(config.solver_config().min_len, 0);
+		assert_eq!(config.solver_config().max_len, 12);
+		assert_eq!(config.theme, ThemeName::HighContrast);
+		assert_eq!(config.keymap, KeymapPreset::Vim);
+		assert!(!config.confirm_exit);
+		assert!(config.accessible);
+		assert!(config.reduced_motion);
+		assert_eq!(config.lang, Some(Lang::De));
+		assert!(config.fold_case);
+		assert_eq!(config.keys[&Action::Solve], vec!["ctrl+s".to_string()]);
+
+		let mut file = NamedTempFile::new().unwrap();
+		writeln!(file, "script = [").unwrap();
+		assert!(Config::load(file.path()).is_err());
+	}
+
+	/// Ensure that a saved configuration reads back unchanged.
+	#[test]
+	fn test_save()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		let path = dir.path().join("quartiles.toml");
+		let mut config = Config {
+			dictionary: Some("scrabble".to_string()),
+			theme: ThemeName::Light,
+			quartiles_only: true,
+			..Default::default()
+		};
+		config.keys.insert(Action::Solve, vec!["ctrl+s".to_string()]);
+		config.save(&path).unwrap();
+		assert_eq!(Config::load(&path).unwrap(), config);
+	}
+}
@@ -0,0 +1,189 @@
+//! # Curation
+//!
+//! Herein is support for curating the dictionary while reviewing a solution:
+//! a bogus word is appended to the dictionary's exclusion list
+//! (`<name>.exclude.txt`), which every later load of the dictionary applies,
+//! and a favorite word is recorded in the favorites file. Both files are plain
+//! word lists, with one word per line.
+
+use std::{
+	collections::BTreeSet,
+	fs::{self, File, OpenOptions},
+	io::{self, Write},
+	path::{Path, PathBuf}
+};
+
+use tracing::trace;
+
+////////////////////////////////////////////////////////////////////////////////
+//                              Exclusion lists.                              //
+////////////////////////////////////////////////////////////////////////////////
+
+/// Answer the path to the exclusion list of the specified dictionary, which
+/// [`Dictionary::open`](quartiles_solver::dictionary::Dictionary::open)
+/// applies whenever it loads the dictionary.
+///
+/// # Arguments
+///
+/// * `directory` - The directory containing the dictionary files.
+/// * `name` - The name of the dictionary.
+///
+/// # Returns
+///
+/// The path.
+#[must_use]
+pub fn exclusion_list(directory: &Path, name: &str) -> PathBuf
+{
+	directory.join(format!("{}.exclude.txt", name))
+}
+
+/// Append the given word to the exclusion list, creating the exclusion list
+/// if necessary. Missing parent directories are created.
+///
+/// # Arguments
+///
+/// * `path` - The exclusion list.
+/// * `word` - The word to exclude.
+///
+/// # Errors
+///
+/// If the file cannot be written, an error is returned.
+pub fn exclude<T: AsRef<Path>>(path: T, word: &str) -> Result<(), io::Error>
+{
+	let path = path.as_ref();
+	if let Some(parent) = path.parent()
+	{
+		fs::create_dir_all(parent)?;
+	}
+	let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+	writeln!(file, "{}", word)?;
+	trace!("Excluded {}: {}", word, path.display());
+	Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Favorites.                                 //
+////////////////////////////////////////////////////////////////////////////////
+
+/// Read the favorite words from the given file. A missing file holds no
+/// favorites, and blank lines are ignored.
+///
+/// # Arguments
+///
+/// * `path` - The favorites file.
+///
+/// # Returns
+///
+/// The favorite words.
+///
+/// # Errors
+///
+/// If the file exists but cannot be read, an error is returned.
+pub fn load_favorites<T: AsRef<Path>>(
+	path: T
+) -> Result<BTreeSet<String>, io::Error>
+{
+	let path = path.as_ref();
+	if !path.exists()
+	{
+		return Ok(BTreeSet::new())
+	}
+	let content = fs::read_to_string(path)?;
+	let favorites = content.lines()
+		.map(str::trim)
+		.filter(|word| !word.is_empty())
+		.map(String::from)
+		.collect();
+	trace!("Read favorites file: {}", path.display());
+	Ok(favorites)
+}
+
+/// Write the given favorite words to the given file, one per line in sorted
+/// order, replacing it atomically. Missing parent directories are created.
+///
+/// # Arguments
+///
+/// * `path` - The favorites file.
+/// * `favorites` - The favorite words.
+///
+/// # Errors
+///
+/// If the file cannot be written, an error is returned.
+pub fn save_favorites<T: AsRef<Path>>(
+	path: T,
+	favorites: &BTreeSet<String>
+) -> Result<(), io::Error>
+{
+	let path = path.as_ref();
+	if let Some(parent) = path.parent()
+	{
+		fs::create_dir_all(parent)?;
+	}
+	let temp_path = path.with_extension("tmp");
+	let mut file = File::create(&temp_path)?;
+	for word in favorites
+	{
+		writeln!(file, "{}", word)?;
+	}
+	file.sync_all()?;
+	fs::rename(&temp_path, path)?;
+	trace!("Wrote favorites file: {}", path.display());
+	Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::collections::BTreeSet;
+
+	use quartiles_solver::dictionary::Dictionary;
+	use tempfile::TempDir;
+
+	use crate::curation::{
+		exclude,
+		exclusion_list,
+		load_favorites,
+		save_favorites
+	};
+
+	/// Ensure that excluded words accumulate in the exclusion list, which the
+	/// next load of the dictionary applies.
+	#[test]
+	fn test_exclude()
+	{
+		let dir = TempDir::new().unwrap();
+		let directory = dir.path().join("dict");
+		let path = exclusion_list(&directory, "tiny");
+		assert!(path.ends_with("dict/tiny.exclude.txt"));
+		exclude(&path, "cross").unwrap();
+		exclude(&path, "word").unwrap();
+		std::fs::write(directory.join("tiny.txt"), "cross\nword\nplay\n")
+			.unwrap();
+		let dictionary = Dictionary::open(&directory, "tiny").unwrap();
+		assert_eq!(dictionary.iter().collect::<Vec<_>>(), ["play"]);
+	}
+
+	/// Ensure that the favorites read back as saved, and that a missing
+	/// favorites file holds no favorites.
+	#[test]
+	fn test_favorites()
+	{
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("nested").join("favorites.txt");
+		assert!(load_favorites(&path).unwrap().is_empty());
+		let favorites = ["truthfully", "cross"]
+			.map(String::from)
+			.into_iter()
+			.collect::<BTreeSet<_>>();
+		save_favorites(&path, &favorites).unwrap();
+		assert_eq!(load_favorites(&path).unwrap(), favorites);
+		assert_eq!(
+			std::fs::read_to_string(&path).unwrap(),
+			"cross\ntruthfully\n"
+		);
+	}
+}
@@ -0,0 +1,264 @@
+//! # Daily puzzle
+//!
+//! Herein is support for the `daily` subcommand, which derives the seed of the
+//! [puzzle generator](quartiles_solver::generator::Generator) from the current
+//! date and the [fingerprint](Dictionary::fingerprint) of the dictionary, so
+//! that every user of the same dictionary gets the same board each day.
+
+use std::{
+	fmt::{self, Display, Formatter},
+	io::{self, ErrorKind},
+	rc::Rc,
+	str::FromStr,
+	time::{SystemTime, UNIX_EPOCH}
+};
+
+use quartiles_solver::{
+	dictionary::Dictionary,
+	generator::{Difficulty, Generator, GeneratorError, Puzzle}
+};
+use serde::{Deserialize, Serialize};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Dates.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A date in the proleptic Gregorian calendar. Dates are formatted and parsed
+/// as `YYYY-MM-DD`.
+#[derive(
+	Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash,
+	Serialize, Deserialize
+)]
+#[serde(into = "String", try_from = "String")]
+#[must_use]
+pub struct Date
+{
+	/// The year.
+	pub year: i64,
+
+	/// The month, in `1..=12`.
+	pub month: u32,
+
+	/// The day of the month, in `1..=31`.
+	pub day: u32
+}
+
+impl Date
+{
+	/// Answer the current date, in UTC. The date doesn't depend on the
+	/// time zone of the user, so that every user sees the same daily puzzle
+	/// at the same moment.
+	///
+	/// # Returns
+	///
+	/// The current date.
+	pub fn today() -> Self
+	{
+		let seconds = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|d| d.as_secs())
+			.unwrap_or_default();
+		Self::from_days((seconds / 86_400) as i64)
+	}
+
+	/// Convert the given number of days since the Unix epoch into a date,
+	/// using Howard Hinnant's
+	/// [`civil_from_days`](https://howardhinnant.github.io/date_algorithms.html)
+	/// algorithm.
+	///
+	/// # Arguments
+	///
+	/// * `days` - The number of days since 1970-01-01.
+	///
+	/// # Returns
+	///
+	/// The date.
+	pub fn from_days(days: i64) -> Self
+	{
+		let z = days + 719_468;
+		let era = z.div_euclid(146_097);
+		let doe = z.rem_euclid(146_097);
+		let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+		let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+		let mp = (5 * doy + 2) / 153;
+		let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+		let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+		let year = yoe + era * 400 + i64::from(month <= 2);
+		Self { year, month, day }
+	}
+
+	/// Convert the date into the number of days since the Unix epoch, using
+	/// Howard Hinnant's `days_from_civil` algorithm. This is the inverse of
+	/// [`from_days`](Self::from_days).
+	///
+	/// # Returns
+	///
+	/// The number of days since 1970-01-01.
+	#[must_use]
+	pub fn days(&self) -> i64
+	{
+		let year = self.year - i64::from(self.month <= 2);
+		let era = year.div_euclid(400);
+		let yoe = year.rem_euclid(400);
+		let month = i64::from(self.month);
+		let mp = if month > 2 { month - 3 } else { month + 9 };
+		let doy = (153 * mp + 2) / 5 + i64::from(self.day) - 1;
+		let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+		era * 146_097 + doe - 719_468
+	}
+}
+
+impl Display for Date
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+	}
+}
+
+impl FromStr for Date
+{
+	type Err = io::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err>
+	{
+		let invalid = || {
+			io::Error::new(
+				ErrorKind::InvalidData,
+				format!("Invalid date (expected YYYY-MM-DD): {}", s)
+			)
+		};
+		let mut parts = s.trim().splitn(3, '-');
+		let mut next = || parts.next().ok_or_else(invalid);
+		let year = next()?.parse::<i64>().map_err(|_| invalid())?;
+		let month = next()?.parse::<u32>().map_err(|_| invalid())?;
+		let day = next()?.parse::<u32>().map_err(|_| invalid())?;
+		let date = Self { year, month, day };
+		// Reject dates that don't exist, e.g., February 30, by checking that
+		// the date survives a round trip through the day count.
+		if !(1..=12).contains(&month) || Self::from_days(date.days()) != date
+		{
+			return Err(invalid())
+		}
+		Ok(date)
+	}
+}
+
+impl From<Date> for String
+{
+	fn from(date: Date) -> Self
+	{
+		date.to_string()
+	}
+}
+
+impl TryFrom<String> for Date
+{
+	type Error = io::Error;
+
+	fn try_from(s: String) -> Result<Self, Self::Error>
+	{
+		s.parse()
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                               Daily puzzle.                                //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The difficulty of every daily puzzle. The difficulty is fixed, since it
+/// participates in the generation of the board, and every user must get the
+/// same board.
+pub const DIFFICULTY: Difficulty = Difficulty::Medium;
+
+/// Derive the seed of the daily puzzle for the given date and dictionary.
+///
+/// # Arguments
+///
+/// * `dictionary` - The dictionary.
+/// * `date` - The date.
+///
+/// # Returns
+///
+/// The seed.
+#[must_use]
+pub fn seed(dictionary: &Dictionary, date: Date) -> u64
+{
+	// The generator scrambles the seed thoroughly, so it suffices to combine
+	// the inputs such that different days never collide.
+	dictionary.fingerprint() ^ (date.days() as u64).wrapping_mul(
+		0x9e37_79b9_7f4a_7c15
+	)
+}
+
+/// Generate the daily puzzle for the given date.
+///
+/// # Arguments
+///
+/// * `dictionary` - The dictionary.
+/// * `date` - The date.
+///
+/// # Returns
+///
+/// The daily puzzle.
+///
+/// # Errors
+///
+/// If the generator fails, its error is returned.
+pub fn puzzle(
+	dictionary: Rc<Dictionary>,
+	date: Date
+) -> Result<Puzzle, GeneratorError>
+{
+	let seed = seed(&dictionary, date);
+	Generator::new(dictionary).generate(seed, DIFFICULTY)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use quartiles_solver::dictionary::Dictionary;
+
+	use crate::daily::{seed, Date};
+
+	/// Ensure that dates convert to and from day counts and strings.
+	#[test]
+	fn test_date()
+	{
+		let epoch = Date { year: 1970, month: 1, day: 1 };
+		assert_eq!(Date::from_days(0), epoch);
+		assert_eq!(epoch.days(), 0);
+		let leap = Date { year: 2024, month: 2, day: 29 };
+		assert_eq!(leap.days(), 19_782);
+		assert_eq!(Date::from_days(19_782), leap);
+		assert_eq!(Date::from_days(-1).to_string(), "1969-12-31");
+		for days in -1000..1000
+		{
+			assert_eq!(Date::from_days(days).days(), days);
+		}
+		assert_eq!("2024-02-29".parse::<Date>().unwrap(), leap);
+		assert!("2023-02-29".parse::<Date>().is_err());
+		assert!("2024-13-01".parse::<Date>().is_err());
+		assert!("yesterday".parse::<Date>().is_err());
+	}
+
+	/// Ensure that the daily seed depends on exactly the date and the words of
+	/// the dictionary.
+	#[test]
+	fn test_seed()
+	{
+		let dictionary =
+			["truth", "trust", "is"].into_iter().collect::<Dictionary>();
+		let same = ["is", "trust", "truth"].into_iter().collect::<Dictionary>();
+		let other = ["truth", "trust"].into_iter().collect::<Dictionary>();
+		let today = Date { year: 2024, month: 6, day: 1 };
+		let tomorrow = Date::from_days(today.days() + 1);
+		assert_eq!(seed(&dictionary, today), seed(&same, today));
+		assert_ne!(seed(&dictionary, today), seed(&dictionary, tomorrow));
+		assert_ne!(seed(&dictionary, today), seed(&other, today));
+	}
+}
@@ -2,26 +2,90 @@
 //!
 //! Herein is support for dictionary construction and manipulation. All runtime
 //! operations are performed against a [`Dictionary`], which is a prefix tree
-//! of words.
+//! of words, indexed in sorted order for enumeration.
 
 use std::{
-	fs::File,
+	borrow::Cow,
+	collections::{HashMap, HashSet},
+	fs::{self, File},
+	fmt::{self, Display, Formatter},
 	io::{self, BufRead, BufReader, ErrorKind, Read, Write},
-	path::Path
+	ops::Range,
+	path::{Path, PathBuf},
+	str::FromStr,
+	sync::{
+		atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+		OnceLock
+	}
 };
 
-use log::{trace, warn};
+use bincode::Options;
+use clap::{ArgAction, Args, ValueEnum};
 use pfx::PrefixTreeSet;
 use serde::{Deserialize, Serialize};
+use tracing::{info, info_span, trace, warn};
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+use crate::solver::MAX_FRAGMENT_BYTES;
 
 ////////////////////////////////////////////////////////////////////////////////
 //                                Definitions.                                //
 ////////////////////////////////////////////////////////////////////////////////
 
-/// A dictionary is a [`PrefixTreeSet`] of words.
-#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+/// The minimum length of a word that Quartiles can form, in characters. A
+/// fragment has at least 2 letters.
+pub const MIN_WORD_LEN: usize = 2;
+
+/// The maximum length of a word that Quartiles can form, in characters. A
+/// word has at most 4 fragments, and a fragment has at most
+/// [`MAX_FRAGMENT_BYTES`] bytes, and thus at most as many characters.
+pub const MAX_WORD_LEN: usize = 4 * MAX_FRAGMENT_BYTES;
+
+/// The [embedded](Dictionary::embedded) English word list, i.e.,
+/// `dict/english.txt` compressed with gzip.
+#[cfg(feature = "embedded-dict")]
+const EMBEDDED_WORDS: &[u8] = include_bytes!("../assets/english.txt.gz");
+
+/// A dictionary is a [`PrefixTreeSet`] of words. [`PrefixTreeSet`] answers
+/// membership, but cannot enumerate the words beneath a prefix, so the
+/// dictionary also keeps an index of its words in sorted order, wherein the
+/// words that share a prefix are contiguous. A second index holds the words
+/// reversed, wherein the words that share a suffix are contiguous, for
+/// [suffix lookups](Self::contains_suffix), and a [`LetterFilter`] rules out
+/// prefixes by their letters alone. The indices are built on demand,
+/// discarded whenever the words change, and never serialized. Nor is whether
+/// the dictionary is [folding](Self::set_folding) its words and lookups, nor
+/// the [source](Self::source) of each word that an
+/// [inclusion list](Self::include_from_file) supplied.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 #[must_use]
-pub struct Dictionary(PrefixTreeSet<String>);
+pub struct Dictionary
+{
+	/// The words.
+	words: PrefixTreeSet<String>,
+
+	/// The words in sorted order, if the index has been built.
+	#[serde(skip)]
+	sorted: OnceLock<Box<[Box<str>]>>,
+
+	/// Whether the dictionary is [folding](Self::set_folding) its words and
+	/// lookups.
+	#[serde(skip)]
+	is_folding: bool,
+
+	/// The words reversed, in sorted order, if the index has been built.
+	#[serde(skip)]
+	reversed: OnceLock<Box<[Box<str>]>>,
+
+	/// The [letter filter](LetterFilter), if it has been built.
+	#[serde(skip)]
+	letter_filter: OnceLock<LetterFilter>,
+
+	/// The [source](Self::source) of each word that an
+	/// [inclusion list](Self::include_from_file) supplied.
+	#[serde(skip)]
+	sources: HashMap<Box<str>, Source>
+}
 
 impl Dictionary
 {
@@ -33,7 +97,68 @@ impl Dictionary
 	#[inline]
 	pub fn new() -> Self
 	{
-		Self(Default::default())
+		Self {
+			words: Default::default(),
+			sorted: OnceLock::new(),
+			is_folding: false,
+			reversed: OnceLock::new(),
+			letter_filter: OnceLock::new(),
+			sources: HashMap::new()
+		}
+	}
+
+	/// Check if the dictionary is [folding](Self::set_folding) its words and
+	/// lookups.
+	///
+	/// # Returns
+	///
+	/// `true` if the dictionary is folding, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_folding(&self) -> bool
+	{
+		self.is_folding
+	}
+
+	/// Enable or disable folding. A folding dictionary
+	/// [normalizes](normalize_word) every word that it stores and every word
+	/// or prefix that it looks up, so lookups ignore case and stray
+	/// whitespace, e.g., `contains(" Hello")` answers `true` for a word list
+	/// that contains `hello`, and vice versa. Enabling folding folds the words
+	/// already stored. Folding costs an allocation per lookup, but a
+	/// [cursor](Self::cursor) never folds, so the [solver](crate::solver)
+	/// folds the fragments of its puzzle once instead.
+	///
+	/// # Arguments
+	///
+	/// * `folding` - Whether to fold.
+	pub fn set_folding(&mut self, folding: bool)
+	{
+		self.is_folding = folding;
+		if folding && self.words.iter().any(|word| normalize_word(word) != *word)
+		{
+			let words = std::mem::take(&mut self.words);
+			self.extend(words.iter());
+		}
+	}
+
+	/// Fold the given word or prefix for lookup, if the dictionary is
+	/// [folding](Self::set_folding).
+	///
+	/// # Arguments
+	///
+	/// * `word` - The word or prefix.
+	///
+	/// # Returns
+	///
+	/// The folded word or prefix.
+	fn fold<'a>(&self, word: &'a str) -> Cow<'a, str>
+	{
+		match self.is_folding
+		{
+			true => Cow::Owned(normalize_word(word)),
+			false => Cow::Borrowed(word)
+		}
 	}
 
 	/// Check if the dictionary is empty.
@@ -45,7 +170,19 @@ impl Dictionary
 	#[must_use]
 	pub fn is_empty(&self) -> bool
 	{
-		self.0.is_empty()
+		self.words.is_empty()
+	}
+
+	/// Get the number of words in the dictionary.
+	///
+	/// # Returns
+	///
+	/// The number of words in the dictionary.
+	#[inline]
+	#[must_use]
+	pub fn len(&self) -> usize
+	{
+		self.words.len()
 	}
 
 	/// Check if the dictionary contains the given word.
@@ -61,7 +198,7 @@ impl Dictionary
 	#[must_use]
 	pub fn contains(&self, word: &str) -> bool
 	{
-		self.0.contains(word)
+		self.words.contains(&*self.fold(word))
 	}
 
 	/// Check if the dictionary contains a word with the given prefix.
@@ -78,27 +215,477 @@ impl Dictionary
 	#[must_use]
 	pub fn contains_prefix(&self, prefix: &str) -> bool
 	{
-		self.0.contains_prefix(prefix)
+		self.words.contains_prefix(&*self.fold(prefix))
+	}
+
+	/// Check if the dictionary contains a word with the given suffix, e.g., so
+	/// that the solver can rule out a fragment that ends no word. The first
+	/// check builds the reversed index, which costs about as much memory as
+	/// the sorted index.
+	///
+	/// # Arguments
+	///
+	/// * `suffix` - The suffix to check.
+	///
+	/// # Returns
+	///
+	/// `true` if the dictionary contains a word with the given suffix, `false`
+	/// otherwise.
+	#[must_use]
+	pub fn contains_suffix(&self, suffix: &str) -> bool
+	{
+		let reversed = self.fold(suffix).chars().rev().collect::<String>();
+		let words = self.reversed();
+		!narrow(words, 0..words.len(), &reversed).is_empty()
+	}
+
+	/// Iterate over every word in the dictionary. The order of iteration is
+	/// deterministic, but otherwise unspecified.
+	///
+	/// # Returns
+	///
+	/// An iterator over the words.
+	pub fn iter(&self) -> impl Iterator<Item = &str>
+	{
+		self.words.iter().map(|word| word.as_str())
+	}
+
+	/// Iterate, in sorted order, over the words of the dictionary that are
+	/// absent from the other dictionary. The other dictionary
+	/// [folds](Self::set_folding) the words if it folds its own.
+	///
+	/// # Arguments
+	///
+	/// * `other` - The other dictionary.
+	///
+	/// # Returns
+	///
+	/// An iterator over the words absent from the other dictionary.
+	pub fn difference<'a>(
+		&'a self,
+		other: &'a Self
+	) -> impl Iterator<Item = &'a str> + 'a
+	{
+		self.sorted()
+			.iter()
+			.map(|word| &**word)
+			.filter(|word| !other.contains(word))
+	}
+
+	/// Answer the words of the dictionary in sorted order, building the index
+	/// first if necessary.
+	///
+	/// # Returns
+	///
+	/// The sorted words.
+	fn sorted(&self) -> &[Box<str>]
+	{
+		self.sorted.get_or_init(|| {
+			let mut words = self.iter().map(Box::from).collect::<Vec<_>>();
+			words.sort_unstable();
+			words.into_boxed_slice()
+		})
+	}
+
+	/// Answer the words of the dictionary reversed, in sorted order, building
+	/// the index first if necessary.
+	///
+	/// # Returns
+	///
+	/// The sorted reversed words.
+	fn reversed(&self) -> &[Box<str>]
+	{
+		self.reversed.get_or_init(|| {
+			let mut words = self.iter()
+				.map(|word| word.chars().rev().collect::<Box<str>>())
+				.collect::<Vec<_>>();
+			words.sort_unstable();
+			words.into_boxed_slice()
+		})
+	}
+
+	/// Answer the [letter filter](LetterFilter) of the dictionary, building it
+	/// first if necessary.
+	///
+	/// # Returns
+	///
+	/// The letter filter.
+	pub fn letter_filter(&self) -> &LetterFilter
+	{
+		self.letter_filter.get_or_init(|| {
+			LetterFilter::new(self.sorted().iter().map(|word| &**word))
+		})
+	}
+
+	/// Discard the indices, because the words changed.
+	fn invalidate(&mut self)
+	{
+		self.sorted.take();
+		self.reversed.take();
+		self.letter_filter.take();
+	}
+
+	/// Compute a fingerprint of the words in the dictionary, using the 64-bit
+	/// [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/) hash. Dictionaries
+	/// with the same words have the same fingerprint, no matter how they were
+	/// loaded, so the fingerprint is stable across machines.
+	///
+	/// # Returns
+	///
+	/// The fingerprint.
+	#[must_use]
+	pub fn fingerprint(&self) -> u64
+	{
+		const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+		const PRIME: u64 = 0x0100_0000_01b3;
+		self.sorted()
+			.iter()
+			.flat_map(|word| word.bytes().chain([b'\n']))
+			.fold(OFFSET_BASIS, |hash, byte| {
+				(hash ^ u64::from(byte)).wrapping_mul(PRIME)
+			})
+	}
+
+	/// Iterate over every word in the dictionary that starts with the given
+	/// prefix, in sorted order. Only the matching words are visited, so the
+	/// cost is proportional to their number, not to the size of the
+	/// dictionary.
+	///
+	/// # Arguments
+	///
+	/// * `prefix` - The prefix.
+	///
+	/// # Returns
+	///
+	/// An iterator over the matching words.
+	pub fn iter_prefix<'a>(
+		&'a self,
+		prefix: &'a str
+	) -> impl Iterator<Item = &'a str> + 'a
+	{
+		let words = self.sorted();
+		words[narrow(words, 0..words.len(), &self.fold(prefix))]
+			.iter()
+			.map(|word| &**word)
+	}
+
+	/// Open a [cursor](DictionaryCursor) at the root of the dictionary, i.e.,
+	/// at the empty prefix. Unlike the other lookups, the cursor never
+	/// [folds](Self::set_folding) what it descends by, lest every step
+	/// allocate, so fragments must already be [normalized](normalize_word).
+	///
+	/// # Returns
+	///
+	/// The cursor.
+	#[inline]
+	pub fn cursor(&self) -> DictionaryCursor<'_>
+	{
+		let words = self.sorted();
+		DictionaryCursor {
+			words,
+			filter: None,
+			prefix: String::new(),
+			chars: 0,
+			signature: 0,
+			probes: 0,
+			range: 0..words.len(),
+			levels: Vec::new()
+		}
+	}
+
+	/// Iterate over every word in the dictionary that matches the given
+	/// [pattern](Pattern), in sorted order. Only words that start with the
+	/// literal prefix of the pattern are visited.
+	///
+	/// # Arguments
+	///
+	/// * `pattern` - The pattern.
+	///
+	/// # Returns
+	///
+	/// An iterator over the matching words.
+	pub fn iter_matching<'a>(
+		&'a self,
+		pattern: &'a Pattern
+	) -> impl Iterator<Item = &'a str> + 'a
+	{
+		self.iter_prefix(&pattern.prefix)
+			.filter(move |word| pattern.matches(word))
 	}
 
-	/// Populate the dictionary with the given words.
+	/// Find the words of the dictionary within the given
+	/// [edit distance](https://en.wikipedia.org/wiki/Levenshtein_distance) of
+	/// the given word, e.g., to suggest the word that a misspelling was meant
+	/// to be. The search walks the prefix tree implicit in the sorted words,
+	/// computing one row of the edit distance table per character, so that
+	/// words that share a prefix share its rows. A prefix whose row exceeds
+	/// the maximum distance everywhere begins no match, so the search skips
+	/// every word that starts with it.
+	///
+	/// # Arguments
+	///
+	/// * `word` - The word.
+	/// * `max_dist` - The maximum edit distance, in characters.
+	///
+	/// # Returns
+	///
+	/// The matching words and their distances from the word, nearest first.
+	/// Ties are broken alphabetically. The word itself, if present, matches
+	/// at distance `0`.
+	#[must_use]
+	pub fn search_levenshtein(
+		&self,
+		word: &str,
+		max_dist: usize
+	) -> Vec<(&str, usize)>
+	{
+		let target = self.fold(word).chars().collect::<Vec<_>>();
+		let words = self.sorted();
+		// The row of the edit distance table for each prefix of the current
+		// candidate, starting with the empty prefix.
+		let mut rows = vec![(0..=target.len()).collect::<Vec<_>>()];
+		let mut prefix = Vec::<char>::new();
+		let mut matches = Vec::new();
+		let mut index = 0;
+		while index < words.len()
+		{
+			let candidate = &*words[index];
+			let chars = candidate.chars().collect::<Vec<_>>();
+			// Keep the rows of the prefix shared with the previous candidate.
+			let shared = prefix.iter()
+				.zip(&chars)
+				.take_while(|(a, b)| a == b)
+				.count();
+			prefix.truncate(shared);
+			rows.truncate(shared + 1);
+			let mut is_dead = false;
+			for &c in &chars[shared..]
+			{
+				let previous = &rows[rows.len() - 1];
+				let mut row = Vec::with_capacity(target.len() + 1);
+				row.push(previous[0] + 1);
+				for (j, &t) in target.iter().enumerate()
+				{
+					let cost = usize::from(t != c);
+					let distance = (previous[j + 1] + 1)
+						.min(row[j] + 1)
+						.min(previous[j] + cost);
+					row.push(distance);
+				}
+				prefix.push(c);
+				is_dead = row.iter().all(|&distance| distance > max_dist);
+				rows.push(row);
+				if is_dead
+				{
+					break
+				}
+			}
+			if is_dead
+			{
+				// Skip every word that starts with the dead prefix.
+				let dead = prefix.iter().collect::<String>();
+				index += words[index..]
+					.partition_point(|word| word.starts_with(&dead));
+			}
+			else
+			{
+				let distance = rows[rows.len() - 1][target.len()];
+				if distance <= max_dist
+				{
+					matches.push((candidate, distance));
+				}
+				index += 1;
+			}
+		}
+		// The words are already sorted, so a stable sort breaks the ties.
+		matches.sort_by_key(|&(_, distance)| distance);
+		matches
+	}
+
+	/// Check if the dictionary contains a word in which the given fragment
+	/// occurs, whether at the start, in the middle, or at the end. Every
+	/// fragment of a solvable puzzle must pass this check, so a fragment that
+	/// fails it is surely a typo. The empty fragment occurs in every word.
+	///
+	/// # Arguments
+	///
+	/// * `fragment` - The fragment to check.
+	///
+	/// # Returns
+	///
+	/// `true` if some word contains the fragment, `false` otherwise.
+	#[must_use]
+	pub fn contains_fragment(&self, fragment: &str) -> bool
+	{
+		// Walking the tree is cheap, so try that before scanning every word.
+		let fragment = self.fold(fragment);
+		self.contains_prefix(&fragment)
+			|| self.iter().any(|word| word.contains(&*fragment))
+	}
+
+	/// Suggest fragments that extend the given prefix, ranked by the number of
+	/// words in which they occur, most common first. Ties are broken
+	/// alphabetically. The prefix itself is never suggested.
+	///
+	/// # Arguments
+	///
+	/// * `prefix` - The prefix that every suggestion must extend.
+	/// * `max_len` - The maximum length of a suggestion, in characters.
+	/// * `limit` - The maximum number of suggestions.
+	///
+	/// # Returns
+	///
+	/// The suggestions.
+	#[must_use]
+	pub fn suggest_fragments(
+		&self,
+		prefix: &str,
+		max_len: usize,
+		limit: usize
+	) -> Vec<String>
+	{
+		let mut counts = HashMap::<&str, usize>::new();
+		let mut seen = HashSet::new();
+		for word in self.iter()
+		{
+			seen.clear();
+			for (start, _) in word.match_indices(prefix)
+			{
+				// Count every extension of the prefix at this position, up to
+				// the maximum length, but count each word only once.
+				let rest = &word[start..];
+				rest.char_indices()
+					.map(|(offset, c)| offset + c.len_utf8())
+					.take(max_len)
+					.filter(|&end| end > prefix.len())
+					.for_each(|end| {
+						seen.insert(&rest[..end]);
+					});
+			}
+			for &fragment in &seen
+			{
+				*counts.entry(fragment).or_default() += 1;
+			}
+		}
+		let mut suggestions = counts.into_iter().collect::<Vec<_>>();
+		suggestions.sort_unstable_by(|(a, m), (b, n)| n.cmp(m).then(a.cmp(b)));
+		suggestions
+			.into_iter()
+			.take(limit)
+			.map(|(fragment, _)| fragment.to_string())
+			.collect()
+	}
+
+	/// Answer the subset of the dictionary whose words have the specified
+	/// lengths, e.g., from [`MIN_WORD_LEN`] to [`MAX_WORD_LEN`], the words that
+	/// Quartiles can form. A full word list often contains single letters and
+	/// long chemical names, whose prefixes the solver would otherwise walk in
+	/// vain. The subset [folds](Self::set_folding) if the dictionary does.
+	///
+	/// # Arguments
+	///
+	/// * `min_len` - The minimum length of a word, in characters.
+	/// * `max_len` - The maximum length of a word, in characters.
+	///
+	/// # Returns
+	///
+	/// The subset.
+	pub fn subset(&self, min_len: usize, max_len: usize) -> Self
+	{
+		let mut subset = self.iter()
+			.filter(|w| (min_len..=max_len).contains(&w.chars().count()))
+			.collect::<Self>();
+		subset.is_folding = self.is_folding;
+		subset.sources = self.sources.iter()
+			.filter(|(word, _)| subset.contains(word))
+			.map(|(word, source)| (word.clone(), *source))
+			.collect();
+		trace!(
+			"Subset of {} to {} characters: {} of {} words",
+			min_len,
+			max_len,
+			subset.len(),
+			self.len()
+		);
+		subset
+	}
+
+	/// Populate the dictionary with the given words, each
+	/// [normalized](normalize_word) first. Blank words are ignored.
 	///
 	/// # Arguments
 	///
 	/// * `words` - The intended content of the dictionary.
-	pub fn populate<T: AsRef<str>>(&mut self, words: &[T])
+	///
+	/// # Returns
+	///
+	/// The tally of the words inserted, duplicated, and rejected.
+	pub fn populate<T: AsRef<str>>(&mut self, words: &[T]) -> Ingestion
 	{
-		for word in words
+		self.populate_with(words, &Normalization::default())
+	}
+
+	/// Populate the dictionary with the given words, each normalized
+	/// according to the given [rules](Normalization) first. Blank words are
+	/// ignored. A word that the rules drop is rejected, and a word already in
+	/// the dictionary is a duplicate; each is logged at the `info` level.
+	///
+	/// # Arguments
+	///
+	/// * `words` - The intended content of the dictionary.
+	/// * `normalization` - The rules of normalization.
+	///
+	/// # Returns
+	///
+	/// The tally of the words inserted, duplicated, and rejected.
+	pub fn populate_with<T: AsRef<str>>(
+		&mut self,
+		words: &[T],
+		normalization: &Normalization
+	) -> Ingestion
+	{
+		let mut ingestion = Ingestion::default();
+		for word in words.iter().map(AsRef::as_ref)
+		{
+			if word.trim().is_empty()
+			{
+				continue
+			}
+			match normalization.apply(word)
+			{
+				None =>
+				{
+					info!("Rejected word: {:?}", word);
+					ingestion.rejected += 1;
+				},
+				Some(normalized) if self.contains(&normalized) =>
+				{
+					info!("Duplicate word: {:?}", word);
+					ingestion.duplicates += 1;
+				},
+				Some(normalized) =>
+				{
+					self.words.insert(self.fold(&normalized).into_owned());
+					ingestion.inserted += 1;
+				}
+			}
+		}
+		if ingestion.inserted > 0
 		{
-			self.0.insert(word.as_ref().to_string());
+			self.invalidate();
 		}
+		ingestion
 	}
 
 	/// Open a dictionary with the given name. Only the specified directory will
 	/// be searched. `name` denotes the dictionary file, sans the extension. If
 	/// a binary dictionary (`<name>.dict`) exists, it will be read; otherwise,
 	/// a text file (`<name>.txt`) will be read and a binary dictionary will be
-	/// created (to optimize future reads).
+	/// created (to optimize future reads). If an exclusion list
+	/// (`<name>.exclude.txt`) exists, then its words are
+	/// [excluded](Self::exclude_from_file) from the dictionary; the binary
+	/// dictionary never reflects the exclusion list, so editing the exclusion
+	/// list takes effect at the next load.
 	///
 	/// # Arguments
 	///
@@ -116,17 +703,64 @@ impl Dictionary
 	///   returned.
 	pub fn open<T: AsRef<Path>>(dir: T, name: &str) -> Result<Self, io::Error>
 	{
+		Self::open_with_progress(dir, name, &Progress::default())
+	}
+
+	/// Open a dictionary with the given name, exactly as [`open`](Self::open)
+	/// does, but report [progress](Progress) as the dictionary file is read,
+	/// and stop early if the progress is [cancelled](Progress::cancel). This
+	/// supports opening a large dictionary on a worker thread while another
+	/// thread observes.
+	///
+	/// A binary dictionary is deserialized all at once, so its progress jumps
+	/// from nothing to everything, and cancellation takes effect only before
+	/// deserialization starts. A text dictionary reports progress line by
+	/// line, and word by word as the prefix tree is populated.
+	///
+	/// # Arguments
+	///
+	/// * `dir` - The directory to search.
+	/// * `name` - The name of the dictionary file.
+	/// * `progress` - The progress, which is updated as the dictionary is
+	///   read.
+	///
+	/// # Returns
+	///
+	/// A dictionary containing the words from the file.
+	///
+	/// # Errors
+	///
+	/// * If the file cannot be opened or read, an error is returned.
+	/// * If the file contains invalid data, an [`ErrKind::InvalidData`] is
+	///   returned.
+	/// * If the progress is cancelled, an [`ErrorKind::Interrupted`] is
+	///   returned.
+	pub fn open_with_progress<T: AsRef<Path>>(
+		dir: T,
+		name: &str,
+		progress: &Progress
+	) -> Result<Self, io::Error>
+	{
+		let _span = info_span!("dictionary_load", name).entered();
 		let dict_path = dir.as_ref().join(format!("{}.dict", name));
-		if dict_path.exists()
+		let mut dictionary = if dict_path.exists()
 		{
-			let dictionary = Self::deserialize_from_file(&dict_path);
+			let total_bytes = fs::metadata(&dict_path)?.len();
+			progress.total_bytes.store(total_bytes, Ordering::Relaxed);
+			progress.check()?;
+			let dictionary = Self::deserialize_from_file(&dict_path)?;
+			progress.bytes_read.store(total_bytes, Ordering::Relaxed);
+			progress.words_inserted.store(dictionary.len(), Ordering::Relaxed);
 			trace!("Read binary dictionary: {}", dict_path.display());
 			dictionary
 		}
 		else
 		{
 			let txt_path = dir.as_ref().join(format!("{}.txt", name));
-			let dictionary = Self::read_from_file(&txt_path)?;
+			let dictionary = Self::read_from_file_with_progress(
+				&txt_path,
+				progress
+			)?;
 			trace!("Read text dictionary: {}", txt_path.display());
 			match dictionary.serialize_to_file(&dict_path)
 			{
@@ -140,85 +774,2562 @@ impl Dictionary
 					e
 				)
 			}
-			Ok(dictionary)
+			dictionary
+		};
+		let exclude_path = dir.as_ref().join(format!("{}.exclude.txt", name));
+		if exclude_path.exists()
+		{
+			dictionary.exclude_from_file(&exclude_path)?;
 		}
+		Ok(dictionary)
+	}
+
+	/// Construct a dictionary from the English word list built into the
+	/// binary, which stands in for a dictionary whose files are missing. The
+	/// list is decompressed and [populated](Self::populate) anew at each call,
+	/// so the caller should keep the result.
+	///
+	/// # Returns
+	///
+	/// A dictionary containing the words of the embedded list.
+	#[cfg(feature = "embedded-dict")]
+	pub fn embedded() -> Self
+	{
+		let _span = info_span!("dictionary_load", name = "embedded").entered();
+		let mut content = String::new();
+		flate2::read::GzDecoder::new(EMBEDDED_WORDS)
+			.read_to_string(&mut content)
+			.expect("Embedded word list is valid gzip");
+		let mut dictionary = Self::new();
+		dictionary.populate(&content.lines().collect::<Vec<_>>());
+		trace!("Read embedded dictionary: {} words", dictionary.len());
+		dictionary
 	}
 
-	/// Construct a dictionary from the contents of the given file. Each line
-	/// in the file is considered a single word.
+	/// List the names of the dictionaries in the specified directory, i.e., the
+	/// distinct names of its text (`<name>.txt`) and binary (`<name>.dict`)
+	/// dictionary files, sans the extensions. Exclusion lists
+	/// (`<name>.exclude.txt`) are not dictionaries, so they are omitted, as are
+	/// subdirectories.
 	///
 	/// # Arguments
 	///
-	/// * `path` - The target file.
+	/// * `dir` - The directory to search.
 	///
 	/// # Returns
 	///
-	/// A dictionary containing the words from the file.
+	/// The names of the dictionaries, sorted and deduplicated. Each name can
+	/// be [opened](Self::open).
 	///
 	/// # Errors
 	///
-	/// If the file cannot be opened or read, an error is returned.
-	pub fn read_from_file<T: AsRef<Path>>(path: T) -> Result<Self, io::Error>
+	/// If the directory cannot be read, an error is returned.
+	pub fn available<T: AsRef<Path>>(dir: T) -> Result<Vec<String>, io::Error>
 	{
-		let file = File::open(path)?;
-		let reader = BufReader::new(file);
-		let words = reader.lines().map(|line| line.unwrap()).collect::<Vec<_>>();
-		let mut dictionary = Self::new();
-		dictionary.populate(&words);
-		Ok(dictionary)
+		let mut names = Vec::new();
+		for entry in fs::read_dir(dir)?
+		{
+			let path = entry?.path();
+			let is_dictionary = path.is_file()
+				&& path.extension().is_some_and(|e| e == "txt" || e == "dict");
+			if let (true, Some(name)) =
+				(is_dictionary, path.file_stem().and_then(|s| s.to_str()))
+			{
+				if !name.ends_with(".exclude")
+				{
+					names.push(name.to_string());
+				}
+			}
+		}
+		names.sort();
+		names.dedup();
+		Ok(names)
 	}
 
-	/// Deserialize a dictionary from the given file. The file must contain a
-	/// serialized dictionary in [`bincode`](bincode) format.
+	/// Remove the given words from the dictionary. Each word is
+	/// [normalized](normalize_word) first, and words absent from the
+	/// dictionary are ignored.
 	///
 	/// # Arguments
 	///
-	/// * `path` - The target file.
+	/// * `words` - The words to remove.
 	///
 	/// # Returns
 	///
-	/// A dictionary deserialized from the file.
+	/// The number of words actually removed.
+	pub fn exclude<I, T>(&mut self, words: I) -> usize
+	where
+		I: IntoIterator<Item = T>,
+		T: AsRef<str>
+	{
+		let removed = words
+			.into_iter()
+			.filter(|word| {
+				let word = normalize_word(word.as_ref());
+				self.sources.remove(&*word);
+				self.words.remove(&word)
+			})
+			.count();
+		if removed > 0
+		{
+			self.invalidate();
+		}
+		removed
+	}
+
+	/// Remove the words listed in the given file from the dictionary. The file
+	/// has the same format as a text dictionary: each line is a single word,
+	/// and blank lines are ignored. This supports exclusion lists of words
+	/// that the official game rejects, e.g., profanity.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The exclusion list.
+	///
+	/// # Returns
+	///
+	/// The number of words actually removed.
+	///
+	/// # Errors
+	///
+	/// If the file cannot be opened or read, an error is returned.
+	pub fn exclude_from_file<T: AsRef<Path>>(
+		&mut self,
+		path: T
+	) -> Result<usize, io::Error>
+	{
+		let file = File::open(path.as_ref())?;
+		let reader = BufReader::new(file);
+		let mut excluded = 0;
+		for line in reader.lines()
+		{
+			excluded += self.exclude([line?]);
+		}
+		trace!(
+			"Excluded {} words: {}",
+			excluded,
+			path.as_ref().display()
+		);
+		Ok(excluded)
+	}
+
+	/// Add the given words to the dictionary, recording that they came from
+	/// the given [source](Source). Each word is [normalized](normalize_word)
+	/// first, and words already in the dictionary keep their source, so the
+	/// base word list, and then each earlier inclusion, takes precedence.
+	///
+	/// # Arguments
+	///
+	/// * `words` - The words to add.
+	/// * `source` - The source of the words.
+	///
+	/// # Returns
+	///
+	/// The number of words actually added.
+	pub fn include<I, T>(&mut self, words: I, source: Source) -> usize
+	where
+		I: IntoIterator<Item = T>,
+		T: AsRef<str>
+	{
+		let mut included = 0;
+		for word in words
+		{
+			let word = normalize_word(word.as_ref());
+			if word.is_empty() || self.words.contains(&word)
+			{
+				continue
+			}
+			if source != Source::Base
+			{
+				self.sources.insert(Box::from(word.as_str()), source);
+			}
+			self.words.insert(word);
+			included += 1;
+		}
+		if included > 0
+		{
+			self.invalidate();
+		}
+		included
+	}
+
+	/// Add the words listed in the given file to the dictionary, recording
+	/// that they came from the given [source](Source), as for
+	/// [`include`](Self::include). The file has the same format as a text
+	/// dictionary: each line is a single word, and blank lines are ignored.
+	/// This supports composing a dictionary from a base word list and, e.g.,
+	/// a list of words that the official game accepts but the base lacks.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The inclusion list.
+	/// * `source` - The source of the words.
+	///
+	/// # Returns
+	///
+	/// The number of words actually added.
+	///
+	/// # Errors
+	///
+	/// If the file cannot be opened or read, an error is returned.
+	pub fn include_from_file<T: AsRef<Path>>(
+		&mut self,
+		path: T,
+		source: Source
+	) -> Result<usize, io::Error>
+	{
+		let file = File::open(path.as_ref())?;
+		let reader = BufReader::new(file);
+		let words = reader.lines().collect::<Result<Vec<_>, _>>()?;
+		let included = self.include(words, source);
+		trace!(
+			"Included {} {} words: {}",
+			included,
+			source,
+			path.as_ref().display()
+		);
+		Ok(included)
+	}
+
+	/// Answer the [source](Source) that supplied the given word. Every word
+	/// that no [inclusion](Self::include) supplied, including a word absent
+	/// from the dictionary, belongs to the [base](Source::Base).
+	///
+	/// # Arguments
+	///
+	/// * `word` - The word.
+	///
+	/// # Returns
+	///
+	/// The source of the word.
+	#[must_use]
+	pub fn source(&self, word: &str) -> Source
+	{
+		self.sources.get(&*self.fold(word)).copied().unwrap_or_default()
+	}
+
+	/// Remove every word that none of the given [sources](Source) supplied,
+	/// e.g., to solve with the custom words alone.
+	///
+	/// # Arguments
+	///
+	/// * `sources` - The sources whose words to keep.
+	///
+	/// # Returns
+	///
+	/// The number of words actually removed.
+	pub fn retain_sources(&mut self, sources: &[Source]) -> usize
+	{
+		let doomed = self.iter()
+			.filter(|word| !sources.contains(&self.source(word)))
+			.map(String::from)
+			.collect::<Vec<_>>();
+		for word in &doomed
+		{
+			self.sources.remove(word.as_str());
+			self.words.remove(word);
+		}
+		if !doomed.is_empty()
+		{
+			self.invalidate();
+		}
+		doomed.len()
+	}
+
+	/// Download a word list from the given URL and install it as the
+	/// dictionary with the given name. The word list must be plain text, with
+	/// one word per line; it is [normalized](normalize_words) according to the
+	/// specified language before it is installed. Both the text dictionary
+	/// (`<name>.txt`) and the binary dictionary (`<name>.dict`) are written to
+	/// the specified directory, replacing any existing files. Requires the
+	/// `net` feature.
+	///
+	/// # Arguments
+	///
+	/// * `url` - The URL of the word list.
+	/// * `dir` - The target directory.
+	/// * `name` - The name of the dictionary file.
+	/// * `language` - The language of the word list.
+	///
+	/// # Returns
+	///
+	/// A dictionary containing the normalized words.
+	///
+	/// # Errors
+	///
+	/// * If the word list cannot be downloaded, an error is returned.
+	/// * If either file cannot be written, an error is returned.
+	#[cfg(feature = "net")]
+	pub fn fetch<T: AsRef<Path>>(
+		url: &str,
+		dir: T,
+		name: &str,
+		language: Language
+	) -> Result<Self, io::Error>
+	{
+		let content = ureq::get(url)
+			.call()
+			.map_err(io::Error::other)?
+			.into_string()?;
+		trace!("Downloaded word list: {}", url);
+		let dictionary = normalize_words(content.lines(), language)
+			.into_iter()
+			.collect::<Self>();
+		dictionary.install(dir, name)?;
+		Ok(dictionary)
+	}
+
+	/// Install the dictionary under the given name. Both the text dictionary
+	/// (`<name>.txt`), whose words are sorted, and the binary dictionary
+	/// (`<name>.dict`) are written to the specified directory, which is
+	/// created if necessary, replacing any existing files.
+	///
+	/// # Arguments
+	///
+	/// * `dir` - The target directory.
+	/// * `name` - The name of the dictionary file.
+	///
+	/// # Errors
+	///
+	/// If the directory cannot be created, or either file cannot be written,
+	/// an error is returned.
+	pub fn install<T: AsRef<Path>>(
+		&self,
+		dir: T,
+		name: &str
+	) -> Result<(), io::Error>
+	{
+		fs::create_dir_all(dir.as_ref())?;
+		let txt_path = dir.as_ref().join(format!("{}.txt", name));
+		let mut writer = io::BufWriter::new(File::create(&txt_path)?);
+		for word in self.sorted()
+		{
+			writeln!(writer, "{}", word)?;
+		}
+		writer.flush()?;
+		trace!("Wrote text dictionary: {}", txt_path.display());
+		let dict_path = dir.as_ref().join(format!("{}.dict", name));
+		self.serialize_to_file(&dict_path)?;
+		trace!("Wrote binary dictionary: {}", dict_path.display());
+		Ok(())
+	}
+
+	/// Construct a dictionary from the contents of the given word list, whose
+	/// [format](InputFormat) is [detected](InputFormat::detect) from its
+	/// extension. For plain text, each line in the file is considered a single
+	/// word. The words [populate](Self::populate) the dictionary, so every
+	/// word is [normalized](normalize_word), and empty words are ignored.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The target file.
+	///
+	/// # Returns
+	///
+	/// A dictionary containing the words from the file.
+	///
+	/// # Errors
+	///
+	/// * If the file cannot be opened or read, an error is returned.
+	/// * If the file is malformed JSON, an [`ErrorKind::InvalidData`] is
+	///   returned.
+	/// * If a SCOWL bundle contains no word lists, an [`ErrorKind::NotFound`]
+	///   is returned.
+	pub fn read_from_file<T: AsRef<Path>>(path: T) -> Result<Self, io::Error>
+	{
+		Self::read_from_file_with_progress(path, &Progress::default())
+	}
+
+	/// Read a dictionary from the given file, exactly as
+	/// [`read_from_file`](Self::read_from_file) does, but report
+	/// [progress](Progress) periodically, including the tally of duplicate and
+	/// rejected words, and stop early if the progress is
+	/// [cancelled](Progress::cancel).
+	///
+	/// # Arguments
+	///
+	/// * `path` - The target file.
+	/// * `progress` - The progress, which is updated as the file is read.
+	///
+	/// # Returns
+	///
+	/// A dictionary containing the words from the file.
+	///
+	/// # Errors
+	///
+	/// * If the file cannot be opened or read, an error is returned.
+	/// * If the file is malformed JSON, an [`ErrorKind::InvalidData`] is
+	///   returned.
+	/// * If a SCOWL bundle contains no word lists, an [`ErrorKind::NotFound`]
+	///   is returned.
+	/// * If the progress is cancelled, an [`ErrorKind::Interrupted`] is
+	///   returned.
+	pub fn read_from_file_with_progress<T: AsRef<Path>>(
+		path: T,
+		progress: &Progress
+	) -> Result<Self, io::Error>
+	{
+		let format = InputFormat::detect(&path);
+		Self::read_from_file_as(
+			path,
+			format,
+			&Normalization::default(),
+			progress
+		)
+	}
+
+	/// Read a dictionary from the given word list, exactly as
+	/// [`read_from_file_with_progress`](Self::read_from_file_with_progress)
+	/// does, but in the specified format, regardless of the extension, and
+	/// normalizing the words according to the given
+	/// [rules](Normalization).
+	///
+	/// # Arguments
+	///
+	/// * `path` - The target file, or directory for a SCOWL bundle.
+	/// * `format` - The format of the word list.
+	/// * `normalization` - The rules of normalization.
+	/// * `progress` - The progress, which is updated as the file is read.
+	///
+	/// # Returns
+	///
+	/// A dictionary containing the words from the file.
+	///
+	/// # Errors
+	///
+	/// * If the file cannot be opened or read, an error is returned.
+	/// * If the file is malformed JSON, an [`ErrorKind::InvalidData`] is
+	///   returned.
+	/// * If a SCOWL bundle contains no word lists, an [`ErrorKind::NotFound`]
+	///   is returned.
+	/// * If the progress is cancelled, an [`ErrorKind::Interrupted`] is
+	///   returned.
+	pub fn read_from_file_as<T: AsRef<Path>>(
+		path: T,
+		format: InputFormat,
+		normalization: &Normalization,
+		progress: &Progress
+	) -> Result<Self, io::Error>
+	{
+		let words = match format
+		{
+			InputFormat::Json => read_json(path.as_ref(), progress)?,
+			InputFormat::Scowl => read_scowl(path.as_ref(), progress)?,
+			_ => read_lines(path.as_ref(), format, progress)?
+		};
+		let mut dictionary = Self::new();
+		for chunk in words.chunks(Progress::INTERVAL)
+		{
+			progress.check()?;
+			let ingestion = dictionary.populate_with(chunk, normalization);
+			progress.words_inserted.store(dictionary.len(), Ordering::Relaxed);
+			progress.duplicates
+				.fetch_add(ingestion.duplicates, Ordering::Relaxed);
+			progress.rejected.fetch_add(ingestion.rejected, Ordering::Relaxed);
+		}
+		Ok(dictionary)
+	}
+
+	/// Deserialize a dictionary from the given file. The file must contain a
+	/// serialized dictionary in [`bincode`](bincode) format, optionally
+	/// [compressed](Compression), and optionally preceded by a
+	/// [header](Header). The compression is detected automatically from the
+	/// magic bytes at the start of the content, but decompression requires
+	/// the `compress` feature. With the `mmap` feature, the file is
+	/// memory-mapped rather than read onto the heap.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The target file.
+	///
+	/// # Returns
+	///
+	/// A dictionary deserialized from the file.
 	///
 	/// # Errors
 	///
 	/// * If the file cannot be opened or read, an error is returned.
 	/// * If the file contains invalid data, an [`ErrKind::InvalidData`] is
 	///   returned.
+	/// * If the file is compressed but the `compress` feature is disabled, an
+	///   [`ErrorKind::Unsupported`] is returned.
 	pub fn deserialize_from_file<T: AsRef<Path>>(
 		path: T
 	) -> Result<Self, io::Error>
 	{
-		let file = File::open(path)?;
-		let mut reader = BufReader::new(file);
-		let mut content = Vec::new();
-		reader.read_to_end(&mut content)?;
-		let dictionary = bincode::deserialize(&content)
-			.map_err(|_e| ErrorKind::InvalidData)?;
-		Ok(dictionary)
+		let file = File::open(path)?;
+		let raw = read_file(file)?;
+		Self::deserialize_from_bytes(&raw)
+	}
+
+	/// Deserialize a dictionary from the given bytes, which must have the
+	/// format of a file accepted by
+	/// [`deserialize_from_file`](Self::deserialize_from_file). The bytes may
+	/// be arbitrarily corrupt: decoding never reads beyond them, never trusts
+	/// an encoded length beyond them, and never decompresses more than
+	/// [`MAX_DECOMPRESSED_BYTES`].
+	///
+	/// # Arguments
+	///
+	/// * `raw` - The bytes.
+	///
+	/// # Returns
+	///
+	/// A dictionary deserialized from the bytes.
+	///
+	/// # Errors
+	///
+	/// * If the bytes contain invalid data, or the number of words disagrees
+	///   with the header, an [`ErrorKind::InvalidData`] is returned.
+	/// * If the bytes are compressed but the `compress` feature is disabled, an
+	///   [`ErrorKind::Unsupported`] is returned, unless the bytes also decode
+	///   as an uncompressed dictionary.
+	pub fn deserialize_from_bytes(raw: &[u8]) -> Result<Self, io::Error>
+	{
+		let (header, content) = Header::split(raw)?;
+		let dictionary = Self::deserialize_content(content)?;
+		match header
+		{
+			Some(header) if header.words != dictionary.len() as u64 =>
+				Err(io::Error::new(
+					ErrorKind::InvalidData,
+					"Header disagrees with content"
+				)),
+			_ => Ok(dictionary)
+		}
+	}
+
+	/// Deserialize a dictionary from the given content, i.e., the bytes that
+	/// follow the header, if any.
+	///
+	/// # Arguments
+	///
+	/// * `raw` - The content.
+	///
+	/// # Returns
+	///
+	/// A dictionary deserialized from the content.
+	///
+	/// # Errors
+	///
+	/// As for [`deserialize_from_bytes`](Self::deserialize_from_bytes).
+	fn deserialize_content(raw: &[u8]) -> Result<Self, io::Error>
+	{
+		let compression = Compression::detect(raw);
+		if compression == Compression::None
+		{
+			return Self::decode(raw)
+		}
+		match compression.decompress(raw)
+		{
+			Ok(decompressed) => Self::decode(&decompressed),
+			// The magic bytes can collide with the word count of an
+			// uncompressed dictionary, e.g., 35,615 words begin with the magic
+			// bytes of gzip, so fall back to reading the content verbatim.
+			// Only if that fails too is the failure to decompress reported.
+			Err(e) =>
+			{
+				trace!("Not actually {:?}; reading verbatim", compression);
+				Self::decode(raw).map_err(|verbatim| match e.kind()
+				{
+					ErrorKind::Unsupported => e,
+					_ => verbatim
+				})
+			}
+		}
+	}
+
+	/// Decode a dictionary from the given uncompressed bytes. Same encoding as
+	/// `bincode::deserialize`, but bounded by the content, so that a corrupt
+	/// length cannot provoke a huge allocation.
+	///
+	/// # Arguments
+	///
+	/// * `content` - The bytes.
+	///
+	/// # Returns
+	///
+	/// A dictionary decoded from the bytes.
+	///
+	/// # Errors
+	///
+	/// If the bytes contain invalid data, an [`ErrorKind::InvalidData`] is
+	/// returned.
+	fn decode(content: &[u8]) -> Result<Self, io::Error>
+	{
+		let dictionary = bincode::options()
+			.with_fixint_encoding()
+			.allow_trailing_bytes()
+			.with_limit(content.len() as u64)
+			.deserialize(content)
+			.map_err(|_e| ErrorKind::InvalidData)?;
+		Ok(dictionary)
+	}
+
+	/// Serialize the dictionary to the given file. The dictionary is serialized
+	/// in [`bincode`](bincode) format, preceded by a [header](Header).
+	///
+	/// # Arguments
+	///
+	/// * `path` - The target file.
+	///
+	/// # Errors
+	///
+	/// * If the file cannot be opened or written, an error is returned.
+	/// * If the file contains invalid data, an [`ErrKind::InvalidData`] is
+	///   returned.
+	pub fn serialize_to_file<T: AsRef<Path>>(
+		&self,
+		path: T
+	) -> Result<(), io::Error>
+	{
+		self.serialize_to_file_with(path, Compression::None)
+	}
+
+	/// Serialize the dictionary to the given file, applying the specified
+	/// [compression](Compression). The dictionary is serialized in
+	/// [`bincode`](bincode) format, preceded by an uncompressed
+	/// [header](Header). The content is written to a sibling file
+	/// first and then renamed over the target, so the target is never
+	/// modified in place: a reader that has the previous file open, or
+	/// [memory-mapped](Self::deserialize_from_file), keeps seeing the previous
+	/// content.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The target file.
+	/// * `compression` - The compression to apply.
+	///
+	/// # Errors
+	///
+	/// * If the file cannot be opened or written, an error is returned.
+	/// * If the file contains invalid data, an [`ErrKind::InvalidData`] is
+	///   returned.
+	/// * If compression is requested but the `compress` feature is disabled,
+	///   an [`ErrorKind::Unsupported`] is returned.
+	pub fn serialize_to_file_with<T: AsRef<Path>>(
+		&self,
+		path: T,
+		compression: Compression
+	) -> Result<(), io::Error>
+	{
+		let content = bincode::serialize(self)
+			.map_err(|_e| ErrorKind::InvalidData)?;
+		let content = compression.compress(content)?;
+		let path = path.as_ref();
+		let temp_path = path.with_extension("tmp");
+		let header = Header {
+			words: self.len() as u64,
+			compression
+		};
+		let mut file = File::create(&temp_path)?;
+		file.write_all(&header.encode())?;
+		file.write_all(&content)?;
+		file.sync_all()?;
+		fs::rename(&temp_path, path)?;
+		Ok(())
+	}
+}
+
+impl<T: AsRef<str>> Extend<T> for Dictionary
+{
+	fn extend<I: IntoIterator<Item = T>>(&mut self, words: I)
+	{
+		for word in words
+		{
+			let word = self.fold(word.as_ref()).into_owned();
+			if !word.is_empty()
+			{
+				self.words.insert(word);
+			}
+		}
+		self.invalidate();
+	}
+}
+
+impl PartialEq for Dictionary
+{
+	fn eq(&self, other: &Self) -> bool
+	{
+		// The index is derived from the words, so it never decides equality.
+		self.words == other.words
+	}
+}
+
+impl Eq for Dictionary {}
+
+impl<T: AsRef<str>> FromIterator<T> for Dictionary
+{
+	fn from_iter<I: IntoIterator<Item = T>>(words: I) -> Self
+	{
+		let mut dictionary = Self::new();
+		dictionary.extend(words);
+		dictionary
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Cursors.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A cursor into a [`Dictionary`], which descends one fragment at a time and
+/// ascends in reverse order, like a search over fragment paths. The cursor
+/// tracks the range of the dictionary's [sorted words](Dictionary::iter_prefix)
+/// that start with its prefix. Descending narrows that range by binary search,
+/// never probing outside of it, and ascending restores the previous range
+/// without probing at all. Beneath a dead prefix, i.e., one that begins no
+/// word, the range is empty and descending is free. With a
+/// [letter filter](Self::with_filter), a prefix that the filter rules out is
+/// dead without a search.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct DictionaryCursor<'a>
+{
+	/// The sorted words of the dictionary.
+	words: &'a [Box<str>],
+
+	/// The letter filter to consult before each search, if any.
+	filter: Option<&'a LetterFilter>,
+
+	/// The current prefix.
+	prefix: String,
+
+	/// The length of the current prefix, in characters.
+	chars: usize,
+
+	/// The [signature](LetterFilter::signature) of the current prefix.
+	signature: u64,
+
+	/// The number of searches performed by descending.
+	probes: u64,
+
+	/// The range of the [words](Self::words) that start with the current
+	/// prefix.
+	range: Range<usize>,
+
+	/// The length of the prefix, in bytes and in characters, its signature,
+	/// and the range of the words that start with it, before each descent.
+	levels: Vec<(usize, usize, u64, Range<usize>)>
+}
+
+impl<'a> DictionaryCursor<'a>
+{
+	/// Consult the specified [letter filter](LetterFilter) before each
+	/// search. The filter must belong to the same dictionary as the cursor,
+	/// since it must never rule out a live prefix.
+	///
+	/// # Arguments
+	///
+	/// * `filter` - The letter filter.
+	///
+	/// # Returns
+	///
+	/// The cursor.
+	pub fn with_filter(mut self, filter: &'a LetterFilter) -> Self
+	{
+		self.filter = Some(filter);
+		self
+	}
+
+	/// Descend by appending the specified fragment to the current prefix.
+	///
+	/// # Arguments
+	///
+	/// * `fragment` - The fragment.
+	///
+	/// # Returns
+	///
+	/// `true` if the new prefix is live, `false` otherwise.
+	pub fn descend(&mut self, fragment: &str) -> bool
+	{
+		self.levels.push((
+			self.prefix.len(),
+			self.chars,
+			self.signature,
+			self.range.clone()
+		));
+		self.prefix.push_str(fragment);
+		self.chars += fragment.chars().count();
+		if let Some(filter) = self.filter
+		{
+			self.signature = self.signature
+				.wrapping_add(LetterFilter::signature(fragment));
+			if !filter.may_begin(self.signature)
+			{
+				self.range = self.range.start..self.range.start;
+				return false
+			}
+		}
+		if !self.range.is_empty()
+		{
+			self.probes += 1;
+			self.range = narrow(self.words, self.range.clone(), &self.prefix);
+		}
+		self.is_live()
+	}
+
+	/// Ascend by removing the most recently appended fragment from the
+	/// current prefix.
+	///
+	/// # Returns
+	///
+	/// `true` if the cursor ascended, `false` if it was already at the root.
+	pub fn ascend(&mut self) -> bool
+	{
+		match self.levels.pop()
+		{
+			Some((len, chars, signature, range)) =>
+			{
+				self.prefix.truncate(len);
+				self.chars = chars;
+				self.signature = signature;
+				self.range = range;
+				true
+			},
+			None => false
+		}
+	}
+
+	/// Answer the current prefix.
+	///
+	/// # Returns
+	///
+	/// The prefix.
+	#[inline]
+	#[must_use]
+	pub fn prefix(&self) -> &str
+	{
+		&self.prefix
+	}
+
+	/// Answer the length of the current prefix, in characters.
+	///
+	/// # Returns
+	///
+	/// The number of characters.
+	#[inline]
+	#[must_use]
+	pub fn char_count(&self) -> usize
+	{
+		self.chars
+	}
+
+	/// Answer the number of searches performed by descending, i.e., the
+	/// number of descents from live prefixes that the
+	/// [letter filter](Self::with_filter), if any, didn't rule out.
+	///
+	/// # Returns
+	///
+	/// The number of searches.
+	#[inline]
+	#[must_use]
+	pub fn probes(&self) -> u64
+	{
+		self.probes
+	}
+
+	/// Answer the number of fragments appended since the root.
+	///
+	/// # Returns
+	///
+	/// The depth of the cursor.
+	#[inline]
+	#[must_use]
+	pub fn depth(&self) -> usize
+	{
+		self.levels.len()
+	}
+
+	/// Check whether the current prefix is live, i.e., whether some word of
+	/// the dictionary starts with it. The root of a nonempty dictionary is
+	/// always live.
+	///
+	/// # Returns
+	///
+	/// `true` if the prefix is live, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_live(&self) -> bool
+	{
+		!self.range.is_empty()
+	}
+
+	/// Check whether the current prefix is itself a word of the dictionary.
+	/// If it is, then it sorts before every other word that it begins.
+	///
+	/// # Returns
+	///
+	/// `true` if the prefix is a word, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_word(&self) -> bool
+	{
+		self.is_live() && *self.words[self.range.start] == self.prefix
+	}
+}
+
+/// Narrow a range of sorted words to the words that start with the given
+/// prefix. The words that start with the prefix are contiguous, because they
+/// sort after every lesser word and before every greater word that doesn't
+/// start with the prefix.
+///
+/// # Arguments
+///
+/// * `words` - The sorted words.
+/// * `range` - The range of words to search, which must include every word
+///   that starts with the prefix.
+/// * `prefix` - The prefix.
+///
+/// # Returns
+///
+/// The range of the words that start with the prefix.
+fn narrow(words: &[Box<str>], range: Range<usize>, prefix: &str) -> Range<usize>
+{
+	let candidates = &words[range.clone()];
+	let start = candidates.partition_point(|word| **word < *prefix);
+	let len = candidates[start..]
+		.partition_point(|word| word.starts_with(prefix));
+	range.start + start..range.start + start + len
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                              Letter filters.                               //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A [Bloom filter](https://en.wikipedia.org/wiki/Bloom_filter) over the
+/// letters of every prefix of the words of a [`Dictionary`], by which a search
+/// can rule out a prefix without probing the dictionary. A prefix is reduced
+/// to the multiset of its letters, whose [signature](Self::signature) is the
+/// wrapping sum of the signatures of the letters, so the signature of a longer
+/// prefix follows from the signature of a shorter one by addition alone. The
+/// filter never rules out a live prefix, but may admit a dead one, e.g., any
+/// anagram of a live prefix.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct LetterFilter
+{
+	/// The bits of the filter.
+	bits: Box<[u64]>
+}
+
+impl LetterFilter
+{
+	/// The number of bits of the filter per prefix, which holds the rate of
+	/// false positives to about 1%.
+	const BITS_PER_PREFIX: usize = 10;
+
+	/// The number of bits probed per signature.
+	const PROBES: u64 = 7;
+
+	/// Build the filter for the specified words.
+	///
+	/// # Arguments
+	///
+	/// * `words` - The words.
+	///
+	/// # Returns
+	///
+	/// The filter.
+	fn new<'a>(words: impl Iterator<Item = &'a str> + Clone) -> Self
+	{
+		let prefixes = words.clone()
+			.map(|word| word.chars().count())
+			.sum::<usize>();
+		let len = (prefixes * Self::BITS_PER_PREFIX).div_ceil(64).max(1);
+		let mut filter = Self { bits: vec![0; len].into_boxed_slice() };
+		for word in words
+		{
+			let mut signature = 0u64;
+			for c in word.chars()
+			{
+				signature = signature.wrapping_add(letter_signature(c));
+				for bit in filter.probes(signature)
+				{
+					filter.bits[bit / 64] |= 1 << (bit % 64);
+				}
+			}
+		}
+		trace!(
+			"Built letter filter: {} prefixes, {} bytes",
+			prefixes,
+			8 * len
+		);
+		filter
+	}
+
+	/// Compute the signature of the multiset of letters of the given text.
+	/// The signature of a concatenation is the wrapping sum of the signatures
+	/// of its parts.
+	///
+	/// # Arguments
+	///
+	/// * `text` - The text.
+	///
+	/// # Returns
+	///
+	/// The signature.
+	#[must_use]
+	pub fn signature(text: &str) -> u64
+	{
+		text.chars().map(letter_signature).fold(0, u64::wrapping_add)
+	}
+
+	/// Check whether some prefix of some word of the dictionary may have the
+	/// letters whose [signature](Self::signature) is given. The empty prefix
+	/// always may.
+	///
+	/// # Arguments
+	///
+	/// * `signature` - The signature.
+	///
+	/// # Returns
+	///
+	/// `false` if no prefix has the letters, `true` if some prefix may.
+	#[must_use]
+	pub fn may_begin(&self, signature: u64) -> bool
+	{
+		signature == 0 || self.probes(signature)
+			.all(|bit| self.bits[bit / 64] & 1 << (bit % 64) != 0)
+	}
+
+	/// Answer the bits to probe for the given signature, by double hashing.
+	///
+	/// # Arguments
+	///
+	/// * `signature` - The signature.
+	///
+	/// # Returns
+	///
+	/// The indices of the bits.
+	fn probes(&self, signature: u64) -> impl Iterator<Item = usize>
+	{
+		let len = 64 * self.bits.len() as u64;
+		let hash = mix(signature);
+		let (h1, h2) = (hash & 0xffff_ffff, hash >> 32 | 1);
+		(0..Self::PROBES).map(move |i| {
+			(h1.wrapping_add(i.wrapping_mul(h2)) % len) as usize
+		})
+	}
+}
+
+/// Compute the signature of a single letter, for a [`LetterFilter`].
+///
+/// # Arguments
+///
+/// * `c` - The letter.
+///
+/// # Returns
+///
+/// The signature.
+#[inline]
+fn letter_signature(c: char) -> u64
+{
+	mix(u64::from(c))
+}
+
+/// Scramble the bits of the given value, using the finalizer of
+/// [SplitMix64](https://prng.di.unimi.it/splitmix64.c).
+///
+/// # Arguments
+///
+/// * `value` - The value.
+///
+/// # Returns
+///
+/// The scrambled value.
+#[inline]
+fn mix(value: u64) -> u64
+{
+	let mut z = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+	z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+	z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+	z ^ (z >> 31)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Progress.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The progress of [opening](Dictionary::open_with_progress) a dictionary,
+/// which one thread updates while another observes. Every counter is
+/// monotonic, and each is only an estimate until opening finishes. The
+/// observer may also [cancel](Self::cancel) the opening.
+#[derive(Debug, Default)]
+pub struct Progress
+{
+	/// The number of bytes of the dictionary file read so far.
+	bytes_read: AtomicU64,
+
+	/// The size of the dictionary file, in bytes, or `0` if not yet known.
+	total_bytes: AtomicU64,
+
+	/// The number of words inserted into the dictionary so far.
+	words_inserted: AtomicUsize,
+
+	/// The number of duplicate words encountered so far.
+	duplicates: AtomicUsize,
+
+	/// The number of words rejected by normalization so far.
+	rejected: AtomicUsize,
+
+	/// Whether opening has been cancelled.
+	cancelled: AtomicBool
+}
+
+impl Progress
+{
+	/// The number of lines or words between successive progress reports,
+	/// which is also the granularity of cancellation.
+	const INTERVAL: usize = 4096;
+
+	/// Answer the number of bytes of the dictionary file read so far.
+	///
+	/// # Returns
+	///
+	/// The number of bytes read.
+	#[inline]
+	#[must_use]
+	pub fn bytes_read(&self) -> u64
+	{
+		self.bytes_read.load(Ordering::Relaxed)
+	}
+
+	/// Answer the size of the dictionary file.
+	///
+	/// # Returns
+	///
+	/// The size of the file, in bytes, or `0` if not yet known.
+	#[inline]
+	#[must_use]
+	pub fn total_bytes(&self) -> u64
+	{
+		self.total_bytes.load(Ordering::Relaxed)
+	}
+
+	/// Answer the number of words inserted into the dictionary so far.
+	///
+	/// # Returns
+	///
+	/// The number of words inserted.
+	#[inline]
+	#[must_use]
+	pub fn words_inserted(&self) -> usize
+	{
+		self.words_inserted.load(Ordering::Relaxed)
+	}
+
+	/// Answer the number of duplicate words encountered so far, i.e., words
+	/// that were already in the dictionary when they were read. Only a text
+	/// dictionary or word list can contain duplicates.
+	///
+	/// # Returns
+	///
+	/// The number of duplicates.
+	#[inline]
+	#[must_use]
+	pub fn duplicates(&self) -> usize
+	{
+		self.duplicates.load(Ordering::Relaxed)
+	}
+
+	/// Answer the number of words rejected by [normalization](Normalization)
+	/// so far.
+	///
+	/// # Returns
+	///
+	/// The number of rejected words.
+	#[inline]
+	#[must_use]
+	pub fn rejected(&self) -> usize
+	{
+		self.rejected.load(Ordering::Relaxed)
+	}
+
+	/// Cancel opening the dictionary. Opening stops at the next progress
+	/// report.
+	#[inline]
+	pub fn cancel(&self)
+	{
+		self.cancelled.store(true, Ordering::Relaxed);
+	}
+
+	/// Check whether opening the dictionary has been cancelled.
+	///
+	/// # Returns
+	///
+	/// `true` if opening has been cancelled, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_cancelled(&self) -> bool
+	{
+		self.cancelled.load(Ordering::Relaxed)
+	}
+
+	/// Check whether opening the dictionary should continue.
+	///
+	/// # Errors
+	///
+	/// If opening has been cancelled, an [`ErrorKind::Interrupted`] is
+	/// returned.
+	fn check(&self) -> Result<(), io::Error>
+	{
+		match self.is_cancelled()
+		{
+			true => Err(io::Error::new(ErrorKind::Interrupted, "Cancelled")),
+			false => Ok(())
+		}
+	}
+}
+
+/// The tally of [populating](Dictionary::populate_with) a dictionary with a
+/// batch of words. Blank words are not tallied.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Ingestion
+{
+	/// The number of words inserted into the dictionary.
+	pub inserted: usize,
+
+	/// The number of words already in the dictionary, including those that
+	/// occurred earlier in the batch.
+	pub duplicates: usize,
+
+	/// The number of words that [normalization](Normalization) dropped.
+	pub rejected: usize
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Sources.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The sources that can supply the words of a [`Dictionary`]. The dictionary
+/// file itself supplies the base, and each
+/// [inclusion list](Dictionary::include_from_file) is tagged with its own
+/// source, so that a solution can say which list supplied each word.
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	PartialEq,
+	Eq,
+	Hash,
+	PartialOrd,
+	Ord,
+	Serialize,
+	Deserialize,
+	ValueEnum
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Source
+{
+	/// The dictionary file.
+	#[default]
+	Base,
+
+	/// A word list curated by the user.
+	Custom,
+
+	/// A word list downloaded from elsewhere.
+	Downloaded
+}
+
+impl Source
+{
+	/// Get the name of the source, as written on the command line.
+	///
+	/// # Returns
+	///
+	/// The name of the source.
+	#[must_use]
+	pub const fn name(self) -> &'static str
+	{
+		match self
+		{
+			Self::Base => "base",
+			Self::Custom => "custom",
+			Self::Downloaded => "downloaded"
+		}
+	}
+}
+
+impl Display for Source
+{
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result
+	{
+		f.write_str(self.name())
+	}
+}
+
+/// A word list to [include](Dictionary::include_from_file) in a dictionary,
+/// tagged with its [source](Source). It is written `[SOURCE=]PATH`, e.g.,
+/// `downloaded=wiktionary.txt`, and the source defaults to
+/// [`Source::Custom`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Inclusion
+{
+	/// The source of the words.
+	pub source: Source,
+
+	/// The path to the word list.
+	pub path: PathBuf
+}
+
+impl FromStr for Inclusion
+{
+	type Err = io::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err>
+	{
+		let tagged = s.split_once('=').and_then(|(source, path)| {
+			Source::from_str(source, true)
+				.ok()
+				.map(|source| (source, path))
+		});
+		let (source, path) = tagged.unwrap_or((Source::Custom, s));
+		match path.is_empty()
+		{
+			true => Err(io::Error::new(
+				ErrorKind::InvalidInput,
+				format!("No path to the word list: {}", s)
+			)),
+			false => Ok(Self { source, path: PathBuf::from(path) })
+		}
+	}
+}
+
+/// How to compose a [`Dictionary`] from its base word list: which
+/// [inclusion lists](Dictionary::include_from_file) to add, which
+/// [exclusion lists](Dictionary::exclude_from_file) to remove, and which
+/// [sources](Source) to keep.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Composition
+{
+	/// The inclusion lists, in order of precedence.
+	pub include: Vec<Inclusion>,
+
+	/// The exclusion lists.
+	pub exclude: Vec<PathBuf>,
+
+	/// The sources whose words to keep, or empty to keep every word.
+	pub sources: Vec<Source>
+}
+
+impl Composition
+{
+	/// Compose the given dictionary: add the words of the inclusion lists,
+	/// then remove the words of the exclusion lists, and finally remove the
+	/// words of the sources not kept, if any are specified.
+	///
+	/// # Arguments
+	///
+	/// * `dictionary` - The dictionary, freshly opened.
+	///
+	/// # Errors
+	///
+	/// If any list cannot be opened or read, an error naming the list is
+	/// returned.
+	pub fn apply(&self, dictionary: &mut Dictionary) -> Result<(), io::Error>
+	{
+		let describe = |kind: &str, path: &Path, e: io::Error| {
+			io::Error::new(
+				e.kind(),
+				format!(
+					"Failed to read {} list: {}: {}",
+					kind,
+					path.display(),
+					e
+				)
+			)
+		};
+		for Inclusion { source, path } in &self.include
+		{
+			dictionary.include_from_file(path, *source)
+				.map_err(|e| describe("inclusion", path, e))?;
+		}
+		for path in &self.exclude
+		{
+			dictionary.exclude_from_file(path)
+				.map_err(|e| describe("exclusion", path, e))?;
+		}
+		if !self.sources.is_empty()
+		{
+			dictionary.retain_sources(&self.sources);
+		}
+		Ok(())
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                               Normalization.                               //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The languages whose word lists can be normalized. The language determines
+/// the alphabet of the dictionary, i.e., which letters a word may contain, and
+/// the default name of the dictionary.
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	PartialEq,
+	Eq,
+	Hash,
+	Serialize,
+	Deserialize,
+	ValueEnum
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Language
+{
+	/// English, whose alphabet is the 26 letters of the Latin alphabet.
+	#[default]
+	English,
+
+	/// French, which adds the accented vowels, the cedilla, and the
+	/// ligatures `æ` and `œ`.
+	French,
+
+	/// German, which adds the umlauts and the eszett.
+	German,
+
+	/// Spanish, which adds the acute accents, the diaeresis, and the eñe.
+	Spanish
+}
+
+impl Language
+{
+	/// Get the name of the language, which is also the default name of its
+	/// dictionary.
+	///
+	/// # Returns
+	///
+	/// The name of the language.
+	#[must_use]
+	pub const fn name(self) -> &'static str
+	{
+		match self
+		{
+			Self::English => "english",
+			Self::French => "french",
+			Self::German => "german",
+			Self::Spanish => "spanish"
+		}
+	}
+
+	/// Check if the given character belongs to the alphabet of the language.
+	/// Only lowercase letters in [NFC](normalize_word) belong to an alphabet.
+	///
+	/// # Arguments
+	///
+	/// * `c` - The character to check.
+	///
+	/// # Returns
+	///
+	/// `true` if the character is a letter of the language, `false`
+	/// otherwise.
+	#[must_use]
+	pub fn is_letter(self, c: char) -> bool
+	{
+		let extra = match self
+		{
+			Self::English => "",
+			Self::French => "àâæçéèêëîïôœùûüÿ",
+			Self::German => "äöüß",
+			Self::Spanish => "áéíñóúü"
+		};
+		c.is_ascii_lowercase() || extra.contains(c)
+	}
+}
+
+/// The rules that normalize the words of a word list as they
+/// [populate](Dictionary::populate_with) a dictionary, so that an arbitrary
+/// word list becomes compatible with Quartiles. Every word is trimmed and
+/// composed into NFC, just as [`normalize_word`] does, and the rules refine
+/// the rest. The default rules only lowercase, and so agree with
+/// [`normalize_word`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Args)]
+pub struct Normalization
+{
+	/// Keep the case of each word, rather than lowercasing it. Fragments are
+	/// always lowercase, so a word that keeps an uppercase letter can never
+	/// be formed.
+	#[arg(long = "keep-case", action = ArgAction::SetFalse)]
+	pub lowercase: bool,
+
+	/// Strip the diacritics from each word, e.g., `café` becomes `cafe`.
+	#[arg(long)]
+	pub strip_diacritics: bool,
+
+	/// Remove the apostrophes from each word, e.g., `o'clock` becomes
+	/// `oclock`.
+	#[arg(long)]
+	pub remove_apostrophes: bool,
+
+	/// Remove the hyphens from each word, e.g., `well-being` becomes
+	/// `wellbeing`.
+	#[arg(long)]
+	pub remove_hyphens: bool,
+
+	/// Drop each word that contains a digit, e.g., `mp3`.
+	#[arg(long)]
+	pub drop_digits: bool
+}
+
+impl Default for Normalization
+{
+	fn default() -> Self
+	{
+		Self {
+			lowercase: true,
+			strip_diacritics: false,
+			remove_apostrophes: false,
+			remove_hyphens: false,
+			drop_digits: false
+		}
+	}
+}
+
+impl Normalization
+{
+	/// The apostrophes that [`remove_apostrophes`](Self::remove_apostrophes)
+	/// removes: the typewriter apostrophe, the right single quotation mark,
+	/// and the modifier letter apostrophe.
+	const APOSTROPHES: [char; 3] = ['\'', '\u{2019}', '\u{2bc}'];
+
+	/// The hyphens that [`remove_hyphens`](Self::remove_hyphens) removes: the
+	/// hyphen-minus, the hyphen, and the non-breaking hyphen.
+	const HYPHENS: [char; 3] = ['-', '\u{2010}', '\u{2011}'];
+
+	/// Normalize the given word according to the rules.
+	///
+	/// # Arguments
+	///
+	/// * `word` - The raw word.
+	///
+	/// # Returns
+	///
+	/// The normalized word, or `None` if the rules drop the word or it is
+	/// empty.
+	#[must_use]
+	pub fn apply(&self, word: &str) -> Option<String>
+	{
+		let word = word.trim();
+		if self.drop_digits && word.chars().any(char::is_numeric)
+		{
+			return None
+		}
+		let mut word = match self.strip_diacritics
+		{
+			true => word.nfd()
+				.filter(|&c| !is_combining_mark(c))
+				.nfc()
+				.collect(),
+			false => word.nfc().collect::<String>()
+		};
+		if self.lowercase
+		{
+			word = word.to_lowercase();
+		}
+		if self.remove_apostrophes
+		{
+			word.retain(|c| !Self::APOSTROPHES.contains(&c));
+		}
+		if self.remove_hyphens
+		{
+			word.retain(|c| !Self::HYPHENS.contains(&c));
+		}
+		(!word.is_empty()).then_some(word)
+	}
+}
+
+/// Normalize a single word, e.g., a fragment typed by the user or a line of a
+/// text dictionary. The word is trimmed, composed into Unicode Normalization
+/// Form C (NFC), and lowercased, so that every spelling of an accented letter
+/// compares equal, regardless of whether it was entered as a single code point
+/// or as a base letter followed by a combining mark.
+///
+/// # Arguments
+///
+/// * `word` - The word.
+///
+/// # Returns
+///
+/// The normalized word.
+#[must_use]
+pub fn normalize_word(word: &str) -> String
+{
+	word.trim().nfc().collect::<String>().to_lowercase()
+}
+
+/// Normalize a raw word list, e.g., one downloaded from the Internet, into the
+/// form expected of a text dictionary. Each word is
+/// [normalized](normalize_word). Words that contain anything other than
+/// [letters](Language::is_letter) of the given language, such as
+/// abbreviations, contractions, or loanwords with foreign diacritics, are
+/// discarded. The result is sorted and free of duplicates.
+///
+/// # Arguments
+///
+/// * `words` - The raw words.
+/// * `language` - The language of the words.
+///
+/// # Returns
+///
+/// The normalized words.
+pub fn normalize_words<I, T>(words: I, language: Language) -> Vec<String>
+where
+	I: IntoIterator<Item = T>,
+	T: AsRef<str>
+{
+	let mut normalized = words
+		.into_iter()
+		.map(|word| normalize_word(word.as_ref()))
+		.filter(|word| {
+			!word.is_empty() && word.chars().all(|c| language.is_letter(c))
+		})
+		.collect::<Vec<_>>();
+	normalized.sort_unstable();
+	normalized.dedup();
+	normalized
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                Word lists.                                 //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The formats of the word lists from which a dictionary can be
+/// [read](Dictionary::read_from_file). Whatever the format, every word is
+/// [normalized](normalize_word), and empty words are ignored.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum InputFormat
+{
+	/// Plain text, with one word per line.
+	#[default]
+	Text,
+
+	/// A JSON array of strings.
+	Json,
+
+	/// Comma-separated values, with one word per row. If the first row is a
+	/// header that names a `word` column, then the words are taken from that
+	/// column; otherwise, from the first column.
+	Csv,
+
+	/// A Hunspell dictionary (`.dic`), whose first line is the approximate
+	/// number of words, and whose other lines are words, each optionally
+	/// followed by a slash and its affix flags, which are stripped.
+	/// Morphological fields and comments are ignored.
+	Hunspell,
+
+	/// A SCOWL bundle, i.e., a directory of word lists named
+	/// `<category>-words.<size>`, like the `final` directory of the SCOWL
+	/// distribution, or a single such word list. The other lists of the
+	/// bundle, e.g., abbreviations, contractions, and proper names, are
+	/// ignored. The word lists are encoded in ISO-8859-1, unless they are
+	/// valid UTF-8.
+	Scowl
+}
+
+impl InputFormat
+{
+	/// Detect the format of the given word list from its extension: `.json`,
+	/// `.csv`, `.dic` for Hunspell, or a number for the size of a SCOWL word
+	/// list. A directory is a SCOWL bundle. Anything else is plain text.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The word list.
+	///
+	/// # Returns
+	///
+	/// The apparent format of the word list.
+	#[must_use]
+	pub fn detect<T: AsRef<Path>>(path: T) -> Self
+	{
+		let path = path.as_ref();
+		if path.is_dir()
+		{
+			return Self::Scowl
+		}
+		let extension = path.extension()
+			.and_then(|e| e.to_str())
+			.map(str::to_ascii_lowercase)
+			.unwrap_or_default();
+		match extension.as_str()
+		{
+			"json" => Self::Json,
+			"csv" => Self::Csv,
+			"dic" => Self::Hunspell,
+			e if is_number(e) => Self::Scowl,
+			_ => Self::Text
+		}
+	}
+}
+
+/// Read the raw words of a line-oriented word list, i.e., plain text, CSV, or
+/// Hunspell, reporting progress periodically. The words are normalized later,
+/// as they populate the dictionary.
+///
+/// # Arguments
+///
+/// * `path` - The word list.
+/// * `format` - The format of the word list.
+/// * `progress` - The progress, which is updated as the file is read.
+///
+/// # Returns
+///
+/// The raw words, in order of appearance.
+///
+/// # Errors
+///
+/// * If the file cannot be opened or read, an error is returned.
+/// * If the progress is cancelled, an [`ErrorKind::Interrupted`] is returned.
+fn read_lines(
+	path: &Path,
+	format: InputFormat,
+	progress: &Progress
+) -> Result<Vec<String>, io::Error>
+{
+	let file = File::open(path)?;
+	progress.total_bytes.store(file.metadata()?.len(), Ordering::Relaxed);
+	let mut reader = BufReader::new(file);
+	let mut words = Vec::new();
+	let mut line = String::new();
+	let mut lines = 0;
+	let mut bytes_read = 0;
+	let mut column = 0;
+	loop
+	{
+		line.clear();
+		let count = reader.read_line(&mut line)?;
+		if count == 0
+		{
+			break
+		}
+		// Ignore the byte order mark that some editors prepend.
+		let content = match lines
+		{
+			0 => line.trim_start_matches('\u{feff}'),
+			_ => &line
+		};
+		let content = content.trim_end_matches(['\n', '\r']);
+		words.extend(extract_word(format, content, lines, &mut column));
+		lines += 1;
+		bytes_read += count as u64;
+		if lines % Progress::INTERVAL == 0
+		{
+			progress.bytes_read.store(bytes_read, Ordering::Relaxed);
+			progress.check()?;
+		}
+	}
+	progress.bytes_read.store(bytes_read, Ordering::Relaxed);
+	Ok(words)
+}
+
+/// Extract the raw word from the given line of a line-oriented word list.
+///
+/// # Arguments
+///
+/// * `format` - The format of the word list.
+/// * `line` - The line, sans the line terminator.
+/// * `number` - The number of the line, counted from zero.
+/// * `column` - The column of a CSV word list that holds the words, which
+///   its header, if any, determines.
+///
+/// # Returns
+///
+/// The raw word, or `None` if the line holds no word.
+fn extract_word(
+	format: InputFormat,
+	line: &str,
+	number: usize,
+	column: &mut usize
+) -> Option<String>
+{
+	match format
+	{
+		InputFormat::Csv =>
+		{
+			let mut fields = csv_fields(line);
+			let header = fields.iter()
+				.position(|f| f.trim().eq_ignore_ascii_case("word"))
+				.filter(|_| number == 0);
+			if let Some(index) = header
+			{
+				*column = index;
+				return None
+			}
+			(*column < fields.len()).then(|| fields.swap_remove(*column))
+		},
+		InputFormat::Hunspell =>
+		{
+			// The first line is the approximate number of words. Lines that
+			// begin with a tab or a hash are comments.
+			if (number == 0 && is_number(line.trim()))
+				|| line.starts_with(['\t', '#'])
+			{
+				return None
+			}
+			let entry = line.split(['\t', ' ']).next().unwrap_or_default();
+			let mut word = String::with_capacity(entry.len());
+			let mut chars = entry.chars();
+			while let Some(c) = chars.next()
+			{
+				match c
+				{
+					// A backslash escapes a slash that belongs to the word.
+					'\\' if chars.as_str().starts_with('/') =>
+					{
+						word.push('/');
+						chars.next();
+					},
+					'/' => break,
+					c => word.push(c)
+				}
+			}
+			Some(word)
+		},
+		_ => Some(line.to_string())
+	}
+}
+
+/// Split a line of comma-separated values into its fields. A field may be
+/// enclosed in double quotes, in which case it may contain commas, and a
+/// doubled double quote stands for one double quote.
+///
+/// # Arguments
+///
+/// * `line` - The line, sans the line terminator.
+///
+/// # Returns
+///
+/// The fields of the line, unquoted.
+fn csv_fields(line: &str) -> Vec<String>
+{
+	let mut fields = Vec::new();
+	let mut field = String::new();
+	let mut quoted = false;
+	let mut chars = line.chars().peekable();
+	while let Some(c) = chars.next()
+	{
+		match c
+		{
+			'"' if quoted && chars.peek() == Some(&'"') =>
+			{
+				field.push('"');
+				chars.next();
+			},
+			'"' => quoted = !quoted,
+			',' if !quoted => fields.push(std::mem::take(&mut field)),
+			c => field.push(c)
+		}
+	}
+	fields.push(field);
+	fields
+}
+
+/// Read the raw words of a JSON word list, i.e., an array of strings. The array
+/// is parsed all at once, so its progress jumps from nothing to everything.
+///
+/// # Arguments
+///
+/// * `path` - The word list.
+/// * `progress` - The progress, which is updated as the file is read.
+///
+/// # Returns
+///
+/// The raw words, in order of appearance.
+///
+/// # Errors
+///
+/// * If the file cannot be opened or read, an error is returned.
+/// * If the file is not an array of strings, an [`ErrorKind::InvalidData`] is
+///   returned.
+/// * If the progress is cancelled, an [`ErrorKind::Interrupted`] is returned.
+fn read_json(path: &Path, progress: &Progress) -> Result<Vec<String>, io::Error>
+{
+	let content = fs::read(path)?;
+	progress.total_bytes.store(content.len() as u64, Ordering::Relaxed);
+	progress.check()?;
+	let words = serde_json::from_slice::<Vec<String>>(&content)
+		.map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+	progress.bytes_read.store(content.len() as u64, Ordering::Relaxed);
+	Ok(words)
+}
+
+/// Read the raw words of a SCOWL bundle, i.e., of each of its word lists, in
+/// order of name, reporting progress after each word list.
+///
+/// # Arguments
+///
+/// * `path` - The directory of the bundle, or a single word list.
+/// * `progress` - The progress, which is updated as the word lists are read.
+///
+/// # Returns
+///
+/// The raw words, in order of appearance.
+///
+/// # Errors
+///
+/// * If a word list cannot be opened or read, an error is returned.
+/// * If the bundle contains no word lists, an [`ErrorKind::NotFound`] is
+///   returned.
+/// * If the progress is cancelled, an [`ErrorKind::Interrupted`] is returned.
+fn read_scowl(
+	path: &Path,
+	progress: &Progress
+) -> Result<Vec<String>, io::Error>
+{
+	let mut paths = Vec::new();
+	if path.is_dir()
+	{
+		for entry in fs::read_dir(path)?
+		{
+			let path = entry?.path();
+			let is_word_list = path.is_file()
+				&& path.file_name()
+					.and_then(|n| n.to_str())
+					.and_then(|n| n.rsplit_once('.'))
+					.is_some_and(|(stem, size)| {
+						stem.ends_with("-words") && is_number(size)
+					});
+			if is_word_list
+			{
+				paths.push(path);
+			}
+		}
+		if paths.is_empty()
+		{
+			return Err(io::Error::new(
+				ErrorKind::NotFound,
+				format!("No SCOWL word lists: {}", path.display())
+			))
+		}
+		paths.sort();
+	}
+	else
+	{
+		paths.push(path.to_path_buf());
+	}
+	let mut total_bytes = 0;
+	for path in &paths
+	{
+		total_bytes += fs::metadata(path)?.len();
+	}
+	progress.total_bytes.store(total_bytes, Ordering::Relaxed);
+	let mut words = Vec::new();
+	let mut bytes_read = 0;
+	for path in &paths
+	{
+		progress.check()?;
+		let content = fs::read(path)?;
+		bytes_read += content.len() as u64;
+		let content = match String::from_utf8(content)
+		{
+			Ok(content) => content,
+			// ISO-8859-1 maps every byte to the code point of the same value.
+			Err(e) => e.into_bytes().into_iter().map(char::from).collect()
+		};
+		words.extend(content.lines().map(str::to_string));
+		progress.bytes_read.store(bytes_read, Ordering::Relaxed);
+		trace!("Read SCOWL word list: {}", path.display());
+	}
+	Ok(words)
+}
+
+/// Check whether the given text is a nonempty run of decimal digits.
+///
+/// # Arguments
+///
+/// * `text` - The text.
+///
+/// # Returns
+///
+/// `true` if the text is a number, `false` otherwise.
+fn is_number(text: &str) -> bool
+{
+	!text.is_empty() && text.bytes().all(|b| b.is_ascii_digit())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                File access.                                //
+////////////////////////////////////////////////////////////////////////////////
+
+/// Read the complete content of the given file into memory.
+///
+/// # Arguments
+///
+/// * `file` - The file.
+///
+/// # Returns
+///
+/// The content of the file.
+///
+/// # Errors
+///
+/// If the file cannot be read, an error is returned.
+#[cfg(not(feature = "mmap"))]
+fn read_file(file: File) -> Result<Vec<u8>, io::Error>
+{
+	let mut reader = BufReader::new(file);
+	let mut content = Vec::new();
+	reader.read_to_end(&mut content)?;
+	Ok(content)
+}
+
+/// Memory-map the given file. The pages of the file are loaded on demand and
+/// shared with the operating system's page cache, so the content is never
+/// copied onto the heap. This lowers both the startup latency and the peak
+/// memory of loading a large dictionary, because only the prefix tree itself
+/// is allocated.
+///
+/// # Arguments
+///
+/// * `file` - The file.
+///
+/// # Returns
+///
+/// The memory map of the file.
+///
+/// # Errors
+///
+/// If the file cannot be mapped, an error is returned.
+#[cfg(feature = "mmap")]
+fn read_file(file: File) -> Result<memmap2::Mmap, io::Error>
+{
+	// SAFETY: The map is read-only and lives only until deserialization
+	// completes. This crate never modifies a dictionary file in place:
+	// `serialize_to_file_with` writes a sibling file and renames it over the
+	// target, which replaces the directory entry but leaves the mapped inode,
+	// and hence the mapped bytes, untouched. Truncating or rewriting the file
+	// in place from another process while it is mapped is undefined behavior,
+	// as for any memory map; the `mmap` feature documents this requirement.
+	unsafe { memmap2::Mmap::map(&file) }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Header.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The header of a binary dictionary, which precedes its content, compressed
+/// or not. The header records the number of words, so that the statistics of
+/// even a large dictionary can be [read](Self::read_from_file) instantly,
+/// without deserializing it. Binary dictionaries written before the header was
+/// introduced lack it, but remain readable.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Header
+{
+	/// The number of words in the dictionary.
+	pub words: u64,
+
+	/// The compression of the content that follows the header.
+	pub compression: Compression
+}
+
+impl Header
+{
+	/// The magic bytes that begin a header. Read as the word count of a
+	/// dictionary without a header, they would claim over a billion words, so
+	/// they never begin a genuine dictionary without a header.
+	const MAGIC: [u8; 4] = *b"QRTL";
+
+	/// The version of the header.
+	const VERSION: u32 = 1;
+
+	/// The size of the header, in bytes: the magic bytes, the version, and
+	/// the number of words.
+	pub const LEN: usize = 16;
+
+	/// Encode the header. The compression is not encoded, since the content
+	/// announces it with its own magic bytes.
+	///
+	/// # Returns
+	///
+	/// The bytes of the header.
+	#[must_use]
+	fn encode(&self) -> [u8; Self::LEN]
+	{
+		let mut raw = [0; Self::LEN];
+		raw[..4].copy_from_slice(&Self::MAGIC);
+		raw[4..8].copy_from_slice(&Self::VERSION.to_le_bytes());
+		raw[8..].copy_from_slice(&self.words.to_le_bytes());
+		raw
+	}
+
+	/// Split the given bytes of a binary dictionary into its header, if any,
+	/// and its content.
+	///
+	/// # Arguments
+	///
+	/// * `raw` - The bytes of the binary dictionary.
+	///
+	/// # Returns
+	///
+	/// The header, or `None` if the bytes lack one, and the content.
+	///
+	/// # Errors
+	///
+	/// If the bytes begin with the magic bytes of a header, but the header is
+	/// truncated or of an unknown version, an [`ErrorKind::InvalidData`] is
+	/// returned.
+	fn split(raw: &[u8]) -> Result<(Option<Self>, &[u8]), io::Error>
+	{
+		if !raw.starts_with(&Self::MAGIC)
+		{
+			return Ok((None, raw))
+		}
+		if raw.len() < Self::LEN
+		{
+			return Err(io::Error::new(ErrorKind::InvalidData, "Truncated"))
+		}
+		let (header, content) = raw.split_at(Self::LEN);
+		let mut version = [0; 4];
+		version.copy_from_slice(&header[4..8]);
+		if u32::from_le_bytes(version) != Self::VERSION
+		{
+			return Err(
+				io::Error::new(ErrorKind::InvalidData, "Unknown version")
+			)
+		}
+		let mut words = [0; 8];
+		words.copy_from_slice(&header[8..]);
+		let header = Self {
+			words: u64::from_le_bytes(words),
+			compression: Compression::detect(content)
+		};
+		Ok((Some(header), content))
+	}
+
+	/// Read the header of the given binary dictionary, without reading the
+	/// rest of it.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The binary dictionary.
+	///
+	/// # Returns
+	///
+	/// The header, or `None` if the binary dictionary lacks one.
+	///
+	/// # Errors
+	///
+	/// * If the file cannot be opened or read, an error is returned.
+	/// * If the header is truncated or of an unknown version, an
+	///   [`ErrorKind::InvalidData`] is returned.
+	pub fn read_from_file<T: AsRef<Path>>(
+		path: T
+	) -> Result<Option<Self>, io::Error>
+	{
+		// Read enough of the content to detect its compression, too.
+		let mut raw = Vec::new();
+		File::open(path)?
+			.take(Self::LEN as u64 + Compression::ZSTD_MAGIC.len() as u64)
+			.read_to_end(&mut raw)?;
+		Ok(Self::split(&raw)?.0)
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                Compression.                                //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The maximum size of a decompressed binary dictionary, in bytes. This is
+/// generous: the binary form of the English dictionary is a few megabytes.
+pub const MAX_DECOMPRESSED_BYTES: u64 = 1 << 28;
+
+/// The compression of a binary dictionary. Compression trades a little load
+/// time for a much smaller file, which matters for large word lists. Both
+/// compressing and decompressing require the `compress` feature.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Compression
+{
+	/// No compression.
+	#[default]
+	None,
+
+	/// [Gzip](https://www.gzip.org) compression.
+	Gzip,
+
+	/// [Zstandard](https://facebook.github.io/zstd/) compression.
+	Zstd
+}
+
+impl Compression
+{
+	/// The magic bytes that begin a gzip stream.
+	const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+	/// The magic bytes that begin a zstd frame.
+	const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+	/// Detect the compression of the given content from its magic bytes.
+	///
+	/// # Arguments
+	///
+	/// * `content` - The content.
+	///
+	/// # Returns
+	///
+	/// The apparent compression of the content.
+	#[must_use]
+	pub fn detect(content: &[u8]) -> Self
+	{
+		if content.starts_with(&Self::GZIP_MAGIC)
+		{
+			Self::Gzip
+		}
+		else if content.starts_with(&Self::ZSTD_MAGIC)
+		{
+			Self::Zstd
+		}
+		else
+		{
+			Self::None
+		}
+	}
+
+	/// Compress the given content.
+	///
+	/// # Arguments
+	///
+	/// * `content` - The content.
+	///
+	/// # Returns
+	///
+	/// The compressed content.
+	///
+	/// # Errors
+	///
+	/// * If compression fails, an error is returned.
+	/// * If the `compress` feature is disabled, an [`ErrorKind::Unsupported`]
+	///   is returned for any compression other than [`None`](Self::None).
+	fn compress(self, content: Vec<u8>) -> Result<Vec<u8>, io::Error>
+	{
+		match self
+		{
+			Self::None => Ok(content),
+			#[cfg(feature = "compress")]
+			Self::Gzip =>
+			{
+				let mut encoder = flate2::write::GzEncoder::new(
+					Vec::new(),
+					flate2::Compression::default()
+				);
+				encoder.write_all(&content)?;
+				encoder.finish()
+			},
+			#[cfg(feature = "compress")]
+			Self::Zstd => zstd::encode_all(content.as_slice(), 0),
+			#[cfg(not(feature = "compress"))]
+			_ => Err(self.unsupported())
+		}
+	}
+
+	/// Decompress the given content, refusing to produce more than
+	/// [`MAX_DECOMPRESSED_BYTES`], so that a tiny corrupt or malicious file
+	/// cannot exhaust memory.
+	///
+	/// # Arguments
+	///
+	/// * `content` - The compressed content.
+	///
+	/// # Returns
+	///
+	/// The decompressed content.
+	///
+	/// # Errors
+	///
+	/// * If the content is corrupt, an error is returned.
+	/// * If the content decompresses to more than
+	///   [`MAX_DECOMPRESSED_BYTES`], an [`ErrorKind::InvalidData`] is
+	///   returned.
+	/// * If the `compress` feature is disabled, an [`ErrorKind::Unsupported`]
+	///   is returned for any compression other than [`None`](Self::None).
+	fn decompress(self, content: &[u8]) -> Result<Vec<u8>, io::Error>
+	{
+		match self
+		{
+			Self::None => Ok(content.to_vec()),
+			#[cfg(feature = "compress")]
+			Self::Gzip =>
+				self.read_bounded(flate2::read::GzDecoder::new(content)),
+			#[cfg(feature = "compress")]
+			Self::Zstd =>
+				self.read_bounded(zstd::stream::read::Decoder::new(content)?),
+			#[cfg(not(feature = "compress"))]
+			_ => Err(self.unsupported())
+		}
+	}
+
+	/// Read the given decoder to its end, refusing to produce more than
+	/// [`MAX_DECOMPRESSED_BYTES`].
+	///
+	/// # Arguments
+	///
+	/// * `decoder` - The decoder.
+	///
+	/// # Returns
+	///
+	/// The decompressed content.
+	///
+	/// # Errors
+	///
+	/// * If the content is corrupt, an error is returned.
+	/// * If the content decompresses to more than
+	///   [`MAX_DECOMPRESSED_BYTES`], an [`ErrorKind::InvalidData`] is
+	///   returned.
+	#[cfg(feature = "compress")]
+	fn read_bounded(self, decoder: impl Read) -> Result<Vec<u8>, io::Error>
+	{
+		// Read one byte past the limit, to tell a stream that exactly fills
+		// the limit from one that overflows it.
+		let mut decompressed = Vec::new();
+		decoder
+			.take(MAX_DECOMPRESSED_BYTES + 1)
+			.read_to_end(&mut decompressed)?;
+		if decompressed.len() as u64 > MAX_DECOMPRESSED_BYTES
+		{
+			return Err(io::Error::new(
+				ErrorKind::InvalidData,
+				format!(
+					"{:?} content exceeds {} bytes when decompressed",
+					self,
+					MAX_DECOMPRESSED_BYTES
+				)
+			))
+		}
+		Ok(decompressed)
+	}
+
+	/// Answer the error for a compression that this build cannot handle.
+	///
+	/// # Returns
+	///
+	/// An [`ErrorKind::Unsupported`] error.
+	#[cfg(not(feature = "compress"))]
+	fn unsupported(self) -> io::Error
+	{
+		io::Error::new(
+			ErrorKind::Unsupported,
+			format!("{:?} compression requires the compress feature", self)
+		)
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Patterns.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A simple glob pattern for querying a [`Dictionary`]. The following syntax
+/// is supported:
+///
+/// * `*` - Any sequence of characters, including the empty sequence.
+/// * `?` - Any single character.
+/// * `[abc]` - Any one of the enclosed characters. Ranges, such as `[a-m]`, are
+///   permitted, and a leading `!` or `^` negates the class.
+/// * `\c` - The character `c` itself, even if it is special.
+///
+/// Any other character matches only itself.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[must_use]
+pub struct Pattern
+{
+	/// The tokens of the pattern.
+	tokens: Vec<Token>,
+
+	/// The literal prefix of the pattern, i.e., the text preceding the first
+	/// wildcard. Every matching word starts with this prefix.
+	prefix: String
+}
+
+/// A token of a [`Pattern`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token
+{
+	/// A literal character.
+	Literal(char),
+
+	/// Any single character.
+	AnyChar,
+
+	/// Any sequence of characters.
+	AnySequence,
+
+	/// A character class.
+	Class
+	{
+		/// Whether the class is negated.
+		negated: bool,
+
+		/// The inclusive character ranges of the class.
+		ranges: Vec<(char, char)>
+	}
+}
+
+impl Token
+{
+	/// Check whether the token matches the given character. A
+	/// [sequence](Token::AnySequence) never matches a single character, as
+	/// [`Pattern::matches`] handles it specially.
+	///
+	/// # Arguments
+	///
+	/// * `c` - The character.
+	///
+	/// # Returns
+	///
+	/// `true` if the token matches the character, `false` otherwise.
+	#[must_use]
+	fn matches(&self, c: char) -> bool
+	{
+		match self
+		{
+			Token::Literal(l) => *l == c,
+			Token::AnyChar => true,
+			Token::AnySequence => false,
+			Token::Class { negated, ranges } =>
+			{
+				ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&c)) != *negated
+			}
+		}
+	}
+}
+
+impl Pattern
+{
+	/// Parse a pattern.
+	///
+	/// # Arguments
+	///
+	/// * `pattern` - The text of the pattern.
+	///
+	/// # Returns
+	///
+	/// The parsed pattern.
+	///
+	/// # Errors
+	///
+	/// If the pattern contains an unterminated character class or a trailing
+	/// escape, an [`ErrorKind::InvalidData`] is returned.
+	pub fn parse(pattern: &str) -> Result<Self, io::Error>
+	{
+		let invalid = |reason: &str| io::Error::new(
+			ErrorKind::InvalidData,
+			format!("{}: {}", reason, pattern)
+		);
+		let mut tokens = Vec::new();
+		let mut chars = pattern.chars().peekable();
+		while let Some(c) = chars.next()
+		{
+			let token = match c
+			{
+				'*' => Token::AnySequence,
+				'?' => Token::AnyChar,
+				'\\' => match chars.next()
+				{
+					Some(c) => Token::Literal(c),
+					None => return Err(invalid("trailing escape"))
+				},
+				'[' =>
+				{
+					let negated = chars.next_if(|c| *c == '!' || *c == '^')
+						.is_some();
+					let mut ranges = Vec::new();
+					loop
+					{
+						let lo = match chars.next()
+						{
+							// A leading `]` is a member, not a terminator.
+							Some(']') if !ranges.is_empty() => break,
+							Some(c) => c,
+							None =>
+							{
+								return Err(invalid("unterminated class"))
+							}
+						};
+						let hi = match chars.next_if_eq(&'-')
+						{
+							Some(_) => match chars.next()
+							{
+								Some(']') | None =>
+								{
+									return Err(invalid("unterminated range"))
+								},
+								Some(hi) => hi
+							},
+							None => lo
+						};
+						ranges.push((lo, hi));
+					}
+					Token::Class { negated, ranges }
+				},
+				c => Token::Literal(c)
+			};
+			tokens.push(token);
+		}
+		let prefix = tokens.iter()
+			.map_while(|token| match token
+			{
+				Token::Literal(c) => Some(*c),
+				_ => None
+			})
+			.collect();
+		Ok(Self { tokens, prefix })
 	}
 
-	/// Serialize the dictionary to the given file. The dictionary is serialized
-	/// in [`bincode`](bincode) format.
+	/// Check whether the given word matches the pattern in its entirety.
 	///
 	/// # Arguments
 	///
-	/// * `path` - The target file.
+	/// * `word` - The word.
 	///
-	/// # Errors
+	/// # Returns
 	///
-	/// * If the file cannot be opened or written, an error is returned.
-	/// * If the file contains invalid data, an [`ErrKind::InvalidData`] is
-	///   returned.
-	pub fn serialize_to_file<T: AsRef<Path>>(
-		&self,
-		path: T
-	) -> Result<(), io::Error>
+	/// `true` if the word matches the pattern, `false` otherwise.
+	#[must_use]
+	pub fn matches(&self, word: &str) -> bool
 	{
-		let mut file = File::create(path)?;
-		let content = bincode::serialize(self)
-			.map_err(|_e| ErrorKind::InvalidData)?;
-		file.write_all(&content)?;
-		Ok(())
+		let chars = word.chars().collect::<Vec<_>>();
+		let tokens = &self.tokens;
+		let (mut t, mut c) = (0, 0);
+		// The position just after the most recent `*`, and the position in the
+		// word where it started to match, for backtracking.
+		let mut backtrack = None;
+		while c < chars.len()
+		{
+			if t < tokens.len() && tokens[t] == Token::AnySequence
+			{
+				t += 1;
+				backtrack = Some((t, c));
+			}
+			else if t < tokens.len() && tokens[t].matches(chars[c])
+			{
+				t += 1;
+				c += 1;
+			}
+			else if let Some((after_star, start)) = backtrack
+			{
+				// Let the most recent `*` absorb one more character.
+				t = after_star;
+				c = start + 1;
+				backtrack = Some((after_star, start + 1));
+			}
+			else
+			{
+				return false
+			}
+		}
+		tokens[t..].iter().all(|token| *token == Token::AnySequence)
+	}
+}
+
+impl FromStr for Pattern
+{
+	type Err = io::Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err>
+	{
+		Self::parse(s)
 	}
 }
 
@@ -229,8 +3340,29 @@ impl Dictionary
 #[cfg(test)]
 mod test
 {
-	use crate::dictionary::Dictionary;
-	use tempfile::NamedTempFile;
+	use std::{fs, io::{ErrorKind, Read}};
+
+	use crate::dictionary::{
+		normalize_word,
+		normalize_words,
+		Composition,
+		Compression,
+		Dictionary,
+		DictionaryCursor,
+		Header,
+		Inclusion,
+		Ingestion,
+		InputFormat,
+		Language,
+		LetterFilter,
+		Normalization,
+		Pattern,
+		Progress,
+		Source,
+		MAX_WORD_LEN,
+		MIN_WORD_LEN
+	};
+	use tempfile::{NamedTempFile, TempDir};
 
 	/// The path to the dictionary file.
 	#[inline]
@@ -240,10 +3372,219 @@ mod test
 		"dict/english.txt"
 	}
 
+	/// Test normalizing the words of a word list as they populate a
+	/// dictionary:
+	///
+	/// * [`Dictionary::populate_with`]
+	/// * [`Normalization::apply`]
+	#[test]
+	fn test_normalization()
+	{
+		let words = [
+			" Café ",
+			"O\u{2019}Clock",
+			"well-being",
+			"MP3",
+			"nai\u{308}ve",
+			""
+		];
+		let normalization = Normalization::default();
+		assert_eq!(normalization.apply(" Café "), Some("café".to_string()));
+		assert_eq!(normalization.apply("  "), None);
+		let mut dictionary = Dictionary::new();
+		dictionary.populate(&words);
+		assert_eq!(
+			dictionary.iter_prefix("").collect::<Vec<_>>(),
+			["café", "mp3", "naïve", "o\u{2019}clock", "well-being"]
+		);
+
+		let normalization = Normalization {
+			lowercase: false,
+			strip_diacritics: true,
+			remove_apostrophes: true,
+			remove_hyphens: true,
+			drop_digits: true
+		};
+		let mut dictionary = Dictionary::new();
+		let ingestion = dictionary.populate_with(&words, &normalization);
+		assert_eq!(
+			ingestion,
+			Ingestion { inserted: 4, duplicates: 0, rejected: 1 }
+		);
+		let ingestion = dictionary.populate_with(
+			&["cafe", "Cafe", "123", "'", " "],
+			&normalization
+		);
+		assert_eq!(
+			ingestion,
+			Ingestion { inserted: 1, duplicates: 1, rejected: 2 }
+		);
+		dictionary.exclude(["cafe"]);
+		assert_eq!(
+			dictionary.iter_prefix("").collect::<Vec<_>>(),
+			["Cafe", "OClock", "naive", "wellbeing"]
+		);
+	}
+
+	/// Test folding the words and lookups of a dictionary, so that lookups
+	/// ignore case and stray whitespace:
+	///
+	/// * [`Dictionary::set_folding`]
+	/// * [`Dictionary::contains`]
+	/// * [`Dictionary::contains_prefix`]
+	/// * [`Dictionary::contains_fragment`]
+	/// * [`Dictionary::iter_prefix`]
+	#[test]
+	fn test_folding()
+	{
+		let normalization = Normalization {
+			lowercase: false,
+			..Default::default()
+		};
+		let mut dictionary = Dictionary::new();
+		dictionary.populate_with(&["Hello", "World"], &normalization);
+		assert!(!dictionary.is_folding());
+		assert!(dictionary.contains("Hello"));
+		assert!(!dictionary.contains("hello"));
+		assert!(!dictionary.contains_prefix("wor"));
+
+		dictionary.set_folding(true);
+		assert!(dictionary.is_folding());
+		assert_eq!(dictionary.len(), 2);
+		for word in ["hello", "Hello", "HELLO ", "\thello\n"]
+		{
+			assert!(dictionary.contains(word), "{:?}", word);
+		}
+		assert!(dictionary.contains_prefix(" WOR"));
+		assert!(dictionary.contains_fragment("ELL"));
+		assert_eq!(dictionary.iter_prefix("H").collect::<Vec<_>>(), ["hello"]);
+
+		// Words stored later are folded, too.
+		dictionary.extend(["Truth "]);
+		dictionary.populate_with(&["TRUTH", "Quartz"], &normalization);
+		assert_eq!(
+			dictionary.iter_prefix("").collect::<Vec<_>>(),
+			["hello", "quartz", "truth", "world"]
+		);
+	}
+
+	/// Test suffix lookups, which consult the reversed index:
+	///
+	/// * [`Dictionary::contains_suffix`]
+	#[test]
+	fn test_contains_suffix()
+	{
+		let mut dictionary = ["truth", "naïve", "wordsmith"]
+			.into_iter()
+			.collect::<Dictionary>();
+		for suffix in ["", "h", "th", "ruth", "truth", "ïve", "smith"]
+		{
+			assert!(dictionary.contains_suffix(suffix), "{:?}", suffix);
+		}
+		for suffix in ["t", "tru", "atruth", "ive", "word"]
+		{
+			assert!(!dictionary.contains_suffix(suffix), "{:?}", suffix);
+		}
+
+		// The index follows changes to the words.
+		dictionary.extend(["quartz"]);
+		assert!(dictionary.contains_suffix("tz"));
+		dictionary.exclude(["truth"]);
+		assert!(!dictionary.contains_suffix("ruth"));
+		assert!(dictionary.contains_suffix("th"));
+		assert!(!Dictionary::new().contains_suffix(""));
+
+		// A folding dictionary folds the suffix, too.
+		dictionary.set_folding(true);
+		assert!(dictionary.contains_suffix("SMITH "));
+	}
+
+	/// Test the letter filter, which must never rule out a live prefix:
+	///
+	/// * [`Dictionary::letter_filter`]
+	/// * [`LetterFilter::signature`]
+	/// * [`LetterFilter::may_begin`]
+	/// * [`DictionaryCursor::with_filter`]
+	#[test]
+	fn test_letter_filter()
+	{
+		assert_eq!(
+			LetterFilter::signature("truth"),
+			LetterFilter::signature("tr")
+				.wrapping_add(LetterFilter::signature("uth"))
+		);
+		assert_eq!(
+			LetterFilter::signature("ab"),
+			LetterFilter::signature("ba")
+		);
+
+		let dictionary = Dictionary::read_from_file(test_path()).unwrap();
+		let filter = dictionary.letter_filter();
+		assert!(filter.may_begin(0));
+		for word in dictionary.iter()
+		{
+			let mut signature = 0u64;
+			for c in word.chars()
+			{
+				signature = signature
+					.wrapping_add(LetterFilter::signature(&c.to_string()));
+				assert!(filter.may_begin(signature), "{:?}", word);
+			}
+		}
+		let rejected = ["qqqq", "zxzx", "jjjj", "xqzj"]
+			.into_iter()
+			.filter(|text| !filter.may_begin(LetterFilter::signature(text)))
+			.count();
+		assert!(rejected > 0);
+
+		// A filtering cursor agrees with the dictionary's own probes.
+		let mut cursor = dictionary.cursor().with_filter(filter);
+		for fragment in ["un", "th", "ink", "able", "s", "qq", "x"]
+		{
+			let is_live = cursor.descend(fragment);
+			assert_eq!(is_live, dictionary.contains_prefix(cursor.prefix()));
+			assert_eq!(cursor.is_word(), dictionary.contains(cursor.prefix()));
+		}
+		while cursor.ascend() {}
+		assert!(cursor.descend("un"));
+		assert!(cursor.is_live());
+	}
+
+	/// Test subsetting a dictionary by word length:
+	///
+	/// * [`Dictionary::subset`]
+	#[test]
+	fn test_subset()
+	{
+		// Four long fragments can spell a long word, so only a word longer
+		// than any four fragments can spell is left out.
+		let long = "internationalizations";
+		let longest = "q".repeat(MAX_WORD_LEN + 1);
+		let dictionary = ["a", "is", "truth", "naïve", long, &longest]
+			.into_iter()
+			.collect::<Dictionary>();
+		let subset = dictionary.subset(MIN_WORD_LEN, MAX_WORD_LEN);
+		assert_eq!(
+			subset.iter_prefix("").collect::<Vec<_>>(),
+			["internationalizations", "is", "naïve", "truth"]
+		);
+		assert!(!subset.contains_prefix("qq"));
+		assert_eq!(dictionary.len(), 6);
+
+		// Lengths count characters, not bytes.
+		let subset = dictionary.subset(5, 5);
+		assert_eq!(
+			subset.iter_prefix("").collect::<Vec<_>>(),
+			["naïve", "truth"]
+		);
+		assert!(dictionary.subset(6, 5).is_empty());
+	}
+
 	/// Test basic functionality of [`Dictionary`]:
 	///
 	/// * [`Dictionary::empty`]
 	/// * [`Dictionary::is_empty`]
+	/// * [`Dictionary::len`]
 	/// * [`Dictionary::populate`]
 	/// * [`Dictionary::contains`]
 	#[test]
@@ -254,6 +3595,7 @@ mod test
 		assert!(!dictionary.contains("hello"));
 		assert!(!dictionary.contains("world"));
 		dictionary.populate(&["hello", "world"]);
+		assert_eq!(dictionary.len(), 2);
 		assert!(dictionary.contains("hello"));
 		assert!(dictionary.contains("world"));
 	}
@@ -272,6 +3614,373 @@ mod test
 		assert!(dictionary.contains("world"));
 	}
 
+	/// Test reporting progress while reading a dictionary, and cancelling it:
+	///
+	/// * [`Dictionary::read_from_file_with_progress`]
+	/// * [`Progress`]
+	#[test]
+	fn test_read_from_file_with_progress()
+	{
+		let progress = Progress::default();
+		let dictionary =
+			Dictionary::read_from_file_with_progress(test_path(), &progress)
+				.unwrap();
+		let size = fs::metadata(test_path()).unwrap().len();
+		assert_eq!(progress.total_bytes(), size);
+		assert_eq!(progress.bytes_read(), size);
+		assert_eq!(progress.words_inserted(), dictionary.len());
+
+		let progress = Progress::default();
+		progress.cancel();
+		let error =
+			Dictionary::read_from_file_with_progress(test_path(), &progress)
+				.unwrap_err();
+		assert_eq!(error.kind(), ErrorKind::Interrupted);
+		assert_eq!(progress.words_inserted(), 0);
+	}
+
+	/// Test reading a dictionary from word lists of every format:
+	///
+	/// * [`Dictionary::read_from_file`]
+	/// * [`Dictionary::read_from_file_as`]
+	/// * [`Dictionary::install`]
+	/// * [`InputFormat::detect`]
+	#[test]
+	fn test_input_formats()
+	{
+		let directory = TempDir::new().unwrap();
+		let write = |name: &str, content: &[u8]| {
+			let path = directory.path().join(name);
+			fs::write(&path, content).unwrap();
+			path
+		};
+		let expected = ["café", "hello", "world"]
+			.into_iter()
+			.collect::<Dictionary>();
+
+		let text = write(
+			"words.txt",
+			"\u{feff}Hello\n\nworld\r\ncafé\n".as_bytes()
+		);
+		assert_eq!(InputFormat::detect(&text), InputFormat::Text);
+		assert_eq!(Dictionary::read_from_file(&text).unwrap(), expected);
+
+		let json = write(
+			"words.json",
+			r#"["Hello", "world", " café "]"#.as_bytes()
+		);
+		assert_eq!(InputFormat::detect(&json), InputFormat::Json);
+		assert_eq!(Dictionary::read_from_file(&json).unwrap(), expected);
+		let bad = write("bad.json", br#"{"words": ["hello"]}"#);
+		assert_eq!(
+			Dictionary::read_from_file(bad).unwrap_err().kind(),
+			ErrorKind::InvalidData
+		);
+
+		// With a header, the words come from the named column.
+		let csv = write(
+			"words.csv",
+			"rank,Word\n1,hello\n2,\"world\"\n3,\"café\"\n4\n".as_bytes()
+		);
+		assert_eq!(InputFormat::detect(&csv), InputFormat::Csv);
+		assert_eq!(Dictionary::read_from_file(&csv).unwrap(), expected);
+		let csv = write(
+			"bare.csv",
+			"hello,1\nworld,2\ncafé,\"3,4\"\n".as_bytes()
+		);
+		assert_eq!(Dictionary::read_from_file(&csv).unwrap(), expected);
+
+		let hunspell = write(
+			"words.dic",
+			"3\nhello/MS\nworld/M po:noun\ncafé\n\tcomment\n".as_bytes()
+		);
+		assert_eq!(InputFormat::detect(&hunspell), InputFormat::Hunspell);
+		assert_eq!(Dictionary::read_from_file(&hunspell).unwrap(), expected);
+
+		// The format can be given, regardless of the extension.
+		let export = write(
+			"words.export",
+			b"word\nhello\nworld\ncaf\xc3\xa9\n"
+		);
+		assert_eq!(InputFormat::detect(&export), InputFormat::Text);
+		let dictionary = Dictionary::read_from_file_as(
+			&export,
+			InputFormat::Csv,
+			&Normalization::default(),
+			&Progress::default()
+		)
+		.unwrap();
+		assert_eq!(dictionary, expected);
+
+		// SCOWL word lists are in ISO-8859-1, and only the lists of words
+		// belong to the dictionary.
+		let scowl = directory.path().join("final");
+		fs::create_dir(&scowl).unwrap();
+		fs::write(scowl.join("english-words.10"), b"hello\nworld\n").unwrap();
+		fs::write(scowl.join("american-words.35"), b"caf\xe9\n").unwrap();
+		fs::write(scowl.join("english-abbreviations.10"), b"etc\n").unwrap();
+		assert_eq!(InputFormat::detect(&scowl), InputFormat::Scowl);
+		assert_eq!(Dictionary::read_from_file(&scowl).unwrap(), expected);
+		let list = scowl.join("american-words.35");
+		assert_eq!(InputFormat::detect(&list), InputFormat::Scowl);
+		assert_eq!(Dictionary::read_from_file(&list).unwrap().len(), 1);
+		let empty = directory.path().join("empty");
+		fs::create_dir(&empty).unwrap();
+		assert_eq!(
+			Dictionary::read_from_file(&empty).unwrap_err().kind(),
+			ErrorKind::NotFound
+		);
+
+		// Installing writes both dictionary files, which open identically.
+		let installed = directory.path().join("installed");
+		expected.install(&installed, "test").unwrap();
+		assert_eq!(
+			fs::read_to_string(installed.join("test.txt")).unwrap(),
+			"café\nhello\nworld\n"
+		);
+		let binary =
+			Dictionary::deserialize_from_file(installed.join("test.dict"))
+				.unwrap();
+		assert_eq!(binary, expected);
+	}
+
+	/// Test that the header of a binary dictionary records its number of
+	/// words, and that a binary dictionary without a header remains readable:
+	///
+	/// * [`Header::read_from_file`]
+	/// * [`Dictionary::serialize_to_file`]
+	/// * [`Dictionary::deserialize_from_bytes`]
+	/// * [`Progress::duplicates`]
+	/// * [`Progress::rejected`]
+	#[test]
+	fn test_header()
+	{
+		let directory = TempDir::new().unwrap();
+		let txt_path = directory.path().join("words.txt");
+		fs::write(&txt_path, "cross\nword\nCross\n\n4u\ncrossword\n")
+			.unwrap();
+		let progress = Progress::default();
+		let dictionary = Dictionary::read_from_file_as(
+			&txt_path,
+			InputFormat::Text,
+			&Normalization { drop_digits: true, ..Default::default() },
+			&progress
+		)
+		.unwrap();
+		assert_eq!(dictionary.len(), 3);
+		assert_eq!(progress.duplicates(), 1);
+		assert_eq!(progress.rejected(), 1);
+
+		let dict_path = directory.path().join("words.dict");
+		dictionary.serialize_to_file(&dict_path).unwrap();
+		let header = Header::read_from_file(&dict_path).unwrap().unwrap();
+		assert_eq!(header.words, 3);
+		assert_eq!(header.compression, Compression::None);
+		let bytes = fs::read(&dict_path).unwrap();
+		assert_eq!(
+			Dictionary::deserialize_from_bytes(&bytes).unwrap(),
+			dictionary
+		);
+
+		// A header that disagrees with the content, or is truncated, betrays
+		// corruption.
+		let mut corrupt = bytes.clone();
+		corrupt[8] += 1;
+		assert_eq!(
+			Dictionary::deserialize_from_bytes(&corrupt).unwrap_err().kind(),
+			ErrorKind::InvalidData
+		);
+		assert_eq!(
+			Dictionary::deserialize_from_bytes(&bytes[..Header::LEN - 1])
+				.unwrap_err()
+				.kind(),
+			ErrorKind::InvalidData
+		);
+
+		// A binary dictionary without a header has no header to read.
+		let legacy = &bytes[Header::LEN..];
+		fs::write(&dict_path, legacy).unwrap();
+		assert_eq!(Header::read_from_file(&dict_path).unwrap(), None);
+		assert_eq!(
+			Dictionary::deserialize_from_file(&dict_path).unwrap(),
+			dictionary
+		);
+	}
+
+	/// Test excluding words from a dictionary:
+	///
+	/// * [`Dictionary::exclude`]
+	/// * [`Dictionary::exclude_from_file`]
+	/// * [`Dictionary::open`]
+	#[test]
+	fn test_exclude()
+	{
+		let mut dictionary = Dictionary::new();
+		dictionary.populate(&["truth", "trust", "is"]);
+		assert_eq!(dictionary.exclude(["Truth", "absent"]), 1);
+		assert!(!dictionary.contains("truth"));
+		assert_eq!(dictionary.len(), 2);
+
+		let dir = TempDir::new().unwrap();
+		fs::write(dir.path().join("test.txt"), "truth\ntrust\nis\n").unwrap();
+		fs::write(dir.path().join("test.exclude.txt"), "trust\n\n").unwrap();
+		let dictionary = Dictionary::open(dir.path(), "test").unwrap();
+		assert!(!dictionary.contains("trust"));
+		assert_eq!(dictionary.len(), 2);
+		// The binary dictionary doesn't reflect the exclusion list.
+		let dictionary =
+			Dictionary::deserialize_from_file(dir.path().join("test.dict"))
+				.unwrap();
+		assert!(dictionary.contains("trust"));
+		let dictionary = Dictionary::open(dir.path(), "test").unwrap();
+		assert!(!dictionary.contains("trust"));
+	}
+
+	/// Test composing a dictionary from tagged sources:
+	///
+	/// * [`Dictionary::include`]
+	/// * [`Dictionary::source`]
+	/// * [`Dictionary::retain_sources`]
+	/// * [`Composition::apply`]
+	#[test]
+	fn test_include()
+	{
+		let mut dictionary = Dictionary::new();
+		dictionary.populate(&["truth", "trust"]);
+		assert_eq!(dictionary.include(["Trust", "tryst"], Source::Custom), 1);
+		assert_eq!(dictionary.include(["tryst", "is"], Source::Downloaded), 1);
+		assert_eq!(dictionary.source("truth"), Source::Base);
+		assert_eq!(dictionary.source("trust"), Source::Base);
+		assert_eq!(dictionary.source("tryst"), Source::Custom);
+		assert_eq!(dictionary.source("is"), Source::Downloaded);
+		assert_eq!(dictionary.source("absent"), Source::Base);
+		// A subset keeps the sources of its words.
+		let subset = dictionary.subset(3, 5);
+		assert_eq!(subset.source("tryst"), Source::Custom);
+		assert_eq!(subset.source("is"), Source::Base);
+		// Excluding a word forgets its source.
+		dictionary.exclude(["tryst"]);
+		dictionary.populate(&["tryst"]);
+		assert_eq!(dictionary.source("tryst"), Source::Base);
+		assert_eq!(dictionary.retain_sources(&[Source::Downloaded]), 3);
+		assert_eq!(dictionary.iter().collect::<Vec<_>>(), ["is"]);
+
+		assert_eq!(
+			"tryst.txt".parse::<Inclusion>().unwrap(),
+			Inclusion { source: Source::Custom, path: "tryst.txt".into() }
+		);
+		assert_eq!(
+			"Downloaded=a=b.txt".parse::<Inclusion>().unwrap(),
+			Inclusion { source: Source::Downloaded, path: "a=b.txt".into() }
+		);
+		assert_eq!(
+			"a=b.txt".parse::<Inclusion>().unwrap(),
+			Inclusion { source: Source::Custom, path: "a=b.txt".into() }
+		);
+		assert!("custom=".parse::<Inclusion>().is_err());
+
+		let dir = TempDir::new().unwrap();
+		let custom = dir.path().join("custom.txt");
+		fs::write(&custom, "tryst\ntruss\n").unwrap();
+		let exclude = dir.path().join("exclude.txt");
+		fs::write(&exclude, "truss\n").unwrap();
+		let mut dictionary = Dictionary::new();
+		dictionary.populate(&["truth", "trust"]);
+		let composition = Composition {
+			include: vec![Inclusion { source: Source::Custom, path: custom }],
+			exclude: vec![exclude],
+			sources: vec![Source::Custom]
+		};
+		composition.apply(&mut dictionary).unwrap();
+		assert_eq!(dictionary.iter().collect::<Vec<_>>(), ["tryst"]);
+		let composition = Composition {
+			include: vec!["missing.txt".parse().unwrap()],
+			..Default::default()
+		};
+		let e = composition.apply(&mut dictionary).unwrap_err();
+		assert!(e.to_string().contains("inclusion list: missing.txt"), "{}", e);
+	}
+
+	/// Test checking and suggesting fragments:
+	///
+	/// * [`Dictionary::contains_fragment`]
+	/// * [`Dictionary::suggest_fragments`]
+	#[test]
+	fn test_fragments()
+	{
+		let dictionary = ["truss", "trust", "trusty", "tryst", "distrust"]
+			.into_iter()
+			.collect::<Dictionary>();
+		assert!(dictionary.contains_fragment(""));
+		assert!(dictionary.contains_fragment("tru"));
+		assert!(dictionary.contains_fragment("sty"));
+		assert!(dictionary.contains_fragment("istr"));
+		assert!(!dictionary.contains_fragment("zz"));
+		assert_eq!(
+			dictionary.suggest_fragments("tr", 4, 3),
+			["tru", "trus", "try"]
+		);
+		assert_eq!(dictionary.suggest_fragments("st", 3, 10), ["str", "sty"]);
+		assert!(dictionary.suggest_fragments("zz", 4, 10).is_empty());
+	}
+
+	/// Test [`Dictionary::available`].
+	#[test]
+	fn test_available()
+	{
+		let dir = TempDir::new().unwrap();
+		for file in ["b.txt", "b.dict", "a.dict", "a.exclude.txt", "notes.md"]
+		{
+			fs::write(dir.path().join(file), "").unwrap();
+		}
+		fs::create_dir(dir.path().join("c.txt")).unwrap();
+		assert_eq!(Dictionary::available(dir.path()).unwrap(), ["a", "b"]);
+		assert!(Dictionary::available(dir.path().join("missing")).is_err());
+	}
+
+	/// Test [`Dictionary::embedded`], which must agree with the English
+	/// dictionary from which the embedded list was made.
+	#[cfg(feature = "embedded-dict")]
+	#[test]
+	fn test_embedded()
+	{
+		let embedded = Dictionary::embedded();
+		let english = Dictionary::read_from_file("dict/english.txt").unwrap();
+		assert_eq!(embedded.len(), english.len());
+		assert_eq!(embedded.fingerprint(), english.fingerprint());
+		assert!(embedded.contains("quarterback"));
+	}
+
+	/// Test [`Dictionary::difference`].
+	#[test]
+	fn test_difference()
+	{
+		let a = ["truth", "trust", "is", "tryst"]
+			.into_iter()
+			.collect::<Dictionary>();
+		let b = ["trust", "truss", "is"].into_iter().collect::<Dictionary>();
+		assert_eq!(a.difference(&b).collect::<Vec<_>>(), ["truth", "tryst"]);
+		assert_eq!(b.difference(&a).collect::<Vec<_>>(), ["truss"]);
+		assert_eq!(a.difference(&a).count(), 0);
+		assert_eq!(Dictionary::new().difference(&a).count(), 0);
+	}
+
+	/// Test [`Dictionary::fingerprint`].
+	#[test]
+	fn test_fingerprint()
+	{
+		let forward =
+			["truth", "trust", "is"].into_iter().collect::<Dictionary>();
+		let backward =
+			["is", "trust", "truth"].into_iter().collect::<Dictionary>();
+		assert_eq!(forward.fingerprint(), backward.fingerprint());
+		// The empty dictionary hashes to the offset basis.
+		assert_eq!(Dictionary::new().fingerprint(), 0xcbf2_9ce4_8422_2325);
+		let mut other = forward.clone();
+		other.exclude(["is"]);
+		assert_ne!(forward.fingerprint(), other.fingerprint());
+	}
+
 	/// Test serializing and deserializing a dictionary:
 	///
 	/// * [`Dictionary::serialize_to_file`]
@@ -284,5 +3993,389 @@ mod test
 		dictionary.serialize_to_file(file.path()).unwrap();
 		let deserialized = Dictionary::deserialize_from_file(file.path()).unwrap();
 		assert_eq!(dictionary, deserialized);
+
+		// Serializing replaces the file rather than rewriting it in place, so
+		// an open handle still reads the previous content.
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("words.dict");
+		let small = ["cross", "word"].into_iter().collect::<Dictionary>();
+		small.serialize_to_file(&path).unwrap();
+		let previous = fs::read(&path).unwrap();
+		let mut open = fs::File::open(&path).unwrap();
+		dictionary.serialize_to_file(&path).unwrap();
+		let mut content = Vec::new();
+		open.read_to_end(&mut content).unwrap();
+		if cfg!(unix)
+		{
+			assert_eq!(content, previous);
+		}
+		let deserialized = Dictionary::deserialize_from_file(&path).unwrap();
+		assert_eq!(deserialized, dictionary);
+		assert!(!path.with_extension("tmp").exists());
+	}
+
+	/// Test compressing and decompressing a dictionary:
+	///
+	/// * [`Dictionary::serialize_to_file_with`]
+	/// * [`Dictionary::deserialize_from_file`]
+	#[test]
+	fn test_compression()
+	{
+		let dictionary =
+			Dictionary::read_from_file(test_path()).unwrap();
+		let file = NamedTempFile::new().unwrap();
+		dictionary.serialize_to_file(file.path()).unwrap();
+		let uncompressed = std::fs::metadata(file.path()).unwrap().len();
+		for compression in [Compression::Gzip, Compression::Zstd]
+		{
+			let file = NamedTempFile::new().unwrap();
+			let result =
+				dictionary.serialize_to_file_with(file.path(), compression);
+			if cfg!(feature = "compress")
+			{
+				result.unwrap();
+				let content = std::fs::read(file.path()).unwrap();
+				let header =
+					Header::read_from_file(file.path()).unwrap().unwrap();
+				assert_eq!(header.compression, compression);
+				assert_eq!(header.words, dictionary.len() as u64);
+				assert_eq!(
+					Compression::detect(&content[Header::LEN..]),
+					compression
+				);
+				assert!((content.len() as u64) < uncompressed);
+				let deserialized =
+					Dictionary::deserialize_from_file(file.path()).unwrap();
+				assert_eq!(dictionary, deserialized);
+			}
+			else
+			{
+				assert_eq!(
+					result.unwrap_err().kind(),
+					std::io::ErrorKind::Unsupported
+				);
+			}
+		}
+	}
+
+	/// Test that corrupt binary dictionaries are refused rather than causing a
+	/// panic or a huge allocation:
+	///
+	/// * [`Dictionary::deserialize_from_bytes`]
+	#[test]
+	fn test_deserialize_corrupt()
+	{
+		let mut dictionary = Dictionary::new();
+		dictionary.populate(&["cross", "crossword", "word"]);
+		let bytes = bincode::serialize(&dictionary).unwrap();
+		let deserialized = Dictionary::deserialize_from_bytes(&bytes).unwrap();
+		assert_eq!(deserialized, dictionary);
+		for len in 0..bytes.len()
+		{
+			assert_eq!(
+				Dictionary::deserialize_from_bytes(&bytes[..len])
+					.unwrap_err()
+					.kind(),
+				ErrorKind::InvalidData,
+				"{}",
+				len
+			);
+		}
+		for index in 0..bytes.len()
+		{
+			let mut bytes = bytes.clone();
+			bytes[index] ^= 0xff;
+			let _ = Dictionary::deserialize_from_bytes(&bytes);
+		}
+		// Claim an absurd number of words, and then an absurdly long word.
+		let mut bytes = u64::MAX.to_le_bytes().to_vec();
+		bytes.extend_from_slice(&u64::MAX.to_le_bytes());
+		assert!(Dictionary::deserialize_from_bytes(&bytes).is_err());
+		let mut bytes = 1u64.to_le_bytes().to_vec();
+		bytes.extend_from_slice(&(1u64 << 40).to_le_bytes());
+		assert!(Dictionary::deserialize_from_bytes(&bytes).is_err());
+		// Garbage behind the magic bytes of each compression.
+		for magic in [&[0x1f, 0x8b][..], &[0x28, 0xb5, 0x2f, 0xfd]]
+		{
+			let mut bytes = magic.to_vec();
+			bytes.extend_from_slice(&[0xff; 16]);
+			assert!(Dictionary::deserialize_from_bytes(&bytes).is_err());
+		}
+		// An uncompressed dictionary whose word count begins with the magic
+		// bytes of gzip, with or without the `compress` feature.
+		let words = (0..0x8b1f)
+			.map(|index| format!("w{}", index))
+			.collect::<Vec<_>>();
+		let dictionary = words.iter()
+			.map(String::as_str)
+			.collect::<Dictionary>();
+		let bytes = bincode::serialize(&dictionary).unwrap();
+		assert_eq!(Compression::detect(&bytes), Compression::Gzip);
+		let deserialized = Dictionary::deserialize_from_bytes(&bytes).unwrap();
+		assert_eq!(deserialized, dictionary);
+	}
+
+	/// Test enumerating a dictionary:
+	///
+	/// * [`Dictionary::iter`]
+	/// * [`Dictionary::len`]
+	/// * [`Dictionary::from_iter`]
+	#[test]
+	fn test_iter()
+	{
+		let dictionary = Dictionary::read_from_file(test_path()).unwrap();
+		assert_eq!(dictionary.iter().count(), dictionary.len());
+		assert!(dictionary.iter().all(|word| dictionary.contains(word)));
+		let copy = dictionary.iter().collect::<Dictionary>();
+		assert_eq!(copy, dictionary);
+		// Iteration order is deterministic.
+		assert!(dictionary.iter().eq(copy.iter()));
+	}
+
+	/// Test querying a dictionary by prefix:
+	///
+	/// * [`Dictionary::iter_prefix`]
+	#[test]
+	fn test_iter_prefix()
+	{
+		let mut dictionary = Dictionary::new();
+		dictionary.populate(&["truth", "truths", "trust", "this", "is"]);
+		assert_eq!(
+			dictionary.iter_prefix("tru").collect::<Vec<_>>(),
+			vec!["trust", "truth", "truths"]
+		);
+		assert_eq!(dictionary.iter_prefix("truths").collect::<Vec<_>>(), [
+			"truths"
+		]);
+		assert_eq!(dictionary.iter_prefix("x").count(), 0);
+		assert_eq!(dictionary.iter_prefix("truthsx").count(), 0);
+		assert_eq!(dictionary.iter_prefix("").count(), 5);
+
+		// Changing the words discards the index.
+		dictionary.populate(&["trunk"]);
+		assert_eq!(dictionary.iter_prefix("trun").collect::<Vec<_>>(), [
+			"trunk"
+		]);
+		assert_eq!(dictionary.exclude(["truth"]), 1);
+		assert_eq!(
+			dictionary.iter_prefix("tru").collect::<Vec<_>>(),
+			vec!["trunk", "trust", "truths"]
+		);
+
+		// Every prefix answers exactly the words that start with it, in sorted
+		// order, however the dictionary happens to iterate.
+		let dictionary = Dictionary::read_from_file(test_path()).unwrap();
+		for prefix in ["", "a", "qu", "cross", "zz", "é", "unthinkabl"]
+		{
+			let mut expected = dictionary.iter()
+				.filter(|word| word.starts_with(prefix))
+				.collect::<Vec<_>>();
+			expected.sort_unstable();
+			assert_eq!(
+				dictionary.iter_prefix(prefix).collect::<Vec<_>>(),
+				expected,
+				"{}",
+				prefix
+			);
+		}
+	}
+
+	/// Test searching a dictionary for near matches:
+	///
+	/// * [`Dictionary::search_levenshtein`]
+	#[test]
+	fn test_search_levenshtein()
+	{
+		let mut dictionary = Dictionary::new();
+		dictionary.populate(&["truth", "truths", "trust", "this", "is"]);
+		assert_eq!(dictionary.search_levenshtein("truht", 2), [
+			("trust", 1),
+			("truth", 2),
+			("truths", 2)
+		]);
+		assert_eq!(dictionary.search_levenshtein("truth", 1), [
+			("truth", 0),
+			("truths", 1)
+		]);
+		assert_eq!(dictionary.search_levenshtein("thus", 1), [("this", 1)]);
+		assert!(dictionary.search_levenshtein("xyzzy", 2).is_empty());
+		assert!(Dictionary::new().search_levenshtein("truth", 2).is_empty());
+
+		// The search agrees with the edit distance computed word by word.
+		fn distance(a: &str, b: &str) -> usize
+		{
+			let b = b.chars().collect::<Vec<_>>();
+			let mut row = (0..=b.len()).collect::<Vec<_>>();
+			for (i, x) in a.chars().enumerate()
+			{
+				let mut next = vec![i + 1];
+				for (j, &y) in b.iter().enumerate()
+				{
+					next.push(
+						(row[j + 1] + 1)
+							.min(next[j] + 1)
+							.min(row[j] + usize::from(x != y))
+					);
+				}
+				row = next;
+			}
+			row[b.len()]
+		}
+		let dictionary = Dictionary::read_from_file(test_path()).unwrap();
+		for word in ["cross", "qwerty", "truthfuly", "unthinkabel", "a"]
+		{
+			let mut expected = dictionary.iter()
+				.map(|candidate| (candidate, distance(candidate, word)))
+				.filter(|&(_, d)| d <= 2)
+				.collect::<Vec<_>>();
+			expected.sort_unstable_by_key(|&(candidate, d)| (d, candidate));
+			assert_eq!(
+				dictionary.search_levenshtein(word, 2),
+				expected,
+				"{}",
+				word
+			);
+		}
+	}
+
+	/// Test walking a dictionary fragment by fragment:
+	///
+	/// * [`Dictionary::cursor`]
+	/// * [`DictionaryCursor::descend`]
+	/// * [`DictionaryCursor::ascend`]
+	#[test]
+	fn test_cursor()
+	{
+		let mut dictionary = Dictionary::new();
+		dictionary.populate(&["cross", "crossword", "word"]);
+		let mut cursor: DictionaryCursor = dictionary.cursor();
+		assert!(cursor.is_live());
+		assert!(!cursor.is_word());
+		assert!(cursor.descend("cr"));
+		assert!(cursor.descend("oss"));
+		assert!(cursor.is_word());
+		assert!(cursor.descend("wo"));
+		assert!(!cursor.is_word());
+		assert!(!cursor.descend("x"));
+		assert!(!cursor.descend("rd"));
+		assert_eq!(cursor.prefix(), "crosswoxrd");
+		assert_eq!(cursor.char_count(), 10);
+		assert_eq!(cursor.depth(), 5);
+		assert!(!cursor.is_word());
+		assert!(cursor.ascend());
+		assert!(cursor.ascend());
+		assert!(cursor.descend("rd"));
+		assert!(cursor.is_word());
+		for _ in 0..4
+		{
+			assert!(cursor.ascend());
+		}
+		assert!(!cursor.ascend());
+		assert_eq!(cursor.prefix(), "");
+		assert_eq!(cursor.char_count(), 0);
+		assert!(cursor.is_live());
+
+		// The cursor agrees with the dictionary's own probes.
+		let dictionary = Dictionary::read_from_file(test_path()).unwrap();
+		let mut cursor = dictionary.cursor();
+		for fragment in ["un", "th", "ink", "able", "s"]
+		{
+			let is_live = cursor.descend(fragment);
+			assert_eq!(is_live, dictionary.contains_prefix(cursor.prefix()));
+			assert_eq!(cursor.is_word(), dictionary.contains(cursor.prefix()));
+		}
+		assert_eq!(cursor.depth(), 5);
+	}
+
+	/// Test normalizing a raw word list:
+	///
+	/// * [`normalize_words`]
+	#[test]
+	fn test_normalize_words()
+	{
+		let raw = "Truth\ntruth\n  trust \n\nAOL\ncafé\ndon't\nx-ray\nis\r\n";
+		assert_eq!(
+			normalize_words(raw.lines(), Language::English),
+			vec!["aol", "is", "trust", "truth"]
+		);
+		assert!(
+			normalize_words(Vec::<String>::new(), Language::English).is_empty()
+		);
+		let raw = ["Straße", "ÄRGER", "cafe\u{301}", "señor", "Œuvre"];
+		assert_eq!(
+			normalize_words(raw, Language::German),
+			vec!["straße", "ärger"]
+		);
+		assert_eq!(
+			normalize_words(raw, Language::French),
+			vec!["café", "œuvre"]
+		);
+		assert_eq!(
+			normalize_words(raw, Language::Spanish),
+			vec!["café", "señor"]
+		);
+	}
+
+	/// Test normalizing a single word:
+	///
+	/// * [`normalize_word`]
+	#[test]
+	fn test_normalize_word()
+	{
+		assert_eq!(normalize_word(" Truth\r"), "truth");
+		// Decomposed and precomposed spellings compare equal.
+		assert_eq!(normalize_word("E\u{301}te\u{301}"), "été");
+		assert_eq!(normalize_word("\u{e9}t\u{e9}"), "été");
+		assert_eq!(normalize_word("ÜBER"), "über");
+		assert_eq!(normalize_word("ß"), "ß");
+	}
+
+	/// Test parsing and matching patterns:
+	///
+	/// * [`Pattern::parse`]
+	/// * [`Pattern::matches`]
+	/// * [`Dictionary::iter_matching`]
+	#[test]
+	fn test_pattern()
+	{
+		let cases = [
+			("truth", "truth", true),
+			("truth", "truths", false),
+			("tru*", "truths", true),
+			("tru*", "tru", true),
+			("*s", "truths", true),
+			("*s", "truth", false),
+			("t*t*s", "truths", true),
+			("t*t*s", "trusts", true),
+			("t?uth", "truth", true),
+			("t?uth", "tuth", false),
+			("[st]ruth", "truth", true),
+			("[!st]ruth", "truth", false),
+			("[a-m]*", "hello", true),
+			("[a-m]*", "world", false),
+			("\\*", "*", true),
+			("\\*", "a", false),
+			("*", "", true)
+		];
+		for (pattern, word, expected) in cases
+		{
+			assert_eq!(
+				Pattern::parse(pattern).unwrap().matches(word),
+				expected,
+				"{} ~ {}",
+				pattern,
+				word
+			);
+		}
+		assert!(Pattern::parse("[abc").is_err());
+		assert!(Pattern::parse("abc\\").is_err());
+
+		let dictionary = Dictionary::read_from_file(test_path()).unwrap();
+		let pattern = Pattern::parse("tru?h*").unwrap();
+		let mut words = dictionary.iter_matching(&pattern).collect::<Vec<_>>();
+		words.sort();
+		assert!(words.contains(&"truth"));
+		assert!(words.contains(&"truths"));
+		assert!(words.iter().all(|word| pattern.matches(word)));
 	}
 }
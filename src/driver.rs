@@ -0,0 +1,345 @@
+//! # Drivers
+//!
+//! Herein are the sources of input and time that drive the
+//! [application](crate::app::App): a dedicated thread reading the terminal and
+//! the system clock in production, and scripted events and a fake clock in
+//! tests, so that the application can be driven end to end without a
+//! terminal.
+
+use std::{
+	io,
+	sync::{
+		Arc,
+		atomic::{AtomicBool, Ordering},
+		mpsc::{self, Receiver, RecvTimeoutError}
+	},
+	thread::{self, JoinHandle},
+	time::{Duration, Instant}
+};
+#[cfg(test)]
+use std::{cell::Cell, collections::VecDeque, rc::Rc};
+
+use crossterm::event::{poll, read, Event};
+#[cfg(test)]
+use crossterm::event::KeyCode;
+
+////////////////////////////////////////////////////////////////////////////////
+//                               Event sources.                               //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A source of terminal events, such as key presses, mouse actions, and
+/// pastes.
+pub trait EventSource
+{
+	/// Wait for the next event, but no longer than the specified timeout.
+	///
+	/// # Arguments
+	///
+	/// * `timeout` - How long to wait for an event.
+	///
+	/// # Returns
+	///
+	/// The next event, or `None` if no event arrived in time.
+	///
+	/// # Errors
+	///
+	/// Any error that occurs while reading the event.
+	fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>>;
+}
+
+/// The events of the real terminal, as reported by crossterm, read by a
+/// dedicated input thread and delivered through a channel. Reading input on
+/// its own thread keeps keyboard latency independent of the solver quantum
+/// and of rendering. While the application waits on the user, the thread
+/// checks less often whether it should stop, so that an idle application
+/// doesn't keep waking up. Dropping the source stops the thread.
+#[derive(Debug)]
+pub struct ThreadedEvents
+{
+	/// The events read by the input thread, or the error that stopped it.
+	receiver: Receiver<io::Result<Event>>,
+
+	/// Whether the input thread should stop.
+	stop: Arc<AtomicBool>,
+
+	/// Whether the application is idle, i.e., waiting on the user rather than
+	/// on a solve, an animation, or a timer.
+	idle: Arc<AtomicBool>,
+
+	/// The input thread, if it hasn't been joined yet.
+	thread: Option<JoinHandle<()>>
+}
+
+impl ThreadedEvents
+{
+	/// Spawn the input thread.
+	///
+	/// # Returns
+	///
+	/// The source of events.
+	///
+	/// # Errors
+	///
+	/// Any error that occurs while spawning the thread.
+	pub fn spawn() -> io::Result<Self>
+	{
+		let (sender, receiver) = mpsc::channel();
+		let stop = Arc::new(AtomicBool::new(false));
+		let stopping = Arc::clone(&stop);
+		let idle = Arc::new(AtomicBool::new(false));
+		let idling = Arc::clone(&idle);
+		let thread = thread::Builder::new()
+			.name("input".to_string())
+			.spawn(move || {
+				// Poll rather than block on reading, so that the thread
+				// notices promptly when it should stop, instead of swallowing
+				// the next key meant for whatever runs afterward. Only an
+				// event, typically a key, stops an idle application, so keep
+				// polling briskly for a while after each one.
+				let mut last_event = Instant::now();
+				while !stopping.load(Ordering::Relaxed)
+				{
+					let interval = match idling.load(Ordering::Relaxed)
+						&& last_event.elapsed() >= IDLE_POLL_DELAY
+					{
+						true => IDLE_POLL_INTERVAL,
+						false => INPUT_POLL_INTERVAL
+					};
+					let event = match poll(interval)
+					{
+						Ok(false) => continue,
+						Ok(true) => read(),
+						Err(e) => Err(e)
+					};
+					last_event = Instant::now();
+					let failed = event.is_err();
+					if sender.send(event).is_err() || failed
+					{
+						break
+					}
+				}
+			})?;
+		Ok(Self {
+			receiver,
+			stop,
+			idle,
+			thread: Some(thread)
+		})
+	}
+}
+
+impl EventSource for ThreadedEvents
+{
+	fn next_event(&mut self, timeout: Duration) -> io::Result<Option<Event>>
+	{
+		// An application that can afford to wait longer than the idle
+		// interval has nothing to do until the user acts.
+		self.idle.store(timeout > IDLE_POLL_INTERVAL, Ordering::Relaxed);
+		match self.receiver.recv_timeout(timeout)
+		{
+			Ok(event) => event.map(Some),
+			Err(RecvTimeoutError::Timeout) => Ok(None),
+			Err(RecvTimeoutError::Disconnected) =>
+			{
+				Err(io::Error::other("Input thread stopped"))
+			}
+		}
+	}
+}
+
+impl Drop for ThreadedEvents
+{
+	fn drop(&mut self)
+	{
+		self.stop.store(true, Ordering::Relaxed);
+		if let Some(thread) = self.thread.take()
+		{
+			let _ = thread.join();
+		}
+	}
+}
+
+/// How long the [input thread](ThreadedEvents) waits for an event before
+/// checking whether it should stop.
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How long the [input thread](ThreadedEvents) waits for an event before
+/// checking whether it should stop, while the application is idle.
+const IDLE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long after the most recent event the [input thread](ThreadedEvents)
+/// keeps polling at the [brisk](INPUT_POLL_INTERVAL) interval, even if the
+/// application is idle, so that quitting stays prompt.
+const IDLE_POLL_DELAY: Duration = Duration::from_secs(2);
+
+/// A script of events, which are delivered in order and without waiting.
+/// Once the script is exhausted, no further events arrive.
+#[cfg(test)]
+#[derive(Clone, Debug, Default)]
+pub struct ScriptedEvents(VecDeque<Event>);
+
+#[cfg(test)]
+impl ScriptedEvents
+{
+	/// Append an event to the script.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The event.
+	pub fn push(&mut self, event: Event)
+	{
+		self.0.push_back(event);
+	}
+
+	/// Append a press of the specified key to the script.
+	///
+	/// # Arguments
+	///
+	/// * `code` - The key.
+	pub fn press(&mut self, code: KeyCode)
+	{
+		self.push(Event::Key(code.into()));
+	}
+
+	/// Append a press of each character of the specified text to the script,
+	/// as if the user had typed it.
+	///
+	/// # Arguments
+	///
+	/// * `text` - The text.
+	pub fn type_text(&mut self, text: &str)
+	{
+		text.chars().for_each(|c| self.press(KeyCode::Char(c)));
+	}
+
+	/// Check whether every event of the script has been delivered.
+	///
+	/// # Returns
+	///
+	/// `true` if the script is exhausted, `false` otherwise.
+	#[must_use]
+	pub fn is_empty(&self) -> bool
+	{
+		self.0.is_empty()
+	}
+}
+
+#[cfg(test)]
+impl EventSource for ScriptedEvents
+{
+	fn next_event(&mut self, _timeout: Duration) -> io::Result<Option<Event>>
+	{
+		Ok(self.0.pop_front())
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Clocks.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A source of the current time.
+pub trait Clock
+{
+	/// Answer the current time.
+	///
+	/// # Returns
+	///
+	/// The current time.
+	fn now(&self) -> Instant;
+}
+
+/// The monotonic clock of the operating system.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock
+{
+	#[inline]
+	fn now(&self) -> Instant
+	{
+		Instant::now()
+	}
+}
+
+/// A clock that stands still until it is [advanced](Self::advance). Clones
+/// share the same time, so a test can keep one clone and hand another to the
+/// application.
+#[cfg(test)]
+#[derive(Clone, Debug)]
+pub struct FakeClock(Rc<Cell<Instant>>);
+
+#[cfg(test)]
+impl Default for FakeClock
+{
+	/// Construct a fake clock that reads the current time of the system
+	/// clock, until it is advanced.
+	fn default() -> Self
+	{
+		Self(Rc::new(Cell::new(Instant::now())))
+	}
+}
+
+#[cfg(test)]
+impl FakeClock
+{
+	/// Advance the clock by the specified duration.
+	///
+	/// # Arguments
+	///
+	/// * `duration` - The duration.
+	pub fn advance(&self, duration: Duration)
+	{
+		self.0.set(self.0.get() + duration);
+	}
+}
+
+#[cfg(test)]
+impl Clock for FakeClock
+{
+	#[inline]
+	fn now(&self) -> Instant
+	{
+		self.0.get()
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::time::Duration;
+
+	use crossterm::event::{Event, KeyCode};
+
+	use crate::driver::{Clock, EventSource, FakeClock, ScriptedEvents};
+
+	/// Ensure that scripted events arrive in order, and that a fake clock
+	/// only moves when advanced, in every clone.
+	#[test]
+	fn test_drivers()
+	{
+		let mut events = ScriptedEvents::default();
+		events.type_text("ab");
+		events.press(KeyCode::Enter);
+		let mut codes = Vec::new();
+		while let Some(Event::Key(event)) =
+			events.next_event(Duration::ZERO).unwrap()
+		{
+			codes.push(event.code);
+		}
+		assert_eq!(
+			codes,
+			[KeyCode::Char('a'), KeyCode::Char('b'), KeyCode::Enter]
+		);
+		assert!(events.is_empty());
+
+		let clock = FakeClock::default();
+		let start = clock.now();
+		assert_eq!(clock.now(), start);
+		clock.clone().advance(Duration::from_secs(2));
+		assert_eq!(clock.now() - start, Duration::from_secs(2));
+	}
+}
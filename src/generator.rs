@@ -0,0 +1,585 @@
+//! # Puzzle generator
+//!
+//! Herein is support for generating Quartiles puzzles from a [`Dictionary`].
+//! A puzzle comprises 5 long words, each split into 4 fragments, and the 20
+//! fragments are shuffled into a grid. Generation is deterministic: the same
+//! dictionary, seed, and [difficulty](Difficulty) always produce the same
+//! puzzle. Candidate grids are graded by the [`Solver`] itself, so every
+//! generated puzzle is guaranteed to be solvable. Words are split in favor of
+//! the chunks of letters that are common in the dictionary, as learned by an
+//! [`Inventory`], so that fragments feel like natural pieces of words.
+
+use std::{
+	error::Error,
+	fmt::{self, Display, Formatter},
+	ops::RangeInclusive,
+	rc::Rc
+};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, trace};
+
+use crate::{
+	dictionary::Dictionary,
+	inventory::{Inventory, CHUNK_LEN},
+	solver::{DuplicatePolicy, Fragment, Solver, Word, MAX_FRAGMENT_BYTES}
+};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Generator.                                 //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The permissible lengths of a fragment, in characters. These are the lengths
+/// of the chunks that an [`Inventory`] counts, so that every fragment can be
+/// weighed.
+const FRAGMENT_LEN: RangeInclusive<usize> = CHUNK_LEN;
+
+/// The maximum number of candidate grids to grade before giving up.
+const MAX_ATTEMPTS: usize = 1000;
+
+/// The difficulty of a generated puzzle, measured by the ambiguity of its
+/// fragments, i.e., how many bonus words the grid yields besides the 5
+/// quartiles. The more bonus words there are, the more false leads the player
+/// must sift through to find the quartiles.
+#[derive(
+	Copy,
+	Clone,
+	Debug,
+	Default,
+	PartialEq,
+	Eq,
+	Hash,
+	Serialize,
+	Deserialize,
+	ValueEnum
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Difficulty
+{
+	/// Few bonus words.
+	Easy,
+
+	/// A moderate number of bonus words, comparable to an official puzzle.
+	#[default]
+	Medium,
+
+	/// Many bonus words.
+	Hard
+}
+
+impl Difficulty
+{
+	/// Get the range of bonus words that a puzzle of this difficulty may
+	/// yield.
+	///
+	/// # Returns
+	///
+	/// The admissible numbers of bonus words.
+	#[must_use]
+	pub const fn bonus_words(self) -> RangeInclusive<usize>
+	{
+		match self
+		{
+			Self::Easy => 0..=17,
+			Self::Medium => 18..=29,
+			Self::Hard => 30..=usize::MAX
+		}
+	}
+}
+
+impl Display for Difficulty
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		match self
+		{
+			Self::Easy => write!(f, "easy"),
+			Self::Medium => write!(f, "medium"),
+			Self::Hard => write!(f, "hard")
+		}
+	}
+}
+
+/// A generated puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[must_use]
+pub struct Puzzle
+{
+	/// The fragments of the puzzle, in grid order.
+	pub fragments: [Fragment; 20],
+
+	/// The quartiles, in alphabetical order.
+	pub quartiles: [Word; 5],
+
+	/// The number of bonus words, i.e., words other than the quartiles, that
+	/// the grid yields.
+	pub bonus_words: usize,
+
+	/// The seed that produced the puzzle.
+	pub seed: u64,
+
+	/// The difficulty of the puzzle.
+	pub difficulty: Difficulty
+}
+
+/// A puzzle generator for a particular [`Dictionary`].
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Generator
+{
+	/// The dictionary from which quartiles are drawn, and against which
+	/// candidate grids are graded.
+	dictionary: Rc<Dictionary>,
+
+	/// The words of the dictionary that can be split into 4 fragments, in
+	/// dictionary order.
+	candidates: Vec<String>,
+
+	/// The inventory of the dictionary, which weights the splits of a word.
+	inventory: Inventory
+}
+
+impl Generator
+{
+	/// Construct a new generator for the given dictionary.
+	///
+	/// # Arguments
+	///
+	/// * `dictionary` - The dictionary.
+	///
+	/// # Returns
+	///
+	/// A new generator.
+	pub fn new(dictionary: Rc<Dictionary>) -> Self
+	{
+		let min = 4 * FRAGMENT_LEN.start();
+		let max = 4 * FRAGMENT_LEN.end();
+		let mut candidates = dictionary
+			.iter()
+			.filter(|word| (min..=max).contains(&word.chars().count()))
+			.map(str::to_string)
+			.collect::<Vec<_>>();
+		// The iteration order of the dictionary is unspecified, so sort the
+		// candidates to ensure that generation is deterministic.
+		candidates.sort_unstable();
+		debug!("Quartile candidates: {}", candidates.len());
+		let inventory = Inventory::learn(&dictionary);
+		debug!("Inventory: {} chunks", inventory.len());
+		Self { dictionary, candidates, inventory }
+	}
+
+	/// Generate a puzzle of the specified difficulty. Candidate grids are
+	/// drawn from the seed until one is solvable, contains exactly 5
+	/// quartiles, and yields the right number of
+	/// [bonus words](Difficulty::bonus_words) for the difficulty.
+	///
+	/// # Arguments
+	///
+	/// * `seed` - The seed.
+	/// * `difficulty` - The difficulty.
+	///
+	/// # Returns
+	///
+	/// The puzzle.
+	///
+	/// # Errors
+	///
+	/// * [`GeneratorError::TooFewCandidates`] if the dictionary doesn't
+	///   contain enough words long enough to be quartiles.
+	/// * [`GeneratorError::Exhausted`] if no suitable grid was found.
+	pub fn generate(
+		&self,
+		seed: u64,
+		difficulty: Difficulty
+	) -> Result<Puzzle, GeneratorError>
+	{
+		if self.candidates.len() < 5
+		{
+			return Err(GeneratorError::TooFewCandidates)
+		}
+		let mut rng = Rng(seed);
+		for attempt in 0..MAX_ATTEMPTS
+		{
+			let Some(mut fragments) = self.draw(&mut rng) else { continue };
+			rng.shuffle(&mut fragments);
+			let Some((quartiles, bonus_words)) = self.grade(fragments)
+			else
+			{
+				continue
+			};
+			trace!("Attempt {}: {} bonus words", attempt, bonus_words);
+			if difficulty.bonus_words().contains(&bonus_words)
+			{
+				debug!("Generated puzzle after {} attempts", attempt + 1);
+				return Ok(Puzzle {
+					fragments,
+					quartiles,
+					bonus_words,
+					seed,
+					difficulty
+				})
+			}
+		}
+		Err(GeneratorError::Exhausted)
+	}
+
+	/// Draw 5 distinct quartiles at random, and split each of them into 4
+	/// fragments at random, favoring the splits whose fragments are
+	/// [common](Self::choose_split).
+	///
+	/// # Arguments
+	///
+	/// * `rng` - The random number generator.
+	///
+	/// # Returns
+	///
+	/// The 20 fragments, grouped by quartile, or `None` if any fragment
+	/// occurs more than once.
+	fn draw(&self, rng: &mut Rng) -> Option<[Fragment; 20]>
+	{
+		let mut fragments = Vec::with_capacity(20);
+		let mut words = Vec::with_capacity(5);
+		while words.len() < 5
+		{
+			let word = &self.candidates[rng.below(self.candidates.len())];
+			if !words.contains(word)
+			{
+				words.push(word.clone());
+			}
+		}
+		for word in &words
+		{
+			let chars = word.chars().collect::<Vec<_>>();
+			let lens = self.choose_split(&chars, rng);
+			let mut start = 0;
+			for len in lens
+			{
+				let fragment = chars[start..start + len]
+					.iter()
+					.collect::<String>();
+				// A fragment must fit, and must not be ambiguous.
+				if fragment.len() > MAX_FRAGMENT_BYTES
+					|| fragments.contains(&fragment)
+				{
+					return None
+				}
+				fragments.push(fragment);
+				start += len;
+			}
+		}
+		let fragments = fragments
+			.iter()
+			.map(|f| Fragment::from(f.as_str()))
+			.collect::<Vec<_>>();
+		fragments.try_into().ok()
+	}
+
+	/// Choose a split of the specified word at random, weighting each split
+	/// by the number of words that contain its rarest fragment. A split into
+	/// common chunks, e.g., `re·fre·sh·ment`, is thus far likelier than one
+	/// into unusual chunks, e.g., `ref·res·hme·nt`.
+	///
+	/// # Arguments
+	///
+	/// * `chars` - The characters of the word.
+	/// * `rng` - The random number generator.
+	///
+	/// # Returns
+	///
+	/// The fragment lengths of the split.
+	fn choose_split(&self, chars: &[char], rng: &mut Rng) -> [usize; 4]
+	{
+		let splits = splits(chars.len());
+		let weights = splits
+			.iter()
+			.map(|lens| self.weigh(chars, lens))
+			.collect::<Vec<_>>();
+		let total = weights.iter().sum::<usize>();
+		if total == 0
+		{
+			return splits[rng.below(splits.len())]
+		}
+		let mut target = rng.below(total);
+		for (lens, weight) in splits.iter().zip(weights)
+		{
+			if target < weight
+			{
+				return *lens
+			}
+			target -= weight;
+		}
+		unreachable!()
+	}
+
+	/// Weigh a split of the specified word by the number of words of the
+	/// dictionary that contain its rarest fragment.
+	///
+	/// # Arguments
+	///
+	/// * `chars` - The characters of the word.
+	/// * `lens` - The fragment lengths of the split.
+	///
+	/// # Returns
+	///
+	/// The weight.
+	fn weigh(&self, chars: &[char], lens: &[usize; 4]) -> usize
+	{
+		let mut start = 0;
+		lens.iter()
+			.map(|&len| {
+				let fragment = chars[start..start + len]
+					.iter()
+					.collect::<String>();
+				start += len;
+				self.inventory.count(&fragment)
+			})
+			.min()
+			.unwrap_or(0)
+	}
+
+	/// Grade the given grid by solving it.
+	///
+	/// # Arguments
+	///
+	/// * `fragments` - The fragments of the grid.
+	///
+	/// # Returns
+	///
+	/// The quartiles, in alphabetical order, and the number of bonus words, or
+	/// `None` if the grid is unsolvable or has more than 5 quartiles.
+	fn grade(&self, fragments: [Fragment; 20]) -> Option<([Word; 5], usize)>
+	{
+		let solver = Solver::builder()
+			.dictionary(Rc::clone(&self.dictionary))
+			.fragments(fragments)
+			.duplicates(DuplicatePolicy::KeepFirst)
+			.build()
+			.ok()?
+			.solve_fully();
+		if !solver.is_solved()
+		{
+			return None
+		}
+		let mut quartiles = solver
+			.solution_paths()
+			.iter()
+			.filter(|path| path.is_full())
+			.map(|path| solver.word(path))
+			.collect::<Vec<_>>();
+		quartiles.sort_unstable();
+		quartiles.dedup();
+		let quartiles: [Word; 5] = quartiles.try_into().ok()?;
+		let bonus_words = solver.solution_deduped().len() - quartiles.len();
+		Some((quartiles, bonus_words))
+	}
+}
+
+/// Enumerate every way to split a word of the specified length into 4
+/// fragments of [permissible](FRAGMENT_LEN) lengths.
+///
+/// # Arguments
+///
+/// * `len` - The length of the word, in characters.
+///
+/// # Returns
+///
+/// The fragment lengths of every split, in lexicographic order.
+fn splits(len: usize) -> Vec<[usize; 4]>
+{
+	let mut splits = Vec::new();
+	for a in FRAGMENT_LEN
+	{
+		for b in FRAGMENT_LEN
+		{
+			for c in FRAGMENT_LEN
+			{
+				let d = len.wrapping_sub(a + b + c);
+				if FRAGMENT_LEN.contains(&d)
+				{
+					splits.push([a, b, c, d]);
+				}
+			}
+		}
+	}
+	splits
+}
+
+/// The complete enumeration of [`Generator`] errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GeneratorError
+{
+	/// The dictionary doesn't contain enough words long enough to be
+	/// quartiles.
+	TooFewCandidates,
+
+	/// No suitable grid was found within the maximum number of attempts.
+	Exhausted
+}
+
+impl Display for GeneratorError
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		match self
+		{
+			Self::TooFewCandidates =>
+				write!(f, "too few words long enough to be quartiles"),
+			Self::Exhausted =>
+				write!(f, "no suitable puzzle found; try another seed")
+		}
+	}
+}
+
+impl Error for GeneratorError {}
+
+////////////////////////////////////////////////////////////////////////////////
+//                          Random number generator.                          //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A [SplitMix64](https://prng.di.unimi.it/splitmix64.c) pseudorandom number
+/// generator. It is implemented here, rather than borrowed from a crate, so
+/// that a seed produces the same puzzle forever, regardless of dependency
+/// upgrades.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng
+{
+	/// Construct a pseudorandom number generator from the specified seed.
+	///
+	/// # Arguments
+	///
+	/// * `seed` - The seed.
+	///
+	/// # Returns
+	///
+	/// The pseudorandom number generator.
+	#[inline]
+	pub const fn new(seed: u64) -> Self
+	{
+		Self(seed)
+	}
+
+	/// Answer the next pseudorandom number.
+	///
+	/// # Returns
+	///
+	/// The next pseudorandom number.
+	pub fn next_u64(&mut self) -> u64
+	{
+		self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+		z ^ (z >> 31)
+	}
+
+	/// Answer a pseudorandom index below the specified bound. The modulo bias
+	/// is negligible for the small bounds used here.
+	///
+	/// # Arguments
+	///
+	/// * `bound` - The exclusive upper bound, which must be positive.
+	///
+	/// # Returns
+	///
+	/// The pseudorandom index.
+	pub fn below(&mut self, bound: usize) -> usize
+	{
+		(self.next_u64() % bound as u64) as usize
+	}
+
+	/// Shuffle the given slice in place, using the Fisher-Yates algorithm.
+	///
+	/// # Arguments
+	///
+	/// * `slice` - The slice to shuffle.
+	pub fn shuffle<T>(&mut self, slice: &mut [T])
+	{
+		for i in (1..slice.len()).rev()
+		{
+			slice.swap(i, self.below(i + 1));
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::rc::Rc;
+
+	use crate::{
+		dictionary::Dictionary,
+		generator::{splits, Difficulty, Generator, GeneratorError, Rng}
+	};
+
+	/// Ensure that words are split into 4 fragments of permissible lengths.
+	#[test]
+	fn test_splits()
+	{
+		assert!(splits(7).is_empty());
+		assert_eq!(splits(8), vec![[2, 2, 2, 2]]);
+		assert_eq!(splits(9).len(), 4);
+		assert_eq!(splits(16), vec![[4, 4, 4, 4]]);
+		assert!(splits(17).is_empty());
+	}
+
+	/// Ensure that splits into common chunks outweigh splits into rare ones.
+	#[test]
+	fn test_choose_split()
+	{
+		let dictionary = ["abcdefghi", "abx", "cdx", "efx", "ghix"]
+			.into_iter()
+			.collect::<Dictionary>();
+		let generator = Generator::new(Rc::new(dictionary));
+		let chars = "abcdefghi".chars().collect::<Vec<_>>();
+		let weights = splits(chars.len())
+			.iter()
+			.map(|lens| generator.weigh(&chars, lens))
+			.collect::<Vec<_>>();
+		// Only ab·cd·ef·ghi avoids the chunks unique to the word itself.
+		assert_eq!(splits(chars.len())[0], [2, 2, 2, 3]);
+		assert_eq!(weights, vec![2, 1, 1, 1]);
+		let mut rng = Rng::new(42);
+		let common = (0..100)
+			.filter(|_| generator.choose_split(&chars, &mut rng) == [2, 2, 2, 3])
+			.count();
+		assert!(common > 25, "{}", common);
+	}
+
+	/// Ensure that generated puzzles are solvable, deterministic, and of the
+	/// requested difficulty.
+	#[test]
+	fn test_generate()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let generator = Generator::new(dictionary);
+		for difficulty in [Difficulty::Easy, Difficulty::Hard]
+		{
+			let puzzle = generator.generate(42, difficulty).unwrap();
+			assert_eq!(puzzle.seed, 42);
+			assert_eq!(puzzle.difficulty, difficulty);
+			assert!(difficulty.bonus_words().contains(&puzzle.bonus_words));
+			for quartile in &puzzle.quartiles
+			{
+				assert!(
+					puzzle.fragments
+						.iter()
+						.any(|f| quartile.starts_with(f.as_str()))
+				);
+			}
+			assert_eq!(generator.generate(42, difficulty), Ok(puzzle));
+		}
+
+		let generator = Generator::new(Rc::new(Dictionary::new()));
+		assert_eq!(
+			generator.generate(42, Difficulty::Easy),
+			Err(GeneratorError::TooFewCandidates)
+		);
+	}
+}
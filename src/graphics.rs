@@ -0,0 +1,254 @@
+//! # Graphics
+//!
+//! Herein is the high-fidelity board renderer, which redraws the outlines of
+//! the tiles of the board as smooth, rounded rectangles on a braille canvas.
+//! Braille patterns subdivide every character cell into a 2×4 grid of dots,
+//! so the corners can be rounded far more gently than the box-drawing
+//! characters allow. Only the outlines change: the contents and the styles of
+//! the tiles are exactly those of the block widgets.
+
+use std::{env, f64::consts::FRAC_PI_2};
+
+use ratatui::{
+	buffer::Buffer,
+	layout::Rect,
+	symbols::Marker,
+	widgets::{
+		Widget,
+		canvas::{Canvas, Points}
+	}
+};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Renderers.                                 //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The radius (in dots) of the corners of a braille tile.
+const CORNER_RADIUS: f64 = 3.0;
+
+/// The number of points plotted along each corner of a braille tile.
+const CORNER_POINTS: usize = 12;
+
+/// The board renderers.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Renderer
+{
+	/// Draw the tiles with the block widgets, i.e., with box-drawing
+	/// characters. This works everywhere.
+	#[default]
+	Blocks,
+
+	/// Redraw the outlines of the tiles on a braille canvas.
+	Braille
+}
+
+impl Renderer
+{
+	/// Choose the best renderer that the terminal supports, as described by
+	/// the environment. Braille requires a Unicode locale, and a terminal
+	/// other than the Linux console, whose font lacks braille patterns.
+	///
+	/// # Returns
+	///
+	/// The renderer.
+	pub fn detect() -> Self
+	{
+		let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+			.iter()
+			.find_map(|name| env::var(name).ok().filter(|v| !v.is_empty()));
+		Self::detect_from(env::var("TERM").ok().as_deref(), locale.as_deref())
+	}
+
+	/// Choose the best renderer that the specified terminal supports.
+	///
+	/// # Arguments
+	///
+	/// * `term` - The terminal type, i.e., the value of `TERM`, if any.
+	/// * `locale` - The locale, e.g., the value of `LANG`, if any.
+	///
+	/// # Returns
+	///
+	/// The renderer.
+	fn detect_from(term: Option<&str>, locale: Option<&str>) -> Self
+	{
+		let is_unicode = locale.is_some_and(|locale| {
+			let locale = locale.to_ascii_lowercase();
+			locale.contains("utf-8") || locale.contains("utf8")
+		});
+		let is_console = matches!(term, None | Some("linux" | "dumb"));
+		match is_unicode && !is_console
+		{
+			true => Renderer::Braille,
+			false => Renderer::Blocks
+		}
+	}
+
+	/// Redraw the outline of the tile that occupies the specified area, which
+	/// the block widget has already rendered. The outline keeps the styles of
+	/// the block widget's border. Does nothing for [`Blocks`](Self::Blocks),
+	/// or if the tile is too small to outline.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The area of the tile, including its border.
+	/// * `buf` - The target buffer.
+	pub fn render_tile(self, area: Rect, buf: &mut Buffer)
+	{
+		if self == Renderer::Blocks || area.width < 4 || area.height < 3
+		{
+			return
+		}
+		// Paint the outline onto a scratch buffer, because the canvas resets
+		// the background of its whole area, which would erase the styles of
+		// the tile.
+		let mut scratch = Buffer::empty(area);
+		let points = outline(area.width, area.height);
+		Canvas::default()
+			.marker(Marker::Braille)
+			.x_bounds([0.0, f64::from(area.width * 2 - 1)])
+			.y_bounds([0.0, f64::from(area.height * 4 - 1)])
+			.paint(|ctx| {
+				ctx.draw(&Points { coords: &points, color: Default::default() })
+			})
+			.render(area, &mut scratch);
+		// Copy only the border, so that the content of the tile survives.
+		for y in area.top()..area.bottom()
+		{
+			for x in area.left()..area.right()
+			{
+				let is_border = x == area.left()
+					|| x == area.right() - 1
+					|| y == area.top()
+					|| y == area.bottom() - 1;
+				let symbol = scratch[(x, y)].symbol();
+				if is_border && symbol != " "
+				{
+					buf[(x, y)].set_symbol(symbol);
+				}
+			}
+		}
+	}
+}
+
+/// Compute the dots of the rounded outline of a tile, in canvas coordinates,
+/// i.e., with the origin at the bottom-left dot. The outline runs through the
+/// dots nearest the middle of the border cells.
+///
+/// # Arguments
+///
+/// * `width` - The width of the tile, in cells.
+/// * `height` - The height of the tile, in cells.
+///
+/// # Returns
+///
+/// The dots of the outline.
+fn outline(width: u16, height: u16) -> Vec<(f64, f64)>
+{
+	let (left, right) = (1.0, f64::from(width * 2 - 2));
+	let (bottom, top) = (2.0, f64::from(height * 4 - 3));
+	let radius = CORNER_RADIUS.min((top - bottom) / 2.0);
+	let mut points = Vec::new();
+	let mut x = left + radius;
+	while x <= right - radius
+	{
+		points.push((x, bottom));
+		points.push((x, top));
+		x += 1.0;
+	}
+	let mut y = bottom + radius;
+	while y <= top - radius
+	{
+		points.push((left, y));
+		points.push((right, y));
+		y += 1.0;
+	}
+	// Sweep a quarter circle around each corner, whose center is inset from
+	// the corner by the radius.
+	let corners = [
+		(left + radius, bottom + radius, -1.0, -1.0),
+		(right - radius, bottom + radius, 1.0, -1.0),
+		(left + radius, top - radius, -1.0, 1.0),
+		(right - radius, top - radius, 1.0, 1.0)
+	];
+	for (cx, cy, sx, sy) in corners
+	{
+		for step in 0..=CORNER_POINTS
+		{
+			let angle = FRAC_PI_2 * step as f64 / CORNER_POINTS as f64;
+			points.push((
+				(cx + sx * radius * angle.cos()).round(),
+				(cy + sy * radius * angle.sin()).round()
+			));
+		}
+	}
+	points
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use ratatui::{
+		buffer::Buffer,
+		layout::Rect,
+		style::{Color, Style},
+		widgets::{Block, BorderType, Borders, Paragraph, Widget}
+	};
+
+	use crate::graphics::Renderer;
+
+	/// Ensure that braille is chosen only for Unicode terminals other than the
+	/// Linux console.
+	#[test]
+	fn test_detect()
+	{
+		let detect = Renderer::detect_from;
+		assert_eq!(
+			detect(Some("xterm-kitty"), Some("en_US.UTF-8")),
+			Renderer::Braille
+		);
+		assert_eq!(
+			detect(Some("xterm"), Some("de_DE.utf8")),
+			Renderer::Braille
+		);
+		assert_eq!(detect(Some("xterm"), Some("C")), Renderer::Blocks);
+		assert_eq!(detect(Some("linux"), Some("C.UTF-8")), Renderer::Blocks);
+		assert_eq!(detect(None, Some("C.UTF-8")), Renderer::Blocks);
+		assert_eq!(detect(Some("xterm"), None), Renderer::Blocks);
+	}
+
+	/// Ensure that a braille tile replaces the border of the block widget,
+	/// keeping its style, but leaves the content alone.
+	#[test]
+	fn test_render_tile()
+	{
+		let area = Rect::new(0, 0, 10, 3);
+		let mut buf = Buffer::empty(area);
+		Paragraph::new("azz")
+			.block(
+				Block::new()
+					.borders(Borders::ALL)
+					.border_type(BorderType::Rounded)
+					.border_style(Style::default().fg(Color::Red))
+			)
+			.render(area, &mut buf);
+		let blocks = buf.clone();
+		Renderer::Blocks.render_tile(area, &mut buf);
+		assert_eq!(buf, blocks);
+		Renderer::Braille.render_tile(area, &mut buf);
+		let is_braille = |x: u16, y: u16| {
+			let c = buf[(x, y)].symbol().chars().next().unwrap();
+			('\u{2801}'..='\u{28ff}').contains(&c)
+		};
+		assert!(is_braille(0, 0));
+		assert!(is_braille(5, 0));
+		assert!(is_braille(9, 1));
+		assert!(is_braille(5, 2));
+		assert_eq!(buf[(0, 0)].fg, Color::Red);
+		assert_eq!(buf[(1, 1)].symbol(), "a");
+		assert_eq!(buf[(3, 1)].symbol(), "z");
+	}
+}
@@ -0,0 +1,760 @@
+//! # History file
+//!
+//! Herein is support for the history file, which records the puzzles that the
+//! user has played. The history file is a [JSON](https://www.json.org) array of
+//! [entries](Entry), in the order that the puzzles were played. An entry also
+//! [caches](CachedSolve) the finished solve of its board, so that reopening
+//! the board needn't search again.
+
+use std::{
+	collections::HashMap,
+	fmt::{self, Display, Formatter},
+	fs::{self, File},
+	hash::{Hash, Hasher},
+	io::{self, ErrorKind, Write},
+	path::Path,
+	rc::Rc,
+	time::Duration
+};
+
+use quartiles_solver::{
+	dictionary::Dictionary,
+	solver::{
+		Checkpoint, Fragment, Solver, SolverConfig, MAX_FRAGMENT_BYTES
+	}
+};
+use serde::{Deserialize, Serialize};
+use tracing::trace;
+
+use crate::daily::Date;
+
+////////////////////////////////////////////////////////////////////////////////
+//                               History file.                                //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A record of a single played puzzle.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[must_use]
+pub struct Entry
+{
+	/// The date on which the puzzle was played. For a daily puzzle, this is
+	/// the date of the puzzle instead, which differs if the puzzle was
+	/// replayed.
+	pub date: Date,
+
+	/// Whether the puzzle was the daily puzzle of [`date`](Self::date).
+	pub daily: bool,
+
+	/// The seed from which the puzzle was generated, if it was generated.
+	pub seed: Option<u64>,
+
+	/// The fragments of the puzzle, in grid order.
+	pub fragments: Vec<String>,
+
+	/// The number of words in the solution.
+	pub words: usize,
+
+	/// The number of quartiles in the solution. Every other word of the
+	/// solution is a bonus word.
+	#[serde(default)]
+	pub quartiles: usize,
+
+	/// The total score of the words in the solution.
+	#[serde(default)]
+	pub score: u32,
+
+	/// How long the solve took, in milliseconds, if known. The duration of a
+	/// resumed solve is unknown.
+	#[serde(default)]
+	pub duration_ms: Option<u64>,
+
+	/// Whether every quartile of the puzzle was found.
+	pub completed: bool,
+
+	/// The finished solve, if it may be reused. Entries recorded before
+	/// solves were cached, or while a word filter was installed, have none.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub cache: Option<CachedSolve>
+}
+
+impl Entry
+{
+	/// Get the board of the puzzle, in order to reopen it.
+	///
+	/// # Returns
+	///
+	/// The fragments, in grid order, or `None` if the entry doesn't record
+	/// exactly 20 fragments of at most [`MAX_FRAGMENT_BYTES`] bytes each.
+	#[must_use]
+	pub fn board(&self) -> Option<[Fragment; 20]>
+	{
+		if self.fragments.len() != 20
+			|| self
+				.fragments
+				.iter()
+				.any(|f| f.is_empty() || f.len() > MAX_FRAGMENT_BYTES)
+		{
+			return None
+		}
+		Some(std::array::from_fn(|i| {
+			Fragment::from(self.fragments[i].as_str())
+		}))
+	}
+
+	/// Get the [provenance](Provenance) of the puzzle, if it was generated.
+	///
+	/// # Returns
+	///
+	/// The provenance, or `None` if the puzzle was entered by hand or the
+	/// entry doesn't record a valid board.
+	#[must_use]
+	pub fn provenance(&self) -> Option<Provenance>
+	{
+		Some(Provenance {
+			fragments: self.board()?,
+			seed: self.seed?,
+			daily: self.daily.then_some(self.date)
+		})
+	}
+}
+
+/// The provenance of a generated puzzle, which accompanies the board into the
+/// history.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Provenance
+{
+	/// The fragments of the generated board, in grid order. If the user edits
+	/// the board, then the provenance no longer applies.
+	pub fragments: [Fragment; 20],
+
+	/// The seed from which the puzzle was generated.
+	pub seed: u64,
+
+	/// The date of the puzzle, if it is a daily puzzle.
+	pub daily: Option<Date>
+}
+
+/// Read every entry from the history file.
+///
+/// # Arguments
+///
+/// * `path` - The history file.
+///
+/// # Returns
+///
+/// The entries, in the order that they were recorded, or an empty vector if
+/// the history file does not exist.
+///
+/// # Errors
+///
+/// * If the file exists but cannot be read, an error is returned.
+/// * If the file contains invalid data, an [`ErrorKind::InvalidData`] is
+///   returned.
+pub fn load<T: AsRef<Path>>(path: T) -> Result<Vec<Entry>, io::Error>
+{
+	let path = path.as_ref();
+	if !path.exists()
+	{
+		return Ok(vec![])
+	}
+	let content = fs::read_to_string(path)?;
+	let entries = serde_json::from_str(&content)
+		.map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+	trace!("Read history file: {}", path.display());
+	Ok(entries)
+}
+
+/// Append the given entry to the history file, creating the history file if
+/// necessary. As with the state file, the history is written to a sibling file
+/// first and then renamed, so that an interrupted write cannot corrupt the
+/// history. Missing parent directories are created.
+///
+/// # Arguments
+///
+/// * `path` - The history file.
+/// * `entry` - The entry.
+///
+/// # Errors
+///
+/// * If the file cannot be read or written, an error is returned.
+/// * If the file contains invalid data, an [`ErrorKind::InvalidData`] is
+///   returned.
+pub fn append<T: AsRef<Path>>(path: T, entry: Entry) -> Result<(), io::Error>
+{
+	let path = path.as_ref();
+	let mut entries = load(path)?;
+	entries.push(entry);
+	let content = serde_json::to_string_pretty(&entries)
+		.map_err(|e| io::Error::new(ErrorKind::InvalidData, e))?;
+	if let Some(parent) = path.parent()
+	{
+		fs::create_dir_all(parent)?;
+	}
+	let temp_path = path.with_extension("tmp");
+	let mut file = File::create(&temp_path)?;
+	file.write_all(content.as_bytes())?;
+	file.sync_all()?;
+	fs::rename(&temp_path, path)?;
+	trace!("Wrote history file: {}", path.display());
+	Ok(())
+}
+
+/// Answer whether the history records a completion of the daily puzzle of the
+/// given date.
+///
+/// # Arguments
+///
+/// * `entries` - The entries of the history.
+/// * `date` - The date.
+///
+/// # Returns
+///
+/// `true` if the daily puzzle was completed, `false` otherwise.
+#[must_use]
+pub fn is_daily_completed(entries: &[Entry], date: Date) -> bool
+{
+	entries.iter().any(|e| e.daily && e.completed && e.date == date)
+}
+
+/// The number of fragments listed by [`Statistics::common_fragments`].
+const COMMON_FRAGMENTS: usize = 10;
+
+/// Aggregate statistics over the entries of the history.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[must_use]
+pub struct Statistics
+{
+	/// The number of puzzles played.
+	pub played: usize,
+
+	/// The number of puzzles completed, i.e., whose every quartile was found.
+	pub completed: usize,
+
+	/// The mean number of bonus words per puzzle, i.e., words that aren't
+	/// quartiles.
+	pub average_bonus_words: f64,
+
+	/// The duration of the fastest completed solve, if any duration is known.
+	pub fastest: Option<Duration>,
+
+	/// The most common fragments, together with the number of puzzles that
+	/// contained them, in descending order of frequency. Ties are broken
+	/// alphabetically.
+	pub common_fragments: Vec<(String, usize)>,
+
+	/// The score of every puzzle, in the order that the puzzles were played.
+	pub scores: Vec<u64>
+}
+
+impl Statistics
+{
+	/// Compute the statistics of the given entries.
+	///
+	/// # Arguments
+	///
+	/// * `entries` - The entries of the history.
+	///
+	/// # Returns
+	///
+	/// The statistics.
+	pub fn new(entries: &[Entry]) -> Self
+	{
+		let played = entries.len();
+		let bonus_words = entries
+			.iter()
+			.map(|e| e.words.saturating_sub(e.quartiles))
+			.sum::<usize>();
+		let mut counts = HashMap::<&str, usize>::new();
+		for entry in entries
+		{
+			for fragment in &entry.fragments
+			{
+				*counts.entry(fragment.as_str()).or_default() += 1;
+			}
+		}
+		let mut common_fragments = counts
+			.into_iter()
+			.map(|(fragment, count)| (fragment.to_string(), count))
+			.collect::<Vec<_>>();
+		common_fragments.sort_by(|(a, m), (b, n)| n.cmp(m).then(a.cmp(b)));
+		common_fragments.truncate(COMMON_FRAGMENTS);
+		Self {
+			played,
+			completed: entries.iter().filter(|e| e.completed).count(),
+			average_bonus_words: match played
+			{
+				0 => 0.0,
+				n => bonus_words as f64 / n as f64
+			},
+			fastest: entries
+				.iter()
+				.filter(|e| e.completed)
+				.filter_map(|e| e.duration_ms)
+				.min()
+				.map(Duration::from_millis),
+			common_fragments,
+			scores: entries.iter().map(|e| u64::from(e.score)).collect()
+		}
+	}
+}
+
+/// A table of history [entries](Entry), formatted for a terminal. Each row
+/// is numbered, so that the entry can be reopened by number.
+#[derive(Copy, Clone, Debug)]
+pub struct Table<'a>(pub &'a [Entry]);
+
+impl Display for Table<'_>
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		writeln!(
+			f,
+			"{:>4}  {:<10}  {:>5}  {:>5}  {:<4}  puzzle",
+			"#",
+			"date",
+			"words",
+			"score",
+			"done"
+		)?;
+		writeln!(f, "{}", "-".repeat(46))?;
+		for (index, entry) in self.0.iter().enumerate()
+		{
+			writeln!(
+				f,
+				"{:>4}  {:<10}  {:>5}  {:>5}  {:<4}  {}",
+				index + 1,
+				entry.date.to_string(),
+				entry.words,
+				entry.score,
+				if entry.completed { "✓" } else { "✗" },
+				describe(entry)
+			)?;
+		}
+		Ok(())
+	}
+}
+
+/// Describe the origin of the puzzle of the given entry, e.g., `daily`,
+/// `seed 42`, or `custom`.
+///
+/// # Arguments
+///
+/// * `entry` - The entry.
+///
+/// # Returns
+///
+/// The description.
+#[must_use]
+pub fn describe(entry: &Entry) -> String
+{
+	match (entry.daily, entry.seed)
+	{
+		(true, _) => "daily".to_string(),
+		(false, Some(seed)) => format!("seed {}", seed),
+		(false, None) => "custom".to_string()
+	}
+}
+
+/// Format the given fragments as a grid of 5 rows of 4 fragments, with
+/// aligned columns.
+///
+/// # Arguments
+///
+/// * `fragments` - The fragments, in grid order.
+///
+/// # Returns
+///
+/// The rows of the grid.
+#[must_use]
+pub fn grid<T: AsRef<str>>(fragments: &[T]) -> Vec<String>
+{
+	let width = fragments
+		.iter()
+		.map(|f| f.as_ref().chars().count())
+		.max()
+		.unwrap_or_default();
+	fragments
+		.chunks(4)
+		.map(|row| {
+			let row = row
+				.iter()
+				.map(|f| format!("{:<width$}", f.as_ref()))
+				.collect::<Vec<_>>();
+			row.join(" ").trim_end().to_string()
+		})
+		.collect()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                              Solution cache.                               //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A finished solve, recorded in the history under the
+/// [key](cache_key) of its board, its dictionary, and its configuration.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[must_use]
+pub struct CachedSolve
+{
+	/// The key of the board, the dictionary, and the configuration.
+	pub key: u64,
+
+	/// The [checkpoint](Checkpoint) of the finished solver.
+	pub checkpoint: Checkpoint
+}
+
+impl CachedSolve
+{
+	/// Cache the specified solver, if it is finished. A solver with a word
+	/// filter must not be cached, since the filter may change before the
+	/// board is reopened.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - The solver.
+	///
+	/// # Returns
+	///
+	/// The cached solve, or `None` if the solver is unfinished.
+	pub fn of(solver: &Solver) -> Option<Self>
+	{
+		if !solver.is_finished()
+		{
+			return None
+		}
+		let checkpoint = solver.checkpoint();
+		Some(Self {
+			key: cache_key(
+				&checkpoint.fragments(),
+				solver.dictionary(),
+				solver.config()
+			),
+			checkpoint
+		})
+	}
+}
+
+/// Compute the key under which the solve of the specified board is
+/// [cached](CachedSolve). The key hashes the fragments, the
+/// [fingerprint](Dictionary::fingerprint) of the dictionary, and every setting
+/// of the configuration that affects the solution, using the 64-bit
+/// [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/) hash, which, unlike
+/// the standard hasher, is stable across runs.
+///
+/// # Arguments
+///
+/// * `fragments` - The fragments of the board.
+/// * `dictionary` - The dictionary that the solver walks.
+/// * `config` - The solver configuration.
+///
+/// # Returns
+///
+/// The key.
+#[must_use]
+pub fn cache_key(
+	fragments: &[Fragment; 20],
+	dictionary: &Dictionary,
+	config: &SolverConfig
+) -> u64
+{
+	let mut hasher = Fnv::default();
+	fragments.iter().for_each(|f| f.as_str().hash(&mut hasher));
+	dictionary.fingerprint().hash(&mut hasher);
+	// The time quantum paces the search, but never changes its result.
+	SolverConfig { time_quantum: Duration::ZERO, ..*config }
+		.hash(&mut hasher);
+	hasher.finish()
+}
+
+/// Resume the most recent cached solve of the specified board, if the
+/// history records one against the same dictionary and configuration.
+///
+/// # Arguments
+///
+/// * `entries` - The entries of the history.
+/// * `dictionary` - The dictionary that the solver walks.
+/// * `fragments` - The fragments of the board.
+/// * `config` - The solver configuration.
+///
+/// # Returns
+///
+/// The finished solver, or `None` if no solve of the board is cached.
+#[must_use]
+pub fn cached_solver(
+	entries: &[Entry],
+	dictionary: Rc<Dictionary>,
+	fragments: &[Fragment; 20],
+	config: SolverConfig
+) -> Option<Solver>
+{
+	let key = cache_key(fragments, &dictionary, &config);
+	let cache = entries.iter()
+		.rev()
+		.filter_map(|entry| entry.cache.as_ref())
+		.find(|cache| cache.key == key)?;
+	// The checkpoint may still disagree, e.g., if the history was edited by
+	// hand, so trust it only if the solver accepts it.
+	let solver = Solver::from_checkpoint(dictionary, cache.checkpoint.clone())
+		.ok()
+		.filter(|solver| solver.is_finished())?;
+	trace!("Reusing cached solve: {:016x}", key);
+	Some(solver.with_config(config))
+}
+
+/// A 64-bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/) hasher.
+struct Fnv(u64);
+
+impl Default for Fnv
+{
+	fn default() -> Self
+	{
+		Self(0xcbf2_9ce4_8422_2325)
+	}
+}
+
+impl Hasher for Fnv
+{
+	fn finish(&self) -> u64
+	{
+		self.0
+	}
+
+	fn write(&mut self, bytes: &[u8])
+	{
+		for &byte in bytes
+		{
+			self.0 = (self.0 ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::{fs, rc::Rc, time::Duration};
+
+	use quartiles_solver::{
+		dictionary::Dictionary,
+		solver::{Fragment, Solver, SolverConfig}
+	};
+	use tempfile::TempDir;
+
+	use crate::{
+		daily::Date,
+		history::{
+			append, cached_solver, grid, is_daily_completed, load, CachedSolve,
+			Entry, Provenance, Statistics, Table
+		}
+	};
+
+	/// Ensure that entries survive a round trip through the history file, and
+	/// that daily completions are recognized.
+	#[test]
+	fn test_round_trip()
+	{
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("history.json");
+		assert!(load(&path).unwrap().is_empty());
+		let date = Date { year: 2024, month: 6, day: 1 };
+		let entry = Entry {
+			date,
+			daily: true,
+			seed: Some(42),
+			fragments: vec!["tru".to_string(), "th".to_string()],
+			words: 30,
+			quartiles: 3,
+			score: 52,
+			duration_ms: Some(1500),
+			completed: false,
+			cache: None
+		};
+		append(&path, entry.clone()).unwrap();
+		assert!(!is_daily_completed(&load(&path).unwrap(), date));
+		let completed = Entry { completed: true, ..entry.clone() };
+		append(&path, completed.clone()).unwrap();
+		let entries = load(&path).unwrap();
+		assert_eq!(entries, vec![entry, completed]);
+		assert!(is_daily_completed(&entries, date));
+		assert!(!is_daily_completed(&entries, Date::from_days(0)));
+		let content = fs::read_to_string(&path).unwrap();
+		assert!(content.contains("\"date\": \"2024-06-01\""));
+
+		fs::write(&path, "not json").unwrap();
+		assert!(load(&path).is_err());
+	}
+
+	/// Ensure that an entry reopens as a board with the correct provenance.
+	#[test]
+	fn test_provenance()
+	{
+		let date = Date { year: 2024, month: 6, day: 1 };
+		let fragments = ["tru", "th"].repeat(10);
+		let mut entry = Entry {
+			date,
+			daily: true,
+			seed: Some(42),
+			fragments: fragments.iter().map(|f| f.to_string()).collect(),
+			words: 5,
+			quartiles: 0,
+			score: 12,
+			duration_ms: None,
+			completed: false,
+			cache: None
+		};
+		let board = entry.board().unwrap();
+		assert_eq!(board[1], Fragment::from("th"));
+		assert_eq!(
+			entry.provenance(),
+			Some(Provenance { fragments: board, seed: 42, daily: Some(date) })
+		);
+		entry.daily = false;
+		assert_eq!(entry.provenance().unwrap().daily, None);
+		entry.seed = None;
+		assert_eq!(entry.provenance(), None);
+		assert!(entry.board().is_some());
+		entry.fragments.pop();
+		assert_eq!(entry.board(), None);
+	}
+
+	/// Ensure that a finished solve survives a round trip through the history
+	/// file, and that it is reused only for the same board, dictionary, and
+	/// configuration.
+	#[test]
+	fn test_cache()
+	{
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("history.json");
+		let mut dictionary = Dictionary::new();
+		dictionary.populate(&["truth", "truthfully", "this", "is"]);
+		let dictionary = Rc::new(dictionary);
+		let fragments = [
+			"tru", "th", "fu", "lly", "is", "ab", "cd", "ef", "gh", "ij", "kl",
+			"mn", "op", "qr", "st", "uv", "wx", "yz", "bd", "fh"
+		].map(Fragment::from);
+		let config = SolverConfig::default();
+		let solver = Solver::new(Rc::clone(&dictionary), fragments)
+			.with_config(config);
+		assert_eq!(CachedSolve::of(&solver), None);
+		let solver = solver.solve_fully();
+		append(&path, Entry {
+			date: Date { year: 2024, month: 6, day: 1 },
+			daily: false,
+			seed: None,
+			fragments: fragments.iter().map(|f| f.to_string()).collect(),
+			words: 4,
+			quartiles: 1,
+			score: 18,
+			duration_ms: None,
+			completed: false,
+			cache: CachedSolve::of(&solver)
+		})
+		.unwrap();
+		let entries = load(&path).unwrap();
+		let cached =
+			cached_solver(&entries, Rc::clone(&dictionary), &fragments, config)
+				.unwrap();
+		assert!(cached.is_finished());
+		assert_eq!(cached.solution(), solver.solution());
+
+		// The time quantum never changes the solution.
+		let paced = SolverConfig {
+			time_quantum: Duration::from_millis(1),
+			..config
+		};
+		assert!(
+			cached_solver(&entries, Rc::clone(&dictionary), &fragments, paced)
+				.is_some()
+		);
+
+		// Anything else that might change the solution misses the cache.
+		let strict = SolverConfig { quartiles_only: true, ..config };
+		assert!(
+			cached_solver(&entries, Rc::clone(&dictionary), &fragments, strict)
+				.is_none()
+		);
+		let mut shuffled = fragments;
+		shuffled.swap(0, 1);
+		assert!(
+			cached_solver(&entries, Rc::clone(&dictionary), &shuffled, config)
+				.is_none()
+		);
+		let mut other = Dictionary::new();
+		other.populate(&["truth"]);
+		assert!(
+			cached_solver(&entries, Rc::new(other), &fragments, config)
+				.is_none()
+		);
+	}
+
+	/// Ensure that entries and grids are tabulated correctly.
+	#[test]
+	fn test_table()
+	{
+		let entry = Entry {
+			date: Date { year: 2024, month: 6, day: 1 },
+			daily: false,
+			seed: None,
+			fragments: vec![],
+			words: 30,
+			quartiles: 5,
+			score: 52,
+			duration_ms: None,
+			completed: true,
+			cache: None
+		};
+		let table = Table(&[entry]).to_string();
+		let lines = table.lines().collect::<Vec<_>>();
+		assert_eq!(lines.len(), 3);
+		assert_eq!(lines[2], "   1  2024-06-01     30     52  ✓     custom");
+		assert_eq!(
+			grid(&["azz", "th", "ss", "tru", "ref", "fu"]),
+			vec!["azz th  ss  tru", "ref fu"]
+		);
+	}
+
+	/// Ensure that statistics aggregate the entries correctly.
+	#[test]
+	fn test_statistics()
+	{
+		assert_eq!(Statistics::new(&[]), Statistics::default());
+		let entry = |fragments: &[&str], words, quartiles, ms, completed| {
+			Entry {
+				date: Date { year: 2024, month: 6, day: 1 },
+				daily: false,
+				seed: None,
+				fragments: fragments.iter().map(|f| f.to_string()).collect(),
+				words,
+				quartiles,
+				score: words as u32,
+				duration_ms: ms,
+				completed,
+				cache: None
+			}
+		};
+		let entries = [
+			entry(&["tru", "th", "is"], 30, 5, Some(900), true),
+			entry(&["th", "is", "re"], 10, 5, Some(400), false),
+			entry(&["th", "ss"], 14, 5, None, true)
+		];
+		let statistics = Statistics::new(&entries);
+		assert_eq!(statistics.played, 3);
+		assert_eq!(statistics.completed, 2);
+		assert_eq!(statistics.average_bonus_words, 13.0);
+		// The faster solve wasn't completed, so it doesn't count.
+		assert_eq!(statistics.fastest, Some(Duration::from_millis(900)));
+		assert_eq!(
+			statistics.common_fragments[..3],
+			[
+				("th".to_string(), 3),
+				("is".to_string(), 2),
+				("re".to_string(), 1)
+			]
+		);
+		assert_eq!(statistics.scores, vec![30, 10, 14]);
+	}
+}
@@ -0,0 +1,437 @@
+//! # HTML export
+//!
+//! Herein is support for rendering the outcome of a solve as a standalone
+//! HTML page, for reviewing the solution in a browser or embedding it in a
+//! blog post. The board is drawn as an inline SVG, beside the words of the
+//! solution in two sections, the quartiles and then the bonus words. Hovering
+//! over (or focusing) a word highlights its fragments on the board and traces
+//! its path through them, in order. The page needs neither scripts nor
+//! external resources: the highlighting is pure CSS.
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::app::Outcome;
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Geometry.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The number of rows of the board.
+const ROWS: usize = 5;
+
+/// The number of columns of the board.
+const COLUMNS: usize = 4;
+
+/// The width of a cell of the board, in pixels.
+const CELL_WIDTH: usize = 120;
+
+/// The height of a cell of the board, in pixels.
+const CELL_HEIGHT: usize = 56;
+
+/// The gap between adjacent cells of the board, in pixels.
+const GAP: usize = 8;
+
+/// Answer the position of the top left corner of the cell at the specified
+/// coordinates, in pixels.
+///
+/// # Arguments
+///
+/// * `row` - The row, counted from zero at the top.
+/// * `column` - The column, counted from zero at the left.
+///
+/// # Returns
+///
+/// The position, as an `(x, y)` pair.
+fn corner(row: usize, column: usize) -> (usize, usize)
+{
+	(
+		GAP + column * (CELL_WIDTH + GAP),
+		GAP + row * (CELL_HEIGHT + GAP)
+	)
+}
+
+/// Answer the position of the center of the cell at the specified
+/// coordinates, in pixels.
+///
+/// # Arguments
+///
+/// * `row` - The row, counted from zero at the top.
+/// * `column` - The column, counted from zero at the left.
+///
+/// # Returns
+///
+/// The position, as an `(x, y)` pair.
+fn center(row: usize, column: usize) -> (usize, usize)
+{
+	let (x, y) = corner(row, column);
+	(x + CELL_WIDTH / 2, y + CELL_HEIGHT / 2)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Pages.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The style sheet of a page, excluding the rules that highlight each word,
+/// which depend on the solution.
+const STYLE: &str = "\
+body { font-family: system-ui, sans-serif; margin: 2em; color: #222; \
+background: #fafafa; }
+main { display: flex; flex-wrap: wrap; gap: 2em; align-items: flex-start; }
+h2 { font-size: 1.1em; margin: 0 0 0.5em; }
+ul { list-style: none; margin: 0 0 1.5em; padding: 0; columns: 2; }
+li { padding: 2px 6px; border-radius: 4px; cursor: default; }
+li:hover, li:focus { background: #dceafb; outline: none; }
+.quartile .word { font-weight: bold; }
+.fragments { color: #777; font-size: 0.85em; }
+.cell rect { fill: #fff; stroke: #aaa; transition: fill 0.15s; }
+.cell text { font-size: 20px; text-anchor: middle; \
+dominant-baseline: central; }
+.path { opacity: 0; pointer-events: none; transition: opacity 0.15s; }
+.path polyline { fill: none; stroke: #2a7ae2; stroke-width: 6; \
+stroke-linecap: round; stroke-linejoin: round; stroke-opacity: 0.6; }
+.path circle { fill: #2a7ae2; }
+.path text { fill: #fff; font-size: 12px; text-anchor: middle; \
+dominant-baseline: central; }
+";
+
+/// A standalone HTML page that renders the board and the solution of an
+/// [outcome](Outcome). The words are numbered in the order listed, i.e., the
+/// quartiles and then the bonus words, and the `n`th word is the list item
+/// `word-n`, whose path is the SVG group `path-n`. The cell at row `r` and
+/// column `c` is the SVG group `cell-r-c`.
+#[derive(Copy, Clone, Debug)]
+pub struct Page<'a>(pub &'a Outcome);
+
+impl Display for Page<'_>
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		let outcome = self.0;
+		let solution = &outcome.solution;
+		writeln!(f, "<!DOCTYPE html>")?;
+		writeln!(f, "<html lang=\"en\">")?;
+		writeln!(f, "<head>")?;
+		writeln!(f, "<meta charset=\"utf-8\">")?;
+		writeln!(f, "<title>Quartiles solution</title>")?;
+		writeln!(f, "<style>")?;
+		write!(f, "{}", STYLE)?;
+		self.write_highlights(f)?;
+		writeln!(f, "</style>")?;
+		writeln!(f, "</head>")?;
+		writeln!(f, "<body>")?;
+		writeln!(f, "<h1>Quartiles</h1>")?;
+		match outcome.is_finished
+		{
+			true => writeln!(
+				f,
+				"<p>{} quartiles · {} words · {} points</p>",
+				solution.quartiles.len(),
+				solution.quartiles.len() + solution.bonus_words.len(),
+				solution.score
+			)?,
+			false => writeln!(f, "<p>The solve was abandoned.</p>")?
+		}
+		writeln!(f, "<main>")?;
+		self.write_board(f)?;
+		self.write_words(f)?;
+		writeln!(f, "</main>")?;
+		writeln!(f, "</body>")?;
+		writeln!(f, "</html>")
+	}
+}
+
+impl Page<'_>
+{
+	/// Write the style rules that highlight each word while its list item is
+	/// hovered or focused: its cells are tinted, and its path is shown.
+	///
+	/// # Arguments
+	///
+	/// * `f` - The formatter.
+	///
+	/// # Errors
+	///
+	/// If the formatter fails, an error is returned.
+	fn write_highlights(&self, f: &mut Formatter) -> fmt::Result
+	{
+		for (index, word) in self.0.solution.words().enumerate()
+		{
+			let hovered = |target: &str| {
+				format!(
+					"body:has(#word-{0}:hover) {1}, \
+						body:has(#word-{0}:focus) {1}",
+					index,
+					target
+				)
+			};
+			writeln!(
+				f,
+				"{} {{ opacity: 1; }}",
+				hovered(&format!("#path-{}", index))
+			)?;
+			let cells = self.coordinates(word)
+				.iter()
+				.map(|(row, column)| {
+					hovered(&format!("#cell-{}-{} rect", row, column))
+				})
+				.collect::<Vec<_>>();
+			if !cells.is_empty()
+			{
+				writeln!(f, "{} {{ fill: #dceafb; }}", cells.join(", "))?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Write the board, as an inline SVG: the cells, each labeled with its
+	/// fragment, and then the path of each word, hidden until highlighted.
+	/// Each step of a path is numbered.
+	///
+	/// # Arguments
+	///
+	/// * `f` - The formatter.
+	///
+	/// # Errors
+	///
+	/// If the formatter fails, an error is returned.
+	fn write_board(&self, f: &mut Formatter) -> fmt::Result
+	{
+		let (width, height) = corner(ROWS, COLUMNS);
+		writeln!(
+			f,
+			"<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" \
+				height=\"{1}\" viewBox=\"0 0 {0} {1}\" role=\"img\" \
+				aria-label=\"Quartiles board\">",
+			width,
+			height
+		)?;
+		for row in 0..ROWS
+		{
+			for column in 0..COLUMNS
+			{
+				let (x, y) = corner(row, column);
+				let (cx, cy) = center(row, column);
+				let fragment = self.0.board
+					.get(row * COLUMNS + column)
+					.map(String::as_str)
+					.unwrap_or_default();
+				writeln!(
+					f,
+					"<g class=\"cell\" id=\"cell-{}-{}\">\
+						<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" \
+						rx=\"8\"/><text x=\"{}\" y=\"{}\">{}</text></g>",
+					row,
+					column,
+					x,
+					y,
+					CELL_WIDTH,
+					CELL_HEIGHT,
+					cx,
+					cy,
+					escape(fragment)
+				)?;
+			}
+		}
+		for (index, word) in self.0.solution.words().enumerate()
+		{
+			let coordinates = self.coordinates(word);
+			let points = coordinates.iter()
+				.map(|&(row, column)| {
+					let (x, y) = center(row, column);
+					format!("{},{}", x, y)
+				})
+				.collect::<Vec<_>>();
+			write!(
+				f,
+				"<g class=\"path\" id=\"path-{}\"><polyline points=\"{}\"/>",
+				index,
+				points.join(" ")
+			)?;
+			for (step, &(row, column)) in coordinates.iter().enumerate()
+			{
+				let (x, y) = corner(row, column);
+				let (x, y) = (x + 14, y + 14);
+				write!(
+					f,
+					"<circle cx=\"{0}\" cy=\"{1}\" r=\"10\"/>\
+						<text x=\"{0}\" y=\"{1}\">{2}</text>",
+					x,
+					y,
+					step + 1
+				)?;
+			}
+			writeln!(f, "</g>")?;
+		}
+		writeln!(f, "</svg>")
+	}
+
+	/// Write the words of the solution, in two sections, the quartiles and
+	/// then the bonus words, each headed by its title and count. Each word is
+	/// followed by its fragments, in order.
+	///
+	/// # Arguments
+	///
+	/// * `f` - The formatter.
+	///
+	/// # Errors
+	///
+	/// If the formatter fails, an error is returned.
+	fn write_words(&self, f: &mut Formatter) -> fmt::Result
+	{
+		let solution = &self.0.solution;
+		let quartiles = solution.quartiles.len();
+		writeln!(f, "<div>")?;
+		let mut index = 0;
+		for (title, words) in solution.sections()
+		{
+			writeln!(f, "<h2>{} ({})</h2>", escape(title), words.len())?;
+			writeln!(f, "<ul>")?;
+			for word in words
+			{
+				let fragments = solution.paths.get(word)
+					.map(|fragments| {
+						fragments.iter()
+							.map(|fragment| escape(fragment))
+							.collect::<Vec<_>>()
+							.join(" · ")
+					})
+					.unwrap_or_default();
+				writeln!(
+					f,
+					"<li id=\"word-{}\" class=\"{}\" tabindex=\"0\">\
+						<span class=\"word\">{}</span> \
+						<span class=\"fragments\">{}</span></li>",
+					index,
+					if index < quartiles { "quartile" } else { "bonus" },
+					escape(word),
+					fragments
+				)?;
+				index += 1;
+			}
+			writeln!(f, "</ul>")?;
+		}
+		writeln!(f, "</div>")
+	}
+
+	/// Answer the grid coordinates of the fragments of the specified word of
+	/// the solution, in order.
+	///
+	/// # Arguments
+	///
+	/// * `word` - The word.
+	///
+	/// # Returns
+	///
+	/// The coordinates, as `(row, column)` pairs, or none if the word isn't
+	/// part of the solution.
+	fn coordinates(&self, word: &str) -> &[(usize, usize)]
+	{
+		self.0.coordinates.get(word).map(Vec::as_slice).unwrap_or_default()
+	}
+}
+
+/// Escape the specified text for inclusion in HTML, either as content or as
+/// the value of a quoted attribute.
+///
+/// # Arguments
+///
+/// * `text` - The text to escape.
+///
+/// # Returns
+///
+/// The escaped text.
+fn escape(text: &str) -> String
+{
+	let mut escaped = String::with_capacity(text.len());
+	for c in text.chars()
+	{
+		match c
+		{
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			'\'' => escaped.push_str("&#39;"),
+			c => escaped.push(c)
+		}
+	}
+	escaped
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use quartiles_solver::{dictionary::Dictionary, solver::{Fragment, Solver}};
+
+	use crate::{
+		app::Outcome,
+		html::{escape, Page}
+	};
+
+	/// Ensure that the page draws every fragment of the board, lists every
+	/// word with its fragments, and highlights the cells of each word on
+	/// hover.
+	#[test]
+	fn test_page()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let fragments = [
+			"azz", "th", "ss", "tru", "ref", "fu", "ra", "nih", "cro", "mat",
+			"wo", "sh", "re", "rds", "tic", "il", "lly", "zz", "is", "ment"
+		].map(Fragment::from);
+		let solver = Solver::new(dictionary.into(), fragments).solve_fully();
+		let outcome = Outcome::of(&solver);
+		let page = Page(&outcome).to_string();
+		assert!(page.starts_with("<!DOCTYPE html>\n"));
+		assert!(page.ends_with("</html>\n"));
+		for fragment in fragments
+		{
+			let label = format!(">{}</text>", fragment);
+			assert!(page.contains(&label), "{}", page);
+		}
+		assert!(page.contains("<h2>Quartiles (5)</h2>"), "{}", page);
+		let words = outcome.solution.words().collect::<Vec<_>>();
+		assert_eq!(page.matches("<li id=\"word-").count(), words.len());
+		let index = words.iter()
+			.position(|&word| word == "truthfully")
+			.unwrap();
+		assert!(page.contains(&format!(
+			"<li id=\"word-{}\" class=\"quartile\" tabindex=\"0\">\
+				<span class=\"word\">truthfully</span> \
+				<span class=\"fragments\">tru · th · fu · lly</span></li>",
+			index
+		)));
+		// "tru" is at row 0, column 3, and "lly" at row 4, column 0.
+		assert!(page.contains(&format!(
+			"body:has(#word-{}:hover) #cell-0-3 rect",
+			index
+		)));
+		assert!(page.contains(&format!(
+			"body:has(#word-{}:focus) #cell-4-0 rect",
+			index
+		)));
+		assert!(page.contains(&format!(
+			"<g class=\"path\" id=\"path-{}\">",
+			index
+		)));
+	}
+
+	/// Ensure that an abandoned solve renders an empty board, and that text
+	/// is escaped.
+	#[test]
+	fn test_abandoned()
+	{
+		let page = Page(&Outcome::default()).to_string();
+		assert!(page.contains("The solve was abandoned."));
+		assert_eq!(page.matches("<g class=\"cell\"").count(), 20);
+		assert!(!page.contains("<li"));
+		assert_eq!(
+			escape("<a & \"b\">'"),
+			"&lt;a &amp; &quot;b&quot;&gt;&#39;"
+		);
+	}
+}
@@ -0,0 +1,334 @@
+//! # Fragment inventory
+//!
+//! Herein is support for learning which short chunks of letters occur most
+//! often in the words of a [`Dictionary`]. Official puzzles favor fragments
+//! that feel like natural pieces of words, so the resulting frequency table
+//! guides the [puzzle generator](crate::generator::Generator) toward such
+//! fragments. The table is also a ready ranking of plausible fragments for any
+//! search that must guess at letters it hasn't been given.
+
+use std::{
+	collections::{HashMap, HashSet},
+	fmt::{self, Display, Formatter},
+	ops::RangeInclusive
+};
+
+use crate::dictionary::Dictionary;
+
+////////////////////////////////////////////////////////////////////////////////
+//                                Inventories.                                //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The lengths of the chunks that an [`Inventory`] counts, in characters.
+pub const CHUNK_LEN: RangeInclusive<usize> = 2..=4;
+
+/// The number of words of a dictionary in which each short chunk of letters
+/// occurs, for every chunk of [`CHUNK_LEN`] characters.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[must_use]
+pub struct Inventory
+{
+	/// The number of words containing each chunk. A word that contains a
+	/// chunk more than once counts only once.
+	counts: HashMap<String, usize>,
+
+	/// The number of words analyzed.
+	words: usize
+}
+
+impl Inventory
+{
+	/// Learn the inventory of the specified dictionary, by counting the
+	/// chunks of every word. This scans the whole dictionary, so the result
+	/// should be kept for as long as the dictionary is in use.
+	///
+	/// # Arguments
+	///
+	/// * `dictionary` - The dictionary.
+	///
+	/// # Returns
+	///
+	/// The inventory.
+	pub fn learn(dictionary: &Dictionary) -> Self
+	{
+		let mut counts = HashMap::<&str, usize>::new();
+		let mut seen = HashSet::new();
+		let mut words = 0;
+		for word in dictionary.iter()
+		{
+			words += 1;
+			seen.clear();
+			let ends = word
+				.char_indices()
+				.map(|(offset, c)| offset + c.len_utf8())
+				.collect::<Vec<_>>();
+			for (index, (start, _)) in word.char_indices().enumerate()
+			{
+				for len in CHUNK_LEN
+				{
+					let Some(&end) = ends.get(index + len - 1) else { break };
+					seen.insert(&word[start..end]);
+				}
+			}
+			for &chunk in &seen
+			{
+				*counts.entry(chunk).or_default() += 1;
+			}
+		}
+		Self {
+			counts: counts
+				.into_iter()
+				.map(|(chunk, count)| (chunk.to_string(), count))
+				.collect(),
+			words
+		}
+	}
+
+	/// Answer the number of words analyzed.
+	///
+	/// # Returns
+	///
+	/// The number of words.
+	#[inline]
+	#[must_use]
+	pub fn words(&self) -> usize
+	{
+		self.words
+	}
+
+	/// Answer the number of distinct chunks counted.
+	///
+	/// # Returns
+	///
+	/// The number of chunks.
+	#[inline]
+	#[must_use]
+	pub fn len(&self) -> usize
+	{
+		self.counts.len()
+	}
+
+	/// Check whether no chunks were counted, e.g., because the dictionary was
+	/// empty.
+	///
+	/// # Returns
+	///
+	/// `true` if no chunks were counted, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_empty(&self) -> bool
+	{
+		self.counts.is_empty()
+	}
+
+	/// Answer the number of words in which the specified chunk occurs. Chunks
+	/// whose lengths fall outside [`CHUNK_LEN`] were not counted.
+	///
+	/// # Arguments
+	///
+	/// * `chunk` - The chunk.
+	///
+	/// # Returns
+	///
+	/// The number of words, or 0 if the chunk was not counted.
+	#[inline]
+	#[must_use]
+	pub fn count(&self, chunk: &str) -> usize
+	{
+		self.counts.get(chunk).copied().unwrap_or(0)
+	}
+
+	/// Answer the share of words in which the specified chunk occurs.
+	///
+	/// # Arguments
+	///
+	/// * `chunk` - The chunk.
+	///
+	/// # Returns
+	///
+	/// The share, between 0 and 1.
+	#[must_use]
+	pub fn frequency(&self, chunk: &str) -> f64
+	{
+		match self.words
+		{
+			0 => 0.0,
+			words => self.count(chunk) as f64 / words as f64
+		}
+	}
+
+	/// Tabulate the most common chunks, most common first. Ties are broken
+	/// alphabetically.
+	///
+	/// # Arguments
+	///
+	/// * `len` - The length of the chunks to tabulate, in characters, or
+	///   `None` to tabulate chunks of every length together.
+	/// * `limit` - The maximum number of rows.
+	///
+	/// # Returns
+	///
+	/// The frequency table.
+	pub fn table(&self, len: Option<usize>, limit: usize) -> FrequencyTable
+	{
+		let mut rows = self.counts
+			.iter()
+			.filter(|(chunk, _)| {
+				len.is_none_or(|len| chunk.chars().count() == len)
+			})
+			.map(|(chunk, &count)| (chunk.clone(), count))
+			.collect::<Vec<_>>();
+		rows.sort_unstable_by(|(a, m), (b, n)| n.cmp(m).then(a.cmp(b)));
+		rows.truncate(limit);
+		FrequencyTable { rows, words: self.words }
+	}
+
+	/// Judge the [rarity](Rarity) of the specified word by the share of words
+	/// that contain its rarest pair of adjacent letters, e.g., the `zz` of
+	/// `razzmatazz`. A word of fewer than 2 letters is common.
+	///
+	/// # Arguments
+	///
+	/// * `word` - The word.
+	///
+	/// # Returns
+	///
+	/// The rarity.
+	pub fn rarity(&self, word: &str) -> Rarity
+	{
+		let bounds = word.char_indices()
+			.map(|(index, _)| index)
+			.chain([word.len()])
+			.collect::<Vec<_>>();
+		let frequency = bounds
+			.windows(3)
+			.map(|bounds| self.frequency(&word[bounds[0]..bounds[2]]))
+			.fold(1.0, f64::min);
+		match frequency
+		{
+			f if f >= UNCOMMON_FREQUENCY => Rarity::Common,
+			f if f >= RARE_FREQUENCY => Rarity::Uncommon,
+			_ => Rarity::Rare
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Rarity.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// How unusual a word looks, as judged by an [`Inventory`]: the rarer the
+/// letters that the word juxtaposes, the rarer the word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rarity
+{
+	/// Every pair of adjacent letters is commonplace.
+	Common,
+
+	/// Some pair of adjacent letters is unusual.
+	Uncommon,
+
+	/// Some pair of adjacent letters is very seldom seen.
+	Rare
+}
+
+/// The share of words below which a pair of adjacent letters makes a word
+/// [uncommon](Rarity::Uncommon).
+const UNCOMMON_FREQUENCY: f64 = 0.005;
+
+/// The share of words below which a pair of adjacent letters makes a word
+/// [rare](Rarity::Rare).
+const RARE_FREQUENCY: f64 = 0.0002;
+
+////////////////////////////////////////////////////////////////////////////////
+//                             Frequency tables.                              //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The most common chunks of an [`Inventory`], as produced by
+/// [`Inventory::table`]. Displays as a table of one chunk per line, with the
+/// number and share of words in which it occurs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[must_use]
+pub struct FrequencyTable
+{
+	/// The chunks and the number of words in which they occur, most common
+	/// first.
+	pub rows: Vec<(String, usize)>,
+
+	/// The number of words analyzed.
+	pub words: usize
+}
+
+impl Display for FrequencyTable
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		writeln!(f, "{:<5} {:>8} {:>7}", "Chunk", "Words", "Share")?;
+		for (chunk, count) in &self.rows
+		{
+			let share = match self.words
+			{
+				0 => 0.0,
+				words => 100.0 * *count as f64 / words as f64
+			};
+			writeln!(f, "{:<5} {:>8} {:>6.2}%", chunk, count, share)?;
+		}
+		Ok(())
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use crate::{
+		dictionary::Dictionary,
+		inventory::{Inventory, Rarity}
+	};
+
+	/// Ensure that chunks are counted once per word, that only chunks of
+	/// permissible lengths are counted, and that the table is ranked.
+	#[test]
+	fn test_learn()
+	{
+		let dictionary = ["banana", "bandana", "cab"]
+			.into_iter()
+			.collect::<Dictionary>();
+		let inventory = Inventory::learn(&dictionary);
+		assert_eq!(inventory.words(), 3);
+		assert_eq!(inventory.count("an"), 2);
+		assert_eq!(inventory.count("ana"), 2);
+		assert_eq!(inventory.count("ab"), 1);
+		assert_eq!(inventory.count("banan"), 0);
+		assert_eq!(inventory.count("b"), 0);
+		assert!((inventory.frequency("an") - 2.0 / 3.0).abs() < 1e-9);
+		let table = inventory.table(Some(2), 3);
+		assert_eq!(
+			table.rows,
+			vec![("an".to_string(), 2), ("ba".into(), 2), ("na".into(), 2)]
+		);
+		assert_eq!(
+			table.to_string().lines().nth(1),
+			Some("an           2  66.67%")
+		);
+		let table = inventory.table(None, usize::MAX);
+		assert_eq!(table.rows.len(), inventory.len());
+		assert!(Inventory::learn(&Dictionary::new()).is_empty());
+	}
+
+	/// Ensure that words are judged by their rarest pair of adjacent letters.
+	#[test]
+	fn test_rarity()
+	{
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let inventory = Inventory::learn(&dictionary);
+		assert_eq!(inventory.rarity("cross"), Rarity::Common);
+		assert_eq!(inventory.rarity("fuzz"), Rarity::Uncommon);
+		assert_eq!(inventory.rarity("razzmatazz"), Rarity::Rare);
+		assert_eq!(inventory.rarity("a"), Rarity::Common);
+		assert_eq!(Inventory::default().rarity("cross"), Rarity::Rare);
+	}
+}
@@ -0,0 +1,908 @@
+//! # Keymaps
+//!
+//! Herein is the mapping from keys to the [actions](Action) of the text-based
+//! user interface (TUI). Two presets are built in: the default keymap, which
+//! uses the arrow and editing keys, and a [vim](https://www.vim.org)-style
+//! keymap, which is modal: navigation happens in normal mode, and fragments are
+//! typed in insert mode. Any action can be rebound from the configuration file.
+
+use std::{
+	collections::BTreeMap,
+	fmt::{self, Display, Formatter},
+	io::{self, ErrorKind}
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+use crate::locale::Messages;
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Actions.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The complete enumeration of actions that can be bound to keys.
+#[derive(
+	Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize,
+	Deserialize
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Action
+{
+	/// Select the cell (or word) above.
+	MoveUp,
+
+	/// Select the cell (or word) below.
+	MoveDown,
+
+	/// Select the cell to the left.
+	MoveLeft,
+
+	/// Select the cell to the right.
+	MoveRight,
+
+	/// Select the next cell, wrapping to the beginning of the row below.
+	NextCell,
+
+	/// Select the previous cell, wrapping to the end of the row above.
+	PreviousCell,
+
+	/// Swap the selected cell with the cell above, keeping it selected.
+	SwapUp,
+
+	/// Swap the selected cell with the cell below, keeping it selected.
+	SwapDown,
+
+	/// Swap the selected cell with the cell to the left, keeping it selected.
+	SwapLeft,
+
+	/// Swap the selected cell with the cell to the right, keeping it
+	/// selected.
+	SwapRight,
+
+	/// Remove the last letter from the selected cell.
+	DeleteChar,
+
+	/// Clear the selected cell.
+	ClearCell,
+
+	/// Clear all cells.
+	ClearAll,
+
+	/// Enter every fragment of the board at once, as a single line of text.
+	BulkEntry,
+
+	/// Explain why the solver does or doesn't find some word.
+	Explain,
+
+	/// Shuffle the displayed positions of the fragments while playing.
+	Shuffle,
+
+	/// Give up the round of play, revealing the words yet to be found.
+	Reveal,
+
+	/// Scroll the solution up by a page.
+	PageUp,
+
+	/// Scroll the solution down by a page. Scrolling to the end resumes
+	/// following the most recently discovered word.
+	PageDown,
+
+	/// Show the next placement of the selected word on the board, if the word
+	/// can be formed in more than one way.
+	NextPlacement,
+
+	/// Mark the selected word of the solution as bogus, removing it from the
+	/// solution and adding it to the dictionary's exclusion list.
+	MarkBogus,
+
+	/// Mark (or unmark) the selected word of the solution as a favorite.
+	MarkFavorite,
+
+	/// Cycle the order of the words within each section of the solution:
+	/// discovery, alphabetical, length, and score.
+	Sort,
+
+	/// Export a share card of the result, which tallies the words and points
+	/// without spoiling them.
+	ExportCard,
+
+	/// Replay the discovery of each word of the solution, or stop the replay.
+	Replay,
+
+	/// Start the solver.
+	Solve,
+
+	/// Exit the program.
+	Exit,
+
+	/// Enter insert mode, in which letters are appended to the selected cell.
+	/// Only meaningful for modal keymaps.
+	InsertMode,
+
+	/// Leave insert mode. Only meaningful for modal keymaps.
+	NormalMode,
+
+	/// Show the keybindings.
+	Help,
+
+	/// Browse the history of played puzzles, in order to reopen one.
+	History,
+
+	/// Show statistics aggregated from the history of played puzzles.
+	Statistics,
+
+	/// Step through the decisions of the most recent solve.
+	#[cfg(feature = "trace-ui")]
+	Trace
+}
+
+impl Action
+{
+	/// Describe the action, for use in the help overlay.
+	///
+	/// # Arguments
+	///
+	/// * `messages` - The messages of the user interface.
+	///
+	/// # Returns
+	///
+	/// A short description of the action.
+	#[must_use]
+	pub fn description(&self, messages: &'static Messages) -> &'static str
+	{
+		match self
+		{
+			Action::MoveUp => messages.text("action.move-up"),
+			Action::MoveDown => messages.text("action.move-down"),
+			Action::MoveLeft => messages.text("action.move-left"),
+			Action::MoveRight => messages.text("action.move-right"),
+			Action::NextCell => messages.text("action.next-cell"),
+			Action::PreviousCell => messages.text("action.previous-cell"),
+			Action::SwapUp => messages.text("action.swap-up"),
+			Action::SwapDown => messages.text("action.swap-down"),
+			Action::SwapLeft => messages.text("action.swap-left"),
+			Action::SwapRight => messages.text("action.swap-right"),
+			Action::DeleteChar => messages.text("action.delete-char"),
+			Action::ClearCell => messages.text("action.clear-cell"),
+			Action::ClearAll => messages.text("action.clear-all"),
+			Action::BulkEntry => messages.text("action.bulk-entry"),
+			Action::Explain => messages.text("action.explain"),
+			Action::Shuffle => messages.text("action.shuffle"),
+			Action::Reveal => messages.text("action.reveal"),
+			Action::PageUp => messages.text("action.page-up"),
+			Action::PageDown => messages.text("action.page-down"),
+			Action::NextPlacement => messages.text("action.next-placement"),
+			Action::MarkBogus => messages.text("action.mark-bogus"),
+			Action::MarkFavorite => messages.text("action.mark-favorite"),
+			Action::Sort => messages.text("action.sort"),
+			Action::ExportCard => messages.text("action.export-card"),
+			Action::Replay => messages.text("action.replay"),
+			Action::Solve => messages.text("action.solve"),
+			Action::Exit => messages.text("action.exit"),
+			Action::InsertMode => messages.text("action.insert-mode"),
+			Action::NormalMode => messages.text("action.normal-mode"),
+			Action::Help => messages.text("action.help"),
+			Action::History => messages.text("action.history"),
+			Action::Statistics => messages.text("action.statistics"),
+			#[cfg(feature = "trace-ui")]
+			Action::Trace => messages.text("action.trace")
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Keys.                                    //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A single key press, together with its modifiers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyChord
+{
+	/// The key.
+	code: KeyCode,
+
+	/// The modifiers.
+	modifiers: KeyModifiers
+}
+
+impl KeyChord
+{
+	/// Construct a key chord.
+	///
+	/// # Arguments
+	///
+	/// * `code` - The key.
+	/// * `modifiers` - The modifiers.
+	///
+	/// # Returns
+	///
+	/// The normalized key chord.
+	pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self
+	{
+		// Shift is already implied by the case of a character and by the
+		// BackTab key itself, so don't require it to match.
+		let modifiers = match code
+		{
+			KeyCode::Char(_) | KeyCode::BackTab =>
+				modifiers - KeyModifiers::SHIFT,
+			_ => modifiers
+		};
+		Self { code, modifiers }
+	}
+
+	/// Construct an unmodified key chord.
+	///
+	/// # Arguments
+	///
+	/// * `code` - The key.
+	///
+	/// # Returns
+	///
+	/// The key chord.
+	#[inline]
+	fn plain(code: KeyCode) -> Self
+	{
+		Self::new(code, KeyModifiers::NONE)
+	}
+}
+
+impl From<KeyEvent> for KeyChord
+{
+	fn from(event: KeyEvent) -> Self
+	{
+		Self::new(event.code, event.modifiers)
+	}
+}
+
+impl Display for KeyChord
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		if self.modifiers.contains(KeyModifiers::CONTROL)
+		{
+			write!(f, "⌃")?;
+		}
+		if self.modifiers.contains(KeyModifiers::ALT)
+		{
+			write!(f, "⌥")?;
+		}
+		if self.modifiers.contains(KeyModifiers::SHIFT)
+		{
+			write!(f, "⇧")?;
+		}
+		match self.code
+		{
+			KeyCode::Up => write!(f, "↑"),
+			KeyCode::Down => write!(f, "↓"),
+			KeyCode::Left => write!(f, "←"),
+			KeyCode::Right => write!(f, "→"),
+			KeyCode::Tab => write!(f, "⇥"),
+			KeyCode::BackTab => write!(f, "⇧⇥"),
+			KeyCode::Backspace => write!(f, "⌫"),
+			KeyCode::Delete => write!(f, "⌦"),
+			KeyCode::Enter => write!(f, "↵"),
+			KeyCode::PageUp => write!(f, "⇞"),
+			KeyCode::PageDown => write!(f, "⇟"),
+			KeyCode::Esc => write!(f, "⎋"),
+			KeyCode::Char(' ') => write!(f, "␣"),
+			KeyCode::Char(c) => write!(f, "{}", c),
+			code => write!(f, "{}", code)
+		}
+	}
+}
+
+/// Parse a key sequence, such as `"ctrl+q"`, `"shift+delete"`, or `"Z Z"`.
+/// Chords are separated by whitespace. Each chord comprises optional
+/// modifiers (`ctrl`, `alt`, `shift`) and a key, joined by `+`. Keys are
+/// single characters or names, such as `esc`, `enter`, `tab`, `up`, or `f1`.
+///
+/// # Arguments
+///
+/// * `sequence` - The key sequence.
+///
+/// # Returns
+///
+/// The key chords of the sequence.
+///
+/// # Errors
+///
+/// If the sequence cannot be parsed, an [`ErrorKind::InvalidData`] is
+/// returned.
+pub fn parse_key_sequence(sequence: &str) -> Result<Vec<KeyChord>, io::Error>
+{
+	let invalid = || io::Error::new(
+		ErrorKind::InvalidData,
+		format!("invalid key sequence: {}", sequence)
+	);
+	let chords = sequence.split_whitespace()
+		.map(|chord| {
+			let mut parts = chord.split('+').collect::<Vec<_>>();
+			// A trailing empty part means that the key itself is `+`.
+			let key = match parts.pop()
+			{
+				Some("") => "+",
+				Some(key) => key,
+				None => return Err(invalid())
+			};
+			let mut modifiers = KeyModifiers::NONE;
+			for modifier in parts.iter().filter(|part| !part.is_empty())
+			{
+				modifiers |= match modifier.to_lowercase().as_str()
+				{
+					"ctrl" | "control" => KeyModifiers::CONTROL,
+					"alt" | "meta" => KeyModifiers::ALT,
+					"shift" => KeyModifiers::SHIFT,
+					_ => return Err(invalid())
+				};
+			}
+			let mut chars = key.chars();
+			let code = match (chars.next(), chars.next())
+			{
+				(Some(c), None) => KeyCode::Char(c),
+				_ => match key.to_lowercase().as_str()
+				{
+					"esc" | "escape" => KeyCode::Esc,
+					"enter" | "return" => KeyCode::Enter,
+					"tab" if modifiers.contains(KeyModifiers::SHIFT) =>
+						KeyCode::BackTab,
+					"tab" => KeyCode::Tab,
+					"backtab" => KeyCode::BackTab,
+					"backspace" => KeyCode::Backspace,
+					"delete" | "del" => KeyCode::Delete,
+					"insert" | "ins" => KeyCode::Insert,
+					"up" => KeyCode::Up,
+					"down" => KeyCode::Down,
+					"left" => KeyCode::Left,
+					"right" => KeyCode::Right,
+					"home" => KeyCode::Home,
+					"end" => KeyCode::End,
+					"pageup" => KeyCode::PageUp,
+					"pagedown" => KeyCode::PageDown,
+					"space" => KeyCode::Char(' '),
+					name => match name.strip_prefix('f')
+						.and_then(|n| n.parse::<u8>().ok())
+					{
+						Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+						_ => return Err(invalid())
+					}
+				}
+			};
+			Ok(KeyChord::new(code, modifiers))
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+	if chords.is_empty()
+	{
+		return Err(invalid())
+	}
+	Ok(chords)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Keymaps.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The names of the built-in keymaps.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeymapPreset
+{
+	/// Arrow and editing keys. Letters are always appended to the selected
+	/// cell.
+	#[default]
+	Default,
+
+	/// Modal, vim-style keys: `hjkl` navigation, `x` to clear, `ZZ` to exit, and
+	/// `i` to type fragments.
+	Vim
+}
+
+/// The editing mode of a modal keymap.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Mode
+{
+	/// Keys perform actions.
+	Normal,
+
+	/// Letters are appended to the selected cell.
+	Insert
+}
+
+/// The result of feeding a key event to a [`Keymap`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Resolution
+{
+	/// The key completed a binding for the specified action.
+	Action(Action),
+
+	/// The key started (or continued) a multi-key binding.
+	Pending,
+
+	/// The key is not bound to any action.
+	Unbound(KeyEvent)
+}
+
+/// A binding of a key sequence to an action.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Binding
+{
+	/// The key sequence.
+	keys: Vec<KeyChord>,
+
+	/// The action.
+	action: Action
+}
+
+/// A keymap binds key sequences to [actions](Action). A modal keymap has
+/// separate bindings for [normal](Mode::Normal) and [insert](Mode::Insert)
+/// modes; a non-modal keymap is always in insert mode, and uses the same
+/// bindings throughout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[must_use]
+pub struct Keymap
+{
+	/// The bindings in normal mode, which are also the bindings of a
+	/// non-modal keymap.
+	normal: Vec<Binding>,
+
+	/// The bindings in insert mode. Only used by modal keymaps.
+	insert: Vec<Binding>,
+
+	/// Whether the keymap is modal.
+	is_modal: bool,
+
+	/// The current editing mode.
+	mode: Mode,
+
+	/// The keys of an incomplete multi-key binding.
+	pending: Vec<KeyChord>
+}
+
+impl Keymap
+{
+	/// Construct the built-in keymap with the given name.
+	///
+	/// # Arguments
+	///
+	/// * `preset` - The name of the keymap.
+	///
+	/// # Returns
+	///
+	/// The requested keymap.
+	pub fn new(preset: KeymapPreset) -> Self
+	{
+		use Action::*;
+		use KeyCode::*;
+		let bind = |keys: &[KeyChord], action| Binding {
+			keys: keys.to_vec(),
+			action
+		};
+		let plain = KeyChord::plain;
+		let shift = |code| KeyChord::new(code, KeyModifiers::SHIFT);
+		let ctrl = |code| KeyChord::new(code, KeyModifiers::CONTROL);
+		let editing = vec![
+			bind(&[plain(Up)], MoveUp),
+			bind(&[plain(Down)], MoveDown),
+			bind(&[plain(Left)], MoveLeft),
+			bind(&[plain(Right)], MoveRight),
+			bind(&[plain(Tab)], NextCell),
+			bind(&[plain(BackTab)], PreviousCell),
+			bind(&[shift(Up)], SwapUp),
+			bind(&[shift(Down)], SwapDown),
+			bind(&[shift(Left)], SwapLeft),
+			bind(&[shift(Right)], SwapRight),
+			bind(&[plain(Backspace)], DeleteChar),
+			bind(&[shift(Delete)], ClearAll),
+			bind(&[plain(Delete)], ClearCell),
+			bind(&[ctrl(Char('b'))], BulkEntry),
+			bind(&[ctrl(Char('e'))], Explain),
+			bind(&[ctrl(Char('r'))], Shuffle),
+			bind(&[ctrl(Char('g'))], Reveal),
+			bind(&[plain(KeyCode::PageUp)], Action::PageUp),
+			bind(&[plain(KeyCode::PageDown)], Action::PageDown),
+			bind(&[plain(Char(' '))], NextPlacement),
+			bind(&[ctrl(Char('x'))], MarkBogus),
+			bind(&[ctrl(Char('f'))], MarkFavorite),
+			bind(&[ctrl(Char('s'))], Sort),
+			bind(&[ctrl(Char('y'))], ExportCard),
+			bind(&[ctrl(Char('p'))], Replay),
+			bind(&[plain(Enter)], Solve),
+			bind(&[plain(Char('?'))], Help),
+			bind(&[plain(F(1))], Help),
+			bind(&[plain(F(2))], History),
+			bind(&[plain(F(3))], Statistics),
+			#[cfg(feature = "trace-ui")]
+			bind(&[plain(F(4))], Trace)
+		];
+		match preset
+		{
+			KeymapPreset::Default =>
+			{
+				let mut normal = editing;
+				normal.push(bind(&[plain(Esc)], Exit));
+				Self {
+					normal,
+					insert: Vec::new(),
+					is_modal: false,
+					mode: Mode::Insert,
+					pending: Vec::new()
+				}
+			},
+			KeymapPreset::Vim =>
+			{
+				let normal = vec![
+					bind(&[plain(Char('k'))], MoveUp),
+					bind(&[plain(Char('j'))], MoveDown),
+					bind(&[plain(Char('h'))], MoveLeft),
+					bind(&[plain(Char('l'))], MoveRight),
+					bind(&[plain(Char('w'))], NextCell),
+					bind(&[plain(Char('b'))], PreviousCell),
+					bind(&[plain(Char('X'))], DeleteChar),
+					bind(&[plain(Char('x'))], ClearCell),
+					bind(&[plain(Char('d')), plain(Char('d'))], ClearAll),
+					bind(&[plain(Char('B'))], BulkEntry),
+					bind(&[plain(Char('e'))], Explain),
+					bind(&[plain(Char('s'))], Shuffle),
+					bind(&[plain(Char('R'))], Reveal),
+					bind(&[plain(Char('i'))], InsertMode),
+					bind(&[plain(Char('a'))], InsertMode),
+					bind(&[plain(Char('Z')), plain(Char('Z'))], Exit),
+					bind(&[plain(Up)], MoveUp),
+					bind(&[plain(Down)], MoveDown),
+					bind(&[plain(Left)], MoveLeft),
+					bind(&[plain(Right)], MoveRight),
+					bind(&[plain(Tab)], NextCell),
+					bind(&[plain(BackTab)], PreviousCell),
+					bind(&[shift(Up)], SwapUp),
+					bind(&[shift(Down)], SwapDown),
+					bind(&[shift(Left)], SwapLeft),
+					bind(&[shift(Right)], SwapRight),
+					bind(&[plain(KeyCode::PageUp)], Action::PageUp),
+					bind(&[plain(KeyCode::PageDown)], Action::PageDown),
+					bind(&[ctrl(Char('b'))], Action::PageUp),
+					bind(&[ctrl(Char('f'))], Action::PageDown),
+					bind(&[plain(Char('n'))], NextPlacement),
+					bind(&[plain(Char(' '))], NextPlacement),
+					bind(&[plain(Char('D'))], MarkBogus),
+					bind(&[plain(Char('F'))], MarkFavorite),
+					bind(&[plain(Char('o'))], Sort),
+					bind(&[plain(Char('y'))], ExportCard),
+					bind(&[plain(Char('p'))], Replay),
+					bind(&[plain(Enter)], Solve),
+					bind(&[plain(Char('?'))], Help),
+					bind(&[plain(F(1))], Help),
+					bind(&[plain(Char('H'))], History),
+					bind(&[plain(F(2))], History),
+					bind(&[plain(Char('S'))], Statistics),
+					bind(&[plain(F(3))], Statistics),
+					#[cfg(feature = "trace-ui")]
+					bind(&[plain(F(4))], Trace)
+				];
+				let mut insert = editing;
+				insert.push(bind(&[plain(Esc)], NormalMode));
+				Self {
+					normal,
+					insert,
+					is_modal: true,
+					mode: Mode::Normal,
+					pending: Vec::new()
+				}
+			}
+		}
+	}
+
+	/// Rebind actions according to the given overrides. Each override replaces
+	/// every existing binding of its action in normal mode (or in the only mode
+	/// of a non-modal keymap).
+	///
+	/// # Arguments
+	///
+	/// * `overrides` - The overrides, as a map from actions to
+	///   [key sequences](parse_key_sequence).
+	///
+	/// # Returns
+	///
+	/// The keymap, with the overrides applied.
+	///
+	/// # Errors
+	///
+	/// If any key sequence cannot be parsed, an [`ErrorKind::InvalidData`] is
+	/// returned.
+	pub fn with_overrides(
+		mut self,
+		overrides: &BTreeMap<Action, Vec<String>>
+	) -> Result<Self, io::Error>
+	{
+		for (action, sequences) in overrides
+		{
+			self.normal.retain(|binding| binding.action != *action);
+			for sequence in sequences
+			{
+				self.normal.push(Binding {
+					keys: parse_key_sequence(sequence)?,
+					action: *action
+				});
+			}
+		}
+		Ok(self)
+	}
+
+	/// Get the current editing mode.
+	///
+	/// # Returns
+	///
+	/// The current editing mode.
+	#[inline]
+	#[must_use]
+	pub fn mode(&self) -> Mode
+	{
+		self.mode
+	}
+
+	/// Check whether the keymap is modal.
+	///
+	/// # Returns
+	///
+	/// `true` if the keymap is modal, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_modal(&self) -> bool
+	{
+		self.is_modal
+	}
+
+	/// Set the editing mode. Non-modal keymaps are always in insert mode, so
+	/// this has no effect on them.
+	///
+	/// # Arguments
+	///
+	/// * `mode` - The new editing mode.
+	pub fn set_mode(&mut self, mode: Mode)
+	{
+		if self.is_modal
+		{
+			self.mode = mode;
+			self.pending.clear();
+		}
+	}
+
+	/// Feed a key event to the keymap.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The key event.
+	///
+	/// # Returns
+	///
+	/// The resolution of the key event.
+	pub fn resolve(&mut self, event: KeyEvent) -> Resolution
+	{
+		self.pending.push(KeyChord::from(event));
+		let bindings = self.bindings();
+		if let Some(binding) = bindings.iter()
+			.find(|binding| binding.keys == self.pending)
+		{
+			let action = binding.action;
+			self.pending.clear();
+			return Resolution::Action(action)
+		}
+		if bindings.iter().any(|binding| binding.keys.starts_with(&self.pending))
+		{
+			return Resolution::Pending
+		}
+		if self.pending.len() > 1
+		{
+			// The key broke an incomplete binding, so start over with just this
+			// key.
+			self.pending.clear();
+			return self.resolve(event)
+		}
+		self.pending.clear();
+		Resolution::Unbound(event)
+	}
+
+	/// Describe the keys bound to the specified action in the current mode,
+	/// for use in hints.
+	///
+	/// # Arguments
+	///
+	/// * `action` - The action.
+	///
+	/// # Returns
+	///
+	/// The label of the first key sequence bound to the action, if any.
+	#[must_use]
+	pub fn label(&self, action: Action) -> Option<String>
+	{
+		self.bindings()
+			.iter()
+			.find(|binding| binding.action == action)
+			.map(|binding| binding.keys.iter().map(|k| k.to_string()).collect())
+	}
+
+	/// Describe every key sequence bound to the specified action in the
+	/// current mode, for use in the help overlay.
+	///
+	/// # Arguments
+	///
+	/// * `action` - The action.
+	///
+	/// # Returns
+	///
+	/// The labels of the key sequences bound to the action, in binding order.
+	#[must_use]
+	pub fn labels(&self, action: Action) -> Vec<String>
+	{
+		self.bindings()
+			.iter()
+			.filter(|binding| binding.action == action)
+			.map(|binding| binding.keys.iter().map(|k| k.to_string()).collect())
+			.collect()
+	}
+
+	/// Get the bindings of the current mode.
+	///
+	/// # Returns
+	///
+	/// The bindings of the current mode.
+	#[must_use]
+	fn bindings(&self) -> &[Binding]
+	{
+		match (self.is_modal, self.mode)
+		{
+			(true, Mode::Insert) => &self.insert,
+			_ => &self.normal
+		}
+	}
+}
+
+impl Default for Keymap
+{
+	fn default() -> Self
+	{
+		Self::new(KeymapPreset::default())
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::collections::BTreeMap;
+
+	use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+	use crate::keymap::{
+		parse_key_sequence, Action, KeyChord, Keymap, KeymapPreset, Mode,
+		Resolution
+	};
+
+	/// Ensure that key sequences are parsed correctly.
+	#[test]
+	fn test_parse_key_sequence()
+	{
+		let cases = [
+			("esc", vec![KeyChord::plain(KeyCode::Esc)]),
+			(
+				"ctrl+q",
+				vec![KeyChord::new(KeyCode::Char('q'), KeyModifiers::CONTROL)]
+			),
+			("shift+tab", vec![KeyChord::plain(KeyCode::BackTab)]),
+			(
+				"shift+delete",
+				vec![KeyChord::new(KeyCode::Delete, KeyModifiers::SHIFT)]
+			),
+			(
+				"Z Z",
+				vec![
+					KeyChord::plain(KeyCode::Char('Z')),
+					KeyChord::plain(KeyCode::Char('Z'))
+				]
+			),
+			("f1", vec![KeyChord::plain(KeyCode::F(1))]),
+			("+", vec![KeyChord::plain(KeyCode::Char('+'))])
+		];
+		for (sequence, expected) in cases
+		{
+			assert_eq!(parse_key_sequence(sequence).unwrap(), expected);
+		}
+		assert!(parse_key_sequence("").is_err());
+		assert!(parse_key_sequence("hyper+q").is_err());
+		assert!(parse_key_sequence("f13").is_err());
+	}
+
+	/// Ensure that the default keymap resolves keys directly to actions, and
+	/// leaves letters unbound.
+	#[test]
+	fn test_default()
+	{
+		let mut keymap = Keymap::default();
+		assert!(!keymap.is_modal());
+		assert_eq!(keymap.mode(), Mode::Insert);
+		assert_eq!(
+			keymap.resolve(KeyCode::Esc.into()),
+			Resolution::Action(Action::Exit)
+		);
+		assert_eq!(
+			keymap.resolve(KeyEvent::new(KeyCode::Delete, KeyModifiers::SHIFT)),
+			Resolution::Action(Action::ClearAll)
+		);
+		assert_eq!(
+			keymap.resolve(KeyCode::Char('z').into()),
+			Resolution::Unbound(KeyCode::Char('z').into())
+		);
+		assert_eq!(keymap.label(Action::Solve), Some("↵".to_string()));
+		assert_eq!(
+			keymap.labels(Action::Help),
+			vec!["?".to_string(), "F1".to_string()]
+		);
+		assert_eq!(
+			keymap.resolve(KeyCode::F(2).into()),
+			Resolution::Action(Action::History)
+		);
+	}
+
+	/// Ensure that the vim keymap honors modes and multi-key bindings.
+	#[test]
+	fn test_vim()
+	{
+		let mut keymap = Keymap::new(KeymapPreset::Vim);
+		assert!(keymap.is_modal());
+		assert_eq!(keymap.mode(), Mode::Normal);
+		assert_eq!(
+			keymap.resolve(KeyCode::Char('j').into()),
+			Resolution::Action(Action::MoveDown)
+		);
+		assert_eq!(keymap.resolve(KeyCode::Char('Z').into()), Resolution::Pending);
+		assert_eq!(
+			keymap.resolve(KeyCode::Char('Z').into()),
+			Resolution::Action(Action::Exit)
+		);
+		// A broken sequence falls back to the last key alone.
+		assert_eq!(keymap.resolve(KeyCode::Char('Z').into()), Resolution::Pending);
+		assert_eq!(
+			keymap.resolve(KeyCode::Char('k').into()),
+			Resolution::Action(Action::MoveUp)
+		);
+		assert_eq!(keymap.label(Action::Exit), Some("ZZ".to_string()));
+		assert_eq!(
+			keymap.resolve(KeyCode::Char('i').into()),
+			Resolution::Action(Action::InsertMode)
+		);
+		keymap.set_mode(Mode::Insert);
+		assert_eq!(
+			keymap.resolve(KeyCode::Char('j').into()),
+			Resolution::Unbound(KeyCode::Char('j').into())
+		);
+		assert_eq!(
+			keymap.resolve(KeyCode::Esc.into()),
+			Resolution::Action(Action::NormalMode)
+		);
+	}
+
+	/// Ensure that overrides replace the bindings of their actions.
+	#[test]
+	fn test_overrides()
+	{
+		let mut overrides = BTreeMap::new();
+		overrides.insert(Action::Exit, vec!["ctrl+q".to_string()]);
+		let mut keymap = Keymap::default().with_overrides(&overrides).unwrap();
+		assert_eq!(
+			keymap.resolve(KeyCode::Esc.into()),
+			Resolution::Unbound(KeyCode::Esc.into())
+		);
+		assert_eq!(
+			keymap.resolve(
+				KeyEvent::new(KeyCode::Char('q'), KeyModifiers::CONTROL)
+			),
+			Resolution::Action(Action::Exit)
+		);
+		assert_eq!(keymap.label(Action::Exit), Some("⌃q".to_string()));
+
+		overrides.insert(Action::Solve, vec!["hyper+s".to_string()]);
+		assert!(Keymap::default().with_overrides(&overrides).is_err());
+	}
+}
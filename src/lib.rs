@@ -1,4 +1,10 @@
 #![allow(dead_code)]
 
 pub mod dictionary;
+pub mod generator;
+pub mod inventory;
+#[cfg(feature = "scripting")]
+pub mod script;
 pub mod solver;
+#[cfg(feature = "trace-ui")]
+pub mod trace;
@@ -0,0 +1,273 @@
+//! # Dictionary loader
+//!
+//! Herein is support for loading a dictionary on a worker thread, so that the
+//! TUI remains responsive while a large dictionary is read.
+
+use std::{
+	io,
+	path::{Path, PathBuf},
+	sync::Arc,
+	thread::{self, JoinHandle},
+	time::Instant
+};
+
+use quartiles_solver::dictionary::{Composition, Dictionary, Progress};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Loaders.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The frames of the spinner that indicates that a dictionary is loading.
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// How long (in ms) to show each frame of the [spinner](SPINNER).
+const SPINNER_FRAME_MS: u128 = 80;
+
+/// A dictionary that is [opening](Dictionary::open_with_progress) on a worker
+/// thread.
+#[derive(Debug)]
+#[must_use]
+pub struct Loader
+{
+	/// The directory containing the dictionary files.
+	directory: PathBuf,
+
+	/// The name of the dictionary.
+	name: String,
+
+	/// When loading started.
+	started: Instant,
+
+	/// The progress of loading, which the worker thread updates.
+	progress: Arc<Progress>,
+
+	/// The worker thread, which answers the dictionary.
+	worker: JoinHandle<Result<Dictionary, io::Error>>
+}
+
+impl Loader
+{
+	/// Start loading the specified dictionary on a new worker thread. Once
+	/// the dictionary has opened, it is [composed](Composition::apply) as
+	/// specified.
+	///
+	/// # Arguments
+	///
+	/// * `directory` - The directory containing the dictionary files.
+	/// * `name` - The name of the dictionary.
+	/// * `composition` - How to compose the dictionary.
+	///
+	/// # Returns
+	///
+	/// The loader.
+	pub fn spawn(
+		directory: PathBuf,
+		name: String,
+		composition: Composition
+	) -> Self
+	{
+		let progress = Arc::new(Progress::default());
+		let worker = {
+			let directory = directory.clone();
+			let name = name.clone();
+			let progress = Arc::clone(&progress);
+			thread::spawn(move || {
+				let mut dictionary = Dictionary::open_with_progress(
+					directory,
+					&name,
+					&progress
+				)?;
+				composition.apply(&mut dictionary)?;
+				Ok(dictionary)
+			})
+		};
+		Self {
+			directory,
+			name,
+			started: Instant::now(),
+			progress,
+			worker
+		}
+	}
+
+	/// Answer the directory containing the dictionary files.
+	///
+	/// # Returns
+	///
+	/// The directory.
+	#[inline]
+	#[must_use]
+	pub fn directory(&self) -> &Path
+	{
+		&self.directory
+	}
+
+	/// Answer the name of the dictionary.
+	///
+	/// # Returns
+	///
+	/// The name.
+	#[inline]
+	#[must_use]
+	pub fn name(&self) -> &str
+	{
+		&self.name
+	}
+
+	/// Answer the progress of loading.
+	///
+	/// # Returns
+	///
+	/// The progress.
+	#[inline]
+	#[must_use]
+	pub fn progress(&self) -> &Progress
+	{
+		&self.progress
+	}
+
+	/// Cancel loading. The worker thread stops soon afterward, whereupon
+	/// [`join`](Self::join) answers an
+	/// [`ErrorKind::Interrupted`](io::ErrorKind::Interrupted).
+	#[inline]
+	pub fn cancel(&self)
+	{
+		self.progress.cancel();
+	}
+
+	/// Check whether the worker thread has finished, such that
+	/// [`join`](Self::join) will not block.
+	///
+	/// # Returns
+	///
+	/// `true` if loading has finished, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_finished(&self) -> bool
+	{
+		self.worker.is_finished()
+	}
+
+	/// Answer the current frame of the spinner, which advances with the time
+	/// elapsed since loading started.
+	///
+	/// # Returns
+	///
+	/// The frame.
+	#[must_use]
+	pub fn spinner(&self) -> char
+	{
+		spinner(self.started)
+	}
+
+	/// Wait for the worker thread to finish, and answer the dictionary.
+	///
+	/// # Returns
+	///
+	/// The dictionary.
+	///
+	/// # Errors
+	///
+	/// * If the dictionary cannot be opened, the error is returned.
+	/// * If the worker thread panicked, an
+	///   [`ErrorKind::Other`](io::ErrorKind::Other) is returned.
+	pub fn join(self) -> Result<Dictionary, io::Error>
+	{
+		self.worker.join().unwrap_or_else(|_| {
+			Err(io::Error::other(
+				format!("Dictionary loader panicked: {}", self.name)
+			))
+		})
+	}
+}
+
+/// Answer the current frame of the [spinner](SPINNER), which advances with
+/// the time elapsed since the specified instant.
+///
+/// # Arguments
+///
+/// * `started` - When the work behind the spinner started.
+///
+/// # Returns
+///
+/// The frame.
+#[must_use]
+pub fn spinner(started: Instant) -> char
+{
+	let frame = started.elapsed().as_millis() / SPINNER_FRAME_MS;
+	SPINNER[frame as usize % SPINNER.len()]
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::{fs, io::ErrorKind, path::PathBuf, thread};
+
+	use quartiles_solver::dictionary::Composition;
+
+	use crate::loader::Loader;
+
+	/// Ensure that a dictionary loads on the worker thread, reporting its
+	/// progress, and that a missing dictionary produces an error.
+	#[test]
+	fn test_load()
+	{
+		let loader = Loader::spawn(
+			PathBuf::from("dict"),
+			"english".into(),
+			Composition::default()
+		);
+		assert_eq!(loader.name(), "english");
+		while !loader.is_finished()
+		{
+			thread::yield_now();
+		}
+		let progress = loader.progress();
+		assert!(progress.total_bytes() > 0);
+		assert_eq!(progress.bytes_read(), progress.total_bytes());
+		assert!(progress.words_inserted() > 0);
+		assert!(loader.join().unwrap().contains("truth"));
+
+		let loader = Loader::spawn(
+			PathBuf::from("dict"),
+			"missing".into(),
+			Composition::default()
+		);
+		assert!(loader.join().is_err());
+	}
+
+	/// Ensure that cancelling a load produces an interruption, and that the
+	/// exclusion lists apply to the loaded dictionary.
+	#[test]
+	fn test_cancel_and_exclude()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		fs::write(dir.path().join("tiny.txt"), "cross\nword\n").unwrap();
+		let exclude = dir.path().join("exclude.txt");
+		fs::write(&exclude, "word\n").unwrap();
+		let loader = Loader::spawn(
+			dir.path().to_path_buf(),
+			"tiny".into(),
+			Composition { exclude: vec![exclude], ..Default::default() }
+		);
+		let dictionary = loader.join().unwrap();
+		assert!(dictionary.contains("cross"));
+		assert!(!dictionary.contains("word"));
+
+		let loader = Loader::spawn(
+			PathBuf::from("dict"),
+			"english".into(),
+			Composition::default()
+		);
+		loader.cancel();
+		// The load may finish before the cancellation is noticed.
+		if let Err(e) = loader.join()
+		{
+			assert_eq!(e.kind(), ErrorKind::Interrupted);
+		}
+	}
+}
@@ -0,0 +1,353 @@
+//! # Localization
+//!
+//! Herein is support for translating the text of the user interface. Every
+//! title, hint, and message of the TUI is looked up by key in the
+//! [messages](Messages) of the selected [language](Lang). The messages of
+//! each language are a simple TOML bundle, embedded in the executable, whose
+//! tables group the messages of a screen, e.g., the key `menu.title` names
+//! `title` in the table `[menu]`. A message may name placeholders in braces,
+//! e.g., `{count}`, which are filled in when the message is
+//! [formatted](Messages::text_with). A message missing from a bundle falls
+//! back to English.
+
+use std::{collections::HashMap, fmt::Display, sync::OnceLock};
+
+use clap::ValueEnum;
+use quartiles_solver::dictionary::Language;
+use serde::{Deserialize, Serialize};
+use toml::{Table, Value};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Languages.                                 //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The languages of the user interface, which match the languages of the
+/// dictionaries.
+#[derive(
+	Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize,
+	ValueEnum
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Lang
+{
+	/// English.
+	#[default]
+	En,
+
+	/// French.
+	Fr,
+
+	/// German.
+	De,
+
+	/// Spanish.
+	Es
+}
+
+impl Lang
+{
+	/// Every language, in declaration order.
+	pub const ALL: [Lang; 4] = [Lang::En, Lang::Fr, Lang::De, Lang::Es];
+
+	/// Answer the bundle of the language, i.e., the source of its messages.
+	///
+	/// # Returns
+	///
+	/// The bundle, in TOML.
+	fn bundle(self) -> &'static str
+	{
+		match self
+		{
+			Lang::En => include_str!("../locales/en.toml"),
+			Lang::Fr => include_str!("../locales/fr.toml"),
+			Lang::De => include_str!("../locales/de.toml"),
+			Lang::Es => include_str!("../locales/es.toml")
+		}
+	}
+}
+
+impl From<Language> for Lang
+{
+	fn from(language: Language) -> Self
+	{
+		match language
+		{
+			Language::English => Lang::En,
+			Language::French => Lang::Fr,
+			Language::German => Lang::De,
+			Language::Spanish => Lang::Es
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Messages.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The messages of a [language](Lang), by key.
+#[derive(Debug)]
+pub struct Messages
+{
+	/// The messages, by dotted key, e.g., `menu.title`.
+	table: HashMap<String, String>,
+
+	/// The messages to consult for a key that this language lacks, if any.
+	fallback: Option<&'static Messages>
+}
+
+impl Messages
+{
+	/// Answer the messages of the specified language, which are parsed from
+	/// the embedded bundle on first use.
+	///
+	/// # Arguments
+	///
+	/// * `lang` - The language.
+	///
+	/// # Returns
+	///
+	/// The messages.
+	///
+	/// # Panics
+	///
+	/// If the embedded bundle is malformed.
+	pub fn of(lang: Lang) -> &'static Messages
+	{
+		static MESSAGES: [OnceLock<Messages>; 4] =
+			[const { OnceLock::new() }; 4];
+		MESSAGES[lang as usize].get_or_init(|| {
+			let table = parse(lang.bundle()).unwrap_or_else(|e| {
+				panic!("Malformed message bundle: {:?}: {}", lang, e)
+			});
+			let fallback = match lang
+			{
+				Lang::En => None,
+				_ => Some(Messages::of(Lang::En))
+			};
+			Messages { table, fallback }
+		})
+	}
+
+	/// Answer the message with the specified key, falling back to English if
+	/// the language lacks it, and to the key itself if English lacks it, too.
+	///
+	/// # Arguments
+	///
+	/// * `key` - The key of the message, e.g., `menu.title`.
+	///
+	/// # Returns
+	///
+	/// The message.
+	#[must_use]
+	pub fn text<'a>(&'a self, key: &'a str) -> &'a str
+	{
+		match self.table.get(key)
+		{
+			Some(message) => message,
+			None => match self.fallback
+			{
+				Some(fallback) => fallback.text(key),
+				None => key
+			}
+		}
+	}
+
+	/// Answer the message with the specified key, as for
+	/// [`text`](Self::text), with each of its placeholders filled in. A
+	/// placeholder that has no argument is left as is.
+	///
+	/// # Arguments
+	///
+	/// * `key` - The key of the message.
+	/// * `args` - The arguments, as pairs of a placeholder name and its value,
+	///   e.g., `("count", &3)` fills in `{count}`.
+	///
+	/// # Returns
+	///
+	/// The formatted message.
+	#[must_use]
+	pub fn text_with(&self, key: &str, args: &[(&str, &dyn Display)]) -> String
+	{
+		let mut message = String::new();
+		let mut rest = self.text(key);
+		while let Some(start) = rest.find('{')
+		{
+			message.push_str(&rest[..start]);
+			let Some(end) = rest[start..].find('}').map(|end| start + end)
+			else
+			{
+				break
+			};
+			let name = &rest[start + 1..end];
+			match args.iter().find(|(placeholder, _)| *placeholder == name)
+			{
+				Some((_, value)) => message.push_str(&value.to_string()),
+				None => message.push_str(&rest[start..=end])
+			}
+			rest = &rest[end + 1..];
+		}
+		message.push_str(rest);
+		message
+	}
+}
+
+/// Parse the specified bundle into messages, keyed by the dotted path of each
+/// message through the nested tables.
+///
+/// # Arguments
+///
+/// * `source` - The bundle, in TOML.
+///
+/// # Returns
+///
+/// The messages.
+///
+/// # Errors
+///
+/// If the bundle isn't valid TOML, or holds a value that is neither a string
+/// nor a table, an error is returned.
+fn parse(source: &str) -> Result<HashMap<String, String>, String>
+{
+	/// Flatten the specified table into the messages, prefixing each key with
+	/// the specified path.
+	fn flatten(
+		table: Table,
+		path: &str,
+		messages: &mut HashMap<String, String>
+	) -> Result<(), String>
+	{
+		for (key, value) in table
+		{
+			let key = match path.is_empty()
+			{
+				true => key,
+				false => format!("{}.{}", path, key)
+			};
+			match value
+			{
+				Value::String(message) =>
+				{
+					messages.insert(key, message);
+				},
+				Value::Table(table) => flatten(table, &key, messages)?,
+				_ => return Err(format!("Not a message: {}", key))
+			}
+		}
+		Ok(())
+	}
+	let table = source.parse::<Table>().map_err(|e| e.to_string())?;
+	let mut messages = HashMap::new();
+	flatten(table, "", &mut messages)?;
+	Ok(messages)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::collections::BTreeSet;
+
+	use crate::locale::{parse, Lang, Messages};
+
+	/// Answer the names of the placeholders of the specified message.
+	///
+	/// # Arguments
+	///
+	/// * `message` - The message.
+	///
+	/// # Returns
+	///
+	/// The names of the placeholders.
+	fn placeholders(message: &str) -> BTreeSet<&str>
+	{
+		message.split('{')
+			.skip(1)
+			.filter_map(|part| part.split_once('}'))
+			.map(|(name, _)| name)
+			.collect()
+	}
+
+	/// Ensure that messages are looked up by dotted key, that a missing
+	/// message falls back to English and then to its key, and that
+	/// placeholders are filled in.
+	#[test]
+	fn test_messages()
+	{
+		let english = Messages::of(Lang::En);
+		let german = Messages::of(Lang::De);
+		assert_eq!(english.text("menu.title"), "Quartiles");
+		assert_eq!(german.text("status.menu"), "Menü");
+		assert_eq!(german.text("no.such.key"), "no.such.key");
+		assert_eq!(
+			english.text_with("status.words", &[("count", &17)]),
+			"17 words"
+		);
+		assert_eq!(
+			english.text_with("status.words", &[]),
+			"{count} words"
+		);
+		let messages = parse("[a]\nb = \"c\"\n[a.d]\ne = \"f\"\n").unwrap();
+		assert_eq!(messages["a.b"], "c");
+		assert_eq!(messages["a.d.e"], "f");
+		assert!(parse("a = 1\n").is_err());
+	}
+
+	/// Ensure that every bundle parses, that every language translates
+	/// exactly the messages of English, with the same placeholders, and that
+	/// every key that the application looks up exists.
+	#[test]
+	fn test_bundles()
+	{
+		let english = parse(Lang::En.bundle()).unwrap();
+		for lang in Lang::ALL
+		{
+			let messages = parse(lang.bundle()).unwrap();
+			let missing = english.keys()
+				.filter(|key| !messages.contains_key(*key))
+				.collect::<Vec<_>>();
+			assert!(missing.is_empty(), "{:?} lacks {:?}", lang, missing);
+			for (key, message) in &messages
+			{
+				let Some(original) = english.get(key)
+				else
+				{
+					panic!("{:?} has an unknown key: {}", lang, key)
+				};
+				assert_eq!(
+					placeholders(message),
+					placeholders(original),
+					"{:?}: {}",
+					lang,
+					key
+				);
+			}
+		}
+		let sources = [
+			include_str!("app.rs"),
+			include_str!("app/history.rs"),
+			include_str!("app/menu.rs"),
+			include_str!("app/play.rs"),
+			include_str!("app/settings.rs"),
+			include_str!("app/statistics.rs"),
+			include_str!("app/trace.rs"),
+			include_str!("keymap.rs"),
+			include_str!("setup.rs")
+		];
+		for source in sources
+		{
+			for call in [".text(", ".text_with("]
+			{
+				for (index, _) in source.match_indices(call)
+				{
+					let rest = source[index + call.len()..].trim_start();
+					let Some(rest) = rest.strip_prefix('"') else { continue };
+					let key = &rest[..rest.find('"').unwrap()];
+					assert!(english.contains_key(key), "Unknown key: {}", key);
+				}
+			}
+		}
+	}
+}
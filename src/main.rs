@@ -16,102 +16,1863 @@
 #![allow(dead_code)]
 #![allow(uncommon_codepoints)]
 
+mod advisor;
 mod app;
+mod batch;
+mod bench;
+mod card;
+mod config;
+mod curation;
+mod daily;
+mod driver;
 mod dictionary;
+#[cfg(feature = "graphics")]
+mod graphics;
+mod history;
+mod html;
+mod keymap;
+mod loader;
+mod locale;
+mod manual;
+mod paths;
+mod play;
+mod replay;
+mod setup;
 mod solver;
+mod state;
+mod theme;
+#[cfg(feature = "trace-ui")]
+mod trace;
 mod tui;
 
-use std::panic;
+use std::{
+	env,
+	fs::{self, File, OpenOptions},
+	io::{self, stderr, stdin, BufReader, BufWriter, IsTerminal, Read, Write},
+	panic,
+	process::ExitCode,
+	path::{Path, PathBuf},
+	rc::Rc,
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH}
+};
 
-use clap::{Parser, Subcommand};
-use log::{debug, trace};
+use clap::{FromArgMatches, Parser, Subcommand, ValueEnum};
+use env_logger::{Builder, Env, Target, WriteStyle};
+use log::LevelFilter;
+use ratatui::backend::CrosstermBackend;
+use tracing::{debug, trace, warn};
+#[cfg(feature = "chrome-trace")]
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+#[cfg(feature = "chrome-trace")]
+use tracing_subscriber::{
+	filter::LevelFilter as TraceLevelFilter,
+	layer::SubscriberExt,
+	Layer
+};
 
-use tui::tui;
-use quartiles_solver::dictionary::Dictionary;
+use app::{App, Outcome, EXIT_FAILED, EXIT_SOLVED, EXIT_UNSOLVED};
+use batch::{compare, parse_board, Batch, BatchFormat};
+use card::CardTarget;
+use config::Config;
+use daily::Date;
+use history::{Provenance, Table};
+use keymap::Keymap;
+use loader::Loader;
+use locale::Lang;
+use setup::Wizard;
+use theme::{Theme, ThemeName};
+use tui::{tui, with_console};
+use quartiles_solver::{
+	dictionary::{
+		normalize_word,
+		Composition,
+		Dictionary,
+		Header,
+		Inclusion,
+		InputFormat,
+		Language,
+		Normalization,
+		Pattern,
+		Progress,
+		Source,
+		MAX_WORD_LEN,
+		MIN_WORD_LEN
+	},
+	generator::{Difficulty, Generator},
+	inventory::Inventory,
+	solver::{
+		closest_words,
+		explain,
+		lookup,
+		verify,
+		Fragment,
+		Solver,
+		WordFilter
+	}
+};
+#[cfg(feature = "scripting")]
+use quartiles_solver::script::ScriptFilter;
 
 ////////////////////////////////////////////////////////////////////////////////
 //                           Command line options.                            //
 ////////////////////////////////////////////////////////////////////////////////
 
+/// The environment variable that lists further directories to search for the
+/// dictionary files, separated as in `PATH`, e.g., a system-wide directory
+/// like `/usr/share/quartiles`.
+const DICT_PATH_VAR: &str = "QUARTILES_DICT_PATH";
+
 /// CLI for solving Quartiles puzzles.
 #[derive(Clone, Debug, Parser)]
 #[command(version = "1.0", author = "Todd L Smith")]
 struct Opts
 {
-	/// The path to the directory containing the dictionary files. Can be
+	/// A directory to search for the dictionary files. May be given more than
+	/// once. The directories are searched in order, followed by those listed
+	/// in `QUARTILES_DICT_PATH`, then the directory from the configuration
+	/// file, whose default is `dict` in the platform's data directory, e.g.,
+	/// `~/.local/share/quartiles-solver`, and finally `dict` in the working
+	/// directory. The dictionary is read from the first directory that holds
+	/// it, but a new dictionary is written to the first directory. Can be
 	/// changed from the TUI.
-	#[arg(short = 'd', long, default_value = "dict")]
-	directory: String,
+	#[arg(short = 'd', long)]
+	directory: Vec<PathBuf>,
 
 	/// The name of the dictionary. This is the name shared by the text and
 	/// binary files, sans the extension. Can be changed from the TUI.
-	#[arg(short = 'n', long, default_value = "english")]
-	dictionary: String,
+	/// Overrides the configuration file, whose default is the name of the
+	/// language, e.g., `english`.
+	#[arg(short = 'n', long)]
+	dictionary: Option<String>,
+
+	/// The language of the dictionary, which determines its default name and
+	/// the alphabet of downloaded word lists. Overrides the configuration
+	/// file.
+	#[arg(short = 'l', long)]
+	language: Option<Language>,
+
+	/// The path to an exclusion list, i.e., a file of words, one per line,
+	/// that are removed from the dictionary after it is loaded. May be given
+	/// more than once. Applied in addition to `<NAME>.exclude.txt` in the
+	/// dictionary directory, if it exists.
+	#[arg(short = 'x', long)]
+	exclude_file: Vec<PathBuf>,
+
+	/// The path to an inclusion list, i.e., a file of words, one per line,
+	/// that are added to the dictionary after it is loaded, optionally
+	/// preceded by the source of the words and `=`, e.g.,
+	/// `downloaded=wiktionary.txt`. The source is one of `custom`, the
+	/// default, or `downloaded`, and is reported beside each word that it
+	/// supplies to a solution. May be given more than once, and the earlier
+	/// list supplies a word found in several. The dictionary itself is the
+	/// `base` source.
+	#[arg(long)]
+	include: Vec<Inclusion>,
+
+	/// Keep only the words supplied by the given source, after the inclusion
+	/// and exclusion lists apply. May be given more than once.
+	#[arg(long)]
+	source: Vec<Source>,
+
+	/// Make dictionary lookups ignore case and stray whitespace, so that a
+	/// word list whose words kept their case still matches. The solver folds
+	/// the fragments of the puzzle once, so it runs just as fast. Overrides
+	/// the configuration file.
+	#[arg(long)]
+	fold_case: bool,
+
+	/// The most time, in seconds, that the solver may spend on a puzzle. Once
+	/// its time runs out, the solver stops and reports the best solution
+	/// found so far, marked as truncated both in the TUI's status bar and in
+	/// the JSON output. Useful with a huge dictionary. Without it, the solver
+	/// runs until it finishes.
+	#[arg(
+		long,
+		value_name = "N",
+		value_parser = clap::value_parser!(u64).range(1..)
+	)]
+	max_solve_seconds: Option<u64>,
+
+	/// The path to the configuration file. A missing configuration file is
+	/// equivalent to an empty one. Defaults to `quartiles.toml` in the
+	/// platform's configuration directory, e.g., `~/.config/quartiles-solver`.
+	#[arg(short = 'c', long)]
+	config: Option<PathBuf>,
+
+	/// The color scheme of the TUI. Overrides the configuration file.
+	#[arg(short = 't', long)]
+	theme: Option<ThemeName>,
+
+	/// Make the TUI accessible, e.g., for a screen reader: mark the cursor
+	/// and the selection with symbols rather than color alone, and describe
+	/// every change of state in words, e.g., the focused cell or each word as
+	/// it is found, on a line above the status bar and, at `info` level, in
+	/// the log. Overrides the configuration file.
+	#[arg(long)]
+	accessible: bool,
+
+	/// Reduce motion in the TUI: don't flash each word as the solver finds
+	/// it, but keep solving and stream the words into the solution list.
+	/// Overrides the configuration file.
+	#[arg(long)]
+	reduced_motion: bool,
+
+	/// The language of the TUI, i.e., of its titles, hints, and messages.
+	/// Overrides the configuration file, whose default is the language of the
+	/// dictionary.
+	#[arg(long)]
+	lang: Option<Lang>,
+
+	/// The path to the log file, to which log records are appended. Without
+	/// a log file, log records are written to standard error, except while
+	/// the TUI is open, since they would corrupt it; then they are appended
+	/// to `quartiles.log` in the platform's state directory instead.
+	#[arg(long)]
+	log_file: Option<PathBuf>,
+
+	/// The most verbose level of log record to write: `off`, `error`, `warn`,
+	/// `info`, `debug`, or `trace`. Overrides the level given by `RUST_LOG`,
+	/// whose default is `warn`, but not its per-module levels.
+	#[arg(long)]
+	log_level: Option<LevelFilter>,
+
+	/// Where to send the share card exported from the TUI, if any, once the
+	/// TUI exits: `stdout`, after the solution, or `clipboard`, via the
+	/// terminal.
+	#[arg(long, default_value = "stdout")]
+	card: CardTarget,
+
+	/// The path to a file in which to record each replay of a solve in the
+	/// TUI, in the asciicast v2 format, e.g., for `asciinema play`. Each
+	/// replay replaces the previous recording.
+	#[arg(long, value_name = "FILE")]
+	record: Option<PathBuf>,
+
+	/// The path to a file to which to write a trace of the spans of each
+	/// subsystem, i.e., dictionary loads, solver quanta, rendered frames, and
+	/// handled events, in the Chrome trace format, e.g., for viewing in
+	/// Perfetto. While tracing, the application's own log records at `debug`
+	/// level and above go to the trace rather than to the log.
+	#[cfg(feature = "chrome-trace")]
+	#[arg(long)]
+	trace_output: Option<PathBuf>,
 
 	#[command(subcommand)]
 	command: Command
 }
 
 /// The subcommands of the CLI.
-#[derive(Copy, Clone, Debug, Subcommand)]
+#[derive(Clone, Debug, Subcommand)]
 enum Command
 {
-	/// Just generate the binary dictionary and exit.
-	Generate,
+	/// Just generate the binary dictionary and exit. Given a word list, install
+	/// it as the dictionary instead, writing both the text and binary
+	/// dictionary files. Existing files are replaced. Given any normalization
+	/// rule, the words are normalized accordingly as they are read; without a
+	/// word list, the binary dictionary is then regenerated from the text
+	/// dictionary, even if it exists. Whenever words are read, the numbers of
+	/// duplicate and rejected words are written to standard error.
+	Generate {
+		/// The path to a word list to install: plain text, JSON, CSV, a
+		/// Hunspell dictionary, or a SCOWL bundle. Its format is detected
+		/// from its extension, unless given.
+		#[arg(short = 'i', long)]
+		input: Option<PathBuf>,
+
+		/// The format of the word list, regardless of its extension.
+		#[arg(short = 'f', long, requires = "input")]
+		input_format: Option<InputFormat>,
+
+		/// Rewrite the text dictionary in canonical form: normalized, sorted,
+		/// and free of duplicates. Implied by a word list.
+		#[arg(short = 'r', long)]
+		rewrite: bool,
+
+		#[command(flatten)]
+		normalization: Normalization
+	},
 
 	/// Open the text-based user interface (TUI) for inputting and solving a
-	/// Quartiles puzzle. The solution will be written to standard output.
+	/// Quartiles puzzle. The solution will be written to standard output, and
+	/// the TUI drawn on standard error. With `--stdin`, the puzzle is read from
+	/// standard input and solved without the TUI. The exit status is 0 if the
+	/// puzzle was solved, 1 if the solver finished, or ran out of time,
+	/// without solving it, 2 if the solve was abandoned, and 3 on failure.
 	Solve {
 		/// How long (in µs) to highlight an individual word in the TUI.
-		#[arg(short = 'd', long, default_value = "400")]
-		highlight_duration: u64,
+		/// Overrides the configuration file, whose default is 400.
+		#[arg(short = 'd', long)]
+		highlight_duration: Option<u64>,
+
+		/// Suppress emission of the solution to standard output.
+		#[arg(short = 'q', long)]
+		quiet: bool,
+
+		/// The minimum length of a word, in letters. Shorter words are
+		/// excluded from the solution. Overrides the configuration file, whose
+		/// default is 0.
+		#[arg(long)]
+		min_len: Option<usize>,
+
+		/// The maximum length of a word, in letters. Longer words are excluded
+		/// from the solution. Overrides the configuration file.
+		#[arg(long)]
+		max_len: Option<usize>,
+
+		/// Exclude every word but the quartiles from the solution, regardless
+		/// of the configuration file.
+		#[arg(long)]
+		quartiles_only: bool,
+
+		/// Write the counters of the solver's work to standard error after
+		/// the solver finishes: the fragment paths expanded, the prefix hits
+		/// and misses, the dictionary probes, and the time per quantum.
+		#[arg(long)]
+		stats: bool,
+
+		/// The format of the solution. As JSON, the solution is accompanied
+		/// by whether the puzzle was solved, and if not, why not: the unused
+		/// fragments, the number of quartiles found, and the near misses. The
+		/// 5 quartiles that together use every fragment are singled out, and
+		/// every word lists the grid coordinates of its fragments, as it does
+		/// with the coordinates format.
+		#[arg(long, default_value = "text")]
+		format: Format,
+
+		/// Read the 20 fragments of the puzzle from standard input, one per
+		/// line or separated by whitespace, and solve it without opening the
+		/// TUI, e.g., to solve a puzzle recognized by an OCR script.
+		#[arg(long = "stdin")]
+		from_stdin: bool
+	},
+
+	/// Solve every puzzle of a batch, one per line, without the TUI. A JSON
+	/// record of each puzzle, comprising its outcome as for `solve --format
+	/// json`, its number of quartiles, and how long it took to solve (in µs),
+	/// is written to the output, one per line. A malformed puzzle is recorded
+	/// with its error instead. A summary is written to standard error.
+	SolveBatch {
+		/// The path to the batch.
+		#[arg(short = 'i', long)]
+		input: PathBuf,
+
+		/// The format of the batch. Detected from the extension of the batch
+		/// if unspecified: `.csv` for CSV, and JSON lines otherwise.
+		#[arg(short = 'f', long)]
+		input_format: Option<BatchFormat>,
+
+		/// The path to the output file, which is replaced. Defaults to
+		/// standard output.
+		#[arg(short = 'o', long)]
+		output: Option<PathBuf>
+	},
+
+	/// Open the TUI at the main menu, from which every mode is reachable:
+	/// solving, playing, the history, the statistics, and the settings. The
+	/// solution of the most recent solve will be written to standard output,
+	/// and the exit status reports that solve, as for `solve`.
+	Menu {
+		/// How long (in µs) to highlight an individual word in the TUI.
+		/// Overrides the configuration file, whose default is 400.
+		#[arg(short = 'd', long)]
+		highlight_duration: Option<u64>,
+
+		/// Time each round of play, ending the round after this many seconds.
+		#[arg(short = 't', long)]
+		time_limit: Option<u64>,
 
 		/// Suppress emission of the solution to standard output.
 		#[arg(short = 'q', long)]
 		quiet: bool
+	},
+
+	/// List every dictionary word that can be formed from the given fragments,
+	/// which need not comprise a complete puzzle. The words are written to
+	/// standard output.
+	Lookup {
+		/// The maximum number of fragments per word.
+		#[arg(short = 'm', long, default_value = "4")]
+		max_fragments: usize,
+
+		/// The fragments.
+		#[arg(required = true)]
+		fragments: Vec<String>
+	},
+
+	/// Explain why a word does or doesn't appear in the solution of a puzzle
+	/// comprising the given fragments, which need not comprise a complete
+	/// puzzle: whether the word is absent from the dictionary, in which case
+	/// its nearest words are suggested, cannot be formed from at most 4
+	/// distinct fragments, or is too long for the solver. The explanation is
+	/// written to standard output, followed by the closest words that can be
+	/// formed.
+	Explain {
+		/// The maximum number of alternative words to suggest.
+		#[arg(short = 'n', long, default_value = "5")]
+		alternatives: usize,
+
+		/// The word to explain.
+		word: String,
+
+		/// The fragments.
+		#[arg(required = true)]
+		fragments: Vec<String>
+	},
+
+	/// Verify that a hand-made puzzle is sound, i.e., that its 5 intended
+	/// quartiles are words of the dictionary that together use each of its 20
+	/// fragments exactly once. The fragments of each quartile are written to
+	/// standard output. If the puzzle is unsound, the problem is written to
+	/// standard error instead, and the exit status is 1.
+	VerifyPuzzle {
+		/// The 20 fragments of the puzzle.
+		#[arg(short = 'f', long, required = true, num_args = 1..)]
+		fragments: Vec<String>,
+
+		/// The 5 intended quartiles, separated by commas.
+		#[arg(short = 'w', long, required = true, value_delimiter = ',')]
+		words: Vec<String>
+	},
+
+	/// Generate a random puzzle from the dictionary. The grid is written to
+	/// standard output, one row per line, and its seed to standard error.
+	Puzzle {
+		/// The seed. The same dictionary, seed, and difficulty always produce
+		/// the same puzzle. Defaults to a seed derived from the clock.
+		#[arg(short = 's', long)]
+		seed: Option<u64>,
+
+		/// The difficulty, which controls how many bonus words the grid
+		/// yields besides the quartiles.
+		#[arg(long, default_value = "medium")]
+		difficulty: Difficulty
+	},
+
+	/// Generate the daily puzzle, which is the same for every user of the same
+	/// dictionary on the same day. The grid is written to standard output, one
+	/// row per line. Completions of the daily puzzle are recorded in the
+	/// history file.
+	Daily {
+		/// The date of the puzzle, as `YYYY-MM-DD`. Defaults to today, in UTC.
+		#[arg(long)]
+		date: Option<Date>,
+
+		/// Open the TUI to solve the puzzle, rather than printing the grid.
+		#[arg(short = 's', long)]
+		solve: bool,
+
+		/// How long (in µs) to highlight an individual word in the TUI.
+		/// Overrides the configuration file, whose default is 400.
+		#[arg(short = 'd', long)]
+		highlight_duration: Option<u64>,
+
+		/// Suppress emission of the solution to standard output.
+		#[arg(short = 'q', long)]
+		quiet: bool
+	},
+
+	/// List the puzzles recorded in the history file, or open the TUI on one
+	/// of them. The history is written to standard output as a numbered table.
+	History {
+		/// The number of the puzzle to reopen, as listed.
+		#[arg(short = 'o', long)]
+		open: Option<usize>,
+
+		/// How long (in µs) to highlight an individual word in the TUI.
+		/// Overrides the configuration file, whose default is 400.
+		#[arg(short = 'd', long)]
+		highlight_duration: Option<u64>,
+
+		/// Suppress emission of the solution to standard output.
+		#[arg(short = 'q', long)]
+		quiet: bool
+	},
+
+	/// Time loading the dictionary and solving a canned puzzle on this
+	/// machine. A table of timings is written to standard output.
+	Bench {
+		/// The number of times to run each operation.
+		#[arg(short = 'i', long, default_value = "5")]
+		iterations: usize
+	},
+
+	/// Query the dictionary. Matching words are written to standard output.
+	Dict {
+		#[command(subcommand)]
+		query: DictQuery
+	},
+
+	/// Generate the manual pages: one for the program, and one for each
+	/// subcommand, closed by its examples. Neither the configuration file nor
+	/// the dictionary is read.
+	Mangen {
+		/// The directory to write the manual pages to, which is created if
+		/// necessary. Existing pages are replaced.
+		#[arg(short = 'o', long, default_value = "man")]
+		out_dir: PathBuf
+	}
+}
+
+impl Command
+{
+	/// Check whether the subcommand opens the TUI.
+	///
+	/// # Returns
+	///
+	/// `true` if the subcommand opens the TUI, `false` otherwise.
+	fn is_tui(&self) -> bool
+	{
+		matches!(
+			self,
+			Command::Solve { from_stdin: false, .. }
+				| Command::Menu { .. }
+				| Command::Daily { solve: true, .. }
+				| Command::History { open: Some(_), .. }
+		)
+	}
+
+	/// Check whether the subcommand only runs the solver against the
+	/// dictionary, which then needs only the [subset](Dictionary::subset) of
+	/// words that Quartiles can form, as when solving a puzzle read from
+	/// standard input. The TUI loads its own subset in the background. The
+	/// puzzle generator learns its chunks from every word, so its puzzles,
+	/// and especially the daily puzzle, would change with fewer. A lookup may
+	/// join more and longer fragments than a puzzle has, so it needs every
+	/// word, too.
+	///
+	/// # Returns
+	///
+	/// `true` if the subcommand only needs the subset, `false` otherwise.
+	fn needs_subset(&self) -> bool
+	{
+		matches!(
+			self,
+			Command::VerifyPuzzle { .. }
+				| Command::Solve { from_stdin: true, .. }
+				| Command::SolveBatch { .. }
+		)
+	}
+
+	/// Check whether the subcommand works with the dictionary files
+	/// themselves, rather than just their words, such that the
+	/// [embedded dictionary](Dictionary::embedded) cannot stand in for them.
+	///
+	/// # Returns
+	///
+	/// `true` if the subcommand needs the dictionary files, `false` otherwise.
+	fn needs_files(&self) -> bool
+	{
+		match self
+		{
+			Command::Generate { .. } => true,
+			Command::Dict { query: DictQuery::Stats } => true,
+			Command::Dict { query: DictQuery::Diff { .. } } => true,
+			#[cfg(feature = "net")]
+			Command::Dict { query: DictQuery::Fetch { .. } } => true,
+			_ => false
+		}
 	}
 }
 
+/// The dictionary queries of the CLI.
+#[derive(Clone, Debug, Subcommand)]
+enum DictQuery
+{
+	/// List every word that starts with the given prefix.
+	Prefix {
+		/// The prefix.
+		prefix: String
+	},
+
+	/// List every word that matches the given glob pattern. `*` matches any
+	/// sequence of letters, `?` matches any single letter, and `[abc]` matches
+	/// any one of the enclosed letters.
+	Match {
+		/// The pattern.
+		pattern: Pattern
+	},
+
+	/// Tabulate the most common chunks of 2 to 4 letters among the words of
+	/// the dictionary, with the number and share of words containing each.
+	/// The puzzle generator favors fragments that are common chunks.
+	Chunks {
+		/// The length of the chunks to tabulate. Defaults to every length.
+		#[arg(short = 'l', long)]
+		len: Option<usize>,
+
+		/// The maximum number of chunks to tabulate.
+		#[arg(short = 'n', long, default_value = "50")]
+		limit: usize
+	},
+
+	/// Report the statistics of the dictionary: its binary dictionary file,
+	/// size, compression, and number of words. The number of words is read
+	/// from the header of the binary dictionary, without loading it, unless
+	/// the binary dictionary predates the header. Exclusion lists are not
+	/// applied.
+	Stats,
+
+	/// Compare two dictionaries, given by name, each found as usual along the
+	/// search path. The words that only the first dictionary has are written
+	/// to standard output, each preceded by `<`, followed by those that only
+	/// the second has, each preceded by `>`. Given a batch of puzzles, as for
+	/// `solve-batch`, each puzzle is then solved with each dictionary, and for
+	/// every puzzle solved differently, its line and the words that only one
+	/// dictionary found are written likewise. A summary is written to
+	/// standard error.
+	Diff {
+		/// The name of the first dictionary.
+		first: String,
+
+		/// The name of the second dictionary.
+		second: String,
+
+		/// The path to a batch of puzzles to solve with each dictionary.
+		#[arg(short = 'p', long)]
+		puzzles: Option<PathBuf>,
+
+		/// The format of the batch. Detected from the extension of the batch
+		/// if unspecified: `.csv` for CSV, and JSON lines otherwise.
+		#[arg(short = 'f', long)]
+		input_format: Option<BatchFormat>
+	},
+
+	/// Download a word list from the given URL, normalize it, and install it
+	/// as the dictionary, writing both the text and binary dictionary files.
+	/// Existing files are replaced.
+	#[cfg(feature = "net")]
+	Fetch {
+		/// The URL of the word list, which must be plain text with one word
+		/// per line.
+		url: String
+	}
+}
+
+/// The formats in which the solution can be written to standard output.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+enum Format
+{
+	/// One word per line, with the quartiles and the bonus words in
+	/// separate sections.
+	#[default]
+	Text,
+
+	/// One word per line, followed by the grid coordinates of its fragments,
+	/// in order, as `row,column` pairs counted from zero at the top left.
+	Coordinates,
+
+	/// A JSON object comprising the solution and its analysis.
+	Json,
+
+	/// A standalone HTML page that draws the board and lists the words, and
+	/// traces the fragment path of a word on the board while the word is
+	/// hovered.
+	Html
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 //                               Main program.                                //
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Parse the command line options and execute the appropriate subcommand.
-fn main()
+/// Every failure panics, with its message written to standard error, and
+/// exits with [`EXIT_FAILED`].
+///
+/// # Returns
+///
+/// The exit status.
+fn main() -> ExitCode
+{
+	panic::catch_unwind(run).unwrap_or(ExitCode::from(EXIT_FAILED))
+}
+
+/// Parse the command line options and execute the appropriate subcommand.
+///
+/// # Returns
+///
+/// The exit status.
+fn run() -> ExitCode
 {
-	// Parse the command line options.
-	let opts = Opts::parse();
+	// Parse the command line options, and then start logging. The help of
+	// each subcommand closes with its examples. Bad options fail like any
+	// other failure, since clap's own exit status would claim that a solve
+	// was abandoned, but the help and the version still exit successfully.
+	let opts = match manual::command()
+		.try_get_matches()
+		.and_then(|matches| Opts::from_arg_matches(&matches))
+	{
+		Ok(opts) => opts,
+		Err(e) if e.use_stderr() =>
+		{
+			let _ = e.print();
+			return ExitCode::from(EXIT_FAILED)
+		},
+		Err(e) => e.exit()
+	};
+	init_logger(&opts.command, opts.log_file.as_deref(), opts.log_level);
+	#[cfg(feature = "chrome-trace")]
+	let _trace = opts.trace_output.as_deref().map(init_tracing);
 	debug!("Command line options: {:?}", opts);
 
-	// Open the dictionary, creating the binary dictionary if necessary.
-	let dictionary = Dictionary::open(&opts.directory, &opts.dictionary)
-		.unwrap_or_else(|_|
-			panic!("Failed to open dictionary: {}/{}.dict or {0}/{1}.txt",
-				opts.directory,
-				opts.dictionary
+	// Generate the manual pages, if requested. They document the command line
+	// interface alone, so they need neither the configuration nor the
+	// dictionary.
+	if let Command::Mangen { out_dir } = &opts.command
+	{
+		manual::generate_man_pages(out_dir)
+			.unwrap_or_else(|e|
+				panic!("Failed to generate manual pages: {}: {}",
+					out_dir.display(),
+					e
+				)
+			);
+		trace!("Exiting after generating manual pages");
+		return ExitCode::from(EXIT_SOLVED)
+	}
+
+	// Read the configuration file, if any.
+	let config_path = opts.config.clone().unwrap_or_else(paths::config_file);
+	let config = Config::load(&config_path)
+		.unwrap_or_else(|e|
+			panic!(
+				"Failed to read configuration: {}: {}",
+				config_path.display(),
+				e
 			)
 		);
+	debug!("Configuration: {:?}", config);
+
+	// Determine the language, the directory, and the name of the dictionary.
+	// A dictionary that is about to be created belongs in the first directory
+	// of the search path, whether or not a later directory already holds one
+	// of the same name.
+	let language = opts.language.unwrap_or(config.language);
+	let lang = opts.lang.or(config.lang).unwrap_or_else(|| language.into());
+	let solve_budget = opts.max_solve_seconds.map(Duration::from_secs);
+	let name = opts.dictionary
+		.clone()
+		.or_else(|| config.dictionary.clone())
+		.unwrap_or_else(|| language.name().to_string());
+	let search_path = search_path(&opts.directory, &config);
+	#[cfg(feature = "net")]
+	let is_fetching = matches!(
+		opts.command,
+		Command::Dict { query: DictQuery::Fetch { .. } }
+	);
+	#[cfg(not(feature = "net"))]
+	let is_fetching = false;
+	let is_installing = matches!(
+		opts.command,
+		Command::Generate { input: Some(_), .. }
+	);
+	let directory = match is_fetching || is_installing
+	{
+		true => search_path[0].clone(),
+		false => setup::locate(&search_path, &name)
+	};
+	debug!("Dictionary directory: {}", directory.display());
+
+	// Install the word list, if one was given to generate the dictionary
+	// from, and then exit. Likewise, regenerate the binary dictionary from
+	// the text dictionary if it must be rewritten or normalized unusually.
+	if let Command::Generate { input, input_format, rewrite, normalization } =
+		&opts.command
+	{
+		let txt_path = directory.join(format!("{}.txt", name));
+		let (path, format) = match input
+		{
+			Some(input) => (
+				input,
+				input_format.unwrap_or_else(|| InputFormat::detect(input))
+			),
+			None => (&txt_path, InputFormat::Text)
+		};
+		let rewrite = *rewrite || input.is_some();
+		if rewrite || *normalization != Normalization::default()
+		{
+			let progress = Progress::default();
+			let dictionary = Dictionary::read_from_file_as(
+				path,
+				format,
+				normalization,
+				&progress
+			)
+			.unwrap_or_else(|e|
+				panic!("Failed to read word list: {}: {}", path.display(), e)
+			);
+			eprintln!(
+				"Read {} words: {} duplicate, {} rejected",
+				dictionary.len(),
+				progress.duplicates(),
+				progress.rejected()
+			);
+			let dict_path = directory.join(format!("{}.dict", name));
+			match rewrite
+			{
+				true => dictionary.install(&directory, &name),
+				false => dictionary.serialize_to_file(&dict_path)
+			}
+			.unwrap_or_else(|e|
+				panic!("Failed to install dictionary: {}/{}: {}",
+					directory.display(),
+					name,
+					e
+				)
+			);
+			trace!(
+				"Exiting after installing dictionary: {} words",
+				dictionary.len()
+			);
+			return ExitCode::from(EXIT_SOLVED)
+		}
+	}
+
+	// Fall back to the embedded dictionary, if any, when there is none,
+	// unless the subcommand needs the dictionary files.
+	let embedded = match !opts.command.needs_files()
+		&& setup::is_missing(&directory, &name)
+	{
+		true => embedded_dictionary(),
+		false => None
+	};
+
+	// Set up a dictionary on first launch, when there is none, unless one is
+	// about to be fetched. The setup wizard needs a terminal, so without one,
+	// just explain how to set up a dictionary.
+	let (directory, name) = match !is_fetching
+		&& embedded.is_none()
+		&& setup::is_missing(&directory, &name)
+	{
+		true if stdin().is_terminal() && stderr().is_terminal() =>
+		{
+			let wizard = Wizard::new(
+				directory,
+				name,
+				language,
+				config.clone(),
+				config_path.clone(),
+				Theme::new(opts.theme.unwrap_or(config.theme)),
+				lang
+			);
+			trace!("Opening setup wizard");
+			let setup = with_console(
+				CrosstermBackend::new(stderr()),
+				|tui| wizard.run(tui)
+			)
+			.unwrap_or_else(|e| panic!("Failed to drive TUI: {}", e));
+			match setup
+			{
+				Some(setup) => (setup.directory, setup.name),
+				None =>
+				{
+					eprintln!("No dictionary was set up");
+					return ExitCode::from(EXIT_FAILED)
+				}
+			}
+		},
+		true => panic!(
+			"No dictionary: {}/{}.dict or {0}/{1}.txt. Run quartiles-solver \
+				in a terminal to set one up.",
+			directory.display(),
+			name
+		),
+		false => (directory, name)
+	};
+
+	// Fold the dictionary, make the TUI accessible, and reduce its motion, if
+	// either the command line or the configuration says so, and settle the
+	// language of the TUI. The setup wizard, which saves the configuration,
+	// has already run, so the command line overrides are never persisted.
+	let config = Config {
+		fold_case: opts.fold_case || config.fold_case,
+		accessible: opts.accessible || config.accessible,
+		reduced_motion: opts.reduced_motion || config.reduced_motion,
+		lang: Some(lang),
+		..config
+	};
+
+	// Open the dictionary, creating the binary dictionary if necessary. If
+	// the dictionary is being fetched, then download it instead. The TUI
+	// loads the dictionary in the background, behind a splash screen, so
+	// that a large dictionary doesn't delay opening the terminal.
+	let in_background = embedded.is_none() && matches!(
+		opts.command,
+		Command::Solve { from_stdin: false, .. }
+			| Command::Menu { .. }
+			| Command::History { open: Some(_), .. }
+	);
+	let mut dictionary = match &opts.command
+	{
+		// The embedded dictionary is already open.
+		_ if embedded.is_some() => embedded.unwrap_or_default(),
+		#[cfg(feature = "net")]
+		Command::Dict { query: DictQuery::Fetch { url } } =>
+		{
+			Dictionary::fetch(url, &directory, &name, language)
+				.unwrap_or_else(|e|
+					panic!("Failed to fetch dictionary: {}: {}", url, e)
+				)
+		},
+		// The statistics come from the dictionary files themselves.
+		Command::Dict { query: DictQuery::Stats } => Dictionary::default(),
+		// The compared dictionaries are opened by name instead.
+		Command::Dict { query: DictQuery::Diff { .. } } =>
+			Dictionary::default(),
+		_ if in_background => Dictionary::default(),
+		_ => Dictionary::open(&directory, &name)
+			.unwrap_or_else(|_|
+				panic!("Failed to open dictionary: {}/{}.dict or {0}/{1}.txt",
+					directory.display(),
+					name
+				)
+			)
+	};
+
+	// Fold the dictionary, if requested, and compose it from the inclusion
+	// and exclusion lists, if any. A dictionary that loads in the background
+	// does both itself. Keep only the words that Quartiles can form, if the
+	// solver is all that will read them.
+	let composition = Composition {
+		include: opts.include.clone(),
+		exclude: opts.exclude_file.clone(),
+		sources: opts.source.clone()
+	};
+	dictionary.set_folding(config.fold_case);
+	if !in_background
+	{
+		composition.apply(&mut dictionary)
+			.unwrap_or_else(|e| panic!("{}", e));
+	}
+	if opts.command.needs_subset()
+	{
+		dictionary = dictionary.subset(MIN_WORD_LEN, MAX_WORD_LEN);
+	}
 
 	// Execute the appropriate subcommand.
 	match opts.command
 	{
-		Command::Generate =>
+		Command::Generate { .. } =>
 		{
 			trace!("Exiting after generating binary dictionary");
 		},
-		Command::Solve { highlight_duration, quiet} =>
+		Command::Solve {
+			highlight_duration,
+			quiet,
+			min_len,
+			max_len,
+			quartiles_only,
+			stats,
+			format,
+			from_stdin
+		} =>
 		{
+			let mut solver_config = config.solver_config();
+			solver_config.min_len = min_len.unwrap_or(solver_config.min_len);
+			solver_config.max_len = max_len.unwrap_or(solver_config.max_len);
+			solver_config.quartiles_only |= quartiles_only;
+			if from_stdin
+			{
+				let fragments = read_fragments(stdin().lock())
+					.unwrap_or_else(|e|
+						panic!("Failed to read fragments: {}", e)
+					);
+				let dictionary = Rc::new(dictionary);
+				// Reuse a solve of the same board from the history, unless a
+				// word filter might judge the words differently this time.
+				let filter = word_filter(&config);
+				let cached = filter.is_none()
+					.then(|| history::load(&config.history_file).ok())
+					.flatten()
+					.and_then(|entries| history::cached_solver(
+						&entries,
+						Rc::clone(&dictionary),
+						&fragments,
+						solver_config
+					));
+				let (solver, duration) = match cached
+				{
+					Some(solver) => (solver, None),
+					None =>
+					{
+						let mut solver = Solver::new(dictionary, fragments)
+							.with_config(solver_config);
+						if let Some(filter) = filter
+						{
+							solver = solver.with_filter(filter);
+						}
+						trace!("Solving puzzle from standard input");
+						let started = Instant::now();
+						let solver = match solve_budget
+						{
+							Some(budget) => solver.solve_within(budget),
+							None => solver.solve_fully()
+						};
+						(solver, Some(started.elapsed()))
+					}
+				};
+				let outcome = Outcome::of(&solver).with_duration(duration);
+				if let Some(metrics) = outcome.metrics.filter(|_| stats)
+				{
+					eprint!("{}", metrics);
+				}
+				return report_outcome(outcome, quiet, format, opts.card)
+			}
+			let app = build_app(
+				opts.theme,
+				&config_path,
+				&config,
+				&directory,
+				&name,
+				&composition,
+				highlight_duration,
+				(!in_background).then_some(dictionary),
+				opts.record.as_deref(),
+				solve_budget
+			)
+			.with_solver_config(solver_config);
+			trace!("Opening TUI");
+			let outcome = tui(app, CrosstermBackend::new(stderr()))
+				.unwrap_or_else(|e| panic!("Failed to drive TUI: {}", e));
+			if let Some(metrics) = outcome.metrics.filter(|_| stats)
+			{
+				eprint!("{}", metrics);
+			}
+			return report_outcome(outcome, quiet, format, opts.card)
+		},
+		Command::SolveBatch { input, input_format, output } =>
+		{
+			let format = input_format
+				.unwrap_or_else(|| BatchFormat::detect(&input));
+			let file = File::open(&input)
+				.unwrap_or_else(|e|
+					panic!("Failed to open batch: {}: {}", input.display(), e)
+				);
+			let mut batch = Batch::new(Rc::new(dictionary))
+				.with_config(config.solver_config());
+			if let Some(filter) = word_filter(&config)
+			{
+				batch = batch.with_filter(filter);
+			}
+			if let Some(budget) = solve_budget
+			{
+				batch = batch.with_time_budget(budget);
+			}
+			let input = BufReader::new(file);
+			let summary = match output
+			{
+				Some(path) => File::create(&path).and_then(|file|
+					batch.run(input, format, BufWriter::new(file))
+				),
+				None => batch.run(input, format, io::stdout().lock())
+			}
+			.unwrap_or_else(|e| panic!("Failed to solve batch: {}", e));
+			eprintln!("{}", summary);
+		},
+		Command::Menu { highlight_duration, time_limit, quiet } =>
+		{
+			let mut app = build_app(
+				opts.theme,
+				&config_path,
+				&config,
+				&directory,
+				&name,
+				&composition,
+				highlight_duration,
+				(!in_background).then_some(dictionary),
+				opts.record.as_deref(),
+				solve_budget
+			)
+			.with_menu();
+			if let Some(seconds) = time_limit
+			{
+				app = app.with_time_limit(Duration::from_secs(seconds));
+			}
+			trace!("Opening TUI");
+			let outcome = tui(app, CrosstermBackend::new(stderr()))
+				.unwrap_or_else(|e| panic!("Failed to drive TUI: {}", e));
+			return report_outcome(outcome, quiet, Format::Text, opts.card)
+		},
+		Command::Lookup { max_fragments, fragments } =>
+		{
+			let fragments = fragments
+				.iter()
+				.map(|f| normalize_word(f))
+				.collect::<Vec<_>>();
+			print_solution(lookup(&dictionary, &fragments, max_fragments));
+		},
+		Command::Explain { alternatives, word, fragments } =>
+		{
+			let word = normalize_word(&word);
+			let fragments = fragments
+				.iter()
+				.map(|f| normalize_word(f))
+				.collect::<Vec<_>>();
+			println!("{}: {}", word, explain(&dictionary, &fragments, &word));
+			let closest =
+				closest_words(&dictionary, &fragments, &word, alternatives);
+			if !closest.is_empty()
+			{
+				println!("Closest formable words:");
+				print_solution(closest);
+			}
+		},
+		Command::VerifyPuzzle { fragments, words } =>
+		{
+			let fragments = fragments
+				.iter()
+				.map(|f| normalize_word(f))
+				.collect::<Vec<_>>();
+			let words = words
+				.iter()
+				.map(|w| normalize_word(w))
+				.collect::<Vec<_>>();
+			match verify(&dictionary, &fragments, &words)
+			{
+				Ok(partition) =>
+				{
+					for (word, fragments) in words.iter().zip(partition)
+					{
+						println!("{}: {}", word, fragments.join("·"));
+					}
+				},
+				Err(e) =>
+				{
+					eprintln!("Unsound puzzle: {}", e);
+					return ExitCode::from(EXIT_UNSOLVED)
+				}
+			}
+		},
+		Command::Puzzle { seed, difficulty } =>
+		{
+			let seed = seed.unwrap_or_else(|| {
+				SystemTime::now()
+					.duration_since(UNIX_EPOCH)
+					.map(|d| d.as_nanos() as u64)
+					.unwrap_or_default()
+			});
+			let puzzle = Generator::new(Rc::new(dictionary))
+				.generate(seed, difficulty)
+				.unwrap_or_else(|e| panic!("Failed to generate puzzle: {}", e));
+			eprintln!(
+				"Seed {} ({}, {} bonus words)",
+				puzzle.seed,
+				puzzle.difficulty,
+				puzzle.bonus_words
+			);
+			print_grid(&puzzle.fragments);
+		},
+		Command::Daily { date, solve, highlight_duration, quiet } =>
+		{
+			let date = date.unwrap_or_else(Date::today);
+			let dictionary = Rc::new(dictionary);
+			let puzzle = daily::puzzle(Rc::clone(&dictionary), date)
+				.unwrap_or_else(|e|
+					panic!("Failed to generate daily puzzle: {}", e)
+				);
+			eprintln!("Daily puzzle for {} (seed {})", date, puzzle.seed);
+			let entries = history::load(&config.history_file)
+				.unwrap_or_else(|e| {
+					warn!(
+						"Ignoring history file: {}: {}",
+						config.history_file.display(),
+						e
+					);
+					vec![]
+				});
+			if history::is_daily_completed(&entries, date)
+			{
+				eprintln!("Already completed");
+			}
+			if !solve
+			{
+				print_grid(&puzzle.fragments);
+				return ExitCode::from(EXIT_SOLVED)
+			}
+			// The generator is gone, so this is the only reference.
+			let dictionary =
+				Rc::try_unwrap(dictionary).unwrap_or_else(|d| (*d).clone());
+			let app = build_app(
+				opts.theme,
+				&config_path,
+				&config,
+				&directory,
+				&name,
+				&composition,
+				highlight_duration,
+				Some(dictionary),
+				opts.record.as_deref(),
+				solve_budget
+			)
+			.with_provenance(Provenance {
+				fragments: puzzle.fragments,
+				seed: puzzle.seed,
+				daily: Some(date)
+			});
 			trace!("Opening TUI");
-			let mut solution = tui(highlight_duration, dictionary)
+			let outcome = tui(app, CrosstermBackend::new(stderr()))
 				.unwrap_or_else(|e| panic!("Failed to drive TUI: {}", e));
-			if !quiet
+			return report_outcome(outcome, quiet, Format::Text, opts.card)
+		},
+		Command::History { open, highlight_duration, quiet } =>
+		{
+			let entries = history::load(&config.history_file)
+				.unwrap_or_else(|e|
+					panic!(
+						"Failed to read history file: {}: {}",
+						config.history_file.display(),
+						e
+					)
+				);
+			match open
+			{
+				None => print!("{}", Table(&entries)),
+				Some(number) =>
+				{
+					let entry = number
+						.checked_sub(1)
+						.and_then(|index| entries.get(index))
+						.unwrap_or_else(|| panic!("No such puzzle: {}", number));
+					let board = entry.board()
+						.unwrap_or_else(|| panic!("Invalid puzzle: {}", number));
+					let app = build_app(
+						opts.theme,
+						&config_path,
+						&config,
+						&directory,
+						&name,
+						&composition,
+						highlight_duration,
+						(!in_background).then_some(dictionary),
+						opts.record.as_deref(),
+						solve_budget
+					);
+					let app = match entry.provenance()
+					{
+						Some(provenance) => app.with_provenance(provenance),
+						None => app.with_fragments(board)
+					};
+					trace!("Opening TUI");
+					let outcome = tui(app, CrosstermBackend::new(stderr()))
+						.unwrap_or_else(|e|
+							panic!("Failed to drive TUI: {}", e)
+						);
+					return report_outcome(
+						outcome,
+						quiet,
+						Format::Text,
+						opts.card
+					)
+				}
+			}
+		},
+		Command::Bench { iterations } =>
+		{
+			let measurements = bench::run(&directory, &name, iterations)
+				.unwrap_or_else(|e| panic!("Failed to run benchmarks: {}", e));
+			let _ = bench::print_table(&measurements);
+		},
+		Command::Dict { query: DictQuery::Prefix { prefix } } =>
+		{
+			print_words(dictionary.iter_prefix(&normalize_word(&prefix)));
+		},
+		Command::Dict { query: DictQuery::Match { pattern } } =>
+		{
+			print_words(dictionary.iter_matching(&pattern));
+		},
+		Command::Dict { query: DictQuery::Chunks { len, limit } } =>
+		{
+			print!("{}", Inventory::learn(&dictionary).table(len, limit));
+		},
+		Command::Dict {
+			query: DictQuery::Diff { first, second, puzzles, input_format }
+		} =>
+		{
+			let open = |name: &str| {
+				let directory = setup::locate(&search_path, name);
+				let mut dictionary = Dictionary::open(&directory, name)
+					.unwrap_or_else(|e|
+						panic!("Failed to open dictionary: {}/{}: {}",
+							directory.display(),
+							name,
+							e
+						)
+					);
+				dictionary.set_folding(config.fold_case);
+				Rc::new(dictionary)
+			};
+			let (first_dictionary, second_dictionary) =
+				(open(&first), open(&second));
+			print_diff(&first_dictionary, &second_dictionary);
+			eprintln!(
+				"{} words only in {}, {} words only in {}",
+				first_dictionary.difference(&second_dictionary).count(),
+				first,
+				second_dictionary.difference(&first_dictionary).count(),
+				second
+			);
+			if let Some(path) = puzzles
 			{
-				solution.sort();
-				solution.dedup();
-				print_solution(solution);
+				let batch = |dictionary| {
+					let mut batch = Batch::new(dictionary)
+						.with_config(config.solver_config());
+					if let Some(budget) = solve_budget
+					{
+						batch = batch.with_time_budget(budget);
+					}
+					match word_filter(&config)
+					{
+						Some(filter) => batch.with_filter(filter),
+						None => batch
+					}
+				};
+				let (first_batch, second_batch) =
+					(batch(first_dictionary), batch(second_dictionary));
+				let format = input_format
+					.unwrap_or_else(|| BatchFormat::detect(&path));
+				let comparison = File::open(&path)
+					.and_then(|file| compare(
+						[(&first, &first_batch), (&second, &second_batch)],
+						BufReader::new(file),
+						format,
+						io::stdout().lock()
+					))
+					.unwrap_or_else(|e|
+						panic!("Failed to compare batch: {}: {}",
+							path.display(),
+							e
+						)
+					);
+				eprintln!("{}", comparison);
 			}
+		},
+		Command::Dict { query: DictQuery::Stats } =>
+		{
+			print_stats(&directory, &name)
+				.unwrap_or_else(|e|
+					panic!("Failed to read dictionary statistics: {}/{}: {}",
+						directory.display(),
+						name,
+						e
+					)
+				);
+		},
+		Command::Mangen { .. } => unreachable!(),
+		#[cfg(feature = "net")]
+		Command::Dict { query: DictQuery::Fetch { .. } } =>
+		{
+			trace!(
+				"Exiting after fetching dictionary: {} words",
+				dictionary.len()
+			);
+		}
+	}
+	ExitCode::from(EXIT_SOLVED)
+}
+
+/// Answer the directories to search for the dictionary files, in order: the
+/// directories given on the command line, then those listed in
+/// [`DICT_PATH_VAR`], then the directory from the configuration, and finally
+/// the `dict` directory beneath the working directory, as when running from a
+/// checkout of the repository.
+///
+/// # Arguments
+///
+/// * `directories` - The directories given on the command line.
+/// * `config` - The configuration.
+///
+/// # Returns
+///
+/// The search path, which is never empty.
+fn search_path(directories: &[PathBuf], config: &Config) -> Vec<PathBuf>
+{
+	let mut search_path = directories.to_vec();
+	if let Some(paths) = env::var_os(DICT_PATH_VAR)
+	{
+		search_path.extend(
+			env::split_paths(&paths).filter(|path| !path.as_os_str().is_empty())
+		);
+	}
+	search_path.push(config.directory.clone());
+	let local = paths::local_dictionary_directory();
+	if !search_path.contains(&local)
+	{
+		search_path.push(local);
+	}
+	search_path
+}
+
+/// Answer the [embedded dictionary](Dictionary::embedded), if the binary was
+/// built with one.
+///
+/// # Returns
+///
+/// The embedded dictionary, if any.
+fn embedded_dictionary() -> Option<Dictionary>
+{
+	#[cfg(feature = "embedded-dict")]
+	{
+		debug!("Using embedded dictionary");
+		Some(Dictionary::embedded())
+	}
+	#[cfg(not(feature = "embedded-dict"))]
+	None
+}
+
+/// Initialize the logger. Log records are appended to the log file, if any.
+/// Otherwise, they are written to standard error, unless the subcommand opens
+/// the TUI, in which case they are appended to the
+/// [default log file](paths::log_file). The level given by `RUST_LOG` applies,
+/// unless overridden.
+///
+/// # Arguments
+///
+/// * `command` - The subcommand.
+/// * `file` - The log file, if any.
+/// * `level` - The most verbose level of log record to write, which overrides
+///   `RUST_LOG`, if any.
+fn init_logger(
+	command: &Command,
+	file: Option<&Path>,
+	level: Option<LevelFilter>
+)
+{
+	// The default log file is best effort, since the user never asked for it;
+	// if it cannot be opened, then the log records are discarded.
+	let file = match file
+	{
+		Some(path) => Some(
+			open_log_file(path)
+				.unwrap_or_else(|e|
+					panic!("Failed to open log file: {}: {}", path.display(), e)
+				)
+		),
+		None if command.is_tui() => match open_log_file(&paths::log_file())
+		{
+			Ok(file) => Some(file),
+			Err(_) => return
+		},
+		None => None
+	};
+	let mut builder = Builder::new();
+	builder
+		.filter_level(LevelFilter::Warn)
+		.parse_env(Env::default())
+		.format_timestamp_millis();
+	if let Some(level) = level
+	{
+		builder.filter_level(level);
+	}
+	if let Some(file) = file
+	{
+		builder
+			.target(Target::Pipe(Box::new(file)))
+			.write_style(WriteStyle::Never);
+	}
+	builder.init();
+}
+
+/// Open the specified log file for appending, creating it and its missing
+/// parent directories as necessary.
+///
+/// # Arguments
+///
+/// * `path` - The log file.
+///
+/// # Returns
+///
+/// The log file.
+///
+/// # Errors
+///
+/// If the file cannot be opened, an error is returned.
+fn open_log_file(path: &Path) -> Result<File, io::Error>
+{
+	if let Some(parent) = path.parent()
+	{
+		fs::create_dir_all(parent)?;
+	}
+	OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// Start writing a Chrome trace to the specified file. The trace is complete
+/// once the returned guard is dropped.
+///
+/// # Arguments
+///
+/// * `path` - The trace file.
+///
+/// # Returns
+///
+/// The guard that flushes the trace.
+#[cfg(feature = "chrome-trace")]
+fn init_tracing(path: &Path) -> FlushGuard
+{
+	let (layer, guard) = ChromeLayerBuilder::new()
+		.file(path)
+		.include_args(true)
+		.build();
+	// Install the subscriber directly, rather than via `init`, which would
+	// also try to replace the logger.
+	let subscriber = tracing_subscriber::registry()
+		.with(layer.with_filter(TraceLevelFilter::DEBUG));
+	tracing::subscriber::set_global_default(subscriber)
+		.unwrap_or_else(|e| panic!("Failed to start tracing: {}", e));
+	debug!("Writing Chrome trace: {}", path.display());
+	guard
+}
+
+/// Build the application state for the TUI, as described by the configuration.
+///
+/// # Arguments
+///
+/// * `theme` - The color scheme, which overrides the configuration, if any.
+/// * `config_path` - The path to the configuration file, to which the TUI
+///   persists changed settings.
+/// * `config` - The configuration.
+/// * `directory` - The directory containing the dictionary files.
+/// * `name` - The name of the dictionary.
+/// * `composition` - How to compose every dictionary that loads in the
+///   background.
+/// * `highlight_duration` - How long (in µs) to highlight an individual word,
+///   which overrides the configuration, if any.
+/// * `dictionary` - The dictionary, if already open. If `None`, then the
+///   dictionary loads in the background, behind a splash screen.
+/// * `record` - The file in which to record each replay of a solve, if any.
+/// * `solve_budget` - How long the solver may run, if its time is limited.
+///
+/// # Returns
+///
+/// The application state.
+#[allow(clippy::too_many_arguments)]
+fn build_app(
+	theme: Option<ThemeName>,
+	config_path: &Path,
+	config: &Config,
+	directory: &Path,
+	name: &str,
+	composition: &Composition,
+	highlight_duration: Option<u64>,
+	dictionary: Option<Dictionary>,
+	record: Option<&Path>,
+	solve_budget: Option<Duration>
+) -> App
+{
+	let keymap = Keymap::new(config.keymap)
+		.with_overrides(&config.keys)
+		.unwrap_or_else(|e| panic!("Failed to build keymap: {}", e));
+	let highlight_duration =
+		highlight_duration.unwrap_or(config.highlight_duration);
+	let app = match dictionary
+	{
+		Some(dictionary) => App::new(highlight_duration, dictionary),
+		None => App::new(highlight_duration, Dictionary::default())
+			.with_loader(Loader::spawn(
+				directory.to_path_buf(),
+				name.to_string(),
+				composition.clone()
+			))
+	};
+	let mut app = app
+		.with_dictionary_name(name)
+		.with_dictionary_directory(directory.to_path_buf())
+		.with_composition(composition.clone())
+		.with_config_file(config_path.to_path_buf())
+		.with_theme(theme.unwrap_or(config.theme))
+		.with_keymap(keymap)
+		.with_confirm_exit(config.confirm_exit)
+		.with_accessible(config.accessible)
+		.with_reduced_motion(config.reduced_motion)
+		.with_solve_budget(solve_budget)
+		.with_lang(config.lang.unwrap_or_default())
+		.with_folding(config.fold_case)
+		.with_solver_config(config.solver_config());
+	if let Some(filter) = word_filter(config)
+	{
+		app = app.with_filter(filter);
+	}
+	#[cfg(feature = "graphics")]
+	{
+		app = app.with_renderer(graphics::Renderer::detect());
+	}
+	if let Some(path) = record
+	{
+		app = app.with_record_file(path.to_path_buf());
+	}
+	app.with_state_file(config.state_file.clone())
+		.with_history_file(config.history_file.clone())
+		.with_favorites_file(config.favorites_file.clone())
+}
+
+/// Build the [word filter](WordFilter) described by the configuration, if any.
+///
+/// # Arguments
+///
+/// * `config` - The configuration.
+///
+/// # Returns
+///
+/// The word filter, if the configuration specifies a script.
+fn word_filter(config: &Config) -> Option<Rc<dyn WordFilter>>
+{
+	let path = config.script.as_ref()?;
+	#[cfg(feature = "scripting")]
+	{
+		let filter = ScriptFilter::from_file(path)
+			.unwrap_or_else(|e|
+				panic!("Failed to compile script: {}: {}", path.display(), e)
+			);
+		Some(Rc::new(filter))
+	}
+	#[cfg(not(feature = "scripting"))]
+	{
+		warn!(
+			"Ignoring script, because scripting is disabled: {}",
+			path.display()
+		);
+		None
+	}
+}
+
+/// Stream the given words to standard output, one per line. Stop quietly if
+/// standard output is closed, e.g., by `head`.
+///
+/// # Arguments
+///
+/// * `words` - The words to print.
+fn print_words<'a>(words: impl Iterator<Item = &'a str>)
+{
+	let mut out = BufWriter::new(io::stdout().lock());
+	for word in words
+	{
+		if writeln!(out, "{}", word).is_err()
+		{
+			return
 		}
 	}
+	let _ = out.flush();
+}
+
+/// Print the statistics of the specified dictionary to standard output. The
+/// number of words comes from the header of the binary dictionary, if
+/// possible; otherwise, from the dictionary itself.
+///
+/// # Arguments
+///
+/// * `directory` - The directory containing the dictionary files.
+/// * `name` - The name of the dictionary.
+///
+/// # Errors
+///
+/// If the dictionary cannot be read, an error is returned.
+fn print_stats(directory: &Path, name: &str) -> io::Result<()>
+{
+	let dict_path = directory.join(format!("{}.dict", name));
+	if !dict_path.exists()
+	{
+		let txt_path = directory.join(format!("{}.txt", name));
+		let dictionary = Dictionary::read_from_file(&txt_path)?;
+		println!("Dictionary:  {}", txt_path.display());
+		println!("Size:        {} bytes", fs::metadata(&txt_path)?.len());
+		println!("Words:       {}", dictionary.len());
+		return Ok(())
+	}
+	let header = Header::read_from_file(&dict_path)?;
+	println!("Dictionary:  {}", dict_path.display());
+	println!("Size:        {} bytes", fs::metadata(&dict_path)?.len());
+	match header
+	{
+		Some(header) =>
+		{
+			println!("Compression: {:?}", header.compression);
+			println!("Words:       {}", header.words);
+		},
+		None =>
+		{
+			trace!("Counting words of binary dictionary without header");
+			let dictionary = Dictionary::deserialize_from_file(&dict_path)?;
+			println!("Words:       {}", dictionary.len());
+		}
+	}
+	Ok(())
+}
+
+/// Stream the words that only one of the given dictionaries has to standard
+/// output, one per line: first those only in the first dictionary, each
+/// preceded by `<`, and then those only in the second, each preceded by `>`.
+/// Stop quietly if standard output is closed, e.g., by `head`.
+///
+/// # Arguments
+///
+/// * `first` - The first dictionary.
+/// * `second` - The second dictionary.
+fn print_diff(first: &Dictionary, second: &Dictionary)
+{
+	let mut out = BufWriter::new(io::stdout().lock());
+	let words = first.difference(second)
+		.map(|word| ('<', word))
+		.chain(second.difference(first).map(|word| ('>', word)));
+	for (side, word) in words
+	{
+		if writeln!(out, "{} {}", side, word).is_err()
+		{
+			return
+		}
+	}
+	let _ = out.flush();
+}
+
+/// Print the given fragments to standard output as a grid of 5 rows of 4
+/// fragments, with aligned columns.
+///
+/// # Arguments
+///
+/// * `fragments` - The fragments, in grid order.
+fn print_grid(fragments: &[Fragment; 20])
+{
+	for row in history::grid(fragments)
+	{
+		println!("{}", row);
+	}
+}
+
+/// Read the 20 fragments of a puzzle, one per line or separated by whitespace.
+/// Each fragment is [normalized](normalize_word).
+///
+/// # Arguments
+///
+/// * `reader` - The source of the fragments.
+///
+/// # Returns
+///
+/// The fragments, in order.
+///
+/// # Errors
+///
+/// * If the reader fails, the error is returned.
+/// * If there are not exactly 20 fragments, or a fragment is empty or too
+///   long, an [`ErrorKind::InvalidData`] is returned.
+fn read_fragments(mut reader: impl Read) -> Result<[Fragment; 20], io::Error>
+{
+	let mut text = String::new();
+	reader.read_to_string(&mut text)?;
+	parse_board(&text.split_whitespace().collect::<Vec<_>>())
+}
+
+/// Report the outcome of a solve: print it to standard output in the
+/// specified format, unless quiet, then export the share card exported from
+/// the TUI, if any, even if quiet, and answer the
+/// [exit status](Outcome::exit_status).
+///
+/// # Arguments
+///
+/// * `outcome` - The outcome to report.
+/// * `quiet` - Whether to suppress printing the outcome.
+/// * `format` - The format.
+/// * `target` - Where to send the share card.
+///
+/// # Returns
+///
+/// The exit status.
+fn report_outcome(
+	mut outcome: Outcome,
+	quiet: bool,
+	format: Format,
+	target: CardTarget
+) -> ExitCode
+{
+	let status = ExitCode::from(outcome.exit_status());
+	let card = outcome.card.take();
+	if !quiet
+	{
+		match print_outcome(outcome, format)
+		{
+			Ok(()) => {},
+			// A reader that stops early, e.g., `head`, has seen all that it
+			// wants, so a broken pipe is a normal end.
+			Err(e) if e.kind() == io::ErrorKind::BrokenPipe => {},
+			Err(e) => panic!("Failed to print solution: {}", e)
+		}
+	}
+	if let Some(card) = card
+	{
+		if let Err(e) = card::export(&card, target)
+		{
+			warn!("Failed to export share card: {}", e);
+		}
+	}
+	status
+}
+
+/// Print the outcome of a solve to standard output in the specified format.
+/// As text, the quartiles and the bonus words are listed in separate
+/// sections, each headed by its title and count. In the text formats, each
+/// word that an inclusion list supplied is followed by its source in brackets,
+/// e.g., `tryst [custom]`.
+///
+/// # Arguments
+///
+/// * `outcome` - The outcome to print.
+/// * `format` - The format.
+///
+/// # Errors
+///
+/// If standard output cannot be written, e.g., because a pipeline closed it
+/// early, an error is returned.
+fn print_outcome(outcome: Outcome, format: Format) -> Result<(), io::Error>
+{
+	let source = |word: &String| {
+		outcome.sources.get(word)
+			.map(|source| format!(" [{}]", source))
+			.unwrap_or_default()
+	};
+	let mut out = io::stdout().lock();
+	match format
+	{
+		Format::Text if outcome.sources.is_empty() =>
+		{
+			write!(out, "{}", outcome.solution)?;
+		},
+		Format::Text =>
+		{
+			for (index, (title, words)) in
+				outcome.solution.sections().enumerate()
+			{
+				if index > 0
+				{
+					writeln!(out)?;
+				}
+				writeln!(out, "{} ({})", title, words.len())?;
+				for word in words
+				{
+					writeln!(out, "{}{}", word, source(word))?;
+				}
+			}
+		},
+		Format::Coordinates =>
+		{
+			for word in outcome.solution.words()
+			{
+				let coordinates = outcome.coordinates.get(word)
+					.map(|coordinates| {
+						coordinates.iter()
+							.map(|(row, column)| format!(" {},{}", row, column))
+							.collect::<String>()
+					})
+					.unwrap_or_default();
+				writeln!(out, "{}{}{}", word, coordinates, source(word))?;
+			}
+		},
+		Format::Json =>
+		{
+			let json = serde_json::to_string_pretty(&outcome)
+				.unwrap_or_else(|e|
+					panic!("Failed to serialize solution: {}", e)
+				);
+			writeln!(out, "{}", json)?;
+		},
+		Format::Html => write!(out, "{}", html::Page(&outcome))?
+	}
+	out.flush()
 }
 
 /// Print the solution to standard output.
@@ -0,0 +1,520 @@
+//! # Manual
+//!
+//! Herein is the documentation that ships inside the binary: the examples that
+//! close the help of each subcommand, all drawn from a single table, and the
+//! manual pages that the `mangen` subcommand generates from the command line
+//! interface, examples included.
+
+use std::{
+	fs::{self, File},
+	io::{self, BufWriter, Write},
+	path::Path
+};
+
+use clap::{Command, CommandFactory};
+use clap_mangen::{
+	roff::{bold, roman, Roff},
+	Man
+};
+
+use crate::Opts;
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Examples.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// An example invocation of a subcommand.
+#[derive(Copy, Clone, Debug)]
+struct Example
+{
+	/// What the invocation does.
+	description: &'static str,
+
+	/// The invocation, as typed at the shell.
+	invocation: &'static str
+}
+
+/// The examples of every subcommand, keyed by the path of the subcommand, i.e.,
+/// its name preceded by the names of its ancestors, separated by spaces.
+const EXAMPLES: &[(&str, &[Example])] = &[
+	("generate", &[
+		Example {
+			description: "Regenerate the binary dictionary from the text one",
+			invocation: "quartiles-solver generate"
+		},
+		Example {
+			description: "Regenerate a dictionary installed elsewhere",
+			invocation: "quartiles-solver -d /usr/share/quartiles -n english \
+				generate"
+		},
+		Example {
+			description: "Install a Hunspell dictionary as the French one",
+			invocation: "quartiles-solver -n french generate -i fr_FR.dic"
+		},
+		Example {
+			description: "Install the words column of a spreadsheet",
+			invocation: "quartiles-solver generate -i words.export -f csv"
+		},
+		Example {
+			description: "Install a word list, stripping its accents",
+			invocation: "quartiles-solver generate -i mots.txt \
+				--strip-diacritics --remove-hyphens"
+		},
+		Example {
+			description: "Sort and deduplicate the text dictionary",
+			invocation: "quartiles-solver generate --rewrite"
+		}
+	]),
+	("solve", &[
+		Example {
+			description: "Input a puzzle and solve it",
+			invocation: "quartiles-solver solve"
+		},
+		Example {
+			description: "Write the solution and its analysis as JSON",
+			invocation: "quartiles-solver solve --format json"
+		},
+		Example {
+			description: "Write where to tap for each word of the solution",
+			invocation: "quartiles-solver solve --format coordinates"
+		},
+		Example {
+			description: "Write the solution as a page to review in a browser",
+			invocation: "quartiles-solver solve --format html"
+		},
+		Example {
+			description: "Report only whether the quartiles were found",
+			invocation: "quartiles-solver solve -q --quartiles-only"
+		},
+		Example {
+			description: "Solve the 20 fragments piped to standard input",
+			invocation: "quartiles-solver solve --stdin"
+		},
+		Example {
+			description: "Add a word list, and note each word that it supplies",
+			invocation: "quartiles-solver --include mine.txt solve"
+		}
+	]),
+	("solve-batch", &[
+		Example {
+			description: "Solve every puzzle of a JSON lines file",
+			invocation: "quartiles-solver solve-batch -i puzzles.jsonl \
+				-o results.jsonl"
+		},
+		Example {
+			description: "Evaluate another dictionary against the same puzzles",
+			invocation: "quartiles-solver -n scrabble solve-batch \
+				-i puzzles.csv -o scrabble.jsonl"
+		}
+	]),
+	("menu", &[
+		Example {
+			description: "Open the main menu",
+			invocation: "quartiles-solver menu"
+		},
+		Example {
+			description: "Play rounds of 2 minutes each",
+			invocation: "quartiles-solver menu -t 120"
+		}
+	]),
+	("lookup", &[
+		Example {
+			description: "List the words formed from a few fragments",
+			invocation: "quartiles-solver lookup tru th fu lly"
+		},
+		Example {
+			description: "List only the words of at most 2 fragments",
+			invocation: "quartiles-solver lookup -m 2 tru th fu lly"
+		}
+	]),
+	("explain", &[
+		Example {
+			description: "Explain whether a word can be formed",
+			invocation: "quartiles-solver explain truthful tru th fu lly"
+		}
+	]),
+	("verify-puzzle", &[
+		Example {
+			description: "Verify a hand-made puzzle",
+			invocation: "quartiles-solver verify-puzzle \
+				-f azz th ss tru ref fu ra nih cro mat \
+				wo sh re rds tic il lly zz is ment \
+				-w crosswords,nihilistic,razzmatazz,refreshment,truthfully"
+		}
+	]),
+	("puzzle", &[
+		Example {
+			description: "Generate a random puzzle",
+			invocation: "quartiles-solver puzzle"
+		},
+		Example {
+			description: "Regenerate a hard puzzle from its seed",
+			invocation: "quartiles-solver puzzle -s 42 --difficulty hard"
+		}
+	]),
+	("daily", &[
+		Example {
+			description: "Print today's puzzle",
+			invocation: "quartiles-solver daily"
+		},
+		Example {
+			description: "Solve the puzzle of a past day",
+			invocation: "quartiles-solver daily --date 2024-06-01 -s"
+		}
+	]),
+	("history", &[
+		Example {
+			description: "List the recorded puzzles",
+			invocation: "quartiles-solver history"
+		},
+		Example {
+			description: "Reopen the third recorded puzzle",
+			invocation: "quartiles-solver history -o 3"
+		}
+	]),
+	("bench", &[
+		Example {
+			description: "Time the dictionary and the solver on this machine",
+			invocation: "quartiles-solver bench -i 10"
+		}
+	]),
+	("dict", &[
+		Example {
+			description: "List the words that start with a prefix",
+			invocation: "quartiles-solver dict prefix quart"
+		},
+		Example {
+			description: "List the words that match a pattern",
+			invocation: "quartiles-solver dict match 'qu?rt*'"
+		}
+	]),
+	("dict prefix", &[
+		Example {
+			description: "List the words that start with a prefix",
+			invocation: "quartiles-solver dict prefix quart"
+		}
+	]),
+	("dict match", &[
+		Example {
+			description: "List the words of 5 letters that end in `zz`",
+			invocation: "quartiles-solver dict match '???zz'"
+		},
+		Example {
+			description: "List the words that start with a vowel and end in \
+				`ly`",
+			invocation: "quartiles-solver dict match '[aeiou]*ly'"
+		}
+	]),
+	("dict chunks", &[
+		Example {
+			description: "Tabulate the 20 most common chunks of 3 letters",
+			invocation: "quartiles-solver dict chunks -l 3 -n 20"
+		}
+	]),
+	("dict stats", &[
+		Example {
+			description: "Count the words of the dictionary",
+			invocation: "quartiles-solver dict stats"
+		}
+	]),
+	("dict diff", &[
+		Example {
+			description: "List the words that only one of two dictionaries has",
+			invocation: "quartiles-solver dict diff english scrabble"
+		},
+		Example {
+			description: "Compare how each solves a batch of puzzles",
+			invocation: "quartiles-solver dict diff english scrabble \
+				-p puzzles.jsonl"
+		}
+	]),
+	("mangen", &[
+		Example {
+			description: "Generate the manual pages, and then read one",
+			invocation: "quartiles-solver mangen -o man && \
+				man -l man/quartiles-solver-solve.1"
+		}
+	])
+];
+
+/// Get the examples of a subcommand.
+///
+/// # Arguments
+///
+/// * `path` - The path of the subcommand, i.e., its name preceded by the
+///   names of its ancestors, separated by spaces.
+///
+/// # Returns
+///
+/// The examples, or `None` if the subcommand has none.
+#[must_use]
+fn examples(path: &str) -> Option<&'static [Example]>
+{
+	EXAMPLES.iter()
+		.find(|(p, _)| *p == path)
+		.map(|(_, examples)| *examples)
+}
+
+/// Compute the path of a subcommand.
+///
+/// # Arguments
+///
+/// * `path` - The path of the parent command, or the empty string for the
+///   root.
+/// * `name` - The name of the subcommand.
+///
+/// # Returns
+///
+/// The path of the subcommand.
+#[must_use]
+fn subpath(path: &str, name: &str) -> String
+{
+	match path
+	{
+		"" => name.to_string(),
+		_ => format!("{} {}", path, name)
+	}
+}
+
+/// Render the examples of a subcommand, as they close its help.
+///
+/// # Arguments
+///
+/// * `path` - The path of the subcommand.
+///
+/// # Returns
+///
+/// The rendered examples, or `None` if the subcommand has none.
+#[must_use]
+fn help_examples(path: &str) -> Option<String>
+{
+	let examples = examples(path)?;
+	let mut text = String::from("Examples:\n");
+	for example in examples
+	{
+		text.push_str(&format!(
+			"  {}:\n    $ {}\n",
+			example.description,
+			example.invocation
+		));
+	}
+	Some(text)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                               Command line.                                //
+////////////////////////////////////////////////////////////////////////////////
+
+/// Build the command line interface, closing the help of each subcommand with
+/// its [examples](EXAMPLES).
+///
+/// # Returns
+///
+/// The command line interface.
+#[must_use]
+pub fn command() -> Command
+{
+	with_examples(Opts::command(), "")
+}
+
+/// Close the help of every subcommand of the specified command with its
+/// [examples](EXAMPLES), recursively.
+///
+/// # Arguments
+///
+/// * `command` - The command.
+/// * `path` - The path of the command, or the empty string for the root.
+///
+/// # Returns
+///
+/// The command, with examples.
+fn with_examples(command: Command, path: &str) -> Command
+{
+	let names = command.get_subcommands()
+		.map(|subcommand| subcommand.get_name().to_string())
+		.collect::<Vec<_>>();
+	names.into_iter().fold(command, |command, name| {
+		let path = subpath(path, &name);
+		command.mut_subcommand(name, |subcommand| {
+			let subcommand = match help_examples(&path)
+			{
+				Some(examples) => subcommand.after_help(examples),
+				None => subcommand
+			};
+			with_examples(subcommand, &path)
+		})
+	})
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                               Manual pages.                                //
+////////////////////////////////////////////////////////////////////////////////
+
+/// Generate the manual pages of the command line interface into the specified
+/// directory, which is created if necessary: `quartiles-solver.1` for the
+/// program, and `quartiles-solver-<SUBCOMMAND>.1` for each subcommand, closed
+/// by its examples. Existing pages are replaced.
+///
+/// # Arguments
+///
+/// * `directory` - The directory.
+///
+/// # Errors
+///
+/// Any error that occurs while creating the directory or writing the pages.
+pub fn generate_man_pages(directory: &Path) -> io::Result<()>
+{
+	fs::create_dir_all(directory)?;
+	// The examples get a section of their own, laid out for the manual, so
+	// the pages are generated from the command line interface without them.
+	let mut command = Opts::command().disable_help_subcommand(true);
+	command.build();
+	generate_man_page(&command, "", directory)
+}
+
+/// Generate the manual page of the specified command, closed by its examples,
+/// and then those of its subcommands, recursively.
+///
+/// # Arguments
+///
+/// * `command` - The command, which must be built.
+/// * `path` - The path of the command, or the empty string for the root.
+/// * `directory` - The directory to write the manual pages to.
+///
+/// # Errors
+///
+/// Any error that occurs while writing the pages.
+fn generate_man_page(
+	command: &Command,
+	path: &str,
+	directory: &Path
+) -> io::Result<()>
+{
+	let man = Man::new(command.clone());
+	let mut file = BufWriter::new(
+		File::create(directory.join(man.get_filename()))?
+	);
+	man.render(&mut file)?;
+	if let Some(examples) = examples(path)
+	{
+		let mut roff = Roff::new();
+		roff.control("SH", ["EXAMPLES"]);
+		for example in examples
+		{
+			roff.control("TP", []);
+			roff.text([roman(example.description)]);
+			roff.text([bold(example.invocation)]);
+		}
+		roff.to_writer(&mut file)?;
+	}
+	file.flush()?;
+	for subcommand in command.get_subcommands().filter(|s| !s.is_hide_set())
+	{
+		let path = subpath(path, subcommand.get_name());
+		generate_man_page(subcommand, &path, directory)?;
+	}
+	Ok(())
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use clap::Command;
+	use tempfile::tempdir;
+
+	use crate::manual::{command, generate_man_pages, subpath, EXAMPLES};
+
+	/// Collect the paths of every subcommand of the specified command,
+	/// recursively.
+	fn paths(command: &Command, path: &str, paths: &mut Vec<String>)
+	{
+		for subcommand in command.get_subcommands()
+		{
+			let path = subpath(path, subcommand.get_name());
+			paths.push(path.clone());
+			self::paths(subcommand, &path, paths);
+		}
+	}
+
+	/// Ensure that every subcommand has examples, and that every example
+	/// belongs to a subcommand.
+	#[test]
+	fn test_coverage()
+	{
+		let mut all = Vec::new();
+		paths(&command(), "", &mut all);
+		for path in &all
+		{
+			// Downloading requires the network, so it goes without examples.
+			if path == "help" || path.ends_with(" help") || path == "dict fetch"
+			{
+				continue;
+			}
+			assert!(
+				EXAMPLES.iter().any(|(p, _)| p == path),
+				"no examples: {}",
+				path
+			);
+		}
+		for (path, examples) in EXAMPLES
+		{
+			assert!(all.iter().any(|p| p == path), "unknown: {}", path);
+			assert!(!examples.is_empty(), "empty: {}", path);
+		}
+	}
+
+	/// Ensure that every example parses, and that its help shows it.
+	#[test]
+	fn test_examples()
+	{
+		for (path, examples) in EXAMPLES
+		{
+			for example in examples.iter()
+			{
+				let invocation = example.invocation
+					.split("&&")
+					.next()
+					.unwrap();
+				let args = invocation
+					.split_whitespace()
+					.map(|arg| arg.trim_matches('\''));
+				assert!(
+					command().try_get_matches_from(args).is_ok(),
+					"{}",
+					invocation
+				);
+			}
+			let mut args = vec!["quartiles-solver"];
+			args.extend(path.split(' '));
+			args.push("--help");
+			let help = command()
+				.try_get_matches_from(args)
+				.unwrap_err()
+				.to_string();
+			assert!(help.contains("Examples:"), "{}", help);
+			assert!(help.contains(examples[0].invocation), "{}", help);
+		}
+	}
+
+	/// Ensure that a manual page is generated for the program and for each
+	/// subcommand, and that a subcommand's page closes with its examples.
+	#[test]
+	fn test_generate_man_pages()
+	{
+		let dir = tempdir().unwrap();
+		let directory = dir.path().join("man");
+		generate_man_pages(&directory).unwrap();
+		assert!(directory.join("quartiles-solver.1").exists());
+		assert!(directory.join("quartiles-solver-dict-match.1").exists());
+		let page = std::fs::read_to_string(
+			directory.join("quartiles-solver-solve.1")
+		).unwrap();
+		assert!(page.contains(".SH EXAMPLES"), "{}", page);
+		assert!(!page.contains(".SH EXTRA"), "{}", page);
+		assert!(page.contains("\\-\\-format json"), "{}", page);
+	}
+}
@@ -0,0 +1,214 @@
+//! # Paths
+//!
+//! Herein are the default locations of the files that the application reads
+//! and writes: the configuration, the dictionaries, the state, the history,
+//! the favorites, the log, and the crash reports. Each belongs to the platform's conventional
+//! directory, i.e., the XDG base directories on Linux,
+//! `~/Library/Application Support` on macOS, and the known folders on
+//! Windows. Where the platform offers no home directory, each falls back to a
+//! path relative to the working directory.
+
+use std::{
+	path::{Path, PathBuf},
+	sync::OnceLock
+};
+
+use directories::ProjectDirs;
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Paths.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The name of the configuration file.
+const CONFIG_FILE: &str = "quartiles.toml";
+
+/// The name of the directory containing the dictionary files.
+const DICTIONARY_DIRECTORY: &str = "dict";
+
+/// The name of the state file.
+const STATE_FILE: &str = "quartiles.state";
+
+/// The name of the history file.
+const HISTORY_FILE: &str = "quartiles-history.json";
+
+/// The name of the favorites file.
+const FAVORITES_FILE: &str = "quartiles-favorites.txt";
+
+/// The name of the log file.
+const LOG_FILE: &str = "quartiles.log";
+
+/// Answer the platform's directories for the application, if the platform
+/// offers a home directory.
+///
+/// # Returns
+///
+/// The project directories, if any.
+fn project_dirs() -> Option<&'static ProjectDirs>
+{
+	static DIRS: OnceLock<Option<ProjectDirs>> = OnceLock::new();
+	DIRS.get_or_init(|| ProjectDirs::from("com", "xebia", "quartiles-solver"))
+		.as_ref()
+}
+
+/// Answer the path to the named file within the directory chosen from the
+/// project directories, or the bare name, if there are none.
+///
+/// # Arguments
+///
+/// * `directory` - How to choose the directory.
+/// * `name` - The name of the file.
+///
+/// # Returns
+///
+/// The path.
+fn resolve(directory: fn(&ProjectDirs) -> &Path, name: &str) -> PathBuf
+{
+	match project_dirs()
+	{
+		Some(dirs) => directory(dirs).join(name),
+		None => PathBuf::from(name)
+	}
+}
+
+/// Answer the default path to the configuration file, e.g.,
+/// `~/.config/quartiles-solver/quartiles.toml` on Linux.
+///
+/// # Returns
+///
+/// The path.
+pub fn config_file() -> PathBuf
+{
+	resolve(ProjectDirs::config_dir, CONFIG_FILE)
+}
+
+/// Answer the default directory containing the dictionary files, e.g.,
+/// `~/.local/share/quartiles-solver/dict` on Linux.
+///
+/// # Returns
+///
+/// The path.
+pub fn dictionary_directory() -> PathBuf
+{
+	resolve(ProjectDirs::data_dir, DICTIONARY_DIRECTORY)
+}
+
+/// Answer the directory containing the dictionary files relative to the
+/// working directory, as when running from a checkout of the repository.
+///
+/// # Returns
+///
+/// The path.
+pub fn local_dictionary_directory() -> PathBuf
+{
+	PathBuf::from(DICTIONARY_DIRECTORY)
+}
+
+/// Answer the default path to the state file, e.g.,
+/// `~/.local/state/quartiles-solver/quartiles.state` on Linux. Platforms
+/// without a state directory use the local data directory instead.
+///
+/// # Returns
+///
+/// The path.
+pub fn state_file() -> PathBuf
+{
+	resolve(
+		|dirs| dirs.state_dir().unwrap_or_else(|| dirs.data_local_dir()),
+		STATE_FILE
+	)
+}
+
+/// Answer the default path to the history file, e.g.,
+/// `~/.local/share/quartiles-solver/quartiles-history.json` on Linux.
+///
+/// # Returns
+///
+/// The path.
+pub fn history_file() -> PathBuf
+{
+	resolve(ProjectDirs::data_dir, HISTORY_FILE)
+}
+
+/// Answer the default path to the favorites file, e.g.,
+/// `~/.local/share/quartiles-solver/quartiles-favorites.txt` on Linux.
+///
+/// # Returns
+///
+/// The path.
+pub fn favorites_file() -> PathBuf
+{
+	resolve(ProjectDirs::data_dir, FAVORITES_FILE)
+}
+
+/// Answer the default path to the log file, e.g.,
+/// `~/.local/state/quartiles-solver/quartiles.log` on Linux. Platforms
+/// without a state directory use the local data directory instead.
+///
+/// # Returns
+///
+/// The path.
+pub fn log_file() -> PathBuf
+{
+	resolve(
+		|dirs| dirs.state_dir().unwrap_or_else(|| dirs.data_local_dir()),
+		LOG_FILE
+	)
+}
+
+/// Answer the default directory for crash reports, e.g.,
+/// `~/.local/state/quartiles-solver` on Linux, beside the state file.
+/// Platforms without a state directory use the local data directory instead.
+///
+/// # Returns
+///
+/// The path.
+pub fn crash_directory() -> PathBuf
+{
+	match project_dirs()
+	{
+		Some(dirs) => dirs.state_dir()
+			.unwrap_or_else(|| dirs.data_local_dir())
+			.to_path_buf(),
+		None => PathBuf::from(".")
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::path::PathBuf;
+
+	use crate::paths::{
+		config_file,
+		crash_directory,
+		dictionary_directory,
+		favorites_file,
+		history_file,
+		local_dictionary_directory,
+		log_file,
+		state_file
+	};
+
+	/// Ensure that the default paths keep their familiar names, and that the
+	/// dictionary directory is absolute whenever the configuration file is.
+	#[test]
+	fn test_defaults()
+	{
+		assert!(config_file().ends_with("quartiles.toml"));
+		assert!(dictionary_directory().ends_with("dict"));
+		assert!(state_file().ends_with("quartiles.state"));
+		assert!(history_file().ends_with("quartiles-history.json"));
+		assert!(favorites_file().ends_with("quartiles-favorites.txt"));
+		assert!(log_file().ends_with("quartiles.log"));
+		assert_eq!(
+			config_file().is_absolute(),
+			dictionary_directory().is_absolute()
+		);
+		assert_eq!(local_dictionary_directory(), PathBuf::from("dict"));
+		assert_eq!(Some(crash_directory().as_path()), state_file().parent());
+	}
+}
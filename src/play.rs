@@ -0,0 +1,775 @@
+//! # Play mode
+//!
+//! Herein is the game logic of the play mode, in which the user forms words
+//! from the board, rather than watching the solver form them. The solver
+//! still solves the board up front, but only to judge the user's guesses.
+//! Several players may share the board, taking turns to guess. Players who
+//! give up may reveal the words that they have yet to find.
+
+use std::{
+	collections::{HashMap, HashSet},
+	time::{Duration, Instant}
+};
+
+use quartiles_solver::{
+	dictionary::normalize_word,
+	solver::{FragmentPath, Solver}
+};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Games.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A game in progress: the words that the board yields, and the words that the
+/// players have found so far. A word counts only for the player who found it
+/// first, so no word can be found twice.
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Game
+{
+	/// The score of every word that the board yields, keyed by word.
+	scores: HashMap<String, u32>,
+
+	/// The quartiles, i.e., the words formed from 4 fragments.
+	quartiles: HashSet<String>,
+
+	/// Every fragment path of every word that the board yields, with its
+	/// score, keyed by word. The first path of a word is the one that the
+	/// solver found first.
+	paths: HashMap<String, Vec<(FragmentPath, u32)>>,
+
+	/// The words found by the players, in the order that they were found.
+	found: Vec<String>,
+
+	/// The player who found each of the [words found](Self::found), by
+	/// index.
+	finders: Vec<usize>,
+
+	/// The total score of the words found by the players.
+	score: u32,
+
+	/// The number of players.
+	players: usize,
+
+	/// The player whose turn it is to guess, by index.
+	turn: usize,
+
+	/// The words that the players had yet to find when they gave up, and
+	/// their scores, in the order [revealed](Self::reveal), if they gave up.
+	revealed: Option<Vec<(String, u32)>>
+}
+
+impl Game
+{
+	/// Start a new game. The solver is run to completion, in order to learn
+	/// every word that the board yields, unless it runs out of time first, in
+	/// which case the game knows only the words found in time.
+	///
+	/// # Arguments
+	///
+	/// * `solver` - A fresh solver for the board.
+	/// * `budget` - The most time that the solver may spend, if its time is
+	///   limited.
+	///
+	/// # Returns
+	///
+	/// The new game.
+	pub fn new(solver: Solver, budget: Option<Duration>) -> Self
+	{
+		let solver = match budget
+		{
+			Some(budget) => solver.solve_within(budget),
+			None => solver.solve_fully()
+		};
+		let mut scores = HashMap::new();
+		let mut quartiles = HashSet::new();
+		let mut paths = HashMap::new();
+		for path in solver.solution_paths()
+		{
+			let word = solver.word(&path).to_string();
+			let score = solver.score(&path);
+			if path.is_full()
+			{
+				quartiles.insert(word.clone());
+			}
+			paths.entry(word.clone())
+				.or_insert_with(Vec::new)
+				.push((path, score));
+			scores.entry(word).or_insert(score);
+		}
+		Self {
+			scores,
+			quartiles,
+			paths,
+			found: Vec::new(),
+			finders: Vec::new(),
+			score: 0,
+			players: 1,
+			turn: 0,
+			revealed: None
+		}
+	}
+
+	/// Share the game among the specified number of players, who take turns
+	/// to guess, starting with the first.
+	///
+	/// # Arguments
+	///
+	/// * `players` - The number of players, which must be positive.
+	///
+	/// # Returns
+	///
+	/// The game, with the players installed.
+	///
+	/// # Panics
+	///
+	/// If `players` is zero.
+	pub fn with_players(mut self, players: usize) -> Self
+	{
+		assert!(players > 0, "A game needs at least one player");
+		self.players = players;
+		self
+	}
+
+	/// Answer the number of players.
+	///
+	/// # Returns
+	///
+	/// The number of players.
+	#[inline]
+	#[must_use]
+	pub fn players(&self) -> usize
+	{
+		self.players
+	}
+
+	/// Answer the player whose turn it is to guess.
+	///
+	/// # Returns
+	///
+	/// The index of the player.
+	#[inline]
+	#[must_use]
+	pub fn turn(&self) -> usize
+	{
+		self.turn
+	}
+
+	/// Answer the player who found the specified word, if any.
+	///
+	/// # Arguments
+	///
+	/// * `word` - The word.
+	///
+	/// # Returns
+	///
+	/// The index of the player, or `None` if the word hasn't been found.
+	#[must_use]
+	pub fn finder(&self, word: &str) -> Option<usize>
+	{
+		self.found
+			.iter()
+			.position(|found| found == word)
+			.map(|index| self.finders[index])
+	}
+
+	/// Answer the words found by the specified player.
+	///
+	/// # Arguments
+	///
+	/// * `player` - The index of the player.
+	///
+	/// # Returns
+	///
+	/// An iterator over the words, in the order that they were found.
+	pub fn found_by(&self, player: usize) -> impl Iterator<Item = &str>
+	{
+		self.found
+			.iter()
+			.zip(&self.finders)
+			.filter(move |&(_, &finder)| finder == player)
+			.map(|(word, _)| word.as_str())
+	}
+
+	/// Answer the total score of the words found by the specified player.
+	///
+	/// # Arguments
+	///
+	/// * `player` - The index of the player.
+	///
+	/// # Returns
+	///
+	/// The player's score.
+	#[must_use]
+	pub fn player_score(&self, player: usize) -> u32
+	{
+		self.found_by(player).map(|word| self.scores[word]).sum()
+	}
+
+	/// Answer the players with the highest score, i.e., the winners, of
+	/// whom there are several in case of a tie.
+	///
+	/// # Returns
+	///
+	/// The indices of the winners, in order.
+	#[must_use]
+	pub fn leaders(&self) -> Vec<usize>
+	{
+		let scores = (0..self.players)
+			.map(|player| self.player_score(player))
+			.collect::<Vec<_>>();
+		let best = scores.iter().copied().max().unwrap_or_default();
+		(0..self.players)
+			.filter(|&player| scores[player] == best)
+			.collect()
+	}
+
+	/// Answer the words found by the user.
+	///
+	/// # Returns
+	///
+	/// The words, in the order that they were found.
+	#[inline]
+	#[must_use]
+	pub fn found(&self) -> &[String]
+	{
+		&self.found
+	}
+
+	/// Check whether the specified word is a quartile.
+	///
+	/// # Arguments
+	///
+	/// * `word` - The word.
+	///
+	/// # Returns
+	///
+	/// `true` if the word is a quartile, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_quartile(&self, word: &str) -> bool
+	{
+		self.quartiles.contains(word)
+	}
+
+	/// Answer the total score of the words found by the players.
+	///
+	/// # Returns
+	///
+	/// The score.
+	#[inline]
+	#[must_use]
+	pub fn score(&self) -> u32
+	{
+		self.score
+	}
+
+	/// Answer the number of words that the board yields.
+	///
+	/// # Returns
+	///
+	/// The number of words.
+	#[inline]
+	#[must_use]
+	pub fn word_count(&self) -> usize
+	{
+		self.scores.len()
+	}
+
+	/// Answer the total score of every word that the board yields, i.e., the
+	/// best possible score.
+	///
+	/// # Returns
+	///
+	/// The maximum score.
+	#[must_use]
+	pub fn max_score(&self) -> u32
+	{
+		self.scores.values().sum()
+	}
+
+	/// Answer every word that the board yields, together with its score.
+	///
+	/// # Returns
+	///
+	/// The words and their scores, quartiles first and then alphabetically.
+	#[must_use]
+	pub fn words(&self) -> Vec<(&str, u32)>
+	{
+		let mut words = self.scores
+			.iter()
+			.map(|(word, &score)| (word.as_str(), score))
+			.collect::<Vec<_>>();
+		words.sort_by_key(|&(word, _)| (!self.is_quartile(word), word));
+		words
+	}
+
+	/// Check whether the user has found the specified word.
+	///
+	/// # Arguments
+	///
+	/// * `word` - The word.
+	///
+	/// # Returns
+	///
+	/// `true` if the word was found, `false` otherwise.
+	#[must_use]
+	pub fn is_found(&self, word: &str) -> bool
+	{
+		self.found.iter().any(|found| found == word)
+	}
+
+	/// Answer the number of quartiles found by the user.
+	///
+	/// # Returns
+	///
+	/// The number of quartiles found.
+	#[must_use]
+	pub fn quartiles_found(&self) -> usize
+	{
+		self.found_quartiles().count()
+	}
+
+	/// Answer the number of quartiles that the board yields.
+	///
+	/// # Returns
+	///
+	/// The number of quartiles.
+	#[inline]
+	#[must_use]
+	pub fn quartile_count(&self) -> usize
+	{
+		self.quartiles.len()
+	}
+
+	/// Answer the quartiles found by the user.
+	///
+	/// # Returns
+	///
+	/// An iterator over the quartiles, in the order that they were found.
+	pub fn found_quartiles(&self) -> impl Iterator<Item = &str>
+	{
+		self.found
+			.iter()
+			.filter(|word| self.is_quartile(word))
+			.map(String::as_str)
+	}
+
+	/// Answer the number of bonus words, i.e., words other than quartiles,
+	/// that the user has yet to find.
+	///
+	/// # Returns
+	///
+	/// The number of bonus words remaining.
+	#[must_use]
+	pub fn bonus_words_remaining(&self) -> usize
+	{
+		let bonus_words = self.word_count() - self.quartile_count();
+		let found = self.found.len() - self.quartiles_found();
+		bonus_words - found
+	}
+
+	/// Check whether the user has won the game, i.e., found every quartile.
+	///
+	/// # Returns
+	///
+	/// `true` if the game is won, `false` otherwise.
+	#[must_use]
+	pub fn is_won(&self) -> bool
+	{
+		self.quartiles_found() == self.quartiles.len()
+	}
+
+	/// Judge the specified guess on behalf of the player whose turn it is,
+	/// crediting the player with it if the board yields it and no one has
+	/// found it yet. Every guess then passes the turn to the next player.
+	///
+	/// # Arguments
+	///
+	/// * `guess` - The guess, which is normalized before judging.
+	///
+	/// # Returns
+	///
+	/// The verdict.
+	pub fn guess(&mut self, guess: &str) -> Verdict
+	{
+		let word = normalize_word(guess);
+		let verdict = if self.found.contains(&word)
+		{
+			Verdict::Duplicate
+		}
+		else
+		{
+			match self.scores.get(&word)
+			{
+				None => Verdict::Invalid,
+				Some(&score) =>
+				{
+					let is_quartile = self.is_quartile(&word);
+					self.found.push(word);
+					self.finders.push(self.turn);
+					self.score += score;
+					match is_quartile
+					{
+						true => Verdict::Quartile { score },
+						false => Verdict::Word { score }
+					}
+				}
+			}
+		};
+		self.turn = (self.turn + 1) % self.players;
+		verdict
+	}
+
+	/// Give up, revealing the words that the players have yet to find, i.e.,
+	/// the words of the solution learned up front, less the words found, so
+	/// that nothing is searched again. The words are revealed in order of
+	/// fragment coverage: first those that use the most fragments that no
+	/// word found uses, since they say the most about the board, then those
+	/// of the most fragments, and then alphabetically.
+	pub fn reveal(&mut self)
+	{
+		let covered = self.found
+			.iter()
+			.filter_map(|word| self.paths.get(word)?.first())
+			.flat_map(|(path, _)| path.iter().flatten())
+			.collect::<HashSet<_>>();
+		// A word formed by several paths is ranked by its best path.
+		let ranks = self.paths
+			.iter()
+			.filter(|(word, _)| !self.is_found(word))
+			.map(|(word, paths)| {
+				let rank = paths
+					.iter()
+					.map(|(path, score)| {
+						let indices = path.iter().flatten().collect::<Vec<_>>();
+						let uncovered = indices
+							.iter()
+							.filter(|index| !covered.contains(index))
+							.count();
+						(uncovered, indices.len(), *score)
+					})
+					.max()
+					.unwrap_or_default();
+				(word.clone(), rank)
+			});
+		let mut revealed = ranks.collect::<Vec<_>>();
+		revealed.sort_by(|(a, (m, n, _)), (b, (p, q, _))| {
+			(p, q).cmp(&(m, n)).then(a.cmp(b))
+		});
+		self.revealed = Some(
+			revealed
+				.into_iter()
+				.map(|(word, (_, _, score))| (word, score))
+				.collect()
+		);
+	}
+
+	/// Answer the words that the players had yet to find when they gave up,
+	/// in the order [revealed](Self::reveal).
+	///
+	/// # Returns
+	///
+	/// The words and their scores, or `None` if the players haven't given
+	/// up.
+	#[must_use]
+	pub fn revealed(&self) -> Option<&[(String, u32)]>
+	{
+		self.revealed.as_deref()
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Timers.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The countdown of a timed game. The round ends when the countdown expires,
+/// or when the timer is [stopped](Self::stop) because the game was won.
+#[derive(Copy, Clone, Debug)]
+#[must_use]
+pub struct Timer
+{
+	/// When the countdown started.
+	started: Instant,
+
+	/// How long the round lasts.
+	limit: Duration,
+
+	/// How long the round had lasted when the timer was stopped, if it was.
+	stopped: Option<Duration>
+}
+
+impl Timer
+{
+	/// Start a countdown.
+	///
+	/// # Arguments
+	///
+	/// * `limit` - How long the round lasts.
+	///
+	/// # Returns
+	///
+	/// The running timer.
+	pub fn new(limit: Duration) -> Self
+	{
+		Self {
+			started: Instant::now(),
+			limit,
+			stopped: None
+		}
+	}
+
+	/// Answer how long the round lasts.
+	///
+	/// # Returns
+	///
+	/// The time limit.
+	#[inline]
+	#[must_use]
+	pub fn limit(&self) -> Duration
+	{
+		self.limit
+	}
+
+	/// Answer how long the round has lasted so far, which never exceeds the
+	/// [limit](Self::limit).
+	///
+	/// # Returns
+	///
+	/// The elapsed time.
+	#[must_use]
+	pub fn elapsed(&self) -> Duration
+	{
+		self.stopped
+			.unwrap_or_else(|| self.started.elapsed())
+			.min(self.limit)
+	}
+
+	/// Answer how much time remains in the round.
+	///
+	/// # Returns
+	///
+	/// The remaining time.
+	#[must_use]
+	pub fn remaining(&self) -> Duration
+	{
+		self.limit - self.elapsed()
+	}
+
+	/// Check whether the countdown has expired.
+	///
+	/// # Returns
+	///
+	/// `true` if no time remains, `false` otherwise.
+	#[must_use]
+	pub fn is_expired(&self) -> bool
+	{
+		self.remaining().is_zero()
+	}
+
+	/// Check whether the round is over, i.e., whether the countdown has
+	/// expired or the timer was stopped.
+	///
+	/// # Returns
+	///
+	/// `true` if the round is over, `false` otherwise.
+	#[must_use]
+	pub fn is_over(&self) -> bool
+	{
+		self.stopped.is_some() || self.is_expired()
+	}
+
+	/// Stop the countdown, freezing the clock. Stopping an expired or stopped
+	/// timer has no effect.
+	pub fn stop(&mut self)
+	{
+		if !self.is_over()
+		{
+			self.stopped = Some(self.elapsed());
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Verdicts.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The verdict on a guess.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Verdict
+{
+	/// The guess is a word of the board, but not a quartile.
+	Word
+	{
+		/// The score of the word.
+		score: u32
+	},
+
+	/// The guess is a quartile.
+	Quartile
+	{
+		/// The score of the quartile.
+		score: u32
+	},
+
+	/// The guess was already found.
+	Duplicate,
+
+	/// The board doesn't yield the guess.
+	Invalid
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::rc::Rc;
+
+	use quartiles_solver::{
+		dictionary::Dictionary,
+		solver::{Fragment, Solver}
+	};
+
+	use std::time::Duration;
+
+	use crate::play::{Game, Timer, Verdict};
+
+	/// Ensure that guesses are judged against the words of the board.
+	#[test]
+	fn test_guess()
+	{
+		let dictionary = [
+			"crossword", "cross", "word", "puzzle", "zle"
+		]
+		.into_iter()
+		.collect::<Dictionary>();
+		let mut fragments = [Fragment::from("x"); 20];
+		for (index, fragment) in ["cr", "oss", "wo", "rd", "puz"]
+			.into_iter()
+			.enumerate()
+		{
+			fragments[index] = Fragment::from(fragment);
+		}
+		let solver = Solver::new(Rc::new(dictionary), fragments);
+		let mut game = Game::new(solver, None);
+		assert_eq!(game.word_count(), 3);
+		assert_eq!(game.quartile_count(), 1);
+		assert_eq!(game.bonus_words_remaining(), 2);
+		assert_eq!(
+			game.words().iter().map(|&(word, _)| word).collect::<Vec<_>>(),
+			["crossword", "cross", "word"]
+		);
+		assert!(!game.is_won());
+		assert!(matches!(game.guess("Cross"), Verdict::Word { .. }));
+		assert_eq!(game.guess("cross"), Verdict::Duplicate);
+		assert_eq!(game.guess("puzzle"), Verdict::Invalid);
+		assert!(matches!(game.guess("crossword"), Verdict::Quartile { .. }));
+		assert_eq!(game.found(), ["cross", "crossword"]);
+		assert_eq!(game.quartiles_found(), 1);
+		assert_eq!(game.found_quartiles().collect::<Vec<_>>(), ["crossword"]);
+		assert_eq!(game.bonus_words_remaining(), 1);
+		assert!(game.score() > 0);
+		assert!(game.score() < game.max_score());
+		assert!(game.is_found("cross"));
+		assert!(!game.is_found("word"));
+		assert!(game.is_won());
+	}
+
+	/// Ensure that giving up reveals only the words yet to be found, ordered
+	/// by the fragments that they use that no word found uses.
+	#[test]
+	fn test_reveal()
+	{
+		let dictionary = ["crossword", "cross", "word", "words"]
+			.into_iter()
+			.collect::<Dictionary>();
+		let dictionary = Rc::new(dictionary);
+		let mut fragments = [Fragment::from("x"); 20];
+		for (index, fragment) in ["cr", "oss", "wo", "rd", "s"]
+			.into_iter()
+			.enumerate()
+		{
+			fragments[index] = Fragment::from(fragment);
+		}
+		let solver = Solver::new(dictionary, fragments);
+		let mut game = Game::new(solver, None);
+		assert_eq!(game.revealed(), None);
+		assert!(matches!(game.guess("cross"), Verdict::Word { .. }));
+		game.reveal();
+		assert_eq!(
+			game.revealed(),
+			Some(
+				&[
+					("words".to_string(), 4),
+					("crossword".to_string(), 8),
+					("word".to_string(), 2)
+				][..]
+			)
+		);
+		assert_eq!(game.found(), ["cross"]);
+	}
+
+	/// Ensure that players take turns, that each word counts only for the
+	/// player who found it first, and that the leaders are reported.
+	#[test]
+	fn test_players()
+	{
+		let dictionary = [
+			"crossword", "cross", "word", "puzzle", "zle"
+		]
+		.into_iter()
+		.collect::<Dictionary>();
+		let mut fragments = [Fragment::from("x"); 20];
+		for (index, fragment) in ["cr", "oss", "wo", "rd", "puz"]
+			.into_iter()
+			.enumerate()
+		{
+			fragments[index] = Fragment::from(fragment);
+		}
+		let solver = Solver::new(Rc::new(dictionary), fragments);
+		let mut game = Game::new(solver, None).with_players(2);
+		assert_eq!(game.players(), 2);
+		assert_eq!(game.turn(), 0);
+		assert_eq!(game.leaders(), [0, 1]);
+		assert!(matches!(game.guess("cross"), Verdict::Word { .. }));
+		assert_eq!(game.turn(), 1);
+		assert_eq!(game.guess("cross"), Verdict::Duplicate);
+		assert_eq!(game.turn(), 0);
+		assert_eq!(game.guess("puzzle"), Verdict::Invalid);
+		assert!(matches!(game.guess("crossword"), Verdict::Quartile { .. }));
+		assert_eq!(game.turn(), 0);
+		assert_eq!(game.finder("cross"), Some(0));
+		assert_eq!(game.finder("crossword"), Some(1));
+		assert_eq!(game.finder("word"), None);
+		assert_eq!(game.found_by(1).collect::<Vec<_>>(), ["crossword"]);
+		assert_eq!(
+			game.player_score(0) + game.player_score(1),
+			game.score()
+		);
+		assert_eq!(game.leaders(), [1]);
+	}
+
+	/// Ensure that timers expire, and that stopping a timer freezes it.
+	#[test]
+	fn test_timer()
+	{
+		let timer = Timer::new(Duration::ZERO);
+		assert!(timer.is_expired());
+		assert!(timer.is_over());
+		assert_eq!(timer.remaining(), Duration::ZERO);
+
+		let limit = Duration::from_secs(3600);
+		let mut timer = Timer::new(limit);
+		assert!(!timer.is_over());
+		assert!(timer.remaining() <= limit);
+		timer.stop();
+		assert!(timer.is_over());
+		assert!(!timer.is_expired());
+		let remaining = timer.remaining();
+		std::thread::sleep(Duration::from_millis(10));
+		assert_eq!(timer.remaining(), remaining);
+	}
+}
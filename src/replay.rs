@@ -0,0 +1,547 @@
+//! # Replays
+//!
+//! Herein is support for replaying a finished solve: the words of the
+//! solution are highlighted on the board one at a time, in the order that the
+//! solver discovered them, at an adjustable speed. A replay can be recorded as
+//! it plays, frame by frame, in the [asciicast v2] format, so that it can be
+//! played back with `asciinema play` or embedded in a web page with the
+//! asciinema player.
+//!
+//! [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+
+use std::{
+	fs::File,
+	io::{self, BufWriter, Write},
+	path::Path,
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH}
+};
+
+use quartiles_solver::solver::FragmentPath;
+use ratatui::{
+	buffer::{Buffer, Cell},
+	style::{Color, Modifier}
+};
+use serde_json::json;
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Replays.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// How long each word is highlighted during a replay at normal speed.
+const STEP_INTERVAL: Duration = Duration::from_millis(800);
+
+/// The speeds of a replay, as multiples of the normal speed, from slowest to
+/// fastest.
+const SPEEDS: [f64; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+
+/// The index of the normal speed within [`SPEEDS`].
+const NORMAL_SPEED: usize = 2;
+
+/// A replay of a finished solve, which steps through the fragment paths of
+/// the distinct words of the solution, in order of discovery.
+#[derive(Clone, Debug)]
+pub struct Replay
+{
+	/// The fragment path of each word, in order of discovery. Never empty.
+	paths: Vec<FragmentPath>,
+
+	/// The index of the fragment path currently highlighted.
+	step: usize,
+
+	/// The index of the current speed within [`SPEEDS`].
+	speed: usize,
+
+	/// When to advance to the next step.
+	until: Instant
+}
+
+impl Replay
+{
+	/// Start a replay at normal speed, highlighting the first fragment path.
+	///
+	/// # Arguments
+	///
+	/// * `paths` - The fragment path of each word, in order of discovery.
+	/// * `now` - The current time.
+	///
+	/// # Returns
+	///
+	/// The replay, or `None` if there are no fragment paths to replay.
+	pub fn new(paths: Vec<FragmentPath>, now: Instant) -> Option<Self>
+	{
+		if paths.is_empty()
+		{
+			return None
+		}
+		let mut replay = Self {
+			paths,
+			step: 0,
+			speed: NORMAL_SPEED,
+			until: now
+		};
+		replay.until = now + replay.interval();
+		Some(replay)
+	}
+
+	/// Answer the fragment path currently highlighted.
+	///
+	/// # Returns
+	///
+	/// The fragment path.
+	pub fn path(&self) -> &FragmentPath
+	{
+		&self.paths[self.step]
+	}
+
+	/// Answer the fragment paths discovered so far, up to and including the
+	/// one currently highlighted.
+	///
+	/// # Returns
+	///
+	/// The fragment paths, in order of discovery.
+	pub fn discovered(&self) -> &[FragmentPath]
+	{
+		&self.paths[..=self.step]
+	}
+
+	/// Answer the index of the fragment path currently highlighted.
+	///
+	/// # Returns
+	///
+	/// The index, counted from zero.
+	#[inline]
+	pub fn step(&self) -> usize
+	{
+		self.step
+	}
+
+	/// Answer the number of steps of the replay.
+	///
+	/// # Returns
+	///
+	/// The number of steps, i.e., of distinct words.
+	#[inline]
+	pub fn len(&self) -> usize
+	{
+		self.paths.len()
+	}
+
+	/// Answer the current speed, as a multiple of the normal speed.
+	///
+	/// # Returns
+	///
+	/// The speed.
+	#[inline]
+	pub fn speed(&self) -> f64
+	{
+		SPEEDS[self.speed]
+	}
+
+	/// Speed up the replay, unless it is already at its fastest. The current
+	/// step is cut short accordingly.
+	///
+	/// # Arguments
+	///
+	/// * `now` - The current time.
+	pub fn faster(&mut self, now: Instant)
+	{
+		if self.speed + 1 < SPEEDS.len()
+		{
+			self.speed += 1;
+			self.until = self.until.min(now + self.interval());
+		}
+	}
+
+	/// Slow down the replay, unless it is already at its slowest.
+	pub fn slower(&mut self)
+	{
+		self.speed = self.speed.saturating_sub(1);
+	}
+
+	/// Advance the replay to the step due at the specified time.
+	///
+	/// # Arguments
+	///
+	/// * `now` - The current time.
+	///
+	/// # Returns
+	///
+	/// `true` if the replay continues, or `false` if its last step is over.
+	pub fn advance(&mut self, now: Instant) -> bool
+	{
+		while now >= self.until
+		{
+			if self.step + 1 == self.paths.len()
+			{
+				return false
+			}
+			self.step += 1;
+			self.until += self.interval();
+		}
+		true
+	}
+
+	/// Answer how long each step lasts at the current speed.
+	///
+	/// # Returns
+	///
+	/// The duration of a step.
+	fn interval(&self) -> Duration
+	{
+		STEP_INTERVAL.div_f64(self.speed())
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                Recordings.                                 //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A recorder that writes the frames of the TUI to an [asciicast v2] file:
+/// a header, followed by one output event per frame that changed anything,
+/// comprising the escape sequences that redraw the changed cells.
+///
+/// [asciicast v2]: https://docs.asciinema.org/manual/asciicast/v2/
+pub struct Recorder
+{
+	/// The destination of the recording.
+	writer: Box<dyn Write>,
+
+	/// When the first frame was recorded, if any.
+	started: Option<Instant>,
+
+	/// The most recently recorded frame, if any.
+	previous: Option<Buffer>
+}
+
+impl Recorder
+{
+	/// Construct a recorder that writes to the specified destination.
+	///
+	/// # Arguments
+	///
+	/// * `writer` - The destination of the recording.
+	///
+	/// # Returns
+	///
+	/// The recorder.
+	pub fn new(writer: impl Write + 'static) -> Self
+	{
+		Self {
+			writer: Box::new(writer),
+			started: None,
+			previous: None
+		}
+	}
+
+	/// Construct a recorder that writes to the specified file, which is
+	/// replaced.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The path to the file.
+	///
+	/// # Returns
+	///
+	/// The recorder.
+	///
+	/// # Errors
+	///
+	/// If the file cannot be created, an error is returned.
+	pub fn create(path: &Path) -> Result<Self, io::Error>
+	{
+		Ok(Self::new(BufWriter::new(File::create(path)?)))
+	}
+
+	/// Record the specified frame. The first frame writes the header, whose
+	/// size is that of the frame, and then the whole frame; every later frame
+	/// writes only the cells that changed since the previous frame, if any.
+	///
+	/// # Arguments
+	///
+	/// * `frame` - The frame.
+	/// * `now` - When the frame was drawn.
+	///
+	/// # Errors
+	///
+	/// If the recording cannot be written, an error is returned.
+	pub fn record(&mut self, frame: &Buffer, now: Instant)
+		-> Result<(), io::Error>
+	{
+		let started = match self.started
+		{
+			Some(started) => started,
+			None =>
+			{
+				let timestamp = SystemTime::now()
+					.duration_since(UNIX_EPOCH)
+					.map(|d| d.as_secs())
+					.unwrap_or_default();
+				let header = json!({
+					"version": 2,
+					"width": frame.area.width,
+					"height": frame.area.height,
+					"timestamp": timestamp,
+					"title": "Quartiles replay"
+				});
+				writeln!(self.writer, "{}", header)?;
+				self.started = Some(now);
+				now
+			}
+		};
+		// Redraw the whole frame at first, and whenever the frame changes
+		// size.
+		let (mut output, previous) = match self.previous.take()
+		{
+			Some(previous) if previous.area == frame.area =>
+			{
+				(String::new(), previous)
+			},
+			_ =>
+			{
+				(
+					"\x1b[?25l\x1b[0m\x1b[2J".to_string(),
+					Buffer::empty(frame.area)
+				)
+			}
+		};
+		output.push_str(&escape_sequences(&previous, frame));
+		if !output.is_empty()
+		{
+			// Round the time to the microsecond, as asciinema does.
+			let time = now.saturating_duration_since(started).as_micros();
+			let event = json!([time as f64 / 1_000_000.0, "o", output]);
+			writeln!(self.writer, "{}", event)?;
+		}
+		self.previous = Some(frame.clone());
+		Ok(())
+	}
+
+	/// Finish the recording, flushing it to its destination.
+	///
+	/// # Errors
+	///
+	/// If the recording cannot be flushed, an error is returned.
+	pub fn finish(mut self) -> Result<(), io::Error>
+	{
+		self.writer.flush()
+	}
+}
+
+/// Compose the escape sequences that redraw the cells of the specified frame
+/// that differ from the previous frame, positioning the cursor only where the
+/// changed cells aren't contiguous, and changing the style only where it
+/// differs from the preceding cell. The sequences end by resetting the style.
+///
+/// # Arguments
+///
+/// * `previous` - The previous frame.
+/// * `next` - The frame to draw.
+///
+/// # Returns
+///
+/// The escape sequences, or an empty string if nothing changed.
+fn escape_sequences(previous: &Buffer, next: &Buffer) -> String
+{
+	let mut output = String::new();
+	let mut cursor = None;
+	let mut style = None;
+	for (x, y, cell) in previous.diff(next)
+	{
+		if cursor != Some((x, y))
+		{
+			output.push_str(&format!("\x1b[{};{}H", y + 1, x + 1));
+		}
+		let cell_style = (cell.fg, cell.bg, cell.modifier);
+		if style != Some(cell_style)
+		{
+			output.push_str(&select_graphic_rendition(cell));
+			style = Some(cell_style);
+		}
+		output.push_str(cell.symbol());
+		cursor = Some((x + 1, y));
+	}
+	if !output.is_empty()
+	{
+		output.push_str("\x1b[0m");
+	}
+	output
+}
+
+/// Compose the escape sequence that selects the style of the specified cell,
+/// starting from the default style.
+///
+/// # Arguments
+///
+/// * `cell` - The cell.
+///
+/// # Returns
+///
+/// The escape sequence.
+fn select_graphic_rendition(cell: &Cell) -> String
+{
+	let mut parameters = vec!["0".to_string()];
+	let modifiers = [
+		(Modifier::BOLD, "1"),
+		(Modifier::DIM, "2"),
+		(Modifier::ITALIC, "3"),
+		(Modifier::UNDERLINED, "4"),
+		(Modifier::SLOW_BLINK, "5"),
+		(Modifier::RAPID_BLINK, "6"),
+		(Modifier::REVERSED, "7"),
+		(Modifier::HIDDEN, "8"),
+		(Modifier::CROSSED_OUT, "9")
+	];
+	for (modifier, parameter) in modifiers
+	{
+		if cell.modifier.contains(modifier)
+		{
+			parameters.push(parameter.to_string());
+		}
+	}
+	parameters.extend(color_parameter(cell.fg, 30));
+	parameters.extend(color_parameter(cell.bg, 40));
+	format!("\x1b[{}m", parameters.join(";"))
+}
+
+/// Compose the parameter that selects the specified color, for the
+/// foreground or the background.
+///
+/// # Arguments
+///
+/// * `color` - The color.
+/// * `base` - `30` for the foreground, or `40` for the background.
+///
+/// # Returns
+///
+/// The parameter, or `None` for the default color.
+fn color_parameter(color: Color, base: u8) -> Option<String>
+{
+	let parameter = match color
+	{
+		Color::Reset => return None,
+		Color::Black => base.to_string(),
+		Color::Red => (base + 1).to_string(),
+		Color::Green => (base + 2).to_string(),
+		Color::Yellow => (base + 3).to_string(),
+		Color::Blue => (base + 4).to_string(),
+		Color::Magenta => (base + 5).to_string(),
+		Color::Cyan => (base + 6).to_string(),
+		Color::Gray => (base + 7).to_string(),
+		Color::DarkGray => (base + 60).to_string(),
+		Color::LightRed => (base + 61).to_string(),
+		Color::LightGreen => (base + 62).to_string(),
+		Color::LightYellow => (base + 63).to_string(),
+		Color::LightBlue => (base + 64).to_string(),
+		Color::LightMagenta => (base + 65).to_string(),
+		Color::LightCyan => (base + 66).to_string(),
+		Color::White => (base + 67).to_string(),
+		Color::Indexed(index) => format!("{};5;{}", base + 8, index),
+		Color::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b)
+	};
+	Some(parameter)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::{
+		cell::RefCell,
+		io::{self, Write},
+		rc::Rc,
+		time::{Duration, Instant}
+	};
+
+	use quartiles_solver::solver::FragmentPath;
+	use ratatui::{buffer::Buffer, layout::Rect, style::{Color, Style}};
+
+	use crate::replay::{Recorder, Replay, STEP_INTERVAL};
+
+	/// A destination that can be inspected while a recorder owns it.
+	#[derive(Clone, Default)]
+	struct Shared(Rc<RefCell<Vec<u8>>>);
+
+	impl Write for Shared
+	{
+		fn write(&mut self, buf: &[u8]) -> io::Result<usize>
+		{
+			self.0.borrow_mut().write(buf)
+		}
+
+		fn flush(&mut self) -> io::Result<()>
+		{
+			Ok(())
+		}
+	}
+
+	/// Ensure that a replay steps through its fragment paths on schedule,
+	/// that its speed can be changed, and that it ends after its last step.
+	#[test]
+	fn test_replay()
+	{
+		assert!(Replay::new(Vec::new(), Instant::now()).is_none());
+		let paths = [0, 2, 4]
+			.map(|index| {
+				FragmentPath::from([Some(index), Some(index + 1), None, None])
+			})
+			.to_vec();
+		let now = Instant::now();
+		let mut replay = Replay::new(paths.clone(), now).unwrap();
+		assert_eq!(replay.len(), 3);
+		assert_eq!(replay.path(), &paths[0]);
+		assert!(replay.advance(now + STEP_INTERVAL / 2));
+		assert_eq!(replay.step(), 0);
+		assert!(replay.advance(now + STEP_INTERVAL));
+		assert_eq!(replay.step(), 1);
+		assert_eq!(replay.discovered(), &paths[..2]);
+		replay.faster(now + STEP_INTERVAL);
+		assert_eq!(replay.speed(), 2.0);
+		assert!(replay.advance(now + STEP_INTERVAL * 3 / 2));
+		assert_eq!(replay.step(), 2);
+		assert!(!replay.advance(now + STEP_INTERVAL * 2));
+		replay.slower();
+		replay.slower();
+		replay.slower();
+		replay.slower();
+		assert_eq!(replay.speed(), 0.25);
+	}
+
+	/// Ensure that a recording comprises the header and one event per frame
+	/// that changed anything, redrawing only the changed cells.
+	#[test]
+	fn test_recorder()
+	{
+		let destination = Shared::default();
+		let mut recorder = Recorder::new(destination.clone());
+		let area = Rect::new(0, 0, 6, 2);
+		let mut frame = Buffer::empty(area);
+		frame.set_string(0, 0, "ab", Style::default().fg(Color::Red));
+		let now = Instant::now();
+		recorder.record(&frame, now).unwrap();
+		recorder.record(&frame, now + Duration::from_millis(250)).unwrap();
+		frame.set_string(4, 1, "z", Style::default());
+		recorder.record(&frame, now + Duration::from_millis(500)).unwrap();
+		recorder.finish().unwrap();
+		let recording = String::from_utf8(destination.0.take()).unwrap();
+		let lines = recording.lines()
+			.map(|line| serde_json::from_str(line).unwrap())
+			.collect::<Vec<serde_json::Value>>();
+		assert_eq!(lines.len(), 3, "{}", recording);
+		assert_eq!(lines[0]["version"], 2);
+		assert_eq!(lines[0]["width"], 6);
+		assert_eq!(lines[0]["height"], 2);
+		assert_eq!(lines[1][0], 0.0);
+		assert_eq!(lines[1][1], "o");
+		let output = lines[1][2].as_str().unwrap();
+		assert!(
+			output.contains("\x1b[2J\x1b[1;1H\x1b[0;31mab"),
+			"{:?}",
+			output
+		);
+		assert_eq!(lines[2][0], 0.5);
+		assert_eq!(lines[2][2], "\x1b[2;5H\x1b[0mz\x1b[0m");
+	}
+}
@@ -0,0 +1,283 @@
+//! # Scripting
+//!
+//! Herein is support for custom [word filters](WordFilter) written in
+//! [Rhai](https://rhai.rs). A script may define either or both of the
+//! following functions:
+//!
+//! * `filter(word, fragments)` - Answer `true` if the word may enter the
+//!   solution, `false` otherwise.
+//! * `score(word, fragments)` - Answer the score of the word.
+//!
+//! In both cases, `word` is the candidate word and `fragments` is the number of
+//! fragments that form it. An absent function falls back to the default
+//! behavior of the solver.
+//!
+//! Scripts run on the solver's thread, which is the UI thread of the TUI, so
+//! the engine bounds their work: a call that exceeds
+//! [`MAX_OPERATIONS`], [`MAX_CALL_LEVELS`], or [`MAX_STRING_SIZE`] is aborted
+//! and its word rejected, rather than hanging the application.
+
+use std::{
+	fmt::{self, Debug, Formatter},
+	fs,
+	io::{self, ErrorKind},
+	path::Path
+};
+
+use rhai::{Engine, EvalAltResult, Scope, AST};
+use tracing::{trace, warn};
+
+use crate::solver::{FragmentPath, WordFilter};
+
+////////////////////////////////////////////////////////////////////////////////
+//                              Script filters.                               //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The maximum number of operations that a single call of a script function
+/// may perform. Filtering a word should take a few dozen operations at most,
+/// so this is generous, yet stops an endless loop within milliseconds.
+pub const MAX_OPERATIONS: u64 = 100_000;
+
+/// The maximum depth of nested function calls within a script.
+pub const MAX_CALL_LEVELS: usize = 32;
+
+/// The maximum depth of nested expressions within a script, both at the top
+/// level and within functions.
+pub const MAX_EXPR_DEPTH: usize = 64;
+
+/// The maximum length of a string that a script may build, in bytes.
+pub const MAX_STRING_SIZE: usize = 1 << 16;
+
+/// A [word filter](WordFilter) backed by a compiled Rhai script.
+#[must_use]
+pub struct ScriptFilter
+{
+	/// The scripting engine.
+	engine: Engine,
+
+	/// The compiled script.
+	ast: AST,
+
+	/// Whether the script defines `filter`.
+	has_filter: bool,
+
+	/// Whether the script defines `score`.
+	has_score: bool
+}
+
+impl ScriptFilter
+{
+	/// Compile a script filter from the given source code.
+	///
+	/// # Arguments
+	///
+	/// * `source` - The source code of the script.
+	///
+	/// # Returns
+	///
+	/// The compiled script filter.
+	///
+	/// # Errors
+	///
+	/// If the script fails to compile, e.g., because it nests expressions
+	/// beyond [`MAX_EXPR_DEPTH`], an [`ErrorKind::InvalidData`] is returned.
+	pub fn from_source(source: &str) -> Result<Self, io::Error>
+	{
+		let mut engine = Engine::new();
+		engine
+			.set_max_operations(MAX_OPERATIONS)
+			.set_max_call_levels(MAX_CALL_LEVELS)
+			.set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH)
+			.set_max_string_size(MAX_STRING_SIZE);
+		let ast = engine
+			.compile(source)
+			.map_err(|e| io::Error::new(ErrorKind::InvalidData, e.to_string()))?;
+		let defines = |name: &str| {
+			ast.iter_functions().any(|f| f.name == name && f.params.len() == 2)
+		};
+		let has_filter = defines("filter");
+		let has_score = defines("score");
+		if !has_filter && !has_score
+		{
+			warn!("Script defines neither filter(word, fragments) nor \
+				score(word, fragments)");
+		}
+		Ok(Self { engine, ast, has_filter, has_score })
+	}
+
+	/// Compile a script filter from the contents of the given file.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The script file.
+	///
+	/// # Returns
+	///
+	/// The compiled script filter.
+	///
+	/// # Errors
+	///
+	/// * If the file cannot be opened or read, an error is returned.
+	/// * If the script fails to compile, an [`ErrorKind::InvalidData`] is
+	///   returned.
+	pub fn from_file<T: AsRef<Path>>(path: T) -> Result<Self, io::Error>
+	{
+		let source = fs::read_to_string(path.as_ref())?;
+		let filter = Self::from_source(&source)?;
+		trace!("Compiled script: {}", path.as_ref().display());
+		Ok(filter)
+	}
+}
+
+impl WordFilter for ScriptFilter
+{
+	fn accept(&self, word: &str, path: &FragmentPath) -> bool
+	{
+		if !self.has_filter
+		{
+			return true
+		}
+		self.engine
+			.call_fn::<bool>(
+				&mut Scope::new(),
+				&self.ast,
+				"filter",
+				(word.to_string(), path.len() as i64)
+			)
+			.unwrap_or_else(|e| {
+				// A script that exceeds its limits would have hung the
+				// application, so reject the word. But a merely broken filter
+				// should not silently discard the solution, so accept the word.
+				let is_exceeded = exceeds_limits(&e);
+				warn!("Script filter failed: {}: {}", word, e);
+				!is_exceeded
+			})
+	}
+
+	fn score(&self, word: &str, path: &FragmentPath) -> Option<u32>
+	{
+		if !self.has_score
+		{
+			return None
+		}
+		match self.engine.call_fn::<i64>(
+			&mut Scope::new(),
+			&self.ast,
+			"score",
+			(word.to_string(), path.len() as i64)
+		)
+		{
+			Ok(score) => Some(score.clamp(0, u32::MAX as i64) as u32),
+			Err(e) =>
+			{
+				warn!("Script scorer failed: {}: {}", word, e);
+				None
+			}
+		}
+	}
+}
+
+/// Check whether the specified script error reports that the script exceeded
+/// the limits of the engine.
+///
+/// # Arguments
+///
+/// * `error` - The error.
+///
+/// # Returns
+///
+/// `true` if the script exceeded its limits, `false` otherwise.
+fn exceeds_limits(error: &EvalAltResult) -> bool
+{
+	match error
+	{
+		EvalAltResult::ErrorTooManyOperations(..)
+		| EvalAltResult::ErrorStackOverflow(..)
+		| EvalAltResult::ErrorDataTooLarge(..) => true,
+		// A limit violation within a function surfaces as the cause of an
+		// error in the function call.
+		EvalAltResult::ErrorInFunctionCall(_, _, cause, _) =>
+		{
+			exceeds_limits(cause)
+		},
+		_ => false
+	}
+}
+
+impl Debug for ScriptFilter
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		f.debug_struct("ScriptFilter")
+			.field("has_filter", &self.has_filter)
+			.field("has_score", &self.has_score)
+			.finish()
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use crate::{
+		script::ScriptFilter,
+		solver::{FragmentPath, WordFilter}
+	};
+
+	/// Ensure that the script functions are honored, and that missing
+	/// functions fall back to the default behavior.
+	#[test]
+	fn test_script_filter()
+	{
+		let path = FragmentPath::default();
+		let filter = ScriptFilter::from_source(
+			"fn filter(word, fragments) { !word.contains(\"z\") }"
+		).unwrap();
+		assert!(filter.accept("truth", &path));
+		assert!(!filter.accept("razz", &path));
+		assert_eq!(filter.score("truth", &path), None);
+
+		let filter = ScriptFilter::from_source(
+			"fn score(word, fragments) { word.len() * 10 }"
+		).unwrap();
+		assert!(filter.accept("razz", &path));
+		assert_eq!(filter.score("truth", &path), Some(50));
+
+		assert!(ScriptFilter::from_source("fn filter(word, {").is_err());
+	}
+
+	/// Ensure that a script that loops forever, recurses forever, or builds a
+	/// huge string is aborted, and that its word is rejected.
+	#[test]
+	fn test_script_limits()
+	{
+		let path = FragmentPath::default();
+		let filter = ScriptFilter::from_source(
+			"fn filter(word, fragments) { loop {} }\n\
+			fn score(word, fragments) { loop {} }"
+		).unwrap();
+		assert!(!filter.accept("truth", &path));
+		assert_eq!(filter.score("truth", &path), None);
+
+		let filter = ScriptFilter::from_source(
+			"fn deeper(n) { deeper(n + 1) }\n\
+			fn filter(word, fragments) { deeper(0) }"
+		).unwrap();
+		assert!(!filter.accept("truth", &path));
+
+		let filter = ScriptFilter::from_source(
+			"fn filter(word, fragments) { \
+				let s = word; loop { s += s; } }"
+		).unwrap();
+		assert!(!filter.accept("truth", &path));
+
+		// An ordinary runtime error still accepts the word.
+		let filter = ScriptFilter::from_source(
+			"fn filter(word, fragments) { throw \"broken\" }"
+		).unwrap();
+		assert!(filter.accept("truth", &path));
+	}
+}
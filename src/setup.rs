@@ -0,0 +1,1049 @@
+//! # Setup wizard
+//!
+//! Herein is the wizard that runs on first launch, when there is no dictionary
+//! to open: it offers to select or download a word list, builds the binary
+//! dictionary from it while showing the progress, and writes the initial
+//! configuration file, so that a new user is guided to a working setup rather
+//! than stopped by a missing file.
+
+use std::{
+	fs,
+	io::{self, ErrorKind},
+	path::{Path, PathBuf},
+	time::Duration
+};
+#[cfg(feature = "net")]
+use std::{
+	thread::{self, JoinHandle},
+	time::Instant
+};
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use quartiles_solver::dictionary::{Composition, Dictionary, Language};
+use ratatui::{
+	backend::Backend,
+	buffer::Buffer,
+	layout::{Constraint, Layout, Rect},
+	text::{Line, Span},
+	widgets::{
+		Block, BorderType, Borders, Gauge, List, ListState, Padding, Paragraph,
+		StatefulWidget, Widget, Wrap
+	}
+};
+
+use crate::{
+	config::Config,
+	driver::{EventSource, ThreadedEvents},
+	loader::Loader,
+	locale::{Lang, Messages},
+	theme::Theme,
+	tui::Tui
+};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Sources.                                  //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The sources of a word list that the wizard offers, plus the way out.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Source
+{
+	/// A word list on this computer, which is copied into the dictionary
+	/// directory.
+	File,
+
+	/// A word list downloaded from a URL.
+	#[cfg(feature = "net")]
+	Download,
+
+	/// No word list: leave the wizard without a dictionary.
+	Quit
+}
+
+impl Source
+{
+	/// The sources, in the order that the wizard lists them.
+	const ALL: &'static [Source] = &[
+		Source::File,
+		#[cfg(feature = "net")]
+		Source::Download,
+		Source::Quit
+	];
+
+	/// Answer the label of the source, as the wizard lists it.
+	///
+	/// # Arguments
+	///
+	/// * `messages` - The messages of the user interface.
+	///
+	/// # Returns
+	///
+	/// The label.
+	fn label(self, messages: &'static Messages) -> &'static str
+	{
+		match self
+		{
+			Source::File => messages.text("setup.file"),
+			#[cfg(feature = "net")]
+			Source::Download => messages.text("setup.download"),
+			Source::Quit => messages.text("setup.quit")
+		}
+	}
+
+	/// Answer the prompt for the location of the word list.
+	///
+	/// # Arguments
+	///
+	/// * `messages` - The messages of the user interface.
+	///
+	/// # Returns
+	///
+	/// The prompt.
+	fn prompt(self, messages: &'static Messages) -> &'static str
+	{
+		match self
+		{
+			Source::File => messages.text("setup.file-prompt"),
+			#[cfg(feature = "net")]
+			Source::Download => messages.text("setup.download-prompt"),
+			Source::Quit => unreachable!()
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Steps.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The steps of the wizard.
+#[derive(Debug)]
+enum Step
+{
+	/// The user is choosing a [source](Source) of the word list.
+	Choose
+	{
+		/// The index of the selected source.
+		selected: usize
+	},
+
+	/// The user is typing the location of the word list.
+	Locate
+	{
+		/// The source of the word list.
+		source: Source,
+
+		/// The location typed so far.
+		location: String
+	},
+
+	/// The dictionary is being built in the background.
+	Build
+	{
+		/// The background work.
+		job: Job
+	},
+
+	/// The dictionary is ready, and the configuration file is written.
+	Done
+	{
+		/// The number of words in the dictionary.
+		words: usize
+	}
+}
+
+/// The background work that builds the dictionary.
+#[derive(Debug)]
+enum Job
+{
+	/// The word list is being read, and the binary dictionary written, with
+	/// measurable progress.
+	Load(Loader),
+
+	/// The word list is being downloaded, which reports no progress.
+	#[cfg(feature = "net")]
+	Download
+	{
+		/// The URL of the word list.
+		url: String,
+
+		/// When the download started.
+		started: Instant,
+
+		/// The worker thread, which answers the dictionary.
+		worker: JoinHandle<io::Result<Dictionary>>
+	}
+}
+
+impl Job
+{
+	/// Check whether the background work has finished, such that
+	/// [`join`](Self::join) will not block.
+	///
+	/// # Returns
+	///
+	/// `true` if the work has finished, `false` otherwise.
+	fn is_finished(&self) -> bool
+	{
+		match self
+		{
+			Job::Load(loader) => loader.is_finished(),
+			#[cfg(feature = "net")]
+			Job::Download { worker, .. } => worker.is_finished()
+		}
+	}
+
+	/// Wait for the background work to finish, and answer the dictionary.
+	///
+	/// # Returns
+	///
+	/// The dictionary.
+	///
+	/// # Errors
+	///
+	/// Any error that occurs while building the dictionary.
+	fn join(self) -> io::Result<Dictionary>
+	{
+		match self
+		{
+			Job::Load(loader) => loader.join(),
+			#[cfg(feature = "net")]
+			Job::Download { worker, .. } => worker.join().unwrap_or_else(|_| {
+				Err(io::Error::other("Dictionary download panicked"))
+			})
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Wizard.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The dictionary that the wizard set up.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[must_use]
+pub struct Setup
+{
+	/// The directory containing the dictionary files.
+	pub directory: PathBuf,
+
+	/// The name of the dictionary.
+	pub name: String
+}
+
+/// The first-run setup wizard, which builds the missing dictionary from a word
+/// list of the user's choosing, and then writes the configuration file.
+#[derive(Debug)]
+#[must_use]
+pub struct Wizard
+{
+	/// The directory to write the dictionary files to.
+	directory: PathBuf,
+
+	/// The name of the dictionary.
+	name: String,
+
+	/// The language of the dictionary, which normalizes downloaded words.
+	language: Language,
+
+	/// The configuration, which is written once the dictionary is ready.
+	config: Config,
+
+	/// The path to the configuration file.
+	config_path: PathBuf,
+
+	/// The color scheme.
+	theme: Theme,
+
+	/// The messages of the user interface, in the selected language.
+	messages: &'static Messages,
+
+	/// The current step.
+	step: Step,
+
+	/// The most recent error, which is shown until the user acts again.
+	error: Option<String>,
+
+	/// Whether the wizard is running.
+	is_running: bool,
+
+	/// The dictionary that was set up, once the wizard finishes.
+	setup: Option<Setup>
+}
+
+/// How long to wait between frames while the dictionary is being built, so
+/// that the progress is animated.
+const FRAME_INTERVAL: Duration = Duration::from_millis(33);
+
+impl Wizard
+{
+	/// Construct a wizard that sets up the specified dictionary.
+	///
+	/// # Arguments
+	///
+	/// * `directory` - The directory to write the dictionary files to, which
+	///   is created if necessary.
+	/// * `name` - The name of the dictionary.
+	/// * `language` - The language of the dictionary.
+	/// * `config` - The configuration, to which the dictionary settings are
+	///   added before it is written.
+	/// * `config_path` - The path to the configuration file.
+	/// * `theme` - The color scheme.
+	/// * `lang` - The language of the user interface.
+	///
+	/// # Returns
+	///
+	/// The wizard.
+	pub fn new(
+		directory: PathBuf,
+		name: String,
+		language: Language,
+		config: Config,
+		config_path: PathBuf,
+		theme: Theme,
+		lang: Lang
+	) -> Self
+	{
+		Self {
+			directory,
+			name,
+			language,
+			config,
+			config_path,
+			theme,
+			messages: Messages::of(lang),
+			step: Step::Choose { selected: 0 },
+			error: None,
+			is_running: true,
+			setup: None
+		}
+	}
+
+	/// Run the wizard in the specified TUI until the user finishes or quits.
+	///
+	/// # Arguments
+	///
+	/// * `tui` - The text-based user interface (TUI).
+	///
+	/// # Returns
+	///
+	/// The dictionary that was set up, or `None` if the user quit.
+	///
+	/// # Errors
+	///
+	/// Any error that occurs while driving the TUI.
+	pub fn run<B: Backend>(self, tui: &mut Tui<B>) -> io::Result<Option<Setup>>
+	{
+		self.run_with(tui, &mut ThreadedEvents::spawn()?)
+	}
+
+	/// Run the wizard against the specified source of events. Same as
+	/// [`run`](Self::run) otherwise.
+	///
+	/// # Arguments
+	///
+	/// * `tui` - The text-based user interface (TUI).
+	/// * `events` - The source of events.
+	///
+	/// # Returns
+	///
+	/// The dictionary that was set up, or `None` if the user quit.
+	///
+	/// # Errors
+	///
+	/// Any error that occurs while driving the TUI.
+	pub fn run_with<B: Backend>(
+		mut self,
+		tui: &mut Tui<B>,
+		events: &mut dyn EventSource
+	) -> io::Result<Option<Setup>>
+	{
+		while self.is_running
+		{
+			self.poll();
+			tui.draw(|frame| self.render(frame.area(), frame.buffer_mut()))?;
+			let timeout = match self.step
+			{
+				Step::Build { .. } => FRAME_INTERVAL,
+				_ => Duration::MAX
+			};
+			if let Some(event) = events.next_event(timeout)?
+			{
+				self.process_event(event);
+			}
+		}
+		Ok(self.setup)
+	}
+
+	/// Finish building the dictionary, if the background work has finished.
+	/// On success, write the configuration file. On failure, return to the
+	/// choice of source, reporting the error.
+	fn poll(&mut self)
+	{
+		let Step::Build { ref job } = self.step else { return };
+		if !job.is_finished()
+		{
+			return
+		}
+		let Step::Build { job } =
+			std::mem::replace(&mut self.step, Step::Choose { selected: 0 })
+		else
+		{
+			unreachable!()
+		};
+		let result = job.join().and_then(|dictionary| {
+			if dictionary.is_empty()
+			{
+				return Err(io::Error::new(
+					ErrorKind::InvalidData,
+					self.messages.text("setup.empty")
+				))
+			}
+			self.write_config()?;
+			Ok(dictionary.len())
+		});
+		match result
+		{
+			Ok(words) =>
+			{
+				self.error = None;
+				self.step = Step::Done { words };
+			},
+			Err(e) => self.error = Some(e.to_string())
+		}
+	}
+
+	/// Record the dictionary in the configuration, and write the
+	/// configuration file.
+	///
+	/// # Errors
+	///
+	/// If the configuration file cannot be written, an error is returned.
+	fn write_config(&mut self) -> io::Result<()>
+	{
+		self.config.directory = self.directory.clone();
+		self.config.language = self.language;
+		self.config.dictionary = (self.name != self.language.name())
+			.then(|| self.name.clone());
+		self.config.save(&self.config_path)
+			.map_err(|e| io::Error::new(
+				e.kind(),
+				self.messages.text_with(
+					"setup.write-failed",
+					&[("path", &self.config_path.display()), ("error", &e)]
+				)
+			))
+	}
+
+	/// Process a single terminal event.
+	///
+	/// # Arguments
+	///
+	/// * `event` - The event.
+	fn process_event(&mut self, event: Event)
+	{
+		match event
+		{
+			Event::Key(key) if key.kind == KeyEventKind::Press =>
+			{
+				self.process_key(key)
+			},
+			Event::Paste(text) =>
+			{
+				if let Step::Locate { ref mut location, .. } = self.step
+				{
+					location.push_str(text.trim());
+				}
+			},
+			_ => {}
+		}
+	}
+
+	/// Process a key press. The arrow keys choose a source, the keyboard types
+	/// its location, `Enter` confirms, and `Esc` goes back, or quits from the
+	/// choice of source. `Ctrl+C` always quits.
+	///
+	/// # Arguments
+	///
+	/// * `key` - The key press.
+	fn process_key(&mut self, key: KeyEvent)
+	{
+		if key.modifiers.contains(KeyModifiers::CONTROL)
+			&& key.code == KeyCode::Char('c')
+		{
+			self.cancel();
+			self.is_running = false;
+			return
+		}
+		match (&mut self.step, key.code)
+		{
+			(Step::Choose { selected }, KeyCode::Up) =>
+			{
+				*selected = selected.saturating_sub(1);
+			},
+			(Step::Choose { selected }, KeyCode::Down) =>
+			{
+				*selected = (*selected + 1).min(Source::ALL.len() - 1);
+			},
+			(Step::Choose { selected }, KeyCode::Enter) =>
+			{
+				match Source::ALL[*selected]
+				{
+					Source::Quit => self.is_running = false,
+					source =>
+					{
+						self.error = None;
+						self.step = Step::Locate {
+							source,
+							location: String::new()
+						};
+					}
+				}
+			},
+			(Step::Choose { .. }, KeyCode::Esc) => self.is_running = false,
+			(Step::Locate { location, .. }, KeyCode::Char(c)) =>
+			{
+				location.push(c);
+			},
+			(Step::Locate { location, .. }, KeyCode::Backspace) =>
+			{
+				location.pop();
+			},
+			(Step::Locate { source, location }, KeyCode::Enter) =>
+			{
+				let (source, location) = (*source, location.trim().to_string());
+				self.start(source, location);
+			},
+			(Step::Locate { source, .. }, KeyCode::Esc) =>
+			{
+				let selected = Source::ALL.iter()
+					.position(|s| s == source)
+					.unwrap_or_default();
+				self.error = None;
+				self.step = Step::Choose { selected };
+			},
+			(Step::Build { .. }, KeyCode::Esc) =>
+			{
+				self.cancel();
+				self.error =
+					Some(self.messages.text("setup.cancelled").to_string());
+				self.step = Step::Choose { selected: 0 };
+			},
+			(Step::Done { .. }, KeyCode::Enter | KeyCode::Esc) =>
+			{
+				self.setup = Some(Setup {
+					directory: self.directory.clone(),
+					name: self.name.clone()
+				});
+				self.is_running = false;
+			},
+			_ => {}
+		}
+	}
+
+	/// Start building the dictionary from the word list at the specified
+	/// location. If it cannot be started, the error is reported, and the user
+	/// may correct the location.
+	///
+	/// # Arguments
+	///
+	/// * `source` - The source of the word list.
+	/// * `location` - The location of the word list: a path or a URL.
+	fn start(&mut self, source: Source, location: String)
+	{
+		if location.is_empty()
+		{
+			return
+		}
+		let result = fs::create_dir_all(&self.directory)
+			.map_err(|e| io::Error::new(
+				e.kind(),
+				self.messages.text_with(
+					"setup.create-failed",
+					&[("path", &self.directory.display()), ("error", &e)]
+				)
+			))
+			.and_then(|_| match source
+			{
+				Source::File => self.copy(Path::new(&location)).map(|_| {
+					Job::Load(Loader::spawn(
+						self.directory.clone(),
+						self.name.clone(),
+						Composition::default()
+					))
+				}),
+				#[cfg(feature = "net")]
+				Source::Download =>
+				{
+					let (directory, name, language) = (
+						self.directory.clone(),
+						self.name.clone(),
+						self.language
+					);
+					let url = location.clone();
+					Ok(Job::Download {
+						url: location,
+						started: Instant::now(),
+						worker: thread::spawn(move || {
+							Dictionary::fetch(&url, directory, &name, language)
+						})
+					})
+				},
+				Source::Quit => unreachable!()
+			});
+		match result
+		{
+			Ok(job) =>
+			{
+				self.error = None;
+				self.step = Step::Build { job };
+			},
+			Err(e) => self.error = Some(e.to_string())
+		}
+	}
+
+	/// Copy the specified word list into the dictionary directory, as the text
+	/// dictionary, unless it is already there.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The path to the word list.
+	///
+	/// # Errors
+	///
+	/// If the word list cannot be copied, an error is returned.
+	fn copy(&self, path: &Path) -> io::Result<()>
+	{
+		let txt_path = self.directory.join(format!("{}.txt", self.name));
+		let is_same = fs::canonicalize(path).ok()
+			== fs::canonicalize(&txt_path).ok();
+		if path.is_file() && is_same
+		{
+			return Ok(())
+		}
+		fs::copy(path, &txt_path)
+			.map(|_| ())
+			.map_err(|e| io::Error::new(
+				e.kind(),
+				self.messages.text_with(
+					"setup.copy-failed",
+					&[("path", &path.display()), ("error", &e)]
+				)
+			))
+	}
+
+	/// Abandon any dictionary that is being built. A download cannot be
+	/// interrupted, so it finishes in the background, unobserved.
+	fn cancel(&mut self)
+	{
+		if let Step::Build { job: Job::Load(ref loader) } = self.step
+		{
+			loader.cancel();
+		}
+	}
+
+	/// Render the wizard as a popup, centered in the specified area.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	fn render(&self, area: Rect, buf: &mut Buffer)
+	{
+		let width = 64.min(area.width);
+		let height = 12.min(area.height);
+		let popup = Rect {
+			x: area.x + (area.width - width) / 2,
+			y: area.y + (area.height - height) / 2,
+			width,
+			height
+		};
+		let hints = match self.step
+		{
+			Step::Choose { .. } => self.messages.text("setup.choose-hints"),
+			Step::Locate { .. } => self.messages.text("setup.locate-hints"),
+			Step::Build { .. } => self.messages.text("setup.build-hints"),
+			Step::Done { .. } => self.messages.text("setup.done-hints")
+		};
+		let block = Block::default()
+			.borders(Borders::ALL)
+			.border_type(BorderType::Rounded)
+			.border_style(self.theme.border)
+			.title_top(
+				Line::from(self.messages.text("setup.title")).centered()
+			)
+			.title_bottom(
+				Line::from(Span::styled(hints, self.theme.hint)).centered()
+			)
+			.padding(Padding::horizontal(1));
+		let inner = block.inner(popup);
+		block.render(popup, buf);
+		let [body, error] = Layout::vertical([
+			Constraint::Fill(1),
+			Constraint::Length(2)
+		])
+		.areas(inner);
+		match self.step
+		{
+			Step::Choose { selected } =>
+			{
+				self.render_choose(body, buf, selected)
+			},
+			Step::Locate { source, ref location } =>
+			{
+				self.render_locate(body, buf, source, location)
+			},
+			Step::Build { ref job } => self.render_build(body, buf, job),
+			Step::Done { words } => self.render_done(body, buf, words)
+		}
+		if let Some(ref message) = self.error
+		{
+			Paragraph::new(message.as_str())
+				.style(self.theme.failure)
+				.wrap(Wrap { trim: true })
+				.render(error, buf);
+		}
+	}
+
+	/// Render the choice of [source](Source).
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `selected` - The index of the selected source.
+	fn render_choose(&self, area: Rect, buf: &mut Buffer, selected: usize)
+	{
+		let [intro, list] = Layout::vertical([
+			Constraint::Length(4),
+			Constraint::Fill(1)
+		])
+		.areas(area);
+		let path = self.directory.join(format!("{}.dict", self.name));
+		let text = self.messages.text_with(
+			"setup.missing",
+			&[
+				("path", &path.display()),
+				("config", &self.config_path.display())
+			]
+		);
+		Paragraph::new(text)
+			.style(self.theme.text)
+			.wrap(Wrap { trim: true })
+			.render(intro, buf);
+		let items = List::new(
+			Source::ALL.iter().map(|source| source.label(self.messages))
+		)
+			.style(self.theme.text)
+			.highlight_style(self.theme.selection)
+			.highlight_symbol("› ");
+		let mut state = ListState::default().with_selected(Some(selected));
+		StatefulWidget::render(&items, list, buf, &mut state);
+	}
+
+	/// Render the prompt for the location of the word list.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `source` - The source of the word list.
+	/// * `location` - The location typed so far.
+	fn render_locate(
+		&self,
+		area: Rect,
+		buf: &mut Buffer,
+		source: Source,
+		location: &str
+	)
+	{
+		let [prompt, input] = Layout::vertical([
+			Constraint::Length(2),
+			Constraint::Fill(1)
+		])
+		.areas(area);
+		Paragraph::new(source.prompt(self.messages))
+			.style(self.theme.text)
+			.render(prompt, buf);
+		Paragraph::new(Line::from(vec![
+			Span::styled(location, self.theme.text),
+			Span::styled(" ", self.theme.cursor)
+		]))
+		.wrap(Wrap { trim: false })
+		.render(input, buf);
+	}
+
+	/// Render the progress of building the dictionary.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `job` - The background work.
+	fn render_build(&self, area: Rect, buf: &mut Buffer, job: &Job)
+	{
+		let [title, path, gauge, words] = Layout::vertical([
+			Constraint::Length(1),
+			Constraint::Length(1),
+			Constraint::Length(1),
+			Constraint::Length(1)
+		])
+		.areas(area);
+		match job
+		{
+			Job::Load(loader) =>
+			{
+				let progress = loader.progress();
+				let (read, total) =
+					(progress.bytes_read(), progress.total_bytes());
+				let ratio = match total
+				{
+					0 => 0.0,
+					total => (read as f64 / total as f64).min(1.0)
+				};
+				let mib = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+				let building = format!(
+					"{} {}",
+					loader.spinner(),
+					self.messages.text_with(
+						"setup.building",
+						&[("name", &loader.name())]
+					)
+				);
+				Paragraph::new(building)
+					.style(self.theme.text)
+					.render(title, buf);
+				Paragraph::new(loader.directory().display().to_string())
+					.style(self.theme.hint)
+					.render(path, buf);
+				Gauge::default()
+					.gauge_style(self.theme.success)
+					.ratio(ratio)
+					.label(format!("{:.1} / {:.1} MiB", mib(read), mib(total)))
+					.render(gauge, buf);
+				let inserted = self.messages.text_with(
+					"splash.inserted",
+					&[("count", &progress.words_inserted())]
+				);
+				Paragraph::new(inserted)
+					.style(self.theme.text)
+					.render(words, buf);
+			},
+			#[cfg(feature = "net")]
+			Job::Download { url, started, .. } =>
+			{
+				let downloading = format!(
+					"{} {}",
+					crate::loader::spinner(*started),
+					self.messages.text("setup.downloading")
+				);
+				Paragraph::new(downloading)
+					.style(self.theme.text)
+					.render(title, buf);
+				Paragraph::new(url.as_str())
+					.style(self.theme.hint)
+					.render(path, buf);
+			}
+		}
+	}
+
+	/// Render the confirmation that the dictionary is ready.
+	///
+	/// # Arguments
+	///
+	/// * `area` - The target area.
+	/// * `buf` - The target buffer.
+	/// * `words` - The number of words in the dictionary.
+	fn render_done(&self, area: Rect, buf: &mut Buffer, words: usize)
+	{
+		let path = self.directory.join(format!("{}.dict", self.name));
+		let text = self.messages.text_with(
+			"setup.ready",
+			&[
+				("count", &words),
+				("path", &path.display()),
+				("config", &self.config_path.display())
+			]
+		);
+		Paragraph::new(text)
+			.style(self.theme.success)
+			.wrap(Wrap { trim: true })
+			.render(area, buf);
+	}
+}
+
+/// Check whether the specified dictionary is missing, i.e., whether neither
+/// its binary nor its text file exists.
+///
+/// # Arguments
+///
+/// * `directory` - The directory containing the dictionary files.
+/// * `name` - The name of the dictionary.
+///
+/// # Returns
+///
+/// `true` if the dictionary is missing, `false` otherwise.
+#[must_use]
+pub fn is_missing(directory: &Path, name: &str) -> bool
+{
+	["dict", "txt"].iter().all(|extension| {
+		!directory.join(format!("{}.{}", name, extension)).exists()
+	})
+}
+
+/// Find the first directory of the search path that holds the specified
+/// dictionary, e.g., so that per-user dictionaries can shadow a system-wide
+/// dictionary of the same name.
+///
+/// # Arguments
+///
+/// * `search_path` - The directories to search, in order. Must not be empty.
+/// * `name` - The name of the dictionary.
+///
+/// # Returns
+///
+/// The first directory that holds the dictionary, or the first directory of
+/// the search path if none does, since that is where a new dictionary
+/// belongs.
+#[must_use]
+pub fn locate(search_path: &[PathBuf], name: &str) -> PathBuf
+{
+	search_path.iter()
+		.find(|directory| !is_missing(directory, name))
+		.unwrap_or(&search_path[0])
+		.clone()
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::{fs, path::Path, thread, time::Duration};
+
+	use crossterm::event::{Event, KeyCode};
+	use quartiles_solver::dictionary::Language;
+	use ratatui::{backend::TestBackend, buffer::Buffer, layout::Rect, Terminal};
+
+	use crate::{
+		config::Config,
+		driver::{EventSource, ScriptedEvents},
+		locale::Lang,
+		setup::{is_missing, locate, Setup, Step, Wizard},
+		theme::{Theme, ThemeName}
+	};
+
+	/// Construct a wizard that sets up the `tiny` dictionary in the specified
+	/// directory, recording it in `quartiles.toml` beside the directory.
+	fn wizard(dir: &Path) -> Wizard
+	{
+		Wizard::new(
+			dir.join("dict"),
+			"tiny".into(),
+			Language::English,
+			Config::default(),
+			dir.join("quartiles.toml"),
+			Theme::new(ThemeName::Dark),
+			Lang::En
+		)
+	}
+
+	/// Render the wizard, and answer the text on screen.
+	fn screen(wizard: &Wizard) -> String
+	{
+		let area = Rect::new(0, 0, 80, 16);
+		let mut buf = Buffer::empty(area);
+		wizard.render(area, &mut buf);
+		buf.content().iter().map(|cell| cell.symbol()).collect()
+	}
+
+	/// Ensure that selecting a word list builds the dictionary, writes the
+	/// configuration file, and sets up the dictionary.
+	#[test]
+	fn test_select()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		let list = dir.path().join("words.txt");
+		fs::write(&list, "Cross\nword\n\npuzzle\n").unwrap();
+		let mut wizard = wizard(dir.path());
+		assert!(is_missing(&dir.path().join("dict"), "tiny"));
+		assert!(screen(&wizard).contains("No dictionary was found"));
+
+		wizard.process_event(Event::Key(KeyCode::Enter.into()));
+		assert!(matches!(wizard.step, Step::Locate { .. }));
+		wizard.process_event(Event::Paste(list.display().to_string()));
+		assert!(screen(&wizard).contains("words.txt"));
+		wizard.process_event(Event::Key(KeyCode::Enter.into()));
+		while matches!(wizard.step, Step::Build { .. })
+		{
+			wizard.poll();
+			thread::yield_now();
+		}
+		assert!(matches!(wizard.step, Step::Done { words: 3 }), "{:?}",
+			wizard.error);
+		assert!(screen(&wizard).contains("The dictionary is ready"));
+		wizard.process_event(Event::Key(KeyCode::Enter.into()));
+		assert!(!wizard.is_running);
+		assert_eq!(
+			wizard.setup,
+			Some(Setup {
+				directory: dir.path().join("dict"),
+				name: "tiny".into()
+			})
+		);
+
+		assert!(!is_missing(&dir.path().join("dict"), "tiny"));
+		assert!(dir.path().join("dict/tiny.dict").exists());
+		let config = Config::load(dir.path().join("quartiles.toml")).unwrap();
+		assert_eq!(config.directory, dir.path().join("dict"));
+		assert_eq!(config.dictionary.as_deref(), Some("tiny"));
+	}
+
+	/// Ensure that the search path is searched in order, and that a missing
+	/// dictionary belongs in the first directory.
+	#[test]
+	fn test_locate()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		let user = dir.path().join("user");
+		let system = dir.path().join("system");
+		fs::create_dir_all(&user).unwrap();
+		fs::create_dir_all(&system).unwrap();
+		fs::write(system.join("tiny.txt"), "word\n").unwrap();
+		let search_path = [user.clone(), system.clone()];
+		assert_eq!(locate(&search_path, "tiny"), system);
+		assert_eq!(locate(&search_path, "other"), user);
+
+		// A per-user dictionary shadows a system-wide one.
+		fs::write(user.join("tiny.dict"), []).unwrap();
+		assert_eq!(locate(&search_path, "tiny"), user);
+		assert_eq!(locate(&search_path[1..], "tiny"), system);
+	}
+
+	/// Ensure that a missing word list is reported, and that the user may
+	/// go back and quit, setting up nothing.
+	#[test]
+	fn test_missing_and_quit()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		let mut wizard = wizard(dir.path());
+		let mut events = ScriptedEvents::default();
+		events.press(KeyCode::Enter);
+		events.type_text("no-such-file.txt");
+		events.press(KeyCode::Enter);
+		while let Some(event) = events.next_event(Duration::ZERO).unwrap()
+		{
+			wizard.process_event(event);
+		}
+		assert!(matches!(wizard.step, Step::Locate { .. }));
+		assert!(screen(&wizard).contains("Failed to copy word list"));
+
+		let mut events = ScriptedEvents::default();
+		events.press(KeyCode::Esc);
+		events.press(KeyCode::Esc);
+		let mut tui = Terminal::new(TestBackend::new(80, 16)).unwrap();
+		assert_eq!(wizard.run_with(&mut tui, &mut events).unwrap(), None);
+		assert!(!dir.path().join("quartiles.toml").exists());
+	}
+}
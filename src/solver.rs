@@ -3,23 +3,64 @@
 //! Herein is the solver for the Quartiles game.
 
 use std::{
-	collections::HashSet,
+	cell::OnceCell,
+	collections::{BTreeSet, HashSet},
 	error::Error,
-	fmt::{self, Display, Formatter},
-	ops::{Index, IndexMut},
+	fmt::{self, Debug, Display, Formatter},
+	iter::FusedIterator,
+	ops::Index,
 	rc::Rc,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc
+	},
 	time::{Duration, Instant}
 };
 
-use fixedstr::{str32, str8};
-use log::{debug, trace};
+use fixedstr::zstr;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, debug_span, trace};
 
-use crate::dictionary::Dictionary;
+use crate::dictionary::{normalize_word, Dictionary, DictionaryCursor};
+#[cfg(feature = "trace-ui")]
+use crate::trace::{Decision, Rejection, Trace};
 
 ////////////////////////////////////////////////////////////////////////////////
 //                                  Solver.                                   //
 ////////////////////////////////////////////////////////////////////////////////
 
+/// The capacity of a [fragment](Fragment), in bytes, including its
+/// terminator. Raise this to support variants of the game with longer
+/// fragments, along with [`WORD_CAPACITY`].
+pub const FRAGMENT_CAPACITY: usize = 16;
+
+/// The maximum length of a fragment, in bytes. Accented letters occupy more
+/// than one byte apiece, so a fragment may hold fewer letters than this.
+pub const MAX_FRAGMENT_BYTES: usize = FRAGMENT_CAPACITY - 1;
+
+/// A fragment of a puzzle. Fragments are stored inline, so that boards are
+/// cheap to copy. Use [`parse_fragment`] to construct a fragment from
+/// untrusted text, since conversion from a longer string silently truncates.
+pub type Fragment = zstr<FRAGMENT_CAPACITY>;
+
+/// The capacity of a [candidate word](Word), in bytes, including its
+/// terminator. Raise this to support dictionaries of longer words, or larger
+/// fragments, at the cost of more memory per word of the solution.
+pub const WORD_CAPACITY: usize = 64;
+
+/// The maximum length of a candidate word, in bytes. The solver cannot find
+/// any longer word.
+pub const MAX_WORD_BYTES: usize = WORD_CAPACITY - 1;
+
+/// A candidate word, i.e., the concatenation of the fragments along some
+/// [fragment path](FragmentPath). Words are stored inline, so that the
+/// search never allocates.
+pub type Word = zstr<WORD_CAPACITY>;
+
+// Every candidate word must fit, so that no word is ever silently truncated:
+// a quartile comprises 4 fragments.
+const _: () = assert!(4 * MAX_FRAGMENT_BYTES <= MAX_WORD_BYTES);
+
 /// The complete context of the Quartiles solver. This permits an iterative
 /// solution to the puzzle, rather than a recursive one. An iterative solution
 /// can be time-sliced and parallelized.
@@ -30,8 +71,18 @@ pub struct Solver
 	/// The dictionary to use for solving the puzzle.
 	dictionary: Rc<Dictionary>,
 
+	/// The [fingerprint](Dictionary::fingerprint) of the dictionary, computed
+	/// on demand for [checkpoints](Self::checkpoint), since computing it
+	/// visits every word.
+	fingerprint: OnceCell<u64>,
+
+	/// The fragments that end some word of the dictionary, as a bit mask in
+	/// which bit `i` is set if fragment index `i` does, computed on demand
+	/// for [suffix pruning](SolverConfig::prune_suffixes).
+	enders: OnceCell<u32>,
+
 	/// The fragments of the puzzle.
-	fragments: [str8; 20],
+	fragments: [Fragment; 20],
 
 	/// The current fragment path.
 	path: FragmentPath,
@@ -40,12 +91,38 @@ pub struct Solver
 	solution: Vec<FragmentPath>,
 
 	/// Whether the solver is finished.
-	is_finished: bool
+	is_finished: bool,
+
+	/// The optional filter that decides whether a valid word may enter the
+	/// solution, and how many points it is worth.
+	filter: Option<Rc<dyn WordFilter>>,
+
+	/// The options that constrain the search.
+	config: SolverConfig,
+
+	/// The token by which another party may abort the search, if any.
+	cancellation: Option<CancellationToken>,
+
+	/// The worst amount by which a call to [`solve`](Self::solve) has
+	/// overrun its time quantum.
+	max_overrun: Duration,
+
+	/// The counters of the work performed by the solver.
+	metrics: SolverMetrics,
+
+	/// The record of the solver's decisions, if tracing is enabled.
+	#[cfg(feature = "trace-ui")]
+	trace: Option<Trace>
 }
 
 impl Solver
 {
-	/// Construct a new solver for the given dictionary.
+	/// Construct a new solver for the given dictionary. To configure the
+	/// solver at the same time, use the [builder](Self::builder) instead. If
+	/// the dictionary is [folding](Dictionary::set_folding), then the
+	/// fragments are [normalized](normalize_word) once, here, since the search
+	/// walks the dictionary by [cursor](DictionaryCursor), which compares
+	/// fragments verbatim.
 	///
 	/// # Arguments
 	///
@@ -55,18 +132,297 @@ impl Solver
 	/// # Returns
 	///
 	/// A new solver for the given dictionary.
-	pub fn new(dictionary: Rc<Dictionary>, fragments: [str8; 20]) -> Self
+	pub fn new(dictionary: Rc<Dictionary>, fragments: [Fragment; 20]) -> Self
 	{
+		// A fragment that normalizes to something too long for a fragment is
+		// kept verbatim rather than truncated.
+		let fragments = match dictionary.is_folding()
+		{
+			true => fragments.map(|fragment| {
+				parse_fragment(&normalize_word(&fragment)).unwrap_or(fragment)
+			}),
+			false => fragments
+		};
 		Self
 		{
 			dictionary,
+			fingerprint: OnceCell::new(),
+			enders: OnceCell::new(),
 			fragments,
 			path: Default::default(),
-			solution: Vec::new(),
-			is_finished: false
+			// An official puzzle typically has a few dozen words, so reserve
+			// enough space to avoid reallocating the solution while solving.
+			solution: Vec::with_capacity(64),
+			is_finished: false,
+			filter: None,
+			config: SolverConfig::default(),
+			cancellation: None,
+			max_overrun: Duration::ZERO,
+			metrics: SolverMetrics::default(),
+			#[cfg(feature = "trace-ui")]
+			trace: None
+		}
+	}
+
+	/// Start building a solver. At least the dictionary and the fragments
+	/// must be supplied before the solver can be [built](SolverBuilder::build).
+	///
+	/// # Returns
+	///
+	/// A new [solver builder](SolverBuilder).
+	#[inline]
+	pub fn builder() -> SolverBuilder
+	{
+		SolverBuilder::default()
+	}
+
+	/// Resume a solver from the given [checkpoint](Checkpoint). The word filter,
+	/// if any, must be reinstalled with [`with_filter`](Self::with_filter). The
+	/// [configuration](SolverConfig) is restored from the checkpoint, so that
+	/// the whole solution obeys the same rules. Any configuration installed
+	/// afterward must agree with [`Checkpoint::config`], except for the
+	/// [time quantum](SolverConfig::time_quantum), which never changes the
+	/// solution.
+	///
+	/// # Arguments
+	///
+	/// * `dictionary` - The dictionary to use for solving the puzzle. This
+	///   must be the same dictionary that was used to produce the
+	///   checkpoint.
+	/// * `checkpoint` - The checkpoint.
+	///
+	/// # Returns
+	///
+	/// A solver that continues from the checkpoint.
+	///
+	/// # Errors
+	///
+	/// * [`CheckpointError::Invalid`] if the checkpoint is not
+	///   [internally consistent](Checkpoint::is_valid), e.g., because it was
+	///   read from a corrupt file.
+	/// * [`CheckpointError::DictionaryMismatch`] if the checkpoint was
+	///   captured against a different dictionary, whose solution it would
+	///   corrupt.
+	pub fn from_checkpoint(
+		dictionary: Rc<Dictionary>,
+		checkpoint: Checkpoint
+	) -> Result<Self, CheckpointError>
+	{
+		if !checkpoint.is_valid()
+		{
+			return Err(CheckpointError::Invalid)
+		}
+		let fingerprint = dictionary.fingerprint();
+		if checkpoint.dictionary != fingerprint
+		{
+			return Err(CheckpointError::DictionaryMismatch)
+		}
+		let fragments = checkpoint.fragments();
+		let mut solver = Self::new(dictionary, fragments);
+		let _ = solver.fingerprint.set(fingerprint);
+		solver.config = checkpoint.config;
+		solver.path = checkpoint.path;
+		solver.solution = checkpoint.solution;
+		solver.is_finished = checkpoint.is_finished;
+		Ok(solver)
+	}
+
+	/// Capture a [checkpoint](Checkpoint) of the solver, from which the search
+	/// can be [resumed](Self::from_checkpoint) later.
+	///
+	/// # Returns
+	///
+	/// The checkpoint.
+	pub fn checkpoint(&self) -> Checkpoint
+	{
+		Checkpoint {
+			dictionary: *self.fingerprint
+				.get_or_init(|| self.dictionary.fingerprint()),
+			fragments: self.fragments.map(|f| f.to_string()),
+			path: self.path,
+			solution: self.solution.clone(),
+			is_finished: self.is_finished,
+			config: self.config
 		}
 	}
 
+	/// Install the specified [word filter](WordFilter), replacing any filter
+	/// already present.
+	///
+	/// # Arguments
+	///
+	/// * `filter` - The word filter.
+	///
+	/// # Returns
+	///
+	/// The solver, with the word filter installed.
+	pub fn with_filter(mut self, filter: Rc<dyn WordFilter>) -> Self
+	{
+		self.filter = Some(filter);
+		self
+	}
+
+	/// Set the [duplicate policy](DuplicatePolicy), which decides whether a
+	/// word formed by more than one fragment path enters the solution once or
+	/// once per path.
+	///
+	/// # Arguments
+	///
+	/// * `duplicates` - The duplicate policy.
+	///
+	/// # Returns
+	///
+	/// The solver, with the duplicate policy set.
+	pub fn with_duplicate_policy(mut self, duplicates: DuplicatePolicy) -> Self
+	{
+		self.config.duplicates = duplicates;
+		self
+	}
+
+	/// Set the [configuration](SolverConfig), replacing every option
+	/// previously set, including the
+	/// [duplicate policy](Self::with_duplicate_policy).
+	///
+	/// # Arguments
+	///
+	/// * `config` - The configuration.
+	///
+	/// # Returns
+	///
+	/// The solver, with the configuration set.
+	pub fn with_config(mut self, config: SolverConfig) -> Self
+	{
+		self.config = config;
+		self
+	}
+
+	/// Honor the specified [cancellation token](CancellationToken), so that
+	/// another party, e.g., another thread, can abort the search, even one
+	/// run by [`solve_fully`](Self::solve_fully). A cancelled solver stops
+	/// before its next candidate word, without finishing, so it can still be
+	/// [checkpointed](Self::checkpoint) and resumed later.
+	///
+	/// # Arguments
+	///
+	/// * `cancellation` - The cancellation token.
+	///
+	/// # Returns
+	///
+	/// The solver, with the cancellation token installed.
+	pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self
+	{
+		self.cancellation = Some(cancellation);
+		self
+	}
+
+	/// Check whether the search has been [cancelled](CancellationToken).
+	///
+	/// # Returns
+	///
+	/// `true` if the cancellation token, if any, has been cancelled, `false`
+	/// otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_cancelled(&self) -> bool
+	{
+		self.cancellation
+			.as_ref()
+			.is_some_and(CancellationToken::is_cancelled)
+	}
+
+	/// Get the dictionary.
+	///
+	/// # Returns
+	///
+	/// The dictionary.
+	#[inline]
+	pub fn dictionary(&self) -> &Dictionary
+	{
+		&self.dictionary
+	}
+
+	/// Get the fragments of the puzzle, in the order of the grid.
+	///
+	/// # Returns
+	///
+	/// The fragments.
+	#[inline]
+	pub fn fragments(&self) -> &[Fragment; 20]
+	{
+		&self.fragments
+	}
+
+	/// Get the [configuration](SolverConfig).
+	///
+	/// # Returns
+	///
+	/// The configuration.
+	#[inline]
+	pub fn config(&self) -> &SolverConfig
+	{
+		&self.config
+	}
+
+	/// Record the solver's decisions in a [trace](Trace) of the specified
+	/// capacity, which retains only the most recent decisions. Tracing slows
+	/// the search, so it is meant for diagnosis only.
+	///
+	/// # Arguments
+	///
+	/// * `capacity` - The maximum number of decisions to retain.
+	///
+	/// # Returns
+	///
+	/// The solver, with tracing enabled.
+	#[cfg(feature = "trace-ui")]
+	pub fn with_trace(mut self, capacity: usize) -> Self
+	{
+		self.trace = Some(Trace::new(capacity));
+		self
+	}
+
+	/// Answer the [trace](Trace) of the solver's decisions, if tracing is
+	/// enabled.
+	///
+	/// # Returns
+	///
+	/// The trace, or `None` if tracing is disabled.
+	#[cfg(feature = "trace-ui")]
+	#[inline]
+	#[must_use]
+	pub fn trace(&self) -> Option<&Trace>
+	{
+		self.trace.as_ref()
+	}
+
+	/// Answer the worst amount by which a call to [`solve`](Self::solve) has
+	/// overrun its time quantum so far. The solver only checks the clock
+	/// between candidate words, so a slow dictionary probe delays the return
+	/// to the caller, which a user interface experiences as unresponsiveness.
+	///
+	/// # Returns
+	///
+	/// The worst overrun, or [`Duration::ZERO`] if no call has overrun.
+	#[inline]
+	#[must_use]
+	pub fn max_overrun(&self) -> Duration
+	{
+		self.max_overrun
+	}
+
+	/// Answer the counters of the work performed by the solver so far,
+	/// accumulated over every call to [`solve`](Self::solve) since the solver
+	/// was constructed or resumed.
+	///
+	/// # Returns
+	///
+	/// The metrics.
+	#[inline]
+	pub fn metrics(&self) -> &SolverMetrics
+	{
+		&self.metrics
+	}
+
 	/// Check if the solver is finished. The solver is finished if the search
 	/// algorithm has terminated due to exhaustion of the search space.
 	///
@@ -117,11 +473,166 @@ impl Solver
 		// full fragment paths are found, but may not be the case for an
 		// unofficial puzzle.
 		let used_indices = full_paths.iter()
-			.flat_map(|p| p.0.iter().flatten())
+			.flat_map(|p| p.iter().flatten())
 			.collect::<HashSet<_>>();
 		used_indices.len() == self.fragments.len()
 	}
 
+	/// Analyze how complete the solution is, e.g., to explain why the solver
+	/// finished without [solving](Self::is_solved) the puzzle: which fragments
+	/// no full fragment path uses, how many quartiles were found, and which
+	/// full fragment paths came closest to forming another quartile. Finding
+	/// the near misses repeats part of the search, so this is meant to be
+	/// called once, after the solver has finished.
+	///
+	/// # Arguments
+	///
+	/// * `limit` - The maximum number of [near misses](NearMiss) to answer.
+	///
+	/// # Returns
+	///
+	/// The analysis.
+	pub fn completeness(&self, limit: usize) -> Completeness
+	{
+		let full_paths = self.solution.iter()
+			.filter(|p| p.is_full())
+			.collect::<Vec<_>>();
+		let quartiles = full_paths.iter()
+			.map(|p| p.word(&self.fragments))
+			.collect::<HashSet<_>>()
+			.len();
+		let mut used = [false; 20];
+		for index in full_paths.iter().flat_map(|p| p.iter().flatten())
+		{
+			used[index] = true;
+		}
+		let mut misses = Vec::new();
+		near_misses_from(
+			&self.dictionary,
+			&self.fragments,
+			&self.config,
+			&used,
+			&mut Vec::with_capacity(4),
+			&mut String::new(),
+			&mut misses
+		);
+		// Prefer the near misses that would use the most unused fragments,
+		// since those are the ones that stand between the solver and a
+		// complete solution. Keep only the best path for each word.
+		misses.sort_by(|(a, a_unused), (b, b_unused)| {
+			b_unused.cmp(a_unused).then_with(|| a.cmp(b))
+		});
+		let mut seen = HashSet::new();
+		let near_misses = misses
+			.into_iter()
+			.filter(|(path, _)| seen.insert(self.join(path)))
+			.take(limit)
+			.map(|(path, unused)| {
+				let prefix = self.join(&path);
+				let completion = self.dictionary
+					.iter_prefix(&prefix)
+					.min_by_key(|word| (word.len(), *word))
+					.map(str::to_string);
+				NearMiss {
+					fragments: path
+						.iter()
+						.map(|&index| self.fragments[index].to_string())
+						.collect(),
+					unused,
+					completion
+				}
+			})
+			.collect();
+		Completeness {
+			unused: (0..self.fragments.len())
+				.filter(|&index| !used[index])
+				.map(|index| self.fragments[index].to_string())
+				.collect(),
+			quartiles,
+			near_misses
+		}
+	}
+
+	/// Select 5 quartiles that together use every fragment exactly once,
+	/// i.e., an exact cover of the board by full fragment paths. An official
+	/// puzzle yields exactly 5 quartiles, which form its only cover, but an
+	/// unofficial puzzle may yield more, of which only a cover can be the
+	/// intended solution.
+	///
+	/// # Returns
+	///
+	/// The fragment paths of the cover, in grid order of their first
+	/// fragments, or `None` if no cover exists.
+	#[must_use]
+	pub fn cover(&self) -> Option<Vec<FragmentPath>>
+	{
+		// Represent each full fragment path by the set of its fragment
+		// indices. A path that repeats a fragment cannot belong to a cover.
+		let candidates = self.solution.iter()
+			.filter(|p| p.is_full() && p.is_disjoint())
+			.map(|p| (*p, p.word(&self.fragments), p.mask()))
+			.collect::<Vec<_>>();
+		let mut cover = Vec::with_capacity(5);
+		if !cover_from(&candidates, 0, &mut cover)
+		{
+			return None
+		}
+		let mut cover = cover
+			.into_iter()
+			.map(|index| candidates[index].0)
+			.collect::<Vec<_>>();
+		cover.sort_by_key(|p| p[0]);
+		Some(cover)
+	}
+
+	/// Concatenate the fragments at the specified indices.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The indices of the fragments.
+	///
+	/// # Returns
+	///
+	/// The concatenation.
+	fn join(&self, path: &[usize]) -> String
+	{
+		path.iter().map(|&index| self.fragments[index].as_str()).collect()
+	}
+
+	/// Estimate the fraction of the search space that has been explored. The
+	/// search visits fragment paths in lexicographic order, so the position
+	/// of the current fragment path in that order measures the progress of
+	/// the search. The estimate assumes that every subtree of the search is
+	/// equally costly, which pruning violates, so it is only a rough guide;
+	/// but it never decreases.
+	///
+	/// # Returns
+	///
+	/// The estimated fraction of the search space explored, between `0.0` and
+	/// `1.0` inclusive. A finished solver always answers `1.0`.
+	#[must_use]
+	pub fn progress(&self) -> f64
+	{
+		if self.is_finished
+		{
+			return 1.0
+		}
+		let mut used = [false; 20];
+		let mut progress = 0.0;
+		let mut weight = 1.0;
+		for index in self.path.iter().flatten()
+		{
+			// Every unused fragment index begins an equally weighted subtree,
+			// and the subtrees of the lesser indices are already exhausted.
+			let available = used.iter().filter(|u| !**u).count();
+			let exhausted = used[..index].iter().filter(|u| !**u).count();
+			weight /= available as f64;
+			progress += exhausted as f64 * weight;
+			used[index] = true;
+		}
+		progress
+	}
+
 	/// Run the solver until a single valid word is found or the specified
 	/// quantum elapses. Always process at least one fragment path, even if
 	/// the quantum is zero, to ensure that the solver always makes progress.
@@ -139,7 +650,7 @@ impl Solver
 	pub fn solve(mut self, duration: Duration) -> (Self, Option<FragmentPath>)
 	{
 		// Ensure that the current fragment path is prima facie valid.
-		assert!(self.path.is_disjoint());
+		assert!(self.config.allow_repeats || self.path.is_disjoint());
 
 		// If the solver is already finished, just return it.
 		if self.is_finished
@@ -147,34 +658,89 @@ impl Solver
 			trace!("solver is already finished");
 			return (self, None)
 		}
+		let _span = debug_span!("solver_quantum").entered();
 
 		// Start the timer. Loop until the timer expires or a single valid word
 		// is discovered.
 		let start_time = Instant::now();
 		let mut found_word = false;
-		loop
+		let allow_repeats = self.config.allow_repeats;
+		let adjacency = self.config.adjacency;
+		// Walk the dictionary alongside the fragment path, so that each step
+		// only narrows or widens the cursor by the fragments that changed.
+		let dictionary = Rc::clone(&self.dictionary);
+		let mut cursor = dictionary.cursor();
+		if self.config.letter_precheck
+		{
+			cursor = cursor.with_filter(dictionary.letter_filter());
+		}
+		retrace(
+			&mut cursor,
+			&FragmentPath::default(),
+			&self.path,
+			&self.fragments
+		);
+		let found = loop
 		{
 			let start_path = self.path;
-			trace!("considering: {}", self.current_word());
+			let word = cursor.prefix();
+			let len = cursor.char_count();
+			trace!("considering: {}", word);
+			self.metrics.paths_expanded += 1;
+			match cursor.is_live()
+			{
+				true => self.metrics.prefix_hits += 1,
+				false => self.metrics.prefix_misses += 1
+			}
 
 			// If the current fragment path corresponds to a valid word, then
 			// add it to the solution. Note that we discovered a valid word, so
 			// that we can return control to the caller after deriving the next
-			// context.
-			if self.dictionary.contains(self.current_word().as_str())
+			// context. Give the word filter, if any, the opportunity to veto
+			// the word.
+			let is_word = cursor.is_word();
+			if self.config.admits_len(len)
+				&& (!self.config.quartiles_only || self.path.is_full())
+				&& is_word
+				&& self.accepts(word, &self.path)
+				&& !self.is_redundant(word)
 			{
-				debug!("found word: {}", self.current_word());
+				debug!("found word: {}", word);
 				self.solution.push(self.path);
 				found_word = true;
 			}
+			#[cfg(feature = "trace-ui")]
+			if is_word
+			{
+				self.trace_candidate(word, len, found_word);
+			}
 
 			// If the current fragment path does not denote the prefix of any
 			// word in the dictionary, then there is no need to continue
-			// searching along this path.
-			if self.dictionary.contains_prefix(self.current_word().as_str())
+			// searching along this path. Likewise if the candidate word has
+			// already reached the maximum length, since appending a fragment
+			// can only lengthen it, or if no fragment that could fill the last
+			// slot ends a word.
+			let can_extend = len < self.config.max_len
+				&& cursor.is_live()
+				&& self.can_finish();
+			#[cfg(feature = "trace-ui")]
+			if !can_extend && len < self.config.max_len && !self.path.is_full()
+			{
+				let word = Word::from(word);
+				self.record(|solver| Decision::Pruned {
+					path: solver.path,
+					word
+				});
+			}
+			if can_extend
 			{
 				// Try to append the next fragment index.
-				match self.path.append()
+				let next = self.path.append_with(
+					allow_repeats,
+					adjacency
+				);
+				match next
 				{
 					Ok(path) =>
 					{
@@ -187,10 +753,13 @@ impl Solver
 						);
 						self.path = path;
 					}
-					Err(FragmentPathError::Overflow) =>
+					Err(
+						FragmentPathError::Overflow | FragmentPathError::Stranded
+					) =>
 					{
-						// The fragment path is already full, so there's nothing
-						// to do here. Just continue the algorithm.
+						// The fragment path is already full, or no fragment is
+						// adjacent to its last one, so there's nothing to do
+						// here. Just continue the algorithm.
 					}
 					Err(_) => unreachable!()
 				}
@@ -200,7 +769,11 @@ impl Solver
 			{
 				// We didn't append a new fragment index, so try to increment
 				// the rightmost fragment index instead.
-				match self.path.increment()
+				let next = self.path.increment_with(
+					allow_repeats,
+					adjacency
+				);
+				match next
 				{
 					Ok(path) =>
 					{
@@ -218,7 +791,11 @@ impl Solver
 						// The rightmost fragment index is already at the
 						// maximum, so try to pop it and increment the previous
 						// fragment index.
-						match self.path.pop_and_increment()
+						let next = self.path.pop_and_increment_with(
+							allow_repeats,
+							adjacency
+						);
+						match next
 						{
 							Ok(path) =>
 							{
@@ -231,14 +808,21 @@ impl Solver
 									self.current_word()
 								);
 								self.path = path;
+								#[cfg(feature = "trace-ui")]
+								self.record(|solver| Decision::Backtracked {
+									path,
+									word: solver.current_word()
+								});
 							}
 							// The fragment path is now empty, so we have
 							// exhausted the search space.
 							Err(FragmentPathError::CannotIncrementEmpty) =>
 							{
 								debug!("exhausted search space");
+								#[cfg(feature = "trace-ui")]
+								self.record(|_| Decision::Exhausted);
 								self.is_finished = true;
-								return (self, None)
+								break None
 							}
 							Err(_) => unreachable!()
 						}
@@ -255,35 +839,99 @@ impl Solver
 				self.path,
 				self.current_word()
 			);
+			retrace(&mut cursor, &start_path, &self.path, &self.fragments);
 
 			if found_word
 			{
 				// The solver has found a valid word, so return the next
 				// context.
 				let word = *self.solution.last().unwrap();
-				return (self, Some(word))
+				break Some(word)
 			}
 
 			let elapsed = Instant::now().duration_since(start_time);
 			if elapsed >= duration
 			{
 				// The solver has run out of time, so return the current
-				// context.
+				// context. Note by how much the quantum was overrun.
 				trace!("quantum elapsed: {:?}", elapsed);
-				return (self, None)
+				let overrun = elapsed - duration;
+				if overrun > self.max_overrun
+				{
+					debug!("quantum overrun: {:?}", overrun);
+					self.max_overrun = overrun;
+				}
+				break None
+			}
+
+			if self.is_cancelled()
+			{
+				// Another party has aborted the search, so return the current
+				// context, which remains valid for checkpointing.
+				debug!("search cancelled");
+				break None
 			}
+		};
+		self.metrics.probes += cursor.probes();
+		self.metrics.record_quantum(Instant::now() - start_time);
+		(self, found)
+	}
+
+	/// Check whether some fragment that could fill the last slot of the
+	/// current fragment path ends a word of the dictionary. Only the last slot
+	/// is checked, since a fragment that ends no word may still begin or
+	/// continue one. Always `true` unless
+	/// [suffix pruning](SolverConfig::prune_suffixes) is enabled.
+	///
+	/// # Returns
+	///
+	/// `false` if appending the last fragment cannot form a word, `true`
+	/// otherwise.
+	fn can_finish(&self) -> bool
+	{
+		if !self.config.prune_suffixes || self.path[2].is_none()
+		{
+			return true
 		}
+		let enders = *self.enders.get_or_init(|| {
+			self.fragments
+				.iter()
+				.enumerate()
+				.filter(|(_, f)| self.dictionary.contains_suffix(f))
+				.fold(0, |enders, (index, _)| enders | 1 << index)
+		});
+		match self.config.allow_repeats
+		{
+			true => enders != 0,
+			false => enders & !self.path.mask() != 0
+		}
+	}
+
+	/// Run the solver until a single valid word is found or the configured
+	/// [time quantum](SolverConfig::time_quantum) elapses. Same as
+	/// [`solve`](Self::solve) with the configured time quantum.
+	///
+	/// # Returns
+	///
+	/// A 2-tuple comprising the continuation context and any valid word found,
+	/// respectively.
+	#[inline]
+	pub fn step(self) -> (Self, Option<FragmentPath>)
+	{
+		let quantum = self.config.time_quantum;
+		self.solve(quantum)
 	}
 
-	/// Run the solver until the search space is exhausted.
+	/// Run the solver until the search space is exhausted, or until the
+	/// search is [cancelled](Self::with_cancellation).
 	///
 	/// # Returns
 	///
 	/// The final context, which must contain a complete solution if the puzzle
-	/// is solvable.
+	/// is solvable and the search wasn't cancelled.
 	pub fn solve_fully(mut self) -> Self
 	{
-		while !self.is_finished
+		while !self.is_finished && !self.is_cancelled()
 		{
 			let next = self.solve(Duration::from_secs(u64::MAX));
 			self = next.0;
@@ -291,822 +939,4025 @@ impl Solver
 		self
 	}
 
-	/// Get the candidate word corresponding to the specified fragment path.
+	/// Run the solver until the search space is exhausted, until the search
+	/// is [cancelled](Self::with_cancellation), or until the specified budget
+	/// elapses, whichever comes first. Whatever was found by then stays in the
+	/// solution, so a solver that ran out of time answers the best partial
+	/// solution, and can still be resumed.
 	///
 	/// # Arguments
 	///
-	/// * `path` - The fragment path.
+	/// * `budget` - The maximum amount of time to run the solver.
 	///
 	/// # Returns
 	///
-	/// The candidate word corresponding to the specified fragment path.
-	#[inline]
-	#[must_use]
-	pub fn word(&self, path: &FragmentPath) -> str32
+	/// The final context, which is [finished](Self::is_finished) only if the
+	/// search completed within the budget.
+	pub fn solve_within(mut self, budget: Duration) -> Self
 	{
-		path.word(&self.fragments)
+		// A budget too large to represent as a deadline is no budget at all.
+		let Some(deadline) = Instant::now().checked_add(budget)
+		else
+		{
+			return self.solve_fully()
+		};
+		while !self.is_finished && !self.is_cancelled()
+		{
+			let remaining = deadline.saturating_duration_since(Instant::now());
+			if remaining.is_zero()
+			{
+				debug!("solver ran out of time after {:?}", budget);
+				break
+			}
+			self = self.solve(remaining).0;
+		}
+		self
 	}
 
-	/// Get the candidate word corresponding to the current fragment path.
+	/// Convert the solver into an iterator over the words of the solution,
+	/// which runs the search lazily, just far enough to discover each word.
+	/// This relieves the caller of resuming the solver after every word or
+	/// [time quantum](SolverConfig::time_quantum). The iteration ends when the
+	/// search space is exhausted or the search is
+	/// [cancelled](Self::with_cancellation); the solver can then be recovered
+	/// with [`Words::into_solver`].
 	///
 	/// # Returns
 	///
-	/// The candidate word corresponding to the current fragment path.
-	#[inline]
-	#[must_use]
-	fn current_word(&self) -> str32
+	/// The iterator, which yields each word with its fragment path, in order
+	/// of discovery.
+	pub fn into_words(self) -> Words
 	{
-		self.path.word(&self.fragments)
+		Words { solver: Some(self) }
 	}
 
-	/// Get the solution to the puzzle, as a list of fragment paths.
+	/// Get the candidate word corresponding to the specified fragment path.
 	///
-	/// # Returns
+	/// # Arguments
 	///
-	/// The solution to the puzzle, as a list of fragment paths.
-	#[inline]
-	#[must_use]
-	pub fn solution_paths(&self) -> Vec<FragmentPath>
-	{
-		self.solution.clone()
-	}
-
-	/// Get the solution to the puzzle, as a list of words.
+	/// * `path` - The fragment path.
 	///
 	/// # Returns
 	///
-	/// The solution to the puzzle, as a list of words.
+	/// The candidate word corresponding to the specified fragment path.
 	#[inline]
 	#[must_use]
-	pub fn solution(&self) -> Vec<str32>
+	pub fn word(&self, path: &FragmentPath) -> Word
 	{
-		self.solution.iter()
-			.map(|p| p.word(&self.fragments))
-			.collect()
+		path.word(&self.fragments)
 	}
-}
-
-////////////////////////////////////////////////////////////////////////////////
-//                              Fragment paths.                               //
-////////////////////////////////////////////////////////////////////////////////
 
-/// A fragment path is a sequence of four or fewer fragment indices that
-/// correspond to a candidate word. The fragment path is filled in order,
-/// from left to right, and vacated in reverse order, from right to left.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-#[must_use]
-pub struct FragmentPath([Option<usize>; 4]);
-
-impl FragmentPath
-{
-	/// Get an iterator over the fragment indices in the fragment path. The
-	/// iterator yields `None` for any unused fragment indices.
+	/// Get the candidate word corresponding to the current fragment path. The
+	/// word is built from scratch, so prefer the [cursor](DictionaryCursor) on
+	/// the hot path.
 	///
 	/// # Returns
 	///
-	/// An iterator over the fragment indices in the fragment path.
+	/// The candidate word corresponding to the current fragment path.
 	#[inline]
-	pub fn iter(&self) -> impl Iterator<Item = Option<usize>> + '_
+	#[must_use]
+	fn current_word(&self) -> Word
 	{
-		self.0.iter().copied()
+		self.path.word(&self.fragments)
 	}
 
-	/// Check if the fragment path is empty.
+	/// Record a decision in the [trace](Trace), if tracing is enabled. The
+	/// decision is only constructed if it will be recorded.
 	///
-	/// # Returns
+	/// # Arguments
 	///
-	/// `true` if the fragment path is empty, `false` otherwise.
-	#[inline]
-	#[must_use]
-	pub fn is_empty(&self) -> bool
+	/// * `decision` - The function that constructs the decision.
+	#[cfg(feature = "trace-ui")]
+	fn record(&mut self, decision: impl FnOnce(&Self) -> Decision)
 	{
-		self.0[0].is_none()
+		if self.trace.is_some()
+		{
+			let decision = decision(self);
+			if let Some(ref mut trace) = self.trace
+			{
+				trace.record(decision);
+			}
+		}
 	}
 
-	/// Check if the fragment path is full.
+	/// Record the solver's decision about the candidate word of the current
+	/// fragment path, if tracing is enabled. Only dictionary words should be
+	/// recorded, since every other candidate is rejected trivially. To
+	/// explain a rejection, the checks of [`solve`](Self::solve) are repeated,
+	/// so the [word filter](WordFilter), if any, may run twice.
 	///
-	/// # Returns
+	/// # Arguments
 	///
-	/// `true` if the fragment path is full, `false` otherwise.
-	#[inline]
-	#[must_use]
-	pub fn is_full(&self) -> bool
+	/// * `word` - The candidate word, which must be a dictionary word.
+	/// * `len` - The length of the candidate word, in characters.
+	/// * `is_accepted` - Whether the candidate word entered the solution.
+	#[cfg(feature = "trace-ui")]
+	fn trace_candidate(&mut self, word: &str, len: usize, is_accepted: bool)
 	{
-		self.0[3].is_some()
+		if self.trace.is_none()
+		{
+			return
+		}
+		let path = self.path;
+		let word = Word::from(word);
+		let decision = if is_accepted
+		{
+			Decision::Accepted { path, word }
+		}
+		else
+		{
+			let reason = if !self.config.admits_len(len)
+			{
+				Rejection::Length
+			}
+			else if self.config.quartiles_only && !path.is_full()
+			{
+				Rejection::QuartilesOnly
+			}
+			else if !self.accepts(word.as_str(), &path)
+			{
+				Rejection::Filtered
+			}
+			else
+			{
+				Rejection::Duplicate
+			};
+			Decision::Rejected { path, word, reason }
+		};
+		self.record(|_| decision);
 	}
 
-	/// Append a fragment index to the fragment path, using the existing
-	/// fragment indices as uniqueness constraints. The result is always a
-	/// [valid](Self::is_disjoint) fragment path.
+	/// Check whether the [word filter](WordFilter), if any, accepts the
+	/// specified candidate word. In the absence of a word filter, every
+	/// candidate word is accepted.
 	///
-	/// # Returns
+	/// # Arguments
 	///
-	/// The fragment path with the fragment index appended.
+	/// * `word` - The candidate word.
+	/// * `path` - The fragment path that produced the candidate word.
 	///
-	/// # Errors
+	/// # Returns
 	///
-	/// [`FragmentPathError::Overflow`] if the fragment path is already full.
-	fn append(&self) -> Result<Self, FragmentPathError>
+	/// `true` if the candidate word is acceptable, `false` otherwise.
+	#[must_use]
+	fn accepts(&self, word: &str, path: &FragmentPath) -> bool
 	{
-		if self.is_full()
-		{
-			Err(FragmentPathError::Overflow)
-		}
-		else
+		match self.filter
 		{
-			// Find the index of the rightmost occupant.
-			let rightmost = self.0.iter()
-				.rposition(|&index| index.is_some())
-				.map(|i| i as i32)
-				.unwrap_or(-1);
-			// Determine which fragment indices are unavailable.
-			let used = HashSet::<usize>::from_iter(
-				self.0.iter().flatten().copied()
-			);
-			// Determine the start index for the new fragment index.
-			let mut start_index = 0;
-			while used.contains(&start_index)
+			None => true,
+			Some(ref filter) =>
 			{
-				start_index += 1;
+				let accepted = filter.accept(word, path);
+				if !accepted
+				{
+					debug!("filter rejected word: {}", word);
+				}
+				accepted
 			}
-			// Append the next fragment index.
-			let mut fragment = *self;
-			fragment[(rightmost + 1) as usize] = Some(start_index);
-			Ok(fragment)
 		}
 	}
 
-	/// Increment the rightmost fragment index in the fragment path, using the
-	/// other fragment indices as uniqueness constraints. The result is always
-	/// a [valid](Self::is_disjoint) fragment path.
+	/// Check whether the specified candidate word would be redundant, i.e.,
+	/// whether the [duplicate policy](DuplicatePolicy) is
+	/// [`KeepFirst`](DuplicatePolicy::KeepFirst) and the word is already in
+	/// the solution. The solution is short and words are found rarely, so a
+	/// linear scan is cheap enough.
 	///
-	/// # Returns
+	/// # Arguments
 	///
-	/// The fragment path with the rightmost fragment index incremented.
+	/// * `word` - The candidate word.
 	///
-	/// # Errors
+	/// # Returns
 	///
-	/// * [`FragmentPathError::CannotIncrementEmpty`] if the fragment path is
-	///   empty.
-	/// * [`FragmentPathError::IndexOverflow`] if the rightmost fragment index
-	///   is already at the maximum value.
-	fn increment(&self) -> Result<Self, FragmentPathError>
+	/// `true` if the candidate word is redundant, `false` otherwise.
+	#[must_use]
+	fn is_redundant(&self, word: &str) -> bool
 	{
-		// Find the index of the rightmost occupant.
-		let rightmost = self.0.iter()
-			.rposition(|&index| index.is_some())
-			.ok_or(FragmentPathError::CannotIncrementEmpty)?;
-		// Determine which fragment indices are unavailable. Use all but the
-		// last fragment index, because the last fragment index is the one that
-		// is incremented.
-		let used = HashSet::<usize>::from_iter(
-			self.0.iter().take(rightmost).flatten().copied()
-		);
-		// Determine the stop index for the rightmost fragment index.
-		let mut stop_index = 19;
-		while used.contains(&stop_index)
-		{
-			stop_index -= 1;
-		}
-		let mut fragment = *self;
-		loop
+		match self.config.duplicates
 		{
-			if fragment[rightmost] >= Some(stop_index)
-			{
-				// The rightmost fragment index is already at (or beyond) the
-				// maximum value, so report an overflow.
-				return Err(FragmentPathError::IndexOverflow)
-			}
-			else
+			DuplicatePolicy::KeepAll => false,
+			DuplicatePolicy::KeepFirst =>
 			{
-				// Increment the rightmost fragment index.
-				let next = fragment[rightmost].unwrap() + 1;
-				fragment[rightmost] = Some(next);
-				if !used.contains(&next)
+				let redundant = self.solution.iter()
+					.any(|p| p.word(&self.fragments).as_str() == word);
+				if redundant
 				{
-					// The incremented fragment index is available, so use it.
-					return Ok(fragment)
+					debug!("ignoring duplicate word: {}", word);
 				}
+				redundant
 			}
 		}
 	}
 
-	/// Pop a fragment index from the fragment path.
+	/// Compute the score of the word corresponding to the specified fragment
+	/// path. The [word filter](WordFilter), if any, may override the score;
+	/// otherwise, the official Quartiles scoring applies: 1 point for a single
+	/// fragment, 2 points for two fragments, 4 points for three fragments, and
+	/// 8 points for a quartile.
 	///
-	/// # Returns
+	/// # Arguments
 	///
-	/// The fragment path with the last fragment index popped.
+	/// * `path` - The fragment path.
 	///
-	/// # Errors
+	/// # Returns
 	///
-	/// [`FragmentPathError::Underflow`] if the fragment path is already empty.
-	fn pop(&self) -> Result<Self, FragmentPathError>
+	/// The score of the word.
+	#[must_use]
+	pub fn score(&self, path: &FragmentPath) -> u32
 	{
-		if self.is_empty()
-		{
-			Err(FragmentPathError::Underflow)
-		}
-		else
-		{
-			let mut indices = self.0;
-			let rightmost = indices.iter()
-				.rposition(|&index| index.is_some())
-				.unwrap();
-			indices[rightmost] = None;
-			Ok(Self(indices))
-		}
+		self.filter
+			.as_ref()
+			.and_then(|filter| filter.score(self.word(path).as_str(), path))
+			.unwrap_or_else(|| match path.len()
+			{
+				0 => 0,
+				n => 1 << (n - 1)
+			})
 	}
 
-	/// Iteratively pop the rightmost fragment index and increment the previous
-	/// fragment until a valid fragment path is obtained.
+	/// Get the solution to the puzzle, as a list of fragment paths.
 	///
 	/// # Returns
 	///
-	/// The next valid fragment path in the sequence.
+	/// The solution to the puzzle, as a list of fragment paths.
+	#[inline]
+	#[must_use]
+	pub fn solution_paths(&self) -> Vec<FragmentPath>
+	{
+		self.solution.clone()
+	}
+
+	/// Get the solution to the puzzle, as a list of words.
 	///
-	/// # Errors
+	/// # Returns
 	///
-	/// * [`FragmentPathError::Underflow`] if the fragment path is already
-	///   empty.
-	/// * [`FragmentPathError::CannotIncrementEmpty`] if the fragment path is
-	///   empty after popping.
-	fn pop_and_increment(&self) -> Result<Self, FragmentPathError>
+	/// The solution to the puzzle, as a list of words.
+	#[inline]
+	#[must_use]
+	pub fn solution(&self) -> Vec<Word>
 	{
-		let mut fragment = *self;
-		loop
-		{
-			fragment = fragment.pop()?;
-			match fragment.increment()
-			{
-				Ok(fragment) => return Ok(fragment),
-				Err(FragmentPathError::IndexOverflow) => continue,
-				Err(FragmentPathError::CannotIncrementEmpty) =>
-					return Err(FragmentPathError::CannotIncrementEmpty),
-				Err(_) => unreachable!()
-			}
-		}
+		self.solution.iter()
+			.map(|p| p.word(&self.fragments))
+			.collect()
 	}
 
-	/// Check if the fragment indices are disjoint. All valid fragment paths are
-	/// disjoint.
+	/// Get every distinct fragment path in the solution that forms the
+	/// specified word, in order of discovery. A word may be formed in more than
+	/// one way, e.g., `truth` from `tr`+`uth` and `tru`+`th`. Under the
+	/// [`KeepFirst`](DuplicatePolicy::KeepFirst) policy, at most one path is
+	/// ever answered.
+	///
+	/// # Arguments
+	///
+	/// * `word` - The word.
 	///
 	/// # Returns
 	///
-	/// `true` if the fragment indices are disjoint, `false` otherwise.
-	fn is_disjoint(&self) -> bool
+	/// The fragment paths that form the word, which is empty if the word is
+	/// not in the solution.
+	#[must_use]
+	pub fn paths_for_word(&self, word: &str) -> Vec<FragmentPath>
 	{
-		let mut seen = [false; 20];
-		for &index in self.0.iter().flatten()
+		let mut paths = Vec::new();
+		for path in &self.solution
 		{
-			if seen[index]
+			if path.word(&self.fragments).as_str() == word
+				&& !paths.contains(path)
 			{
-				return false
+				paths.push(*path);
 			}
-			seen[index] = true
 		}
-		true
+		paths
 	}
 
-	/// Get the candidate word corresponding to the fragment path.
+	/// Remove every fragment path that forms the specified word from the
+	/// solution, e.g., because the user judged the word bogus. The solver
+	/// doesn't find the word again unless it is restarted.
 	///
 	/// # Arguments
 	///
-	/// * `fragments - The fragments of the puzzle.
+	/// * `word` - The word.
 	///
 	/// # Returns
 	///
-	/// The candidate word corresponding to the fragment path.
-	#[inline]
+	/// `true` if the word was in the solution, `false` otherwise.
+	pub fn retract(&mut self, word: &str) -> bool
+	{
+		let len = self.solution.len();
+		let fragments = self.fragments;
+		self.solution.retain(|path| path.word(&fragments).as_str() != word);
+		self.solution.len() != len
+	}
+
+	/// Get the solution to the puzzle, as a list of distinct words. Each word
+	/// appears once, at the position of its first discovery, regardless of the
+	/// [duplicate policy](DuplicatePolicy).
+	///
+	/// # Returns
+	///
+	/// The solution to the puzzle, as a list of distinct words.
 	#[must_use]
-	fn word(&self, fragments: &[str8; 20]) -> str32
+	pub fn solution_deduped(&self) -> Vec<Word>
 	{
-		let mut word = str32::new();
-		for &index in self.0.iter().flatten()
-		{
-			word.push(&fragments[index]);
-		}
-		word
+		let mut seen = HashSet::new();
+		self.solution.iter()
+			.map(|p| p.word(&self.fragments))
+			.filter(|word| seen.insert(*word))
+			.collect()
 	}
 }
 
-impl Index<usize> for FragmentPath
+/// An iterator over the words of the solution of a [`Solver`], which runs the
+/// search lazily. See [`Solver::into_words`].
+#[derive(Clone, Debug)]
+#[must_use]
+pub struct Words
 {
-	type Output = Option<usize>;
+	/// The solver, which is only absent while it runs.
+	solver: Option<Solver>
+}
 
+impl Words
+{
+	/// Get the solver, e.g., to check its progress.
+	///
+	/// # Returns
+	///
+	/// The solver.
 	#[inline]
-	fn index(&self, index: usize) -> &Self::Output
+	pub fn solver(&self) -> &Solver
 	{
-		&self.0[index]
+		self.solver.as_ref().expect("solver is present between words")
 	}
-}
 
-impl IndexMut<usize> for FragmentPath
-{
+	/// Recover the solver, e.g., to [checkpoint](Solver::checkpoint) it or to
+	/// [finish](Solver::solve_fully) the search.
+	///
+	/// # Returns
+	///
+	/// The solver.
 	#[inline]
-	fn index_mut(&mut self, index: usize) -> &mut Self::Output
+	pub fn into_solver(self) -> Solver
 	{
-		&mut self.0[index]
+		self.solver.expect("solver is present between words")
 	}
 }
 
-/// The complete enumeration of [`FragmentPath`] errors.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-enum FragmentPathError
+impl Iterator for Words
 {
-	/// The fragment path is already full, so no more fragments can be appended.
-	Overflow,
-
-	/// The fragment path is already empty, so no more fragments can be popped.
-	Underflow,
-
-	/// The fragment index is already at the maximum value of 19, so it cannot
-	/// be incremented.
-	IndexOverflow,
-
-	/// The fragment path is empty, so it cannot be incremented.
-	CannotIncrementEmpty
-}
+	type Item = (Word, FragmentPath);
 
-impl Display for FragmentPathError
-{
-	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	fn next(&mut self) -> Option<Self::Item>
+	{
+		loop
+		{
+			let solver = self.solver.take()?;
+			if solver.is_finished || solver.is_cancelled()
+			{
+				self.solver = Some(solver);
+				return None
+			}
+			let (solver, path) = solver.solve(Duration::from_secs(u64::MAX));
+			let item = path.map(|path| (solver.word(&path), path));
+			self.solver = Some(solver);
+			if item.is_some()
+			{
+				return item
+			}
+		}
+	}
+}
+
+impl FusedIterator for Words {}
+
+/// How the [`Solver`] treats a word that is formed by more than one fragment
+/// path, e.g., `truth` from `tr`+`uth` and `tru`+`th`.
+#[derive(
+	Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize
+)]
+pub enum DuplicatePolicy
+{
+	/// Keep every fragment path, so that the solution may contain the same
+	/// word more than once. This is the default.
+	#[default]
+	KeepAll,
+
+	/// Keep only the first fragment path that forms each word.
+	KeepFirst
+}
+
+/// Which fragments may follow one another within a word, according to their
+/// positions on the grid of 5 rows of 4 fragments. Adjacency rules turn the
+/// solver into a general tile-word solver, e.g., for variants in the style of
+/// Boggle.
+#[derive(
+	Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize
+)]
+pub enum Adjacency
+{
+	/// Any fragment may follow any other, as in the official game. This is
+	/// the default.
+	#[default]
+	Unconstrained,
+
+	/// Consecutive fragments must be orthogonally adjacent, i.e., share an
+	/// edge.
+	Orthogonal,
+
+	/// Consecutive fragments must be orthogonally or diagonally adjacent,
+	/// i.e., share an edge or a corner, as in Boggle.
+	Diagonal
+}
+
+impl Adjacency
+{
+	/// Check whether the specified fragment index may follow another.
+	///
+	/// # Arguments
+	///
+	/// * `previous` - The index of the preceding fragment, if any.
+	/// * `next` - The index of the following fragment.
+	///
+	/// # Returns
+	///
+	/// `true` if `next` may follow `previous`, `false` otherwise. Any
+	/// fragment may begin a word.
+	#[must_use]
+	pub fn admits(&self, previous: Option<usize>, next: usize) -> bool
+	{
+		let Some(previous) = previous else { return true };
+		let dx = (previous % 4).abs_diff(next % 4);
+		let dy = (previous / 4).abs_diff(next / 4);
+		match self
+		{
+			Adjacency::Unconstrained => true,
+			Adjacency::Orthogonal => dx + dy == 1,
+			Adjacency::Diagonal => dx.max(dy) == 1
+		}
+	}
+}
+
+/// The options that constrain the search performed by a [`Solver`]. Every
+/// constraint is enforced during the search, rather than by filtering the
+/// solution afterward, so that tighter constraints make the search faster.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[must_use]
+pub struct SolverConfig
+{
+	/// The minimum length of a word, in characters. Shorter words never enter
+	/// the solution.
+	pub min_len: usize,
+
+	/// The maximum length of a word, in characters. Longer words never enter
+	/// the solution, and fragment paths that exceed this length are not
+	/// explored.
+	pub max_len: usize,
+
+	/// Whether only quartiles, i.e., words formed from 4 fragments, may enter
+	/// the solution.
+	pub quartiles_only: bool,
+
+	/// How to treat a word that is formed by more than one fragment path.
+	pub duplicates: DuplicatePolicy,
+
+	/// Whether a word may use the same fragment more than once, as some
+	/// variants of the game permit. Otherwise, the fragments of a word are
+	/// distinct, as in the official game.
+	pub allow_repeats: bool,
+
+	/// Which fragments may follow one another within a word, according to
+	/// their positions on the grid.
+	pub adjacency: Adjacency,
+
+	/// Whether to prune a fragment path of 3 fragments if no fragment that
+	/// could complete it ends a word of the dictionary. The pruning never
+	/// changes the solution, but the first check builds the dictionary's
+	/// [reversed index](Dictionary::contains_suffix), which costs memory.
+	pub prune_suffixes: bool,
+
+	/// Whether to check the letters of each candidate word against the
+	/// dictionary's [letter filter](crate::dictionary::LetterFilter) before
+	/// searching the dictionary for it. The precheck never changes the
+	/// solution, but the first check builds the filter, which costs memory.
+	pub letter_precheck: bool,
+
+	/// How long a single [step](Solver::step) of the solver may run before it
+	/// yields control to the caller.
+	pub time_quantum: Duration
+}
+
+impl Default for SolverConfig
+{
+	fn default() -> Self
+	{
+		Self {
+			min_len: 0,
+			max_len: usize::MAX,
+			quartiles_only: false,
+			duplicates: DuplicatePolicy::default(),
+			allow_repeats: false,
+			adjacency: Adjacency::default(),
+			prune_suffixes: true,
+			letter_precheck: false,
+			time_quantum: Duration::from_millis(5)
+		}
+	}
+}
+
+impl SolverConfig
+{
+	/// Check whether a word of the specified length satisfies the length
+	/// constraints.
+	///
+	/// # Arguments
+	///
+	/// * `len` - The length of the word, in characters.
+	///
+	/// # Returns
+	///
+	/// `true` if the length is admissible, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn admits_len(&self, len: usize) -> bool
+	{
+		(self.min_len..=self.max_len).contains(&len)
+	}
+}
+
+/// The counters of the work performed by a [`Solver`], so that performance
+/// work can rest on observation rather than guesswork.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[must_use]
+pub struct SolverMetrics
+{
+	/// The number of fragment paths considered.
+	pub paths_expanded: u64,
+
+	/// The number of fragment paths whose candidate words begin some word of
+	/// the dictionary.
+	pub prefix_hits: u64,
+
+	/// The number of fragment paths whose candidate words begin no word of
+	/// the dictionary.
+	pub prefix_misses: u64,
+
+	/// The number of searches of the dictionary. A fragment appended to a
+	/// dead prefix, or ruled out by the
+	/// [letter precheck](SolverConfig::letter_precheck), costs no search.
+	pub probes: u64,
+
+	/// The number of calls to [`solve`](Solver::solve) that searched, i.e.,
+	/// the number of time quanta.
+	pub quanta: u64,
+
+	/// The total time spent searching.
+	pub elapsed: Duration,
+
+	/// The longest time spent searching in a single quantum.
+	pub max_quantum: Duration
+}
+
+impl SolverMetrics
+{
+	/// Record a time quantum.
+	///
+	/// # Arguments
+	///
+	/// * `elapsed` - The time spent searching in the quantum.
+	fn record_quantum(&mut self, elapsed: Duration)
+	{
+		self.quanta += 1;
+		self.elapsed += elapsed;
+		self.max_quantum = self.max_quantum.max(elapsed);
+	}
+
+	/// Answer the mean time spent searching per quantum.
+	///
+	/// # Returns
+	///
+	/// The mean time, or zero if there were no quanta.
+	#[must_use]
+	pub fn mean_quantum(&self) -> Duration
+	{
+		match self.quanta
+		{
+			0 => Duration::ZERO,
+			quanta => self.elapsed.div_f64(quanta as f64)
+		}
+	}
+}
+
+impl Display for SolverMetrics
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		writeln!(f, "Paths expanded:    {}", self.paths_expanded)?;
+		writeln!(f, "Prefix hits:       {}", self.prefix_hits)?;
+		writeln!(f, "Prefix misses:     {}", self.prefix_misses)?;
+		writeln!(f, "Dictionary probes: {}", self.probes)?;
+		writeln!(f, "Quanta:            {}", self.quanta)?;
+		writeln!(f, "Time:              {:?}", self.elapsed)?;
+		writeln!(f, "Mean quantum:      {:?}", self.mean_quantum())?;
+		writeln!(f, "Worst quantum:     {:?}", self.max_quantum)
+	}
+}
+
+/// A builder for a [`Solver`], so that the growing set of solver options does
+/// not devolve into a long argument list. The dictionary and the fragments are
+/// required; every other option has a sensible default. For example:
+///
+/// ```no_run
+/// # use std::{rc::Rc, time::Duration};
+/// # use quartiles_solver::{
+/// #     dictionary::Dictionary,
+/// #     solver::{Fragment, Solver}
+/// # };
+/// # let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+/// # let fragments = [Fragment::from("a"); 20];
+/// let solver = Solver::builder()
+///     .dictionary(dictionary)
+///     .fragments(fragments)
+///     .quartiles_only(true)
+///     .min_len(3)
+///     .time_quantum(Duration::from_millis(10))
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct SolverBuilder
+{
+	/// The dictionary to use for solving the puzzle.
+	dictionary: Option<Rc<Dictionary>>,
+
+	/// The fragments of the puzzle.
+	fragments: Option<[Fragment; 20]>,
+
+	/// The optional word filter.
+	filter: Option<Rc<dyn WordFilter>>,
+
+	/// The optional cancellation token.
+	cancellation: Option<CancellationToken>,
+
+	/// The options that constrain the search.
+	config: SolverConfig
+}
+
+impl SolverBuilder
+{
+	/// Set the dictionary to use for solving the puzzle. Required.
+	///
+	/// # Arguments
+	///
+	/// * `dictionary` - The dictionary.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn dictionary(mut self, dictionary: Rc<Dictionary>) -> Self
+	{
+		self.dictionary = Some(dictionary);
+		self
+	}
+
+	/// Set the fragments of the puzzle. Required.
+	///
+	/// # Arguments
+	///
+	/// * `fragments` - The fragments.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn fragments(mut self, fragments: [Fragment; 20]) -> Self
+	{
+		self.fragments = Some(fragments);
+		self
+	}
+
+	/// Set the [word filter](WordFilter).
+	///
+	/// # Arguments
+	///
+	/// * `filter` - The word filter.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn filter(mut self, filter: Rc<dyn WordFilter>) -> Self
+	{
+		self.filter = Some(filter);
+		self
+	}
+
+	/// Set the [cancellation token](CancellationToken). See
+	/// [`Solver::with_cancellation`].
+	///
+	/// # Arguments
+	///
+	/// * `cancellation` - The cancellation token.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn cancellation(mut self, cancellation: CancellationToken) -> Self
+	{
+		self.cancellation = Some(cancellation);
+		self
+	}
+
+	/// Set the complete [configuration](SolverConfig), replacing every option
+	/// previously set.
+	///
+	/// # Arguments
+	///
+	/// * `config` - The configuration.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn config(mut self, config: SolverConfig) -> Self
+	{
+		self.config = config;
+		self
+	}
+
+	/// Set whether only quartiles may enter the solution. See
+	/// [`SolverConfig::quartiles_only`].
+	///
+	/// # Arguments
+	///
+	/// * `quartiles_only` - Whether only quartiles may enter the solution.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn quartiles_only(mut self, quartiles_only: bool) -> Self
+	{
+		self.config.quartiles_only = quartiles_only;
+		self
+	}
+
+	/// Set whether a word may use the same fragment more than once. See
+	/// [`SolverConfig::allow_repeats`].
+	///
+	/// # Arguments
+	///
+	/// * `allow_repeats` - Whether fragments may repeat within a word.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn allow_repeats(mut self, allow_repeats: bool) -> Self
+	{
+		self.config.allow_repeats = allow_repeats;
+		self
+	}
+
+	/// Set which fragments may follow one another within a word. See
+	/// [`SolverConfig::adjacency`].
+	///
+	/// # Arguments
+	///
+	/// * `adjacency` - The adjacency rule.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn adjacency(mut self, adjacency: Adjacency) -> Self
+	{
+		self.config.adjacency = adjacency;
+		self
+	}
+
+	/// Set the minimum length of a word. See [`SolverConfig::min_len`].
+	///
+	/// # Arguments
+	///
+	/// * `min_len` - The minimum length, in characters.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn min_len(mut self, min_len: usize) -> Self
+	{
+		self.config.min_len = min_len;
+		self
+	}
+
+	/// Set the maximum length of a word. See [`SolverConfig::max_len`].
+	///
+	/// # Arguments
+	///
+	/// * `max_len` - The maximum length, in characters.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn max_len(mut self, max_len: usize) -> Self
+	{
+		self.config.max_len = max_len;
+		self
+	}
+
+	/// Set whether to prune fragment paths whose last fragment cannot end a
+	/// word. See [`SolverConfig::prune_suffixes`].
+	///
+	/// # Arguments
+	///
+	/// * `prune_suffixes` - Whether to prune.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn prune_suffixes(mut self, prune_suffixes: bool) -> Self
+	{
+		self.config.prune_suffixes = prune_suffixes;
+		self
+	}
+
+	/// Set whether to check the letters of each candidate word before
+	/// searching the dictionary for it. See [`SolverConfig::letter_precheck`].
+	///
+	/// # Arguments
+	///
+	/// * `letter_precheck` - Whether to precheck.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn letter_precheck(mut self, letter_precheck: bool) -> Self
+	{
+		self.config.letter_precheck = letter_precheck;
+		self
+	}
+
+	/// Set the [duplicate policy](DuplicatePolicy).
+	///
+	/// # Arguments
+	///
+	/// * `duplicates` - The duplicate policy.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn duplicates(mut self, duplicates: DuplicatePolicy) -> Self
+	{
+		self.config.duplicates = duplicates;
+		self
+	}
+
+	/// Set the time quantum of a single [step](Solver::step). See
+	/// [`SolverConfig::time_quantum`].
+	///
+	/// # Arguments
+	///
+	/// * `time_quantum` - The time quantum.
+	///
+	/// # Returns
+	///
+	/// The builder.
+	pub fn time_quantum(mut self, time_quantum: Duration) -> Self
+	{
+		self.config.time_quantum = time_quantum;
+		self
+	}
+
+	/// Build the solver.
+	///
+	/// # Returns
+	///
+	/// The solver.
+	///
+	/// # Errors
+	///
+	/// * [`SolverBuilderError::MissingDictionary`] if no dictionary was set.
+	/// * [`SolverBuilderError::MissingFragments`] if no fragments were set.
+	pub fn build(self) -> Result<Solver, SolverBuilderError>
+	{
+		let dictionary = self.dictionary
+			.ok_or(SolverBuilderError::MissingDictionary)?;
+		let fragments = self.fragments
+			.ok_or(SolverBuilderError::MissingFragments)?;
+		let mut solver = Solver::new(dictionary, fragments)
+			.with_config(self.config);
+		solver.filter = self.filter;
+		solver.cancellation = self.cancellation;
+		Ok(solver)
+	}
+}
+
+/// The complete enumeration of [`SolverBuilder`] errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SolverBuilderError
+{
+	/// No dictionary was supplied.
+	MissingDictionary,
+
+	/// No fragments were supplied.
+	MissingFragments
+}
+
+impl Display for SolverBuilderError
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		match self
+		{
+			Self::MissingDictionary => write!(f, "no dictionary was supplied"),
+			Self::MissingFragments => write!(f, "no fragments were supplied")
+		}
+	}
+}
+
+impl Error for SolverBuilderError {}
+
+/// A token by which one party can abort a search run by another. Clones share
+/// the same state, so the party that cancels keeps a clone of the token that
+/// it installs in the [`Solver`]. Cancellation is permanent; a fresh token is
+/// needed to resume the search.
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken
+{
+	/// Construct a new cancellation token, not yet cancelled.
+	///
+	/// # Returns
+	///
+	/// The new cancellation token.
+	#[inline]
+	pub fn new() -> Self
+	{
+		Self::default()
+	}
+
+	/// Cancel the search. Every solver that honors this token, or a clone
+	/// of it, stops before its next candidate word.
+	#[inline]
+	pub fn cancel(&self)
+	{
+		self.0.store(true, Ordering::Relaxed);
+	}
+
+	/// Check whether the search has been cancelled.
+	///
+	/// # Returns
+	///
+	/// `true` if the search has been cancelled, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_cancelled(&self) -> bool
+	{
+		self.0.load(Ordering::Relaxed)
+	}
+}
+
+/// A checkpoint captures the complete progress of a [`Solver`], and the
+/// configuration under which it was made, sans the dictionary and the word
+/// filter, such that the search can be resumed later, even by another process.
+/// Checkpoints are serializable.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[must_use]
+pub struct Checkpoint
+{
+	/// The [fingerprint](Dictionary::fingerprint) of the dictionary against
+	/// which the checkpoint was captured.
+	dictionary: u64,
+
+	/// The fragments of the puzzle.
+	fragments: [String; 20],
+
+	/// The current fragment path.
+	path: FragmentPath,
+
+	/// The solution to the puzzle, as a list of fragment paths.
+	solution: Vec<FragmentPath>,
+
+	/// Whether the solver is finished.
+	is_finished: bool,
+
+	/// The configuration of the solver, which decides which words enter the
+	/// solution, and whether fragment paths may use the same fragment more
+	/// than once.
+	config: SolverConfig
+}
+
+impl Checkpoint
+{
+	/// Get the fragments of the puzzle. Fragments longer than
+	/// [`MAX_FRAGMENT_BYTES`] are truncated, but a valid checkpoint never
+	/// contains such fragments.
+	///
+	/// # Returns
+	///
+	/// The fragments of the puzzle.
+	#[must_use]
+	pub fn fragments(&self) -> [Fragment; 20]
+	{
+		std::array::from_fn(|i| Fragment::from(self.fragments[i].as_str()))
+	}
+
+	/// Check whether the solver was finished when the checkpoint was captured.
+	///
+	/// # Returns
+	///
+	/// `true` if the solver was finished, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_finished(&self) -> bool
+	{
+		self.is_finished
+	}
+
+	/// Get the configuration of the solver when the checkpoint was captured.
+	///
+	/// # Returns
+	///
+	/// The configuration.
+	#[inline]
+	pub fn config(&self) -> SolverConfig
+	{
+		self.config
+	}
+
+	/// Check whether the checkpoint is internally consistent. A checkpoint read
+	/// from an untrusted source, such as a file, should be validated before it
+	/// is [resumed](Solver::from_checkpoint). Fragment paths may use the same
+	/// fragment more than once only if the checkpoint's
+	/// [configuration](Self::config) allows repeats.
+	///
+	/// # Returns
+	///
+	/// `true` if the checkpoint is valid, `false` otherwise.
+	#[must_use]
+	pub fn is_valid(&self) -> bool
+	{
+		let is_valid_path = |path: &FragmentPath| {
+			path.iter().flatten().all(|index| index < 20)
+				&& (self.config.allow_repeats || path.is_disjoint())
+		};
+		self.fragments
+			.iter()
+			.all(|f| !f.is_empty() && f.len() <= MAX_FRAGMENT_BYTES)
+			&& is_valid_path(&self.path)
+			&& self.solution.iter().all(is_valid_path)
+	}
+}
+
+/// The complete enumeration of errors that prevent a solver from resuming
+/// from a [checkpoint](Checkpoint).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckpointError
+{
+	/// The checkpoint is not [internally consistent](Checkpoint::is_valid).
+	Invalid,
+
+	/// The checkpoint was captured against a different dictionary.
+	DictionaryMismatch
+}
+
+impl Display for CheckpointError
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		match self
+		{
+			Self::Invalid => write!(f, "the checkpoint is inconsistent"),
+			Self::DictionaryMismatch =>
+			{
+				write!(f, "the checkpoint is for a different dictionary")
+			}
+		}
+	}
+}
+
+impl Error for CheckpointError {}
+
+/// Move a [cursor](DictionaryCursor) from one fragment path to another, so
+/// that its prefix becomes the candidate word of the new fragment path.
+/// Successive fragment paths usually share all but their rightmost fragment
+/// index, so the cursor ascends only past the fragments after the common
+/// prefix, and descends only through the new ones, rather than walking the
+/// dictionary from its root.
+///
+/// # Arguments
+///
+/// * `cursor` - The cursor, whose prefix is the candidate word of `from`.
+/// * `from` - The old fragment path.
+/// * `to` - The new fragment path.
+/// * `fragments` - The fragments of the puzzle.
+fn retrace(
+	cursor: &mut DictionaryCursor,
+	from: &FragmentPath,
+	to: &FragmentPath,
+	fragments: &[Fragment; 20]
+)
+{
+	let common = from.iter()
+		.zip(to.iter())
+		.take_while(|(old, new)| old.is_some() && old == new)
+		.count();
+	while cursor.depth() > common
+	{
+		cursor.ascend();
+	}
+	for index in to.iter().skip(common).map_while(|index| index)
+	{
+		cursor.descend(&fragments[index]);
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Lookup.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// Find every word in the dictionary that can be formed by concatenating up to
+/// `max_fragments` distinct fragments, in any order. Unlike the [`Solver`],
+/// which requires the 20 fragments of a complete puzzle, this accepts any
+/// number of fragments, making it a general fragment-anagram tool. The search
+/// is pruned by prefix, just like the solver's.
+///
+/// # Arguments
+///
+/// * `dictionary` - The dictionary.
+/// * `fragments` - The fragments. Duplicate fragments are permitted, and each
+///   occurrence may be used once.
+/// * `max_fragments` - The maximum number of fragments per word.
+///
+/// # Returns
+///
+/// The formable words, sorted and without duplicates.
+#[must_use]
+pub fn lookup<T: AsRef<str>>(
+	dictionary: &Dictionary,
+	fragments: &[T],
+	max_fragments: usize
+) -> Vec<String>
+{
+	let fragments = fragments.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+	let mut used = vec![false; fragments.len()];
+	let mut cursor = dictionary.cursor();
+	let mut words = BTreeSet::new();
+	lookup_from(
+		&fragments,
+		max_fragments,
+		&mut used,
+		&mut cursor,
+		&mut words
+	);
+	words.into_iter().collect()
+}
+
+/// Extend the current candidate word by each unused fragment in turn,
+/// recording valid words and recursing while the candidate is the prefix of
+/// some word. The depth is bounded by `remaining`, so the recursion is shallow.
+///
+/// # Arguments
+///
+/// * `fragments` - The fragments.
+/// * `remaining` - The number of fragments that may still be appended.
+/// * `used` - Which fragments the current candidate word already uses.
+/// * `cursor` - The cursor, whose prefix is the current candidate word.
+/// * `words` - The valid words found so far.
+fn lookup_from(
+	fragments: &[&str],
+	remaining: usize,
+	used: &mut [bool],
+	cursor: &mut DictionaryCursor,
+	words: &mut BTreeSet<String>
+)
+{
+	if remaining == 0
+	{
+		return
+	}
+	for (index, fragment) in fragments.iter().enumerate()
+	{
+		if used[index] || fragment.is_empty()
+		{
+			continue
+		}
+		let is_live = cursor.descend(fragment);
+		if cursor.is_word()
+		{
+			words.insert(cursor.prefix().to_string());
+		}
+		if is_live
+		{
+			used[index] = true;
+			lookup_from(fragments, remaining - 1, used, cursor, words);
+			used[index] = false;
+		}
+		cursor.ascend();
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                               Explanations.                                //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The maximum number of near misses that an [explanation](Explanation)
+/// suggests for a word absent from the dictionary.
+const MAX_NEAR_WORDS: usize = 3;
+
+/// The maximum edit distance between a word absent from the dictionary and
+/// the near misses that its [explanation](Explanation) suggests.
+const MAX_NEAR_DISTANCE: usize = 2;
+
+/// Why a word does or doesn't appear in the solution of a puzzle, as
+/// determined by [`explain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Explanation
+{
+	/// The word is absent from the dictionary.
+	NotInDictionary
+	{
+		/// The words of the dictionary nearest to the word, within
+		/// [`MAX_NEAR_DISTANCE`] edits, nearest first, in case the word is a
+		/// misspelling.
+		near: Vec<String>
+	},
+
+	/// The word is in the dictionary, but no sequence of at most 4 distinct
+	/// fragments forms it.
+	NotFormable,
+
+	/// The word can be formed, but is longer than [`MAX_WORD_BYTES`], so the
+	/// solver cannot represent it.
+	TooLong
+	{
+		/// The length of the word, in bytes.
+		len: usize
+	},
+
+	/// The word can be formed, so the solver finds it, unless the
+	/// [configuration](SolverConfig) or a [word filter](WordFilter) excludes
+	/// it.
+	Formable
+	{
+		/// Every sequence of fragments that forms the word.
+		paths: Vec<Vec<String>>
+	}
+}
+
+impl Display for Explanation
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		match self
+		{
+			Explanation::NotInDictionary { near } if near.is_empty() =>
+			{
+				write!(f, "not in the dictionary")
+			},
+			Explanation::NotInDictionary { near } => write!(
+				f,
+				"not in the dictionary; did you mean {}?",
+				near.join(", ")
+			),
+			Explanation::NotFormable => write!(
+				f,
+				"in the dictionary, but not formable from at most 4 distinct \
+				fragments"
+			),
+			Explanation::TooLong { len } => write!(
+				f,
+				"formable, but {} bytes long, which exceeds the limit of {} \
+				bytes",
+				len,
+				MAX_WORD_BYTES
+			),
+			Explanation::Formable { paths } =>
+			{
+				let paths = paths
+					.iter()
+					.map(|path| path.join("·"))
+					.collect::<Vec<_>>();
+				write!(f, "formable as {}", paths.join(", "))
+			}
+		}
+	}
+}
+
+/// Explain why the specified word does or doesn't appear in the solution of
+/// a puzzle comprising the given fragments, which need not comprise a
+/// complete puzzle.
+///
+/// # Arguments
+///
+/// * `dictionary` - The dictionary.
+/// * `fragments` - The fragments. Duplicate fragments are permitted, and each
+///   occurrence may be used once.
+/// * `word` - The word, which should already be
+///   [normalized](crate::dictionary::normalize_word).
+///
+/// # Returns
+///
+/// The explanation.
+#[must_use]
+pub fn explain<T: AsRef<str>>(
+	dictionary: &Dictionary,
+	fragments: &[T],
+	word: &str
+) -> Explanation
+{
+	if !dictionary.contains(word)
+	{
+		let near = dictionary.search_levenshtein(word, MAX_NEAR_DISTANCE)
+			.into_iter()
+			.take(MAX_NEAR_WORDS)
+			.map(|(word, _)| word.to_string())
+			.collect();
+		return Explanation::NotInDictionary { near }
+	}
+	let fragments = fragments.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+	let mut used = vec![false; fragments.len()];
+	let mut path = Vec::new();
+	let mut paths = Vec::new();
+	explain_from(&fragments, word, &mut used, &mut path, &mut paths);
+	match paths.is_empty()
+	{
+		true => Explanation::NotFormable,
+		false if word.len() > MAX_WORD_BYTES =>
+		{
+			Explanation::TooLong { len: word.len() }
+		},
+		false => Explanation::Formable {
+			paths: paths
+				.into_iter()
+				.map(|path| {
+					path.into_iter()
+						.map(|index| fragments[index].to_string())
+						.collect()
+				})
+				.collect()
+		}
+	}
+}
+
+/// Consume the rest of the word with each unused fragment that begins it,
+/// recording every complete sequence of fragments. The depth is bounded by
+/// the 4 fragments of a quartile, so the recursion is shallow.
+///
+/// # Arguments
+///
+/// * `fragments` - The fragments.
+/// * `rest` - The rest of the word, not yet formed by the current path.
+/// * `used` - Which fragments the current path already uses.
+/// * `path` - The indices of the fragments of the current path.
+/// * `paths` - The complete paths found so far.
+fn explain_from(
+	fragments: &[&str],
+	rest: &str,
+	used: &mut [bool],
+	path: &mut Vec<usize>,
+	paths: &mut Vec<Vec<usize>>
+)
+{
+	if rest.is_empty()
+	{
+		paths.push(path.clone());
+		return
+	}
+	if path.len() == 4
+	{
+		return
+	}
+	for (index, fragment) in fragments.iter().enumerate()
+	{
+		if used[index] || fragment.is_empty() || !rest.starts_with(fragment)
+		{
+			continue
+		}
+		used[index] = true;
+		path.push(index);
+		explain_from(fragments, &rest[fragment.len()..], used, path, paths);
+		path.pop();
+		used[index] = false;
+	}
+}
+
+/// Find the formable words closest to the specified word, e.g., to suggest
+/// what the user might have meant by a word that the solver didn't find.
+/// Closeness is measured by edit distance, with ties broken alphabetically.
+///
+/// # Arguments
+///
+/// * `dictionary` - The dictionary.
+/// * `fragments` - The fragments. Duplicate fragments are permitted, and each
+///   occurrence may be used once.
+/// * `word` - The word, which should already be
+///   [normalized](crate::dictionary::normalize_word).
+/// * `limit` - The maximum number of words to answer.
+///
+/// # Returns
+///
+/// The closest words that the solver can find, closest first, excluding the
+/// word itself.
+#[must_use]
+pub fn closest_words<T: AsRef<str>>(
+	dictionary: &Dictionary,
+	fragments: &[T],
+	word: &str,
+	limit: usize
+) -> Vec<String>
+{
+	let mut words = lookup(dictionary, fragments, 4)
+		.into_iter()
+		.filter(|candidate| candidate != word)
+		.filter(|candidate| candidate.len() <= MAX_WORD_BYTES)
+		.map(|candidate| (edit_distance(word, &candidate), candidate))
+		.collect::<Vec<_>>();
+	words.sort();
+	words.into_iter().take(limit).map(|(_, word)| word).collect()
+}
+
+/// Compute the edit distance between two words, i.e., the minimum number of
+/// single-character insertions, deletions, and substitutions that transform
+/// one into the other.
+///
+/// # Arguments
+///
+/// * `a` - The first word.
+/// * `b` - The second word.
+///
+/// # Returns
+///
+/// The edit distance.
+#[must_use]
+fn edit_distance(a: &str, b: &str) -> usize
+{
+	let b = b.chars().collect::<Vec<_>>();
+	let mut row = (0..=b.len()).collect::<Vec<_>>();
+	for (i, ca) in a.chars().enumerate()
+	{
+		let mut diagonal = row[0];
+		row[0] = i + 1;
+		for (j, &cb) in b.iter().enumerate()
+		{
+			let substitution = diagonal + usize::from(ca != cb);
+			diagonal = row[j + 1];
+			row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+		}
+	}
+	row[b.len()]
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                               Completeness.                                //
+////////////////////////////////////////////////////////////////////////////////
+
+/// Why the solution of a puzzle is or isn't complete, as determined by
+/// [`Solver::completeness`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Completeness
+{
+	/// The fragments that no full fragment path uses, in grid order.
+	pub unused: Vec<String>,
+
+	/// The number of distinct quartiles found, i.e., words formed by full
+	/// fragment paths.
+	pub quartiles: usize,
+
+	/// The full fragment paths that came closest to forming another quartile,
+	/// closest first.
+	pub near_misses: Vec<NearMiss>
+}
+
+impl Completeness
+{
+	/// Check whether the analysis found nothing amiss, i.e., whether every
+	/// fragment is used and at least 5 quartiles were found.
+	///
+	/// # Returns
+	///
+	/// `true` if the solution is complete, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_complete(&self) -> bool
+	{
+		self.unused.is_empty() && self.quartiles >= 5
+	}
+}
+
+impl Display for Completeness
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		writeln!(f, "Quartiles found: {}", self.quartiles)?;
+		if !self.unused.is_empty()
+		{
+			writeln!(f, "Unused fragments: {}", self.unused.join(" "))?;
+		}
+		if !self.near_misses.is_empty()
+		{
+			writeln!(f, "Near misses:")?;
+			for near_miss in &self.near_misses
+			{
+				writeln!(f, "  {}", near_miss)?;
+			}
+		}
+		Ok(())
+	}
+}
+
+/// A full fragment path whose word is not in the dictionary, but which begins
+/// some word that is, so the puzzle might have intended a quartile like it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NearMiss
+{
+	/// The fragments of the path, in order.
+	pub fragments: Vec<String>,
+
+	/// How many of the fragments no full fragment path of the solution uses.
+	pub unused: usize,
+
+	/// The shortest word of the dictionary that the path begins, if any.
+	pub completion: Option<String>
+}
+
+impl Display for NearMiss
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		write!(f, "{}", self.fragments.join("·"))?;
+		if let Some(completion) = &self.completion
+		{
+			write!(f, " (cf. {})", completion)?;
+		}
+		Ok(())
+	}
+}
+
+/// Extend a partial cover of the board by each full fragment path that uses
+/// the lowest uncovered fragment and no covered one, backtracking on failure.
+/// Every fragment must be covered by some path, so trying only the paths that
+/// cover the lowest uncovered fragment loses no cover. The depth is bounded
+/// by the 5 quartiles of a cover, so the recursion is shallow.
+///
+/// # Arguments
+///
+/// * `candidates` - The full fragment paths, each with its word and the set
+///   of its fragment indices, as a bit mask.
+/// * `covered` - The set of fragment indices already covered, as a bit mask.
+/// * `cover` - The indices of the candidates in the partial cover.
+///
+/// # Returns
+///
+/// `true` if the partial cover was completed, `false` otherwise.
+fn cover_from(
+	candidates: &[(FragmentPath, Word, u32)],
+	covered: u32,
+	cover: &mut Vec<usize>
+) -> bool
+{
+	const ALL: u32 = (1 << 20) - 1;
+	if covered == ALL
+	{
+		return true
+	}
+	let lowest = 1 << (!covered).trailing_zeros();
+	for (index, (_, word, mask)) in candidates.iter().enumerate()
+	{
+		// The quartiles of a cover must be distinct words, too.
+		if mask & lowest == 0
+			|| mask & covered != 0
+			|| cover.iter().any(|&other| candidates[other].1 == *word)
+		{
+			continue
+		}
+		cover.push(index);
+		if cover_from(candidates, covered | mask, cover)
+		{
+			return true
+		}
+		cover.pop();
+	}
+	false
+}
+
+/// Extend the current path by each admissible fragment in turn, recording
+/// every full path whose word begins, but is not, a word of the dictionary.
+/// The search honors the repetition and adjacency rules of the configuration,
+/// and is pruned by prefix, so it is cheap. The depth is bounded by the 4
+/// fragments of a quartile, so the recursion is shallow.
+///
+/// # Arguments
+///
+/// * `dictionary` - The dictionary.
+/// * `fragments` - The fragments of the puzzle.
+/// * `config` - The configuration of the solver.
+/// * `used` - Which fragments the full paths of the solution use.
+/// * `path` - The indices of the fragments of the current path.
+/// * `word` - The word of the current path.
+/// * `misses` - The near misses found so far, each with the number of its
+///   fragments that the solution leaves unused.
+fn near_misses_from(
+	dictionary: &Dictionary,
+	fragments: &[Fragment; 20],
+	config: &SolverConfig,
+	used: &[bool; 20],
+	path: &mut Vec<usize>,
+	word: &mut String,
+	misses: &mut Vec<(Vec<usize>, usize)>
+)
+{
+	if path.len() == 4
+	{
+		if !dictionary.contains(word)
+		{
+			let unused = path.iter()
+				.collect::<HashSet<_>>()
+				.into_iter()
+				.filter(|&&index| !used[index])
+				.count();
+			misses.push((path.clone(), unused));
+		}
+		return
+	}
+	for (index, fragment) in fragments.iter().enumerate()
+	{
+		if fragment.is_empty()
+			|| (!config.allow_repeats && path.contains(&index))
+			|| !config.adjacency.admits(path.last().copied(), index)
+		{
+			continue
+		}
+		let len = word.len();
+		word.push_str(fragment);
+		if dictionary.contains_prefix(word)
+		{
+			path.push(index);
+			near_misses_from(
+				dictionary,
+				fragments,
+				config,
+				used,
+				path,
+				word,
+				misses
+			);
+			path.pop();
+		}
+		word.truncate(len);
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                               Verification.                                //
+////////////////////////////////////////////////////////////////////////////////
+
+/// Verify that a hand-made puzzle is sound, i.e., that its 5 intended
+/// quartiles are words of the dictionary that together use each of its 20
+/// fragments exactly once. This checks only the intended solution, so it is
+/// much cheaper than [solving](Solver) the puzzle.
+///
+/// # Arguments
+///
+/// * `dictionary` - The dictionary.
+/// * `fragments` - The fragments of the puzzle. Duplicate fragments are
+///   permitted, and each occurrence must be used once.
+/// * `words` - The intended quartiles, which should already be
+///   [normalized](crate::dictionary::normalize_word).
+///
+/// # Returns
+///
+/// The fragments of each quartile, in the order of the words.
+///
+/// # Errors
+///
+/// [`VerificationError`] describes the first problem found.
+pub fn verify<T: AsRef<str>, U: AsRef<str>>(
+	dictionary: &Dictionary,
+	fragments: &[T],
+	words: &[U]
+) -> Result<Vec<Vec<String>>, VerificationError>
+{
+	if fragments.len() != 20
+	{
+		return Err(VerificationError::FragmentCount(fragments.len()))
+	}
+	if words.len() != 5
+	{
+		return Err(VerificationError::WordCount(words.len()))
+	}
+	let fragments = fragments.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+	// Find every way to form each word from exactly 4 distinct fragments.
+	let mut choices = Vec::with_capacity(words.len());
+	for word in words.iter().map(AsRef::as_ref)
+	{
+		if !dictionary.contains(word)
+		{
+			return Err(VerificationError::NotInDictionary(word.to_string()))
+		}
+		let mut used = vec![false; fragments.len()];
+		let mut paths = Vec::new();
+		explain_from(&fragments, word, &mut used, &mut Vec::new(), &mut paths);
+		paths.retain(|path| path.len() == 4);
+		if paths.is_empty()
+		{
+			return Err(VerificationError::NotQuartile(word.to_string()))
+		}
+		choices.push(paths);
+	}
+	// Choose one way per word, such that no fragment is used twice. Since
+	// there are 5 words of 4 fragments apiece, every fragment is then used.
+	let mut partition = Vec::with_capacity(words.len());
+	if !partition_from(&choices, 0, &mut partition)
+	{
+		return Err(VerificationError::NoPartition)
+	}
+	Ok(partition
+		.into_iter()
+		.map(|path| {
+			path.iter().map(|&index| fragments[index].to_string()).collect()
+		})
+		.collect())
+}
+
+/// Choose a way to form each remaining word, such that no fragment is used
+/// twice, backtracking on failure. The depth is bounded by the 5 quartiles of
+/// a puzzle, so the recursion is shallow.
+///
+/// # Arguments
+///
+/// * `choices` - The ways to form each word, as fragment indices.
+/// * `used` - The set of fragment indices already used, as a bit mask.
+/// * `partition` - The ways chosen so far, one per word.
+///
+/// # Returns
+///
+/// `true` if a way was chosen for every word, `false` otherwise.
+fn partition_from<'a>(
+	choices: &'a [Vec<Vec<usize>>],
+	used: u32,
+	partition: &mut Vec<&'a [usize]>
+) -> bool
+{
+	let Some(paths) = choices.get(partition.len()) else { return true };
+	for path in paths
+	{
+		let mask = path.iter().fold(0u32, |mask, &index| mask | 1 << index);
+		if mask & used != 0
+		{
+			continue
+		}
+		partition.push(path);
+		if partition_from(choices, used | mask, partition)
+		{
+			return true
+		}
+		partition.pop();
+	}
+	false
+}
+
+/// The complete enumeration of problems found by [`verify`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerificationError
+{
+	/// The puzzle doesn't have exactly 20 fragments.
+	FragmentCount(usize),
+
+	/// The puzzle doesn't have exactly 5 quartiles.
+	WordCount(usize),
+
+	/// The word is absent from the dictionary.
+	NotInDictionary(String),
+
+	/// The word cannot be formed from exactly 4 distinct fragments.
+	NotQuartile(String),
+
+	/// Every word is a quartile, but no fragment can be left unused and none
+	/// used twice.
+	NoPartition
+}
+
+impl Display for VerificationError
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		match self
+		{
+			Self::FragmentCount(count) =>
+				write!(f, "expected 20 fragments, but found {}", count),
+			Self::WordCount(count) =>
+				write!(f, "expected 5 words, but found {}", count),
+			Self::NotInDictionary(word) =>
+				write!(f, "not in the dictionary: {}", word),
+			Self::NotQuartile(word) => write!(
+				f,
+				"not formable from exactly 4 distinct fragments: {}",
+				word
+			),
+			Self::NoPartition => write!(
+				f,
+				"the words do not use every fragment exactly once"
+			)
+		}
+	}
+}
+
+impl Error for VerificationError {}
+
+////////////////////////////////////////////////////////////////////////////////
+//                               Word filters.                                //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A word filter decides whether a valid word discovered by the [`Solver`] may
+/// enter the solution, and optionally overrides its score. Word filters permit
+/// house rules without recompiling the solver.
+pub trait WordFilter: Debug
+{
+	/// Check whether the specified word may enter the solution. The word is
+	/// already known to be in the dictionary.
+	///
+	/// # Arguments
+	///
+	/// * `word` - The candidate word.
+	/// * `path` - The fragment path that produced the candidate word.
+	///
+	/// # Returns
+	///
+	/// `true` if the word may enter the solution, `false` otherwise.
+	fn accept(&self, word: &str, path: &FragmentPath) -> bool;
+
+	/// Compute the score of the specified word.
+	///
+	/// # Arguments
+	///
+	/// * `word` - The word.
+	/// * `path` - The fragment path that produced the word.
+	///
+	/// # Returns
+	///
+	/// The score of the word, or `None` to use the default scoring.
+	fn score(&self, _word: &str, _path: &FragmentPath) -> Option<u32>
+	{
+		None
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Fragments.                                 //
+////////////////////////////////////////////////////////////////////////////////
+
+/// Convert the specified text into a [fragment](Fragment), refusing text that
+/// would not fit rather than truncating it. The text is used verbatim, so it
+/// should already be [normalized](crate::dictionary::normalize_word).
+///
+/// # Arguments
+///
+/// * `text` - The text.
+///
+/// # Returns
+///
+/// The fragment.
+///
+/// # Errors
+///
+/// [`FragmentError::TooLong`] if the text exceeds [`MAX_FRAGMENT_BYTES`].
+pub fn parse_fragment(text: &str) -> Result<Fragment, FragmentError>
+{
+	match text.len() <= MAX_FRAGMENT_BYTES
+	{
+		true => Ok(Fragment::from(text)),
+		false => Err(FragmentError::TooLong {
+			fragment: text.to_string()
+		})
+	}
+}
+
+/// The complete enumeration of [fragment](Fragment) errors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FragmentError
+{
+	/// The fragment exceeds [`MAX_FRAGMENT_BYTES`].
+	TooLong
+	{
+		/// The offending fragment.
+		fragment: String
+	}
+}
+
+impl Display for FragmentError
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		match self
+		{
+			Self::TooLong { fragment } => write!(
+				f,
+				"fragment too long: {} ({} bytes, but at most {} fit)",
+				fragment,
+				fragment.len(),
+				MAX_FRAGMENT_BYTES
+			)
+		}
+	}
+}
+
+impl Error for FragmentError {}
+
+////////////////////////////////////////////////////////////////////////////////
+//                              Fragment paths.                               //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A fragment path is a sequence of four or fewer fragment indices that
+/// correspond to a candidate word. The fragment path is filled in order,
+/// from left to right, and vacated in reverse order, from right to left.
+/// Alongside the fragment indices, the fragment path carries a bit mask of
+/// the fragment indices in use, which every operation updates incrementally,
+/// so the search never has to collect them. Only the fragment indices are
+/// serialized.
+#[derive(
+	Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize
+)]
+#[serde(from = "[Option<usize>; 4]", into = "[Option<usize>; 4]")]
+#[must_use]
+pub struct FragmentPath
+{
+	/// The fragment indices, filled from left to right.
+	indices: [Option<usize>; 4],
+
+	/// The fragment indices in use, as a bit mask in which bit `i` is set if
+	/// fragment index `i` occupies some slot.
+	mask: u32
+}
+
+impl FragmentPath
+{
+	/// Get an iterator over the fragment indices in the fragment path. The
+	/// iterator yields `None` for any unused fragment indices.
+	///
+	/// # Returns
+	///
+	/// An iterator over the fragment indices in the fragment path.
+	#[inline]
+	pub fn iter(&self) -> impl Iterator<Item = Option<usize>> + '_
+	{
+		self.indices.iter().copied()
+	}
+
+	/// Check if the fragment path is empty.
+	///
+	/// # Returns
+	///
+	/// `true` if the fragment path is empty, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_empty(&self) -> bool
+	{
+		self.indices[0].is_none()
+	}
+
+	/// Get the number of fragment indices in the fragment path.
+	///
+	/// # Returns
+	///
+	/// The number of fragment indices in the fragment path.
+	#[inline]
+	#[must_use]
+	pub fn len(&self) -> usize
+	{
+		self.indices.iter().flatten().count()
+	}
+
+	/// Get the grid coordinates of the fragments in the fragment path, in
+	/// order. The board has 5 rows of 4 fragments, so fragment index `i` sits
+	/// at row `i / 4` and column `i % 4`, both counted from zero at the top
+	/// left.
+	///
+	/// # Returns
+	///
+	/// The coordinates of the fragments, as `(row, column)` pairs.
+	#[must_use]
+	pub fn coordinates(&self) -> Vec<(usize, usize)>
+	{
+		self.indices.iter()
+			.flatten()
+			.map(|&index| (index / 4, index % 4))
+			.collect()
+	}
+
+	/// Check if the fragment path is full.
+	///
+	/// # Returns
+	///
+	/// `true` if the fragment path is full, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_full(&self) -> bool
+	{
+		self.indices[3].is_some()
+	}
+
+	/// Append a fragment index to the fragment path, using the existing
+	/// fragment indices as uniqueness constraints. The result is always a
+	/// [valid](Self::is_disjoint) fragment path.
+	///
+	/// # Returns
+	///
+	/// The fragment path with the fragment index appended.
+	///
+	/// # Errors
+	///
+	/// [`FragmentPathError::Overflow`] if the fragment path is already full.
+	#[inline]
+	fn append(&self) -> Result<Self, FragmentPathError>
+	{
+		self.append_with(false, Adjacency::Unconstrained)
+	}
+
+	/// Append a fragment index to the fragment path, using the existing
+	/// fragment indices as uniqueness constraints, unless repeats are allowed.
+	/// Without repeats, the result is always a [valid](Self::is_disjoint)
+	/// fragment path. The appended fragment index must also be
+	/// [adjacent](Adjacency) to the last one.
+	///
+	/// # Arguments
+	///
+	/// * `allow_repeats` - Whether the fragment path may use the same fragment
+	///   index more than once.
+	/// * `adjacency` - Which fragment indices may follow one another.
+	///
+	/// # Returns
+	///
+	/// The fragment path with the fragment index appended.
+	///
+	/// # Errors
+	///
+	/// * [`FragmentPathError::Overflow`] if the fragment path is already full.
+	/// * [`FragmentPathError::Stranded`] if no fragment index is admissible.
+	fn append_with(
+		&self,
+		allow_repeats: bool,
+		adjacency: Adjacency
+	) -> Result<Self, FragmentPathError>
+	{
+		if self.is_full()
+		{
+			Err(FragmentPathError::Overflow)
+		}
+		else
+		{
+			// Find the index of the rightmost occupant.
+			let rightmost = self.indices.iter()
+				.rposition(|&index| index.is_some())
+				.map(|i| i as i32)
+				.unwrap_or(-1);
+			// Determine which fragment indices are unavailable.
+			let used = match allow_repeats
+			{
+				true => 0,
+				false => self.mask
+			};
+			// Determine the start index for the new fragment index, which must
+			// be adjacent to the last fragment index, if any.
+			let previous = self.indices.iter().flatten().last().copied();
+			let mut start_index = 0;
+			while used & 1 << start_index != 0
+				|| !adjacency.admits(previous, start_index)
+			{
+				start_index += 1;
+				if start_index == 20
+				{
+					return Err(FragmentPathError::Stranded)
+				}
+			}
+			// Append the next fragment index.
+			let mut fragment = *self;
+			fragment.set((rightmost + 1) as usize, Some(start_index));
+			Ok(fragment)
+		}
+	}
+
+	/// Increment the rightmost fragment index in the fragment path, using the
+	/// other fragment indices as uniqueness constraints. The result is always
+	/// a [valid](Self::is_disjoint) fragment path.
+	///
+	/// # Returns
+	///
+	/// The fragment path with the rightmost fragment index incremented.
+	///
+	/// # Errors
+	///
+	/// * [`FragmentPathError::CannotIncrementEmpty`] if the fragment path is
+	///   empty.
+	/// * [`FragmentPathError::IndexOverflow`] if the rightmost fragment index
+	///   is already at the maximum value.
+	#[inline]
+	fn increment(&self) -> Result<Self, FragmentPathError>
+	{
+		self.increment_with(false, Adjacency::Unconstrained)
+	}
+
+	/// Increment the rightmost fragment index in the fragment path, using the
+	/// other fragment indices as uniqueness constraints, unless repeats are
+	/// allowed. Without repeats, the result is always a
+	/// [valid](Self::is_disjoint) fragment path. The incremented fragment
+	/// index must also be [adjacent](Adjacency) to the previous one.
+	///
+	/// # Arguments
+	///
+	/// * `allow_repeats` - Whether the fragment path may use the same fragment
+	///   index more than once.
+	/// * `adjacency` - Which fragment indices may follow one another.
+	///
+	/// # Returns
+	///
+	/// The fragment path with the rightmost fragment index incremented.
+	///
+	/// # Errors
+	///
+	/// * [`FragmentPathError::CannotIncrementEmpty`] if the fragment path is
+	///   empty.
+	/// * [`FragmentPathError::IndexOverflow`] if the rightmost fragment index
+	///   is already at the maximum value.
+	fn increment_with(
+		&self,
+		allow_repeats: bool,
+		adjacency: Adjacency
+	) -> Result<Self, FragmentPathError>
+	{
+		// Find the index of the rightmost occupant.
+		let rightmost = self.indices.iter()
+			.rposition(|&index| index.is_some())
+			.ok_or(FragmentPathError::CannotIncrementEmpty)?;
+		// Determine which fragment indices are unavailable. Use all but the
+		// last fragment index, because the last fragment index is the one that
+		// is incremented. Without repeats, no other slot shares its bit.
+		let current = self.indices[rightmost].unwrap();
+		let used = match allow_repeats
+		{
+			true => 0,
+			false => self.mask & !(1 << current)
+		};
+		// Determine the fragment index that the rightmost one must be adjacent
+		// to, if any.
+		let previous = rightmost.checked_sub(1).and_then(|i| self.indices[i]);
+		// Determine the stop index for the rightmost fragment index.
+		let mut stop_index = 19;
+		while used & 1 << stop_index != 0
+		{
+			stop_index -= 1;
+		}
+		let mut next = current;
+		loop
+		{
+			if next >= stop_index
+			{
+				// The rightmost fragment index is already at (or beyond) the
+				// maximum value, so report an overflow.
+				return Err(FragmentPathError::IndexOverflow)
+			}
+			else
+			{
+				// Increment the rightmost fragment index.
+				next += 1;
+				if used & 1 << next == 0 && adjacency.admits(previous, next)
+				{
+					// The incremented fragment index is available, so use it.
+					let mut fragment = *self;
+					fragment.set(rightmost, Some(next));
+					return Ok(fragment)
+				}
+			}
+		}
+	}
+
+	/// Pop a fragment index from the fragment path.
+	///
+	/// # Returns
+	///
+	/// The fragment path with the last fragment index popped.
+	///
+	/// # Errors
+	///
+	/// [`FragmentPathError::Underflow`] if the fragment path is already empty.
+	fn pop(&self) -> Result<Self, FragmentPathError>
+	{
+		if self.is_empty()
+		{
+			Err(FragmentPathError::Underflow)
+		}
+		else
+		{
+			let rightmost = self.indices.iter()
+				.rposition(|&index| index.is_some())
+				.unwrap();
+			let mut fragment = *self;
+			fragment.set(rightmost, None);
+			Ok(fragment)
+		}
+	}
+
+	/// Iteratively pop the rightmost fragment index and increment the previous
+	/// fragment until a valid fragment path is obtained.
+	///
+	/// # Returns
+	///
+	/// The next valid fragment path in the sequence.
+	///
+	/// # Errors
+	///
+	/// * [`FragmentPathError::Underflow`] if the fragment path is already
+	///   empty.
+	/// * [`FragmentPathError::CannotIncrementEmpty`] if the fragment path is
+	///   empty after popping.
+	#[inline]
+	fn pop_and_increment(&self) -> Result<Self, FragmentPathError>
+	{
+		self.pop_and_increment_with(false, Adjacency::Unconstrained)
+	}
+
+	/// Iteratively pop the rightmost fragment index and increment the previous
+	/// fragment until a valid fragment path is obtained.
+	///
+	/// # Arguments
+	///
+	/// * `allow_repeats` - Whether the fragment path may use the same fragment
+	///   index more than once.
+	/// * `adjacency` - Which fragment indices may follow one another.
+	///
+	/// # Returns
+	///
+	/// The next valid fragment path in the sequence.
+	///
+	/// # Errors
+	///
+	/// * [`FragmentPathError::Underflow`] if the fragment path is already
+	///   empty.
+	/// * [`FragmentPathError::CannotIncrementEmpty`] if the fragment path is
+	///   empty after popping.
+	fn pop_and_increment_with(
+		&self,
+		allow_repeats: bool,
+		adjacency: Adjacency
+	) -> Result<Self, FragmentPathError>
+	{
+		let mut fragment = *self;
+		loop
+		{
+			fragment = fragment.pop()?;
+			match fragment.increment_with(allow_repeats, adjacency)
+			{
+				Ok(fragment) => return Ok(fragment),
+				Err(FragmentPathError::IndexOverflow) => continue,
+				Err(FragmentPathError::CannotIncrementEmpty) =>
+					return Err(FragmentPathError::CannotIncrementEmpty),
+				Err(_) => unreachable!()
+			}
+		}
+	}
+
+	/// Get the fragment indices in use as a bit mask, in which bit `i` is set
+	/// if fragment index `i` occupies some slot.
+	///
+	/// # Returns
+	///
+	/// The bit mask.
+	#[inline]
+	#[must_use]
+	fn mask(&self) -> u32
+	{
+		self.mask
+	}
+
+	/// Occupy or vacate the specified slot, updating the
+	/// [bit mask](Self::mask) to match. The bit of a vacated fragment index
+	/// survives if the fragment index still occupies another slot, which
+	/// happens only when repeats are allowed.
+	///
+	/// # Arguments
+	///
+	/// * `slot` - The slot.
+	/// * `index` - The new occupant of the slot, if any.
+	fn set(&mut self, slot: usize, index: Option<usize>)
+	{
+		if let Some(old) = std::mem::replace(&mut self.indices[slot], index)
+		{
+			if !self.indices.contains(&Some(old))
+			{
+				self.mask &= !(1 << old);
+			}
+		}
+		if let Some(index) = index
+		{
+			self.mask |= 1 << index;
+		}
+	}
+
+	/// Check if the fragment indices are disjoint. All valid fragment paths are
+	/// disjoint.
+	///
+	/// # Returns
+	///
+	/// `true` if the fragment indices are disjoint, `false` otherwise.
+	fn is_disjoint(&self) -> bool
+	{
+		let mut seen = [false; 20];
+		for &index in self.indices.iter().flatten()
+		{
+			if seen[index]
+			{
+				return false
+			}
+			seen[index] = true
+		}
+		true
+	}
+
+	/// Get the candidate word corresponding to the fragment path.
+	///
+	/// # Arguments
+	///
+	/// * `fragments - The fragments of the puzzle.
+	///
+	/// # Returns
+	///
+	/// The candidate word corresponding to the fragment path.
+	#[inline]
+	#[must_use]
+	fn word(&self, fragments: &[Fragment; 20]) -> Word
+	{
+		let mut word = Word::new();
+		for &index in self.indices.iter().flatten()
+		{
+			word.push(&fragments[index]);
+		}
+		word
+	}
+}
+
+impl Index<usize> for FragmentPath
+{
+	type Output = Option<usize>;
+
+	#[inline]
+	fn index(&self, index: usize) -> &Self::Output
+	{
+		&self.indices[index]
+	}
+}
+
+impl From<[Option<usize>; 4]> for FragmentPath
+{
+	fn from(indices: [Option<usize>; 4]) -> Self
+	{
+		// An index beyond the board has no bit, but such a fragment path is
+		// never valid anyway.
+		let mask = indices.iter()
+			.flatten()
+			.filter(|&&index| index < 20)
+			.fold(0, |mask, &index| mask | 1 << index);
+		Self { indices, mask }
+	}
+}
+
+impl From<FragmentPath> for [Option<usize>; 4]
+{
+	fn from(path: FragmentPath) -> Self
+	{
+		path.indices
+	}
+}
+
+/// The complete enumeration of [`FragmentPath`] errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FragmentPathError
+{
+	/// The fragment path is already full, so no more fragments can be appended.
+	Overflow,
+
+	/// The fragment path is already empty, so no more fragments can be popped.
+	Underflow,
+
+	/// The fragment index is already at the maximum value of 19, so it cannot
+	/// be incremented.
+	IndexOverflow,
+
+	/// The fragment path is empty, so it cannot be incremented.
+	CannotIncrementEmpty,
+
+	/// No fragment index may be appended, because every candidate is either
+	/// used or not [adjacent](Adjacency) to the last fragment index.
+	Stranded
+}
+
+impl Display for FragmentPathError
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
 	{
 		match self
 		{
-			Self::Overflow => write!(f, "fragment path is already full"),
-			Self::Underflow => write!(f, "fragment path is already empty"),
-			Self::IndexOverflow =>
-				write!(f, "fragment index is already at maximum"),
-			Self::CannotIncrementEmpty => write!(f, "fragment path is empty")
+			Self::Overflow => write!(f, "fragment path is already full"),
+			Self::Underflow => write!(f, "fragment path is already empty"),
+			Self::IndexOverflow =>
+				write!(f, "fragment index is already at maximum"),
+			Self::CannotIncrementEmpty => write!(f, "fragment path is empty"),
+			Self::Stranded => write!(f, "no fragment index is admissible")
+		}
+	}
+}
+
+impl Error for FragmentPathError {}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::{
+		collections::HashSet,
+		rc::Rc,
+		time::{Duration, Instant}
+	};
+
+	use proptest::prelude::*;
+
+	use crate::{
+		dictionary::Dictionary,
+		solver::{
+			closest_words, edit_distance, explain, lookup, verify, Adjacency,
+			CancellationToken, Checkpoint, CheckpointError,
+			parse_fragment, DuplicatePolicy, Explanation, Fragment,
+			FragmentError, FragmentPath, FragmentPathError, NearMiss, Solver,
+			SolverBuilderError, SolverConfig, SolverMetrics, VerificationError,
+			Word,
+			retrace, WordFilter,
+			MAX_FRAGMENT_BYTES, MAX_WORD_BYTES
+		}
+	};
+
+	/// A sound board, whose quartiles are "truthfully", "razzmatazz",
+	/// "crosswords", "nihilistic", and "refreshment".
+	const BOARD: [&str; 20] = [
+		"azz", "th", "ss", "tru", "ref", "fu", "ra", "nih", "cro", "mat", "wo",
+		"sh", "re", "rds", "tic", "il", "lly", "zz", "is", "ment"
+	];
+
+	/// Ensure that appending a fragment index to a fragment path works for all
+	/// interesting cases.
+	#[test]
+	fn test_append()
+	{
+		let path = FragmentPath::default();
+		assert_eq!(path, FragmentPath::from([None, None, None, None]));
+		assert!(path.is_empty());
+		assert!(!path.is_full());
+		assert!(path.is_disjoint());
+		let path = path.append().unwrap();
+		assert_eq!(path, FragmentPath::from([Some(0), None, None, None]));
+		assert!(!path.is_empty());
+		assert!(!path.is_full());
+		assert!(path.is_disjoint());
+		let path = path.append().unwrap();
+		assert_eq!(path, FragmentPath::from([Some(0), Some(1), None, None]));
+		assert!(!path.is_empty());
+		assert!(!path.is_full());
+		assert!(path.is_disjoint());
+		let path = path.append().unwrap();
+		assert_eq!(path, FragmentPath::from([Some(0), Some(1), Some(2), None]));
+		assert!(!path.is_empty());
+		assert!(!path.is_full());
+		assert!(path.is_disjoint());
+		let path = path.append().unwrap();
+		assert_eq!(
+			path,
+			FragmentPath::from([Some(0), Some(1), Some(2), Some(3)])
+		);
+		assert!(!path.is_empty());
+		assert!(path.is_full());
+		assert!(path.is_disjoint());
+		assert_eq!(path.append(), Err(FragmentPathError::Overflow));
+	}
+
+	/// Advance the specified fragment path to the next one, exactly as the
+	/// [solver](Solver::solve) does: try to append a fragment index if the
+	/// path may be extended, then fall back on incrementing the rightmost one,
+	/// and finally on popping and incrementing.
+	///
+	/// # Arguments
+	///
+	/// * `path` - The fragment path.
+	/// * `extend` - Whether the fragment path may be extended.
+	/// * `allow_repeats` - Whether fragment indices may repeat.
+	/// * `adjacency` - Which fragment indices may follow one another.
+	///
+	/// # Returns
+	///
+	/// The next fragment path, or `None` if the search space is exhausted.
+	fn advance(
+		path: FragmentPath,
+		extend: bool,
+		allow_repeats: bool,
+		adjacency: Adjacency
+	) -> Option<FragmentPath>
+	{
+		if extend
+		{
+			match path.append_with(allow_repeats, adjacency)
+			{
+				Ok(next) => return Some(next),
+				Err(
+					FragmentPathError::Overflow | FragmentPathError::Stranded
+				) => {},
+				Err(e) => panic!("{:?}: {}", path, e)
+			}
+		}
+		match path.increment_with(allow_repeats, adjacency)
+		{
+			Ok(next) => Some(next),
+			Err(FragmentPathError::IndexOverflow) =>
+			{
+				match path.pop_and_increment_with(allow_repeats, adjacency)
+				{
+					Ok(next) => Some(next),
+					Err(FragmentPathError::CannotIncrementEmpty) => None,
+					Err(e) => panic!("{:?}: {}", path, e)
+				}
+			},
+			Err(e) => panic!("{:?}: {}", path, e)
+		}
+	}
+
+	/// Ensure that, when nothing is pruned, the search enumerates every
+	/// disjoint fragment path exactly once, in lexicographic order.
+	#[test]
+	fn test_enumeration()
+	{
+		assert_eq!(
+			FragmentPath::default().increment(),
+			Err(FragmentPathError::CannotIncrementEmpty)
+		);
+		let free = Adjacency::Unconstrained;
+		let mut path = FragmentPath::default();
+		let mut previous = Vec::new();
+		let mut count = 0;
+		while let Some(next) = advance(path, true, false, free)
+		{
+			assert!(next.is_disjoint(), "{:?}", next);
+			let indices = next.iter().collect::<Vec<_>>();
+			assert!(indices > previous, "{:?} after {:?}", next, path);
+			previous = indices;
+			path = next;
+			count += 1;
+		}
+		assert_eq!(count, 20 + 20 * 19 + 20 * 19 * 18 + 20 * 19 * 18 * 17);
+	}
+
+	proptest!
+	{
+		/// Ensure, for arbitrary prunings under every repeat policy and
+		/// adjacency rule, that the search visits exactly the fragment paths
+		/// whose proper prefixes were all extended, each once and in
+		/// lexicographic order, and that they stay disjoint unless repeats are
+		/// allowed. Each visited fragment path is extended or pruned by the
+		/// next of the decisions, and pruned once they run out, so that a
+		/// failure shrinks to the fewest extensions that exhibit it.
+		#[test]
+		fn test_enumeration_properties(
+			allow_repeats in any::<bool>(),
+			adjacency in prop_oneof![
+				Just(Adjacency::Unconstrained),
+				Just(Adjacency::Orthogonal),
+				Just(Adjacency::Diagonal)
+			],
+			decisions in prop::collection::vec(
+				prop::bool::weighted(0.25),
+				0..256
+			)
+		)
+		{
+			// Count the admissible successors of a fragment path.
+			let successors = |path: &FragmentPath| {
+				let used = if allow_repeats { 0 } else { path.mask() };
+				let previous = path.iter().flatten().last();
+				(0..20)
+					.filter(|&next| {
+						used & 1 << next == 0 && adjacency.admits(previous, next)
+					})
+					.count()
+			};
+			let mut decisions = decisions.into_iter();
+			let mut extended = HashSet::from([FragmentPath::default()]);
+			let mut expected = successors(&FragmentPath::default());
+			let mut path = FragmentPath::default();
+			let mut extend = true;
+			let mut previous = Vec::new();
+			let mut count = 0;
+			while let Some(next) =
+				advance(path, extend, allow_repeats, adjacency)
+			{
+				prop_assert!(
+					extended.contains(&next.pop().unwrap()),
+					"{:?} has an unextended prefix",
+					next
+				);
+				prop_assert!(allow_repeats || next.is_disjoint());
+				let indices = next.iter().collect::<Vec<_>>();
+				prop_assert!(indices > previous, "{:?}", next);
+				extend = decisions.next().unwrap_or(false);
+				if extend && !next.is_full()
+				{
+					extended.insert(next);
+					expected += successors(&next);
+				}
+				previous = indices;
+				path = next;
+				count += 1;
+			}
+			prop_assert_eq!(count, expected);
+		}
+	}
+
+	/// Ensure that popping a fragment index from a fragment path works for all
+	/// interesting cases.
+	#[test]
+	fn test_pop()
+	{
+		let path = FragmentPath::default();
+		assert_eq!(
+			path.pop(),
+			Err(FragmentPathError::Underflow)
+		);
+
+		let path = path.append().unwrap();
+		let path = path.append().unwrap();
+		let path = path.append().unwrap();
+		let path = path.append().unwrap();
+		assert_eq!(
+			path,
+			FragmentPath::from([Some(0), Some(1), Some(2), Some(3)])
+		);
+		assert!(!path.is_empty());
+		assert!(path.is_full());
+		assert!(path.is_disjoint());
+		let path = path.pop().unwrap();
+		assert_eq!(path, FragmentPath::from([Some(0), Some(1), Some(2), None]));
+		assert!(!path.is_empty());
+		assert!(!path.is_full());
+		assert!(path.is_disjoint());
+		let path = path.pop().unwrap();
+		assert_eq!(path, FragmentPath::from([Some(0), Some(1), None, None]));
+		assert!(!path.is_empty());
+		assert!(!path.is_full());
+		assert!(path.is_disjoint());
+		let path = path.pop().unwrap();
+		assert_eq!(path, FragmentPath::from([Some(0), None, None, None]));
+		assert!(!path.is_empty());
+		assert!(!path.is_full());
+		assert!(path.is_disjoint());
+		let path = path.pop().unwrap();
+		assert_eq!(path, FragmentPath::from([None, None, None, None]));
+		assert!(path.is_empty());
+		assert!(!path.is_full());
+		assert!(path.is_disjoint());
+	}
+
+	/// Ensure that popping and incrementing a fragment path works for all
+	/// interesting cases.
+	#[test]
+	fn test_pop_and_increment()
+	{
+		let path = FragmentPath::default();
+		assert_eq!(
+			path.pop_and_increment(),
+			Err(FragmentPathError::Underflow)
+		);
+
+		let path = path.append().unwrap();
+		let path = path.append().unwrap();
+		let path = path.append().unwrap();
+		let path = path.append().unwrap();
+		assert_eq!(
+			path,
+			FragmentPath::from([Some(0), Some(1), Some(2), Some(3)])
+		);
+		assert!(!path.is_empty());
+		assert!(path.is_full());
+		assert!(path.is_disjoint());
+		let path = path.pop_and_increment().unwrap();
+		assert_eq!(path, FragmentPath::from([Some(0), Some(1), Some(3), None]));
+		assert!(!path.is_empty());
+		assert!(!path.is_full());
+		assert!(path.is_disjoint());
+		let path = path.pop_and_increment().unwrap();
+		assert_eq!(path, FragmentPath::from([Some(0), Some(2), None, None]));
+		assert!(!path.is_empty());
+		assert!(!path.is_full());
+		assert!(path.is_disjoint());
+		let path = path.pop_and_increment().unwrap();
+		assert_eq!(path, FragmentPath::from([Some(1), None, None, None]));
+		assert!(!path.is_empty());
+		assert!(!path.is_full());
+		assert!(path.is_disjoint());
+		assert_eq!(
+			path.pop_and_increment(),
+			Err(FragmentPathError::CannotIncrementEmpty)
+		);
+
+		let path = FragmentPath::from([Some(19), Some(18), Some(17), Some(16)]);
+		assert_eq!(
+			path.pop_and_increment(),
+			Err(FragmentPathError::CannotIncrementEmpty)
+		);
+
+		let path = FragmentPath::from([Some(18), Some(17), Some(16), Some(15)]);
+		let path = path.pop_and_increment().unwrap();
+		assert_eq!(
+			path,
+			FragmentPath::from([Some(18), Some(17), Some(19), None])
+		);
+		let path = path.pop_and_increment().unwrap();
+		assert_eq!(path, FragmentPath::from([Some(18), Some(19), None, None]));
+		let path = path.pop_and_increment().unwrap();
+		assert_eq!(path, FragmentPath::from([Some(19), None, None, None]));
+		assert_eq!(
+			path.pop_and_increment(),
+			Err(FragmentPathError::CannotIncrementEmpty)
+		);
+	}
+
+	/// Ensure that retracing a cursor agrees with building the candidate word
+	/// from scratch, however the fragment path changes.
+	#[test]
+	fn test_retrace()
+	{
+		let mut fragments = [Fragment::from("x"); 20];
+		for (index, fragment) in ["ab", "cde", "é", "f"].into_iter().enumerate()
+		{
+			fragments[index] = Fragment::from(fragment);
+		}
+		let dictionary = ["abcdeéf", "éf", "f"].into_iter()
+			.collect::<Dictionary>();
+		let mut cursor = dictionary.cursor();
+		let mut previous = FragmentPath::default();
+		let paths = [
+			FragmentPath::default(),
+			FragmentPath::from([Some(0), None, None, None]),
+			FragmentPath::from([Some(0), Some(1), None, None]),
+			FragmentPath::from([Some(0), Some(1), Some(2), Some(3)]),
+			FragmentPath::from([Some(0), Some(1), Some(2), Some(4)]),
+			FragmentPath::from([Some(0), Some(2), None, None]),
+			FragmentPath::from([Some(2), Some(3), Some(1), None]),
+			FragmentPath::from([Some(3), None, None, None]),
+			FragmentPath::default()
+		];
+		for path in paths
+		{
+			retrace(&mut cursor, &previous, &path, &fragments);
+			let word = path.word(&fragments);
+			assert_eq!(cursor.prefix(), word.as_str(), "{:?}", path);
+			assert_eq!(cursor.char_count(), word.chars().count(), "{:?}", path);
+			assert_eq!(cursor.depth(), path.len(), "{:?}", path);
+			assert_eq!(
+				cursor.is_word(),
+				dictionary.contains(word.as_str()),
+				"{:?}",
+				path
+			);
+			previous = path;
+		}
+	}
+
+	/// Ensure that the coordinates of a fragment path follow its fragment
+	/// indices across the grid.
+	#[test]
+	fn test_coordinates()
+	{
+		assert!(FragmentPath::default().coordinates().is_empty());
+		let path = FragmentPath::from([Some(0), Some(7), Some(13), Some(19)]);
+		assert_eq!(
+			path.coordinates(),
+			vec![(0, 0), (1, 3), (3, 1), (4, 3)]
+		);
+		let path = FragmentPath::from([Some(5), Some(2), None, None]);
+		assert_eq!(path.coordinates(), vec![(1, 1), (0, 2)]);
+	}
+
+	/// Ensure that the bit mask of a fragment path sets exactly the bits of
+	/// its leading fragment indices.
+	#[test]
+	fn test_mask()
+	{
+		assert_eq!(FragmentPath::default().mask(), 0);
+		let path = FragmentPath::from([Some(0), Some(19), Some(7), None]);
+		assert_eq!(path.mask(), 1 | 1 << 19 | 1 << 7);
+		let path = FragmentPath::from([Some(3), Some(3), None, None]);
+		assert_eq!(path.mask(), 1 << 3);
+		assert_eq!(path.pop().unwrap().mask(), 1 << 3);
+		assert_eq!(path.pop().unwrap().pop().unwrap().mask(), 0);
+
+		// Every operation keeps the bit mask in step with the fragment
+		// indices, with or without repeats.
+		for allow_repeats in [false, true]
+		{
+			let mut path = FragmentPath::default();
+			for _ in 0..10_000
+			{
+				let expected = FragmentPath::from(<[_; 4]>::from(path));
+				assert_eq!(path.mask(), expected.mask(), "{:?}", path);
+				path = path.append_with(allow_repeats, Adjacency::Unconstrained)
+					.or_else(|_| {
+						path.increment_with(
+							allow_repeats,
+							Adjacency::Unconstrained
+						)
+					})
+					.or_else(|_| {
+						path.pop_and_increment_with(
+							allow_repeats,
+							Adjacency::Unconstrained
+						)
+					})
+					.unwrap();
+			}
+		}
+	}
+
+	/// Ensure that the disjointedness of fragment paths is correctly
+	/// determined. Be exhaustive, since it's cheap and the space is easy to
+	/// enumerate.
+	#[test]
+	fn test_is_disjoint()
+	{
+		let path = FragmentPath::default();
+		assert!(path.is_disjoint());
+
+		for i in 0..20
+		{
+			let path = FragmentPath::from([Some(i), None, None, None]);
+			assert!(path.is_disjoint());
+		}
+
+		for i in 0..20
+		{
+			for j in 0..20
+			{
+				let path = FragmentPath::from([Some(i), Some(j), None, None]);
+				assert_eq!(path.is_disjoint(), i != j, "{}, {}", i, j);
+			}
+		}
+
+		for i in 0..20
+		{
+			for j in 0..20
+			{
+				for k in 0..20
+				{
+					let path =
+						FragmentPath::from([Some(i), Some(j), Some(k), None]);
+					assert_eq!(
+						path.is_disjoint(),
+						i != j && i != k && j != k,
+						"{}, {}, {}", i, j, k
+					);
+				}
+			}
+		}
+
+		for i in 0..20
+		{
+			for j in 0..20
+			{
+				for k in 0..20
+				{
+					for l in 0..20
+					{
+						let path = FragmentPath::from(
+							[Some(i), Some(j), Some(k), Some(l)]
+						);
+						assert_eq!(
+							path.is_disjoint(),
+							i != j && i != k && i != l
+								&& j != k && j != l
+								&& k != l,
+							"{}, {}, {}, {}", i, j, k, l
+						);
+					}
+				}
+			}
 		}
 	}
-}
 
-impl Error for FragmentPathError {}
+	/// Ensure that allowing repeats relaxes the uniqueness constraints of
+	/// appending and incrementing, so that a fragment path may reuse a
+	/// fragment index, and that forbidding them preserves disjointness.
+	#[test]
+	fn test_repeats()
+	{
+		let free = Adjacency::Unconstrained;
+		let mut path = FragmentPath::default();
+		for len in 1..=4
+		{
+			path = path.append_with(true, free).unwrap();
+			assert_eq!(path.len(), len);
+			assert_eq!(path.iter().flatten().collect::<Vec<_>>(), vec![0; len]);
+		}
+		assert!(!path.is_disjoint());
+		assert_eq!(
+			path.append_with(true, free),
+			Err(FragmentPathError::Overflow)
+		);
+		let path = path.increment_with(true, free).unwrap();
+		assert_eq!(
+			path,
+			FragmentPath::from([Some(0), Some(0), Some(0), Some(1)])
+		);
+		let path = FragmentPath::from([Some(0), Some(19), None, None]);
+		assert_eq!(
+			path.increment_with(true, free),
+			Err(FragmentPathError::IndexOverflow)
+		);
+		assert_eq!(
+			path.pop_and_increment_with(true, free).unwrap(),
+			FragmentPath::from([Some(1), None, None, None])
+		);
+		let path = FragmentPath::from([Some(1), None, None, None]);
+		assert_eq!(
+			path.append_with(true, free).unwrap(),
+			FragmentPath::from([Some(1), Some(0), None, None])
+		);
+		assert_eq!(
+			path.append_with(false, free).unwrap(),
+			FragmentPath::from([Some(1), Some(0), None, None])
+		);
+		let path = FragmentPath::from([Some(0), None, None, None]);
+		assert_eq!(
+			path.append_with(false, free).unwrap(),
+			FragmentPath::from([Some(0), Some(1), None, None])
+		);
+	}
 
-////////////////////////////////////////////////////////////////////////////////
-//                                   Tests.                                   //
-////////////////////////////////////////////////////////////////////////////////
+	/// Ensure that the solver finds words that reuse a fragment only when
+	/// the [configuration](SolverConfig) allows repeats.
+	#[test]
+	fn test_solver_repeats()
+	{
+		let dictionary = Rc::new(
+			["banana", "ban", "nana"].into_iter().collect::<Dictionary>()
+		);
+		let mut fragments = [Fragment::from("x"); 20];
+		fragments[0] = Fragment::from("ba");
+		fragments[1] = Fragment::from("na");
+		let distinct = Solver::new(Rc::clone(&dictionary), fragments)
+			.solve_fully()
+			.solution_deduped();
+		assert!(distinct.is_empty());
+		let repeated = Solver::builder()
+			.dictionary(dictionary)
+			.fragments(fragments)
+			.allow_repeats(true)
+			.build()
+			.unwrap()
+			.solve_fully();
+		assert!(repeated.config().allow_repeats);
+		let mut solution = repeated.solution_deduped();
+		solution.sort();
+		assert_eq!(solution, ["banana", "nana"].map(Word::from));
+	}
 
-#[cfg(test)]
-mod test
-{
-	use std::{collections::HashSet, rc::Rc};
-	use crate::{
-		dictionary::Dictionary,
-		solver::{FragmentPath, FragmentPathError, Solver}
-	};
-	use fixedstr::{str32, str8};
+	/// Ensure that the adjacency rules admit exactly the neighbors of a
+	/// fragment on the grid, and that appending and incrementing respect them.
+	#[test]
+	fn test_adjacency()
+	{
+		// Index 5 is the second fragment of the second row.
+		let neighbors = |adjacency: Adjacency| {
+			(0..20)
+				.filter(|&next| adjacency.admits(Some(5), next))
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(
+			neighbors(Adjacency::Unconstrained),
+			(0..20).collect::<Vec<_>>()
+		);
+		assert_eq!(neighbors(Adjacency::Orthogonal), vec![1, 4, 6, 9]);
+		assert_eq!(
+			neighbors(Adjacency::Diagonal),
+			vec![0, 1, 2, 4, 6, 8, 9, 10]
+		);
+		// Any fragment may begin a word, and rows don't wrap around.
+		assert!(Adjacency::Orthogonal.admits(None, 19));
+		assert!(Adjacency::Diagonal.admits(Some(19), 14));
+		assert!(!Adjacency::Orthogonal.admits(Some(19), 14));
+		assert!(!Adjacency::Diagonal.admits(Some(3), 4));
 
-	/// Ensure that appending a fragment index to a fragment path works for all
-	/// interesting cases.
+		let path = FragmentPath::from([Some(5), None, None, None]);
+		let path = path
+			.append_with(false, Adjacency::Orthogonal)
+			.unwrap();
+		assert_eq!(path, FragmentPath::from([Some(5), Some(1), None, None]));
+		let path = path
+			.increment_with(false, Adjacency::Orthogonal)
+			.unwrap();
+		assert_eq!(path, FragmentPath::from([Some(5), Some(4), None, None]));
+		let path = FragmentPath::from([Some(5), Some(9), None, None]);
+		assert_eq!(
+			path.increment_with(false, Adjacency::Orthogonal),
+			Err(FragmentPathError::IndexOverflow)
+		);
+		// Both orthogonal neighbors of the corner are used, so the path is
+		// stranded.
+		let path = FragmentPath::from([Some(1), Some(4), Some(0), None]);
+		assert_eq!(
+			path.append_with(false, Adjacency::Orthogonal),
+			Err(FragmentPathError::Stranded)
+		);
+	}
+
+	/// Ensure that the solver only finds words whose consecutive fragments
+	/// are adjacent, according to the [configuration](SolverConfig).
 	#[test]
-	fn test_append()
+	fn test_solver_adjacency()
 	{
-		let path = FragmentPath::default();
-		assert_eq!(path, FragmentPath([None, None, None, None]));
-		assert!(path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
-		let path = path.append().unwrap();
-		assert_eq!(path, FragmentPath([Some(0), None, None, None]));
-		assert!(!path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
-		let path = path.append().unwrap();
-		assert_eq!(path, FragmentPath([Some(0), Some(1), None, None]));
-		assert!(!path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
-		let path = path.append().unwrap();
-		assert_eq!(path, FragmentPath([Some(0), Some(1), Some(2), None]));
-		assert!(!path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
-		let path = path.append().unwrap();
-		assert_eq!(path, FragmentPath([Some(0), Some(1), Some(2), Some(3)]));
-		assert!(!path.is_empty());
-		assert!(path.is_full());
-		assert!(path.is_disjoint());
-		assert_eq!(path.append(), Err(FragmentPathError::Overflow));
+		let dictionary = Rc::new(
+			["bana", "bas", "bat"].into_iter().collect::<Dictionary>()
+		);
+		let mut fragments = [Fragment::from("x"); 20];
+		fragments[0] = Fragment::from("ba");
+		fragments[1] = Fragment::from("na");
+		fragments[5] = Fragment::from("s");
+		fragments[19] = Fragment::from("t");
+		let solve = |adjacency: Adjacency| {
+			let mut solution = Solver::builder()
+				.dictionary(Rc::clone(&dictionary))
+				.fragments(fragments)
+				.adjacency(adjacency)
+				.build()
+				.unwrap()
+				.solve_fully()
+				.solution_deduped();
+			solution.sort();
+			solution
+		};
+		assert_eq!(
+			solve(Adjacency::Unconstrained),
+			["bana", "bas", "bat"].map(Word::from)
+		);
+		assert_eq!(solve(Adjacency::Orthogonal), [Word::from("bana")]);
+		assert_eq!(
+			solve(Adjacency::Diagonal),
+			["bana", "bas"].map(Word::from)
+		);
 	}
 
-	/// Ensure that popping a fragment index from a fragment path works for all
-	/// interesting cases.
+	/// Ensure the correctness of the solution to a canonical puzzle. Only give
+	/// the solver 1s to solve the puzzle, which should be sufficient.
 	#[test]
-	fn test_increment()
+	fn test_solver()
 	{
-		let mut path = FragmentPath::default();
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let cases = [
+			(
+				[
+					Fragment::from("azz"),
+					Fragment::from("th"),
+					Fragment::from("ss"),
+					Fragment::from("tru"),
+					Fragment::from("ref"),
+					Fragment::from("fu"),
+					Fragment::from("ra"),
+					Fragment::from("nih"),
+					Fragment::from("cro"),
+					Fragment::from("mat"),
+					Fragment::from("wo"),
+					Fragment::from("sh"),
+					Fragment::from("re"),
+					Fragment::from("rds"),
+					Fragment::from("tic"),
+					Fragment::from("il"),
+					Fragment::from("lly"),
+					Fragment::from("zz"),
+					Fragment::from("is"),
+					Fragment::from("ment")
+				],
+				vec![
+					Word::from("cross"),
+					Word::from("crosswords"),
+					Word::from("fully"),
+					Word::from("fuss"),
+					Word::from("fuzz"),
+					Word::from("is"),
+					Word::from("mat"),
+					Word::from("nihilistic"),
+					Word::from("rail"),
+					Word::from("rally"),
+					Word::from("rare"),
+					Word::from("rash"),
+					Word::from("razz"),
+					Word::from("razzmatazz"),
+					Word::from("recross"),
+					Word::from("ref"),
+					Word::from("refresh"),
+					Word::from("refreshment"),
+					Word::from("rewords"),
+					Word::from("this"),
+					Word::from("thrash"),
+					Word::from("thresh"),
+					Word::from("tic"),
+					Word::from("truss"),
+					Word::from("truth"),
+					Word::from("truthfully"),
+					Word::from("words"),
+					Word::from("wore")
+				]
+			),
+			(
+				[
+					Fragment::from("tab"),
+					Fragment::from("nch"),
+					Fragment::from("ec"),
+					Fragment::from("dis"),
+					Fragment::from("oo"),
+					Fragment::from("per"),
+					Fragment::from("mb"),
+					Fragment::from("ous"),
+					Fragment::from("cour"),
+					Fragment::from("le"),
+					Fragment::from("mar"),
+					Fragment::from("te"),
+					Fragment::from("zle"),
+					Fragment::from("su"),
+					Fragment::from("la"),
+					Fragment::from("ba"),
+					Fragment::from("ket"),
+					Fragment::from("del"),
+					Fragment::from("il"),
+					Fragment::from("chi")
+				],
+				vec![
+					Word::from("bail"),
+					Word::from("bale"),
+					Word::from("bamboo"),
+					Word::from("bamboozle"),
+					Word::from("bate"),
+					Word::from("chi"),
+					Word::from("chinchilla"),
+					Word::from("courteous"),
+					Word::from("delectable"),
+					Word::from("discourteous"),
+					Word::from("diskette"),
+					Word::from("lamb"),
+					Word::from("late"),
+					Word::from("leper"),
+					Word::from("market"),
+					Word::from("per"),
+					Word::from("peril"),
+					Word::from("perilous"),
+					Word::from("super"),
+					Word::from("supermarket"),
+					Word::from("tab"),
+					Word::from("table"),
+					Word::from("taboo")
+				]
+			)
+		];
+		for (fragments, expected) in cases.iter()
+		{
+			let solver = Solver::new(Rc::clone(&dictionary), *fragments);
+			let solver = solver.solve_fully();
+			assert!(solver.is_finished());
+			assert!(solver.is_solved());
+			let mut solution = solver.solution();
+			solution.sort();
+			for word in solution.iter()
+			{
+				assert!(
+					dictionary.contains(word.as_str()),
+					"not in dictionary: {}",
+					word
+				);
+			}
+			let expected = HashSet::<Word>::from_iter(expected.iter().cloned());
+			let solution = HashSet::<Word>::from_iter(solution.iter().cloned());
+			// The solution may contain additional words, so we only check that
+			// the expected words are present. The test dictionary should be
+			// capable enough to find the expected solution.
+			assert!(expected.is_subset(&solution));
+		}
+	}
+
+	/// A word filter that rejects words shorter than 5 characters and doubles
+	/// the score of every accepted word.
+	#[derive(Debug)]
+	struct LongWordFilter;
+
+	impl WordFilter for LongWordFilter
+	{
+		fn accept(&self, word: &str, _path: &FragmentPath) -> bool
+		{
+			word.len() >= 5
+		}
+
+		fn score(&self, _word: &str, path: &FragmentPath) -> Option<u32>
+		{
+			Some(2 << (path.len() - 1))
+		}
+	}
+
+	/// Ensure that the word filter vetoes words and overrides scores.
+	#[test]
+	fn test_filter()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let fragments = [
+			Fragment::from("azz"),
+			Fragment::from("th"),
+			Fragment::from("ss"),
+			Fragment::from("tru"),
+			Fragment::from("ref"),
+			Fragment::from("fu"),
+			Fragment::from("ra"),
+			Fragment::from("nih"),
+			Fragment::from("cro"),
+			Fragment::from("mat"),
+			Fragment::from("wo"),
+			Fragment::from("sh"),
+			Fragment::from("re"),
+			Fragment::from("rds"),
+			Fragment::from("tic"),
+			Fragment::from("il"),
+			Fragment::from("lly"),
+			Fragment::from("zz"),
+			Fragment::from("is"),
+			Fragment::from("ment")
+		];
+		let solver = Solver::new(Rc::clone(&dictionary), fragments);
+		let path = FragmentPath::from([Some(3), Some(1), None, None]);
+		assert_eq!(solver.score(&path), 2);
+		let solver = solver
+			.with_filter(Rc::new(LongWordFilter))
+			.solve_fully();
+		assert!(solver.is_solved());
+		let solution = solver.solution();
+		assert!(solution.iter().all(|word| word.len() >= 5));
+		assert!(solution.contains(&Word::from("truth")));
+		assert!(!solution.contains(&Word::from("is")));
+		assert_eq!(solver.score(&path), 4);
+	}
+
+	/// A word filter that cancels the search upon accepting its first word.
+	#[derive(Debug)]
+	struct CancellingFilter(CancellationToken);
+
+	impl WordFilter for CancellingFilter
+	{
+		fn accept(&self, _word: &str, _path: &FragmentPath) -> bool
+		{
+			self.0.cancel();
+			true
+		}
+	}
+
+	/// Ensure that a cancelled search stops promptly, even when solving
+	/// fully, and that it can be resumed from a checkpoint.
+	#[test]
+	fn test_cancellation()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let fragments = BOARD.map(Fragment::from);
+
+		// Cancellation may come from another thread.
+		let token = CancellationToken::new();
+		let remote = token.clone();
+		std::thread::spawn(move || remote.cancel()).join().unwrap();
+		let solver = Solver::builder()
+			.dictionary(Rc::clone(&dictionary))
+			.fragments(fragments)
+			.cancellation(token)
+			.build()
+			.unwrap()
+			.solve_fully();
+		assert!(solver.is_cancelled());
+		assert!(!solver.is_finished());
+		assert!(solver.solution().is_empty());
+
+		let token = CancellationToken::new();
+		let solver = Solver::new(Rc::clone(&dictionary), fragments)
+			.with_filter(Rc::new(CancellingFilter(token.clone())))
+			.with_cancellation(token)
+			.solve_fully();
+		assert!(solver.is_cancelled());
+		assert!(!solver.is_finished());
+		assert_eq!(solver.solution().len(), 1);
+		let resumed =
+			Solver::from_checkpoint(Rc::clone(&dictionary), solver.checkpoint())
+				.unwrap()
+				.solve_fully();
+		let expected = Solver::new(dictionary, fragments).solve_fully();
+		assert!(resumed.is_solved());
+		assert_eq!(resumed.solution(), expected.solution());
+	}
+
+	/// Ensure that a solve within a budget stops, unfinished, when the budget
+	/// is spent, and otherwise finds the same solution as solving fully.
+	#[test]
+	fn test_solve_within()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let fragments = BOARD.map(Fragment::from);
+		let solver = Solver::new(Rc::clone(&dictionary), fragments)
+			.solve_within(Duration::ZERO);
+		assert!(!solver.is_finished());
+		assert!(solver.solution().is_empty());
+		let solver = Solver::new(Rc::clone(&dictionary), fragments)
+			.solve_within(Duration::from_secs(3600));
+		let expected =
+			Solver::new(Rc::clone(&dictionary), fragments).solve_fully();
+		assert!(solver.is_solved());
+		assert_eq!(solver.solution(), expected.solution());
+		// A budget too large to represent as a deadline is no budget at all.
+		let solver = Solver::new(dictionary, fragments)
+			.solve_within(Duration::from_secs(u64::MAX));
+		assert!(solver.is_solved());
+		assert_eq!(solver.solution(), expected.solution());
+	}
+
+	/// Ensure that the solver folds the fragments of its puzzle for a folding
+	/// dictionary, since the cursor by which it walks the dictionary doesn't.
+	#[test]
+	fn test_folding()
+	{
+		let mut fragments = [Fragment::from("xq"); 20];
+		fragments[0] = Fragment::from("TRU");
+		fragments[1] = Fragment::from("th ");
+		fragments[2] = Fragment::from("Ful");
+		let mut dictionary = ["truth", "truthful"]
+			.into_iter()
+			.collect::<Dictionary>();
+		let solver = Solver::new(Rc::new(dictionary.clone()), fragments)
+			.solve_fully();
+		assert!(solver.solution().is_empty());
+		dictionary.set_folding(true);
+		let solver = Solver::new(Rc::new(dictionary), fragments).solve_fully();
+		let mut words = solver.solution()
+			.iter()
+			.map(|word| word.to_string())
+			.collect::<Vec<_>>();
+		words.sort();
+		assert_eq!(words, ["truth", "truthful"]);
+	}
+
+	/// Ensure that iterating over the words discovers the same solution as
+	/// solving fully, and that the solver can be recovered midway.
+	#[test]
+	fn test_into_words()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let fragments = BOARD.map(Fragment::from);
+		let expected = Solver::new(Rc::clone(&dictionary), fragments)
+			.solve_fully();
+		let words = Solver::new(Rc::clone(&dictionary), fragments)
+			.into_words()
+			.collect::<Vec<_>>();
 		assert_eq!(
-			path.increment(),
-			Err(FragmentPathError::CannotIncrementEmpty)
+			words.iter().map(|(word, _)| *word).collect::<Vec<_>>(),
+			expected.solution()
+		);
+		assert_eq!(
+			words.iter().map(|(_, path)| *path).collect::<Vec<_>>(),
+			expected.solution_paths()
 		);
 
-		path = path.append().unwrap();
-		for i in 0..19
+		let mut words = Solver::new(dictionary, fragments).into_words();
+		assert_eq!(words.by_ref().take(3).count(), 3);
+		assert_eq!(words.solver().solution().len(), 3);
+		let solver = words.into_solver().solve_fully();
+		assert_eq!(solver.solution(), expected.solution());
+	}
+
+	/// Ensure that a solver resumed from a checkpoint, even one that has been
+	/// serialized, produces the same solution as an uninterrupted solver.
+	#[test]
+	fn test_checkpoint()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let fragments = [
+			Fragment::from("tab"),
+			Fragment::from("nch"),
+			Fragment::from("ec"),
+			Fragment::from("dis"),
+			Fragment::from("oo"),
+			Fragment::from("per"),
+			Fragment::from("mb"),
+			Fragment::from("ous"),
+			Fragment::from("cour"),
+			Fragment::from("le"),
+			Fragment::from("mar"),
+			Fragment::from("te"),
+			Fragment::from("zle"),
+			Fragment::from("su"),
+			Fragment::from("la"),
+			Fragment::from("ba"),
+			Fragment::from("ket"),
+			Fragment::from("del"),
+			Fragment::from("il"),
+			Fragment::from("chi")
+		];
+		let expected = Solver::new(Rc::clone(&dictionary), fragments)
+			.solve_fully()
+			.solution();
+		let start_time = Instant::now();
+		let mut solver = Solver::new(Rc::clone(&dictionary), fragments);
+		assert_eq!(solver.max_overrun(), Duration::ZERO);
+		for _ in 0..10
 		{
-			assert_eq!(path, FragmentPath([Some(i), None, None, None]));
-			assert!(!path.is_empty());
-			assert!(!path.is_full());
-			assert!(path.is_disjoint());
-			path = path.increment().unwrap();
+			solver = solver.solve(Duration::ZERO).0;
 		}
-		assert_eq!(path, FragmentPath([Some(19), None, None, None]));
-		assert!(!path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
-		assert_eq!(path.increment(), Err(FragmentPathError::IndexOverflow));
+		assert!(solver.max_overrun() <= start_time.elapsed());
+		let checkpoint = solver.checkpoint();
+		assert!(checkpoint.is_valid());
+		assert!(!checkpoint.is_finished());
+		assert_eq!(checkpoint.fragments(), fragments);
+		let bytes = bincode::serialize(&checkpoint).unwrap();
+		let checkpoint = bincode::deserialize::<Checkpoint>(&bytes).unwrap();
+		let solver = Solver::from_checkpoint(Rc::clone(&dictionary), checkpoint)
+			.unwrap()
+			.solve_fully();
+		assert!(solver.is_solved());
+		assert_eq!(solver.solution(), expected);
 
-		path = path.append().unwrap();
-		for i in 0..18
+		// A solver that allows repeats checkpoints fragment paths that reuse
+		// fragments, and resumes from them.
+		let config = SolverConfig {
+			allow_repeats: true,
+			..Default::default()
+		};
+		let expected = Solver::new(Rc::clone(&dictionary), fragments)
+			.with_config(config)
+			.solve_fully()
+			.solution();
+		let mut solver = Solver::new(Rc::clone(&dictionary), fragments)
+			.with_config(config);
+		while solver.checkpoint().path.is_disjoint() && !solver.is_finished()
 		{
-			assert_eq!(path, FragmentPath([Some(19), Some(i), None, None]));
-			assert!(!path.is_empty());
-			assert!(!path.is_full());
-			assert!(path.is_disjoint());
-			path = path.increment().unwrap();
+			solver = solver.solve(Duration::ZERO).0;
 		}
-		assert_eq!(path, FragmentPath([Some(19), Some(18), None, None]));
-		assert!(!path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
-		assert_eq!(path.increment(), Err(FragmentPathError::IndexOverflow));
+		let checkpoint = solver.checkpoint();
+		assert!(!checkpoint.path.is_disjoint());
+		assert!(checkpoint.is_valid());
+		assert!(checkpoint.config().allow_repeats);
+		let solver = Solver::from_checkpoint(Rc::clone(&dictionary), checkpoint)
+			.unwrap()
+			.solve_fully();
+		assert_eq!(solver.solution(), expected);
+
+		// The configuration is restored from the checkpoint, so that the
+		// resumed search obeys the same rules.
+		let config = SolverConfig { min_len: 8, ..Default::default() };
+		let checkpoint = Solver::new(Rc::clone(&dictionary), fragments)
+			.with_config(config)
+			.solve(Duration::ZERO)
+			.0
+			.checkpoint();
+		assert_eq!(checkpoint.config(), config);
+		let bytes = bincode::serialize(&checkpoint).unwrap();
+		let checkpoint = bincode::deserialize::<Checkpoint>(&bytes).unwrap();
+		let resumed =
+			Solver::from_checkpoint(Rc::clone(&dictionary), checkpoint).unwrap();
+		assert_eq!(*resumed.config(), config);
+
+		// A checkpoint is refused by a different dictionary.
+		let other = ["tabled"].into_iter().collect::<Dictionary>();
+		assert_eq!(
+			Solver::from_checkpoint(Rc::new(other), solver.checkpoint()).err(),
+			Some(CheckpointError::DictionaryMismatch)
+		);
+
+		// A corrupt checkpoint is refused rather than resumed.
+		let mut checkpoint = solver.checkpoint();
+		checkpoint.config.allow_repeats = false;
+		checkpoint.path = FragmentPath::from([Some(0), Some(0), None, None]);
+		assert!(!checkpoint.is_valid());
+		assert_eq!(
+			Solver::from_checkpoint(dictionary, checkpoint).err(),
+			Some(CheckpointError::Invalid)
+		);
+	}
+
+	/// Ensure that duplicate words are retained or dropped according to the
+	/// duplicate policy, and that deduplication preserves discovery order.
+	#[test]
+	fn test_duplicates()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let fragments = ["tru", "th"]
+			.repeat(10)
+			.iter()
+			.map(|f| Fragment::from(*f))
+			.collect::<Vec<_>>();
+		let fragments: [Fragment; 20] = fragments.try_into().unwrap();
+		let solver = Solver::new(Rc::clone(&dictionary), fragments)
+			.solve_fully();
+		let truth = Word::from("truth");
+		let all = solver.solution();
+		assert!(all.iter().filter(|word| **word == truth).count() > 1);
+		let deduped = solver.solution_deduped();
+		assert_eq!(deduped.iter().filter(|word| **word == truth).count(), 1);
+		assert_eq!(
+			deduped.iter().collect::<HashSet<_>>().len(),
+			deduped.len()
+		);
+
+		let solver = Solver::new(Rc::clone(&dictionary), fragments)
+			.with_duplicate_policy(DuplicatePolicy::KeepFirst)
+			.solve_fully();
+		assert_eq!(solver.solution(), deduped);
+		assert_eq!(solver.solution_deduped(), deduped);
+		assert_eq!(solver.paths_for_word("truth").len(), 1);
+	}
+
+	/// Ensure that the length constraints of the [`SolverConfig`] are
+	/// enforced, both when accepting words and when pruning the search.
+	#[test]
+	fn test_length_constraints()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let fragments = BOARD.map(Fragment::from);
+		let unconstrained = Solver::new(Rc::clone(&dictionary), fragments)
+			.solve_fully()
+			.solution();
+		assert!(unconstrained.iter().any(|word| word.len() < 3));
+		assert!(unconstrained.iter().any(|word| word.len() > 6));
+
+		let config = SolverConfig {
+			min_len: 3,
+			max_len: 6,
+			..Default::default()
+		};
+		let solver = Solver::new(Rc::clone(&dictionary), fragments)
+			.with_config(config)
+			.solve_fully();
+		assert_eq!(solver.config(), &config);
+		let expected = unconstrained
+			.iter()
+			.filter(|word| (3..=6).contains(&word.len()))
+			.copied()
+			.collect::<Vec<_>>();
+		assert_eq!(solver.solution(), expected);
+		assert!(!solver.is_solved());
+	}
+
+	/// Ensure that suffix pruning never changes the solution, with or without
+	/// repeated fragments, and that neither does the letter precheck.
+	#[test]
+	fn test_prune_suffixes()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let fragments = BOARD.map(Fragment::from);
+		for allow_repeats in [false, true]
+		{
+			let solve = |prune_suffixes| {
+				Solver::builder()
+					.dictionary(Rc::clone(&dictionary))
+					.fragments(fragments)
+					.allow_repeats(allow_repeats)
+					.prune_suffixes(prune_suffixes)
+					.build()
+					.unwrap()
+					.solve_fully()
+					.solution()
+			};
+			assert_eq!(solve(true), solve(false));
+		}
+
+		// Nor does the letter precheck.
+		let solve = |letter_precheck| {
+			Solver::builder()
+				.dictionary(Rc::clone(&dictionary))
+				.fragments(fragments)
+				.letter_precheck(letter_precheck)
+				.build()
+				.unwrap()
+				.solve_fully()
+				.solution()
+		};
+		assert_eq!(solve(true), solve(false));
+
+		// A fragment that ends no word never completes a quartile.
+		let dictionary = Rc::new(
+			["abcd", "abcdx"].into_iter().collect::<Dictionary>()
+		);
+		let fragments = ["a", "b", "c", "d"]
+			.into_iter()
+			.chain(["x"; 16])
+			.map(Fragment::from)
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+		let solver = Solver::new(dictionary, fragments).solve_fully();
+		assert_eq!(solver.solution(), [Word::from("abcd")]);
+		assert!(solver.enders.get().is_some_and(|enders| enders & 1 == 0));
+	}
+
+	/// Ensure that the metrics count the work of the solver, and that each
+	/// fragment path considered is either a prefix hit or a prefix miss.
+	#[test]
+	fn test_metrics()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let fragments = BOARD.map(Fragment::from);
+		let solver = Solver::new(Rc::clone(&dictionary), fragments);
+		assert_eq!(solver.metrics(), &SolverMetrics::default());
+		assert_eq!(solver.metrics().mean_quantum(), Duration::ZERO);
+		let solver = solver.solve_fully();
+		let metrics = *solver.metrics();
+		assert!(metrics.paths_expanded > 0);
+		assert_eq!(
+			metrics.prefix_hits + metrics.prefix_misses,
+			metrics.paths_expanded
+		);
+		assert!(metrics.prefix_misses > 0);
+		assert!(metrics.probes > 0);
+		assert!(metrics.quanta > solver.solution().len() as u64);
+		assert!(metrics.max_quantum <= metrics.elapsed);
+		assert!(metrics.mean_quantum() <= metrics.max_quantum);
+		assert!(metrics.to_string().contains("Paths expanded:"));
 
-		path = path.append().unwrap();
-		for i in 0..17
-		{
-			assert_eq!(path, FragmentPath([Some(19), Some(18), Some(i), None]));
-			assert!(!path.is_empty());
-			assert!(!path.is_full());
-			assert!(path.is_disjoint());
-			path = path.increment().unwrap();
-		}
-		assert_eq!(path, FragmentPath([Some(19), Some(18), Some(17), None]));
-		assert!(!path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
-		assert_eq!(path.increment(), Err(FragmentPathError::IndexOverflow));
+		// The letter precheck spares some searches of the dictionary.
+		let prechecked = Solver::builder()
+			.dictionary(dictionary)
+			.fragments(fragments)
+			.letter_precheck(true)
+			.build()
+			.unwrap()
+			.solve_fully();
+		let prechecked = *prechecked.metrics();
+		assert_eq!(prechecked.paths_expanded, metrics.paths_expanded);
+		assert!(prechecked.probes < metrics.probes);
+	}
 
-		path = path.append().unwrap();
-		for i in 0..16
+	/// Ensure that the longest possible candidate word, comprising 4 of the
+	/// largest fragments, is found intact rather than truncated.
+	#[test]
+	fn test_long_word()
+	{
+		let pieces = ["a", "b", "c", "d"].map(|c| c.repeat(MAX_FRAGMENT_BYTES));
+		let long = pieces.concat();
+		assert_eq!(long.len(), 4 * MAX_FRAGMENT_BYTES);
+		let dictionary =
+			Rc::new([long.as_str()].into_iter().collect::<Dictionary>());
+		let mut fragments = [Fragment::from("x"); 20];
+		for (index, fragment) in pieces.iter().enumerate()
 		{
-			assert_eq!(
-				path,
-				FragmentPath([Some(19), Some(18), Some(17), Some(i)])
-			);
-			assert!(!path.is_empty());
-			assert!(path.is_full());
-			assert!(path.is_disjoint());
-			path = path.increment().unwrap();
+			fragments[index] = parse_fragment(fragment).unwrap();
 		}
+		let solution = Solver::new(dictionary, fragments)
+			.solve_fully()
+			.solution();
+		assert_eq!(solution, vec![Word::from(&long)]);
+		assert_eq!(solution[0].len(), long.len());
+	}
+
+	/// Ensure that fragments that fit are accepted intact, and that longer
+	/// ones are refused rather than truncated.
+	#[test]
+	fn test_parse_fragment()
+	{
+		let longest = "é".repeat(MAX_FRAGMENT_BYTES / 2);
+		assert_eq!(parse_fragment(&longest).unwrap().as_str(), longest);
+		assert_eq!(parse_fragment("").unwrap(), Fragment::new());
+		let long = "é".repeat(MAX_FRAGMENT_BYTES / 2 + 1);
 		assert_eq!(
-			path,
-			FragmentPath([Some(19), Some(18), Some(17), Some(16)])
+			parse_fragment(&long),
+			Err(FragmentError::TooLong { fragment: long.clone() })
 		);
-		assert!(!path.is_empty());
-		assert!(path.is_full());
-		assert!(path.is_disjoint());
-		assert_eq!(path.increment(), Err(FragmentPathError::IndexOverflow));
+		assert!(parse_fragment(&long)
+			.unwrap_err()
+			.to_string()
+			.starts_with("fragment too long"));
+	}
 
-		path = FragmentPath([Some(1), Some(2), Some(3), None]);
-		path = path.append().unwrap();
-		assert_eq!(path, FragmentPath([Some(1), Some(2), Some(3), Some(0)]));
-		assert!(!path.is_empty());
-		assert!(path.is_full());
-		assert!(path.is_disjoint());
+	/// Ensure that the [builder](Solver::builder) requires the dictionary and
+	/// the fragments, and that it configures the solver.
+	#[test]
+	fn test_builder()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let fragments = BOARD.map(Fragment::from);
+		assert_eq!(
+			Solver::builder().fragments(fragments).build().unwrap_err(),
+			SolverBuilderError::MissingDictionary
+		);
 		assert_eq!(
-			path.increment().unwrap(),
-			FragmentPath([Some(1), Some(2), Some(3), Some(4)])
+			Solver::builder()
+				.dictionary(Rc::clone(&dictionary))
+				.build()
+				.unwrap_err(),
+			SolverBuilderError::MissingFragments
 		);
 
-		path = FragmentPath([Some(1), Some(19), Some(3), None]);
-		path = path.append().unwrap();
-		assert_eq!(path, FragmentPath([Some(1), Some(19), Some(3), Some(0)]));
-		assert!(!path.is_empty());
-		assert!(path.is_full());
-		assert!(path.is_disjoint());
-		path = path.increment().unwrap();
+		let solver = Solver::builder()
+			.dictionary(Rc::clone(&dictionary))
+			.fragments(fragments)
+			.quartiles_only(true)
+			.min_len(3)
+			.time_quantum(Duration::from_millis(10))
+			.build()
+			.unwrap();
 		assert_eq!(
-			path,
-			FragmentPath([Some(1), Some(19), Some(3), Some(2)])
+			solver.config(),
+			&SolverConfig {
+				min_len: 3,
+				quartiles_only: true,
+				time_quantum: Duration::from_millis(10),
+				..Default::default()
+			}
 		);
-		path = path.increment().unwrap();
-		for i in 4..18
-		{
-			assert_eq!(
-				path,
-				FragmentPath([Some(1), Some(19), Some(3), Some(i)])
-			);
-			assert!(!path.is_empty());
-			assert!(path.is_full());
-			assert!(path.is_disjoint());
-			path = path.increment().unwrap();
-		}
+		let solver = solver.solve_fully();
+		assert!(solver.is_solved());
+		let mut solution = solver.solution();
+		solution.sort();
 		assert_eq!(
-			path,
-			FragmentPath([Some(1), Some(19), Some(3), Some(18)])
+			solution,
+			[
+				"crosswords",
+				"nihilistic",
+				"razzmatazz",
+				"refreshment",
+				"truthfully"
+			].map(Word::from)
 		);
-		assert!(!path.is_empty());
-		assert!(path.is_full());
-		assert!(path.is_disjoint());
-		assert_eq!(path.increment(), Err(FragmentPathError::IndexOverflow));
 	}
 
-	/// Ensure that popping a fragment index from a fragment path works for all
-	/// interesting cases.
+	/// Ensure that the [progress](Solver::progress) estimate starts at zero,
+	/// never decreases, and finishes at one.
 	#[test]
-	fn test_pop()
+	fn test_progress()
 	{
-		let path = FragmentPath::default();
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let fragments = BOARD.map(Fragment::from);
+		let mut solver = Solver::new(dictionary, fragments);
+		assert_eq!(solver.progress(), 0.0);
+		let mut last = 0.0;
+		while !solver.is_finished()
+		{
+			solver = solver.solve(Duration::ZERO).0;
+			let progress = solver.progress();
+			assert!((last..=1.0).contains(&progress), "{} < {}", progress, last);
+			last = progress;
+		}
+		assert_eq!(solver.progress(), 1.0);
+	}
+
+	/// Ensure that every alternative fragment path of a word is reported.
+	#[test]
+	fn test_paths_for_word()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let fragments = ["tru", "th"]
+			.repeat(10)
+			.iter()
+			.map(|f| Fragment::from(*f))
+			.collect::<Vec<_>>();
+		let fragments: [Fragment; 20] = fragments.try_into().unwrap();
+		let solver = Solver::new(Rc::clone(&dictionary), fragments)
+			.solve_fully();
+		let paths = solver.paths_for_word("truth");
+		// Every "tru" (even index) can precede every "th" (odd index).
+		assert_eq!(paths.len(), 100);
+		assert!(paths.iter().all(|path| solver.word(path).as_str() == "truth"));
 		assert_eq!(
-			path.pop(),
-			Err(FragmentPathError::Underflow)
+			paths.iter().collect::<HashSet<_>>().len(),
+			paths.len()
 		);
+		assert!(solver.paths_for_word("xyzzy").is_empty());
+	}
 
-		let path = path.append().unwrap();
-		let path = path.append().unwrap();
-		let path = path.append().unwrap();
-		let path = path.append().unwrap();
-		assert_eq!(path, FragmentPath([Some(0), Some(1), Some(2), Some(3)]));
-		assert!(!path.is_empty());
-		assert!(path.is_full());
-		assert!(path.is_disjoint());
-		let path = path.pop().unwrap();
-		assert_eq!(path, FragmentPath([Some(0), Some(1), Some(2), None]));
-		assert!(!path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
-		let path = path.pop().unwrap();
-		assert_eq!(path, FragmentPath([Some(0), Some(1), None, None]));
-		assert!(!path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
-		let path = path.pop().unwrap();
-		assert_eq!(path, FragmentPath([Some(0), None, None, None]));
-		assert!(!path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
-		let path = path.pop().unwrap();
-		assert_eq!(path, FragmentPath([None, None, None, None]));
-		assert!(path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
+	/// Ensure that retracting a word removes every fragment path that forms
+	/// it, and nothing else.
+	#[test]
+	fn test_retract()
+	{
+		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
+		let fragments = ["tru", "th"]
+			.repeat(10)
+			.iter()
+			.map(|f| Fragment::from(*f))
+			.collect::<Vec<_>>();
+		let fragments: [Fragment; 20] = fragments.try_into().unwrap();
+		let mut solver = Solver::new(dictionary, fragments).solve_fully();
+		let words = solver.solution_deduped();
+		assert!(solver.retract("truth"));
+		assert!(!solver.retract("truth"));
+		assert!(solver.paths_for_word("truth").is_empty());
+		assert_eq!(solver.solution_deduped().len(), words.len() - 1);
 	}
 
-	/// Ensure that popping and incrementing a fragment path works for all
-	/// interesting cases.
+	/// Ensure that lookup finds every word formable from up to the maximum
+	/// number of fragments, using each fragment at most once.
 	#[test]
-	fn test_pop_and_increment()
+	fn test_lookup()
 	{
-		let path = FragmentPath::default();
+		let mut dictionary = Dictionary::new();
+		dictionary.populate(&["is", "this", "truth", "truths", "thistle", "tt"]);
+		let fragments = ["tru", "th", "is", "s", "t"];
 		assert_eq!(
-			path.pop_and_increment(),
-			Err(FragmentPathError::Underflow)
+			lookup(&dictionary, &fragments, 4),
+			vec!["is", "this", "truth", "truths"]
 		);
-
-		let path = path.append().unwrap();
-		let path = path.append().unwrap();
-		let path = path.append().unwrap();
-		let path = path.append().unwrap();
-		assert_eq!(path, FragmentPath([Some(0), Some(1), Some(2), Some(3)]));
-		assert!(!path.is_empty());
-		assert!(path.is_full());
-		assert!(path.is_disjoint());
-		let path = path.pop_and_increment().unwrap();
-		assert_eq!(path, FragmentPath([Some(0), Some(1), Some(3), None]));
-		assert!(!path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
-		let path = path.pop_and_increment().unwrap();
-		assert_eq!(path, FragmentPath([Some(0), Some(2), None, None]));
-		assert!(!path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
-		let path = path.pop_and_increment().unwrap();
-		assert_eq!(path, FragmentPath([Some(1), None, None, None]));
-		assert!(!path.is_empty());
-		assert!(!path.is_full());
-		assert!(path.is_disjoint());
 		assert_eq!(
-			path.pop_and_increment(),
-			Err(FragmentPathError::CannotIncrementEmpty)
+			lookup(&dictionary, &fragments, 2),
+			vec!["is", "this", "truth"]
 		);
+		assert!(lookup(&dictionary, &fragments, 0).is_empty());
+		assert_eq!(lookup(&dictionary, &["t", "t"], 4), vec!["tt"]);
+	}
 
-		let path = FragmentPath([Some(19), Some(18), Some(17), Some(16)]);
+	/// Ensure that every reason for a missing word is explained, and that the
+	/// closest formable words are suggested.
+	#[test]
+	fn test_explain()
+	{
+		let long = "supercalifragilisticexpialidocious\
+			pneumonoultramicroscopicsilicovolcanoconiosis";
+		assert!(long.len() > MAX_WORD_BYTES);
+		let mut dictionary = Dictionary::new();
+		dictionary.populate(&["is", "this", "truth", "truths", "thistle", long]);
+		let fragments = [
+			"tru", "th", "is", "s", "t", "supercalifragilisticexpialidocious",
+			"pneumonoultramicroscopicsilicovolcanoconiosis"
+		];
 		assert_eq!(
-			path.pop_and_increment(),
-			Err(FragmentPathError::CannotIncrementEmpty)
+			explain(&dictionary, &fragments, "truthy"),
+			Explanation::NotInDictionary {
+				near: vec!["truth".to_string(), "truths".into()]
+			}
 		);
-
-		let path = FragmentPath([Some(18), Some(17), Some(16), Some(15)]);
-		let path = path.pop_and_increment().unwrap();
-		assert_eq!(path, FragmentPath([Some(18), Some(17), Some(19), None]));
-		let path = path.pop_and_increment().unwrap();
-		assert_eq!(path, FragmentPath([Some(18), Some(19), None, None]));
-		let path = path.pop_and_increment().unwrap();
-		assert_eq!(path, FragmentPath([Some(19), None, None, None]));
 		assert_eq!(
-			path.pop_and_increment(),
-			Err(FragmentPathError::CannotIncrementEmpty)
+			explain(&dictionary, &fragments, "truthy").to_string(),
+			"not in the dictionary; did you mean truth, truths?"
+		);
+		assert_eq!(
+			explain(&dictionary, &fragments, "xylophone").to_string(),
+			"not in the dictionary"
+		);
+		assert_eq!(
+			explain(&dictionary, &fragments, "thistle"),
+			Explanation::NotFormable
+		);
+		assert_eq!(
+			explain(&dictionary, &fragments, long),
+			Explanation::TooLong { len: long.len() }
+		);
+		assert_eq!(
+			explain(&dictionary, &fragments, "truths"),
+			Explanation::Formable {
+				paths: vec![vec!["tru".to_string(), "th".into(), "s".into()]]
+			}
+		);
+		assert_eq!(
+			explain(&dictionary, &fragments, "truths").to_string(),
+			"formable as tru·th·s"
+		);
+		assert_eq!(
+			closest_words(&dictionary, &fragments, "thistle", 2),
+			vec!["this", "is"]
 		);
+		assert_eq!(edit_distance("kitten", "sitting"), 3);
+		assert_eq!(edit_distance("", "abc"), 3);
 	}
 
-	/// Ensure that the disjointedness of fragment paths is correctly
-	/// determined. Be exhaustive, since it's cheap and the space is easy to
-	/// enumerate.
+	/// Ensure that the analysis of an incomplete solution reports the unused
+	/// fragments, the quartiles found, and the near misses.
 	#[test]
-	fn test_is_disjoint()
+	fn test_completeness()
 	{
-		let path = FragmentPath::default();
-		assert!(path.is_disjoint());
-
-		for i in 0..20
-		{
-			let path = FragmentPath([Some(i), None, None, None]);
-			assert!(path.is_disjoint());
-		}
-
-		for i in 0..20
+		let dictionary = Rc::new(
+			["crossword", "understanding", "understandings"]
+				.into_iter()
+				.collect::<Dictionary>()
+		);
+		let mut fragments = [Fragment::from("x"); 20];
+		for (index, fragment) in
+			["cr", "oss", "wo", "rd", "un", "der", "sta", "nd"]
+				.into_iter()
+				.enumerate()
 		{
-			for j in 0..20
-			{
-				let path = FragmentPath([Some(i), Some(j), None, None]);
-				assert_eq!(path.is_disjoint(), i != j, "{}, {}", i, j);
-			}
+			fragments[index] = Fragment::from(fragment);
 		}
+		let solver = Solver::new(dictionary, fragments).solve_fully();
+		assert!(!solver.is_solved());
+		let completeness = solver.completeness(5);
+		assert!(!completeness.is_complete());
+		assert_eq!(completeness.quartiles, 1);
+		assert_eq!(completeness.unused.len(), 16);
+		assert_eq!(completeness.unused[..4], ["un", "der", "sta", "nd"]);
+		assert_eq!(
+			completeness.near_misses,
+			vec![NearMiss {
+				fragments: vec![
+					"un".to_string(),
+					"der".into(),
+					"sta".into(),
+					"nd".into()
+				],
+				unused: 4,
+				completion: Some("understanding".to_string())
+			}]
+		);
+		assert_eq!(
+			completeness.near_misses[0].to_string(),
+			"un·der·sta·nd (cf. understanding)"
+		);
+		assert!(solver.completeness(0).near_misses.is_empty());
+	}
 
-		for i in 0..20
-		{
-			for j in 0..20
-			{
-				for k in 0..20
-				{
-					let path = FragmentPath([Some(i), Some(j), Some(k), None]);
-					assert_eq!(
-						path.is_disjoint(),
-						i != j && i != k && j != k,
-						"{}, {}, {}", i, j, k
-					);
-				}
-			}
-		}
+	/// Ensure that an exact cover is selected from more than 5 quartiles,
+	/// even when a quartile found first must be abandoned.
+	#[test]
+	fn test_cover()
+	{
+		let fragments = [
+			"ab", "ac", "ad", "af", "ag", "ah", "aj", "ak", "al", "am",
+			"an", "ap", "aq", "ar", "as", "at", "av", "aw", "ax", "ay"
+		].map(Fragment::from);
+		let quartile = |indices: [usize; 4]| {
+			indices.iter()
+				.map(|&index| fragments[index].as_str())
+				.collect::<String>()
+		};
+		// The decoy covers the first row, but the others cover the columns
+		// of the first 4 rows, so the decoy cannot belong to a cover.
+		let decoy = quartile([0, 1, 2, 3]);
+		let intended = [
+			[0, 4, 8, 12],
+			[1, 5, 9, 13],
+			[2, 6, 10, 14],
+			[3, 7, 11, 15],
+			[16, 17, 18, 19]
+		];
+		let dictionary = Rc::new(
+			intended.iter()
+				.map(|&indices| quartile(indices))
+				.chain([decoy])
+				.collect::<Dictionary>()
+		);
+		let solver = Solver::new(Rc::clone(&dictionary), fragments)
+			.solve_fully();
+		assert!(solver.is_solved());
+		assert_eq!(solver.solution_paths().len(), 6);
+		let cover = solver.cover().unwrap();
+		assert_eq!(
+			cover.iter()
+				.map(|path| solver.word(path).to_string())
+				.collect::<Vec<_>>(),
+			intended.map(quartile)
+		);
 
-		for i in 0..20
-		{
-			for j in 0..20
-			{
-				for k in 0..20
-				{
-					for l in 0..20
-					{
-						let path =
-							FragmentPath([Some(i), Some(j), Some(k), Some(l)]);
-						assert_eq!(
-							path.is_disjoint(),
-							i != j && i != k && i != l
-								&& j != k && j != l
-								&& k != l,
-							"{}, {}, {}, {}", i, j, k, l
-						);
-					}
-				}
-			}
-		}
+		// Without the last quartile, no cover exists.
+		let mut dictionary = (*dictionary).clone();
+		dictionary.exclude([quartile([16, 17, 18, 19])]);
+		let solver = Solver::new(Rc::new(dictionary), fragments).solve_fully();
+		assert_eq!(solver.cover(), None);
 	}
 
-	/// Ensure the correctness of the solution to a canonical puzzle. Only give
-	/// the solver 1s to solve the puzzle, which should be sufficient.
+	/// Ensure that a sound puzzle is verified, and that every problem with an
+	/// unsound one is reported.
 	#[test]
-	fn test_solver()
+	fn test_verify()
 	{
-		let dictionary = Rc::new(Dictionary::open("dict", "english").unwrap());
-		let cases = [
-			(
-				[
-					str8::from("azz"),
-					str8::from("th"),
-					str8::from("ss"),
-					str8::from("tru"),
-					str8::from("ref"),
-					str8::from("fu"),
-					str8::from("ra"),
-					str8::from("nih"),
-					str8::from("cro"),
-					str8::from("mat"),
-					str8::from("wo"),
-					str8::from("sh"),
-					str8::from("re"),
-					str8::from("rds"),
-					str8::from("tic"),
-					str8::from("il"),
-					str8::from("lly"),
-					str8::from("zz"),
-					str8::from("is"),
-					str8::from("ment")
-				],
-				vec![
-					str32::from("cross"),
-					str32::from("crosswords"),
-					str32::from("fully"),
-					str32::from("fuss"),
-					str32::from("fuzz"),
-					str32::from("is"),
-					str32::from("mat"),
-					str32::from("nihilistic"),
-					str32::from("rail"),
-					str32::from("rally"),
-					str32::from("rare"),
-					str32::from("rash"),
-					str32::from("razz"),
-					str32::from("razzmatazz"),
-					str32::from("recross"),
-					str32::from("ref"),
-					str32::from("refresh"),
-					str32::from("refreshment"),
-					str32::from("rewords"),
-					str32::from("this"),
-					str32::from("thrash"),
-					str32::from("thresh"),
-					str32::from("tic"),
-					str32::from("truss"),
-					str32::from("truth"),
-					str32::from("truthfully"),
-					str32::from("words"),
-					str32::from("wore")
-				]
-			),
-			(
-				[
-					str8::from("tab"),
-					str8::from("nch"),
-					str8::from("ec"),
-					str8::from("dis"),
-					str8::from("oo"),
-					str8::from("per"),
-					str8::from("mb"),
-					str8::from("ous"),
-					str8::from("cour"),
-					str8::from("le"),
-					str8::from("mar"),
-					str8::from("te"),
-					str8::from("zle"),
-					str8::from("su"),
-					str8::from("la"),
-					str8::from("ba"),
-					str8::from("ket"),
-					str8::from("del"),
-					str8::from("il"),
-					str8::from("chi")
-				],
-				vec![
-					str32::from("bail"),
-					str32::from("bale"),
-					str32::from("bamboo"),
-					str32::from("bamboozle"),
-					str32::from("bate"),
-					str32::from("chi"),
-					str32::from("chinchilla"),
-					str32::from("courteous"),
-					str32::from("delectable"),
-					str32::from("discourteous"),
-					str32::from("diskette"),
-					str32::from("lamb"),
-					str32::from("late"),
-					str32::from("leper"),
-					str32::from("market"),
-					str32::from("per"),
-					str32::from("peril"),
-					str32::from("perilous"),
-					str32::from("super"),
-					str32::from("supermarket"),
-					str32::from("tab"),
-					str32::from("table"),
-					str32::from("taboo")
-				]
-			)
+		let dictionary = Dictionary::open("dict", "english").unwrap();
+		let fragments = BOARD;
+		let words = [
+			"truthfully", "razzmatazz", "crosswords", "nihilistic",
+			"refreshment"
 		];
-		for (fragments, expected) in cases.iter()
-		{
-			let solver = Solver::new(Rc::clone(&dictionary), *fragments);
-			let solver = solver.solve_fully();
-			assert!(solver.is_finished());
-			assert!(solver.is_solved());
-			let mut solution = solver.solution();
-			solution.sort();
-			for word in solution.iter()
-			{
-				assert!(
-					dictionary.contains(word.as_str()),
-					"not in dictionary: {}",
-					word
-				);
-			}
-			let expected = HashSet::<str32>::from_iter(expected.iter().cloned());
-			let solution = HashSet::<str32>::from_iter(solution.iter().cloned());
-			// The solution may contain additional words, so we only check that
-			// the expected words are present. The test dictionary should be
-			// capable enough to find the expected solution.
-			assert!(expected.is_subset(&solution));
-		}
+		let partition = verify(&dictionary, &fragments, &words).unwrap();
+		assert_eq!(partition[0], ["tru", "th", "fu", "lly"]);
+		assert_eq!(partition[4], ["ref", "re", "sh", "ment"]);
+		assert_eq!(
+			verify(&dictionary, &fragments[..19], &words),
+			Err(VerificationError::FragmentCount(19))
+		);
+		assert_eq!(
+			verify(&dictionary, &fragments, &words[..4]),
+			Err(VerificationError::WordCount(4))
+		);
+		let mut unsound = words;
+		unsound[0] = "truthfuly";
+		assert_eq!(
+			verify(&dictionary, &fragments, &unsound),
+			Err(VerificationError::NotInDictionary("truthfuly".to_string()))
+		);
+		unsound[0] = "truth";
+		assert_eq!(
+			verify(&dictionary, &fragments, &unsound),
+			Err(VerificationError::NotQuartile("truth".to_string()))
+		);
+		unsound[0] = "razzmatazz";
+		assert_eq!(
+			verify(&dictionary, &fragments, &unsound),
+			Err(VerificationError::NoPartition)
+		);
 	}
 }
@@ -0,0 +1,193 @@
+//! # State file
+//!
+//! Herein is support for the state file, which persists a
+//! [checkpoint](Checkpoint) of an in-progress solve. If the terminal
+//! disconnects mid-solve, e.g., because an SSH session dropped, then the next
+//! launch of the TUI can offer to resume the interrupted solve.
+
+use std::{
+	fs::{self, File},
+	io::{self, BufReader, ErrorKind, Read, Write},
+	path::Path
+};
+
+use bincode::Options;
+use quartiles_solver::solver::Checkpoint;
+use tracing::trace;
+
+////////////////////////////////////////////////////////////////////////////////
+//                                State file.                                 //
+////////////////////////////////////////////////////////////////////////////////
+
+/// Write the given checkpoint to the state file, replacing its previous
+/// content. The checkpoint is serialized in [`bincode`](bincode) format. The
+/// checkpoint is written to a sibling file first and then renamed, so that a
+/// disconnect mid-write cannot corrupt the state file. Missing parent
+/// directories are created.
+///
+/// # Arguments
+///
+/// * `path` - The state file.
+/// * `checkpoint` - The checkpoint.
+///
+/// # Errors
+///
+/// * If the file cannot be opened or written, an error is returned.
+/// * If the checkpoint cannot be serialized, an [`ErrorKind::InvalidData`] is
+///   returned.
+pub fn save<T: AsRef<Path>>(
+	path: T,
+	checkpoint: &Checkpoint
+) -> Result<(), io::Error>
+{
+	let path = path.as_ref();
+	let content = bincode::serialize(checkpoint)
+		.map_err(|_e| ErrorKind::InvalidData)?;
+	if let Some(parent) = path.parent()
+	{
+		fs::create_dir_all(parent)?;
+	}
+	let temp_path = path.with_extension("tmp");
+	let mut file = File::create(&temp_path)?;
+	file.write_all(&content)?;
+	file.sync_all()?;
+	fs::rename(&temp_path, path)?;
+	trace!("Wrote state file: {}", path.display());
+	Ok(())
+}
+
+/// The largest state file that will be read, in bytes. A checkpoint holds
+/// only the fragments, two dozen or so fragment paths, and a few flags, so a
+/// larger file is surely corrupt.
+pub const MAX_STATE_BYTES: u64 = 1 << 20;
+
+/// Read a checkpoint from the state file, if any. Decoding is bounded by the
+/// size of the file, itself bounded by [`MAX_STATE_BYTES`], so that a corrupt
+/// or hostile file cannot provoke a huge allocation. Whether the checkpoint
+/// matches the dictionary is checked only when it is
+/// [resumed](quartiles_solver::solver::Solver::from_checkpoint).
+///
+/// # Arguments
+///
+/// * `path` - The state file.
+///
+/// # Returns
+///
+/// The checkpoint, or `None` if the state file does not exist.
+///
+/// # Errors
+///
+/// * If the file cannot be opened or read, an error is returned.
+/// * If the file contains invalid data, or exceeds [`MAX_STATE_BYTES`], an
+///   [`ErrorKind::InvalidData`] is returned.
+pub fn load<T: AsRef<Path>>(path: T) -> Result<Option<Checkpoint>, io::Error>
+{
+	let path = path.as_ref();
+	if !path.exists()
+	{
+		return Ok(None)
+	}
+	let file = File::open(path)?;
+	let mut reader = BufReader::new(file).take(MAX_STATE_BYTES + 1);
+	let mut content = Vec::new();
+	reader.read_to_end(&mut content)?;
+	if content.len() as u64 > MAX_STATE_BYTES
+	{
+		return Err(ErrorKind::InvalidData.into())
+	}
+	// Same encoding as `bincode::serialize`, but bounded by the content, so
+	// that a corrupt length cannot provoke a huge allocation.
+	let checkpoint: Checkpoint = bincode::options()
+		.with_fixint_encoding()
+		.allow_trailing_bytes()
+		.with_limit(content.len() as u64)
+		.deserialize(&content)
+		.map_err(|_e| ErrorKind::InvalidData)?;
+	if !checkpoint.is_valid()
+	{
+		return Err(ErrorKind::InvalidData.into())
+	}
+	trace!("Read state file: {}", path.display());
+	Ok(Some(checkpoint))
+}
+
+/// Remove the state file, if it exists.
+///
+/// # Arguments
+///
+/// * `path` - The state file.
+///
+/// # Errors
+///
+/// If the file exists but cannot be removed, an error is returned.
+pub fn clear<T: AsRef<Path>>(path: T) -> Result<(), io::Error>
+{
+	match fs::remove_file(path.as_ref())
+	{
+		Ok(()) =>
+		{
+			trace!("Removed state file: {}", path.as_ref().display());
+			Ok(())
+		},
+		Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+		Err(e) => Err(e)
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::{fs, rc::Rc};
+
+	use quartiles_solver::{
+		dictionary::Dictionary,
+		solver::{Fragment, Solver}
+	};
+	use tempfile::TempDir;
+
+	use crate::state::{clear, load, save, MAX_STATE_BYTES};
+
+	/// Ensure that a checkpoint survives a round trip through the state file,
+	/// and that missing and corrupt state files are handled.
+	#[test]
+	fn test_save_load_clear()
+	{
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("quartiles.state");
+		assert_eq!(load(&path).unwrap(), None);
+
+		let fragments = ["a", "b", "c", "d"]
+			.repeat(5)
+			.iter()
+			.map(|f| Fragment::from(*f))
+			.collect::<Vec<_>>();
+		let solver = Solver::new(
+			Rc::new(Dictionary::new()),
+			fragments.try_into().unwrap()
+		);
+		let checkpoint = solver.checkpoint();
+		save(&path, &checkpoint).unwrap();
+		assert_eq!(load(&path).unwrap(), Some(checkpoint));
+
+		clear(&path).unwrap();
+		assert_eq!(load(&path).unwrap(), None);
+		clear(&path).unwrap();
+
+		fs::write(&path, b"garbage").unwrap();
+		assert!(load(&path).is_err());
+
+		// A fragment whose encoded length is absurd is rejected without
+		// trying to allocate it.
+		let mut content = 0u64.to_le_bytes().to_vec();
+		content.extend(u64::MAX.to_le_bytes());
+		fs::write(&path, &content).unwrap();
+		assert!(load(&path).is_err());
+
+		fs::write(&path, vec![0; MAX_STATE_BYTES as usize + 1]).unwrap();
+		assert!(load(&path).is_err());
+	}
+}
@@ -0,0 +1,288 @@
+//! # Themes
+//!
+//! Herein are the color schemes of the text-based user interface (TUI). Every
+//! color choice made by the application is expressed as a semantic role of a
+//! [`Theme`], so that the complete scheme can be swapped at once.
+
+use clap::ValueEnum;
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use serde::{Deserialize, Serialize};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Themes.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// The names of the built-in themes.
+#[derive(
+	Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName
+{
+	/// Light text on a dark terminal. This is the original color scheme.
+	#[default]
+	Dark,
+
+	/// Dark text on a light terminal.
+	Light,
+
+	/// Maximal contrast, conveying state through modifiers as well as color.
+	HighContrast,
+
+	/// A palette that avoids the red/green distinction, based on the
+	/// [Okabe-Ito](https://jfly.uni-koeln.de/color/) colors.
+	Colorblind
+}
+
+/// A theme assigns a [`Style`] to every semantic role in the TUI.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[must_use]
+pub struct Theme
+{
+	/// The base style of text and lists.
+	pub text: Style,
+
+	/// The style of pane and cell borders.
+	pub border: Style,
+
+	/// The style of the border of an empty cell, which must be populated before
+	/// solving.
+	pub empty_border: Style,
+
+	/// The style of the border of a cell whose fragment occurs in no word of
+	/// the dictionary, which is probably a typo.
+	pub warning: Style,
+
+	/// The style of the suggested completions of the fragment being typed.
+	pub suggestion: Style,
+
+	/// The style of keybinding hints.
+	pub hint: Style,
+
+	/// The style of the primary action hint, e.g., solving.
+	pub action: Style,
+
+	/// The style of the exit hint.
+	pub exit: Style,
+
+	/// The style of the selected cell.
+	pub cursor: Style,
+
+	/// The style of a cell that participates in the highlighted fragment path.
+	pub path: Style,
+
+	/// The style of the border of a cell that participates in the highlighted
+	/// fragment path.
+	pub path_border: Style,
+
+	/// The style of a quartile in the solution list.
+	pub quartile: Style,
+
+	/// The style of any other word in the solution list.
+	pub word: Style,
+
+	/// The style of the most recently discovered word in the solution list.
+	pub discovery: Style,
+
+	/// The style of the selected word in the solution list.
+	pub selection: Style,
+
+	/// The style of the indication that the puzzle was solved.
+	pub success: Style,
+
+	/// The style of the indication that the puzzle was not solved.
+	pub failure: Style,
+
+	/// The style of the status bar.
+	pub status: Style
+}
+
+impl Theme
+{
+	/// Construct the built-in theme with the given name.
+	///
+	/// # Arguments
+	///
+	/// * `name` - The name of the theme.
+	///
+	/// # Returns
+	///
+	/// The requested theme.
+	pub fn new(name: ThemeName) -> Self
+	{
+		match name
+		{
+			ThemeName::Dark => Self::dark(),
+			ThemeName::Light => Self::light(),
+			ThemeName::HighContrast => Self::high_contrast(),
+			ThemeName::Colorblind => Self::colorblind()
+		}
+	}
+
+	/// Construct the [dark](ThemeName::Dark) theme.
+	///
+	/// # Returns
+	///
+	/// The dark theme.
+	pub fn dark() -> Self
+	{
+		Self {
+			text: Style::default().fg(Color::White),
+			border: Style::default().fg(Color::White),
+			empty_border: Style::default().fg(Color::Red),
+			warning: Style::default().fg(Color::Yellow),
+			suggestion: Style::default().fg(Color::DarkGray),
+			hint: Style::default().fg(Color::Cyan),
+			action: Style::default().fg(Color::Green).bold(),
+			exit: Style::default().fg(Color::Yellow).bold(),
+			cursor: Style::default().fg(Color::Black).bg(Color::Cyan),
+			path: Style::default().fg(Color::Black).bg(Color::Green),
+			path_border: Style::default().fg(Color::Black),
+			quartile: Style::default().fg(Color::Green),
+			word: Style::default().fg(Color::White),
+			discovery: Style::default().fg(Color::Black).bg(Color::Green),
+			selection: Style::default().fg(Color::Black).bg(Color::Cyan),
+			success: Style::default().fg(Color::Green).bold(),
+			failure: Style::default().fg(Color::Red).bold(),
+			status: Style::default().fg(Color::White).bg(Color::DarkGray)
+		}
+	}
+
+	/// Construct the [light](ThemeName::Light) theme.
+	///
+	/// # Returns
+	///
+	/// The light theme.
+	pub fn light() -> Self
+	{
+		Self {
+			text: Style::default().fg(Color::Black),
+			border: Style::default().fg(Color::DarkGray),
+			empty_border: Style::default().fg(Color::Red),
+			warning: Style::default().fg(Color::Yellow),
+			suggestion: Style::default().fg(Color::Gray),
+			hint: Style::default().fg(Color::Blue),
+			action: Style::default().fg(Color::Green).bold(),
+			exit: Style::default().fg(Color::Magenta).bold(),
+			cursor: Style::default().fg(Color::White).bg(Color::Blue),
+			path: Style::default().fg(Color::White).bg(Color::Green),
+			path_border: Style::default().fg(Color::Black),
+			quartile: Style::default().fg(Color::Green).bold(),
+			word: Style::default().fg(Color::Black),
+			discovery: Style::default().fg(Color::White).bg(Color::Green),
+			selection: Style::default().fg(Color::White).bg(Color::Blue),
+			success: Style::default().fg(Color::Green).bold(),
+			failure: Style::default().fg(Color::Red).bold(),
+			status: Style::default().fg(Color::Black).bg(Color::Gray)
+		}
+	}
+
+	/// Construct the [high-contrast](ThemeName::HighContrast) theme.
+	///
+	/// # Returns
+	///
+	/// The high-contrast theme.
+	pub fn high_contrast() -> Self
+	{
+		Self {
+			text: Style::default().fg(Color::White),
+			border: Style::default().fg(Color::White),
+			empty_border: Style::default()
+				.fg(Color::Yellow)
+				.add_modifier(Modifier::BOLD | Modifier::SLOW_BLINK),
+			warning: Style::default()
+				.fg(Color::Yellow)
+				.add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+			suggestion: Style::default().fg(Color::White).italic(),
+			hint: Style::default().fg(Color::White),
+			action: Style::default().fg(Color::Yellow).bold(),
+			exit: Style::default().fg(Color::Yellow).bold(),
+			cursor: Style::default().fg(Color::Black).bg(Color::White).bold(),
+			path: Style::default().fg(Color::Black).bg(Color::Yellow).bold(),
+			path_border: Style::default().fg(Color::Yellow),
+			quartile: Style::default()
+				.fg(Color::Yellow)
+				.add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+			word: Style::default().fg(Color::White),
+			discovery: Style::default().fg(Color::Black).bg(Color::Yellow),
+			selection: Style::default().fg(Color::Black).bg(Color::White),
+			success: Style::default().fg(Color::White).bold(),
+			failure: Style::default().fg(Color::White).bold().reversed(),
+			status: Style::default().fg(Color::Black).bg(Color::White)
+		}
+	}
+
+	/// Construct the [colorblind](ThemeName::Colorblind) theme. Quartiles
+	/// and successes are blue, while empty cells and failures are vermilion,
+	/// so that no state is conveyed solely by the red/green distinction.
+	///
+	/// # Returns
+	///
+	/// The colorblind theme.
+	pub fn colorblind() -> Self
+	{
+		const BLUE: Color = Color::Rgb(0, 114, 178);
+		const SKY_BLUE: Color = Color::Rgb(86, 180, 233);
+		const ORANGE: Color = Color::Rgb(230, 159, 0);
+		const VERMILION: Color = Color::Rgb(213, 94, 0);
+		const YELLOW: Color = Color::Rgb(240, 228, 66);
+		Self {
+			text: Style::default().fg(Color::White),
+			border: Style::default().fg(Color::White),
+			empty_border: Style::default().fg(VERMILION),
+			warning: Style::default().fg(YELLOW),
+			suggestion: Style::default().fg(Color::DarkGray),
+			hint: Style::default().fg(SKY_BLUE),
+			action: Style::default().fg(SKY_BLUE).bold(),
+			exit: Style::default().fg(ORANGE).bold(),
+			cursor: Style::default().fg(Color::Black).bg(SKY_BLUE),
+			path: Style::default().fg(Color::Black).bg(ORANGE),
+			path_border: Style::default().fg(Color::Black),
+			quartile: Style::default().fg(SKY_BLUE).bold(),
+			word: Style::default().fg(Color::White),
+			discovery: Style::default().fg(Color::Black).bg(ORANGE),
+			selection: Style::default().fg(Color::White).bg(BLUE),
+			success: Style::default().fg(SKY_BLUE).bold(),
+			failure: Style::default().fg(VERMILION).bold(),
+			status: Style::default().fg(Color::White).bg(BLUE)
+		}
+	}
+}
+
+impl Default for Theme
+{
+	fn default() -> Self
+	{
+		Self::new(ThemeName::default())
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use clap::ValueEnum;
+
+	use crate::theme::{Theme, ThemeName};
+
+	/// Ensure that every theme distinguishes the states that the TUI must
+	/// convey.
+	#[test]
+	fn test_distinct_roles()
+	{
+		for name in ThemeName::value_variants()
+		{
+			let theme = Theme::new(*name);
+			assert_ne!(theme.quartile, theme.word, "{:?}", name);
+			assert_ne!(theme.empty_border, theme.border, "{:?}", name);
+			assert_ne!(theme.warning, theme.border, "{:?}", name);
+			assert_ne!(theme.warning, theme.empty_border, "{:?}", name);
+			assert_ne!(theme.success, theme.failure, "{:?}", name);
+			assert_ne!(theme.cursor, theme.text, "{:?}", name);
+			assert_ne!(theme.path, theme.text, "{:?}", name);
+		}
+	}
+}
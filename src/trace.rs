@@ -0,0 +1,350 @@
+//! # Solver traces
+//!
+//! Herein is support for recording the decisions of the
+//! [solver](crate::solver::Solver) in a ring buffer, so that a developer can
+//! step through them after the fact, e.g., to learn why the solver didn't find
+//! some word. The solver records only when
+//! [tracing](crate::solver::Solver::with_trace) is enabled, and only in builds
+//! with the `trace-ui` feature.
+
+use std::{
+	collections::VecDeque,
+	fmt::{self, Display, Formatter}
+};
+
+use crate::solver::{FragmentPath, Word};
+
+////////////////////////////////////////////////////////////////////////////////
+//                                  Traces.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A ring buffer of the most recent decisions of the solver. Once the buffer
+/// is full, recording a decision discards the oldest one.
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct Trace
+{
+	/// The decisions, oldest first.
+	decisions: VecDeque<Decision>,
+
+	/// The maximum number of decisions to retain.
+	capacity: usize,
+
+	/// The number of decisions discarded to make room for newer ones.
+	dropped: usize
+}
+
+impl Trace
+{
+	/// Construct an empty trace.
+	///
+	/// # Arguments
+	///
+	/// * `capacity` - The maximum number of decisions to retain.
+	///
+	/// # Returns
+	///
+	/// The new trace.
+	pub fn new(capacity: usize) -> Self
+	{
+		Self {
+			decisions: VecDeque::with_capacity(capacity),
+			capacity,
+			dropped: 0
+		}
+	}
+
+	/// Record the specified decision, discarding the oldest decision if the
+	/// trace is full.
+	///
+	/// # Arguments
+	///
+	/// * `decision` - The decision.
+	pub fn record(&mut self, decision: Decision)
+	{
+		if self.capacity == 0
+		{
+			self.dropped += 1;
+			return
+		}
+		if self.decisions.len() == self.capacity
+		{
+			self.decisions.pop_front();
+			self.dropped += 1;
+		}
+		self.decisions.push_back(decision);
+	}
+
+	/// Answer the number of decisions retained.
+	///
+	/// # Returns
+	///
+	/// The number of decisions.
+	#[inline]
+	#[must_use]
+	pub fn len(&self) -> usize
+	{
+		self.decisions.len()
+	}
+
+	/// Check whether the trace is empty.
+	///
+	/// # Returns
+	///
+	/// `true` if no decisions are retained, `false` otherwise.
+	#[inline]
+	#[must_use]
+	pub fn is_empty(&self) -> bool
+	{
+		self.decisions.is_empty()
+	}
+
+	/// Answer the number of decisions discarded to make room for newer ones.
+	///
+	/// # Returns
+	///
+	/// The number of decisions discarded.
+	#[inline]
+	#[must_use]
+	pub fn dropped(&self) -> usize
+	{
+		self.dropped
+	}
+
+	/// Answer an iterator over the decisions retained.
+	///
+	/// # Returns
+	///
+	/// The iterator, oldest decision first.
+	#[inline]
+	pub fn iter(&self) -> impl Iterator<Item = &Decision>
+	{
+		self.decisions.iter()
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                 Decisions.                                 //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A decision of the solver about a candidate word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decision
+{
+	/// The candidate word entered the solution.
+	Accepted
+	{
+		/// The fragment path of the candidate word.
+		path: FragmentPath,
+
+		/// The candidate word.
+		word: Word
+	},
+
+	/// The candidate word is in the dictionary, but was kept out of the
+	/// solution.
+	Rejected
+	{
+		/// The fragment path of the candidate word.
+		path: FragmentPath,
+
+		/// The candidate word.
+		word: Word,
+
+		/// Why the candidate word was kept out of the solution.
+		reason: Rejection
+	},
+
+	/// No word of the dictionary starts with the candidate word, so the
+	/// search abandoned every extension of its fragment path.
+	Pruned
+	{
+		/// The fragment path of the candidate word.
+		path: FragmentPath,
+
+		/// The candidate word.
+		word: Word
+	},
+
+	/// Every extension of a fragment path was exhausted, so the search
+	/// backtracked to the specified fragment path.
+	Backtracked
+	{
+		/// The fragment path to which the search backtracked.
+		path: FragmentPath,
+
+		/// The candidate word of the fragment path.
+		word: Word
+	},
+
+	/// The search space was exhausted, so the solver finished.
+	Exhausted
+}
+
+impl Decision
+{
+	/// Answer the fragment path of the decision, if any.
+	///
+	/// # Returns
+	///
+	/// The fragment path, or `None` if the decision concerns the whole search.
+	#[must_use]
+	pub fn path(&self) -> Option<&FragmentPath>
+	{
+		match self
+		{
+			Decision::Accepted { path, .. }
+			| Decision::Rejected { path, .. }
+			| Decision::Pruned { path, .. }
+			| Decision::Backtracked { path, .. } => Some(path),
+			Decision::Exhausted => None
+		}
+	}
+
+	/// Answer the candidate word of the decision, if any.
+	///
+	/// # Returns
+	///
+	/// The candidate word, or `None` if the decision concerns the whole
+	/// search.
+	#[must_use]
+	pub fn word(&self) -> Option<&str>
+	{
+		match self
+		{
+			Decision::Accepted { word, .. }
+			| Decision::Rejected { word, .. }
+			| Decision::Pruned { word, .. }
+			| Decision::Backtracked { word, .. } => Some(word.as_str()),
+			Decision::Exhausted => None
+		}
+	}
+}
+
+impl Display for Decision
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		match self
+		{
+			Decision::Accepted { word, .. } => write!(f, "accept {}", word),
+			Decision::Rejected { word, reason, .. } =>
+			{
+				write!(f, "reject {} ({})", word, reason)
+			},
+			Decision::Pruned { word, .. } => write!(f, "prune {}", word),
+			Decision::Backtracked { word, .. } =>
+			{
+				write!(f, "backtrack to {}", word)
+			},
+			Decision::Exhausted => write!(f, "exhausted")
+		}
+	}
+}
+
+/// Why a dictionary word was kept out of the solution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rejection
+{
+	/// The word is too short or too long for the
+	/// [configuration](crate::solver::SolverConfig).
+	Length,
+
+	/// The word isn't a quartile, but only quartiles were wanted.
+	QuartilesOnly,
+
+	/// The [word filter](crate::solver::WordFilter) vetoed the word.
+	Filtered,
+
+	/// The word is already in the solution.
+	Duplicate
+}
+
+impl Display for Rejection
+{
+	fn fmt(&self, f: &mut Formatter) -> fmt::Result
+	{
+		match self
+		{
+			Rejection::Length => write!(f, "length"),
+			Rejection::QuartilesOnly => write!(f, "quartiles only"),
+			Rejection::Filtered => write!(f, "filtered"),
+			Rejection::Duplicate => write!(f, "duplicate")
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::rc::Rc;
+
+	use crate::{
+		dictionary::Dictionary,
+		solver::{Fragment, Solver, SolverConfig},
+		trace::{Decision, Rejection, Trace}
+	};
+
+	/// Ensure that a full trace discards its oldest decisions.
+	#[test]
+	fn test_ring_buffer()
+	{
+		let mut trace = Trace::new(2);
+		assert!(trace.is_empty());
+		for _ in 0..3
+		{
+			trace.record(Decision::Exhausted);
+		}
+		assert_eq!(trace.len(), 2);
+		assert_eq!(trace.dropped(), 1);
+	}
+
+	/// Ensure that the solver records its decisions, including why it
+	/// rejected a dictionary word.
+	#[test]
+	fn test_solver_trace()
+	{
+		let dictionary = ["cross", "crossword", "word"]
+			.into_iter()
+			.collect::<Dictionary>();
+		let mut fragments = [Fragment::from("x"); 20];
+		for (index, fragment) in ["cr", "oss", "wo", "rd"]
+			.into_iter()
+			.enumerate()
+		{
+			fragments[index] = Fragment::from(fragment);
+		}
+		let config = SolverConfig {
+			min_len: 5,
+			..Default::default()
+		};
+		let solver = Solver::new(Rc::new(dictionary), fragments)
+			.with_config(config)
+			.with_trace(1024)
+			.solve_fully();
+		let trace = solver.trace().unwrap();
+		let decisions = trace.iter().collect::<Vec<_>>();
+		let find = |word: &str| {
+			decisions.iter()
+				.filter(|decision| decision.word() == Some(word))
+				.map(|decision| decision.to_string())
+				.collect::<Vec<_>>()
+		};
+		assert!(find("cross").contains(&"accept cross".to_string()));
+		assert!(find("crossword").contains(&"accept crossword".to_string()));
+		assert!(find("word").contains(&"reject word (length)".to_string()));
+		assert!(find("x").contains(&"prune x".to_string()));
+		assert!(decisions.iter().any(|d| matches!(
+			d,
+			Decision::Rejected { reason: Rejection::Length, .. }
+		)));
+		assert!(decisions.iter().any(|d| matches!(d, Decision::Backtracked {
+			..
+		})));
+		assert_eq!(decisions.last(), Some(&&Decision::Exhausted));
+	}
+}
@@ -5,87 +5,295 @@
 //! Ratatui library, as every application that uses Ratatui will need to
 //! initialize and restore the terminal in the same way. But currently it
 //! remains a responsibility of the application to do so.
+//!
+//! The TUI draws on standard error, leaving standard output to the solution,
+//! so that the solution can be piped to another program.
 
-use std::{io::{self, stdout, Stdout}, panic};
+use std::{
+	backtrace::Backtrace,
+	fmt::Display,
+	fs,
+	io::{self, stderr, Stderr},
+	panic::{self, PanicHookInfo},
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex},
+	time::{SystemTime, UNIX_EPOCH}
+};
 
 use crossterm::{
+	event::{
+		DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste,
+		EnableMouseCapture
+	},
 	execute,
 	terminal::{
 		disable_raw_mode, enable_raw_mode,
 		EnterAlternateScreen, LeaveAlternateScreen
 	}
 };
-use quartiles_solver::dictionary::Dictionary;
 use ratatui::{backend::{Backend, CrosstermBackend}, Terminal};
 
-use crate::app::App;
+use crate::{app::{App, Outcome}, daily::Date, paths};
 
 ////////////////////////////////////////////////////////////////////////////////
 //                         Text-based user interface.                         //
 ////////////////////////////////////////////////////////////////////////////////
 
-/// The text-based user interface (TUI) type.
-pub type Tui = Terminal<CrosstermBackend<Stdout>>;
+/// The text-based user interface (TUI) type, drawing through the specified
+/// [backend](Backend). The terminal, via standard error, is the default.
+pub type Tui<B = CrosstermBackend<Stderr>> = Terminal<B>;
+
+/// A [backend](Backend) that can take over a console for the duration of the
+/// application, e.g., by switching to the alternate screen, and give it back
+/// afterward. Alternative frontends plug in by implementing this trait.
+pub trait Console: Backend
+{
+	/// Take over the console.
+	///
+	/// # Errors
+	///
+	/// Any error that occurs while preparing the console.
+	fn enter(&mut self) -> io::Result<()>;
+
+	/// Give the console back, restoring it to its original state. This runs
+	/// from the panic hook, after the backend itself may have been lost, so it
+	/// receives no backend.
+	///
+	/// # Errors
+	///
+	/// Any error that occurs while restoring the console.
+	fn leave() -> io::Result<()>;
+}
+
+impl Console for CrosstermBackend<Stderr>
+{
+	fn enter(&mut self) -> io::Result<()>
+	{
+		// Bracketed paste delivers pasted text all at once, rather than as
+		// individual keys, so that it can be assigned to several cells. Mouse
+		// capture allows cells to be dragged around the board.
+		execute!(
+			self,
+			EnterAlternateScreen,
+			EnableBracketedPaste,
+			EnableMouseCapture
+		)?;
+		enable_raw_mode()
+	}
+
+	fn leave() -> io::Result<()>
+	{
+		let mut stderr = stderr();
+		execute!(
+			stderr,
+			DisableMouseCapture,
+			DisableBracketedPaste,
+			LeaveAlternateScreen
+		)?;
+		disable_raw_mode()?;
+		// Take care to restore the cursor.
+		CrosstermBackend::new(stderr).show_cursor()
+	}
+}
 
 /// Open the text-based user interface (TUI) for inputting and solving a
-/// Quartiles puzzle. Arrange for the terminal to be restored to its original
-/// state in case of panic.
+/// Quartiles puzzle on the specified console, and run the specified
+/// application until it exits. Arrange for the console to be restored to its
+/// original state in case of panic, and for a
+/// [crash report](write_crash_report) to be written to the
+/// [crash directory](paths::crash_directory).
+///
+/// # Arguments
+///
+/// * `app` - The application to run.
+/// * `console` - The console, e.g., a [`CrosstermBackend`] over standard
+///   error.
+///
+/// # Returns
+///
+/// The [outcome](Outcome) of the solve.
+///
+/// # Errors
+///
+/// Any error that occurs while driving the TUI.
+pub fn tui<C: Console + 'static>(app: App, console: C)
+	-> io::Result<Outcome>
+{
+	with_console(console, |tui| app.run(tui))
+}
+
+/// Take over the specified console, run the specified function against a TUI
+/// that draws on it, and then give the console back. Arrange for the console
+/// to be restored to its original state in case of panic, and for a
+/// [crash report](write_crash_report) to be written to the
+/// [crash directory](paths::crash_directory).
 ///
 /// # Arguments
 ///
-/// * `highlight_duration_µs` - How long (in µs) to highlight an individual
-///   word in the TUI.
-/// * `dictionary` - The dictionary to use for solving the puzzle.
+/// * `console` - The console, e.g., a [`CrosstermBackend`] over standard
+///   error.
+/// * `run` - The function, which drives the TUI until it is done.
 ///
 /// # Returns
 ///
-/// The solution to the puzzle, as a word list.
+/// The result of the function.
 ///
 /// # Errors
 ///
 /// Any error that occurs while driving the TUI.
-pub fn tui(highlight_duration_µs: u64, dictionary: Dictionary) -> io::Result<Vec<String>>
+pub fn with_console<C: Console + 'static, T>(
+	mut console: C,
+	run: impl FnOnce(&mut Tui<C>) -> io::Result<T>
+) -> io::Result<T>
 {
 	// Capture the original panic hook and replace it with one that restores
-	// the terminal before panicking.
-	let original_hook = panic::take_hook();
-	let mut tui = tui_init()?;
+	// the console and writes a crash report before panicking.
+	let original_hook: Arc<dyn Fn(&PanicHookInfo<'_>) + Send + Sync> =
+		Arc::from(panic::take_hook());
+	console.enter()?;
+	let mut tui = Terminal::new(console)?;
+	let hook = Arc::clone(&original_hook);
 	panic::set_hook(Box::new(move |info| {
-		let _ = tui_restore();
-		original_hook(info);
+		let _ = C::leave();
+		let context = match CRASH_CONTEXT.try_lock()
+		{
+			Ok(context) => context.clone(),
+			Err(_) => "Unavailable".to_string()
+		};
+		let report = crash_report(info, &context, &Backtrace::force_capture());
+		match write_crash_report(&paths::crash_directory(), &report)
+		{
+			Ok(path) => eprintln!("Crash report written to {}", path.display()),
+			Err(e) => eprintln!("Failed to write crash report: {}", e)
+		}
+		hook(info);
 	}));
-	let result = App::new(highlight_duration_µs, dictionary).run(&mut tui);
-	tui_restore()?;
+	let result = run(&mut tui);
+	// Reinstate the original panic hook, so that a later session doesn't
+	// restore the console or report a crash twice.
+	panic::set_hook(Box::new(move |info| original_hook(info)));
+	C::leave()?;
 	result
 }
 
-/// Initialize the text-based user interface (TUI).
+////////////////////////////////////////////////////////////////////////////////
+//                               Crash reports.                               //
+////////////////////////////////////////////////////////////////////////////////
+
+/// A summary of the application state, as of the most recent frame, for
+/// inclusion in a crash report. The panic hook cannot reach the application
+/// itself, so the application [publishes](set_crash_context) the summary here.
+static CRASH_CONTEXT: Mutex<String> = Mutex::new(String::new());
+
+/// Publish a summary of the application state, for inclusion in any
+/// subsequent crash report.
 ///
-/// # Returns
+/// # Arguments
+///
+/// * `context` - The summary.
+pub fn set_crash_context(context: String)
+{
+	if let Ok(mut current) = CRASH_CONTEXT.lock()
+	{
+		*current = context;
+	}
+}
+
+/// Compose a crash report, comprising the panic, the application state, and
+/// the backtrace.
 ///
-/// The initialized TUI.
+/// # Arguments
 ///
-/// # Errors
+/// * `panic` - The panic, including its message and location.
+/// * `context` - A summary of the application state.
+/// * `backtrace` - The backtrace of the panicking thread.
 ///
-/// Any error that occurs while initializing the TUI.
-fn tui_init() -> io::Result<Tui>
+/// # Returns
+///
+/// The crash report.
+fn crash_report(
+	panic: &dyn Display,
+	context: &str,
+	backtrace: &Backtrace
+) -> String
 {
-	let mut stdout = stdout();
-	execute!(stdout, EnterAlternateScreen)?;
-	enable_raw_mode()?;
-	Terminal::new(CrosstermBackend::new(stdout))
+	format!(
+		"{} {} crashed\n\n{}\n\nApplication state:\n{}\n\nBacktrace:\n{}\n",
+		env!("CARGO_PKG_NAME"),
+		env!("CARGO_PKG_VERSION"),
+		panic,
+		context,
+		backtrace
+	)
 }
 
-/// Restore the terminal to its original state.
+/// Write the given crash report to a new file in the specified directory,
+/// which is created if necessary, named for the current time, e.g.,
+/// `quartiles-crash-2024-06-01-123456.log`.
+///
+/// # Arguments
+///
+/// * `dir` - The directory.
+/// * `report` - The crash report.
+///
+/// # Returns
+///
+/// The path to the crash file.
 ///
 /// # Errors
 ///
-/// Any error that occurs while restoring the terminal.
-fn tui_restore() -> io::Result<()>
+/// If the crash file cannot be written, an error is returned.
+fn write_crash_report(dir: &Path, report: &str) -> io::Result<PathBuf>
 {
-	let mut stdout = stdout();
-	execute!(stdout, LeaveAlternateScreen)?;
-	disable_raw_mode()?;
-	// Take care to restore the cursor.
-	CrosstermBackend::new(stdout).show_cursor()
+	let seconds = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_secs())
+		.unwrap_or_default();
+	let date = Date::from_days((seconds / 86_400) as i64);
+	let time = seconds % 86_400;
+	let path = dir.join(format!(
+		"quartiles-crash-{}-{:02}{:02}{:02}.log",
+		date,
+		time / 3600,
+		time / 60 % 60,
+		time % 60
+	));
+	fs::create_dir_all(dir)?;
+	fs::write(&path, report)?;
+	Ok(path)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//                                   Tests.                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+#[cfg(test)]
+mod test
+{
+	use std::{backtrace::Backtrace, fs};
+
+	use crate::tui::{crash_report, write_crash_report};
+
+	/// Ensure that a crash report holds the panic, the application state, and
+	/// the backtrace, and that it is written to a timestamped file in a
+	/// directory that needn't exist yet.
+	#[test]
+	fn test_crash_report()
+	{
+		let dir = tempfile::TempDir::new().unwrap();
+		let report = crash_report(
+			&"panicked at src/app.rs:1:1:\noops",
+			"Screen: solve",
+			&Backtrace::disabled()
+		);
+		assert!(report.contains("oops"), "{}", report);
+		assert!(report.contains("Application state:\nScreen: solve"));
+		assert!(report.contains("Backtrace:"));
+		let path = write_crash_report(&dir.path().join("state"), &report)
+			.unwrap();
+		let name = path.file_name().unwrap().to_str().unwrap();
+		assert!(name.starts_with("quartiles-crash-"), "{}", name);
+		assert!(name.ends_with(".log"), "{}", name);
+		assert_eq!(fs::read_to_string(&path).unwrap(), report);
+	}
 }